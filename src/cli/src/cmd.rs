@@ -33,14 +33,32 @@ pub use df::DFCmd;
 pub mod diff;
 pub use diff::DiffCmd;
 
+pub mod export;
+pub use export::ExportCmd;
+
+pub mod materialize;
+pub use materialize::MaterializeCmd;
+
 pub mod moo;
 pub use moo::MooCmd;
 
+pub mod mv;
+pub use mv::MvCmd;
+
+pub mod reflog;
+pub use reflog::ReflogCmd;
+
 pub mod remote;
 
 pub mod schemas;
 pub use schemas::SchemasCmd;
 
+pub mod stash;
+pub use stash::StashCmd;
+
+pub mod verify;
+pub use verify::VerifyCmd;
+
 #[async_trait]
 pub trait RunCmd {
     fn name(&self) -> &str;
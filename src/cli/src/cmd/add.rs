@@ -24,6 +24,18 @@ pub fn add_args() -> Command {
                 .required(true)
                 .action(clap::ArgAction::Append),
         )
+        .arg(
+            Arg::new("exclude")
+                .long("exclude")
+                .help("Glob of paths to skip when adding a directory, can be repeated. Applied on top of .oxenignore.")
+                .action(clap::ArgAction::Append),
+        )
+}
+
+pub fn parse_excludes(args: &clap::ArgMatches) -> Vec<String> {
+    args.get_many::<String>("exclude")
+        .map(|excludes| excludes.cloned().collect())
+        .unwrap_or_default()
 }
 
 #[async_trait]
@@ -49,6 +61,9 @@ impl RunCmd for AddCmd {
             paths,
             is_remote: false,
             directory: None,
+            excludes: parse_excludes(args),
+            chunked: false,
+            force: false,
         };
 
         // Recursively look up from the current dir for .oxen directory
@@ -59,7 +74,7 @@ impl RunCmd for AddCmd {
             if opts.is_remote {
                 command::remote::add(&repository, path, &opts).await?;
             } else {
-                command::add(&repository, path)?;
+                command::add_with_opts(&repository, path, &opts)?;
             }
         }
 
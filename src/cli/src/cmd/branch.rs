@@ -4,7 +4,7 @@ use colored::Colorize;
 
 use liboxen::api;
 use liboxen::error::OxenError;
-use liboxen::model::LocalRepository;
+use liboxen::model::{Branch, LocalRepository};
 
 use crate::cmd::RunCmd;
 use crate::helpers::{check_remote_version, check_remote_version_blocking, get_host_from_repo};
@@ -63,6 +63,24 @@ impl RunCmd for BranchCmd {
                     .help("Rename the current local branch.")
                     .action(clap::ArgAction::Set),
             )
+            .arg(
+                Arg::new("copy")
+                    .long("copy")
+                    .short('c')
+                    .number_of_values(2)
+                    .value_names(["SRC", "DST"])
+                    .help("Copy a branch to a new branch without checking it out. Pass --remote to copy a remote branch.")
+                    .action(clap::ArgAction::Set),
+            )
+            .arg(
+                Arg::new("sort")
+                    .long("sort")
+                    .help("Sort the local branches by the date of their last commit, prefix with - for newest first")
+                    .value_parser(["committerdate", "-committerdate"])
+                    .allow_hyphen_values(true)
+                    .conflicts_with_all(["remote", "copy", "delete", "force-delete", "move"])
+                    .action(clap::ArgAction::Set),
+            )
             .arg(
                 Arg::new("show-current")
                     .long("show-current")
@@ -85,7 +103,12 @@ impl RunCmd for BranchCmd {
         } else if args.get_flag("all") {
             self.list_all_branches(&repo).await
         } else if let Some(remote_name) = args.get_one::<String>("remote") {
-            if let Some(branch_name) = args.get_one::<String>("delete") {
+            if let Some(copy) = args.get_many::<String>("copy") {
+                let [src, dst] = copy.collect::<Vec<_>>()[..] else {
+                    return Err(OxenError::basic_str("Usage: oxen branch -c <src> <dst>"));
+                };
+                self.copy_remote_branch(&repo, remote_name, src, dst).await
+            } else if let Some(branch_name) = args.get_one::<String>("delete") {
                 self.delete_remote_branch(&repo, remote_name, branch_name)
                     .await
             } else {
@@ -93,6 +116,11 @@ impl RunCmd for BranchCmd {
             }
         } else if let Some(name) = args.get_one::<String>("name") {
             self.create_branch(&repo, name)
+        } else if let Some(copy) = args.get_many::<String>("copy") {
+            let [src, dst] = copy.collect::<Vec<_>>()[..] else {
+                return Err(OxenError::basic_str("Usage: oxen branch -c <src> <dst>"));
+            };
+            self.copy_branch(&repo, src, dst)
         } else if let Some(name) = args.get_one::<String>("delete") {
             self.delete_branch(&repo, name)
         } else if let Some(name) = args.get_one::<String>("force-delete") {
@@ -101,6 +129,11 @@ impl RunCmd for BranchCmd {
             self.rename_current_branch(&repo, name)
         } else if args.get_flag("show-current") {
             self.show_current_branch(&repo)
+        } else if let Some(sort) = args.get_one::<String>("sort") {
+            let branches =
+                api::local::branches::list_by_last_commit_time(&repo, sort.starts_with('-'))?;
+            self.print_branches(&branches);
+            Ok(())
         } else {
             self.list_branches(&repo)
         }
@@ -120,7 +153,12 @@ impl BranchCmd {
 
     pub fn list_branches(&self, repo: &LocalRepository) -> Result<(), OxenError> {
         let branches = api::local::branches::list(repo)?;
+        self.print_branches(&branches);
+
+        Ok(())
+    }
 
+    fn print_branches(&self, branches: &[Branch]) {
         for branch in branches.iter() {
             if branch.is_head {
                 let branch_str = format!("* {}", branch.name).green();
@@ -129,8 +167,6 @@ impl BranchCmd {
                 println!("  {}", branch.name)
             }
         }
-
-        Ok(())
     }
 
     pub fn show_current_branch(&self, repo: &LocalRepository) -> Result<(), OxenError> {
@@ -145,6 +181,45 @@ impl BranchCmd {
         Ok(())
     }
 
+    pub fn copy_branch(
+        &self,
+        repo: &LocalRepository,
+        src: &str,
+        dst: &str,
+    ) -> Result<(), OxenError> {
+        let branch = api::local::branches::copy(repo, src, dst)?;
+        println!(
+            "Copied branch {src} -> {} ({})",
+            branch.name, branch.commit_id
+        );
+        Ok(())
+    }
+
+    pub async fn copy_remote_branch(
+        &self,
+        repo: &LocalRepository,
+        remote_name: &str,
+        src: &str,
+        dst: &str,
+    ) -> Result<(), OxenError> {
+        let host = get_host_from_repo(repo)?;
+        check_remote_version(host).await?;
+
+        let remote = repo
+            .get_remote(remote_name)
+            .ok_or(OxenError::remote_not_set(remote_name))?;
+        let remote_repo = api::remote::repositories::get_by_remote(&remote)
+            .await?
+            .ok_or(OxenError::remote_not_found(remote.clone()))?;
+
+        let branch = api::remote::branches::copy(&remote_repo, src, dst).await?;
+        println!(
+            "Copied branch {remote_name}/{src} -> {remote_name}/{} ({})",
+            branch.name, branch.commit_id
+        );
+        Ok(())
+    }
+
     pub fn delete_branch(&self, repo: &LocalRepository, name: &str) -> Result<(), OxenError> {
         api::local::branches::delete(repo, name)?;
         Ok(())
@@ -1,6 +1,5 @@
 use async_trait::async_trait;
 use clap::{Arg, Command};
-use liboxen::api;
 use liboxen::command;
 use liboxen::error::OxenError;
 use liboxen::model::LocalRepository;
@@ -28,27 +27,44 @@ impl RunCmd for CheckoutCmd {
                     .exclusive(true)
                     .action(clap::ArgAction::Set),
             )
+            .arg(
+                Arg::new("track")
+                    .long("track")
+                    .short('t')
+                    .help("Create a local branch from a remote branch, ie origin/feature, track it and check it out")
+                    .exclusive(true)
+                    .action(clap::ArgAction::Set),
+            )
             .arg(
                 Arg::new("ours")
                     .long("ours")
-                    .help("Checkout the content of the base branch and take it as the working directories version. Will overwrite your working file.")
+                    .help("Checkout the content of the base branch and take it as the working directories version. Will overwrite your working file and stage it.")
                     .action(clap::ArgAction::SetTrue),
             )
             .arg(
                 Arg::new("theirs")
                     .long("theirs")
-                    .help("Checkout the content of the merge branch and take it as the working directories version. Will overwrite your working file.")
+                    .help("Checkout the content of the merge branch and take it as the working directories version. Will overwrite your working file and stage it.")
                     .action(clap::ArgAction::SetTrue),
             )
+            .arg(
+                Arg::new("paths")
+                    .help("Paths to restore from the commit and stage, without moving HEAD. Ie: oxen checkout HEAD~2 -- data/train.csv")
+                    .last(true)
+                    .num_args(1..)
+                    .action(clap::ArgAction::Append),
+            )
     }
 
     async fn run(&self, args: &clap::ArgMatches) -> Result<(), OxenError> {
         // Find the repository
-        let repo = LocalRepository::from_current_dir()?;
+        let mut repo = LocalRepository::from_current_dir()?;
 
         // Parse Args
         if let Some(name) = args.get_one::<String>("create") {
             self.create_checkout_branch(&repo, name)?
+        } else if let Some(remote_branch) = args.get_one::<String>("track") {
+            self.checkout_track(&mut repo, remote_branch).await?
         } else if args.get_flag("ours") {
             let Some(name) = args.get_one::<String>("name") else {
                 return Err(OxenError::basic_str(
@@ -65,6 +81,16 @@ impl RunCmd for CheckoutCmd {
             };
 
             self.checkout_theirs(&repo, name)?
+        } else if let Some(paths) = args.get_many::<String>("paths") {
+            let Some(name) = args.get_one::<String>("name") else {
+                return Err(OxenError::basic_str(
+                    "Err: Usage `oxen checkout <commit> -- <path>`",
+                ));
+            };
+
+            for path in paths {
+                self.checkout_path(&repo, name, path)?;
+            }
         } else if let Some(name) = args.get_one::<String>("name") {
             self.checkout(&repo, name).await?;
         }
@@ -78,6 +104,15 @@ impl CheckoutCmd {
         Ok(())
     }
 
+    pub async fn checkout_track(
+        &self,
+        repo: &mut LocalRepository,
+        remote_branch: &str,
+    ) -> Result<(), OxenError> {
+        command::checkout_track(repo, remote_branch).await?;
+        Ok(())
+    }
+
     pub fn checkout_theirs(&self, repo: &LocalRepository, path: &str) -> Result<(), OxenError> {
         command::checkout_theirs(repo, path)?;
         Ok(())
@@ -88,12 +123,22 @@ impl CheckoutCmd {
         Ok(())
     }
 
+    pub fn checkout_path(
+        &self,
+        repo: &LocalRepository,
+        name: &str,
+        path: &str,
+    ) -> Result<(), OxenError> {
+        command::checkout_path(repo, name, path)?;
+        Ok(())
+    }
+
     pub fn create_checkout_branch(
         &self,
         repo: &LocalRepository,
         name: &str,
     ) -> Result<(), OxenError> {
-        api::local::branches::create_checkout(repo, name)?;
+        command::create_checkout(repo, name)?;
         Ok(())
     }
 }
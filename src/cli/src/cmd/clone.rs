@@ -1,4 +1,8 @@
+use std::path::PathBuf;
+use std::str::FromStr;
+
 use async_trait::async_trait;
+use bytesize::ByteSize;
 use clap::{arg, Arg, Command};
 
 use liboxen::api;
@@ -37,6 +41,21 @@ impl RunCmd for CloneCmd {
                     .help("This downloads the full commit history, all the data files, and all the commit databases. Useful if you want to have the entire history locally or push to a new remote.")
                     .action(clap::ArgAction::SetTrue),
             )
+            .arg(
+                Arg::new("into")
+                    .long("into")
+                    .help("Clone into an existing directory. Files already in the directory are kept, and the clone errors if any of them would be overwritten.")
+                    .action(clap::ArgAction::Set),
+            )
+            .arg(
+                Arg::new("filter-lfs-only")
+                    .long("filter-lfs-only")
+                    .value_name("SIZE")
+                    .help("Only download files up to SIZE (ie 1MB, 500KB). Larger files are left as placeholders until you run `oxen materialize <path>`.")
+                    .num_args(0..=1)
+                    .default_missing_value("1MB")
+                    .action(clap::ArgAction::Set),
+            )
             .arg(
                 Arg::new("branch")
                     .long("branch")
@@ -57,16 +76,33 @@ impl RunCmd for CloneCmd {
             .get_one::<String>("branch")
             .expect("Must supply a branch");
 
-        let dst = std::env::current_dir().expect("Could not get current working directory");
-        // Get the name of the repo from the url
-        let name = url.split('/').last().unwrap();
-        let dst = dst.join(name);
+        let into = args.get_one::<String>("into").map(PathBuf::from);
+        let lfs_threshold = match args.get_one::<String>("filter-lfs-only") {
+            Some(size) => {
+                let size = ByteSize::from_str(size).map_err(|err| {
+                    OxenError::basic_str(format!("Invalid size for --filter-lfs-only: {err}"))
+                })?;
+                Some(size.as_u64())
+            }
+            None => None,
+        };
+
+        let dst = if let Some(into) = &into {
+            into.to_owned()
+        } else {
+            let dst = std::env::current_dir().expect("Could not get current working directory");
+            // Get the name of the repo from the url
+            let name = url.split('/').last().unwrap();
+            dst.join(name)
+        };
 
         let opts = CloneOpts {
             url: url.to_string(),
             dst,
             shallow,
             all,
+            into: into.is_some(),
+            lfs_threshold,
             branch: branch.to_string(),
         };
 
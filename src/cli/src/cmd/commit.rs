@@ -26,25 +26,76 @@ impl RunCmd for CommitCmd {
                     .help("The message for the commit. Should be descriptive about what changed.")
                     .long("message")
                     .short('m')
-                    .required(true)
                     .action(clap::ArgAction::Set),
             )
+            .arg(
+                Arg::new("file")
+                    .help(
+                        "Read the commit message from a file. Lines starting with '#' are ignored.",
+                    )
+                    .long("file")
+                    .short('F')
+                    .conflicts_with("message")
+                    .action(clap::ArgAction::Set),
+            )
+            .arg(
+                Arg::new("allow-empty")
+                    .help("Create the commit even if there are no staged changes.")
+                    .long("allow-empty")
+                    .action(clap::ArgAction::SetTrue),
+            )
     }
 
     async fn run(&self, args: &clap::ArgMatches) -> Result<(), OxenError> {
-        // Parse Args
-        let Some(message) = args.get_one::<String>("message") else {
-            return Err(OxenError::basic_str(
-                "Err: Usage `oxen commit -m <message>`",
-            ));
-        };
-
         let repo = LocalRepository::from_current_dir()?;
         check_repo_migration_needed(&repo)?;
 
+        // Parse Args
+        let message = if let Some(message) = args.get_one::<String>("message") {
+            message.to_owned()
+        } else if let Some(file) = args.get_one::<String>("file") {
+            command::commit::message_from_file(file)?
+        } else {
+            message_from_editor(&repo)?
+        };
+
         println!("Committing with message: {message}");
-        command::commit(&repo, message)?;
+        if args.get_flag("allow-empty") {
+            command::commit::commit_allow_empty(&repo, &message)?;
+        } else {
+            command::commit(&repo, &message)?;
+        }
 
         Ok(())
     }
 }
+
+/// Open $EDITOR on a message file prefilled with the repo commit template
+fn message_from_editor(repo: &LocalRepository) -> Result<String, OxenError> {
+    let Ok(editor) = std::env::var("VISUAL").or_else(|_| std::env::var("EDITOR")) else {
+        return Err(OxenError::basic_str(
+            "Err: Usage `oxen commit -m <message>` or `oxen commit -F <file>`, or set $EDITOR",
+        ));
+    };
+
+    // The editor may come with arguments, ie: EDITOR="code --wait"
+    let words = shlex::split(&editor).unwrap_or_default();
+    let Some((program, editor_args)) = words.split_first() else {
+        return Err(OxenError::basic_str(format!(
+            "Err: Could not parse editor command '{editor}'"
+        )));
+    };
+
+    let path = command::commit::prepare_message_file(repo)?;
+    let status = std::process::Command::new(program)
+        .args(editor_args)
+        .arg(&path)
+        .status()?;
+    if !status.success() {
+        return Err(OxenError::basic_str(format!(
+            "Editor '{editor}' exited with {status}, not committing"
+        )));
+    }
+
+    command::commit::message_from_file(&path)
+}
@@ -52,6 +52,53 @@ impl RunCmd for ConfigCmd {
                     .help("Delete a remote from the current working repository.")
                     .action(clap::ArgAction::Set),
             )
+            .arg(
+                Arg::new("unset-remote")
+                    .long("unset-remote")
+                    .value_name("NAME")
+                    .help("Unset a remote for your current working repository.")
+                    .action(clap::ArgAction::Set),
+            )
+            .arg(
+                Arg::new("rename-remote")
+                    .long("rename-remote")
+                    .number_of_values(2)
+                    .value_names(["OLD", "NEW"])
+                    .help("Rename a remote for your current working repository.")
+                    .action(clap::ArgAction::Set),
+            )
+            .arg(
+                Arg::new("force")
+                    .long("force")
+                    .short('f')
+                    .help("Unset the remote even if a branch tracks it as an upstream.")
+                    .requires("unset-remote")
+                    .action(clap::ArgAction::SetTrue),
+            )
+            .arg(
+                Arg::new("hooks")
+                    .long("hooks")
+                    .value_name("ENABLED")
+                    .value_parser(clap::value_parser!(bool))
+                    .help("Run the pre-commit and post-commit scripts in .oxen/hooks (true or false).")
+                    .action(clap::ArgAction::Set),
+            )
+            .arg(
+                Arg::new("df-page-size")
+                    .long("df-page-size")
+                    .value_name("ROWS")
+                    .value_parser(clap::value_parser!(usize))
+                    .help("Default page size for `oxen df` and the data frame endpoints in this repository.")
+                    .action(clap::ArgAction::Set),
+            )
+            .arg(
+                Arg::new("df-max-rows")
+                    .long("df-max-rows")
+                    .value_name("ROWS")
+                    .value_parser(clap::value_parser!(usize))
+                    .help("Default max rows `oxen df` prints in this repository.")
+                    .action(clap::ArgAction::Set),
+            )
             .arg(
                 Arg::new("auth-token")
                     .long("auth")
@@ -95,6 +142,56 @@ impl RunCmd for ConfigCmd {
             }
         }
 
+        if let Some(remote) = args.get_many::<String>("rename-remote") {
+            if let [old_name, new_name] = remote.collect::<Vec<_>>()[..] {
+                match self.rename_remote(&mut repo, old_name, new_name) {
+                    Ok(_) => {}
+                    Err(err) => {
+                        eprintln!("{err}")
+                    }
+                }
+            } else {
+                eprintln!("invalid arguments for --rename-remote");
+            }
+        }
+
+        if let Some(name) = args.get_one::<String>("unset-remote") {
+            let force = args.get_flag("force");
+            match self.unset_remote(&mut repo, name, force) {
+                Ok(_) => {}
+                Err(err) => {
+                    eprintln!("{err}")
+                }
+            }
+        }
+
+        if let Some(enabled) = args.get_one::<bool>("hooks") {
+            match command::config::set_hooks_enabled(&mut repo, *enabled) {
+                Ok(_) => {}
+                Err(err) => {
+                    eprintln!("{err}")
+                }
+            }
+        }
+
+        let df_page_size = args.get_one::<usize>("df-page-size").copied();
+        let df_max_rows = args.get_one::<usize>("df-max-rows").copied();
+        if df_page_size.is_some() || df_max_rows.is_some() {
+            let mut defaults = repo.df_defaults().clone();
+            if df_page_size.is_some() {
+                defaults.page_size = df_page_size;
+            }
+            if df_max_rows.is_some() {
+                defaults.max_rows = df_max_rows;
+            }
+            match command::config::set_df_defaults(&mut repo, defaults) {
+                Ok(_) => {}
+                Err(err) => {
+                    eprintln!("{err}")
+                }
+            }
+        }
+
         if let Some(auth) = args.get_many::<String>("auth-token") {
             if let [host, token] = auth.collect::<Vec<_>>()[..] {
                 match self.set_auth_token(host, token) {
@@ -157,6 +254,28 @@ impl ConfigCmd {
         Ok(())
     }
 
+    pub fn unset_remote(
+        &self,
+        repo: &mut LocalRepository,
+        name: &str,
+        force: bool,
+    ) -> Result<(), OxenError> {
+        command::config::unset_remote(repo, name, force)?;
+
+        Ok(())
+    }
+
+    pub fn rename_remote(
+        &self,
+        repo: &mut LocalRepository,
+        old_name: &str,
+        new_name: &str,
+    ) -> Result<(), OxenError> {
+        command::config::rename_remote(repo, old_name, new_name)?;
+
+        Ok(())
+    }
+
     pub fn set_auth_token(&self, host: &str, token: &str) -> Result<(), OxenError> {
         let mut config = AuthConfig::get_or_create()?;
         config.add_host_auth_token(host, token);
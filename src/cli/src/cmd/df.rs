@@ -5,6 +5,8 @@ use clap::{arg, Arg, ArgMatches, Command};
 
 use liboxen::command;
 use liboxen::error::OxenError;
+use liboxen::model::LocalRepository;
+use liboxen::util;
 
 use crate::cmd::RunCmd;
 
@@ -30,6 +32,22 @@ impl RunCmd for DFCmd {
                 .help("Output file to store the transformed data")
                 .action(clap::ArgAction::Set),
         )
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .help("How to print the result. `arrow` writes an Arrow IPC stream to stdout for piping into other tools instead of a table.")
+                .value_parser(["table", "arrow"])
+                .default_value("table")
+                .conflicts_with("output")
+                .action(clap::ArgAction::Set),
+        )
+        .arg(
+            Arg::new("partition-by")
+                .long("partition-by")
+                .help("Write --output as a directory of parquet files partitioned by a comma separated set of columns, ie: --output out/ --partition-by year,month writes out/year=.../month=.../*.parquet")
+                .requires("output")
+                .action(clap::ArgAction::Set),
+        )
         .arg(
             Arg::new("delimiter")
                 .long("delimiter")
@@ -62,6 +80,28 @@ impl RunCmd for DFCmd {
                 .help("Combine row data from different files. The number of columns must match.")
                 .action(clap::ArgAction::Append),
         )
+        .arg(
+            Arg::new("concat")
+                .long("concat")
+                .help("Stack the rows of other files under the input, matching columns by name. The files must have the same columns and types unless --merge-schema or --coerce is set.")
+                .num_args(1..)
+                .action(clap::ArgAction::Append),
+        )
+        .arg(
+            Arg::new("coerce")
+                .long("coerce")
+                .help("With --concat, cast mismatched columns to a common type and fill missing columns with nulls.")
+                .requires("concat")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("merge-schema")
+                .long("merge-schema")
+                .help("With --concat, keep the union of the columns and fill the ones a file is missing with nulls. Shared columns must have the same type.")
+                .requires("concat")
+                .conflicts_with("coerce")
+                .action(clap::ArgAction::SetTrue),
+        )
         .arg(
             Arg::new("slice")
                 .long("slice")
@@ -77,7 +117,14 @@ impl RunCmd for DFCmd {
         .arg(
             Arg::new("page-size")
                 .long("page-size")
-                .help("Paginated through the data frame. Default page-size = 10")
+                .help("Paginated through the data frame. Defaults to the page size set with `oxen config --df-page-size`, otherwise shows every row.")
+                .action(clap::ArgAction::Set),
+        )
+        .arg(
+            Arg::new("max-rows")
+                .long("max-rows")
+                .help("Max number of rows to print, the rest are elided. Defaults to the value set with `oxen config --df-max-rows`.")
+                .value_parser(clap::value_parser!(usize))
                 .action(clap::ArgAction::Set),
         )
         .arg(
@@ -103,7 +150,7 @@ impl RunCmd for DFCmd {
             Arg::new("sort")
                 .long("sort")
                 .short('s')
-                .help("Sort the output by a column name. Is run at the end of all the other transforms.")
+                .help("Sort the output by a comma separated set of column names, each with an optional :asc or :desc direction ie: \"label:asc,score:desc\". Is run at the end of all the other transforms.")
                 .action(clap::ArgAction::Set),
         )
         .arg(
@@ -112,6 +159,26 @@ impl RunCmd for DFCmd {
                 .help("Run a sql query on the data frame.")
                 .action(clap::ArgAction::Set),
         )
+        .arg(
+            Arg::new("sql-file")
+                .long("sql-file")
+                .help("Run the SELECT query saved in a file on the data frame. Use ${table} in the query for the table the data frame is loaded into.")
+                .conflicts_with("sql")
+                .action(clap::ArgAction::Set),
+        )
+        .arg(
+            Arg::new("explain")
+                .long("explain")
+                .help("Print the DuckDB query plan for the --sql or --sql-file query instead of running it.")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("apply-schema")
+                .long("apply-schema")
+                .help("Coerce the data frame to a committed schema, given a file path, schema hash or schema name. Columns are reordered and cast, erroring on incompatible data.")
+                .conflicts_with_all(["sql", "sql-file", "concat"])
+                .action(clap::ArgAction::Set),
+        )
         .arg(
             Arg::new("text2sql")
                 .long("text2sql")
@@ -155,6 +222,32 @@ impl RunCmd for DFCmd {
                 .help("Print the full list of columns and data types within the schema.")
                 .action(clap::ArgAction::SetTrue),
         )
+        .arg(
+            Arg::new("infer-schema")
+                .long("infer-schema")
+                .help("Print the column types detected when the file is indexed for editing, without indexing it.")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("profile")
+                .long("profile")
+                .help("Print a data quality report as json: the null rate, distinct count, min, max and anomalies for each column.")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("null-count")
+                .long("null-count")
+                .help("Print the number of null values in each column.")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("drop-nulls")
+                .long("drop-nulls")
+                .help("Drop the rows that have a null value. Optionally pass comma separated columns to check, otherwise every column is checked. Ie: --drop-nulls 'label,score'")
+                .num_args(0..=1)
+                .default_missing_value("")
+                .action(clap::ArgAction::Set),
+        )
         .arg(
             Arg::new("add-col")
                 .long("add-col")
@@ -167,6 +260,184 @@ impl RunCmd for DFCmd {
                 .help("Add a row and cast to the values data types to match the current schema. If used with --add-col, row is added first, then column. Format 'comma,separated,vals'")
                 .action(clap::ArgAction::Set),
         )
+        .arg(
+            Arg::new("hash-rows")
+                .long("hash-rows")
+                .help("Append a _row_hash column with a stable hash of each row. Optionally takes a comma separated set of columns to hash, in the order they should be hashed. Defaults to all columns.")
+                .num_args(0..=1)
+                .default_missing_value("")
+                .action(clap::ArgAction::Set),
+        )
+        .arg(
+            Arg::new("pivot")
+                .long("pivot")
+                .help("Pivot a long data frame to wide. Requires --index, --columns, and --values, --columns is the column whose values become the new column names.")
+                .requires_all(["index", "columns", "values"])
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("index")
+                .long("index")
+                .help("Column to group the rows by when pivoting.")
+                .requires("pivot")
+                .action(clap::ArgAction::Set),
+        )
+        .arg(
+            Arg::new("values")
+                .long("values")
+                .help("Column to fill in the pivoted cells with.")
+                .requires("pivot")
+                .action(clap::ArgAction::Set),
+        )
+        .arg(
+            Arg::new("agg")
+                .long("agg")
+                .help("With --pivot, the function to combine values that land in the same pivoted cell. Ie: first, sum, avg, count. Default is first. With --group-by, comma separated fn:col aggregations, output as fn_col. Ie: --agg 'sum:amount,mean:price'")
+                .action(clap::ArgAction::Set),
+        )
+        .arg(
+            Arg::new("group-by")
+                .long("group-by")
+                .help("Group rows by a comma separated set of columns and compute the --agg aggregations for each group. Ie: --group-by 'store,item' --agg 'sum:amount,mean:price'")
+                .requires("agg")
+                .conflicts_with("pivot")
+                .action(clap::ArgAction::Set),
+        )
+        .arg(
+            Arg::new("aggregate-over-dirs")
+                .long("aggregate-over-dirs")
+                .help("Treat the input as a directory and run the --group-by over every tabular file under it, unioned by column name. Ie: oxen df data/ --aggregate-over-dirs --group-by label --agg 'count:image'")
+                .requires("group-by")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("window")
+                .long("window")
+                .help("Comma separated fn:col:size rolling aggregations over each row and the size - 1 rows before it, appended as fn_col_size columns. Ie: --window 'mean:price:7' --order-by date")
+                .action(clap::ArgAction::Set),
+        )
+        .arg(
+            Arg::new("seed-split")
+                .long("seed-split")
+                .help("Comma separated train,val,test (or train,test) fractions. Appends a split column assigning each row by hashing it with --seed, so the split is the same across runs and machines. Ie: --seed-split 0.8,0.1,0.1 --seed 42")
+                .action(clap::ArgAction::Set),
+        )
+        .arg(
+            Arg::new("seed")
+                .long("seed")
+                .help("With --seed-split, the seed to hash the rows with. Defaults to 0.")
+                .requires("seed-split")
+                .value_parser(clap::value_parser!(u64))
+                .action(clap::ArgAction::Set),
+        )
+        .arg(
+            Arg::new("order-by")
+                .long("order-by")
+                .help("With --window, the column to order the rows by. Defaults to the order of the file.")
+                .requires("window")
+                .action(clap::ArgAction::Set),
+        )
+        .arg(
+            Arg::new("transpose")
+                .long("transpose")
+                .help("Swap the rows and columns of a small data frame, the column names become the first column.")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("where-in")
+                .long("where-in")
+                .help("Keep the rows whose column is one of the values in a file, one value per line or a column of a tabular file. Ie: --where-in 'id:ids.txt'")
+                .action(clap::ArgAction::Set),
+        )
+        .arg(
+            Arg::new("dedup")
+                .long("dedup")
+                .help("Drop duplicate rows, keeping the first occurrence. Optionally pass comma separated columns to compare, otherwise every column is compared. Ie: --dedup 'file,label'")
+                .num_args(0..=1)
+                .default_missing_value("")
+                .action(clap::ArgAction::Set),
+        )
+        .arg(
+            Arg::new("detect-duplicates")
+                .long("detect-duplicates")
+                .help("Only show rows that have a duplicate, numbered by duplicate_group with the duplicate_count of each group. Optionally pass comma separated columns to compare, otherwise every column is compared. Ie: --detect-duplicates 'file'")
+                .num_args(0..=1)
+                .default_missing_value("")
+                .action(clap::ArgAction::Set),
+        )
+        .arg(
+            Arg::new("rename")
+                .long("rename")
+                .help("Rename columns, keeping their order. Ie: --rename 'file:path,label:category'")
+                .action(clap::ArgAction::Set),
+        )
+        .arg(
+            Arg::new("cast")
+                .long("cast")
+                .help("Cast columns to a type: int, bigint, float, double, bool, str, date or timestamp. Ie: --cast 'count:int,score:double'")
+                .action(clap::ArgAction::Set),
+        )
+        .arg(
+            Arg::new("normalize")
+                .long("normalize")
+                .help("Scale numeric columns with minmax into [0, 1] or with zscore to mean 0 and standard deviation 1. Ie: --normalize 'minmax:price,zscore:age'")
+                .action(clap::ArgAction::Set),
+        )
+        .arg(
+            Arg::new("normalize-append")
+                .long("normalize-append")
+                .help("Add the --normalize results as new col_method columns instead of replacing the columns.")
+                .requires("normalize")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("coalesce")
+                .long("coalesce")
+                .help("Take the first non-null value across columns into a new column, named with --as. Ie: --coalesce 'email,work_email' --as contact")
+                .action(clap::ArgAction::Set),
+        )
+        .arg(
+            Arg::new("as")
+                .long("as")
+                .help("Name of the --coalesce column, replaces the column if it exists. Default: coalesced")
+                .requires("coalesce")
+                .action(clap::ArgAction::Set),
+        )
+        .arg(
+            Arg::new("fuzzy-join")
+                .long("fuzzy-join")
+                .help("Join the rows of another file where the --on column is similar rather than equal, using the jaro winkler similarity. Ie: --fuzzy-join b.csv --on name --threshold 0.9")
+                .requires("on")
+                .action(clap::ArgAction::Set),
+        )
+        .arg(
+            Arg::new("on")
+                .long("on")
+                .help("Column to compare for --fuzzy-join, must be in both files.")
+                .requires("fuzzy-join")
+                .action(clap::ArgAction::Set),
+        )
+        .arg(
+            Arg::new("threshold")
+                .long("threshold")
+                .help("Minimum similarity, between 0 and 1, for --fuzzy-join to match two rows. Default: 0.9")
+                .requires("fuzzy-join")
+                .value_parser(clap::value_parser!(f64))
+                .action(clap::ArgAction::Set),
+        )
+        .arg(
+            Arg::new("assert")
+                .long("assert")
+                .help("Check an assertion against the data frame and exit with a non-zero code if it fails, can be repeated. `count` is the number of rows, `null_count(col)` and `null_rate(col)` the nulls in a column, other DuckDB aggregates work too. Ie: --assert 'count > 0' --assert 'null_rate(email) < 0.01'")
+                .action(clap::ArgAction::Append),
+        )
+        .arg(
+            Arg::new("write")
+                .long("write")
+                .help("Write the result back to the input file.")
+                .conflicts_with("output")
+                .action(clap::ArgAction::SetTrue),
+        )
         .arg(
             Arg::new("delete-row")
                 .long("delete-row")
@@ -177,16 +448,65 @@ impl RunCmd for DFCmd {
 
     async fn run(&self, args: &clap::ArgMatches) -> Result<(), OxenError> {
         // Parse Args
-        let opts = DFCmd::parse_df_args(args);
+        let mut opts = DFCmd::parse_df_args(args);
         let Some(path) = args.get_one::<String>("DF_SPEC") else {
             return Err(OxenError::basic_str("Must supply a DataFrame to process."));
         };
 
-        if args.get_flag("schema") || args.get_flag("schema-flat") {
+        if args.get_flag("write") {
+            opts.output = Some(PathBuf::from(path));
+        }
+
+        if let Some(assertions) = args.get_many::<String>("assert") {
+            let assertions: Vec<String> = assertions.cloned().collect();
+            if let Err(err) = command::df::assert(path, &assertions) {
+                eprintln!("{err}");
+                std::process::exit(1);
+            }
+        } else if args.get_flag("profile") {
+            let profile = command::df::profile(path, opts)?;
+            println!("{}", serde_json::to_string_pretty(&profile)?);
+        } else if let Some(schema_ref) = args.get_one::<String>("apply-schema") {
+            let repo = LocalRepository::from_current_dir()?;
+            command::df::apply_schema(&repo, path, schema_ref, opts)?;
+        } else if args.get_flag("explain") {
+            // The plan only covers the query, so filters like --where-in would silently be ignored
+            let mut other_transforms = opts.clone();
+            other_transforms.sql = None;
+            if other_transforms.has_transform() {
+                return Err(OxenError::basic_str(
+                    "Err: --explain only explains the --sql or --sql-file query, put any filters in the query itself",
+                ));
+            }
+            let sql = if let Some(sql) = &opts.sql {
+                sql.to_owned()
+            } else if let Some(sql_path) = args.get_one::<String>("sql-file") {
+                util::fs::read_from_path(sql_path)?
+            } else {
+                return Err(OxenError::basic_str(
+                    "Err: Usage `oxen df <path> --sql <query> --explain`",
+                ));
+            };
+            command::df::explain(path, &sql)?;
+        } else if let Some(sql_path) = args.get_one::<String>("sql-file") {
+            command::df::sql_file(path, sql_path, opts)?;
+        } else if args.get_flag("null-count") {
+            let null_counts = command::df::null_counts(path, opts)?;
+            println!("{null_counts}");
+        } else if args.get_one::<String>("format").map(String::as_str) == Some("arrow") {
+            command::df::df_to_arrow_stream(path, opts, std::io::stdout().lock())?;
+        } else if args.get_flag("infer-schema") {
+            let schema = command::df::infer_schema(path)?;
+            println!("{}", schema.verbose_str());
+        } else if args.get_flag("schema") || args.get_flag("schema-flat") {
             let flatten = args.get_flag("schema-flat");
             let result = command::df::schema(path, flatten, opts)?;
             println!("{result}");
+        } else if let Ok(repo) = LocalRepository::from_current_dir() {
+            DFCmd::set_max_rows(opts.max_rows.or(repo.df_defaults().max_rows));
+            command::df::df_with_repo_defaults(&repo, path, opts)?;
         } else {
+            DFCmd::set_max_rows(opts.max_rows);
             command::df(path, opts)?;
         }
 
@@ -195,6 +515,13 @@ impl RunCmd for DFCmd {
 }
 
 impl DFCmd {
+    // polars reads the display limit from the environment when formatting
+    fn set_max_rows(max_rows: Option<usize>) {
+        if let Some(max_rows) = max_rows {
+            std::env::set_var("POLARS_FMT_MAX_ROWS", max_rows.to_string());
+        }
+    }
+
     pub fn parse_df_args(args: &ArgMatches) -> liboxen::opts::DFOpts {
         let vstack: Option<Vec<PathBuf>> = if let Some(vstack) = args.get_many::<String>("vstack") {
             let values: Vec<PathBuf> = vstack.map(std::path::PathBuf::from).collect();
@@ -203,10 +530,65 @@ impl DFCmd {
             None
         };
 
+        let concat: Option<Vec<PathBuf>> = args
+            .get_many::<String>("concat")
+            .map(|paths| paths.map(PathBuf::from).collect());
+
+        // When pivoting, --columns names the column to pivot on rather than a selection
+        let pivot = if args.get_flag("pivot") {
+            Some(liboxen::opts::PivotOpts {
+                index: args.get_one::<String>("index").cloned().unwrap_or_default(),
+                columns: args
+                    .get_one::<String>("columns")
+                    .cloned()
+                    .unwrap_or_default(),
+                values: args
+                    .get_one::<String>("values")
+                    .cloned()
+                    .unwrap_or_default(),
+                agg: args
+                    .get_one::<String>("agg")
+                    .cloned()
+                    .unwrap_or(String::from("first")),
+            })
+        } else {
+            None
+        };
+        let columns = if pivot.is_some() {
+            None
+        } else {
+            args.get_one::<String>("columns").map(String::from)
+        };
+
+        let group_by = args.get_one::<String>("group-by").map(String::from);
+        let agg = if group_by.is_some() {
+            args.get_one::<String>("agg").map(String::from)
+        } else {
+            None
+        };
+
         liboxen::opts::DFOpts {
+            agg,
+            aggregate_over_dirs: args.get_flag("aggregate-over-dirs"),
+            group_by,
             output: args
                 .get_one::<String>("output")
                 .map(std::path::PathBuf::from),
+            partition_by: args.get_one::<String>("partition-by").map(String::from),
+            concat,
+            coerce: args.get_flag("coerce"),
+            merge_schema: args.get_flag("merge-schema"),
+            dedup: args.get_one::<String>("dedup").map(String::from),
+            where_in: args.get_one::<String>("where-in").map(String::from),
+            detect_duplicates: args
+                .get_one::<String>("detect-duplicates")
+                .map(String::from),
+            drop_nulls: args.get_one::<String>("drop-nulls").map(String::from),
+            fuzzy_join: args
+                .get_one::<String>("fuzzy-join")
+                .map(std::path::PathBuf::from),
+            fuzzy_on: args.get_one::<String>("on").map(String::from),
+            fuzzy_threshold: args.get_one::<f64>("threshold").copied(),
             delimiter: args.get_one::<String>("delimiter").map(String::from),
             slice: args.get_one::<String>("slice").map(String::from),
             page_size: args
@@ -215,6 +597,11 @@ impl DFCmd {
             page: args
                 .get_one::<String>("page")
                 .map(|x| x.parse::<usize>().expect("page must be valid int")),
+            pivot,
+            rename: args.get_one::<String>("rename").map(String::from),
+            cast: args.get_one::<String>("cast").map(String::from),
+            coalesce: args.get_one::<String>("coalesce").map(String::from),
+            coalesce_as: args.get_one::<String>("as").map(String::from),
             head: args
                 .get_one::<String>("head")
                 .map(|x| x.parse::<usize>().expect("head must be valid int")),
@@ -224,13 +611,22 @@ impl DFCmd {
             row: args
                 .get_one::<String>("row")
                 .map(|x| x.parse::<usize>().expect("row must be valid int")),
+            max_rows: args.get_one::<usize>("max-rows").copied(),
+            normalize: args.get_one::<String>("normalize").map(String::from),
+            normalize_append: args.get_flag("normalize-append"),
             take: args.get_one::<String>("take").map(String::from),
-            columns: args.get_one::<String>("columns").map(String::from),
+            transpose: args.get_flag("transpose"),
+            columns,
             item: args.get_one::<String>("item").map(String::from),
             vstack,
+            window: args.get_one::<String>("window").map(String::from),
+            seed_split: args.get_one::<String>("seed-split").map(String::from),
+            seed: args.get_one::<u64>("seed").copied(),
+            order_by: args.get_one::<String>("order-by").map(String::from),
             add_col: args.get_one::<String>("add-col").map(String::from),
             add_row: args.get_one::<String>("add-row").map(String::from),
             delete_row: args.get_one::<String>("delete-row").map(String::from),
+            hash_rows: args.get_one::<String>("hash-rows").map(String::from),
             sort_by: args.get_one::<String>("sort").map(String::from),
             sql: args.get_one::<String>("sql").map(String::from),
             text2sql: args.get_one::<String>("text2sql").map(String::from),
@@ -1,15 +1,11 @@
 use async_trait::async_trait;
 use clap::{Arg, Command};
-use colored::ColoredString;
-use colored::Colorize;
 use std::path::PathBuf;
 
+use liboxen::api;
 use liboxen::command;
-use liboxen::core::df::pretty_print;
-use liboxen::core::df::tabular;
 use liboxen::error::OxenError;
-use liboxen::model::diff::tabular_diff::TabularDiffMods;
-use liboxen::model::diff::{ChangeType, DiffResult, TextDiff};
+use liboxen::model::diff::DiffResult;
 use liboxen::opts::DiffOpts;
 use liboxen::util;
 
@@ -54,44 +50,40 @@ impl RunCmd for DiffCmd {
                 .required(false)
                 .long("output")
                 .short('o')
-                .help("Output directory path to write the results of the comparison. Will write both match.csv (rows with same keys and compares) and diff.csv (rows with different compares between files.")
+                .help("Output path to write the changed rows of the comparison to, the file type comes from the extension. With --output-format this can be a directory to write diff.{format} into. For images, the path to write the two images side by side to.")
                 .action(clap::ArgAction::Set))
+            .arg(Arg::new("output-format")
+                .long("output-format")
+                .help("Format to write the changed rows of a tabular comparison in.")
+                .value_parser(command::diff::TABULAR_OUTPUT_FORMATS)
+                .requires("output")
+                .action(clap::ArgAction::Set))
+            .arg(Arg::new("reverse")
+                .long("reverse")
+                .help("Swap RESOURCE1 and RESOURCE2, so additions and removals are inverted.")
+                .action(clap::ArgAction::SetTrue))
+            .arg(Arg::new("null-equals-null")
+                .long("null-equals-null")
+                .help("Match rows where the key columns are null in both resources. By default null keys never match, like in SQL, so those rows show up as removed and added.")
+                .action(clap::ArgAction::SetTrue))
+            .arg(Arg::new("no-color")
+                .long("no-color")
+                .help("Print the diff without colors.")
+                .action(clap::ArgAction::SetTrue))
     }
 
     async fn run(&self, args: &clap::ArgMatches) -> Result<(), OxenError> {
         // Parse Args
-        let opts = DiffCmd::parse_args(args);
-
-        // If the user specifies two files without revisions, we will compare the files on disk
-        let mut diff_result =
-            if opts.revision_1.is_none() && opts.revision_2.is_none() && opts.path_2.is_some() {
-                // If we do not have revisions set, just compare the files on disk
-                command::diff(
-                    opts.path_1,
-                    opts.path_2,
-                    opts.keys,
-                    opts.targets,
-                    None,
-                    opts.revision_1,
-                    opts.revision_2,
-                )?
-            } else {
-                // If we have revisions set, pass in the repo_dir to be able
-                // to compare the files at those revisions within the .oxen repo
-                let repo_dir = util::fs::get_repo_root_from_current_dir().unwrap();
-                command::diff(
-                    opts.path_1,
-                    opts.path_2,
-                    opts.keys,
-                    opts.targets,
-                    Some(repo_dir),
-                    opts.revision_1,
-                    opts.revision_2,
-                )?
-            };
-
-        DiffCmd::print_diff_result(&diff_result)?;
-        DiffCmd::maybe_save_diff_output(&mut diff_result, opts.output)?;
+        let mut opts = DiffCmd::parse_args(args);
+
+        // Pass in the repo_dir to be able to compare the files at revisions within the .oxen repo,
+        // two files on disk are still compared directly but follow the repo's .oxenattributes
+        opts.repo_dir = util::fs::get_repo_root_from_current_dir();
+
+        let mut diff_result = command::diff::diff_with_opts(&opts)?;
+
+        DiffCmd::print_diff_result(&diff_result, opts.no_color)?;
+        DiffCmd::maybe_save_diff_output(&mut diff_result, opts.output, opts.output_format)?;
 
         Ok(())
     }
@@ -138,6 +130,10 @@ impl DiffCmd {
             revision_1: revision1,
             revision_2: revision2,
             output,
+            output_format: args.get_one::<String>("output-format").cloned(),
+            reverse: args.get_flag("reverse"),
+            no_color: args.get_flag("no-color"),
+            null_equals_null: args.get_flag("null-equals-null"),
         }
     }
 
@@ -150,101 +146,38 @@ impl DiffCmd {
         }
     }
 
-    pub fn print_diff_result(result: &DiffResult) -> Result<(), OxenError> {
-        match result {
-            DiffResult::Tabular(result) => {
-                // println!("{:?}", ct.summary);
-                DiffCmd::print_column_changes(&result.summary.modifications)?;
-                DiffCmd::print_row_changes(&result.summary.modifications)?;
-                println!("{}", pretty_print::df_to_str(&result.contents));
-            }
-            DiffResult::Text(diff) => {
-                DiffCmd::print_text_diff(diff);
-            }
-        }
-
+    pub fn print_diff_result(result: &DiffResult, no_color: bool) -> Result<(), OxenError> {
+        print!("{}", result.format(!no_color));
         Ok(())
     }
 
-    fn print_row_changes(mods: &TabularDiffMods) -> Result<(), OxenError> {
-        let mut outputs: Vec<ColoredString> = vec![];
-
-        if mods.row_counts.modified + mods.row_counts.added + mods.row_counts.removed == 0 {
-            println!();
-            return Ok(());
-        }
-
-        outputs.push("\nRow changes: \n".into());
-        if mods.row_counts.modified > 0 {
-            outputs.push(format!("   Δ {} (modified)\n", mods.row_counts.modified).yellow());
-        }
-
-        if mods.row_counts.added > 0 {
-            outputs.push(format!("   + {} (added)\n", mods.row_counts.added).green());
-        }
-
-        if mods.row_counts.removed > 0 {
-            outputs.push(format!("   - {} (removed)\n", mods.row_counts.removed).red());
-        }
-
-        for output in outputs {
-            print!("{output}");
-        }
-
-        println!();
-
-        Ok(())
-    }
-
-    // TODO: Truncate to "and x more"
-    fn print_column_changes(mods: &TabularDiffMods) -> Result<(), OxenError> {
-        let mut outputs: Vec<ColoredString> = vec![];
-
-        if !mods.col_changes.added.is_empty() || !mods.col_changes.added.is_empty() {
-            outputs.push("Column changes:\n".into());
-        }
-
-        for col in &mods.col_changes.added {
-            outputs.push(format!("   + {} ({})\n", col.name, col.dtype).green());
-        }
-
-        for col in &mods.col_changes.removed {
-            outputs.push(format!("   - {} ({})\n", col.name, col.dtype).red());
-        }
-
-        for output in outputs {
-            print!("{output}");
-        }
-
-        Ok(())
-    }
-
-    fn print_text_diff(diff: &TextDiff) {
-        for line in &diff.lines {
-            match line.modification {
-                ChangeType::Unchanged => println!("{}", line.text),
-                ChangeType::Added => println!("{}", line.text.green()),
-                ChangeType::Removed => println!("{}", line.text.red()),
-                ChangeType::Modified => println!("{}", line.text.yellow()),
-            }
-        }
-    }
-
     pub fn maybe_save_diff_output(
         result: &mut DiffResult,
         output: Option<PathBuf>,
+        output_format: Option<String>,
     ) -> Result<(), OxenError> {
         match result {
             DiffResult::Tabular(result) => {
-                let mut df = result.contents.clone();
                 // Save to disk if we have an output
                 if let Some(file_path) = output {
-                    tabular::write_df(&mut df, file_path.clone())?;
+                    let path =
+                        command::diff::save_tabular(result, file_path, output_format.as_deref())?;
+                    println!("Wrote diff to {}", path.display());
                 }
             }
             DiffResult::Text(_) => {
                 println!("Saving to disk not supported for text output");
             }
+            DiffResult::Binary(_) => {
+                println!("Saving to disk not supported for binary output");
+            }
+            DiffResult::Image(result) => {
+                // Save the two images side by side
+                if let Some(file_path) = output {
+                    api::local::diff::image_diff::write_composite(result, &file_path)?;
+                    println!("Wrote side by side image to {}", file_path.display());
+                }
+            }
         }
 
         Ok(())
@@ -0,0 +1,69 @@
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use clap::{Arg, Command};
+
+use liboxen::command;
+use liboxen::core::export::ExportFormat;
+use liboxen::error::OxenError;
+use liboxen::model::LocalRepository;
+use liboxen::util;
+
+use crate::cmd::RunCmd;
+use crate::helpers::check_repo_migration_needed;
+
+pub const NAME: &str = "export";
+pub struct ExportCmd;
+
+#[async_trait]
+impl RunCmd for ExportCmd {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn args(&self) -> Command {
+        // Setups the CLI args for the command
+        Command::new(NAME)
+            .about("Export the dataset metadata of a commit, such as MLCommons Croissant JSON.")
+            .arg(
+                Arg::new("format")
+                    .help("The metadata format to export.")
+                    .long("format")
+                    .short('f')
+                    .default_value(ExportFormat::Croissant.as_str())
+                    .value_parser([ExportFormat::Croissant.as_str()]),
+            )
+            .arg(
+                Arg::new("revision")
+                    .help("The branch or commit id to export, defaults to HEAD.")
+                    .long("revision")
+                    .short('r'),
+            )
+            .arg(
+                Arg::new("output")
+                    .help("Write the metadata to this file instead of stdout.")
+                    .long("output")
+                    .short('o'),
+            )
+    }
+
+    async fn run(&self, args: &clap::ArgMatches) -> Result<(), OxenError> {
+        let repo = LocalRepository::from_current_dir()?;
+        check_repo_migration_needed(&repo)?;
+
+        let format = args.get_one::<String>("format").expect("has default");
+        let format = ExportFormat::from_str(format)?;
+        let revision = args.get_one::<String>("revision").map(String::as_str);
+
+        let metadata = command::export(&repo, revision, format)?;
+        match args.get_one::<String>("output") {
+            Some(output) => {
+                util::fs::write_to_path(output, &metadata)?;
+                println!("Exported {format} metadata to {output}");
+            }
+            None => println!("{metadata}"),
+        }
+
+        Ok(())
+    }
+}
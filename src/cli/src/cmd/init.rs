@@ -1,7 +1,7 @@
 use std::path::PathBuf;
 
 use async_trait::async_trait;
-use clap::{arg, Command};
+use clap::{arg, Arg, Command};
 use liboxen::error::OxenError;
 
 use crate::cmd::RunCmd;
@@ -23,6 +23,12 @@ impl RunCmd for InitCmd {
         Command::new(INIT)
             .about("Initializes a local repository")
             .arg(arg!([PATH] "The directory to establish the repo in. Defaults to the current directory."))
+            .arg(
+                Arg::new("bare")
+                    .long("bare")
+                    .help("Create a repository without a working tree, suitable for pushing to and serving.")
+                    .action(clap::ArgAction::SetTrue),
+            )
     }
 
     async fn run(&self, args: &clap::ArgMatches) -> Result<(), OxenError> {
@@ -36,8 +42,13 @@ impl RunCmd for InitCmd {
 
         // Initialize the repository
         let directory = dunce::canonicalize(PathBuf::from(&path))?;
-        command::init(&directory)?;
-        println!("🐂 repository initialized at: {directory:?}");
+        if args.get_flag("bare") {
+            command::init_bare(&directory)?;
+            println!("🐂 bare repository initialized at: {directory:?}");
+        } else {
+            command::init(&directory)?;
+            println!("🐂 repository initialized at: {directory:?}");
+        }
         Ok(())
     }
 }
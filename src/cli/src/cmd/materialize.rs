@@ -0,0 +1,46 @@
+use async_trait::async_trait;
+use clap::{arg, Command};
+
+use liboxen::command;
+use liboxen::error::OxenError;
+use liboxen::model::LocalRepository;
+use liboxen::util;
+
+use crate::cmd::RunCmd;
+use crate::helpers::check_repo_migration_needed;
+
+pub const NAME: &str = "materialize";
+pub struct MaterializeCmd;
+
+#[async_trait]
+impl RunCmd for MaterializeCmd {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn args(&self) -> Command {
+        // Setups the CLI args for the command
+        Command::new(NAME)
+            .about(
+                "Download files that were left as placeholders by `oxen clone --filter-lfs-only`.",
+            )
+            .arg_required_else_help(true)
+            .arg(arg!(<PATH> ... "The placeholder files or directories to download"))
+    }
+
+    async fn run(&self, args: &clap::ArgMatches) -> Result<(), OxenError> {
+        let repo = LocalRepository::from_current_dir()?;
+        check_repo_migration_needed(&repo)?;
+
+        let current_dir = std::env::current_dir()?;
+        for path in args.get_many::<String>("PATH").expect("required") {
+            // Placeholders are not on disk, so resolve the path without canonicalizing
+            let path = util::fs::path_relative_to_dir(current_dir.join(path), &repo.path)?;
+            for path in command::materialize(&repo, &path).await? {
+                println!("{}", path.to_string_lossy());
+            }
+        }
+
+        Ok(())
+    }
+}
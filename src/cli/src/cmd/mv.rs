@@ -0,0 +1,50 @@
+use async_trait::async_trait;
+use clap::{Arg, Command};
+
+use liboxen::command;
+use liboxen::error::OxenError;
+use liboxen::model::LocalRepository;
+
+use crate::cmd::RunCmd;
+use crate::helpers::check_repo_migration_needed;
+
+pub const NAME: &str = "mv";
+pub struct MvCmd;
+
+#[async_trait]
+impl RunCmd for MvCmd {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn args(&self) -> Command {
+        // Setups the CLI args for the command
+        Command::new(NAME)
+            .about("Move or rename a committed file and stage it as a move.")
+            .arg(
+                Arg::new("source")
+                    .help("The committed file to move.")
+                    .required(true)
+                    .action(clap::ArgAction::Set),
+            )
+            .arg(
+                Arg::new("destination")
+                    .help("The new path, or an existing directory to move the file into.")
+                    .required(true)
+                    .action(clap::ArgAction::Set),
+            )
+    }
+
+    async fn run(&self, args: &clap::ArgMatches) -> Result<(), OxenError> {
+        let src = args.get_one::<String>("source").expect("required");
+        let dst = args.get_one::<String>("destination").expect("required");
+
+        let repo = LocalRepository::from_current_dir()?;
+        check_repo_migration_needed(&repo)?;
+
+        // Paths are relative to where the command is run, like add and rm
+        let current_dir = std::env::current_dir()?;
+        command::mv(&repo, current_dir.join(src), current_dir.join(dst))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+use async_trait::async_trait;
+use clap::Command;
+use time::format_description;
+
+use liboxen::command;
+use liboxen::error::OxenError;
+use liboxen::model::LocalRepository;
+
+use crate::cmd::RunCmd;
+use crate::helpers::check_repo_migration_needed;
+
+pub const NAME: &str = "reflog";
+pub struct ReflogCmd;
+
+#[async_trait]
+impl RunCmd for ReflogCmd {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn args(&self) -> Command {
+        // Setups the CLI args for the command
+        Command::new(NAME).about(
+            "Show where HEAD has pointed after commits, checkouts and merges, most recent first.",
+        )
+    }
+
+    async fn run(&self, _args: &clap::ArgMatches) -> Result<(), OxenError> {
+        let repo = LocalRepository::from_current_dir()?;
+        check_repo_migration_needed(&repo)?;
+
+        // 2022-10-21 16:08:39
+        let format = format_description::parse("[year]-[month]-[day] [hour]:[minute]:[second]")
+            .map_err(|err| OxenError::basic_str(err.to_string()))?;
+
+        let entries = command::reflog(&repo)?;
+        for (i, entry) in entries.iter().enumerate().rev() {
+            let timestamp = entry
+                .timestamp
+                .format(&format)
+                .map_err(|err| OxenError::basic_str(err.to_string()))?;
+            println!("{} HEAD@{{{}}} {}", timestamp, entries.len() - 1 - i, entry);
+        }
+
+        Ok(())
+    }
+}
@@ -6,3 +6,9 @@ pub use diff::RemoteDiffCmd;
 
 pub mod df;
 pub use df::RemoteDfCmd;
+
+pub mod info;
+pub use info::RemoteInfoCmd;
+
+pub mod merge;
+pub use merge::RemoteMergeCmd;
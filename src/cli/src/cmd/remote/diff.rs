@@ -31,9 +31,9 @@ impl RunCmd for RemoteDiffCmd {
         check_repo_migration_needed(&repository)?;
 
         let mut remote_diff =
-            command::remote::diff(&repository, opts.revision_1, &opts.path_1).await?;
-        DiffCmd::print_diff_result(&remote_diff)?;
-        DiffCmd::maybe_save_diff_output(&mut remote_diff, opts.output)?;
+            command::remote::diff(&repository, opts.revision_1, &opts.path_1, opts.reverse).await?;
+        DiffCmd::print_diff_result(&remote_diff, opts.no_color)?;
+        DiffCmd::maybe_save_diff_output(&mut remote_diff, opts.output, opts.output_format)?;
 
         // TODO: Allow them to save a remote diff to disk
 
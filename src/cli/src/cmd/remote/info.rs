@@ -0,0 +1,73 @@
+use async_trait::async_trait;
+use bytesize::ByteSize;
+use clap::{Arg, Command};
+use time::format_description;
+
+use liboxen::api;
+use liboxen::constants::DEFAULT_REMOTE_NAME;
+use liboxen::error::OxenError;
+use liboxen::model::LocalRepository;
+
+use crate::cmd::RunCmd;
+use crate::helpers::check_repo_migration_needed;
+
+pub const NAME: &str = "info";
+pub struct RemoteInfoCmd;
+
+#[async_trait]
+impl RunCmd for RemoteInfoCmd {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn args(&self) -> Command {
+        // Setups the CLI args for the command
+        Command::new(NAME)
+            .about(
+                "Show the size, commit count, branch count and last update of a remote repository.",
+            )
+            .arg(
+                Arg::new("REMOTE")
+                    .help("The name of the remote to describe. Defaults to origin.")
+                    .default_value(DEFAULT_REMOTE_NAME)
+                    .action(clap::ArgAction::Set),
+            )
+    }
+
+    async fn run(&self, args: &clap::ArgMatches) -> Result<(), OxenError> {
+        // Parse Args
+        let remote_name = args
+            .get_one::<String>("REMOTE")
+            .map(String::as_str)
+            .unwrap_or(DEFAULT_REMOTE_NAME);
+
+        let repo = LocalRepository::from_current_dir()?;
+        check_repo_migration_needed(&repo)?;
+
+        let remote = repo
+            .get_remote(remote_name)
+            .ok_or(OxenError::remote_not_set(remote_name))?;
+        let remote_repo = api::remote::repositories::get_by_remote(&remote)
+            .await?
+            .ok_or(OxenError::remote_repo_not_found(&remote.url))?;
+
+        let stats = api::remote::repositories::stats(&remote_repo).await?;
+
+        // Fri, 21 Oct 2022 16:08:39 -0700
+        let format = format_description::parse(
+            "[weekday], [day] [month repr:long] [year] [hour]:[minute]:[second] [offset_hour sign:mandatory]",
+        ).unwrap();
+        let last_updated = match stats.last_updated {
+            Some(timestamp) => timestamp.format(&format).unwrap(),
+            None => String::from("never"),
+        };
+
+        println!("Remote:       {} {}", remote.name, remote.url);
+        println!("Size:         {}", ByteSize::b(stats.data_size));
+        println!("Commits:      {}", stats.num_commits);
+        println!("Branches:     {}", stats.num_branches);
+        println!("Last updated: {last_updated}");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+use async_trait::async_trait;
+use clap::{Arg, Command};
+
+use liboxen::api;
+use liboxen::error::OxenError;
+use liboxen::model::LocalRepository;
+use liboxen::view::merge::MergeResult;
+
+use crate::cmd::RunCmd;
+use crate::helpers::check_repo_migration_needed;
+
+pub const NAME: &str = "merge";
+pub struct RemoteMergeCmd;
+
+#[async_trait]
+impl RunCmd for RemoteMergeCmd {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn args(&self) -> Command {
+        // Setups the CLI args for the command
+        Command::new(NAME)
+            .about(
+                "Merge the head branch into the base branch on the remote, without pulling either.",
+            )
+            .arg(
+                Arg::new("BASE")
+                    .help("The branch or commit to merge into.")
+                    .required(true)
+                    .action(clap::ArgAction::Set),
+            )
+            .arg(
+                Arg::new("HEAD")
+                    .help("The branch or commit to merge.")
+                    .required(true)
+                    .action(clap::ArgAction::Set),
+            )
+    }
+
+    async fn run(&self, args: &clap::ArgMatches) -> Result<(), OxenError> {
+        // Parse Args
+        let (Some(base), Some(head)) = (
+            args.get_one::<String>("BASE"),
+            args.get_one::<String>("HEAD"),
+        ) else {
+            return Err(OxenError::basic_str(
+                "Err: Usage `oxen remote merge <base> <head>`",
+            ));
+        };
+
+        let repo = LocalRepository::from_current_dir()?;
+        check_repo_migration_needed(&repo)?;
+
+        let remote_repo = api::remote::repositories::get_default_remote(&repo).await?;
+        match api::remote::merger::merge(&remote_repo, base, head).await? {
+            MergeResult::Merged(commit) => {
+                println!("Merged {head} into {base}");
+                println!("{commit}");
+                Ok(())
+            }
+            MergeResult::Conflicts(conflicts) => {
+                let paths = conflicts
+                    .iter()
+                    .map(|conflict| format!("  {}", conflict.path))
+                    .collect::<Vec<String>>()
+                    .join("\n");
+                Err(OxenError::basic_str(format!(
+                    "Could not merge {head} into {base}, {} conflicts:\n{paths}",
+                    conflicts.len()
+                )))
+            }
+        }
+    }
+}
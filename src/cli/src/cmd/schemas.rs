@@ -15,6 +15,9 @@ pub use add::SchemasAddCmd;
 pub mod name;
 pub use name::SchemasNameCmd;
 
+pub mod history;
+pub use history::SchemasHistoryCmd;
+
 pub mod list;
 pub use list::SchemasListCmd;
 
@@ -48,7 +51,7 @@ impl RunCmd for SchemasCmd {
         ;
 
         // These are all the subcommands for the schemas command
-        // including `add`, `name`, `list`, and `rm`
+        // including `add`, `history`, `name`, `list`, and `rm`
         let sub_commands = self.get_subcommands();
         for cmd in sub_commands.values() {
             command = command.subcommand(cmd.args());
@@ -94,6 +97,7 @@ impl SchemasCmd {
     fn get_subcommands(&self) -> HashMap<String, Box<dyn RunCmd>> {
         let commands: Vec<Box<dyn RunCmd>> = vec![
             Box::new(SchemasAddCmd),
+            Box::new(SchemasHistoryCmd),
             Box::new(SchemasListCmd),
             Box::new(SchemasNameCmd),
         ];
@@ -0,0 +1,73 @@
+use async_trait::async_trait;
+use clap::{Arg, Command};
+use colored::Colorize;
+use std::path::PathBuf;
+
+use liboxen::command;
+use liboxen::error::OxenError;
+use liboxen::model::LocalRepository;
+use liboxen::util;
+
+use crate::cmd::RunCmd;
+pub const NAME: &str = "history";
+
+pub struct SchemasHistoryCmd;
+
+#[async_trait]
+impl RunCmd for SchemasHistoryCmd {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn args(&self) -> Command {
+        // Setups the CLI args for the command
+        Command::new(NAME)
+            .about("List the commits that changed the schema of a file, most recent first.")
+            .arg(
+                Arg::new("PATH")
+                    .help("Path of the tabular file to show the schema history for.")
+                    .required(true)
+                    .action(clap::ArgAction::Set),
+            )
+    }
+
+    async fn run(&self, args: &clap::ArgMatches) -> Result<(), OxenError> {
+        // Parse Args
+        let path = args.get_one::<String>("PATH").expect("Must supply a path");
+
+        let repository = LocalRepository::from_current_dir()?;
+        let path = PathBuf::from(path);
+        let path = if path.is_absolute() {
+            util::fs::path_relative_to_dir(&path, &repository.path)?
+        } else {
+            path
+        };
+
+        let changes = command::schemas::history(&repository, &path)?;
+        if changes.is_empty() {
+            eprintln!("No schema history for {path:?}");
+            return Ok(());
+        }
+
+        for change in changes {
+            println!(
+                "{} {}",
+                format!("commit {}", change.commit.id).yellow(),
+                change.commit.message
+            );
+            match &change.schema {
+                Some(schema) => println!("schema {}", schema.hash),
+                None => println!("schema removed"),
+            }
+            for field in change.diff.added.iter() {
+                println!("{}", format!("  + {}: {}", field.name, field.dtype).green());
+            }
+            for field in change.diff.removed.iter() {
+                println!("{}", format!("  - {}: {}", field.name, field.dtype).red());
+            }
+            println!();
+        }
+
+        Ok(())
+    }
+}
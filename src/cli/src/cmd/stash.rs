@@ -0,0 +1,63 @@
+use async_trait::async_trait;
+use clap::{Arg, Command};
+
+use liboxen::command;
+use liboxen::error::OxenError;
+use liboxen::model::LocalRepository;
+
+use crate::cmd::RunCmd;
+use crate::helpers::check_repo_migration_needed;
+
+pub const NAME: &str = "stash";
+pub struct StashCmd;
+
+#[async_trait]
+impl RunCmd for StashCmd {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn args(&self) -> Command {
+        // Setups the CLI args for the command
+        Command::new(NAME)
+            .about("Save your uncommitted changes away and clean the working directory.")
+            .arg(
+                Arg::new("message")
+                    .help("A message to describe the stashed changes.")
+                    .long("message")
+                    .short('m')
+                    .action(clap::ArgAction::Set),
+            )
+            .subcommand(
+                Command::new("pop")
+                    .about("Reapply the most recent stash and remove it from the stash list."),
+            )
+            .subcommand(Command::new("list").about("List the stashed changes."))
+    }
+
+    async fn run(&self, args: &clap::ArgMatches) -> Result<(), OxenError> {
+        let repo = LocalRepository::from_current_dir()?;
+        check_repo_migration_needed(&repo)?;
+
+        match args.subcommand() {
+            Some(("pop", _)) => {
+                command::stash::pop(&repo)?;
+            }
+            Some(("list", _)) => {
+                let entries = command::stash::list(&repo)?;
+                for (i, entry) in entries.iter().enumerate() {
+                    println!("stash@{{{i}}}: {entry}");
+                }
+            }
+            Some((cmd, _)) => {
+                return Err(OxenError::basic_str(format!("Unknown subcommand {cmd}")));
+            }
+            None => {
+                let message = args.get_one::<String>("message").map(|m| m.as_str());
+                command::stash::stash(&repo, message)?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,61 @@
+use async_trait::async_trait;
+use clap::{Arg, Command};
+
+use liboxen::command;
+use liboxen::error::OxenError;
+use liboxen::model::LocalRepository;
+
+use crate::cmd::RunCmd;
+use crate::helpers::check_repo_migration_needed;
+
+pub const NAME: &str = "verify";
+pub struct VerifyCmd;
+
+#[async_trait]
+impl RunCmd for VerifyCmd {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn args(&self) -> Command {
+        // Setups the CLI args for the command
+        Command::new(NAME)
+            .about("Check that every commit has intact parents and version files.")
+            .arg(
+                Arg::new("fix")
+                    .help("Re-download missing or corrupt version files from the remote.")
+                    .long("fix")
+                    .action(clap::ArgAction::SetTrue),
+            )
+    }
+
+    async fn run(&self, args: &clap::ArgMatches) -> Result<(), OxenError> {
+        let repo = LocalRepository::from_current_dir()?;
+        check_repo_migration_needed(&repo)?;
+
+        let fix = args.get_flag("fix");
+        let report = command::verify(&repo, fix).await?;
+
+        for issue in report.fixed.iter() {
+            println!("fixed: {issue}");
+        }
+        for issue in report.issues.iter() {
+            println!("{issue}");
+        }
+
+        if !report.is_ok() {
+            return Err(OxenError::basic_str(format!(
+                "Found {} problems in {} commits and {} entries",
+                report.issues.len(),
+                report.num_commits,
+                report.num_entries
+            )));
+        }
+
+        println!(
+            "Verified {} commits and {} entries",
+            report.num_commits, report.num_entries
+        );
+        Ok(())
+    }
+}
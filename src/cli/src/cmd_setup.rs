@@ -10,6 +10,8 @@ use crate::cmd::add::add_args;
 use crate::cmd::df::DFCmd;
 use crate::cmd::remote::commit::RemoteCommitCmd;
 use crate::cmd::remote::df::RemoteDfCmd;
+use crate::cmd::remote::info::RemoteInfoCmd;
+use crate::cmd::remote::merge::RemoteMergeCmd;
 
 pub const ADD: &str = "add";
 pub const BRANCH: &str = "branch";
@@ -55,16 +57,34 @@ pub fn remote() -> Command {
                 .short('p')
                 .help("Specify a path in which to add the file to. Will strip down the path to the file's basename, and add in this directory.")
                 .action(clap::ArgAction::Set))
+                .arg(Arg::new("chunked")
+                .long("chunked")
+                .help("Upload the file in parts, retrying each part on failure. Useful for large files.")
+                .action(clap::ArgAction::SetTrue))
+                .arg(Arg::new("force")
+                .long("force")
+                .short('f')
+                .help("Stage the file even if another identifier has staged changes to the same path.")
+                .action(clap::ArgAction::SetTrue))
         )
         .subcommand(RemoteCommitCmd.args())
         .subcommand(RemoteDfCmd.args())
         .subcommand(diff())
         .subcommand(download())
+        .subcommand(RemoteInfoCmd.args())
         .subcommand(log())
         .subcommand(ls())
+        .subcommand(RemoteMergeCmd.args())
         .subcommand(restore())
         .subcommand(rm())
-        .subcommand(status())
+        .subcommand(
+            status().arg(
+                Arg::new("all-staged")
+                    .long("all-staged")
+                    .help("List every identifier with staged changes on the current branch.")
+                    .action(clap::ArgAction::SetTrue),
+            ),
+        )
         .subcommand(metadata())
         .arg(
             Arg::new("verbose")
@@ -122,6 +142,13 @@ pub fn info() -> Command {
                 .help("If present, will print the metadata info as json.")
                 .action(clap::ArgAction::SetTrue),
         )
+        .arg(
+            Arg::new("recursive")
+                .long("recursive")
+                .short('r')
+                .help("If present, will summarize the total size, file count, and data types of all the files in a directory.")
+                .action(clap::ArgAction::SetTrue),
+        )
 }
 
 pub fn metadata() -> Command {
@@ -148,9 +175,18 @@ pub fn metadata() -> Command {
 }
 
 pub fn log() -> Command {
-    Command::new(LOG).about("See log of commits").arg(
-        arg!([REVISION] "The commit or branch id you want to get history from. Defaults to main."),
-    )
+    Command::new(LOG)
+        .about("See log of commits")
+        .arg(
+            arg!([REVISION] "The commit or branch id you want to get history from. Defaults to main."),
+        )
+        .arg(arg!([PATH] "Only show the commits that added, modified or removed this path. If the revision is not a branch or commit, it is used as the path."))
+        .arg(
+            Arg::new("graph")
+                .long("graph")
+                .help("Draw an ASCII graph of the branch and merge history.")
+                .action(clap::ArgAction::SetTrue),
+        )
 }
 
 pub fn fetch() -> Command {
@@ -184,6 +220,12 @@ pub fn ls() -> Command {
                 .help("Commit id or branch name to list from")
                 .action(clap::ArgAction::Set),
         )
+        .arg(
+            Arg::new("long")
+                .short('l')
+                .help("Use a long listing format with sizes, data types, and the commit that last modified each entry")
+                .action(clap::ArgAction::SetTrue),
+        )
         .arg(
             Arg::new("page")
                 .long("page")
@@ -365,6 +407,15 @@ pub fn rm() -> Command {
                 .help("Removes the file from the staging area.")
                 .action(clap::ArgAction::SetTrue),
         )
+        .arg(
+            Arg::new("cached")
+                .long("cached")
+                .help(
+                    "Stages the removal of a committed file but keeps it in the working directory.",
+                )
+                .conflicts_with("staged")
+                .action(clap::ArgAction::SetTrue),
+        )
         .arg(
             Arg::new("recursive")
                 .long("recursive")
@@ -391,6 +442,17 @@ pub fn restore() -> Command {
                 .help("Restore content in staging area. By default, if --staged is given, the contents are restored from HEAD. Use --source to restore from a different commit.")
                 .action(clap::ArgAction::SetTrue),
         )
+        .arg(
+            Arg::new("patch")
+                .long("patch")
+                .short('p')
+                .help("Interactively pick which hunks of a text file to restore. Pass a comma separated list of hunk indices, ie --patch=0,2, to restore them without prompting.")
+                .num_args(0..=1)
+                .require_equals(true)
+                .value_delimiter(',')
+                .value_parser(clap::value_parser!(usize))
+                .action(clap::ArgAction::Append),
+        )
 }
 
 pub fn merge() -> Command {
@@ -457,6 +519,33 @@ pub fn push() -> Command {
                 .help("Remove the remote branch")
                 .action(clap::ArgAction::SetTrue),
         )
+        .arg(
+            Arg::new("set-upstream")
+                .long("set-upstream")
+                .short('u')
+                .help("Track the remote branch so that a bare `oxen push` or `oxen pull` uses it")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("wait")
+                .long("wait")
+                .help("Wait until the server has finished processing the pushed commit, exits with an error if processing fails")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("only-unsynced")
+                .long("only-unsynced")
+                .help("Before pushing, list the commits that are missing on the remote and will be transferred")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("progress")
+                .long("progress")
+                .help("How to report progress: bar, plain, or json. json writes newline delimited events to stderr")
+                .value_parser(["bar", "plain", "json"])
+                .default_value("bar")
+                .action(clap::ArgAction::Set),
+        )
 }
 
 pub fn pull() -> Command {
@@ -480,6 +569,14 @@ pub fn pull() -> Command {
                 .help("This pulls the full commit history, all the data files, and all the commit databases. Useful if you want to have the entire history locally or push to a new remote.")
                 .action(clap::ArgAction::SetTrue),
         )
+        .arg(
+            Arg::new("progress")
+                .long("progress")
+                .help("How to report progress: bar, plain, or json. json writes newline delimited events to stderr")
+                .value_parser(["bar", "plain", "json"])
+                .default_value("bar")
+                .action(clap::ArgAction::Set),
+        )
 }
 
 pub fn diff() -> Command {
@@ -534,6 +631,20 @@ pub fn commit_cache() -> Command {
                 .help("Force recompute the cache even if it already exists.")
                 .action(clap::ArgAction::SetTrue),
         )
+        .arg(
+            Arg::new("fail-fast")
+                .long("fail-fast")
+                .help("With --all, stop at the first repository that fails. By default the other repositories are still computed, and the command exits nonzero at the end if any failed.")
+                .requires("all")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("since-commit")
+                .long("since-commit")
+                .help("Only compute the cache for commits newer than this commit id.")
+                .conflicts_with("all")
+                .action(clap::ArgAction::Set),
+        )
         .arg(arg!([REVISION] "The commit or branch id you want to compute the cache for. Defaults to main."))
 }
 
@@ -763,18 +874,26 @@ pub fn save() -> Command {
         )
         .arg(
             Arg::new("output")
-                .help("Name of the output .tar.gz archive")
+                .help("Name of the output archive")
                 .short('o')
                 .long("output")
                 .required(true),
         )
+        .arg(
+            Arg::new("compress")
+                .help("How to compress the archive, `none` skips compression for data that is already compressed")
+                .long("compress")
+                .value_parser(["none", "gzip", "zstd"])
+                .default_value("gzip")
+                .action(clap::ArgAction::Set),
+        )
 }
 
 pub fn load() -> Command {
     Command::new(LOAD)
-            .about("Load a repository backup from a .tar.gz archive")
+            .about("Load a repository backup from a .tar, .tar.gz, or .tar.zst archive")
             .arg(Arg::new("SRC_PATH")
-                .help("Path to the .tar.gz archive to load")
+                .help("Path to the archive to load, the compression is detected automatically")
                 .required(true)
                 .index(1))
             .arg(Arg::new("DEST_PATH")
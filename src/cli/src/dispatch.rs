@@ -1,12 +1,14 @@
 use liboxen::api;
 use liboxen::command;
 use liboxen::config::UserConfig;
+use liboxen::constants;
 use liboxen::error;
 use liboxen::error::OxenError;
 use liboxen::model::file::FileNew;
 use liboxen::model::schema;
 use liboxen::model::EntryDataType;
 use liboxen::model::LocalRepository;
+use liboxen::model::RemoteBranch;
 use liboxen::model::RepoNew;
 use liboxen::opts::AddOpts;
 use liboxen::opts::DFOpts;
@@ -19,6 +21,7 @@ use liboxen::opts::RestoreOpts;
 use liboxen::opts::RmOpts;
 use liboxen::opts::UploadOpts;
 use liboxen::util;
+use liboxen::util::compression::ArchiveCompression;
 use liboxen::view::PaginatedDirEntries;
 
 use colored::Colorize;
@@ -29,6 +32,7 @@ use std::env;
 use std::fmt::Write;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::time::Duration;
 
 use crate::helpers::{
     check_remote_version, check_remote_version_blocking, check_repo_migration_needed,
@@ -301,7 +305,7 @@ pub async fn add(opts: AddOpts) -> Result<(), OxenError> {
         if opts.is_remote {
             command::remote::add(&repository, path, &opts).await?;
         } else {
-            command::add(&repository, path)?;
+            command::add_with_opts(&repository, path, &opts)?;
         }
     }
 
@@ -335,20 +339,107 @@ pub async fn restore(opts: RestoreOpts) -> Result<(), OxenError> {
     Ok(())
 }
 
-pub async fn push(remote: &str, branch: &str) -> Result<(), OxenError> {
+/// Restore hunks of a text file. Prompts for each hunk unless the indices are given.
+pub fn restore_patch(opts: RestoreOpts, indices: Option<Vec<usize>>) -> Result<(), OxenError> {
     let repo_dir = env::current_dir().unwrap();
     let repository = LocalRepository::from_dir(&repo_dir)?;
+
+    check_repo_migration_needed(&repository)?;
+    let indices = match indices {
+        Some(indices) => indices,
+        None => prompt_for_hunks(&repository, &opts)?,
+    };
+
+    if indices.is_empty() {
+        println!("No hunks selected, {:?} is unchanged", opts.path);
+        return Ok(());
+    }
+
+    command::restore::restore_hunks(&repository, &opts, &indices)?;
+    Ok(())
+}
+
+fn prompt_for_hunks(repo: &LocalRepository, opts: &RestoreOpts) -> Result<Vec<usize>, OxenError> {
+    let hunks = command::restore::list_hunks(repo, opts)?;
+    let mut selected: Vec<usize> = vec![];
+    for hunk in hunks.iter() {
+        println!("{hunk}");
+        print!(
+            "Restore this hunk [{}/{}] (y,n,q)? ",
+            hunk.index + 1,
+            hunks.len()
+        );
+        std::io::Write::flush(&mut std::io::stdout())?;
+
+        let mut answer = String::new();
+        std::io::stdin().read_line(&mut answer)?;
+        match answer.trim() {
+            "y" | "Y" => selected.push(hunk.index),
+            "q" | "Q" => break,
+            _ => {}
+        }
+    }
+    Ok(selected)
+}
+
+pub async fn push(
+    remote: &str,
+    branch: &str,
+    set_upstream: bool,
+    explicit: bool,
+    wait: bool,
+    only_unsynced: bool,
+) -> Result<(), OxenError> {
+    let repo_dir = env::current_dir().unwrap();
+    let mut repository = LocalRepository::from_dir(&repo_dir)?;
     let host = get_host_from_repo(&repository)?;
 
     check_repo_migration_needed(&repository)?;
     check_remote_version_blocking(host.clone()).await?;
     check_remote_version(host).await?;
 
-    command::push_remote_branch(&repository, remote, branch).await?;
+    let remote_branch = resolve_remote_branch(&repository, remote, branch, explicit)?;
+    if only_unsynced {
+        let commits = command::push::unsynced_commits(
+            &repository,
+            &remote_branch.remote,
+            &remote_branch.branch,
+        )
+        .await?;
+        command::push::write_unsynced_commits(&mut std::io::stdout(), &commits)?;
+    }
+    let branch = if set_upstream {
+        command::push_set_upstream(
+            &mut repository,
+            &remote_branch.remote,
+            &remote_branch.branch,
+        )
+        .await?
+    } else {
+        command::push_remote_branch(&repository, &remote_branch.remote, &remote_branch.branch)
+            .await?
+    };
+
+    if wait {
+        println!(
+            "Waiting for the server to process commit {}",
+            branch.commit_id
+        );
+        let timeout = Duration::from_secs(constants::DEFAULT_CACHE_WAIT_TIMEOUT_SECS);
+        command::push::wait_for_remote_cache(&repository, &remote_branch.remote, &branch, timeout)
+            .await?;
+        println!("Server finished processing commit {}", branch.commit_id);
+    }
     Ok(())
 }
 
-pub async fn pull(remote: &str, branch: &str, all: bool) -> Result<(), OxenError> {
+pub async fn pull(
+    remote: &str,
+    branch: &str,
+    all: bool,
+    explicit_remote: bool,
+    explicit_branch: bool,
+) -> Result<(), OxenError> {
     let repo_dir = env::current_dir().unwrap();
     let repository = LocalRepository::from_dir(&repo_dir)?;
 
@@ -357,10 +448,45 @@ pub async fn pull(remote: &str, branch: &str, all: bool) -> Result<(), OxenError
     check_remote_version_blocking(host.clone()).await?;
     check_remote_version(host).await?;
 
-    command::pull_remote_branch(&repository, remote, branch, all).await?;
+    // A missing branch is the upstream of the current branch, or the remote's default branch
+    let remote_branch = match (explicit_remote, explicit_branch) {
+        (_, true) => RemoteBranch {
+            remote: remote.to_string(),
+            branch: branch.to_string(),
+        },
+        (true, false) => command::pull::remote_default_branch(&repository, remote).await?,
+        (false, false) => command::pull::upstream_or_default(&repository).await?,
+    };
+    command::pull_remote_branch(
+        &repository,
+        &remote_branch.remote,
+        &remote_branch.branch,
+        all,
+    )
+    .await?;
     Ok(())
 }
 
+/// Fall back to the upstream of the current branch if the remote and branch were not passed in
+fn resolve_remote_branch(
+    repository: &LocalRepository,
+    remote: &str,
+    branch: &str,
+    explicit: bool,
+) -> Result<RemoteBranch, OxenError> {
+    if !explicit {
+        if let Some(current) = api::local::branches::current_branch(repository)? {
+            if let Some(upstream) = repository.upstream(&current.name) {
+                return Ok(upstream);
+            }
+        }
+    }
+    Ok(RemoteBranch {
+        remote: remote.to_string(),
+        branch: branch.to_string(),
+    })
+}
+
 pub async fn unlock_branch(remote: &str, branch: &str) -> Result<(), OxenError> {
     let repo_dir = env::current_dir().unwrap();
     let repository = LocalRepository::from_dir(&repo_dir)?;
@@ -406,6 +532,25 @@ pub async fn log_commits(opts: LogOpts) -> Result<(), OxenError> {
         util::fs::get_repo_root(&current_dir).ok_or(OxenError::basic_str(error::NO_REPO_FOUND))?;
     let repository = LocalRepository::from_dir(&repo_dir)?;
 
+    let mut opts = opts;
+    if !opts.remote {
+        // `oxen log <path>`, the single argument is a path if it is not a revision
+        if opts.path.is_none() {
+            if let Some(revision) = &opts.revision {
+                if api::local::revisions::get(&repository, revision)?.is_none() {
+                    opts.path = Some(PathBuf::from(revision));
+                    opts.revision = None;
+                }
+            }
+        }
+        if let Some(path) = &opts.path {
+            opts.path = Some(util::fs::path_relative_to_dir(
+                current_dir.join(path),
+                &repository.path,
+            )?);
+        }
+    }
+
     let commits = api::local::commits::list_with_opts(&repository, &opts).await?;
 
     // Fri, 21 Oct 2022 16:08:39 -0700
@@ -415,15 +560,21 @@ pub async fn log_commits(opts: LogOpts) -> Result<(), OxenError> {
 
     let mut output = Pager::new();
 
-    for commit in commits {
-        let commit_id_str = format!("commit {}", commit.id).yellow();
-        write_to_pager(&mut output, &format!("{}\n", commit_id_str))?;
-        write_to_pager(&mut output, &format!("Author: {}", commit.author))?;
-        write_to_pager(
-            &mut output,
-            &format!("Date:   {}\n", commit.timestamp.format(&format).unwrap()),
-        )?;
-        write_to_pager(&mut output, &format!("    {}\n", commit.message))?;
+    if opts.graph {
+        for line in util::commit_graph::render(&commits) {
+            write_to_pager(&mut output, &line)?;
+        }
+    } else {
+        for commit in commits {
+            let commit_id_str = format!("commit {}", commit.id).yellow();
+            write_to_pager(&mut output, &format!("{}\n", commit_id_str))?;
+            write_to_pager(&mut output, &format!("Author: {}", commit.author))?;
+            write_to_pager(
+                &mut output,
+                &format!("Date:   {}\n", commit.timestamp.format(&format).unwrap()),
+            )?;
+            write_to_pager(&mut output, &format!("    {}\n", commit.message))?;
+        }
     }
 
     match minus::page_all(output) {
@@ -442,6 +593,11 @@ pub fn info(opts: InfoOpts) -> Result<(), OxenError> {
     let repo_dir =
         util::fs::get_repo_root(&current_dir).ok_or(OxenError::basic_str(error::NO_REPO_FOUND))?;
     let repository = LocalRepository::from_dir(&repo_dir)?;
+
+    if opts.recursive {
+        return info_recursive(&repository, opts);
+    }
+
     let metadata = command::info(&repository, opts.to_owned())?;
 
     if opts.output_as_json {
@@ -474,6 +630,38 @@ pub fn info(opts: InfoOpts) -> Result<(), OxenError> {
     Ok(())
 }
 
+fn info_recursive(repository: &LocalRepository, opts: InfoOpts) -> Result<(), OxenError> {
+    let output_as_json = opts.output_as_json;
+    let verbose = opts.verbose;
+    let metadata = command::info::info_recursive(repository, opts)?;
+
+    if output_as_json {
+        let json = serde_json::to_string(&metadata)?;
+        println!("{}", json);
+    } else {
+        /*
+        dirname size num_files data_types
+        */
+        if verbose {
+            println!("dirname\tsize\tnum_files\tdata_types");
+        }
+
+        let data_types = metadata
+            .data_types
+            .iter()
+            .map(|t| format!("{}:{}", t.data_type, t.count))
+            .collect::<Vec<String>>()
+            .join(",");
+
+        println!(
+            "{}\t{}\t{}\t{}",
+            metadata.dirname, metadata.size, metadata.num_files, data_types
+        );
+    }
+
+    Ok(())
+}
+
 pub async fn remote_ls(opts: &ListOpts) -> Result<(), OxenError> {
     let paths = &opts.paths;
     if paths.is_empty() {
@@ -532,7 +720,9 @@ pub async fn remote_ls(opts: &ListOpts) -> Result<(), OxenError> {
     maybe_display_types(&entries);
 
     for entry in entries.entries {
-        if entry.is_dir {
+        if opts.long {
+            println!("  {}", command::remote::ls::long_format(&entry));
+        } else if entry.is_dir {
             println!("  {}/", entry.filename);
         } else {
             println!("  {}", entry.filename);
@@ -644,13 +834,17 @@ pub fn inspect(path: &Path) -> Result<(), OxenError> {
     command::db_inspect::inspect(path)
 }
 
-pub fn save(repo_path: &Path, output_path: &Path) -> Result<(), OxenError> {
+pub fn save(
+    repo_path: &Path,
+    output_path: &Path,
+    compression: ArchiveCompression,
+) -> Result<(), OxenError> {
     let repo_path = Path::new(repo_path);
     let repo_dir =
         util::fs::get_repo_root(repo_path).ok_or(OxenError::basic_str(error::NO_REPO_FOUND))?;
     let repo = LocalRepository::from_dir(&repo_dir)?;
 
-    command::save(&repo, output_path)?;
+    command::save_with_compression(&repo, output_path, compression)?;
 
     Ok(())
 }
@@ -24,8 +24,15 @@ async fn main() {
         Box::new(cmd::CommitCmd),
         Box::new(cmd::CreateRemoteCmd),
         Box::new(cmd::DFCmd),
+        Box::new(cmd::DiffCmd),
+        Box::new(cmd::ExportCmd),
         Box::new(cmd::InitCmd),
+        Box::new(cmd::MaterializeCmd),
+        Box::new(cmd::MvCmd),
+        Box::new(cmd::ReflogCmd),
         Box::new(cmd::SchemasCmd),
+        Box::new(cmd::StashCmd),
+        Box::new(cmd::VerifyCmd),
     ];
 
     let mut command = Command::new("oxen")
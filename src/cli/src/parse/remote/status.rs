@@ -5,6 +5,13 @@ use crate::run;
 use std::path::PathBuf;
 
 pub async fn status(sub_matches: &ArgMatches) {
+    if sub_matches.get_flag("all-staged") {
+        if let Err(err) = run::remote::status::all_staged_status().await {
+            eprintln!("{err}");
+        }
+        return;
+    }
+
     let directory = sub_matches.get_one::<String>("path").map(PathBuf::from);
 
     let is_remote = true;
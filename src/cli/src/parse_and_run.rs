@@ -8,9 +8,12 @@ use crate::cmd;
 use crate::cmd::remote::commit::RemoteCommitCmd;
 use crate::cmd::BranchCmd;
 use crate::cmd::RunCmd;
-use crate::cmd_setup::{ADD, COMMIT, DF, DIFF, DOWNLOAD, LOG, LS, METADATA, RESTORE, RM, STATUS};
+use crate::cmd_setup::{
+    ADD, COMMIT, DF, DIFF, DOWNLOAD, INFO, LOG, LS, MERGE, METADATA, RESTORE, RM, STATUS,
+};
 use crate::dispatch;
 
+use clap::parser::ValueSource;
 use clap::ArgMatches;
 use liboxen::command::migrate::{
     AddDirectoriesToCacheMigration, CacheDataFrameSizeMigration, CreateMerkleTreesMigration,
@@ -22,6 +25,8 @@ use liboxen::model::EntryDataType;
 use liboxen::model::LocalRepository;
 use liboxen::opts::{AddOpts, DownloadOpts, InfoOpts, ListOpts, LogOpts, RmOpts, UploadOpts};
 use liboxen::util;
+use liboxen::util::compression::ArchiveCompression;
+use liboxen::util::progress_bar::ProgressMode;
 use liboxen::{command, opts::RestoreOpts};
 use std::path::{Path, PathBuf};
 
@@ -77,6 +82,24 @@ pub async fn remote(sub_matches: &ArgMatches) {
             (LS, sub_matches) => {
                 remote_ls(sub_matches).await;
             }
+            (INFO, sub_matches) => {
+                let cmd = cmd::remote::RemoteInfoCmd {};
+                match cmd.run(sub_matches).await {
+                    Ok(_) => {}
+                    Err(err) => {
+                        eprintln!("{err}")
+                    }
+                }
+            }
+            (MERGE, sub_matches) => {
+                let cmd = cmd::remote::RemoteMergeCmd {};
+                match cmd.run(sub_matches).await {
+                    Ok(_) => {}
+                    Err(err) => {
+                        eprintln!("{err}")
+                    }
+                }
+            }
             (METADATA, sub_matches) => match remote_metadata(sub_matches).await {
                 Ok(_) => {}
                 Err(err) => {
@@ -231,6 +254,9 @@ async fn remote_add(sub_matches: &ArgMatches) {
         paths,
         is_remote: true,
         directory: sub_matches.get_one::<String>("path").map(PathBuf::from),
+        excludes: cmd::add::parse_excludes(sub_matches),
+        chunked: sub_matches.get_flag("chunked"),
+        force: sub_matches.get_flag("force"),
     };
     match dispatch::add(opts).await {
         Ok(_) => {}
@@ -369,6 +395,7 @@ async fn remote_ls(sub_matches: &ArgMatches) {
             .expect("Must supply page-size")
             .parse::<usize>()
             .expect("page-size must be a valid integer."),
+        long: sub_matches.get_flag("long"),
     };
 
     match dispatch::remote_ls(&opts).await {
@@ -391,12 +418,14 @@ pub fn info(sub_matches: &ArgMatches) {
     let path = path.unwrap();
     let verbose = sub_matches.get_flag("verbose");
     let output_as_json = sub_matches.get_flag("json");
+    let recursive = sub_matches.get_flag("recursive");
 
     let opts = InfoOpts {
         path,
         revision,
         verbose,
         output_as_json,
+        recursive,
     };
 
     match dispatch::info(opts) {
@@ -413,6 +442,8 @@ async fn remote_log(sub_matches: &ArgMatches) {
     let opts = LogOpts {
         revision,
         remote: true,
+        graph: sub_matches.get_flag("graph"),
+        path: None,
     };
     match dispatch::log_commits(opts).await {
         Ok(_) => {}
@@ -425,9 +456,13 @@ async fn remote_log(sub_matches: &ArgMatches) {
 pub async fn log(sub_matches: &ArgMatches) {
     let revision = sub_matches.get_one::<String>("REVISION").map(String::from);
 
+    let path = sub_matches.get_one::<String>("PATH").map(PathBuf::from);
+
     let opts = LogOpts {
         revision,
         remote: false,
+        graph: sub_matches.get_flag("graph"),
+        path,
     };
     match dispatch::log_commits(opts).await {
         Ok(_) => {}
@@ -457,6 +492,9 @@ pub async fn add(sub_matches: &ArgMatches) {
         paths,
         is_remote: false,
         directory: None,
+        excludes: cmd::add::parse_excludes(sub_matches),
+        chunked: false,
+        force: false,
     };
     match dispatch::add(opts).await {
         Ok(_) => {}
@@ -477,6 +515,7 @@ pub async fn remote_rm(sub_matches: &ArgMatches) {
         // The path will get overwritten for each file that is removed
         path: paths.first().unwrap().to_path_buf(),
         staged: sub_matches.get_flag("staged"),
+        cached: sub_matches.get_flag("cached"),
         recursive: sub_matches.get_flag("recursive"),
         remote: true,
     };
@@ -500,6 +539,7 @@ pub async fn rm(sub_matches: &ArgMatches) {
         // The path will get overwritten for each file that is removed
         path: paths.first().unwrap().to_path_buf(),
         staged: sub_matches.get_flag("staged"),
+        cached: sub_matches.get_flag("cached"),
         recursive: sub_matches.get_flag("recursive"),
         remote: false,
     };
@@ -523,7 +563,16 @@ pub async fn remote_restore(sub_matches: &ArgMatches) {
         source_ref: None,
     };
 
-    match dispatch::restore(opts).await {
+    let result = if sub_matches.contains_id("patch") {
+        let indices: Option<Vec<usize>> = sub_matches
+            .get_many::<usize>("patch")
+            .map(|indices| indices.copied().collect());
+        dispatch::restore_patch(opts, indices)
+    } else {
+        dispatch::restore(opts).await
+    };
+
+    match result {
         Ok(_) => {}
         Err(err) => {
             eprintln!("{err}")
@@ -550,7 +599,16 @@ pub async fn restore(sub_matches: &ArgMatches) {
         }
     };
 
-    match dispatch::restore(opts).await {
+    let result = if sub_matches.contains_id("patch") {
+        let indices: Option<Vec<usize>> = sub_matches
+            .get_many::<usize>("patch")
+            .map(|indices| indices.copied().collect());
+        dispatch::restore_patch(opts, indices)
+    } else {
+        dispatch::restore(opts).await
+    };
+
+    match result {
         Ok(_) => {}
         Err(err) => {
             eprintln!("{err}")
@@ -587,7 +645,12 @@ pub async fn push(sub_matches: &ArgMatches) {
             .await
             .expect("Could not delete remote branch");
     } else {
-        match dispatch::push(remote, branch).await {
+        set_progress_mode(sub_matches);
+        let set_upstream = sub_matches.get_flag("set-upstream");
+        let explicit = is_explicit_remote_branch(sub_matches);
+        let wait = sub_matches.get_flag("wait");
+        let only_unsynced = sub_matches.get_flag("only-unsynced");
+        match dispatch::push(remote, branch, set_upstream, explicit, wait, only_unsynced).await {
             Ok(_) => {}
             Err(err) => {
                 eprintln!("{err}")
@@ -604,8 +667,11 @@ pub async fn pull(sub_matches: &ArgMatches) {
         .get_one::<String>("BRANCH")
         .expect("Must supply a branch");
 
+    set_progress_mode(sub_matches);
     let all = sub_matches.get_flag("all");
-    match dispatch::pull(remote, branch, all).await {
+    let explicit_remote = sub_matches.value_source("REMOTE") == Some(ValueSource::CommandLine);
+    let explicit_branch = sub_matches.value_source("BRANCH") == Some(ValueSource::CommandLine);
+    match dispatch::pull(remote, branch, all, explicit_remote, explicit_branch).await {
         Ok(_) => {}
         Err(err) => {
             eprintln!("{err}")
@@ -613,6 +679,25 @@ pub async fn pull(sub_matches: &ArgMatches) {
     }
 }
 
+/// Report push and pull progress the way --progress asked for
+fn set_progress_mode(sub_matches: &ArgMatches) {
+    let mode = sub_matches
+        .get_one::<String>("progress")
+        .map(|mode| {
+            mode.parse()
+                .expect("--progress must be bar, plain, or json")
+        })
+        .unwrap_or(ProgressMode::Bar);
+    util::progress_bar::set_progress_mode(mode);
+}
+
+/// Whether the user passed REMOTE or BRANCH, rather than relying on the defaults
+fn is_explicit_remote_branch(sub_matches: &ArgMatches) -> bool {
+    ["REMOTE", "BRANCH"]
+        .iter()
+        .any(|id| sub_matches.value_source(id) == Some(ValueSource::CommandLine))
+}
+
 pub async fn compute_commit_cache(sub_matches: &ArgMatches) {
     let path_str = sub_matches.get_one::<String>("PATH").expect("required");
     let path = Path::new(path_str);
@@ -620,15 +705,49 @@ pub async fn compute_commit_cache(sub_matches: &ArgMatches) {
     let force = sub_matches.get_flag("force");
 
     if sub_matches.get_flag("all") {
-        match command::commit_cache::compute_cache_on_all_repos(path, force).await {
-            Ok(_) => {}
+        let fail_fast = sub_matches.get_flag("fail-fast");
+        match command::commit_cache::compute_cache_on_all_repos(path, force, fail_fast).await {
+            Ok(summary) => {
+                println!(
+                    "Computed cache for {} repositories, {} failed",
+                    summary.succeeded.len(),
+                    summary.failed.len()
+                );
+                for (repo_path, err) in summary.failed.iter() {
+                    println!("Failed {repo_path:?}: {err}");
+                }
+                if summary.has_failures() {
+                    std::process::exit(1);
+                }
+            }
             Err(err) => {
-                println!("Err: {err}")
+                println!("Err: {err}");
+                std::process::exit(1);
             }
         }
     } else {
         let revision = sub_matches.get_one::<String>("REVISION").map(String::from);
 
+        if let Some(since_commit) = sub_matches.get_one::<String>("since-commit") {
+            let result = LocalRepository::new(path).and_then(|repo| {
+                command::commit_cache::compute_cache_since_commit(
+                    &repo,
+                    since_commit,
+                    revision,
+                    force,
+                )
+            });
+            match result {
+                Ok(commits) => {
+                    println!("Computed cache for {} commits", commits.len())
+                }
+                Err(err) => {
+                    println!("Err: {err}")
+                }
+            }
+            return;
+        }
+
         match LocalRepository::new(path) {
             Ok(repo) => match command::commit_cache::compute_cache(&repo, revision, force).await {
                 Ok(_) => {}
@@ -754,11 +873,21 @@ pub async fn save(sub_matches: &ArgMatches) {
     // Match on the PATH arg
     let repo_str = sub_matches.get_one::<String>("PATH").expect("Required");
     let output_str = sub_matches.get_one::<String>("output").expect("Required");
+    let compress_str = sub_matches
+        .get_one::<String>("compress")
+        .expect("Must supply compression");
 
     let repo_path = Path::new(repo_str);
     let output_path = Path::new(output_str);
+    let compression: ArchiveCompression = match compress_str.parse() {
+        Ok(compression) => compression,
+        Err(err) => {
+            eprintln!("{err}");
+            return;
+        }
+    };
 
-    dispatch::save(repo_path, output_path).expect("Error saving repo backup.");
+    dispatch::save(repo_path, output_path, compression).expect("Error saving repo backup.");
 }
 
 pub async fn load(sub_matches: &ArgMatches) {
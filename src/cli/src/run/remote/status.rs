@@ -8,6 +8,8 @@ use liboxen::model::staged_data::StagedDataOpts;
 use liboxen::model::LocalRepository;
 use liboxen::util;
 
+use colored::Colorize;
+
 use crate::helpers::{check_remote_version, check_remote_version_blocking, get_host_from_repo};
 
 pub async fn remote_status(
@@ -50,3 +52,44 @@ pub async fn remote_status(
 
     Ok(())
 }
+
+pub async fn all_staged_status() -> Result<(), OxenError> {
+    let repo_dir = util::fs::get_repo_root_from_current_dir()
+        .ok_or(OxenError::basic_str(error::NO_REPO_FOUND))?;
+
+    let repository = LocalRepository::from_dir(&repo_dir)?;
+    let host = get_host_from_repo(&repository)?;
+    check_remote_version_blocking(host.clone()).await?;
+    check_remote_version(host).await?;
+
+    let Some(current_branch) = api::local::branches::current_branch(&repository)? else {
+        return Err(OxenError::basic_str(
+            "You cannot query remote status unless you are on a branch.",
+        ));
+    };
+
+    let remote_repo = api::remote::repositories::get_default_remote(&repository).await?;
+    let statuses = api::remote::staging::status_all(&remote_repo, &current_branch.name).await?;
+    if statuses.is_empty() {
+        println!("Nothing staged on remote branch {}", current_branch.name);
+        return Ok(());
+    }
+
+    println!("Staged on remote branch {}\n", current_branch.name);
+    for status in statuses {
+        println!("{}", status.identifier.bold());
+        for path in status.staged_files.iter() {
+            println!("  {}", path.to_string_lossy().green());
+        }
+        for path in status.modified_files.iter() {
+            println!(
+                "  {} {}",
+                "modified:".yellow(),
+                path.to_string_lossy().yellow()
+            );
+        }
+        println!();
+    }
+
+    Ok(())
+}
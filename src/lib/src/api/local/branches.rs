@@ -6,7 +6,9 @@
 use std::collections::HashSet;
 use std::path::Path;
 
-use crate::constants::{BRANCH_LOCKS_DIR, OXEN_HIDDEN_DIR};
+use time::OffsetDateTime;
+
+use crate::constants::{BRANCH_LOCKS_DIR, DEFAULT_BRANCH_NAME};
 use crate::core::index::{
     CommitEntryReader, CommitReader, CommitWriter, EntryIndexer, RefReader, RefWriter,
 };
@@ -21,6 +23,37 @@ pub fn list(repo: &LocalRepository) -> Result<Vec<Branch>, OxenError> {
     Ok(branches)
 }
 
+/// Timestamp of the commit at the head of the branch
+pub fn last_commit_time(
+    repo: &LocalRepository,
+    branch: &Branch,
+) -> Result<OffsetDateTime, OxenError> {
+    let commit = api::local::commits::get_by_id(repo, &branch.commit_id)?
+        .ok_or(OxenError::commit_id_does_not_exist(&branch.commit_id))?;
+    Ok(commit.timestamp)
+}
+
+/// List all the local branches ordered by the time of their last commit, newest first if
+/// `descending`. Branches with the same time are ordered by name.
+pub fn list_by_last_commit_time(
+    repo: &LocalRepository,
+    descending: bool,
+) -> Result<Vec<Branch>, OxenError> {
+    let mut branches = list(repo)?
+        .into_iter()
+        .map(|branch| Ok((last_commit_time(repo, &branch)?, branch)))
+        .collect::<Result<Vec<(OffsetDateTime, Branch)>, OxenError>>()?;
+    branches.sort_by(|(a_time, a), (b_time, b)| {
+        let order = if descending {
+            b_time.cmp(a_time)
+        } else {
+            a_time.cmp(b_time)
+        };
+        order.then_with(|| a.name.cmp(&b.name))
+    });
+    Ok(branches.into_iter().map(|(_, branch)| branch).collect())
+}
+
 /// Get a branch by name
 pub fn get_by_name(repo: &LocalRepository, name: &str) -> Result<Option<Branch>, OxenError> {
     let ref_reader = RefReader::new(repo)?;
@@ -69,6 +102,21 @@ pub fn current_branch(repo: &LocalRepository) -> Result<Option<Branch>, OxenErro
     Ok(branch)
 }
 
+/// The branch clients use when they do not give one: the branch HEAD points to, then `main`,
+/// then the first branch by name. `None` if the repository does not have any branches yet.
+pub fn default_branch(repo: &LocalRepository) -> Result<Option<Branch>, OxenError> {
+    if let Some(branch) = current_branch(repo)? {
+        return Ok(Some(branch));
+    }
+
+    let mut branches = list(repo)?;
+    if let Some(branch) = branches.iter().find(|b| b.name == DEFAULT_BRANCH_NAME) {
+        return Ok(Some(branch.clone()));
+    }
+    branches.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(branches.into_iter().next())
+}
+
 /// # Create a new branch from the head commit
 /// This creates a new pointer to the current commit with a name,
 /// it does not switch you to this branch, you still must call `checkout_branch`
@@ -90,6 +138,21 @@ pub fn create(repo: &LocalRepository, name: &str, commit_id: &str) -> Result<Bra
     }
 }
 
+/// # Copy a branch
+/// Creates `dst` pointing at the same commit as `src` without checking either out.
+/// Errors if `src` does not exist or `dst` already exists.
+pub fn copy(repo: &LocalRepository, src: &str, dst: &str) -> Result<Branch, OxenError> {
+    let Some(src_branch) = get_by_name(repo, src)? else {
+        return Err(OxenError::local_branch_not_found(src));
+    };
+    if exists(repo, dst)? {
+        return Err(OxenError::basic_str(format!(
+            "Branch already exists: {dst}"
+        )));
+    }
+    create(repo, dst, &src_branch.commit_id)
+}
+
 /// # Create a branch and check it out in one go
 /// This creates a branch with name,
 /// then switches HEAD to point to the branch
@@ -181,7 +244,7 @@ pub async fn set_working_branch(repo: &LocalRepository, name: &str) -> Result<()
 
 pub fn lock(repo: &LocalRepository, name: &str) -> Result<(), OxenError> {
     // Errors if lock exists - to avoid double-request ("is_locked" -> if false "lock")
-    let oxen_dir = repo.path.join(OXEN_HIDDEN_DIR);
+    let oxen_dir = util::fs::oxen_hidden_dir(&repo.path);
     let locks_dir = oxen_dir.join(BRANCH_LOCKS_DIR);
 
     let clean_name = branch_name_no_slashes(name);
@@ -218,7 +281,7 @@ pub fn lock(repo: &LocalRepository, name: &str) -> Result<(), OxenError> {
 
 pub fn is_locked(repo: &LocalRepository, name: &str) -> Result<bool, OxenError> {
     // Get the oxen hidden dir
-    let oxen_dir = repo.path.join(OXEN_HIDDEN_DIR);
+    let oxen_dir = util::fs::oxen_hidden_dir(&repo.path);
     let locks_dir = oxen_dir.join(BRANCH_LOCKS_DIR);
 
     // Create locks dir if not exists
@@ -240,7 +303,7 @@ pub fn is_locked(repo: &LocalRepository, name: &str) -> Result<bool, OxenError>
 
 pub fn read_lock_file(repo: &LocalRepository, name: &str) -> Result<String, OxenError> {
     // Get the oxen hidden dir
-    let oxen_dir = repo.path.join(OXEN_HIDDEN_DIR);
+    let oxen_dir = util::fs::oxen_hidden_dir(&repo.path);
     let locks_dir = oxen_dir.join(BRANCH_LOCKS_DIR);
 
     // Add a file with the branch name to the locks dir
@@ -280,7 +343,7 @@ pub fn latest_synced_commit(repo: &LocalRepository, name: &str) -> Result<Commit
 
 pub fn unlock(repo: &LocalRepository, name: &str) -> Result<(), OxenError> {
     // Get the oxen hidden dir
-    let oxen_dir = repo.path.join(OXEN_HIDDEN_DIR);
+    let oxen_dir = util::fs::oxen_hidden_dir(&repo.path);
     let locks_dir = oxen_dir.join(BRANCH_LOCKS_DIR);
 
     // Add a file with the branch name to the locks dir
@@ -453,6 +516,7 @@ mod tests {
     use crate::constants::DEFAULT_BRANCH_NAME;
     use crate::core;
     use crate::error::OxenError;
+    use crate::model::Branch;
     use crate::test;
     use crate::util;
 
@@ -601,6 +665,35 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_local_copy_branch() -> Result<(), OxenError> {
+        test::run_training_data_repo_test_fully_committed(|repo| {
+            let og_branch = api::local::branches::current_branch(&repo)?.unwrap();
+
+            // Move the source branch ahead of HEAD so the copy is not just HEAD
+            let src = "experiment";
+            api::local::branches::create_from_head(&repo, src)?;
+            let commits = api::local::commits::list(&repo)?;
+            let older_commit = &commits[1];
+            api::local::branches::update(&repo, src, &older_commit.id)?;
+
+            let dst = api::local::branches::copy(&repo, src, "experiment-copy")?;
+            let src_branch = api::local::branches::get_by_name(&repo, src)?.unwrap();
+            assert_eq!(dst.commit_id, src_branch.commit_id);
+            assert_eq!(dst.commit_id, older_commit.id);
+
+            // Nothing was checked out
+            let current = api::local::branches::current_branch(&repo)?.unwrap();
+            assert_eq!(current.name, og_branch.name);
+
+            // Cannot copy onto an existing branch, or from a missing one
+            assert!(api::local::branches::copy(&repo, src, &og_branch.name).is_err());
+            assert!(api::local::branches::copy(&repo, "does-not-exist", "other").is_err());
+
+            Ok(())
+        })
+    }
+
     #[tokio::test]
     async fn test_local_delete_branch() -> Result<(), OxenError> {
         test::run_select_data_repo_test_no_commits_async("labels", |repo| async move {
@@ -625,4 +718,43 @@ mod tests {
         })
         .await
     }
+
+    #[test]
+    fn test_list_by_last_commit_time() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let path = repo.path.join("file.txt");
+            let mut commits = vec![];
+            for i in 0..3 {
+                test::write_txt_file_to_path(&path, &format!("version {i}"))?;
+                command::add(&repo, &path)?;
+                commits.push(command::commit(&repo, &format!("commit {i}"))?);
+            }
+
+            // Branches created newest first, so creation order is not commit order
+            api::local::branches::create(&repo, "middle", &commits[1].id)?;
+            api::local::branches::create(&repo, "oldest", &commits[0].id)?;
+
+            let time = api::local::branches::last_commit_time(
+                &repo,
+                &api::local::branches::get_by_name(&repo, "middle")?.unwrap(),
+            )?;
+            assert_eq!(time, commits[1].timestamp);
+
+            let names = |branches: Vec<Branch>| -> Vec<String> {
+                branches.into_iter().map(|branch| branch.name).collect()
+            };
+            let newest_first = api::local::branches::list_by_last_commit_time(&repo, true)?;
+            assert_eq!(
+                names(newest_first),
+                vec![DEFAULT_BRANCH_NAME, "middle", "oldest"]
+            );
+            let oldest_first = api::local::branches::list_by_last_commit_time(&repo, false)?;
+            assert_eq!(
+                names(oldest_first),
+                vec!["oldest", "middle", DEFAULT_BRANCH_NAME]
+            );
+
+            Ok(())
+        })
+    }
 }
@@ -18,12 +18,12 @@ use crate::error::OxenError;
 use crate::model::{Commit, CommitEntry, LocalRepository, StagedData};
 use crate::opts::LogOpts;
 use crate::util::fs::commit_content_is_valid_path;
-use crate::view::{PaginatedCommits, StatusMessage};
+use crate::view::{CursorPaginatedCommits, PaginatedCommits, StatusMessage};
 use crate::{api, util};
 use rayon::prelude::*;
 use rocksdb::{DBWithThreadMode, MultiThreaded};
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
 /// Iterate over commits and get the one with the latest timestamp
@@ -292,12 +292,35 @@ pub fn list_all_paginated(
     })
 }
 
+/// List a page of all the commits after an opaque cursor, ordered by commit id.
+/// Pass an empty cursor to get the first page.
+pub fn list_all_cursor_paginated(
+    repo: &LocalRepository,
+    cursor: &str,
+    page_size: usize,
+) -> Result<CursorPaginatedCommits, OxenError> {
+    let commits = list_all(repo)?;
+    let (commits, pagination, next_cursor) =
+        util::paginate_with_cursor(commits, cursor, page_size, |c| c.id.to_owned())?;
+    Ok(CursorPaginatedCommits {
+        status: StatusMessage::resource_found(),
+        commits,
+        pagination,
+        next_cursor,
+    })
+}
+
 /// Get commit history given options
 pub async fn list_with_opts(
     repo: &LocalRepository,
     opts: &LogOpts,
 ) -> Result<Vec<Commit>, OxenError> {
     if opts.remote {
+        if opts.path.is_some() {
+            return Err(OxenError::basic_str(
+                "Filtering the remote log by path is not supported",
+            ));
+        }
         let remote_repo = api::remote::repositories::get_default_remote(repo).await?;
         let revision = if let Some(revision) = &opts.revision {
             revision.to_owned()
@@ -316,38 +339,121 @@ pub async fn list_with_opts(
         } else {
             committer.history_from_head()?
         };
-        Ok(commits)
+        match &opts.path {
+            Some(path) => filter_commits_by_path(repo, commits, path),
+            None => Ok(commits),
+        }
+    }
+}
+
+/// Keep the commits where `path` was added, modified or removed compared to their parents.
+/// Like `git log <path>`, a merge is only kept if the path differs from every parent.
+pub fn filter_commits_by_path(
+    repo: &LocalRepository,
+    commits: Vec<Commit>,
+    path: impl AsRef<Path>,
+) -> Result<Vec<Commit>, OxenError> {
+    let path = path.as_ref();
+    let committer = CommitReader::new(repo)?;
+    // Hash of the path in each commit we have looked at, None if it is not in the commit
+    let mut hashes: HashMap<String, Option<String>> = HashMap::new();
+
+    let mut filtered: Vec<Commit> = vec![];
+    for commit in commits {
+        let hash = path_hash_in_commit(repo, &commit, path, &mut hashes)?;
+        let touched = if commit.parent_ids.is_empty() {
+            hash.is_some()
+        } else {
+            let mut differs_from_all = true;
+            for parent_id in commit.parent_ids.iter() {
+                let parent = committer
+                    .get_commit_by_id(parent_id)?
+                    .ok_or(OxenError::commit_id_does_not_exist(parent_id))?;
+                if path_hash_in_commit(repo, &parent, path, &mut hashes)? == hash {
+                    differs_from_all = false;
+                    break;
+                }
+            }
+            differs_from_all
+        };
+
+        if touched {
+            filtered.push(commit);
+        }
     }
+    Ok(filtered)
+}
+
+fn path_hash_in_commit(
+    repo: &LocalRepository,
+    commit: &Commit,
+    path: &Path,
+    hashes: &mut HashMap<String, Option<String>>,
+) -> Result<Option<String>, OxenError> {
+    if let Some(hash) = hashes.get(&commit.id) {
+        return Ok(hash.clone());
+    }
+    let reader = CommitEntryReader::new(repo, commit)?;
+    let hash = reader.get_entry(path)?.map(|entry| entry.hash);
+    hashes.insert(commit.id.clone(), hash.clone());
+    Ok(hash)
+}
+
+/// List the commits after `base` up to and including `head`, newest first.
+/// Both `base` and `head` can be a branch name or commit id.
+pub fn list_between(
+    repo: &LocalRepository,
+    base: &str,
+    head: &str,
+) -> Result<Vec<Commit>, OxenError> {
+    let committer = CommitReader::new(repo)?;
+    let base_commit_id = match api::local::branches::get_commit_id(repo, base)? {
+        Some(branch_commit_id) => branch_commit_id,
+        None => String::from(base),
+    };
+    let head_commit_id = match api::local::branches::get_commit_id(repo, head)? {
+        Some(branch_commit_id) => branch_commit_id,
+        None => String::from(head),
+    };
+    log::debug!(
+        "list_between: base_commit_id: {} head_commit_id: {}",
+        base_commit_id,
+        head_commit_id
+    );
+    match committer.history_from_base_to_head(&base_commit_id, &head_commit_id) {
+        Ok(commits) => Ok(commits),
+        Err(_) => Err(OxenError::local_revision_not_found(format!(
+            "{base}..{head}"
+        ))),
+    }
+}
+
+/// Paginated version of `list_between`
+pub fn list_between_paginated(
+    repo: &LocalRepository,
+    base: &str,
+    head: &str,
+    page_number: usize,
+    page_size: usize,
+) -> Result<PaginatedCommits, OxenError> {
+    let commits = list_between(repo, base, head)?;
+    let (commits, pagination) = util::paginate(commits, page_number, page_size);
+    Ok(PaginatedCommits {
+        status: StatusMessage::resource_found(),
+        commits,
+        pagination,
+    })
 }
 
 /// List the history for a specific branch or commit (revision)
 pub fn list_from(repo: &LocalRepository, revision: &str) -> Result<Vec<Commit>, OxenError> {
     log::debug!("list_from: {}", revision);
-    let committer = CommitReader::new(repo)?;
-    if revision.contains("..") {
+    if let Some((base, head)) = revision.split_once("..") {
         // This is BASE..HEAD format, and we only want to history from BASE to HEAD
-        let split: Vec<&str> = revision.split("..").collect();
-        let base = split[0];
-        let head = split[1];
-        let base_commit_id = match api::local::branches::get_commit_id(repo, base)? {
-            Some(branch_commit_id) => branch_commit_id,
-            None => String::from(base),
-        };
-        let head_commit_id = match api::local::branches::get_commit_id(repo, head)? {
-            Some(branch_commit_id) => branch_commit_id,
-            None => String::from(head),
-        };
-        log::debug!(
-            "list_from: base_commit_id: {} head_commit_id: {}",
-            base_commit_id,
-            head_commit_id
-        );
-        return match committer.history_from_base_to_head(&base_commit_id, &head_commit_id) {
-            Ok(commits) => Ok(commits),
-            Err(_) => Err(OxenError::local_revision_not_found(revision)),
-        };
+        return list_between(repo, base, head);
     }
 
+    let committer = CommitReader::new(repo)?;
     let commit_id = match api::local::branches::get_commit_id(repo, revision)? {
         Some(branch_commit_id) => branch_commit_id,
         None => String::from(revision),
@@ -511,10 +617,14 @@ pub fn merge_objects_dbs(repo_objects_dir: &Path, tmp_objects_dir: &Path) -> Res
 }
 #[cfg(test)]
 mod tests {
+    use std::path::PathBuf;
+
     use crate::api;
     use crate::command;
     use crate::error::OxenError;
+    use crate::opts::LogOpts;
     use crate::test;
+    use crate::util;
 
     #[tokio::test]
     async fn test_commit_history_is_complete() -> Result<(), OxenError> {
@@ -590,4 +700,53 @@ mod tests {
         })
         .await
     }
+
+    #[tokio::test]
+    async fn test_list_with_opts_filters_by_path() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test_async(|repo| async move {
+            let path_a = repo.path.join("a.txt");
+            let path_b = repo.path.join("b.txt");
+
+            test::write_txt_file_to_path(&path_a, "a")?;
+            command::add(&repo, &path_a)?;
+            let add_a = command::commit(&repo, "Adding a")?;
+
+            test::write_txt_file_to_path(&path_b, "b")?;
+            command::add(&repo, &path_b)?;
+            command::commit(&repo, "Adding b")?;
+
+            test::write_txt_file_to_path(&path_a, "a modified")?;
+            command::add(&repo, &path_a)?;
+            let modify_a = command::commit(&repo, "Modifying a")?;
+
+            test::write_txt_file_to_path(&path_b, "b modified")?;
+            command::add(&repo, &path_b)?;
+            command::commit(&repo, "Modifying b")?;
+
+            util::fs::remove_file(&path_a)?;
+            command::add(&repo, &path_a)?;
+            let remove_a = command::commit(&repo, "Removing a")?;
+
+            let opts = LogOpts {
+                revision: None,
+                remote: false,
+                graph: false,
+                path: Some(PathBuf::from("a.txt")),
+            };
+            let commits = api::local::commits::list_with_opts(&repo, &opts).await?;
+            let ids: Vec<String> = commits.into_iter().map(|commit| commit.id).collect();
+            assert_eq!(ids, vec![remove_a.id, modify_a.id, add_a.id]);
+
+            let opts = LogOpts {
+                path: Some(PathBuf::from("b.txt")),
+                ..opts
+            };
+            let commits = api::local::commits::list_with_opts(&repo, &opts).await?;
+            let messages: Vec<String> = commits.into_iter().map(|commit| commit.message).collect();
+            assert_eq!(messages, vec!["Modifying b", "Adding b"]);
+
+            Ok(())
+        })
+        .await
+    }
 }
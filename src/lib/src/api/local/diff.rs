@@ -27,8 +27,8 @@ use crate::model::{
 use crate::{api, constants, util};
 
 use crate::core::index::CommitEntryReader;
-use polars::prelude::DataFrame;
 use polars::prelude::IntoLazy;
+use polars::prelude::{BooleanChunked, ChunkFull, DataFrame, IntoSeries, StringChunked};
 
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
@@ -42,6 +42,8 @@ use crate::model::diff::DiffResult;
 
 use crate::opts::DFOpts;
 
+pub mod binary_diff;
+pub mod image_diff;
 pub mod join_diff;
 pub mod utf8_diff;
 
@@ -49,6 +51,10 @@ const TARGETS_HASH_COL: &str = "_targets_hash";
 const KEYS_HASH_COL: &str = "_keys_hash";
 const DUPES_PATH: &str = "dupes.json";
 
+fn is_files_image(file_1: impl AsRef<Path>, file_2: impl AsRef<Path>) -> bool {
+    util::fs::is_image(file_1.as_ref()) && util::fs::is_image(file_2.as_ref())
+}
+
 fn is_files_tabular(file_1: impl AsRef<Path>, file_2: impl AsRef<Path>) -> bool {
     util::fs::is_tabular(file_1.as_ref()) && util::fs::is_tabular(file_2.as_ref())
 }
@@ -56,16 +62,23 @@ fn is_files_utf8(file_1: impl AsRef<Path>, file_2: impl AsRef<Path>) -> bool {
     util::fs::is_utf8(file_1.as_ref()) && util::fs::is_utf8(file_2.as_ref())
 }
 
+/// Diff two files on disk. For tabular files, `null_equals_null` controls whether two rows
+/// with null values in the same key columns are joined together. It defaults to SQL
+/// semantics where null never equals null, so rows with null keys show up as removed and added.
 pub fn diff_files(
     file_1: impl AsRef<Path>,
     file_2: impl AsRef<Path>,
     keys: Vec<String>,
     targets: Vec<String>,
     display: Vec<String>,
+    null_equals_null: bool,
 ) -> Result<DiffResult, OxenError> {
     if is_files_tabular(&file_1, &file_2) {
-        let result = tabular(file_1, file_2, keys, targets, display)?;
+        let result = tabular(file_1, file_2, keys, targets, display, null_equals_null)?;
         Ok(result)
+    } else if is_files_image(&file_1, &file_2) {
+        let result = image_diff::diff(file_1, file_2)?;
+        Ok(DiffResult::Image(result))
     } else if is_files_utf8(&file_1, &file_2) {
         let result = utf8_diff::diff(file_1, file_2)?;
         Ok(DiffResult::Text(result))
@@ -78,12 +91,38 @@ pub fn diff_files(
     }
 }
 
+/// Diff two files of `repo`, where `path_1` and `path_2` are the repo relative paths used to
+/// look up `.oxenattributes`. Paths marked binary or -diff are only compared by size and hash.
+#[allow(clippy::too_many_arguments)]
+pub fn diff_repo_files(
+    repo: &LocalRepository,
+    path_1: impl AsRef<Path>,
+    path_2: impl AsRef<Path>,
+    file_1: impl AsRef<Path>,
+    file_2: impl AsRef<Path>,
+    keys: Vec<String>,
+    targets: Vec<String>,
+    display: Vec<String>,
+    null_equals_null: bool,
+) -> Result<DiffResult, OxenError> {
+    let attributes = index::oxenattributes::load(repo);
+    if !attributes.for_path(path_1).should_diff_contents()
+        || !attributes.for_path(path_2).should_diff_contents()
+    {
+        let result = binary_diff::diff(file_1, file_2)?;
+        return Ok(DiffResult::Binary(result));
+    }
+
+    diff_files(file_1, file_2, keys, targets, display, null_equals_null)
+}
+
 pub fn tabular(
     file_1: impl AsRef<Path>,
     file_2: impl AsRef<Path>,
     keys: Vec<String>,
     targets: Vec<String>,
     display: Vec<String>,
+    null_equals_null: bool,
 ) -> Result<DiffResult, OxenError> {
     let df_1 = tabular::read_df(file_1, DFOpts::empty())?;
     let df_2 = tabular::read_df(file_2, DFOpts::empty())?;
@@ -93,7 +132,7 @@ pub fn tabular(
 
     validate_required_fields(schema_1, schema_2, keys.clone(), targets.clone())?;
 
-    diff_dfs(&df_1, &df_2, keys, targets, display)
+    diff_dfs(&df_1, &df_2, keys, targets, display, null_equals_null)
 }
 
 fn validate_required_fields(
@@ -121,22 +160,30 @@ fn validate_required_fields(
     Ok(())
 }
 
+/// If the keys are not specified, every unchanged column is used as a key to match whole rows,
+/// in which case nulls always match each other regardless of `null_equals_null`.
 pub fn diff_dfs(
     df_1: &DataFrame,
     df_2: &DataFrame,
     keys: Vec<String>,
     targets: Vec<String>,
     display: Vec<String>,
+    null_equals_null: bool,
 ) -> Result<DiffResult, OxenError> {
     let schema_diff = get_schema_diff(df_1, df_2);
 
+    let null_equals_null = null_equals_null || keys.is_empty();
     let (keys, targets) = get_keys_targets_smart_defaults(keys, targets, &schema_diff)?;
     let display = get_display_smart_defaults(&keys, &targets, display, &schema_diff);
 
     log::debug!("df_1 is {:?}", df_1);
     log::debug!("df_2 is {:?}", df_2);
 
-    let (df_1, df_2) = hash_dfs(df_1.clone(), df_2.clone(), &keys, &targets)?;
+    let (mut df_1, mut df_2) = hash_dfs(df_1.clone(), df_2.clone(), &keys, &targets)?;
+    if !null_equals_null {
+        df_1 = unmatch_null_keys(df_1, &keys, "left")?;
+        df_2 = unmatch_null_keys(df_2, &keys, "right")?;
+    }
 
     let compare = join_diff::diff(&df_1, &df_2, schema_diff, &keys, &targets, &display)?;
 
@@ -325,6 +372,32 @@ fn hash_dfs(
     Ok((left_df, right_df))
 }
 
+/// Give rows with a null in any of the key columns a key hash that cannot match any other row
+fn unmatch_null_keys(
+    mut df: DataFrame,
+    keys: &[String],
+    side: &str,
+) -> Result<DataFrame, OxenError> {
+    let mut has_null = BooleanChunked::full("", false, df.height());
+    for key in keys.iter() {
+        has_null = &has_null | &df.column(key)?.is_null();
+    }
+
+    let hashes: StringChunked = df
+        .column(KEYS_HASH_COL)?
+        .str()?
+        .into_iter()
+        .zip(has_null.into_iter())
+        .enumerate()
+        .map(|(i, (hash, is_null))| match is_null {
+            Some(true) => Some(format!("null.{side}.{i}")),
+            _ => hash.map(String::from),
+        })
+        .collect();
+    df.with_column(hashes.into_series().with_name(KEYS_HASH_COL))?;
+    Ok(df)
+}
+
 pub fn count_added_rows(base_df: DataFrame, head_df: DataFrame) -> Result<usize, OxenError> {
     // Hash the rows
     let base_df = tabular::df_hash_rows(base_df)?;
@@ -1235,6 +1308,47 @@ pub fn list_diff_entries(
     })
 }
 
+/// List the files that were moved between two commits as (old path, new path) pairs.
+/// A file counts as moved when it was removed from one path and added at another with
+/// the exact same content hash, which is what `oxen mv` stages.
+pub fn list_moved_entries(
+    repo: &LocalRepository,
+    base_commit: &Commit,
+    head_commit: &Commit,
+) -> Result<Vec<(PathBuf, PathBuf)>, OxenError> {
+    let object_reader = ObjectDBReader::new(repo)?;
+    let base_reader =
+        CommitEntryReader::new_from_commit_id(repo, &base_commit.id, object_reader.clone())?;
+    let head_reader =
+        CommitEntryReader::new_from_commit_id(repo, &head_commit.id, object_reader.clone())?;
+
+    let base_entries = base_reader.list_entries_set()?;
+    let head_entries = head_reader.list_entries_set()?;
+
+    let mut removed_by_hash: HashMap<String, Vec<PathBuf>> = HashMap::new();
+    for base_entry in base_entries.difference(&head_entries) {
+        removed_by_hash
+            .entry(base_entry.hash.to_owned())
+            .or_default()
+            .push(base_entry.path.to_owned());
+    }
+
+    let mut added: Vec<&CommitEntry> = head_entries.difference(&base_entries).collect();
+    added.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let mut moved: Vec<(PathBuf, PathBuf)> = vec![];
+    for head_entry in added {
+        if let Some(removed_paths) = removed_by_hash.get_mut(&head_entry.hash) {
+            if let Some(removed_path) = removed_paths.pop() {
+                moved.push((removed_path, head_entry.path.to_owned()));
+            }
+        }
+    }
+    log::debug!("list_moved_entries found {} moved files", moved.len());
+
+    Ok(moved)
+}
+
 // Find the directories that are in HEAD but not in BASE
 fn collect_added_directories(
     repo: &LocalRepository,
@@ -0,0 +1,21 @@
+use crate::error::OxenError;
+use crate::model::diff::binary_diff::{BinaryDiff, BinarySummary};
+use crate::util;
+
+use std::path::Path;
+
+/// Compare the size and hash of two files without looking at their contents
+pub fn diff(file_1: impl AsRef<Path>, file_2: impl AsRef<Path>) -> Result<BinaryDiff, OxenError> {
+    Ok(BinaryDiff {
+        left: summarize(file_1.as_ref())?,
+        right: summarize(file_2.as_ref())?,
+    })
+}
+
+fn summarize(path: &Path) -> Result<BinarySummary, OxenError> {
+    Ok(BinarySummary {
+        path: path.to_path_buf(),
+        num_bytes: std::fs::metadata(path)?.len(),
+        hash: util::hasher::hash_file_contents(path)?,
+    })
+}
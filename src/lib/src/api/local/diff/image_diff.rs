@@ -0,0 +1,58 @@
+use crate::error::OxenError;
+use crate::model::diff::image_diff::{ImageDiff, ImageSummary};
+use crate::util;
+
+use image::io::Reader as ImageReader;
+use image::RgbaImage;
+use std::path::Path;
+
+/// Compare the dimensions, format, file size and pixels of two images
+pub fn diff(file_1: impl AsRef<Path>, file_2: impl AsRef<Path>) -> Result<ImageDiff, OxenError> {
+    Ok(ImageDiff {
+        left: summarize(file_1.as_ref())?,
+        right: summarize(file_2.as_ref())?,
+    })
+}
+
+/// Write the two images of the diff next to each other, left on the left, into `output`
+pub fn write_composite(diff: &ImageDiff, output: impl AsRef<Path>) -> Result<(), OxenError> {
+    let left = ImageReader::open(&diff.left.path)?
+        .with_guessed_format()?
+        .decode()?;
+    let right = ImageReader::open(&diff.right.path)?
+        .with_guessed_format()?
+        .decode()?;
+
+    let width = left.width() + right.width();
+    let height = left.height().max(right.height());
+    let mut composite = RgbaImage::new(width, height);
+    image::imageops::overlay(&mut composite, &left.to_rgba8(), 0, 0);
+    image::imageops::overlay(&mut composite, &right.to_rgba8(), left.width() as i64, 0);
+
+    let output = output.as_ref();
+    if let Some(parent) = output.parent() {
+        if !parent.as_os_str().is_empty() && !parent.exists() {
+            util::fs::create_dir_all(parent)?;
+        }
+    }
+    composite.save(output)?;
+    Ok(())
+}
+
+fn summarize(path: &Path) -> Result<ImageSummary, OxenError> {
+    let reader = ImageReader::open(path)?.with_guessed_format()?;
+    let format = reader
+        .format()
+        .map(|format| format!("{format:?}").to_lowercase())
+        .unwrap_or_else(|| String::from("unknown"));
+    let img = reader.decode()?;
+
+    Ok(ImageSummary {
+        path: path.to_path_buf(),
+        width: img.width(),
+        height: img.height(),
+        format,
+        num_bytes: std::fs::metadata(path)?.len(),
+        pixel_hash: util::hasher::hash_buffer(img.to_rgba8().as_raw()),
+    })
+}
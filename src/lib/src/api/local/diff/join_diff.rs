@@ -15,7 +15,7 @@ use crate::view::compare::{
 use polars::chunked_array::ops::SortMultipleOptions;
 use polars::datatypes::{AnyValue, StringChunked};
 use polars::lazy::dsl::coalesce;
-use polars::lazy::dsl::{all, as_struct, col, GetOutput};
+use polars::lazy::dsl::{all, as_struct, col, lit, GetOutput};
 use polars::lazy::frame::IntoLazy;
 use polars::prelude::ChunkCompare;
 use polars::prelude::{DataFrame, DataFrameJoinOps};
@@ -25,6 +25,8 @@ use super::{tabular, SchemaDiff};
 
 const TARGETS_HASH_COL: &str = "_targets_hash";
 const KEYS_HASH_COL: &str = "_keys_hash";
+// Marks which side of the join a row came from, since the keys themselves may be null
+const ROW_PRESENT_COL: &str = "_row_present";
 const DIFF_STATUS_COL: &str = ".oxen.diff.status";
 
 const DIFF_STATUS_ADDED: &str = "added";
@@ -67,7 +69,11 @@ pub fn diff(
         schema_diff.clone(),
     )?;
 
-    let joined_df = add_diff_status_column(joined_df, keys.clone(), targets.clone())?;
+    let joined_df = add_diff_status_column(joined_df, targets.clone())?;
+    let joined_df = joined_df.drop_many(&[
+        format!("{}.left", ROW_PRESENT_COL),
+        format!("{}.right", ROW_PRESENT_COL),
+    ]);
 
     let mut joined_df = joined_df.filter(
         &joined_df
@@ -266,9 +272,20 @@ fn join_hashed_dfs(
     targets: Vec<&str>,
     schema_diff: SchemaDiff,
 ) -> Result<DataFrame, OxenError> {
-    let mut joined_df = left_df.outer_join(right_df, [KEYS_HASH_COL], [KEYS_HASH_COL])?;
+    let left_df = left_df
+        .clone()
+        .lazy()
+        .with_column(lit(true).alias(ROW_PRESENT_COL))
+        .collect()?;
+    let right_df = right_df
+        .clone()
+        .lazy()
+        .with_column(lit(true).alias(ROW_PRESENT_COL))
+        .collect()?;
+    let mut joined_df = left_df.outer_join(&right_df, [KEYS_HASH_COL], [KEYS_HASH_COL])?;
 
     let mut cols_to_rename = targets.clone();
+    cols_to_rename.push(ROW_PRESENT_COL);
     for key in keys.iter() {
         cols_to_rename.push(key);
     }
@@ -315,13 +332,12 @@ fn join_hashed_dfs(
 
 fn add_diff_status_column(
     joined_df: DataFrame,
-    keys: Vec<&str>,
     targets: Vec<&str>,
 ) -> Result<DataFrame, OxenError> {
     // Columns required for determining group membership in the closure
     let col_names = [
-        format!("{}.left", keys[0]),
-        format!("{}.right", keys[0]),
+        format!("{}.left", ROW_PRESENT_COL),
+        format!("{}.right", ROW_PRESENT_COL),
         format!("{}.left", TARGETS_HASH_COL),
         format!("{}.right", TARGETS_HASH_COL),
     ];
@@ -350,14 +366,14 @@ fn add_diff_status_column(
                         let out: StringChunked = ca
                             .into_iter()
                             .map(|row| {
-                                let key_left = row.first();
-                                let key_right = row.get(1);
+                                let present_left = row.first();
+                                let present_right = row.get(1);
                                 let target_hash_left = row.get(2);
                                 let target_hash_right = row.get(3);
 
                                 test_function(
-                                    key_left,
-                                    key_right,
+                                    present_left,
+                                    present_right,
                                     target_hash_left,
                                     target_hash_right,
                                     has_targets,
@@ -401,18 +417,18 @@ fn calculate_compare_mods(joined_df: &DataFrame) -> Result<AddRemoveModifyCounts
 }
 
 fn test_function(
-    key_left: Option<&AnyValue>,
-    key_right: Option<&AnyValue>,
+    present_left: Option<&AnyValue>,
+    present_right: Option<&AnyValue>,
     target_hash_left: Option<&AnyValue>,
     target_hash_right: Option<&AnyValue>,
     has_targets: bool,
 ) -> String {
     // TODO better error handling
-    if let Some(AnyValue::Null) = key_left {
+    if let Some(AnyValue::Null) = present_left {
         return DIFF_STATUS_ADDED.to_string();
     }
 
-    if let Some(AnyValue::Null) = key_right {
+    if let Some(AnyValue::Null) = present_right {
         return DIFF_STATUS_REMOVED.to_string();
     }
 
@@ -2,6 +2,7 @@ use crate::error::OxenError;
 use crate::model::diff::change_type::ChangeType;
 use crate::model::diff::text_diff::LineDiff;
 use crate::model::diff::text_diff::TextDiff;
+use crate::model::diff::text_diff::TextHunk;
 use crate::util;
 
 use difference::{Changeset, Difference};
@@ -15,37 +16,84 @@ pub fn diff(
     let version_file_2 = version_file_2.as_ref();
     let original_data = util::fs::read_from_path(version_file_1)?;
     let compare_data = util::fs::read_from_path(version_file_2)?;
-    let Changeset { diffs, .. } = Changeset::new(&original_data, &compare_data, "\n");
 
-    let mut result = TextDiff { lines: vec![] };
-    for diff in diffs {
-        match diff {
-            Difference::Same(ref x) => {
-                for split in x.split('\n') {
-                    result.lines.push(LineDiff {
-                        modification: ChangeType::Unchanged,
-                        text: split.to_string(),
-                    });
-                }
-            }
-            Difference::Add(ref x) => {
-                for split in x.split('\n') {
-                    result.lines.push(LineDiff {
-                        modification: ChangeType::Added,
-                        text: split.to_string(),
-                    });
-                }
-            }
-            Difference::Rem(ref x) => {
-                for split in x.split('\n') {
-                    result.lines.push(LineDiff {
-                        modification: ChangeType::Removed,
-                        text: split.to_string(),
-                    });
-                }
-            }
+    Ok(TextDiff {
+        lines: line_diffs(&original_data, &compare_data),
+    })
+}
+
+/// Group the changed lines between `current` and `source` into hunks, in file order.
+pub fn hunks(current: &str, source: &str) -> Vec<TextHunk> {
+    let mut hunks: Vec<TextHunk> = vec![];
+    let mut hunk: Option<TextHunk> = None;
+    let mut line = 1;
+    for diff in line_diffs(current, source) {
+        if diff.modification == ChangeType::Unchanged {
+            hunks.extend(hunk.take());
+            line += 1;
+            continue;
+        }
+
+        let index = hunks.len();
+        let hunk = hunk.get_or_insert_with(|| TextHunk {
+            index,
+            start: line,
+            current: vec![],
+            source: vec![],
+        });
+        if diff.modification == ChangeType::Removed {
+            hunk.current.push(diff.text);
+            line += 1;
+        } else {
+            hunk.source.push(diff.text);
         }
     }
+    hunks.extend(hunk);
+    hunks
+}
+
+/// Take the lines from `source` for the hunks in `indices`, and keep `current` everywhere else.
+pub fn apply_hunks(current: &str, source: &str, indices: &[usize]) -> String {
+    let mut lines: Vec<String> = vec![];
+    let mut num_hunks = 0;
+    let mut in_hunk = false;
+    for diff in line_diffs(current, source) {
+        if diff.modification == ChangeType::Unchanged {
+            in_hunk = false;
+            lines.push(diff.text);
+            continue;
+        }
 
-    Ok(result)
+        if !in_hunk {
+            in_hunk = true;
+            num_hunks += 1;
+        }
+        let selected = indices.contains(&(num_hunks - 1));
+        match diff.modification {
+            ChangeType::Removed if !selected => lines.push(diff.text),
+            ChangeType::Added if selected => lines.push(diff.text),
+            _ => {}
+        }
+    }
+    lines.join("\n")
+}
+
+fn line_diffs(original_data: &str, compare_data: &str) -> Vec<LineDiff> {
+    let Changeset { diffs, .. } = Changeset::new(original_data, compare_data, "\n");
+
+    let mut lines = vec![];
+    for diff in diffs {
+        let (modification, text) = match diff {
+            Difference::Same(x) => (ChangeType::Unchanged, x),
+            Difference::Add(x) => (ChangeType::Added, x),
+            Difference::Rem(x) => (ChangeType::Removed, x),
+        };
+        for split in text.split('\n') {
+            lines.push(LineDiff {
+                modification,
+                text: split.to_string(),
+            });
+        }
+    }
+    lines
 }
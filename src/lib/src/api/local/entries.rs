@@ -624,7 +624,11 @@ pub fn list_tabular_files_in_repo(
     let commit_reader = CommitReader::new(local_repo)?;
     let commits = commit_reader.list_all()?;
 
-    for (path, _schema) in schemas.iter() {
+    // Schemas come back from a hash map, sort by path so callers can paginate stably
+    let mut paths: Vec<&PathBuf> = schemas.keys().collect();
+    paths.sort();
+
+    for path in paths {
         let entry = entry_reader.get_entry(path)?;
 
         if entry.is_some() {
@@ -1391,6 +1395,65 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_list_tabular_is_sorted_and_pages_stably() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let dir_path = repo.path.join("data").join("train");
+            util::fs::create_dir_all(&dir_path)?;
+
+            for name in [
+                "zebra.csv",
+                "apple.csv",
+                "mango.tsv",
+                "kiwi.csv",
+                "berry.tsv",
+            ] {
+                util::fs::write(dir_path.join(name), "1,2,3\nhello,world,sup\n")?;
+            }
+            util::fs::write(repo.path.join("root.csv"), "1,2,3\nhello,world,sup\n")?;
+            util::fs::write(repo.path.join("notes.txt"), "not tabular\n")?;
+
+            command::add(&repo, &repo.path)?;
+            let commit = command::commit(&repo, "Adding tabular files")?;
+
+            let paths = |entries: Vec<crate::model::MetadataEntry>| -> Vec<String> {
+                entries
+                    .into_iter()
+                    .map(|entry| entry.resource.unwrap().path)
+                    .collect()
+            };
+
+            let first = paths(api::local::entries::list_tabular_files_in_repo(
+                &repo, &commit,
+            )?);
+            assert_eq!(first.len(), 6);
+
+            let mut sorted = first.clone();
+            sorted.sort();
+            assert_eq!(first, sorted);
+
+            for _ in 0..5 {
+                let again = paths(api::local::entries::list_tabular_files_in_repo(
+                    &repo, &commit,
+                )?);
+                assert_eq!(first, again);
+            }
+
+            // Paging through returns every file exactly once
+            let page_size = 4;
+            let mut paged: Vec<String> = vec![];
+            for page in 1..=2 {
+                let entries = api::local::entries::list_tabular_files_in_repo(&repo, &commit)?;
+                let (entries, pagination) = util::paginate(entries, page, page_size);
+                assert_eq!(pagination.total_pages, 2);
+                paged.extend(paths(entries));
+            }
+            assert_eq!(paged, first);
+
+            Ok(())
+        })
+    }
+
     #[test]
     fn test_file_metadata_shows_is_indexed() -> Result<(), OxenError> {
         // skip on windows
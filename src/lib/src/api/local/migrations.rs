@@ -1,11 +1,6 @@
 use std::path::Path;
 
-use crate::{
-    api,
-    constants::{LAST_MIGRATION_FILE, OXEN_HIDDEN_DIR},
-    error::OxenError,
-    view::RepositoryView,
-};
+use crate::{api, constants::LAST_MIGRATION_FILE, error::OxenError, util, view::RepositoryView};
 
 pub fn list_unmigrated(
     data_dir: &Path,
@@ -37,7 +32,8 @@ pub fn list_unmigrated(
         for repo in api::local::repositories::list_repos_in_namespace(&namespace_path) {
             let repo_name = repo.path.file_name().unwrap().to_str().unwrap();
 
-            let repo_last_migration = repo.path.join(OXEN_HIDDEN_DIR).join(LAST_MIGRATION_FILE);
+            let repo_last_migration =
+                util::fs::oxen_hidden_dir(&repo.path).join(LAST_MIGRATION_FILE);
 
             // If no repo-level migration file, the repo is migrated up to the global migration.
             // If we're here, that's out of date.
@@ -50,6 +46,7 @@ pub fn list_unmigrated(
                 result.push(RepositoryView {
                     namespace: namespace.clone(),
                     name: repo_name.to_string(),
+                    default_branch: None,
                 });
             } else if let Ok(repo_last_migration) = std::fs::read_to_string(&repo_last_migration) {
                 if repo_last_migration <= migration_tstamp {
@@ -57,6 +54,7 @@ pub fn list_unmigrated(
                     result.push(RepositoryView {
                         namespace: namespace.clone(),
                         name: repo_name.to_string(),
+                        default_branch: None,
                     });
                 }
             }
@@ -3,6 +3,7 @@ use crate::command;
 use crate::constants;
 use crate::core::cache::commit_cacher;
 use crate::core::index::CommitEntryWriter;
+use crate::core::index::CommitReader;
 use crate::core::index::Stager;
 use crate::core::index::{CommitEntryReader, CommitWriter, RefWriter};
 use crate::error::OxenError;
@@ -16,11 +17,27 @@ use crate::model::{CommitStats, LocalRepository, RepoNew};
 use crate::util;
 use fd_lock::RwLock;
 use jwalk::WalkDir;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs::File;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use time::OffsetDateTime;
 
+/// Commit count and last updated time, keyed by the commit db sequence number they were computed at
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+struct CommitSummary {
+    sequence_number: u64,
+    num_commits: usize,
+    #[serde(with = "time::serde::rfc3339::option")]
+    last_updated: Option<OffsetDateTime>,
+}
+
+fn commit_summary_path(repo: &LocalRepository) -> PathBuf {
+    util::fs::oxen_hidden_dir(&repo.path)
+        .join(constants::CACHE_DIR)
+        .join("commit_summary.json")
+}
+
 pub fn get_by_namespace_and_name(
     sync_dir: &Path,
     namespace: impl AsRef<str>,
@@ -90,10 +107,60 @@ pub fn get_repo_stats(repo: &LocalRepository) -> RepoStats {
         }
     }
 
+    let (num_commits, last_updated) = match get_commit_summary(repo) {
+        Ok(summary) => (summary.num_commits, summary.last_updated),
+        Err(err) => {
+            log::error!("Err: could not count commits for repo stats {err}");
+            (0, None)
+        }
+    };
+
+    let num_branches = match api::local::branches::list(repo) {
+        Ok(branches) => branches.len(),
+        Err(err) => {
+            log::error!("Err: could not count branches for repo stats {err}");
+            0
+        }
+    };
+
     RepoStats {
         data_size,
         data_types,
+        num_commits,
+        num_branches,
+        last_updated,
+    }
+}
+
+/// Reads the commit summary from the cache, only walking the commit db when it has been written to since
+fn get_commit_summary(repo: &LocalRepository) -> Result<CommitSummary, OxenError> {
+    let reader = CommitReader::new(repo)?;
+    let sequence_number = reader.sequence_number();
+    let path = commit_summary_path(repo);
+
+    if path.exists() {
+        match util::fs::read_from_path(&path).and_then(|contents| {
+            serde_json::from_str::<CommitSummary>(&contents).map_err(OxenError::from)
+        }) {
+            Ok(summary) if summary.sequence_number == sequence_number => return Ok(summary),
+            Ok(_) => log::debug!("commit summary cache is stale, recomputing"),
+            Err(err) => log::warn!("could not read commit summary cache {path:?}: {err}"),
+        }
+    }
+
+    let commits = reader.list_all()?;
+    let summary = CommitSummary {
+        sequence_number,
+        num_commits: commits.len(),
+        last_updated: commits.iter().map(|commit| commit.timestamp).max(),
+    };
+
+    if let Some(parent) = path.parent() {
+        util::fs::create_dir_all(parent)?;
     }
+    util::fs::write_to_path(&path, serde_json::to_string(&summary)?)?;
+
+    Ok(summary)
 }
 
 pub fn list_namespaces(sync_dir: &Path) -> Result<Vec<String>, OxenError> {
@@ -231,7 +298,7 @@ pub fn create(root_dir: &Path, new_repo: RepoNew) -> Result<LocalRepository, Oxe
         CommitEntryWriter::create_objects_dbs(&local_repo)?;
     }
 
-    // Create HEAD file and point it to DEFAULT_BRANCH_NAME
+    // Create HEAD file and point it to the requested default branch, or DEFAULT_BRANCH_NAME
 
     {
         // Make go out of scope to release LOCK
@@ -240,7 +307,11 @@ pub fn create(root_dir: &Path, new_repo: RepoNew) -> Result<LocalRepository, Oxe
             local_repo.path
         );
         let ref_writer = RefWriter::new(&local_repo)?;
-        ref_writer.set_head(constants::DEFAULT_BRANCH_NAME);
+        let head = new_repo
+            .default_branch
+            .as_deref()
+            .unwrap_or(constants::DEFAULT_BRANCH_NAME);
+        ref_writer.set_head(head);
         log::debug!(
             "api::local::repositories::create AFTER ref writer: {:?}",
             local_repo.path
@@ -591,4 +662,30 @@ mod tests {
             Ok(())
         })
     }
+
+    #[test]
+    fn test_local_repository_api_repo_stats_cache_updates_on_commit() -> Result<(), OxenError> {
+        test::run_training_data_repo_test_fully_committed(|repo| {
+            let num_commits = api::local::commits::list_all(&repo)?.len();
+            let stats = api::local::repositories::get_repo_stats(&repo);
+            assert_eq!(stats.num_commits, num_commits);
+            assert!(super::commit_summary_path(&repo).exists());
+
+            // Served from the cache the second time around
+            let cached = api::local::repositories::get_repo_stats(&repo);
+            assert_eq!(cached.num_commits, num_commits);
+            assert_eq!(cached.last_updated, stats.last_updated);
+
+            // A new commit invalidates the cache
+            test::write_txt_file_to_path(repo.path.join("new_file.txt"), "new file")?;
+            command::add(&repo, repo.path.join("new_file.txt"))?;
+            let commit = command::commit(&repo, "Adding new file")?;
+
+            let updated = api::local::repositories::get_repo_stats(&repo);
+            assert_eq!(updated.num_commits, num_commits + 1);
+            assert_eq!(updated.last_updated, Some(commit.timestamp));
+
+            Ok(())
+        })
+    }
 }
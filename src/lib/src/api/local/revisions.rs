@@ -2,15 +2,45 @@
 
 use std::path::{Path, PathBuf};
 
+use crate::constants;
 use crate::core::index::{CommitDirEntryReader, ObjectDBReader};
 use crate::error::OxenError;
 use crate::model::{Commit, LocalRepository};
 use crate::{api, util};
 
-/// Get a commit object from a commit id or branch name
+/// Get a commit object from a commit id, branch name or HEAD.
+/// A `~N` suffix walks back N first parents, ie `HEAD~2` or `main~1`.
 /// Returns Ok(None) if the revision does not exist
 pub fn get(repo: &LocalRepository, revision: impl AsRef<str>) -> Result<Option<Commit>, OxenError> {
     let revision = revision.as_ref();
+    // Branch names cannot contain '~', so this never shadows a branch
+    if let Some((base, generations)) = revision.rsplit_once('~') {
+        let generations = if generations.is_empty() {
+            1
+        } else {
+            generations
+                .parse::<usize>()
+                .map_err(|_| OxenError::basic_str(format!("Invalid revision: {revision}")))?
+        };
+        let Some(mut commit) = get(repo, base)? else {
+            return Ok(None);
+        };
+        for _ in 0..generations {
+            let Some(parent_id) = commit.parent_ids.first() else {
+                return Ok(None);
+            };
+            match api::local::commits::get_by_id(repo, parent_id)? {
+                Some(parent) => commit = parent,
+                None => return Ok(None),
+            }
+        }
+        return Ok(Some(commit));
+    }
+
+    if revision == constants::HEAD_FILE {
+        return api::local::commits::head_commit(repo).map(Some);
+    }
+
     if api::local::branches::exists(repo, revision)? {
         let branch = api::local::branches::get_by_name(repo, revision)?;
         let branch = branch.ok_or(OxenError::local_branch_not_found(revision))?;
@@ -5,7 +5,8 @@ use crate::{api, util};
 
 use crate::core::index::SchemaReader;
 use crate::error::OxenError;
-use crate::model::{LocalRepository, Schema};
+use crate::model::diff::tabular_diff::TabularSchemaDiff;
+use crate::model::{LocalRepository, Schema, SchemaChange};
 
 pub fn list(
     repo: &LocalRepository,
@@ -70,6 +71,49 @@ pub fn get_by_path_from_ref(
     }
 }
 
+/// Walk the history from HEAD and list each commit that changed the schema of the file,
+/// most recent first, with the fields added and removed relative to the previous schema
+pub fn history(
+    repo: &LocalRepository,
+    path: impl AsRef<Path>,
+) -> Result<Vec<SchemaChange>, OxenError> {
+    let path = path.as_ref();
+    let mut commits = api::local::commits::list(repo)?;
+    commits.reverse();
+
+    let mut changes: Vec<SchemaChange> = vec![];
+    let mut prev_schema: Option<Schema> = None;
+    for commit in commits {
+        let schema_reader = SchemaReader::new(repo, &commit.id)?;
+        let schema = schema_reader.get_schema_for_file(path)?;
+
+        let prev_hash = prev_schema.as_ref().map(|s| &s.hash);
+        let hash = schema.as_ref().map(|s| &s.hash);
+        if prev_hash == hash {
+            continue;
+        }
+
+        let empty = Schema::empty();
+        let prev = prev_schema.as_ref().unwrap_or(&empty);
+        let current = schema.as_ref().unwrap_or(&empty);
+        let diff = TabularSchemaDiff {
+            added: current.added_fields(prev),
+            removed: current.removed_fields(prev),
+        };
+        log::debug!("schema history {:?} changed in commit {}", path, commit.id);
+
+        prev_schema = schema.clone();
+        changes.push(SchemaChange {
+            commit,
+            schema,
+            diff,
+        });
+    }
+
+    changes.reverse();
+    Ok(changes)
+}
+
 pub fn get_by_hash(repo: &LocalRepository, hash: String) -> Result<Option<Schema>, OxenError> {
     let version_path = util::fs::version_path_from_schema_hash(repo.path.clone(), hash);
     // Read schema from that path
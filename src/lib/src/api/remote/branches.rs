@@ -4,8 +4,9 @@ use crate::error::OxenError;
 use crate::model::{Branch, Commit, LocalRepository, RemoteRepository};
 use crate::view::{
     BranchLockResponse, BranchNewFromExisting, BranchRemoteMerge, BranchResponse, CommitResponse,
-    ListBranchesResponse, StatusMessage,
+    CursorPaginatedBranches, ListBranchesResponse, StatusMessage,
 };
+use futures::stream::{self, Stream, TryStreamExt};
 use serde_json::json;
 
 pub async fn get_by_name(
@@ -76,6 +77,24 @@ pub async fn create_from_or_get(
     }
 }
 
+/// Copy the remote branch `src` to a new remote branch `dst` pointing at the same commit.
+/// Errors if `src` does not exist or `dst` already exists.
+pub async fn copy(
+    repository: &RemoteRepository,
+    src: &str,
+    dst: &str,
+) -> Result<Branch, OxenError> {
+    if get_by_name(repository, src).await?.is_none() {
+        return Err(OxenError::remote_branch_not_found(src));
+    }
+    if get_by_name(repository, dst).await?.is_some() {
+        return Err(OxenError::basic_str(format!(
+            "Remote branch already exists: {dst}"
+        )));
+    }
+    create_from_or_get(repository, dst, src).await
+}
+
 pub async fn list(repository: &RemoteRepository) -> Result<Vec<Branch>, OxenError> {
     let url = api::endpoint::url_from_repo(repository, "/branches")?;
 
@@ -101,6 +120,51 @@ pub async fn list(repository: &RemoteRepository) -> Result<Vec<Branch>, OxenErro
     }
 }
 
+/// Fetch a single page of branches after an opaque cursor, pass an empty cursor for the first page
+pub async fn list_paginated(
+    repository: &RemoteRepository,
+    cursor: &str,
+    page_size: usize,
+) -> Result<CursorPaginatedBranches, OxenError> {
+    let uri = format!("/branches?cursor={cursor}&page_size={page_size}");
+    let url = api::endpoint::url_from_repo(repository, &uri)?;
+
+    let client = client::new_for_url(&url)?;
+    match client.get(&url).send().await {
+        Ok(res) => {
+            let body = client::parse_json_body(&url, res).await?;
+            let response: Result<CursorPaginatedBranches, serde_json::Error> =
+                serde_json::from_str(&body);
+            match response {
+                Ok(j_res) => Ok(j_res),
+                Err(err) => Err(OxenError::basic_str(format!(
+                    "list_paginated() Could not deserialize response [{err}]\n{body}"
+                ))),
+            }
+        }
+        Err(err) => Err(OxenError::basic_str(format!(
+            "list_paginated() Request failed: {err}"
+        ))),
+    }
+}
+
+/// Lazily iterate over all the remote branches, fetching `page_size` branches at a time
+pub fn list_stream(
+    repository: &RemoteRepository,
+    page_size: usize,
+) -> impl Stream<Item = Result<Branch, OxenError>> + '_ {
+    // The state is the cursor for the next page, None once we have fetched the last page
+    stream::try_unfold(Some(String::new()), move |cursor| async move {
+        let Some(cursor) = cursor else {
+            return Ok(None);
+        };
+        let page = list_paginated(repository, &cursor, page_size).await?;
+        Ok(Some((page.branches, page.next_cursor)))
+    })
+    .map_ok(|branches| stream::iter(branches.into_iter().map(Ok)))
+    .try_flatten()
+}
+
 pub async fn update(
     repository: &RemoteRepository,
     branch_name: &str,
@@ -321,9 +385,11 @@ mod tests {
     use crate::constants;
     use crate::constants::DEFAULT_BRANCH_NAME;
     use crate::error::OxenError;
-    use crate::model::NewCommitBody;
+    use crate::model::{Branch, NewCommitBody};
     use crate::test;
 
+    use futures::TryStreamExt;
+
     #[tokio::test]
     async fn test_create_remote_branch() -> Result<(), OxenError> {
         test::run_empty_remote_repo_test(|_local_repo, remote_repo| async move {
@@ -353,6 +419,29 @@ mod tests {
         .await
     }
 
+    #[tokio::test]
+    async fn test_copy_remote_branch() -> Result<(), OxenError> {
+        test::run_empty_remote_repo_test(|_local_repo, remote_repo| async move {
+            let src = "experiment";
+            api::remote::branches::create_from_or_get(&remote_repo, src, DEFAULT_BRANCH_NAME)
+                .await?;
+
+            let dst = api::remote::branches::copy(&remote_repo, src, "experiment-copy").await?;
+            let src_branch = api::remote::branches::get_by_name(&remote_repo, src)
+                .await?
+                .unwrap();
+            assert_eq!(dst.name, "experiment-copy");
+            assert_eq!(dst.commit_id, src_branch.commit_id);
+
+            // The destination exists now
+            let result = api::remote::branches::copy(&remote_repo, src, "experiment-copy").await;
+            assert!(result.is_err());
+
+            Ok(remote_repo)
+        })
+        .await
+    }
+
     #[tokio::test]
     async fn test_get_branch_by_name() -> Result<(), OxenError> {
         test::run_empty_remote_repo_test(|_local_repo, remote_repo| async move {
@@ -396,6 +485,42 @@ mod tests {
         .await
     }
 
+    #[tokio::test]
+    async fn test_list_remote_branches_stream_multiple_pages() -> Result<(), OxenError> {
+        test::run_empty_remote_repo_test(|_local_repo, remote_repo| async move {
+            let num_branches = 25;
+            for i in 0..num_branches {
+                api::remote::branches::create_from_or_get(
+                    &remote_repo,
+                    &format!("branch-{i}"),
+                    DEFAULT_BRANCH_NAME,
+                )
+                .await?;
+            }
+
+            // Small page size so that we have to fetch multiple pages
+            let page_size = 10;
+            let first_page =
+                api::remote::branches::list_paginated(&remote_repo, "", page_size).await?;
+            assert_eq!(first_page.branches.len(), page_size);
+            assert!(first_page.next_cursor.is_some());
+
+            let branches: Vec<Branch> = api::remote::branches::list_stream(&remote_repo, page_size)
+                .try_collect()
+                .await?;
+            // All the branches we created + main
+            assert_eq!(branches.len(), num_branches + 1);
+            for i in 0..num_branches {
+                let name = format!("branch-{i}");
+                assert!(branches.iter().any(|b| b.name == name));
+            }
+            assert!(branches.iter().any(|b| b.name == DEFAULT_BRANCH_NAME));
+
+            Ok(remote_repo)
+        })
+        .await
+    }
+
     #[tokio::test]
     async fn test_delete_branch() -> Result<(), OxenError> {
         test::run_empty_remote_repo_test(|_local_repo, remote_repo| async move {
@@ -732,6 +857,8 @@ mod tests {
                     message: "adding labels file".to_string(),
                     author: "me".to_string(),
                     email: "me&aol.gov".to_string(),
+                    expected_parent_id: None,
+                    idempotency_key: None,
                 },
             )
             .await?;
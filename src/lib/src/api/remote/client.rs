@@ -1,14 +1,55 @@
 use crate::config::AuthConfig;
-use crate::error::OxenError;
+use crate::constants;
+use crate::error::{OxenError, StringError};
 use crate::view::http;
 use crate::view::OxenResponse;
 
 pub use reqwest::Url;
 use reqwest::{header, Client, ClientBuilder, IntoUrl};
+use std::time::Duration;
 
 const VERSION: &str = crate::constants::OXEN_VERSION;
 const USER_AGENT: &str = "Oxen";
 
+/// How long a client waits on the remote before giving up
+#[derive(Clone, Copy, Debug)]
+pub struct ClientTimeouts {
+    pub connect: Duration,
+    // Max time between reads, reset every time data arrives
+    pub read: Duration,
+}
+
+impl Default for ClientTimeouts {
+    fn default() -> Self {
+        ClientTimeouts {
+            connect: Duration::from_secs(constants::DEFAULT_CONNECT_TIMEOUT_SECS),
+            read: Duration::from_secs(constants::DEFAULT_READ_TIMEOUT_SECS),
+        }
+    }
+}
+
+impl ClientTimeouts {
+    /// Defaults, overridden by the OXEN_CONNECT_TIMEOUT_SECS and OXEN_READ_TIMEOUT_SECS environment variables
+    pub fn from_env() -> ClientTimeouts {
+        let defaults = ClientTimeouts::default();
+        ClientTimeouts {
+            connect: timeout_from_env("OXEN_CONNECT_TIMEOUT_SECS").unwrap_or(defaults.connect),
+            read: timeout_from_env("OXEN_READ_TIMEOUT_SECS").unwrap_or(defaults.read),
+        }
+    }
+}
+
+fn timeout_from_env(name: &str) -> Option<Duration> {
+    let secs = std::env::var(name).ok()?;
+    match secs.parse::<u64>() {
+        Ok(secs) => Some(Duration::from_secs(secs)),
+        Err(err) => {
+            log::warn!("Invalid {name}={secs}, using the default: {err}");
+            None
+        }
+    }
+}
+
 pub fn get_host_from_url<U: IntoUrl>(url: U) -> Result<String, OxenError> {
     let parsed_url = url.into_url()?;
     let mut host_str = parsed_url.host_str().unwrap_or_default().to_string();
@@ -22,16 +63,28 @@ pub fn get_host_from_url<U: IntoUrl>(url: U) -> Result<String, OxenError> {
 // new one for each request so we can take advantage of keep-alive
 pub fn new_for_url<U: IntoUrl>(url: U) -> Result<Client, OxenError> {
     let host = get_host_from_url(url)?;
-    new_for_host(host, true)
+    new_for_host(host, true, ClientTimeouts::from_env())
+}
+
+pub fn new_for_url_with_timeouts<U: IntoUrl>(
+    url: U,
+    timeouts: ClientTimeouts,
+) -> Result<Client, OxenError> {
+    let host = get_host_from_url(url)?;
+    new_for_host(host, true, timeouts)
 }
 
 pub fn new_for_url_no_user_agent<U: IntoUrl>(url: U) -> Result<Client, OxenError> {
     let host = get_host_from_url(url)?;
-    new_for_host(host, false)
+    new_for_host(host, false, ClientTimeouts::from_env())
 }
 
-fn new_for_host<S: AsRef<str>>(host: S, should_add_user_agent: bool) -> Result<Client, OxenError> {
-    match builder_for_host(host.as_ref(), should_add_user_agent)?.build() {
+fn new_for_host<S: AsRef<str>>(
+    host: S,
+    should_add_user_agent: bool,
+    timeouts: ClientTimeouts,
+) -> Result<Client, OxenError> {
+    match builder_for_host(host.as_ref(), should_add_user_agent, timeouts)?.build() {
         Ok(client) => Ok(client),
         Err(reqwest_err) => Err(OxenError::HTTP(reqwest_err)),
     }
@@ -39,18 +92,22 @@ fn new_for_host<S: AsRef<str>>(host: S, should_add_user_agent: bool) -> Result<C
 
 pub fn builder_for_url<U: IntoUrl>(url: U) -> Result<ClientBuilder, OxenError> {
     let host = get_host_from_url(url)?;
-    builder_for_host(host, true)
+    builder_for_host(host, true, ClientTimeouts::from_env())
 }
 
 fn builder_for_host<S: AsRef<str>>(
     host: S,
     should_add_user_agent: bool,
+    timeouts: ClientTimeouts,
 ) -> Result<ClientBuilder, OxenError> {
     let builder = if should_add_user_agent {
         builder()
     } else {
         builder_no_user_agent()
     };
+    let builder = builder
+        .connect_timeout(timeouts.connect)
+        .read_timeout(timeouts.read);
 
     let config = match AuthConfig::get() {
         Ok(config) => config,
@@ -171,6 +228,12 @@ fn parse_status_and_message(
         }
         http::STATUS_ERROR => {
             log::debug!("Status error: {status}");
+            if response.status_message == http::MSG_HASH_MISMATCH {
+                return Err(OxenError::HashMismatch(StringError::from(
+                    response.desc_or_msg(),
+                )));
+            }
+
             if let Some(msg) = response_msg_override {
                 if let Some(response_type) = response_type {
                     if response.desc_or_msg() == response_type {
@@ -187,3 +250,43 @@ fn parse_status_and_message(
         status => Err(OxenError::basic_str(format!("Unknown status [{status}]"))),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use crate::api::remote::client::{self, ClientTimeouts};
+    use crate::error::OxenError;
+
+    #[tokio::test]
+    async fn test_client_times_out_on_unresponsive_host() -> Result<(), OxenError> {
+        // Accept connections but never respond
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let server = tokio::spawn(async move {
+            let mut sockets = vec![];
+            while let Ok((socket, _)) = listener.accept().await {
+                sockets.push(socket);
+            }
+        });
+
+        let url = format!("http://{addr}/api/version");
+        let timeouts = ClientTimeouts {
+            connect: Duration::from_secs(1),
+            read: Duration::from_secs(1),
+        };
+        let client = client::new_for_url_with_timeouts(&url, timeouts)?;
+
+        let start = Instant::now();
+        let result = client.get(&url).send().await;
+        let elapsed = start.elapsed();
+        server.abort();
+
+        let err = OxenError::from(result.unwrap_err());
+        assert!(matches!(err, OxenError::RequestTimeout(_)), "{err:?}");
+        assert!(elapsed >= Duration::from_secs(1));
+        assert!(elapsed < Duration::from_secs(5));
+
+        Ok(())
+    }
+}
@@ -3,22 +3,26 @@ use crate::constants::{
     COMMITS_DIR, DEFAULT_PAGE_NUM, DIRS_DIR, DIR_HASHES_DIR, HISTORY_DIR, OBJECTS_DIR, TREE_DIR,
 };
 
+use crate::core::cache::cacher_status::{CacherStatus, CacherStatusType};
 use crate::core::db::{self};
 use crate::core::index::pusher::UnsyncedCommitEntries;
 use crate::core::index::{CommitDBReader, CommitEntryWriter, CommitReader, CommitWriter, Merger};
 use crate::error::OxenError;
 use crate::model::commit::CommitWithBranchName;
-use crate::model::{Branch, Commit, LocalRepository, RemoteRepository};
+use crate::model::{Branch, Commit, CommitEntry, LocalRepository, RemoteRepository};
 use crate::opts::PaginateOpts;
 use crate::util::fs::oxen_hidden_dir;
 use crate::util::hasher::hash_buffer;
 use crate::util::progress_bar::{oxify_bar, ProgressBarType};
-use crate::view::commit::{CommitSyncStatusResponse, CommitTreeValidationResponse};
+use crate::view::commit::{
+    CommitCacheStatusResponse, CommitSyncStatusResponse, CommitTreeValidationResponse,
+};
 use crate::{api, constants};
 use crate::{current_function, util};
 // use crate::util::ReadProgress;
 use crate::view::{
-    CommitResponse, IsValidStatusMessage, ListCommitResponse, PaginatedCommits, StatusMessage,
+    CommitResponse, CursorPaginatedCommits, IsValidStatusMessage, ListCommitResponse,
+    PaginatedCommitEntries, PaginatedCommitEntriesResponse, PaginatedCommits, StatusMessage,
 };
 
 use std::path::{Path, PathBuf};
@@ -31,9 +35,11 @@ use async_tar::Archive;
 use bytesize::ByteSize;
 use flate2::write::GzEncoder;
 use flate2::Compression;
+use futures::stream::{self, Stream};
 use futures_util::TryStreamExt;
 use indicatif::{ProgressBar, ProgressStyle};
 use rocksdb::{DBWithThreadMode, MultiThreaded};
+use tokio::io::AsyncWriteExt;
 
 pub struct ChunkParams {
     pub chunk_num: usize,
@@ -176,6 +182,169 @@ async fn list_commit_history_paginated(
     }
 }
 
+/// Get the path, hash and size of every file in a revision on the remote, sorted by path.
+/// Only the metadata is transferred, none of the file contents.
+pub async fn download_entries_manifest(
+    remote_repo: &RemoteRepository,
+    revision: &str,
+) -> Result<Vec<CommitEntry>, OxenError> {
+    let mut entries: Vec<CommitEntry> = Vec::new();
+    let mut page_num = DEFAULT_PAGE_NUM;
+    let page_size = constants::DEFAULT_PAGE_SIZE;
+
+    loop {
+        let page_opts = PaginateOpts {
+            page_num,
+            page_size,
+        };
+        let page = download_entries_manifest_page(remote_repo, revision, &page_opts).await?;
+        entries.extend(page.entries);
+        if page_num < page.pagination.total_pages {
+            page_num += 1;
+        } else {
+            break;
+        }
+    }
+
+    Ok(entries)
+}
+
+/// One page of [`download_entries_manifest`]
+pub async fn download_entries_manifest_page(
+    remote_repo: &RemoteRepository,
+    revision: &str,
+    page_opts: &PaginateOpts,
+) -> Result<PaginatedCommitEntries, OxenError> {
+    let page_num = page_opts.page_num;
+    let page_size = page_opts.page_size;
+    let uri = format!("/commits/{revision}/manifest?page={page_num}&page_size={page_size}");
+    let url = api::endpoint::url_from_repo(remote_repo, &uri)?;
+
+    let client = client::new_for_url(&url)?;
+    match client.get(&url).send().await {
+        Ok(res) => {
+            let body = client::parse_json_body(&url, res).await?;
+            let response: Result<PaginatedCommitEntriesResponse, serde_json::Error> =
+                serde_json::from_str(&body);
+            match response {
+                Ok(j_res) => Ok(j_res.entries),
+                Err(err) => Err(OxenError::basic_str(format!(
+                    "api::remote::commits::download_entries_manifest() Could not deserialize response [{err}]\n{body}"
+                ))),
+            }
+        }
+        Err(err) => Err(OxenError::basic_str(format!(
+            "api::remote::commits::download_entries_manifest() Request failed: {err}"
+        ))),
+    }
+}
+
+/// List the commits after `base` up to and including `head` on the remote, newest first.
+/// Only the commits in the range are fetched, not the full history.
+pub async fn list_between(
+    remote_repo: &RemoteRepository,
+    base: &str,
+    head: &str,
+) -> Result<Vec<Commit>, OxenError> {
+    let mut all_commits: Vec<Commit> = Vec::new();
+    let mut page_num = DEFAULT_PAGE_NUM;
+    let page_size = 100;
+
+    loop {
+        let page_opts = PaginateOpts {
+            page_num,
+            page_size,
+        };
+        let paginated_commits = list_between_paginated(remote_repo, base, head, &page_opts).await?;
+        all_commits.extend(paginated_commits.commits);
+        if page_num < paginated_commits.pagination.total_pages {
+            page_num += 1;
+        } else {
+            break;
+        }
+    }
+
+    Ok(all_commits)
+}
+
+/// Fetch a single page of the commits after `base` up to and including `head`
+pub async fn list_between_paginated(
+    remote_repo: &RemoteRepository,
+    base: &str,
+    head: &str,
+    page_opts: &PaginateOpts,
+) -> Result<PaginatedCommits, OxenError> {
+    let page_num = page_opts.page_num;
+    let page_size = page_opts.page_size;
+    let base = urlencoding::encode(base);
+    let head = urlencoding::encode(head);
+    let uri =
+        format!("/commits/between?base={base}&head={head}&page={page_num}&page_size={page_size}");
+    let url = api::endpoint::url_from_repo(remote_repo, &uri)?;
+
+    let client = client::new_for_url(&url)?;
+    match client.get(&url).send().await {
+        Ok(res) => {
+            let body = client::parse_json_body(&url, res).await?;
+            let response: Result<PaginatedCommits, serde_json::Error> = serde_json::from_str(&body);
+            match response {
+                Ok(j_res) => Ok(j_res),
+                Err(err) => Err(OxenError::basic_str(format!(
+                    "list_between() Could not deserialize response [{err}]\n{body}"
+                ))),
+            }
+        }
+        Err(err) => Err(OxenError::basic_str(format!(
+            "list_between() Request failed: {err}"
+        ))),
+    }
+}
+
+/// Fetch a single page of all the commits after an opaque cursor, pass an empty cursor for the first page
+pub async fn list_all_cursor_paginated(
+    remote_repo: &RemoteRepository,
+    cursor: &str,
+    page_size: usize,
+) -> Result<CursorPaginatedCommits, OxenError> {
+    let uri = format!("/commits/all?cursor={cursor}&page_size={page_size}");
+    let url = api::endpoint::url_from_repo(remote_repo, &uri)?;
+
+    let client = client::new_for_url(&url)?;
+    match client.get(&url).send().await {
+        Ok(res) => {
+            let body = client::parse_json_body(&url, res).await?;
+            let response: Result<CursorPaginatedCommits, serde_json::Error> =
+                serde_json::from_str(&body);
+            match response {
+                Ok(j_res) => Ok(j_res),
+                Err(err) => Err(OxenError::basic_str(format!(
+                    "list_all_cursor_paginated() Could not deserialize response [{err}]\n{body}"
+                ))),
+            }
+        }
+        Err(err) => Err(OxenError::basic_str(format!(
+            "list_all_cursor_paginated() Request failed: {err}"
+        ))),
+    }
+}
+
+/// Lazily iterate over all the commits in the remote repo, fetching `page_size` commits at a time
+pub fn list_all_stream(
+    remote_repo: &RemoteRepository,
+    page_size: usize,
+) -> impl Stream<Item = Result<Commit, OxenError>> + '_ {
+    // The state is the cursor for the next page, None once we have fetched the last page
+    stream::try_unfold(Some(String::new()), move |cursor| async move {
+        let Some(cursor) = cursor else {
+            return Ok(None);
+        };
+        let page = list_all_cursor_paginated(remote_repo, &cursor, page_size).await?;
+        Ok(Some((page.commits, page.next_cursor)))
+    })
+    .map_ok(|commits| stream::iter(commits.into_iter().map(Ok)))
+    .try_flatten()
+}
+
 async fn list_all_commits_paginated(
     remote_repo: &RemoteRepository,
     page_opts: &PaginateOpts,
@@ -235,6 +404,66 @@ pub async fn commit_is_synced(
     }
 }
 
+/// Status of the cachers the server runs on a commit after it is pushed, `None` if they have not started
+pub async fn cache_status(
+    remote_repo: &RemoteRepository,
+    commit_id: &str,
+) -> Result<Option<CacherStatus>, OxenError> {
+    let uri = format!("/commits/{commit_id}/cache_status");
+    let url = api::endpoint::url_from_repo(remote_repo, &uri)?;
+    log::debug!("cache_status checking URL: {}", url);
+
+    let client = client::new_for_url(&url)?;
+    match client.get(&url).send().await {
+        Ok(res) => {
+            let body = client::parse_json_body(&url, res).await?;
+            log::debug!("cache_status got response body: {}", body);
+            let response: Result<CommitCacheStatusResponse, serde_json::Error> =
+                serde_json::from_str(&body);
+            match response {
+                Ok(response) => Ok(response.cache_status),
+                Err(err) => Err(OxenError::basic_str(format!(
+                    "api::remote::commits::cache_status() Could not deserialize response [{err}]\n{body}"
+                ))),
+            }
+        }
+        Err(err) => Err(OxenError::basic_str(format!(
+            "api::remote::commits::cache_status() Request failed: {err}"
+        ))),
+    }
+}
+
+/// Poll the cache status of a pushed commit until the server finishes caching it.
+/// Errors if caching failed or did not finish within `timeout`.
+pub async fn wait_for_cache(
+    remote_repo: &RemoteRepository,
+    commit_id: &str,
+    timeout: time::Duration,
+) -> Result<CacherStatus, OxenError> {
+    let start = time::Instant::now();
+    let poll_interval = time::Duration::from_millis(constants::CACHE_STATUS_POLL_INTERVAL_MS);
+    loop {
+        match cache_status(remote_repo, commit_id).await? {
+            Some(status) if status.status == CacherStatusType::Success => return Ok(status),
+            Some(status) if status.status == CacherStatusType::Failed => {
+                return Err(OxenError::basic_str(format!(
+                    "Server failed to process commit {commit_id}: {}",
+                    status.status_message
+                )));
+            }
+            status => log::debug!("wait_for_cache commit {commit_id} status {:?}", status),
+        }
+
+        if start.elapsed() >= timeout {
+            return Err(OxenError::basic_str(format!(
+                "Timed out after {}s waiting for the server to process commit {commit_id}",
+                timeout.as_secs()
+            )));
+        }
+        tokio::time::sleep(poll_interval).await;
+    }
+}
+
 pub async fn latest_commit_synced(
     remote_repo: &RemoteRepository,
     commit_id: &str,
@@ -339,6 +568,7 @@ pub async fn can_push(
     remote_branch_name: &str,
     local_repo: &LocalRepository,
     local_head: &Commit,
+    compression: Compression,
 ) -> Result<bool, OxenError> {
     // Before we do this, need to ensure that we are working in the same repo
     // If we don't, downloading the commits db in the next step
@@ -374,7 +604,7 @@ pub async fn can_push(
 
     let tar_base_dir = Path::new("tmp").join(&local_head.id); // Still want to save out in tmp, which is good
 
-    let enc = GzEncoder::new(Vec::new(), Compression::default());
+    let enc = GzEncoder::new(Vec::new(), compression);
     let mut tar = tar::Builder::new(enc);
 
     let tar_path = tar_base_dir.join(TREE_DIR);
@@ -477,6 +707,40 @@ pub async fn download_commit_entries_db_to_repo(
     let hidden_dir = util::fs::oxen_hidden_dir(&local_repo.path);
     download_commit_entries_db_to_path(remote_repo, commit_id, hidden_dir).await
 }
+/// Download the tarball of a single commit to `dst`, load it into a repository with
+/// [`commit_tarball::load`](crate::core::index::commit_tarball::load)
+pub async fn download_commit_tarball(
+    remote_repo: &RemoteRepository,
+    commit_or_branch: &str,
+    dst: impl AsRef<Path>,
+) -> Result<PathBuf, OxenError> {
+    let uri = format!("/commits/{commit_or_branch}/tarball");
+    let url = api::endpoint::url_from_repo(remote_repo, &uri)?;
+    log::debug!("{} downloading from {}", current_function!(), url);
+
+    let client = client::new_for_url(&url)?;
+    let res = client.get(&url).send().await?;
+    if !res.status().is_success() {
+        client::parse_json_body(&url, res).await?;
+        return Err(OxenError::basic_str(format!(
+            "Could not download tarball for commit {commit_or_branch}"
+        )));
+    }
+
+    let dst = dst.as_ref();
+    if let Some(parent) = dst.parent() {
+        util::fs::create_dir_all(parent)?;
+    }
+    let mut file = tokio::fs::File::create(dst).await?;
+    let mut stream = res.bytes_stream();
+    while let Some(chunk) = stream.try_next().await? {
+        file.write_all(&chunk).await?;
+    }
+    file.flush().await?;
+
+    Ok(dst.to_path_buf())
+}
+
 pub async fn download_objects_db_to_path(
     remote_repo: &RemoteRepository,
     dst: impl AsRef<Path>,
@@ -800,12 +1064,13 @@ pub async fn post_tree_objects_to_server(
     local_repo: &LocalRepository,
     remote_repo: &RemoteRepository,
     commit: &Commit,
+    compression: Compression,
 ) -> Result<(), OxenError> {
     let objects_dir = util::fs::oxen_hidden_dir(local_repo.path.clone()).join(OBJECTS_DIR);
 
     let tar_subdir = Path::new(OBJECTS_DIR);
 
-    let enc = GzEncoder::new(Vec::new(), Compression::default());
+    let enc = GzEncoder::new(Vec::new(), compression);
     let mut tar = tar::Builder::new(enc);
 
     log::debug!(
@@ -840,6 +1105,7 @@ pub async fn post_commit_db_to_server(
     local_repo: &LocalRepository,
     remote_repo: &RemoteRepository,
     commit: &Commit,
+    compression: Compression,
 ) -> Result<(), OxenError> {
     let commit_dir = util::fs::oxen_hidden_dir(&local_repo.path)
         .join(HISTORY_DIR)
@@ -848,7 +1114,7 @@ pub async fn post_commit_db_to_server(
     // This will be the subdir within the tarball
     let tar_subdir = Path::new(HISTORY_DIR).join(commit.id.clone());
 
-    let enc = GzEncoder::new(Vec::new(), Compression::default());
+    let enc = GzEncoder::new(Vec::new(), compression);
     let mut tar = tar::Builder::new(enc);
 
     // Don't send any errantly downloaded local cache files (from old versions of oxen clone)
@@ -1164,13 +1430,19 @@ mod tests {
     use crate::constants::COMMITS_DIR;
     use crate::constants::DEFAULT_BRANCH_NAME;
     use crate::core::db;
+    use crate::core::index::commit_tarball;
     use crate::core::index::pusher::UnsyncedCommitEntries;
     use crate::core::index::CommitDBReader;
     use crate::error::OxenError;
 
     use crate::model::entry::commit_entry::Entry;
+    use crate::model::Commit;
+    use crate::opts::PaginateOpts;
     use crate::test;
+    use crate::util;
+    use futures::TryStreamExt;
     use rocksdb::{DBWithThreadMode, MultiThreaded};
+    use std::fs::File;
 
     #[tokio::test]
     async fn test_remote_commits_post_commits_to_server() -> Result<(), OxenError> {
@@ -1329,6 +1601,89 @@ mod tests {
         .await
     }
 
+    #[tokio::test]
+    async fn test_download_entries_manifest() -> Result<(), OxenError> {
+        test::run_training_data_repo_test_fully_committed_async(|local_repo| async move {
+            let mut local_repo = local_repo;
+            let head = api::local::commits::head_commit(&local_repo)?;
+            let mut local_entries = api::local::entries::list_all(&local_repo, &head)?;
+            local_entries.sort_by(|a, b| a.path.cmp(&b.path));
+
+            // Set the proper remote
+            let name = local_repo.dirname();
+            let remote = test::repo_remote_url_from(&name);
+            command::config::set_remote(&mut local_repo, constants::DEFAULT_REMOTE_NAME, &remote)?;
+
+            // Create Remote
+            let remote_repo = test::create_remote_repo(&local_repo).await?;
+
+            // Push it
+            command::push(&local_repo).await?;
+
+            // The manifest matches the committed entries
+            let manifest =
+                api::remote::commits::download_entries_manifest(&remote_repo, &head.id).await?;
+            assert_eq!(manifest.len(), local_entries.len());
+            for (remote_entry, local_entry) in manifest.iter().zip(local_entries.iter()) {
+                assert_eq!(remote_entry.path, local_entry.path);
+                assert_eq!(remote_entry.hash, local_entry.hash);
+                assert_eq!(remote_entry.num_bytes, local_entry.num_bytes);
+            }
+
+            // Pages split the same list
+            let page_opts = PaginateOpts {
+                page_num: 2,
+                page_size: 3,
+            };
+            let page = api::remote::commits::download_entries_manifest_page(
+                &remote_repo,
+                DEFAULT_BRANCH_NAME,
+                &page_opts,
+            )
+            .await?;
+            assert_eq!(page.pagination.total_entries, local_entries.len());
+            let paths: Vec<_> = page.entries.iter().map(|e| e.path.clone()).collect();
+            let expected: Vec<_> = local_entries[3..6].iter().map(|e| e.path.clone()).collect();
+            assert_eq!(paths, expected);
+
+            api::remote::repositories::delete(&remote_repo).await?;
+
+            Ok(())
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_list_remote_commits_all_stream() -> Result<(), OxenError> {
+        test::run_training_data_repo_test_fully_committed_async(|local_repo| async move {
+            let mut local_repo = local_repo;
+            let num_local_commits = api::local::commits::list_all(&local_repo)?.len();
+
+            // Set the proper remote
+            let name = local_repo.dirname();
+            let remote = test::repo_remote_url_from(&name);
+            command::config::set_remote(&mut local_repo, constants::DEFAULT_REMOTE_NAME, &remote)?;
+
+            // Create Remote
+            let remote_repo = test::create_remote_repo(&local_repo).await?;
+
+            // Push it
+            command::push(&local_repo).await?;
+
+            // Page size of 1 so each commit is its own page
+            let remote_commits: Vec<Commit> =
+                api::remote::commits::list_all_stream(&remote_repo, 1)
+                    .try_collect()
+                    .await?;
+            assert_eq!(remote_commits.len(), num_local_commits);
+
+            api::remote::repositories::delete(&remote_repo).await?;
+
+            Ok(())
+        })
+        .await
+    }
+
     #[tokio::test]
     async fn test_list_remote_commits_base_head() -> Result<(), OxenError> {
         test::run_training_data_fully_sync_remote(|local_repo, remote_repo| async move {
@@ -1360,6 +1715,48 @@ mod tests {
         .await
     }
 
+    #[tokio::test]
+    async fn test_list_remote_commits_between() -> Result<(), OxenError> {
+        test::run_training_data_fully_sync_remote(|local_repo, remote_repo| async move {
+            // Push a few more commits on top of the synced history
+            let base_commit = api::local::commits::head_commit(&local_repo)?;
+            let mut pushed: Vec<Commit> = vec![];
+            for i in 0..4 {
+                let path = local_repo.path.join(format!("range_{i}.txt"));
+                util::fs::write_to_path(&path, format!("commit {i}"))?;
+                command::add(&local_repo, &path)?;
+                pushed.push(command::commit(&local_repo, &format!("Range commit {i}"))?);
+            }
+            command::push(&local_repo).await?;
+
+            // Everything after the base up to the third new commit
+            let head_commit = &pushed[2];
+            let page_opts = PaginateOpts {
+                page_num: 1,
+                page_size: 2,
+            };
+            let page = api::remote::commits::list_between_paginated(
+                &remote_repo,
+                &base_commit.id,
+                &head_commit.id,
+                &page_opts,
+            )
+            .await?;
+            assert_eq!(page.pagination.total_entries, 3);
+            assert_eq!(page.commits.len(), 2);
+
+            let commits =
+                api::remote::commits::list_between(&remote_repo, &base_commit.id, &head_commit.id)
+                    .await?;
+            let ids: Vec<&str> = commits.iter().map(|c| c.id.as_str()).collect();
+            let expected: Vec<&str> = pushed[0..3].iter().rev().map(|c| c.id.as_str()).collect();
+            assert_eq!(ids, expected);
+
+            Ok(remote_repo)
+        })
+        .await
+    }
+
     #[tokio::test]
     async fn test_download_commits_db() -> Result<(), OxenError> {
         test::run_training_data_fully_sync_remote(|local_repo, remote_repo| async move {
@@ -1397,6 +1794,48 @@ mod tests {
         })
         .await
     }
+    #[tokio::test]
+    async fn test_download_commit_tarballs_into_fresh_repo() -> Result<(), OxenError> {
+        test::run_training_data_fully_sync_remote(|local_repo, remote_repo| async move {
+            let history = api::local::commits::list(&local_repo)?;
+            let head = api::local::commits::head_commit(&local_repo)?;
+            let head_entries = api::local::entries::list_all(&local_repo, &head)?;
+            let remote_clone = remote_repo.clone();
+
+            test::run_empty_dir_test_async(|new_dir| async move {
+                let repo = command::init(new_dir.join("backup"))?;
+
+                // Load the tarballs oldest first, each one only has the versions it added
+                for commit in history.iter().rev() {
+                    let tarball = new_dir.join(format!("{}.tar.gz", commit.id));
+                    api::remote::commits::download_commit_tarball(
+                        &remote_repo,
+                        &commit.id,
+                        &tarball,
+                    )
+                    .await?;
+                    let loaded = commit_tarball::load(&repo, File::open(&tarball)?)?;
+                    assert_eq!(loaded.id, commit.id);
+                }
+
+                for commit in history.iter() {
+                    assert!(api::local::commits::get_by_id(&repo, &commit.id)?.is_some());
+                }
+                let entries = api::local::entries::list_all(&repo, &head)?;
+                assert_eq!(entries.len(), head_entries.len());
+                for entry in entries.iter() {
+                    assert!(util::fs::version_path(&repo, entry).exists());
+                }
+
+                Ok(new_dir)
+            })
+            .await?;
+
+            Ok(remote_clone)
+        })
+        .await
+    }
+
     #[tokio::test]
     async fn test_latest_commit_synced() -> Result<(), OxenError> {
         test::run_training_data_sync_test_no_commits(|mut local_repo, remote_repo| async move {
@@ -7,6 +7,7 @@ use crate::model::entry::commit_entry::Entry;
 use crate::model::{MetadataEntry, NewCommitBody, RemoteRepository};
 use crate::opts::UploadOpts;
 use crate::util::progress_bar::{oxen_progress_bar, ProgressBarType};
+use crate::view::{LineRangeResponse, SignedUrlResponse};
 use crate::{api, constants};
 use crate::{current_function, util};
 
@@ -36,6 +37,45 @@ pub async fn get_entry(
     Ok(entry)
 }
 
+/// Read the lines `[start, start + len)` of a committed text file on the remote
+pub async fn read_lines(
+    remote_repo: &RemoteRepository,
+    revision: impl AsRef<str>,
+    path: impl AsRef<Path>,
+    start: usize,
+    len: usize,
+) -> Result<LineRangeResponse, OxenError> {
+    let path = path.as_ref().to_string_lossy();
+    let revision = revision.as_ref();
+    let uri = format!("/line_range/{revision}/{path}?start={start}&len={len}");
+    let url = api::endpoint::url_from_repo(remote_repo, &uri)?;
+
+    let client = client::new_for_url(&url)?;
+    let response = client.get(&url).send().await?;
+    let body = client::parse_json_body(&url, response).await?;
+    Ok(serde_json::from_str(&body)?)
+}
+
+/// Get a url that downloads the file at the revision without an access token,
+/// until it expires in `expires_in_secs`
+pub async fn create_signed_url(
+    remote_repo: &RemoteRepository,
+    revision: impl AsRef<str>,
+    path: impl AsRef<Path>,
+    expires_in_secs: u64,
+) -> Result<String, OxenError> {
+    let path = path.as_ref().to_string_lossy();
+    let revision = revision.as_ref();
+    let uri = format!("/signed_url/{revision}/{path}?expires_in={expires_in_secs}");
+    let url = api::endpoint::url_from_repo(remote_repo, &uri)?;
+
+    let client = client::new_for_url(&url)?;
+    let response = client.post(&url).send().await?;
+    let body = client::parse_json_body(&url, response).await?;
+    let response: SignedUrlResponse = serde_json::from_str(&body)?;
+    api::endpoint::url_from_repo(remote_repo, &response.uri)
+}
+
 pub async fn upload_entries(
     remote_repo: &RemoteRepository,
     opts: &UploadOpts,
@@ -83,6 +123,8 @@ pub async fn upload_entries(
         message: opts.message.clone(),
         author: user.name,
         email: user.email,
+        expected_parent_id: None,
+        idempotency_key: Some(uuid::Uuid::new_v4().to_string()),
     };
     let commit =
         api::remote::staging::commit(remote_repo, &branch_name, &identifier, &commit).await?;
@@ -92,6 +134,59 @@ pub async fn upload_entries(
     Ok(())
 }
 
+/// Upload a single file straight into the remote versions directory, returning its hash.
+///
+/// The server re-hashes the body and rejects it with `OxenError::HashMismatch` if the bytes
+/// were corrupted in transit, in which case the upload is retried with backoff.
+pub async fn upload_single(
+    remote_repo: &RemoteRepository,
+    path: impl AsRef<Path>,
+) -> Result<String, OxenError> {
+    let path = path.as_ref();
+    let buffer = fs::read(path)?;
+    let hash = util::hasher::hash_buffer(&buffer);
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| OxenError::file_has_no_name(path))?
+        .to_string_lossy();
+    let file_name = urlencoding::encode(&file_name);
+
+    let uri = format!("/versions/{hash}?filename={file_name}");
+    let url = api::endpoint::url_from_repo(remote_repo, &uri)?;
+    let client = client::new_for_url(&url)?;
+
+    let mut total_tries = 0;
+    while total_tries < constants::NUM_HTTP_RETRIES {
+        let result = match client.post(&url).body(buffer.clone()).send().await {
+            Ok(res) => client::parse_json_body(&url, res).await.map(|_| ()),
+            Err(err) => Err(OxenError::from(err)),
+        };
+
+        match result {
+            Ok(_) => return Ok(hash),
+            Err(OxenError::HashMismatch(err)) => {
+                total_tries += 1;
+                // Exponentially back off
+                let sleep_time = total_tries * total_tries;
+                log::debug!(
+                    "upload_single hash mismatch for {:?} sleeping {}: {}",
+                    path,
+                    sleep_time,
+                    err
+                );
+                tokio::time::sleep(std::time::Duration::from_secs(sleep_time)).await;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+
+    Err(OxenError::basic_str(format!(
+        "Upload of {:?} failed hash verification after {} tries",
+        path,
+        constants::NUM_HTTP_RETRIES
+    )))
+}
+
 /// Pings the remote server first to see if the entry exists
 /// and get the size before downloading
 pub async fn download_entry(
@@ -490,6 +585,59 @@ async fn download_entry_chunk(
     }
 }
 
+/// Download the content for the given ids into `dst`, splitting them into sub batches
+/// so that no single request is too large for the server to accept
+pub async fn download_content_by_ids(
+    remote_repo: &RemoteRepository,
+    content_ids: &[(String, PathBuf)], // tuple of content id and entry path
+    dst: impl AsRef<Path>,
+) -> Result<u64, OxenError> {
+    let batches = content_id_batches(
+        content_ids,
+        constants::MAX_CONTENT_IDS_PER_REQUEST,
+        constants::MAX_CONTENT_IDS_REQUEST_BYTES,
+    );
+    log::debug!(
+        "download_content_by_ids {} content ids in {} batches",
+        content_ids.len(),
+        batches.len()
+    );
+
+    let mut size: u64 = 0;
+    for batch in batches {
+        size += download_data_from_version_paths(remote_repo, batch, &dst).await?;
+    }
+    Ok(size)
+}
+
+/// Split the content ids into batches of at most `max_count` ids, where the ids in each
+/// batch add up to at most `max_bytes` in the request body. An id larger than `max_bytes`
+/// on its own still gets a batch of its own.
+pub fn content_id_batches(
+    content_ids: &[(String, PathBuf)],
+    max_count: usize,
+    max_bytes: usize,
+) -> Vec<&[(String, PathBuf)]> {
+    let mut batches = vec![];
+    let mut start = 0;
+    let mut batch_bytes = 0;
+    for (idx, (content_id, _)) in content_ids.iter().enumerate() {
+        // Each id is written to the body followed by a newline
+        let id_bytes = content_id.len() + 1;
+        let batch_len = idx - start;
+        if batch_len > 0 && (batch_len >= max_count || batch_bytes + id_bytes > max_bytes) {
+            batches.push(&content_ids[start..idx]);
+            start = idx;
+            batch_bytes = 0;
+        }
+        batch_bytes += id_bytes;
+    }
+    if start < content_ids.len() {
+        batches.push(&content_ids[start..]);
+    }
+    batches
+}
+
 pub async fn download_data_from_version_paths(
     remote_repo: &RemoteRepository,
     content_ids: &[(String, PathBuf)], // tuple of content id and entry path
@@ -612,12 +760,72 @@ pub async fn try_download_data_from_version_paths(
 #[cfg(test)]
 mod tests {
 
+    use crate::constants;
     use crate::constants::DEFAULT_BRANCH_NAME;
     use crate::error::OxenError;
     use crate::test;
-    use crate::{api, util};
+    use crate::{api, command, util};
 
-    use std::path::Path;
+    use std::path::{Path, PathBuf};
+
+    #[tokio::test]
+    async fn test_read_lines_from_remote() -> Result<(), OxenError> {
+        test::run_remote_repo_test_bounding_box_csv_pushed(|remote_repo| async move {
+            let remote_path = Path::new("annotations")
+                .join("train")
+                .join("bounding_box.csv");
+            let response = api::remote::entries::read_lines(
+                &remote_repo,
+                DEFAULT_BRANCH_NAME,
+                &remote_path,
+                2,
+                2,
+            )
+            .await?;
+
+            assert_eq!(response.start, 2);
+            assert_eq!(response.total_lines, 7);
+            assert_eq!(
+                response.lines,
+                vec![
+                    "train/dog_1.jpg,dog,102.5,31.0,386,330",
+                    "train/dog_2.jpg,dog,7.0,29.5,246,247"
+                ]
+            );
+
+            Ok(remote_repo)
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_signed_url_downloads_until_expired() -> Result<(), OxenError> {
+        test::run_remote_repo_test_bounding_box_csv_pushed(|remote_repo| async move {
+            let remote_path = Path::new("annotations")
+                .join("train")
+                .join("bounding_box.csv");
+            let url = api::remote::entries::create_signed_url(
+                &remote_repo,
+                DEFAULT_BRANCH_NAME,
+                &remote_path,
+                1,
+            )
+            .await?;
+
+            // Plain client, no auth header
+            let response = reqwest::get(&url).await?;
+            assert!(response.status().is_success());
+            let body = response.text().await?;
+            assert!(body.starts_with("file,label,min_x,min_y,width,height"));
+
+            tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+            let response = reqwest::get(&url).await?;
+            assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+            Ok(remote_repo)
+        })
+        .await
+    }
 
     #[tokio::test]
     async fn test_download_file_large() -> Result<(), OxenError> {
@@ -741,4 +949,135 @@ mod tests {
         })
         .await
     }
+
+    #[test]
+    fn test_content_id_batches_capped_by_bytes() {
+        let content_ids: Vec<(String, PathBuf)> = (0..5)
+            .map(|i| (format!("content_{i}"), PathBuf::from(format!("{i}.txt"))))
+            .collect();
+
+        // Each id is 9 bytes plus a newline, so only two fit under 25 bytes
+        let batches = api::remote::entries::content_id_batches(&content_ids, 100, 25);
+        let sizes: Vec<usize> = batches.iter().map(|b| b.len()).collect();
+        assert_eq!(sizes, vec![2, 2, 1]);
+
+        // An id larger than the cap still gets its own batch
+        let batches = api::remote::entries::content_id_batches(&content_ids, 100, 5);
+        assert_eq!(batches.len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_download_content_by_ids_in_sub_batches() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test_async(|repo| async move {
+            let mut repo = repo;
+
+            let num_files = 2_500;
+            let data_dir = repo.path.join("data");
+            util::fs::create_dir_all(&data_dir)?;
+            for i in 0..num_files {
+                util::fs::write_to_path(data_dir.join(format!("{i}.txt")), format!("file {i}"))?;
+            }
+            command::add(&repo, &data_dir)?;
+            let commit = command::commit(&repo, "Adding many files")?;
+
+            let remote = test::repo_remote_url_from(&repo.dirname());
+            command::config::set_remote(&mut repo, constants::DEFAULT_REMOTE_NAME, &remote)?;
+            let remote_repo = test::create_remote_repo(&repo).await?;
+            command::push(&repo).await?;
+
+            // Request the content the same way pull does, by version path relative to dst
+            let dst = repo.path.join("downloaded");
+            let entries = api::local::entries::list_all(&repo, &commit)?;
+            let content_ids: Vec<(String, PathBuf)> = entries
+                .iter()
+                .map(|entry| {
+                    let version_path = util::fs::version_path_from_dst(&dst, entry);
+                    let version_path = util::fs::path_relative_to_dir(&version_path, &dst).unwrap();
+                    (
+                        String::from(version_path.to_str().unwrap()).replace('\\', "/"),
+                        version_path,
+                    )
+                })
+                .collect();
+            assert_eq!(content_ids.len(), num_files);
+
+            let batches = api::remote::entries::content_id_batches(
+                &content_ids,
+                constants::MAX_CONTENT_IDS_PER_REQUEST,
+                constants::MAX_CONTENT_IDS_REQUEST_BYTES,
+            );
+            assert!(batches.len() >= 3);
+            for batch in batches.iter() {
+                assert!(batch.len() <= constants::MAX_CONTENT_IDS_PER_REQUEST);
+                let num_bytes: usize = batch.iter().map(|(id, _)| id.len() + 1).sum();
+                assert!(num_bytes <= constants::MAX_CONTENT_IDS_REQUEST_BYTES);
+            }
+            let num_batched: usize = batches.iter().map(|b| b.len()).sum();
+            assert_eq!(num_batched, num_files);
+
+            api::remote::entries::download_content_by_ids(&remote_repo, &content_ids, &dst).await?;
+
+            // All of the content made it down
+            for (entry, (_, version_path)) in entries.iter().zip(content_ids.iter()) {
+                let downloaded = util::fs::read_from_path(dst.join(version_path))?;
+                let original = util::fs::read_from_path(repo.path.join(&entry.path))?;
+                assert_eq!(downloaded, original);
+            }
+
+            api::remote::repositories::delete(&remote_repo).await?;
+
+            Ok(())
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_upload_single_retries_on_hash_mismatch() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test_async(|local_repo| async move {
+            let mut server = mockito::Server::new_async().await;
+            let server_url = server.url();
+
+            let namespace = constants::DEFAULT_NAMESPACE;
+            let name = local_repo.dirname();
+
+            let mut remote_repo = test::create_remote_repo(&local_repo).await?;
+            let original_remote_url = remote_repo.remote.url;
+            remote_repo.remote.url = format!("{server_url}/{namespace}/{name}");
+
+            let path = local_repo.path.join("hello.txt");
+            util::fs::write_to_path(&path, "hello")?;
+            let hash = util::hasher::hash_file_contents(&path)?;
+
+            // The first attempt is corrupted in transit, the retry goes through
+            let uri = format!("/api/repos/{namespace}/{name}/versions/{hash}");
+            let mock_mismatch = server
+                .mock("POST", mockito::Matcher::Regex(format!("^{uri}")))
+                .with_status(400)
+                .with_body(
+                    r#"{"status": "error", "status_message": "hash_mismatch", "status_description": "corrupted"}"#,
+                )
+                .expect(1)
+                .create_async()
+                .await;
+            let mock_ok = server
+                .mock("POST", mockito::Matcher::Regex(format!("^{uri}")))
+                .with_status(200)
+                .with_body(r#"{"status": "success", "status_message": "resource_created"}"#)
+                .expect(1)
+                .create_async()
+                .await;
+
+            let uploaded = api::remote::entries::upload_single(&remote_repo, &path).await?;
+            assert_eq!(uploaded, hash);
+            mock_mismatch.assert_async().await;
+            mock_ok.assert_async().await;
+
+            // cleanup
+            remote_repo.remote.url = original_remote_url;
+
+            api::remote::repositories::delete(&remote_repo).await?;
+            Ok(())
+        })
+        .await
+    }
 }
@@ -5,7 +5,7 @@ use crate::api;
 use crate::api::remote::client;
 use crate::error::OxenError;
 use crate::model::RemoteRepository;
-use crate::view::merge::{Mergeable, MergeableResponse};
+use crate::view::merge::{MergeResult, MergeSuccessResponse, Mergeable, MergeableResponse};
 
 /// Can check the mergability of base into head
 /// base or head are strings that can be branch names or commit ids
@@ -40,12 +40,66 @@ pub async fn mergability(
     }
 }
 
+/// Ask the server to merge head into base, returning the resulting commit or the conflicting
+/// files if head could not be merged
+/// base or head are strings that can be branch names or commit ids
+pub async fn merge(
+    remote_repo: &RemoteRepository,
+    base: &str,
+    head: &str,
+) -> Result<MergeResult, OxenError> {
+    let uri = format!("/merge/{base}..{head}");
+    let url = api::endpoint::url_from_repo(remote_repo, &uri)?;
+    log::debug!("url: {url}");
+
+    let client = client::new_for_url(&url)?;
+    match client.post(&url).send().await {
+        Ok(res) => {
+            if res.status() == reqwest::StatusCode::BAD_REQUEST {
+                // Conflicts come back as a bad request with what would have been merged
+                let body = res.text().await?;
+                log::debug!("got body: {}", body);
+                let response: Result<MergeableResponse, serde_json::Error> =
+                    serde_json::from_str(&body);
+                return match response {
+                    Ok(val) => Ok(MergeResult::Conflicts(val.mergeable.conflicts)),
+                    Err(_) => Err(OxenError::basic_str(format!(
+                        "api::remote::merger::merge could not merge {base}..{head}\n\n{body}"
+                    ))),
+                };
+            }
+
+            let body = client::parse_json_body(&url, res).await?;
+            log::debug!("got body: {}", body);
+            let response: Result<MergeSuccessResponse, serde_json::Error> =
+                serde_json::from_str(&body);
+            match response {
+                Ok(MergeSuccessResponse {
+                    commit: Some(commit),
+                    ..
+                }) => Ok(MergeResult::Merged(commit)),
+                Ok(_) => Err(OxenError::basic_str(format!(
+                    "api::remote::merger::merge server did not return the merge commit, upgrade the server\n\n{body}"
+                ))),
+                Err(err) => Err(OxenError::basic_str(format!(
+                    "api::remote::merger::merge error parsing response from {url}\n\nErr {err:?} \n\n{body}"
+                ))),
+            }
+        }
+        Err(err) => {
+            let err = format!("api::remote::merger::merge Request failed: {url}\nErr {err:?}");
+            Err(OxenError::basic_str(err))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
     use crate::constants::DEFAULT_REMOTE_NAME;
     use crate::error::OxenError;
     use crate::test;
+    use crate::view::merge::MergeResult;
     use crate::{api, command};
 
     #[tokio::test]
@@ -181,4 +235,85 @@ mod tests {
         })
         .await
     }
+
+    #[tokio::test]
+    async fn test_remote_merger_merge_non_conflicting_branches() -> Result<(), OxenError> {
+        test::run_training_data_fully_sync_remote(|local_repo, remote_repo| async move {
+            let base = "main";
+            let head = "add-data";
+
+            // Add a file on head
+            command::create_checkout(&local_repo, head)?;
+            let path = local_repo.path.join("file_1.txt");
+            test::write_txt_file_to_path(&path, "hello")?;
+            command::add(&local_repo, &path)?;
+            let head_commit = command::commit(&local_repo, "adding file 1")?;
+            command::push_remote_branch(&local_repo, DEFAULT_REMOTE_NAME, head).await?;
+
+            // Add a different file on base so they diverge
+            command::checkout(&local_repo, base).await?;
+            let path = local_repo.path.join("file_2.txt");
+            test::write_txt_file_to_path(&path, "world")?;
+            command::add(&local_repo, &path)?;
+            let base_commit = command::commit(&local_repo, "adding file 2")?;
+            command::push_remote_branch(&local_repo, DEFAULT_REMOTE_NAME, base).await?;
+
+            let result = api::remote::merger::merge(&remote_repo, base, head).await?;
+            let MergeResult::Merged(commit) = result else {
+                panic!("Expected a merge commit, got {result:?}");
+            };
+            assert_eq!(commit.parent_ids.len(), 2);
+            assert!(commit.parent_ids.contains(&base_commit.id));
+            assert!(commit.parent_ids.contains(&head_commit.id));
+
+            // Base now points at the merge commit
+            let branch = api::remote::branches::get_by_name(&remote_repo, base)
+                .await?
+                .unwrap();
+            assert_eq!(branch.commit_id, commit.id);
+
+            Ok(remote_repo)
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_remote_merger_merge_reports_conflicts() -> Result<(), OxenError> {
+        test::run_training_data_fully_sync_remote(|local_repo, remote_repo| async move {
+            let base = "main";
+            let head = "add-data";
+
+            // Modify README.md on head
+            command::create_checkout(&local_repo, head)?;
+            let path = local_repo.path.join("README.md");
+            test::write_txt_file_to_path(&path, "I am the README now")?;
+            command::add(&local_repo, &path)?;
+            command::commit(&local_repo, "modifying readme on head")?;
+            command::push_remote_branch(&local_repo, DEFAULT_REMOTE_NAME, head).await?;
+
+            // Modify README.md on base to conflict
+            command::checkout(&local_repo, base).await?;
+            let path = local_repo.path.join("README.md");
+            test::write_txt_file_to_path(&path, "I am on main conflicting the README")?;
+            command::add(&local_repo, &path)?;
+            let base_commit = command::commit(&local_repo, "modifying readme on main")?;
+            command::push_remote_branch(&local_repo, DEFAULT_REMOTE_NAME, base).await?;
+
+            let result = api::remote::merger::merge(&remote_repo, base, head).await?;
+            let MergeResult::Conflicts(conflicts) = result else {
+                panic!("Expected conflicts, got {result:?}");
+            };
+            assert_eq!(conflicts.len(), 1);
+            assert_eq!(conflicts[0].path, "README.md");
+
+            // Base did not move
+            let branch = api::remote::branches::get_by_name(&remote_repo, base)
+                .await?
+                .unwrap();
+            assert_eq!(branch.commit_id, base_commit.id);
+
+            Ok(remote_repo)
+        })
+        .await
+    }
 }
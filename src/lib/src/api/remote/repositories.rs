@@ -3,7 +3,10 @@ use crate::api::remote::client;
 use crate::constants::{DEFAULT_HOST, DEFAULT_REMOTE_NAME};
 use crate::error::OxenError;
 use crate::model::{Branch, LocalRepository, Remote, RemoteRepository, RepoNew};
-use crate::view::repository::{RepositoryDataTypesResponse, RepositoryDataTypesView};
+use crate::view::repository::{
+    RepositoryDataTypesResponse, RepositoryDataTypesView, RepositoryStatsResponse,
+    RepositoryStatsView,
+};
 use crate::view::{NamespaceView, RepositoryResponse, StatusMessage};
 use serde_json::json;
 use serde_json::value;
@@ -79,9 +82,34 @@ pub async fn get_by_name_host_and_remote(
     get_by_remote(&remote).await
 }
 
+/// Check if the repository exists with a HEAD request, without fetching the repository body.
+/// Servers that predate HEAD support answer 404 or 405, so those fall back to a GET.
 pub async fn exists(repo: &RemoteRepository) -> Result<bool, OxenError> {
-    let repo = get_by_remote_repo(repo).await?;
-    Ok(repo.is_some())
+    let url = api::endpoint::url_from_repo(repo, "")?;
+    log::debug!("api::remote::repositories::exists url: {}", url);
+
+    let client = client::new_for_url(&url)?;
+    match client.head(&url).send().await {
+        Ok(res) => {
+            let status = res.status();
+            if status.is_success() {
+                Ok(true)
+            } else if 404 == status || 405 == status {
+                log::debug!("HEAD returned {status} for {url}, falling back to GET");
+                Ok(get_by_remote_repo(repo).await?.is_some())
+            } else {
+                Err(OxenError::basic_str(format!(
+                    "api::repositories::exists() Unexpected status {status} from {url}"
+                )))
+            }
+        }
+        Err(err) => {
+            log::error!("Failed to check remote url {url}\n{err:?}");
+            Err(OxenError::basic_str(format!(
+                "api::repositories::exists() Request failed at url {url}"
+            )))
+        }
+    }
 }
 
 pub async fn get_by_remote(remote: &Remote) -> Result<Option<RemoteRepository>, OxenError> {
@@ -164,6 +192,38 @@ pub async fn get_repo_data_by_remote(
     }
 }
 
+/// Get the size, commit count, branch count and last updated time of a remote repository
+pub async fn stats(repo: &RemoteRepository) -> Result<RepositoryStatsView, OxenError> {
+    let url = api::endpoint::url_from_repo(repo, "/stats")?;
+    log::debug!("api::remote::repositories::stats url: {}", url);
+
+    let client = client::new_for_url(&url)?;
+    match client.get(&url).send().await {
+        Ok(res) => {
+            let body = client::parse_json_body(&url, res).await?;
+            log::debug!("repositories::stats {}\n {}", url, body);
+
+            let response: Result<RepositoryStatsResponse, serde_json::Error> =
+                serde_json::from_str(&body);
+            match response {
+                Ok(j_res) => Ok(j_res.repository),
+                Err(err) => {
+                    log::debug!("Err: {}", err);
+                    Err(OxenError::basic_str(format!(
+                        "api::repositories::stats() Could not deserialize repository stats [{url}]"
+                    )))
+                }
+            }
+        }
+        Err(err) => {
+            log::error!("Failed to get remote url {url}\n{err:?}");
+            Err(OxenError::basic_str(format!(
+                "api::repositories::stats() Request failed at url {url}"
+            )))
+        }
+    }
+}
+
 pub async fn create_empty(repo: RepoNew) -> Result<RemoteRepository, OxenError> {
     let namespace = repo.namespace.as_ref();
     let repo_name = repo.name.as_ref();
@@ -461,6 +521,33 @@ mod tests {
     use crate::model::RepoNew;
     use crate::test;
 
+    #[tokio::test]
+    async fn test_remote_repo_stats() -> Result<(), OxenError> {
+        test::run_training_data_fully_sync_remote(|local_repo, remote_repo| async move {
+            api::remote::branches::create_from_or_get(
+                &remote_repo,
+                "feature/stats",
+                DEFAULT_BRANCH_NAME,
+            )
+            .await?;
+
+            let stats = api::remote::repositories::stats(&remote_repo).await?;
+
+            let local_commits = api::local::commits::list_all(&local_repo)?;
+            let remote_branches = api::remote::branches::list(&remote_repo).await?;
+            assert_eq!(stats.num_commits, local_commits.len());
+            assert_eq!(stats.num_branches, 2);
+            assert_eq!(stats.num_branches, remote_branches.len());
+            assert!(stats.data_size > 0);
+
+            let head = api::local::commits::head_commit(&local_repo)?;
+            assert_eq!(stats.last_updated, Some(head.timestamp));
+
+            Ok(remote_repo)
+        })
+        .await
+    }
+
     #[tokio::test]
     async fn test_repo_pre_and_post_clone() -> Result<(), OxenError> {
         test::run_empty_local_repo_test_async(|local_repo| async move {
@@ -603,6 +690,100 @@ mod tests {
         .await
     }
 
+    #[tokio::test]
+    async fn test_repository_exists() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test_async(|local_repo| async move {
+            let remote_repo = test::create_remote_repo(&local_repo).await?;
+            assert!(api::remote::repositories::exists(&remote_repo).await?);
+
+            let mut bogus_repo = remote_repo.clone();
+            bogus_repo.name = format!("{}-bogus", remote_repo.name);
+            bogus_repo.remote.url = format!("{}-bogus", remote_repo.remote.url);
+            assert!(!api::remote::repositories::exists(&bogus_repo).await?);
+
+            // cleanup
+            api::remote::repositories::delete(&remote_repo).await?;
+            Ok(())
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_repository_exists_does_not_fetch_body() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test_async(|local_repo| async move {
+            let mut server = mockito::Server::new_async().await;
+            let server_url = server.url();
+
+            let namespace = constants::DEFAULT_NAMESPACE;
+            let name = local_repo.dirname();
+
+            let mut remote_repo = test::create_remote_repo(&local_repo).await?;
+            let original_remote_url = remote_repo.remote.url;
+            remote_repo.remote.url = format!("{server_url}/{namespace}/{name}");
+
+            // Only answers HEAD, a GET of the full body would fail to parse
+            let path = format!("/api/repos/{namespace}/{name}");
+            let mock_head = server.mock("HEAD", &path[..]).create_async().await;
+            let mock_get = server
+                .mock("GET", &path[..])
+                .with_body("not a repository")
+                .expect(0)
+                .create_async()
+                .await;
+
+            assert!(api::remote::repositories::exists(&remote_repo).await?);
+            mock_head.assert();
+            mock_get.assert();
+
+            // cleanup
+            remote_repo.remote.url = original_remote_url;
+            api::remote::repositories::delete(&remote_repo).await?;
+            Ok(())
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_repository_exists_falls_back_to_get() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test_async(|local_repo| async move {
+            let mut server = mockito::Server::new_async().await;
+            let server_url = server.url();
+
+            let namespace = constants::DEFAULT_NAMESPACE;
+            let name = local_repo.dirname();
+
+            let mut remote_repo = test::create_remote_repo(&local_repo).await?;
+            let original_remote_url = remote_repo.remote.url;
+            remote_repo.remote.url = format!("{server_url}/{namespace}/{name}");
+
+            // An older server without the HEAD route
+            let path = format!("/api/repos/{namespace}/{name}");
+            let mock_head = server
+                .mock("HEAD", &path[..])
+                .with_status(405)
+                .create_async()
+                .await;
+            let body = format!(
+                r#"{{"status": "success", "status_message": "resource_found", "repository": {{"namespace": "{namespace}", "name": "{name}"}}}}"#
+            );
+            let mock_get = server
+                .mock("GET", &path[..])
+                .with_body(body)
+                .create_async()
+                .await;
+
+            assert!(api::remote::repositories::exists(&remote_repo).await?);
+            mock_head.assert();
+            mock_get.assert();
+
+            // cleanup
+            remote_repo.remote.url = original_remote_url;
+            api::remote::repositories::delete(&remote_repo).await?;
+            Ok(())
+        })
+        .await
+    }
+
     #[tokio::test]
     async fn test_delete_repository() -> Result<(), OxenError> {
         test::run_empty_local_repo_test_async(|local_repo| async move {
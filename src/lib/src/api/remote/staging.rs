@@ -3,19 +3,21 @@ pub mod commit;
 pub mod dataset;
 pub mod diff;
 pub mod get_row;
+pub mod list_staged;
 pub mod modify_df;
 pub mod restore_df;
 pub mod rm_df_mod;
 pub mod rm_file;
 pub mod status;
 
-pub use add_file::{add_file, add_files};
+pub use add_file::{add_file, add_file_chunked, add_files};
 pub use commit::commit;
 pub use dataset::index_dataset;
 pub use diff::diff;
 pub use get_row::get_row;
+pub use list_staged::list_staged;
 pub use modify_df::modify_df;
 pub use restore_df::restore_df;
 pub use rm_df_mod::rm_df_mod;
 pub use rm_file::rm_file;
-pub use status::status;
+pub use status::{status, status_all};
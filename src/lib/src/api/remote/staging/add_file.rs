@@ -1,20 +1,27 @@
 use crate::api;
 use crate::api::remote::client;
+use crate::constants;
 use crate::error::OxenError;
 use crate::model::RemoteRepository;
-use crate::view::FilePathsResponse;
+use crate::util;
+use crate::util::progress_bar::{oxen_progress_bar, ProgressBarType};
+use crate::view::{ChunkedUploadRequest, ChunkedUploadResponse, FilePathsResponse};
 
 use bytesize::ByteSize;
+use std::io::Read;
 use std::path::PathBuf;
 
+/// Stage a file on the remote branch. The server refuses paths another identifier has staged
+/// changes to unless `force` is set.
 pub async fn add_file(
     remote_repo: &RemoteRepository,
     branch_name: &str,
     identifier: &str,
     directory_name: &str,
     path: PathBuf,
+    force: bool,
 ) -> Result<PathBuf, OxenError> {
-    let uri = format!("/staging/{identifier}/file/{branch_name}/{directory_name}");
+    let uri = format!("/staging/{identifier}/file/{branch_name}/{directory_name}?force={force}");
     let url = api::endpoint::url_from_repo(remote_repo, &uri)?;
 
     let file_name = path
@@ -117,6 +124,123 @@ pub async fn add_files(
     }
 }
 
+/// Stage a file that is too large for a single request by uploading it in parts of `chunk_size`
+/// bytes. The server puts the parts back together and checks the hash before staging it, and
+/// refuses paths another identifier has staged changes to unless `force` is set.
+pub async fn add_file_chunked(
+    remote_repo: &RemoteRepository,
+    branch_name: &str,
+    identifier: &str,
+    directory_name: &str,
+    path: PathBuf,
+    chunk_size: u64,
+    force: bool,
+) -> Result<PathBuf, OxenError> {
+    if chunk_size == 0 {
+        return Err(OxenError::basic_str("Chunk size must be greater than zero"));
+    }
+
+    let file_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| OxenError::basic_str(format!("Invalid file name: {path:?}")))?
+        .to_string();
+    let total_size = path.metadata()?.len();
+    let total_chunks = std::cmp::max(1, total_size.div_ceil(chunk_size)) as usize;
+    let hash = util::hasher::hash_file_contents(&path)?;
+
+    // Ask the server for the part uris
+    let upload_req = ChunkedUploadRequest {
+        branch: branch_name.to_string(),
+        directory: directory_name.to_string(),
+        file_name,
+        total_size,
+        total_chunks,
+        hash,
+    };
+    let uri = format!("/staging/{identifier}/chunked_uploads");
+    let url = api::endpoint::url_from_repo(remote_repo, &uri)?;
+    let client = client::new_for_url(&url)?;
+    let res = client.post(&url).json(&upload_req).send().await?;
+    let body = client::parse_json_body(&url, res).await?;
+    let upload: ChunkedUploadResponse = serde_json::from_str(&body)?;
+
+    println!(
+        "Uploading {} in {} parts",
+        ByteSize::b(total_size),
+        upload.part_uris.len()
+    );
+    let bar = oxen_progress_bar(total_size, ProgressBarType::Bytes);
+
+    // Only read one part at a time so large files don't have to fit in memory
+    let mut file = std::fs::File::open(&path)?;
+    let mut bytes_read = 0;
+    for part_uri in upload.part_uris.iter() {
+        let part_size = std::cmp::min(chunk_size, total_size - bytes_read);
+        let mut buffer = vec![0u8; part_size as usize];
+        file.read_exact(&mut buffer)?;
+        upload_part_with_retry(remote_repo, part_uri, buffer).await?;
+        bytes_read += part_size;
+        bar.inc(part_size);
+    }
+    bar.finish_and_clear();
+
+    // Put the parts back together in the staging area
+    let uri = format!(
+        "/staging/{identifier}/chunked_uploads/{}/complete?force={force}",
+        upload.upload_id
+    );
+    let url = api::endpoint::url_from_repo(remote_repo, &uri)?;
+    let res = client.post(&url).send().await?;
+    let body = client::parse_json_body(&url, res).await?;
+    let response: FilePathsResponse = serde_json::from_str(&body)?;
+    response
+        .paths
+        .into_iter()
+        .next()
+        .ok_or_else(|| OxenError::basic_str("Chunked upload did not stage a file"))
+}
+
+async fn upload_part_with_retry(
+    remote_repo: &RemoteRepository,
+    part_uri: &str,
+    buffer: Vec<u8>,
+) -> Result<(), OxenError> {
+    let url = api::endpoint::url_from_repo(remote_repo, part_uri)?;
+    let client = client::new_for_url(&url)?;
+
+    let mut total_tries = 0;
+    let mut last_error = String::from("");
+    while total_tries < constants::NUM_HTTP_RETRIES {
+        let result = match client.put(&url).body(buffer.clone()).send().await {
+            Ok(res) => client::parse_json_body(&url, res).await.map(|_| ()),
+            Err(err) => Err(OxenError::from(err)),
+        };
+
+        match result {
+            Ok(_) => return Ok(()),
+            Err(err) => {
+                total_tries += 1;
+                // Exponentially back off
+                let sleep_time = total_tries * total_tries;
+                log::debug!(
+                    "upload_part_with_retry upload failed sleeping {}: {:?}",
+                    sleep_time,
+                    err
+                );
+                last_error = format!("{:?}", err);
+                tokio::time::sleep(std::time::Duration::from_secs(sleep_time)).await;
+            }
+        }
+    }
+
+    Err(OxenError::basic_str(format!(
+        "Upload part failed after {} tries: {}",
+        constants::NUM_HTTP_RETRIES,
+        last_error
+    )))
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -126,7 +250,7 @@ mod tests {
     use crate::model::NewCommitBody;
     use crate::opts::CloneOpts;
     use crate::{api, constants};
-    use crate::{command, test};
+    use crate::{command, test, util};
 
     use std::path::Path;
 
@@ -151,6 +275,7 @@ mod tests {
                 &identifier,
                 directory_name,
                 path,
+                false,
             )
             .await;
             assert!(result.is_ok());
@@ -244,6 +369,7 @@ mod tests {
                 &identifier,
                 directory_name,
                 file_to_post,
+                false,
             )
             .await;
             assert!(result.is_ok());
@@ -252,6 +378,8 @@ mod tests {
                 message: "Add one image".to_string(),
                 author: "Test User".to_string(),
                 email: "test@oxen.ai".to_string(),
+                expected_parent_id: None,
+                idempotency_key: None,
             };
             let commit =
                 api::remote::staging::commit(&remote_repo, branch_name, &identifier, &body).await?;
@@ -293,4 +421,79 @@ mod tests {
         })
         .await
     }
+
+    #[tokio::test]
+    async fn test_stage_file_chunked() -> Result<(), OxenError> {
+        test::run_remote_repo_test_bounding_box_csv_pushed(|remote_repo| async move {
+            let branch_name = "add-chunked";
+            api::remote::branches::create_from_or_get(
+                &remote_repo,
+                branch_name,
+                DEFAULT_BRANCH_NAME,
+            )
+            .await?;
+
+            // Small chunks so the image is split into many parts
+            let directory_name = "images";
+            let identifier = UserConfig::identifier()?;
+            let path = test::test_img_file();
+            let result = api::remote::staging::add_file_chunked(
+                &remote_repo,
+                branch_name,
+                &identifier,
+                directory_name,
+                path.clone(),
+                1024,
+                false,
+            )
+            .await?;
+            assert_eq!(
+                result,
+                Path::new(directory_name).join(path.file_name().unwrap())
+            );
+
+            let entries = api::remote::staging::status(
+                &remote_repo,
+                branch_name,
+                &identifier,
+                Path::new(directory_name),
+                constants::DEFAULT_PAGE_NUM,
+                constants::DEFAULT_PAGE_SIZE,
+            )
+            .await?;
+            assert_eq!(entries.added_files.entries.len(), 1);
+            assert_eq!(entries.added_files.total_entries, 1);
+
+            // Commit and download the file to make sure it was put back together intact
+            let body = NewCommitBody {
+                message: "Add chunked image".to_string(),
+                author: "Test User".to_string(),
+                email: "test@oxen.ai".to_string(),
+                expected_parent_id: None,
+                idempotency_key: None,
+            };
+            api::remote::staging::commit(&remote_repo, branch_name, &identifier, &body).await?;
+
+            let remote_repo_cloned = remote_repo.clone();
+            test::run_empty_dir_test_async(|dir| async move {
+                let local_path = dir.join("downloaded.jpeg");
+                api::remote::entries::download_entry(
+                    &remote_repo,
+                    &result,
+                    &local_path,
+                    branch_name,
+                )
+                .await?;
+                assert_eq!(
+                    util::hasher::hash_file_contents(&local_path)?,
+                    util::hasher::hash_file_contents(&path)?
+                );
+                Ok(dir)
+            })
+            .await?;
+
+            Ok(remote_repo_cloned)
+        })
+        .await
+    }
 }
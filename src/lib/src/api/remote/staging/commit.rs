@@ -1,9 +1,13 @@
 use crate::api;
 use crate::api::remote::client;
+use crate::constants;
 use crate::error::OxenError;
 use crate::model::{Branch, Commit, NewCommitBody, RemoteRepository};
 use crate::view::CommitResponse;
 
+/// Commit the remote staged data on a branch. When the body has an `idempotency_key` the
+/// request is retried if the connection drops, the server returns the commit the first
+/// attempt made rather than committing twice.
 pub async fn commit(
     remote_repo: &RemoteRepository,
     branch_name: &str,
@@ -16,11 +20,23 @@ pub async fn commit(
     log::debug!("commit_staged {}\n{}", url, body);
 
     let client = client::new_for_url(&url)?;
-    let res = client
-        .post(&url)
-        .body(reqwest::Body::from(body))
-        .send()
-        .await?;
+    let mut total_tries = 0;
+    let res = loop {
+        match client.post(&url).body(body.clone()).send().await {
+            Ok(res) => break res,
+            Err(err)
+                if commit.idempotency_key.is_some()
+                    && total_tries < constants::NUM_HTTP_RETRIES =>
+            {
+                total_tries += 1;
+                // Exponentially back off
+                let sleep_time = total_tries * total_tries;
+                log::debug!("commit_staged retrying in {sleep_time}s after error: {err}");
+                tokio::time::sleep(std::time::Duration::from_secs(sleep_time)).await;
+            }
+            Err(err) => return Err(OxenError::from(err)),
+        }
+    };
 
     let body = client::parse_json_body(&url, res).await?;
     log::debug!("commit_staged got body: {}", body);
@@ -86,6 +102,8 @@ mod tests {
                 message: "Add staged data".to_string(),
                 author: "Test User".to_string(),
                 email: "test@oxen.ai".to_string(),
+                expected_parent_id: None,
+                idempotency_key: None,
             };
             let commit =
                 api::remote::staging::commit(&remote_repo, branch_name, &identifier, &body).await?;
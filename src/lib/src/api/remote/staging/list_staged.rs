@@ -0,0 +1,103 @@
+use crate::api;
+use crate::api::remote::client;
+use crate::error::OxenError;
+use crate::model::RemoteRepository;
+use crate::view::{PaginatedStagedFiles, PaginatedStagedFilesResponse};
+
+/// List one page of the file paths `identifier` has staged on the branch
+pub async fn list_staged(
+    remote_repo: &RemoteRepository,
+    branch_name: &str,
+    identifier: &str,
+    page: usize,
+    page_size: usize,
+) -> Result<PaginatedStagedFiles, OxenError> {
+    let uri =
+        format!("/staging/{identifier}/files/{branch_name}?page={page}&page_size={page_size}");
+    let url = api::endpoint::url_from_repo(remote_repo, &uri)?;
+    log::debug!("list_staged url: {url}");
+
+    let client = client::new_for_url(&url)?;
+    match client.get(&url).send().await {
+        Ok(res) => {
+            let body = client::parse_json_body(&url, res).await?;
+            let response: Result<PaginatedStagedFilesResponse, serde_json::Error> =
+                serde_json::from_str(&body);
+            match response {
+                Ok(val) => Ok(val.staged),
+                Err(err) => Err(OxenError::basic_str(format!(
+                    "api::staging::list_staged error parsing response from {url}\n\nErr {err:?} \n\n{body}"
+                ))),
+            }
+        }
+        Err(err) => {
+            let err = format!("api::staging::list_staged Request failed: {url}\nErr {err:?}");
+            Err(OxenError::basic_str(err))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::api;
+    use crate::config::UserConfig;
+    use crate::constants::DEFAULT_BRANCH_NAME;
+    use crate::error::OxenError;
+    use crate::test;
+    use crate::util;
+
+    #[tokio::test]
+    async fn test_list_staged_paginates() -> Result<(), OxenError> {
+        test::run_remote_repo_test_bounding_box_csv_pushed(|remote_repo| async move {
+            let branch_name = "add-notes";
+            api::remote::branches::create_from_or_get(
+                &remote_repo,
+                branch_name,
+                DEFAULT_BRANCH_NAME,
+            )
+            .await?;
+
+            let upload_dir = test::test_run_dir().join(format!("notes_{}", uuid::Uuid::new_v4()));
+            util::fs::create_dir_all(&upload_dir)?;
+            let mut paths = vec![];
+            for i in 0..5 {
+                let path = upload_dir.join(format!("note_{i}.txt"));
+                util::fs::write_to_path(&path, format!("note {i}"))?;
+                paths.push(path);
+            }
+
+            let identifier = UserConfig::identifier()?;
+            api::remote::staging::add_files(&remote_repo, branch_name, &identifier, "notes", paths)
+                .await?;
+
+            let page_size = 2;
+            let mut listed: Vec<PathBuf> = vec![];
+            for page in 1..=3 {
+                let staged = api::remote::staging::list_staged(
+                    &remote_repo,
+                    branch_name,
+                    &identifier,
+                    page,
+                    page_size,
+                )
+                .await?;
+                assert_eq!(staged.pagination.total_entries, 5);
+                assert_eq!(staged.pagination.total_pages, 3);
+                assert_eq!(staged.pagination.page_number, page);
+                listed.extend(staged.paths);
+            }
+
+            let expected: Vec<PathBuf> = (0..5)
+                .map(|i| PathBuf::from("notes").join(format!("note_{i}.txt")))
+                .collect();
+            assert_eq!(listed, expected);
+
+            util::fs::remove_dir_all(&upload_dir)?;
+
+            Ok(remote_repo)
+        })
+        .await
+    }
+}
@@ -61,6 +61,7 @@ mod tests {
                 &identifier,
                 directory_name,
                 path,
+                false,
             )
             .await;
             assert!(result.is_ok());
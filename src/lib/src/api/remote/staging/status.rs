@@ -2,7 +2,9 @@ use crate::api;
 use crate::api::remote::client;
 use crate::error::OxenError;
 use crate::model::RemoteRepository;
-use crate::view::{RemoteStagedStatus, RemoteStagedStatusResponse};
+use crate::view::{
+    AllStagedStatusResponse, IdentifierStagedStatus, RemoteStagedStatus, RemoteStagedStatusResponse,
+};
 
 use std::path::Path;
 
@@ -42,6 +44,35 @@ pub async fn status(
     }
 }
 
+/// List every identifier with staged changes on the branch, and the paths they staged
+pub async fn status_all(
+    remote_repo: &RemoteRepository,
+    branch_name: &str,
+) -> Result<Vec<IdentifierStagedStatus>, OxenError> {
+    let uri = format!("/staging/status_all/{branch_name}");
+    let url = api::endpoint::url_from_repo(remote_repo, &uri)?;
+    log::debug!("status_all url: {url}");
+
+    let client = client::new_for_url(&url)?;
+    match client.get(&url).send().await {
+        Ok(res) => {
+            let body = client::parse_json_body(&url, res).await?;
+            let response: Result<AllStagedStatusResponse, serde_json::Error> =
+                serde_json::from_str(&body);
+            match response {
+                Ok(val) => Ok(val.identifiers),
+                Err(err) => Err(OxenError::basic_str(format!(
+                    "api::staging::status_all error parsing response from {url}\n\nErr {err:?} \n\n{body}"
+                ))),
+            }
+        }
+        Err(err) => {
+            let err = format!("api::staging::status_all Request failed: {url}\nErr {err:?}");
+            Err(OxenError::basic_str(err))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -13,39 +13,53 @@ pub mod config;
 pub mod db_inspect;
 pub mod df;
 pub mod diff;
+pub mod export;
 pub mod fetch;
 pub mod helpers;
 pub mod info;
 pub mod init;
 pub mod load;
+pub mod materialize;
 pub mod merge;
 pub mod migrate;
+pub mod mv;
 pub mod pull;
 pub mod push;
+pub mod reflog;
 pub mod remote;
 pub mod restore;
 pub mod rm;
 pub mod save;
 pub mod schemas;
+pub mod stash;
 pub mod status;
+pub mod verify;
 
-pub use crate::command::add::add;
+pub use crate::command::add::{add, add_with_opts};
 pub use crate::command::branch::unlock;
 pub use crate::command::checkout::{
-    checkout, checkout_combine, checkout_ours, checkout_theirs, create_checkout,
+    checkout, checkout_combine, checkout_ours, checkout_path, checkout_theirs, checkout_track,
+    create_checkout,
 };
 pub use crate::command::clone::{clone, clone_url, deep_clone_url, shallow_clone_url};
 pub use crate::command::commit::commit;
 pub use crate::command::df::{df, schema};
 pub use crate::command::diff::{diff, diff_commits};
+pub use crate::command::export::export;
 pub use crate::command::fetch::fetch;
 pub use crate::command::info::info;
-pub use crate::command::init::init;
+pub use crate::command::init::{init, init_bare};
 pub use crate::command::load::load;
+pub use crate::command::materialize::materialize;
 pub use crate::command::merge::merge;
+pub use crate::command::mv::mv;
 pub use crate::command::pull::{pull, pull_all, pull_remote_branch, pull_shallow};
-pub use crate::command::push::{push, push_remote_branch, push_remote_repo_branch_name};
+pub use crate::command::push::{
+    push, push_remote_branch, push_remote_repo_branch_name, push_set_upstream,
+};
+pub use crate::command::reflog::reflog;
 pub use crate::command::restore::restore;
 pub use crate::command::rm::rm;
-pub use crate::command::save::save;
+pub use crate::command::save::{save, save_with_compression};
 pub use crate::command::status::{status, status_from_dir};
+pub use crate::command::verify::verify;
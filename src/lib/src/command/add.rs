@@ -4,11 +4,13 @@
 //!
 
 use glob::glob;
+use ignore::gitignore::Gitignore;
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
 use super::helpers;
 use crate::core::index::{oxenignore, CommitEntryReader, SchemaReader, Stager};
+use crate::opts::AddOpts;
 use crate::{api, error::OxenError, model::LocalRepository};
 /// # Stage files into repository
 ///
@@ -38,11 +40,30 @@ use crate::{api, error::OxenError, model::LocalRepository};
 /// # }
 /// ```
 pub fn add<P: AsRef<Path>>(repo: &LocalRepository, path: P) -> Result<(), OxenError> {
+    let ignore = oxenignore::create(repo);
+    add_with_ignore(repo, path, &ignore)
+}
+
+/// # Stage files into repository, skipping paths that match `opts.excludes`
+/// The excludes are applied on top of the .oxenignore file.
+pub fn add_with_opts<P: AsRef<Path>>(
+    repo: &LocalRepository,
+    path: P,
+    opts: &AddOpts,
+) -> Result<(), OxenError> {
+    let ignore = oxenignore::create_with_excludes(repo, &opts.excludes);
+    add_with_ignore(repo, path, &ignore)
+}
+
+fn add_with_ignore<P: AsRef<Path>>(
+    repo: &LocalRepository,
+    path: P,
+    ignore: &Option<Gitignore>,
+) -> Result<(), OxenError> {
     let stager = Stager::new_with_merge(repo)?;
     let commit = api::local::commits::head_commit(repo)?;
     let reader = CommitEntryReader::new(repo, &commit)?;
     let schema_reader = SchemaReader::new(repo, &commit.id)?;
-    let ignore = oxenignore::create(repo);
     log::debug!("---START--- oxen add: {:?}", path.as_ref());
 
     // Collect paths that match the glob pattern either:
@@ -66,7 +87,7 @@ pub fn add<P: AsRef<Path>>(repo: &LocalRepository, path: P) -> Result<(), OxenEr
 
     // Get all entries in the head commit
     for path in paths {
-        stager.add(path.as_ref(), &reader, &schema_reader, &ignore)?;
+        stager.add(path.as_ref(), &reader, &schema_reader, ignore)?;
     }
 
     log::debug!("---END--- oxen add: {:?}", path.as_ref());
@@ -81,6 +102,7 @@ mod tests {
     use crate::api;
     use crate::command;
     use crate::error::OxenError;
+    use crate::opts::AddOpts;
     use crate::test;
     use crate::util;
 
@@ -104,6 +126,37 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_command_add_dir_with_exclude() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let data_dir = repo.path.join("data");
+            util::fs::create_dir_all(data_dir.join("nested"))?;
+            util::fs::write_to_path(data_dir.join("a.txt"), "a")?;
+            util::fs::write_to_path(data_dir.join("b.bin"), "b")?;
+            util::fs::write_to_path(data_dir.join("nested").join("c.txt"), "c")?;
+            util::fs::write_to_path(data_dir.join("nested").join("d.bin"), "d")?;
+
+            let opts = AddOpts {
+                paths: vec![data_dir.clone()],
+                directory: None,
+                is_remote: false,
+                excludes: vec![String::from("*.bin")],
+                chunked: false,
+                force: false,
+            };
+            command::add_with_opts(&repo, &data_dir, &opts)?;
+
+            let status = command::status(&repo)?;
+            assert_eq!(status.staged_files.len(), 2);
+            assert!(status
+                .staged_files
+                .keys()
+                .all(|path| path.extension().unwrap() == "txt"));
+
+            Ok(())
+        })
+    }
+
     #[test]
     fn test_command_add_modified_file_in_subdirectory() -> Result<(), OxenError> {
         test::run_training_data_repo_test_fully_committed(|repo| {
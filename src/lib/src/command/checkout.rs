@@ -6,9 +6,9 @@
 use std::path::Path;
 
 use crate::core::df::tabular;
-use crate::core::index::MergeConflictReader;
+use crate::core::index::{self, reflog, EntryIndexer, MergeConflictReader};
 use crate::error::OxenError;
-use crate::model::{Branch, LocalRepository};
+use crate::model::{Branch, LocalRepository, RemoteBranch};
 use crate::opts::{DFOpts, RestoreOpts};
 use crate::{api, command, util};
 
@@ -28,9 +28,15 @@ pub async fn checkout(
         }
 
         println!("Checkout branch: {value}");
+        let (from, old_commit_id) = head_position(repo)?;
         api::local::branches::set_working_branch(repo, value).await?;
         api::local::branches::set_head(repo, value)?;
-        api::local::branches::get_by_name(repo, value)
+        let branch = api::local::branches::get_by_name(repo, value)?;
+        if let Some(branch) = &branch {
+            let message = format!("checkout: moving from {from} to {value}");
+            reflog::record(repo, old_commit_id, &branch.commit_id, message);
+        }
+        Ok(branch)
     } else {
         // If we are already on the commit, do nothing
         if api::local::branches::is_checked_out(repo, value) {
@@ -38,22 +44,91 @@ pub async fn checkout(
             return Ok(None);
         }
 
+        let (from, old_commit_id) = head_position(repo)?;
         api::local::branches::set_working_commit_id(repo, value).await?;
         api::local::branches::set_head(repo, value)?;
+        let message = format!("checkout: moving from {from} to {value}");
+        reflog::record(repo, old_commit_id, value, message);
         Ok(None)
     }
 }
 
+// The branch or commit HEAD points at, for the reflog message, and its commit id
+fn head_position(repo: &LocalRepository) -> Result<(String, Option<String>), OxenError> {
+    let old_commit_id = reflog::head_commit_id(repo);
+    let from = match api::local::branches::current_branch(repo)? {
+        Some(branch) => branch.name,
+        None => old_commit_id.clone().unwrap_or_default(),
+    };
+    Ok((from, old_commit_id))
+}
+
 /// Create and checkout a branch
 pub fn create_checkout<S: AsRef<str>>(
     repo: &LocalRepository,
     value: S,
 ) -> Result<Branch, OxenError> {
-    api::local::branches::create_checkout(repo, value.as_ref())
+    let (from, old_commit_id) = head_position(repo)?;
+    let branch = api::local::branches::create_checkout(repo, value.as_ref())?;
+    let message = format!("checkout: moving from {from} to {}", branch.name);
+    reflog::record(repo, old_commit_id, &branch.commit_id, message);
+    Ok(branch)
+}
+
+/// # Create a local branch from a remote one and check it out
+/// `value` is of the form `remote/branch`, ie `origin/feature`. This pulls the head commit of the
+/// remote branch, creates a local branch of the same name tracking it, then switches to it.
+pub async fn checkout_track(
+    repo: &mut LocalRepository,
+    value: impl AsRef<str>,
+) -> Result<Branch, OxenError> {
+    let value = value.as_ref();
+    let Some((remote_name, branch_name)) = value.split_once('/') else {
+        return Err(OxenError::basic_str(format!(
+            "Invalid remote branch '{value}', expected the form <remote>/<branch>"
+        )));
+    };
+
+    if api::local::branches::exists(repo, branch_name)? {
+        return Err(OxenError::basic_str(format!(
+            "Branch already exists: {branch_name}"
+        )));
+    }
+
+    let remote = repo
+        .get_remote(remote_name)
+        .ok_or(OxenError::remote_not_set(remote_name))?;
+    let remote_repo = api::remote::repositories::get_by_remote(&remote)
+        .await?
+        .ok_or(OxenError::remote_repo_not_found(&remote.url))?;
+
+    let rb = RemoteBranch {
+        remote: remote.name.to_owned(),
+        branch: branch_name.to_owned(),
+    };
+
+    println!("Track remote branch: {value}");
+    let indexer = EntryIndexer::new(repo)?;
+    let commit = indexer
+        .pull_most_recent_commit_object(&remote_repo, &rb, false)
+        .await?
+        .ok_or_else(|| OxenError::remote_branch_not_found(branch_name))?;
+    indexer
+        .pull_all_entries_for_commit(&remote_repo, &commit)
+        .await?;
+    index::commit_sync_status::mark_commit_as_synced(repo, &commit)?;
+
+    repo.set_upstream(branch_name, rb);
+    repo.save_default()?;
+
+    checkout(repo, branch_name)
+        .await?
+        .ok_or_else(|| OxenError::local_branch_not_found(branch_name))
 }
 
 /// # Checkout a file and take their changes
-/// This overwrites the current file with the changes in the branch we are merging in
+/// This overwrites the current file with the changes in the branch we are merging in,
+/// and stages it to mark the conflict as resolved
 pub fn checkout_theirs(repo: &LocalRepository, path: impl AsRef<Path>) -> Result<(), OxenError> {
     let merger = MergeConflictReader::new(repo)?;
     let conflicts = merger.list_conflicts()?;
@@ -71,15 +146,17 @@ pub fn checkout_theirs(repo: &LocalRepository, path: impl AsRef<Path>) -> Result
         // Lookup the file for the merge commit entry and copy it over
         command::restore(
             repo,
-            RestoreOpts::from_path_ref(path, conflict.merge_entry.commit_id.clone()),
-        )
+            RestoreOpts::from_path_ref(&path, conflict.merge_entry.commit_id.clone()),
+        )?;
+        command::add(repo, repo.path.join(path))
     } else {
         Err(OxenError::could_not_find_merge_conflict(path))
     }
 }
 
 /// # Checkout a file and take our changes
-/// This overwrites the current file with the changes we had in our current branch
+/// This overwrites the current file with the changes we had in our current branch,
+/// and stages it to mark the conflict as resolved
 pub fn checkout_ours(repo: &LocalRepository, path: impl AsRef<Path>) -> Result<(), OxenError> {
     let merger = MergeConflictReader::new(repo)?;
     let conflicts = merger.list_conflicts()?;
@@ -97,13 +174,38 @@ pub fn checkout_ours(repo: &LocalRepository, path: impl AsRef<Path>) -> Result<(
         // Lookup the file for the base commit entry and copy it over
         command::restore(
             repo,
-            RestoreOpts::from_path_ref(path, conflict.base_entry.commit_id.clone()),
-        )
+            RestoreOpts::from_path_ref(&path, conflict.base_entry.commit_id.clone()),
+        )?;
+        command::add(repo, repo.path.join(path))
     } else {
         Err(OxenError::could_not_find_merge_conflict(path))
     }
 }
 
+/// # Checkout a single path from a commit
+/// Writes the version of `path` from `revision` into the working directory and stages it,
+/// without moving HEAD. `path` can be a file or a directory, relative to the repository root.
+pub fn checkout_path(
+    repo: &LocalRepository,
+    revision: impl AsRef<str>,
+    path: impl AsRef<Path>,
+) -> Result<(), OxenError> {
+    let revision = revision.as_ref();
+    let path = path.as_ref();
+    let path = if path.is_absolute() {
+        util::fs::path_relative_to_dir(path, &repo.path)?
+    } else {
+        path.to_path_buf()
+    };
+
+    let commit = api::local::revisions::get(repo, revision)?
+        .ok_or_else(|| OxenError::revision_not_found(revision.into()))?;
+    log::debug!("checkout_path {:?} from commit {}", path, commit.id);
+
+    command::restore(repo, RestoreOpts::from_path_ref(&path, commit.id))?;
+    command::add(repo, repo.path.join(&path))
+}
+
 /// # Combine Conflicting Tabular Data Files
 /// This overwrites the current file with the changes in their file
 pub fn checkout_combine<P: AsRef<Path>>(repo: &LocalRepository, path: P) -> Result<(), OxenError> {
@@ -158,11 +260,15 @@ pub fn checkout_combine<P: AsRef<Path>>(repo: &LocalRepository, path: P) -> Resu
 mod tests {
     use crate::api;
     use crate::command;
+    use crate::constants;
     use crate::constants::DEFAULT_BRANCH_NAME;
     use crate::error::OxenError;
+    use crate::model::LocalRepository;
     use crate::test;
     use crate::util;
 
+    use std::path::Path;
+
     #[tokio::test]
     async fn test_command_checkout_non_existant_commit_id() -> Result<(), OxenError> {
         test::run_empty_local_repo_test_async(|repo| async move {
@@ -215,6 +321,43 @@ mod tests {
         .await
     }
 
+    #[test]
+    fn test_command_checkout_path_from_older_commit() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let hello_file = repo.path.join("hello.txt");
+            let world_file = repo.path.join("world.txt");
+            util::fs::write_to_path(&hello_file, "Hello v1")?;
+            util::fs::write_to_path(&world_file, "World v1")?;
+            command::add(&repo, &repo.path)?;
+            command::commit(&repo, "Adding hello and world")?;
+
+            util::fs::write_to_path(&hello_file, "Hello v2")?;
+            command::add(&repo, &hello_file)?;
+            command::commit(&repo, "Updating hello")?;
+
+            util::fs::write_to_path(&hello_file, "Hello v3")?;
+            util::fs::write_to_path(&world_file, "World v3")?;
+            command::add(&repo, &repo.path)?;
+            let head = command::commit(&repo, "Updating hello and world")?;
+
+            command::checkout_path(&repo, "HEAD~2", "hello.txt")?;
+
+            // Only hello.txt is restored, and it is staged
+            assert_eq!(util::fs::read_from_path(&hello_file)?, "Hello v1");
+            assert_eq!(util::fs::read_from_path(&world_file)?, "World v3");
+            let status = command::status(&repo)?;
+            assert_eq!(status.staged_files.len(), 1);
+            assert!(status.staged_files.contains_key(Path::new("hello.txt")));
+            assert!(status.modified_files.is_empty());
+
+            // HEAD did not move
+            let current = api::local::commits::head_commit(&repo)?;
+            assert_eq!(current.id, head.id);
+
+            Ok(())
+        })
+    }
+
     #[tokio::test]
     async fn test_command_checkout_current_branch_name_does_nothing() -> Result<(), OxenError> {
         test::run_empty_local_repo_test_async(|repo| async move {
@@ -816,4 +959,128 @@ mod tests {
         })
         .await
     }
+
+    #[tokio::test]
+    async fn test_checkout_theirs_and_ours_stage_resolved_conflicts() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test_async(|repo| async move {
+            let og_branch = DEFAULT_BRANCH_NAME;
+            let theirs_path = repo.path.join("theirs.txt");
+            let ours_path = repo.path.join("ours.txt");
+            util::fs::write_to_path(&theirs_path, "base theirs")?;
+            util::fs::write_to_path(&ours_path, "base ours")?;
+            command::add(&repo, &repo.path)?;
+            command::commit(&repo, "Adding base files")?;
+
+            // Change both files on a branch
+            let branch_name = "feature";
+            api::local::branches::create_checkout(&repo, branch_name)?;
+            util::fs::write_to_path(&theirs_path, "branch theirs")?;
+            util::fs::write_to_path(&ours_path, "branch ours")?;
+            command::add(&repo, &repo.path)?;
+            command::commit(&repo, "Changing files on branch")?;
+
+            // Change both files on main
+            command::checkout(&repo, og_branch).await?;
+            util::fs::write_to_path(&theirs_path, "main theirs")?;
+            util::fs::write_to_path(&ours_path, "main ours")?;
+            command::add(&repo, &repo.path)?;
+            command::commit(&repo, "Changing files on main")?;
+
+            // Both files conflict
+            command::merge(&repo, branch_name)?;
+            let status = command::status(&repo)?;
+            assert_eq!(status.merge_conflicts.len(), 2);
+
+            command::checkout_theirs(&repo, "theirs.txt")?;
+            command::checkout_ours(&repo, "ours.txt")?;
+
+            assert_eq!(util::fs::read_from_path(&theirs_path)?, "branch theirs");
+            assert_eq!(util::fs::read_from_path(&ours_path)?, "main ours");
+
+            // Both sides are staged and no longer in conflict
+            let status = command::status(&repo)?;
+            assert!(status.merge_conflicts.is_empty());
+            let staged_theirs = status.staged_files.get(Path::new("theirs.txt")).unwrap();
+            assert_eq!(
+                staged_theirs.hash,
+                util::hasher::hash_file_contents(&theirs_path)?
+            );
+            let staged_ours = status.staged_files.get(Path::new("ours.txt")).unwrap();
+            assert_eq!(
+                staged_ours.hash,
+                util::hasher::hash_file_contents(&ours_path)?
+            );
+
+            Ok(())
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_command_checkout_track_remote_branch() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test_async(|mut repo| async move {
+            // Set the proper remote
+            let remote = test::repo_remote_url_from(&repo.dirname());
+            command::config::set_remote(&mut repo, constants::DEFAULT_REMOTE_NAME, &remote)?;
+
+            // Create Remote
+            let remote_repo = test::create_remote_repo(&repo).await?;
+
+            // Push main, then a feature branch with an extra file
+            let hello_file = repo.path.join("hello.txt");
+            util::fs::write_to_path(&hello_file, "Hello")?;
+            command::add(&repo, &hello_file)?;
+            command::commit(&repo, "Adding hello")?;
+            command::push(&repo).await?;
+
+            let branch_name = "feature";
+            command::create_checkout(&repo, branch_name)?;
+            let feature_file = repo.path.join("feature.txt");
+            util::fs::write_to_path(&feature_file, "A feature")?;
+            command::add(&repo, &feature_file)?;
+            let feature_commit = command::commit(&repo, "Adding feature")?;
+            command::push(&repo).await?;
+
+            test::run_empty_dir_test_async(|new_repo_dir| async move {
+                let mut cloned_repo =
+                    command::clone_url(&remote_repo.remote.url, &new_repo_dir.join("new_repo"))
+                        .await?;
+                assert!(!api::local::branches::exists(&cloned_repo, branch_name)?);
+
+                let branch = command::checkout_track(
+                    &mut cloned_repo,
+                    format!("{}/{}", constants::DEFAULT_REMOTE_NAME, branch_name),
+                )
+                .await?;
+                assert_eq!(branch.name, branch_name);
+                assert_eq!(branch.commit_id, feature_commit.id);
+
+                // The local branch exists, is checked out and has the remote files
+                assert!(api::local::branches::exists(&cloned_repo, branch_name)?);
+                let current_branch = api::local::branches::current_branch(&cloned_repo)?.unwrap();
+                assert_eq!(current_branch.name, branch_name);
+                assert!(cloned_repo.path.join("feature.txt").exists());
+
+                // The tracking info is persisted to the config
+                let reloaded = LocalRepository::from_dir(&cloned_repo.path)?;
+                let upstream = reloaded.upstream(branch_name).unwrap();
+                assert_eq!(upstream.remote, constants::DEFAULT_REMOTE_NAME);
+                assert_eq!(upstream.branch, branch_name);
+
+                // Tracking it again fails since the branch now exists locally
+                let result = command::checkout_track(
+                    &mut cloned_repo,
+                    format!("{}/{}", constants::DEFAULT_REMOTE_NAME, branch_name),
+                )
+                .await;
+                assert!(result.is_err());
+
+                api::remote::repositories::delete(&remote_repo).await?;
+
+                Ok(new_repo_dir)
+            })
+            .await
+        })
+        .await
+    }
 }
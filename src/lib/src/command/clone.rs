@@ -3,14 +3,15 @@
 //! Clone data from a remote repository
 //!
 
-use std::path::Path;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
 use crate::config::RemoteConfig;
 use crate::constants::{DEFAULT_BRANCH_NAME, DEFAULT_REMOTE_NAME, REPO_CONFIG_FILENAME};
-use crate::core::index::EntryIndexer;
+use crate::core::index::{CommitEntryReader, EntryIndexer};
 use crate::error::OxenError;
 use crate::model::{LocalRepository, Remote, RemoteBranch, RemoteRepository};
-use crate::opts::{CloneOpts, PullOpts};
+use crate::opts::{CloneOpts, DFDefaults, PullOpts};
 use crate::util::progress_bar::{oxen_progress_bar, ProgressBarType};
 use crate::{api, util};
 
@@ -60,6 +61,8 @@ async fn _clone(
         dst: dst.as_ref().to_owned(),
         shallow,
         all,
+        into: false,
+        lfs_threshold: None,
         branch: DEFAULT_BRANCH_NAME.to_string(),
     };
     clone(&opts).await
@@ -91,11 +94,18 @@ async fn clone_repo(
 ) -> Result<LocalRepository, OxenError> {
     api::remote::repositories::pre_clone(&remote_repo).await?;
 
-    // if directory already exists -> return Err
+    // if directory already exists -> return Err, unless we are cloning into it
     let repo_path = &opts.dst;
     if repo_path.exists() {
-        let err = format!("Directory already exists: {}", remote_repo.name);
-        return Err(OxenError::basic_str(err));
+        if !opts.into {
+            let err = format!("Directory already exists: {}", remote_repo.name);
+            return Err(OxenError::basic_str(err));
+        }
+
+        if util::fs::oxen_hidden_dir(repo_path).exists() {
+            let err = format!("Directory is already an oxen repository: {:?}", repo_path);
+            return Err(OxenError::basic_str(err));
+        }
     }
 
     // if directory does not exist, create it
@@ -115,6 +125,11 @@ async fn clone_repo(
     let remote_cfg = RemoteConfig {
         remote_name: Some(DEFAULT_REMOTE_NAME.to_string()),
         remotes: vec![remote_repo.remote.clone()],
+        upstreams: BTreeMap::new(),
+        hooks_enabled: false,
+        df_defaults: DFDefaults::default(),
+        bare: false,
+        column_access: BTreeMap::new(),
     };
 
     let toml = toml::to_string(&remote_cfg)?;
@@ -123,6 +138,17 @@ async fn clone_repo(
     // Pull all commit objects, but not entries
     let rb = RemoteBranch::from_branch(&opts.branch);
     let indexer = EntryIndexer::new(&local_repo)?;
+
+    // Make sure we are not going to overwrite any of the existing files
+    if opts.into && !opts.shallow {
+        if let Err(err) = check_clone_into_conflicts(&local_repo, &remote_repo, &indexer, &rb).await
+        {
+            util::fs::remove_dir_all(&oxen_hidden_path)?;
+            return Err(err);
+        }
+    }
+
+    local_repo.write_lfs_threshold(opts.lfs_threshold)?;
     maybe_pull_entries(&local_repo, &remote_repo, &indexer, &rb, opts).await?;
 
     if opts.all {
@@ -167,6 +193,47 @@ async fn clone_repo(
     Ok(local_repo)
 }
 
+/// Errors with the list of conflicting paths if any of the files in the branch already
+/// exist in the directory we are cloning into
+async fn check_clone_into_conflicts(
+    local_repo: &LocalRepository,
+    remote_repo: &RemoteRepository,
+    indexer: &EntryIndexer,
+    rb: &RemoteBranch,
+) -> Result<(), OxenError> {
+    let should_update_head = false;
+    let Some(commit) = indexer
+        .pull_most_recent_commit_object(remote_repo, rb, should_update_head)
+        .await?
+    else {
+        return Ok(());
+    };
+
+    let reader = CommitEntryReader::new(local_repo, &commit)?;
+    let mut conflicts: Vec<PathBuf> = reader
+        .list_entries()?
+        .into_iter()
+        .map(|entry| entry.path)
+        .filter(|path| local_repo.path.join(path).exists())
+        .collect();
+
+    if conflicts.is_empty() {
+        return Ok(());
+    }
+
+    conflicts.sort();
+    let paths = conflicts
+        .iter()
+        .map(|path| format!("  {}", path.to_string_lossy()))
+        .collect::<Vec<String>>()
+        .join("\n");
+    let err = format!(
+        "Cannot clone into {:?}, the following files would be overwritten:\n{}",
+        local_repo.path, paths
+    );
+    Err(OxenError::basic_str(err))
+}
+
 async fn maybe_pull_entries(
     local_repo: &LocalRepository,
     remote_repo: &RemoteRepository,
@@ -273,6 +340,47 @@ mod tests {
         .await
     }
 
+    #[tokio::test]
+    async fn test_clone_into_existing_dir() -> Result<(), OxenError> {
+        test::run_training_data_fully_sync_remote(|_local_repo, remote_repo| async move {
+            let cloned_remote = remote_repo.clone();
+
+            test::run_empty_dir_test_async(|dir| async move {
+                // Extra file that is not in the repo is kept
+                let into_dir = dir.join("existing");
+                util::fs::create_dir_all(&into_dir)?;
+                let extra_file = into_dir.join("my_notes.txt");
+                util::fs::write_to_path(&extra_file, "keep me")?;
+
+                let mut opts = CloneOpts::new(remote_repo.remote.url.to_owned(), &into_dir);
+                opts.into = true;
+                let cloned_repo = command::clone(&opts).await?;
+                assert_eq!(util::fs::read_from_path(&extra_file)?, "keep me");
+                assert!(cloned_repo.path.join("README.md").exists());
+
+                // A file that is in the repo is a conflict
+                let conflict_dir = dir.join("conflict");
+                util::fs::create_dir_all(&conflict_dir)?;
+                let conflict_file = conflict_dir.join("README.md");
+                util::fs::write_to_path(&conflict_file, "my own readme")?;
+
+                let mut opts = CloneOpts::new(remote_repo.remote.url.to_owned(), &conflict_dir);
+                opts.into = true;
+                let result = command::clone(&opts).await;
+                assert!(result.is_err());
+                assert!(result.unwrap_err().to_string().contains("README.md"));
+                assert_eq!(util::fs::read_from_path(&conflict_file)?, "my own readme");
+                assert!(!util::fs::oxen_hidden_dir(&conflict_dir).exists());
+
+                Ok(dir)
+            })
+            .await?;
+
+            Ok(cloned_remote)
+        })
+        .await
+    }
+
     // Test for clone --all that checks to make sure we have all commits, all deleted files, etc
     #[tokio::test]
     async fn test_clone_dash_all() -> Result<(), OxenError> {
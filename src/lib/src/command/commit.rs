@@ -3,12 +3,19 @@
 //! Commit the staged data
 //!
 
+use std::path::{Path, PathBuf};
+
 use crate::api;
 use crate::command;
-use crate::core::index::CommitEntryWriter;
+use crate::constants::{
+    COMMIT_EDITMSG_FILE, COMMIT_TEMPLATE_FILE, POST_COMMIT_HOOK, PRE_COMMIT_HOOK,
+};
+use crate::core::hooks;
+use crate::core::index::{reflog, CommitEntryWriter};
 use crate::error;
 use crate::error::OxenError;
 use crate::model::{Commit, LocalRepository};
+use crate::util;
 
 /// # Commit the staged files in the repo
 ///
@@ -40,18 +47,102 @@ use crate::model::{Commit, LocalRepository};
 /// # }
 /// ```
 pub fn commit(repo: &LocalRepository, message: &str) -> Result<Commit, OxenError> {
+    commit_with_opts(repo, message, false)
+}
+
+/// # Commit even if nothing is staged
+/// The new commit has the same tree as HEAD, useful for marking a state of the repo
+pub fn commit_allow_empty(repo: &LocalRepository, message: &str) -> Result<Commit, OxenError> {
+    commit_with_opts(repo, message, true)
+}
+
+/// # Read a commit message from a file
+/// Comment lines starting with `#` are stripped, errors if nothing is left
+pub fn message_from_file(path: impl AsRef<Path>) -> Result<String, OxenError> {
+    let path = path.as_ref();
+    if !path.exists() {
+        return Err(OxenError::path_does_not_exist(path));
+    }
+    let contents = util::fs::read_from_path(path)?;
+    let message = clean_message(&contents);
+    if message.is_empty() {
+        return Err(OxenError::basic_str(
+            "Aborting commit due to empty commit message.",
+        ));
+    }
+    Ok(message)
+}
+
+/// Strip comment lines and surrounding blank lines from a commit message
+pub fn clean_message(message: &str) -> String {
+    message
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .map(|line| line.trim_end())
+        .collect::<Vec<&str>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+/// Path to the repo commit template, .oxen/commit_template
+pub fn template_path(repo: &LocalRepository) -> PathBuf {
+    util::fs::oxen_hidden_dir(&repo.path).join(COMMIT_TEMPLATE_FILE)
+}
+
+/// Contents of the repo commit template if one has been written
+pub fn template(repo: &LocalRepository) -> Result<Option<String>, OxenError> {
+    let path = template_path(repo);
+    if !path.exists() {
+        return Ok(None);
+    }
+    Ok(Some(util::fs::read_from_path(path)?))
+}
+
+/// Scratch file the editor writes the message to, prefilled with the template
+pub fn prepare_message_file(repo: &LocalRepository) -> Result<PathBuf, OxenError> {
+    let path = util::fs::oxen_hidden_dir(&repo.path).join(COMMIT_EDITMSG_FILE);
+    let mut contents = template(repo)?.unwrap_or_default();
+    if !contents.is_empty() && !contents.ends_with('\n') {
+        contents.push('\n');
+    }
+    contents.push_str("# Please enter the commit message for your changes. Lines starting\n");
+    contents.push_str("# with '#' will be ignored, and an empty message aborts the commit.\n");
+    util::fs::write_to_path(&path, contents)?;
+    Ok(path)
+}
+
+fn commit_with_opts(
+    repo: &LocalRepository,
+    message: &str,
+    allow_empty: bool,
+) -> Result<Commit, OxenError> {
     let status = command::status::status_without_untracked(repo)?;
 
-    if !status.has_added_entries() && status.staged_schemas.is_empty() {
+    if !allow_empty && !status.has_added_entries() && status.staged_schemas.is_empty() {
         return Err(OxenError::NothingToCommit(
             error::string_error::StringError::new(
                 r"No files are staged, not committing.
-Stage a file or directory with `oxen add <file>`"
+Stage a file or directory with `oxen add <file>`, or use --allow-empty"
                     .to_string(),
             ),
         ));
     }
+
+    let mut staged_files: Vec<PathBuf> = status.staged_files.keys().cloned().collect();
+    staged_files.sort();
+    hooks::run(repo, PRE_COMMIT_HOOK, &staged_files)
+        .map_err(|err| OxenError::basic_str(format!("{err}, not committing")))?;
+
+    let old_commit_id = reflog::head_commit_id(repo);
     let commit = api::local::commits::commit(repo, &status, message)?;
+    let summary = message.lines().next().unwrap_or_default();
+    reflog::record(
+        repo,
+        old_commit_id,
+        &commit.id,
+        format!("commit: {summary}"),
+    );
     // Open then close commit entry writer to force indexing on rocksbds
     {
         // Get time here
@@ -59,6 +150,12 @@ Stage a file or directory with `oxen add <file>`"
         let _ = CommitEntryWriter::new(repo, &commit)?;
         let _elapsed = start.elapsed();
     }
+
+    // The commit is already made, so a failing post-commit hook only warns
+    if let Err(err) = hooks::run(repo, POST_COMMIT_HOOK, &staged_files) {
+        log::warn!("{err}");
+        eprintln!("Warning: {err}");
+    }
     log::info!("DONE COMMITTING in command::commit {}", commit);
     Ok(commit)
 }
@@ -340,4 +437,150 @@ mod tests {
             Ok(())
         })
     }
+
+    #[test]
+    fn test_command_commit_empty_is_refused() -> Result<(), OxenError> {
+        test::run_training_data_repo_test_fully_committed(|repo| {
+            let head = api::local::commits::head_commit(&repo)?;
+
+            let result = command::commit(&repo, "Nothing changed");
+            assert!(matches!(result, Err(OxenError::NothingToCommit(_))));
+            assert_eq!(api::local::commits::head_commit(&repo)?.id, head.id);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_command_commit_message_from_file() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let hello_file = repo.path.join("hello.txt");
+            util::fs::write_to_path(&hello_file, "Hello World")?;
+            command::add(&repo, &hello_file)?;
+
+            let message_file = repo.path.join("message.txt");
+            util::fs::write_to_path(
+                &message_file,
+                "Add hello file\n\nSays hello to the world\n# Please enter the commit message\n# Lines starting with '#' will be ignored\n",
+            )?;
+
+            let message = command::commit::message_from_file(&message_file)?;
+            let commit = command::commit(&repo, &message)?;
+
+            let commit = api::local::commits::get_by_id(&repo, &commit.id)?.unwrap();
+            assert_eq!(commit.message, "Add hello file\n\nSays hello to the world");
+            assert!(!commit.message.contains('#'));
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_command_commit_message_file_only_comments() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let message_file = repo.path.join("message.txt");
+            util::fs::write_to_path(&message_file, "# nothing to see here\n\n")?;
+
+            let result = command::commit::message_from_file(&message_file);
+            assert!(result.is_err());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_command_commit_template_prefills_message_file() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            assert!(command::commit::template(&repo)?.is_none());
+
+            let template = "[dataset] \n\n# Describe the data that changed";
+            util::fs::write_to_path(command::commit::template_path(&repo), template)?;
+
+            let path = command::commit::prepare_message_file(&repo)?;
+            let contents = util::fs::read_from_path(&path)?;
+            assert!(contents.starts_with(template));
+
+            // Nothing was filled in but the template prefix
+            let message = command::commit::message_from_file(&path)?;
+            assert_eq!(message, "[dataset]");
+
+            Ok(())
+        })
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_command_commit_pre_commit_hook_rejects_staged_file() -> Result<(), OxenError> {
+        use std::os::unix::fs::PermissionsExt;
+
+        use crate::constants::{POST_COMMIT_HOOK, PRE_COMMIT_HOOK};
+        use crate::core::hooks;
+        use crate::opts::RestoreOpts;
+
+        test::run_empty_local_repo_test(|mut repo| {
+            let hooks_dir = hooks::hooks_dir(&repo);
+            util::fs::create_dir_all(&hooks_dir)?;
+            let write_hook = |name: &str, script: &str| -> Result<(), OxenError> {
+                let path = hooks_dir.join(name);
+                util::fs::write_to_path(&path, script)?;
+                std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))?;
+                Ok(())
+            };
+            write_hook(
+                PRE_COMMIT_HOOK,
+                "#!/bin/sh\nif grep -q secret; then\n  echo 'secrets are not allowed' >&2\n  exit 1\nfi\n",
+            )?;
+            write_hook(POST_COMMIT_HOOK, "#!/bin/sh\ntouch post_commit_ran\n")?;
+
+            // Hooks are off until enabled
+            let secret_file = repo.path.join("secret.txt");
+            util::fs::write_to_path(&secret_file, "password")?;
+            command::add(&repo, &secret_file)?;
+            command::commit(&repo, "Hooks are not enabled")?;
+            assert!(!repo.path.join("post_commit_ran").exists());
+
+            repo.set_hooks_enabled(true);
+            repo.save_default()?;
+            let num_commits = api::local::commits::list(&repo)?.len();
+
+            // The pre-commit hook rejects the staged secret
+            let secret_file = repo.path.join("another_secret.txt");
+            util::fs::write_to_path(&secret_file, "password")?;
+            command::add(&repo, &secret_file)?;
+            let result = command::commit(&repo, "Adding a secret");
+            assert!(result.is_err());
+            assert_eq!(api::local::commits::list(&repo)?.len(), num_commits);
+            assert!(!repo.path.join("post_commit_ran").exists());
+
+            // Other files make it through and run the post-commit hook
+            command::restore(&repo, RestoreOpts::from_staged_path("another_secret.txt"))?;
+            let hello_file = repo.path.join("hello.txt");
+            util::fs::write_to_path(&hello_file, "Hello World")?;
+            command::add(&repo, &hello_file)?;
+            command::commit(&repo, "Adding hello")?;
+            assert_eq!(api::local::commits::list(&repo)?.len(), num_commits + 1);
+            assert!(repo.path.join("post_commit_ran").exists());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_command_commit_allow_empty() -> Result<(), OxenError> {
+        test::run_training_data_repo_test_fully_committed(|repo| {
+            let head = api::local::commits::head_commit(&repo)?;
+            let num_entries = CommitEntryReader::new(&repo, &head)?.num_entries()?;
+
+            let commit = command::commit::commit_allow_empty(&repo, "Marking a state")?;
+            assert_ne!(commit.id, head.id);
+            assert_eq!(commit.parent_ids, vec![head.id]);
+            assert_eq!(api::local::commits::head_commit(&repo)?.id, commit.id);
+
+            // Same files as the parent
+            let reader = CommitEntryReader::new(&repo, &commit)?;
+            assert_eq!(reader.num_entries()?, num_entries);
+
+            Ok(())
+        })
+    }
 }
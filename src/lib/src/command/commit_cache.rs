@@ -4,16 +4,36 @@
 //! were created before the cache was introduced.
 //!
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use crate::api;
 use crate::core::cache;
 use crate::error::OxenError;
-use crate::model::LocalRepository;
+use crate::model::{Commit, LocalRepository};
 use crate::opts::LogOpts;
 
-/// Run the computation cache on all repositories within a directory
-pub async fn compute_cache_on_all_repos(path: &Path, force: bool) -> Result<(), OxenError> {
+/// The repositories the cache was computed on, and the ones that failed with their error
+#[derive(Debug, Default)]
+pub struct ComputeCacheSummary {
+    pub succeeded: Vec<PathBuf>,
+    pub failed: Vec<(PathBuf, String)>,
+}
+
+impl ComputeCacheSummary {
+    pub fn has_failures(&self) -> bool {
+        !self.failed.is_empty()
+    }
+}
+
+/// Run the computation cache on all repositories within a directory.
+/// Failures are logged and collected in the summary so one bad repository does not block the
+/// rest, unless `fail_fast` is set in which case the first error is returned.
+pub async fn compute_cache_on_all_repos(
+    path: &Path,
+    force: bool,
+    fail_fast: bool,
+) -> Result<ComputeCacheSummary, OxenError> {
+    let mut summary = ComputeCacheSummary::default();
     let namespaces = api::local::repositories::list_namespaces(path)?;
     for namespace in namespaces {
         let namespace_path = path.join(namespace);
@@ -23,19 +43,24 @@ pub async fn compute_cache_on_all_repos(path: &Path, force: bool) -> Result<(),
             match compute_cache(&repo, None, force).await {
                 Ok(_) => {
                     println!("Done.");
+                    summary.succeeded.push(repo.path);
                 }
                 Err(err) => {
                     log::error!(
                         "Could not compute cache for repo {:?}\nErr: {}",
                         repo.path,
                         err
-                    )
+                    );
+                    if fail_fast {
+                        return Err(err);
+                    }
+                    summary.failed.push((repo.path, err.to_string()));
                 }
             }
         }
     }
 
-    Ok(())
+    Ok(summary)
 }
 
 /// Run the computation cache on all repositories within a directory
@@ -52,6 +77,8 @@ pub async fn compute_cache(
         let opts = LogOpts {
             revision: Some(revision),
             remote: false,
+            graph: false,
+            path: None,
         };
         api::local::commits::list_with_opts(repo, &opts).await?
     } else {
@@ -63,3 +90,137 @@ pub async fn compute_cache(
     }
     Ok(())
 }
+
+/// Incrementally compute the cache for the commits newer than `since_commit`, up to `revision` or HEAD.
+/// Commits that already have every cacher computed successfully are skipped unless `force` is set.
+/// Returns the commits the cachers were run on.
+pub fn compute_cache_since_commit(
+    repo: &LocalRepository,
+    since_commit: &str,
+    revision: Option<String>,
+    force: bool,
+) -> Result<Vec<Commit>, OxenError> {
+    let head = match revision {
+        Some(revision) => revision,
+        None => api::local::commits::head_commit(repo)?.id,
+    };
+    println!(
+        "Compute cache for commits since [{since_commit}] up to [{head}] on repo {:?}",
+        repo.path
+    );
+
+    let commits = api::local::commits::list_from(repo, &format!("{since_commit}..{head}"))?;
+    let mut computed: Vec<Commit> = vec![];
+    // list_from returns newest first, compute oldest first
+    for commit in commits.into_iter().rev() {
+        if !force && cache::commit_cacher::is_cached(repo, &commit)? {
+            log::debug!("Skipping already cached commit {}", commit);
+            continue;
+        }
+
+        println!("Compute cache for commit {:?}", commit);
+        cache::commit_cacher::run_all(repo, &commit, force)?;
+        computed.push(commit);
+    }
+    Ok(computed)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api;
+    use crate::command;
+    use crate::constants;
+    use crate::core::cache::commit_cacher;
+    use crate::error::OxenError;
+    use crate::test;
+    use crate::util;
+
+    #[tokio::test]
+    async fn test_compute_cache_on_all_repos_continues_on_error() -> Result<(), OxenError> {
+        test::run_empty_dir_test_async(|dir| async move {
+            let namespace_dir = dir.join("namespace");
+            let mut repos = vec![];
+            for name in ["repo_a", "repo_b", "repo_c"] {
+                let repo = command::init(&namespace_dir.join(name))?;
+                let path = repo.path.join("hello.txt");
+                util::fs::write_to_path(&path, name)?;
+                command::add(&repo, &path)?;
+                command::commit(&repo, "Adding hello")?;
+                repos.push(repo);
+            }
+
+            // Swap the commits db for a file so reading the commits fails
+            let corrupt_repo = &repos[1];
+            let commits_dir =
+                util::fs::oxen_hidden_dir(&corrupt_repo.path).join(constants::COMMITS_DIR);
+            util::fs::remove_dir_all(&commits_dir)?;
+            util::fs::write_to_path(&commits_dir, "corrupt")?;
+
+            // Fail fast stops at the corrupt repo
+            let result = command::commit_cache::compute_cache_on_all_repos(&dir, false, true).await;
+            assert!(result.is_err());
+
+            let summary =
+                command::commit_cache::compute_cache_on_all_repos(&dir, false, false).await?;
+            assert!(summary.has_failures());
+            assert_eq!(summary.failed.len(), 1);
+            assert_eq!(summary.failed[0].0, corrupt_repo.path);
+            assert_eq!(summary.succeeded.len(), 2);
+
+            for repo in [&repos[0], &repos[2]] {
+                assert!(summary.succeeded.contains(&repo.path));
+                let commit = api::local::commits::head_commit(repo)?;
+                assert!(commit_cacher::is_cached(repo, &commit)?);
+            }
+
+            Ok(dir)
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_compute_cache_since_commit() -> Result<(), OxenError> {
+        test::run_training_data_repo_test_fully_committed_async(|repo| async move {
+            command::commit_cache::compute_cache(&repo, None, false).await?;
+            let since_commit = api::local::commits::head_commit(&repo)?;
+            assert!(commit_cacher::is_cached(&repo, &since_commit)?);
+
+            // Add a couple of new commits
+            let mut new_commit_ids = vec![];
+            for i in 0..2 {
+                let path = repo.path.join(format!("new_file_{i}.txt"));
+                util::fs::write_to_path(&path, format!("new file {i}"))?;
+                command::add(&repo, &path)?;
+                let commit = command::commit(&repo, &format!("Adding file {i}"))?;
+                assert!(!commit_cacher::is_cached(&repo, &commit)?);
+                new_commit_ids.push(commit.id);
+            }
+
+            let computed = command::commit_cache::compute_cache_since_commit(
+                &repo,
+                &since_commit.id,
+                None,
+                false,
+            )?;
+
+            // Only the new commits should have been computed, oldest first
+            let computed_ids: Vec<String> = computed.iter().map(|c| c.id.to_owned()).collect();
+            assert_eq!(computed_ids, new_commit_ids);
+            for commit in computed.iter() {
+                assert!(commit_cacher::is_cached(&repo, commit)?);
+            }
+
+            // Running again is a no-op since everything is cached
+            let computed = command::commit_cache::compute_cache_since_commit(
+                &repo,
+                &since_commit.id,
+                None,
+                false,
+            )?;
+            assert!(computed.is_empty());
+
+            Ok(())
+        })
+        .await
+    }
+}
@@ -5,6 +5,7 @@
 
 use crate::error::OxenError;
 use crate::model::{LocalRepository, Remote};
+use crate::opts::DFDefaults;
 
 /// # Set the remote for a repository
 /// Tells the CLI where to push the changes to
@@ -25,3 +26,130 @@ pub fn delete_remote(repo: &mut LocalRepository, name: &str) -> Result<(), OxenE
     repo.save_default()?;
     Ok(())
 }
+
+/// # Unset a remote for a repository
+/// Errors if a local branch tracks an upstream on the remote, unless `force` is true,
+/// in which case those branches stop tracking it.
+pub fn unset_remote(repo: &mut LocalRepository, name: &str, force: bool) -> Result<(), OxenError> {
+    if !repo.has_remote(name) {
+        return Err(OxenError::basic_str(format!(
+            "Remote '{name}' does not exist"
+        )));
+    }
+
+    let branches = repo.branches_tracking_remote(name);
+    if !branches.is_empty() {
+        if !force {
+            return Err(OxenError::basic_str(format!(
+                "Remote '{name}' is the upstream of branches {branches:?}, use --force to unset it anyway"
+            )));
+        }
+        repo.remove_upstreams_for_remote(name);
+    }
+
+    repo.delete_remote(name);
+    repo.save_default()?;
+    Ok(())
+}
+
+/// # Rename a remote for a repository
+/// Branches tracking the remote keep tracking it under the new name
+pub fn rename_remote(
+    repo: &mut LocalRepository,
+    old_name: &str,
+    new_name: &str,
+) -> Result<Remote, OxenError> {
+    if !repo.has_remote(old_name) {
+        return Err(OxenError::basic_str(format!(
+            "Remote '{old_name}' does not exist"
+        )));
+    }
+    if repo.has_remote(new_name) {
+        return Err(OxenError::basic_str(format!(
+            "Remote '{new_name}' already exists"
+        )));
+    }
+
+    repo.rename_remote(old_name, new_name);
+    repo.save_default()?;
+    // Just renamed it, so it must exist
+    Ok(repo.get_remote(new_name).unwrap())
+}
+
+/// # Turn the repository hooks on or off
+/// When on, the executable scripts in .oxen/hooks are run on commit
+pub fn set_hooks_enabled(repo: &mut LocalRepository, enabled: bool) -> Result<(), OxenError> {
+    repo.set_hooks_enabled(enabled);
+    repo.save_default()?;
+    Ok(())
+}
+
+/// # Set the repository defaults for `oxen df`
+/// Page size and max display rows used when the command line or request does not set them
+pub fn set_df_defaults(repo: &mut LocalRepository, defaults: DFDefaults) -> Result<(), OxenError> {
+    repo.set_df_defaults(defaults);
+    repo.save_default()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::command;
+    use crate::error::OxenError;
+    use crate::model::{LocalRepository, RemoteBranch};
+    use crate::test;
+
+    #[test]
+    fn test_config_add_rename_unset_remote() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|mut repo| {
+            let url = "http://0.0.0.0:3000/repositories/OxenData";
+            command::config::set_remote(&mut repo, "origin", url)?;
+            let persisted = LocalRepository::from_dir(&repo.path)?;
+            assert_eq!(persisted.get_remote("origin").unwrap().url, url);
+
+            command::config::rename_remote(&mut repo, "origin", "upstream")?;
+            let persisted = LocalRepository::from_dir(&repo.path)?;
+            assert!(!persisted.has_remote("origin"));
+            assert_eq!(persisted.get_remote("upstream").unwrap().url, url);
+            assert_eq!(persisted.remote().unwrap().name, "upstream");
+
+            command::config::unset_remote(&mut repo, "upstream", false)?;
+            let persisted = LocalRepository::from_dir(&repo.path)?;
+            assert!(persisted.remotes.is_empty());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_config_unset_remote_tracked_by_upstream() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|mut repo| {
+            let url = "http://0.0.0.0:3000/repositories/OxenData";
+            command::config::set_remote(&mut repo, "origin", url)?;
+            repo.set_upstream(
+                "main",
+                RemoteBranch {
+                    remote: String::from("origin"),
+                    branch: String::from("main"),
+                },
+            );
+            repo.save_default()?;
+
+            // Renaming keeps the upstream pointed at the remote
+            command::config::rename_remote(&mut repo, "origin", "other")?;
+            let persisted = LocalRepository::from_dir(&repo.path)?;
+            assert_eq!(persisted.upstream("main").unwrap().remote, "other");
+
+            // Need --force to unset a remote a branch is tracking
+            assert!(command::config::unset_remote(&mut repo, "other", false).is_err());
+            assert!(LocalRepository::from_dir(&repo.path)?.has_remote("other"));
+
+            command::config::unset_remote(&mut repo, "other", true)?;
+            let persisted = LocalRepository::from_dir(&repo.path)?;
+            assert!(!persisted.has_remote("other"));
+            assert!(persisted.upstream("main").is_none());
+
+            Ok(())
+        })
+    }
+}
@@ -3,23 +3,43 @@
 //! Interact with DataFrames
 //!
 
+use std::io::Write;
 use std::path::Path;
 
+use polars::frame::DataFrame;
+
+use crate::command;
+use crate::core::db::df_db;
 use crate::core::df::tabular;
 use crate::error::OxenError;
+use crate::model::{DataFrameProfile, LocalRepository, Schema};
 use crate::opts::DFOpts;
 use crate::util;
 
 /// Interact with DataFrames
-pub fn df<P: AsRef<Path>>(input: P, opts: DFOpts) -> Result<(), OxenError> {
+pub fn df<P: AsRef<Path>>(input: P, opts: DFOpts) -> Result<DataFrame, OxenError> {
     let mut df = tabular::show_path(input, opts.clone())?;
 
-    if let Some(output) = opts.output {
+    if let Some(output) = &opts.output {
         println!("Writing {output:?}");
-        tabular::write_df(&mut df, output)?;
+        match opts.partition_by_columns() {
+            Some(columns) => tabular::write_df_partitioned(&mut df, output, &columns)?,
+            None => tabular::write_df(&mut df, output)?,
+        }
     }
 
-    Ok(())
+    Ok(df)
+}
+
+/// Same as [`df`], with the page size and max display rows the caller did not set taken from
+/// the repository config
+pub fn df_with_repo_defaults<P: AsRef<Path>>(
+    repo: &LocalRepository,
+    input: P,
+    mut opts: DFOpts,
+) -> Result<DataFrame, OxenError> {
+    opts.apply_defaults(repo.df_defaults());
+    df(input, opts)
 }
 
 /// Get a human readable schema for a DataFrame
@@ -27,13 +47,124 @@ pub fn schema<P: AsRef<Path>>(input: P, flatten: bool, opts: DFOpts) -> Result<S
     tabular::schema_to_string(input, flatten, &opts)
 }
 
+/// Run the transforms on a DataFrame and write the result to `writer` as an Arrow IPC stream
+/// instead of printing it, ie. `oxen df data.csv --format arrow | other-tool`
+pub fn df_to_arrow_stream<P: AsRef<Path>>(
+    input: P,
+    opts: DFOpts,
+    writer: impl Write,
+) -> Result<DataFrame, OxenError> {
+    let mut df = tabular::read_df(input, opts)?;
+    tabular::write_df_arrow_stream(&mut df, writer)?;
+    Ok(df)
+}
+
+/// Detect the column types of a file the way DuckDB will when it is indexed for editing,
+/// so they can be checked before the file is committed
+pub fn infer_schema<P: AsRef<Path>>(input: P) -> Result<Schema, OxenError> {
+    df_db::infer_schema(input.as_ref())
+}
+
+/// Profile the columns of a DataFrame for data quality checks
+pub fn profile<P: AsRef<Path>>(input: P, opts: DFOpts) -> Result<DataFrameProfile, OxenError> {
+    let df = tabular::read_df(input, opts)?;
+    tabular::df_profile(&df)
+}
+
+/// Count the nulls in each column of a DataFrame
+pub fn null_counts<P: AsRef<Path>>(input: P, opts: DFOpts) -> Result<DataFrame, OxenError> {
+    let df = tabular::read_df(input, opts)?;
+    tabular::df_null_counts(&df)
+}
+
+/// Run the SELECT query saved in `sql_path` against a DataFrame, `${table}` in the
+/// query is replaced with the table the DataFrame is loaded into
+pub fn sql_file<P: AsRef<Path>>(
+    input: P,
+    sql_path: impl AsRef<Path>,
+    opts: DFOpts,
+) -> Result<DataFrame, OxenError> {
+    let sql = util::fs::read_from_path(sql_path)?;
+    let mut df = df_db::query_file(input.as_ref(), &sql)?;
+    println!("{df}");
+
+    if let Some(output) = opts.output {
+        println!("Writing {output:?}");
+        tabular::write_df(&mut df, output)?;
+    }
+
+    Ok(df)
+}
+
+/// Print the DuckDB query plan for a SELECT query against a DataFrame instead of running it
+pub fn explain<P: AsRef<Path>>(input: P, sql: &str) -> Result<String, OxenError> {
+    let plan = df_db::explain_file(input.as_ref(), sql)?;
+    println!("{plan}");
+    Ok(plan)
+}
+
+/// Check data validation assertions against a DataFrame, ie. `count > 0` or `null_rate(email) < 0.01`.
+/// Returns an error listing every assertion that failed.
+pub fn assert<P: AsRef<Path>>(input: P, assertions: &[String]) -> Result<(), OxenError> {
+    let failed = df_db::failed_assertions(input.as_ref(), assertions)?;
+    if !failed.is_empty() {
+        let listed = failed
+            .iter()
+            .map(|assertion| format!("  {assertion}"))
+            .collect::<Vec<String>>()
+            .join("\n");
+        return Err(OxenError::basic_str(format!(
+            "{} of {} assertions failed:\n{listed}",
+            failed.len(),
+            assertions.len()
+        )));
+    }
+
+    println!("All {} assertions passed", assertions.len());
+    Ok(())
+}
+
+/// Read a DataFrame and coerce it to the schema committed at HEAD under `schema_ref`, which can
+/// be the path of a committed file, a schema hash or a schema name
+pub fn apply_schema<P: AsRef<Path>>(
+    repo: &LocalRepository,
+    input: P,
+    schema_ref: &str,
+    opts: DFOpts,
+) -> Result<DataFrame, OxenError> {
+    let schemas = command::schemas::get_from_head(repo, schema_ref)?;
+    let mut schemas = schemas.into_values();
+    let Some(schema) = schemas.next() else {
+        return Err(OxenError::schema_does_not_exist(schema_ref));
+    };
+    if schemas.any(|other| other.hash != schema.hash) {
+        return Err(OxenError::basic_str(format!(
+            "Multiple schemas match {schema_ref:?}, use a file path or schema hash"
+        )));
+    }
+
+    // Read the values as they are written so the cast to the schema does not lose anything
+    let df = tabular::read_df_untyped(input, &opts)?;
+    let df = tabular::apply_schema(df, &schema)?;
+    let mut df = tabular::transform(df, opts.clone())?;
+    println!("{df}");
+
+    if let Some(output) = opts.output {
+        println!("Writing {output:?}");
+        tabular::write_df(&mut df, output)?;
+    }
+
+    Ok(df)
+}
+
 /// Add a row to a dataframe
 pub fn add_row(path: &Path, data: &str) -> Result<(), OxenError> {
     if util::fs::is_tabular(path) {
         let mut opts = DFOpts::empty();
         opts.add_row = Some(data.to_string());
         opts.output = Some(path.to_path_buf());
-        df(path, opts)
+        df(path, opts)?;
+        Ok(())
     } else {
         let err = format!("{} is not a tabular file", path.display());
         Err(OxenError::basic_str(err))
@@ -46,9 +177,376 @@ pub fn add_column(path: &Path, data: &str) -> Result<(), OxenError> {
         let mut opts = DFOpts::empty();
         opts.add_col = Some(data.to_string());
         opts.output = Some(path.to_path_buf());
-        df(path, opts)
+        df(path, opts)?;
+        Ok(())
     } else {
         let err = format!("{} is not a tabular file", path.display());
         Err(OxenError::basic_str(err))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+    use std::path::Path;
+
+    use polars::prelude::{IpcStreamReader, SerReader};
+
+    use crate::api;
+    use crate::command;
+    use crate::constants::DUCKDB_DF_TABLE_NAME;
+    use crate::core::db::df_db;
+    use crate::core::df::tabular;
+    use crate::error::OxenError;
+    use crate::model::LocalRepository;
+    use crate::opts::{DFDefaults, DFOpts};
+    use crate::test;
+    use crate::util;
+
+    #[test]
+    fn test_command_df_committed_csv_gz() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let path = repo.path.join("annotations.csv.gz");
+            let mut encoder = flate2::write::GzEncoder::new(
+                std::fs::File::create(&path)?,
+                flate2::Compression::default(),
+            );
+            encoder.write_all(b"image,label\n0001.jpg,cat\n0002.jpg,dog\n0003.jpg,cat\n")?;
+            encoder.finish()?;
+
+            command::add(&repo, &path)?;
+            command::commit(&repo, "Adding compressed annotations")?;
+
+            command::df(&path, DFOpts::empty())?;
+
+            let df = tabular::read_df(&path, DFOpts::empty())?;
+            assert_eq!(df.height(), 3);
+            assert_eq!(df.get_column_names(), vec!["image", "label"]);
+
+            let schema = command::df::schema(&path, true, DFOpts::empty())?;
+            assert!(schema.contains("label"));
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_command_df_committed_csv_gz_version_file() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let path = repo.path.join("annotations.csv.gz");
+            let mut encoder = flate2::write::GzEncoder::new(
+                std::fs::File::create(&path)?,
+                flate2::Compression::default(),
+            );
+            encoder.write_all(b"image,label\n0001.jpg,cat\n0002.jpg,dog\n0003.jpg,cat\n")?;
+            encoder.finish()?;
+
+            command::add(&repo, &path)?;
+            let commit = command::commit(&repo, "Adding compressed annotations")?;
+
+            // The committed revision is read from the versions dir, not the working copy
+            let entry = api::local::entries::get_commit_entry(
+                &repo,
+                &commit,
+                Path::new("annotations.csv.gz"),
+            )?
+            .unwrap();
+            let version_path = util::fs::version_path(&repo, &entry);
+            assert!(version_path.ends_with("data.csv.gz"));
+            assert!(version_path.exists());
+
+            let conn = duckdb::Connection::open_in_memory()?;
+            df_db::index_file(&version_path, &conn)?;
+            assert_eq!(df_db::count(&conn, DUCKDB_DF_TABLE_NAME)?, 3);
+
+            let df = tabular::read_df(&version_path, DFOpts::empty())?;
+            assert_eq!(df.height(), 3);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_command_df_apply_schema_casts_and_reorders() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let committed = repo.path.join("reviews.csv");
+            test::write_txt_file_to_path(
+                &committed,
+                "text,label,score\nhello,1,0.5\nworld,0,0.25\n",
+            )?;
+            command::add(&repo, &committed)?;
+            command::commit(&repo, "Adding reviews")?;
+
+            // Columns are out of order and the scores are read as integers
+            let external = repo.path.join("external.csv");
+            test::write_txt_file_to_path(&external, "score,text,label\n1,moo,1\n2,baa,0\n")?;
+
+            let df = command::df::apply_schema(&repo, &external, "reviews.csv", DFOpts::empty())?;
+            assert_eq!(df.get_column_names(), vec!["text", "label", "score"]);
+            assert_eq!(
+                df.column("score")?.dtype(),
+                &polars::prelude::DataType::Float64
+            );
+            assert_eq!(
+                df.column("label")?.dtype(),
+                &polars::prelude::DataType::Int64
+            );
+            assert_eq!(df.height(), 2);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_command_df_apply_schema_keeps_leading_zeros() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let committed = repo.path.join("zips.csv");
+            test::write_txt_file_to_path(&committed, "zip,city\n02134,Boston\nK1A 0B1,Ottawa\n")?;
+            command::add(&repo, &committed)?;
+            command::commit(&repo, "Adding zips")?;
+
+            // On its own every zip here would be inferred as an integer
+            let external = repo.path.join("external.csv");
+            test::write_txt_file_to_path(&external, "zip,city\n02134,Boston\n10001,New York\n")?;
+
+            let df = command::df::apply_schema(&repo, &external, "zips.csv", DFOpts::empty())?;
+            assert_eq!(
+                df.column("zip")?.dtype(),
+                &polars::prelude::DataType::String
+            );
+            assert_eq!(df.column("zip")?.str()?.get(0), Some("02134"));
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_command_df_apply_schema_incompatible_data() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let committed = repo.path.join("reviews.csv");
+            test::write_txt_file_to_path(
+                &committed,
+                "text,label,score\nhello,1,0.5\nworld,0,0.25\n",
+            )?;
+            command::add(&repo, &committed)?;
+            command::commit(&repo, "Adding reviews")?;
+
+            let external = repo.path.join("external.csv");
+            test::write_txt_file_to_path(&external, "text,label,score\nmoo,1,0.5\nbaa,abc,0.1\n")?;
+
+            let result =
+                command::df::apply_schema(&repo, &external, "reviews.csv", DFOpts::empty());
+            let err = result.unwrap_err().to_string();
+            assert!(err.contains("label"));
+            assert!(err.contains("abc"));
+
+            // Missing columns are reported too
+            let missing = repo.path.join("missing.csv");
+            util::fs::write_to_path(&missing, "text,score\nmoo,0.5\n")?;
+            let result = command::df::apply_schema(&repo, &missing, "reviews.csv", DFOpts::empty());
+            assert!(result.unwrap_err().to_string().contains("label"));
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_command_df_uses_repo_default_page_size() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|mut repo| {
+            let path = repo.path.join("numbers.csv");
+            let rows: Vec<String> = (0..20).map(|i| format!("{i},{}", i * 2)).collect();
+            test::write_txt_file_to_path(&path, &format!("a,b\n{}\n", rows.join("\n")))?;
+
+            // Without a repo default every row is shown
+            let df = command::df::df_with_repo_defaults(&repo, &path, DFOpts::empty())?;
+            assert_eq!(df.height(), 20);
+
+            command::config::set_df_defaults(
+                &mut repo,
+                DFDefaults {
+                    page_size: Some(5),
+                    max_rows: None,
+                },
+            )?;
+            let repo = LocalRepository::from_dir(&repo.path)?;
+            assert_eq!(repo.df_defaults().page_size, Some(5));
+
+            let df = command::df::df_with_repo_defaults(&repo, &path, DFOpts::empty())?;
+            assert_eq!(df.height(), 5);
+
+            // The page still applies
+            let mut opts = DFOpts::empty();
+            opts.page = Some(2);
+            let df = command::df::df_with_repo_defaults(&repo, &path, opts)?;
+            assert_eq!(df.height(), 5);
+            assert_eq!(df.column("a").unwrap().i64().unwrap().get(0), Some(5));
+
+            // An explicit page size overrides the default
+            let mut opts = DFOpts::empty();
+            opts.page_size = Some(8);
+            let df = command::df::df_with_repo_defaults(&repo, &path, opts)?;
+            assert_eq!(df.height(), 8);
+
+            // Writing is never truncated by the default
+            let mut opts = DFOpts::empty();
+            opts.output = Some(repo.path.join("out.csv"));
+            let df = command::df::df_with_repo_defaults(&repo, &path, opts)?;
+            assert_eq!(df.height(), 20);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_command_df_partition_by_writes_hive_layout() -> Result<(), OxenError> {
+        test::run_empty_dir_test(|dir| {
+            let path = dir.join("sales.csv");
+            test::write_txt_file_to_path(
+                &path,
+                "year,month,amount\n2023,1,10\n2023,2,20\n2024,1,30\n2024,1,40\n",
+            )?;
+
+            let out = dir.join("out");
+            let mut opts = DFOpts::empty();
+            opts.output = Some(out.clone());
+            opts.partition_by = Some(String::from("year,month"));
+            command::df(&path, opts)?;
+
+            for partition in [
+                "year=2023/month=1",
+                "year=2023/month=2",
+                "year=2024/month=1",
+            ] {
+                let partition_dir = out.join(partition);
+                let has_parquet = std::fs::read_dir(&partition_dir)?
+                    .filter_map(|entry| entry.ok())
+                    .any(|entry| entry.path().extension() == Some(std::ffi::OsStr::new("parquet")));
+                assert!(has_parquet, "missing parquet in {partition_dir:?}");
+            }
+            assert!(!out.join("year=2024").join("month=2").exists());
+
+            // Reading the partitions back gives the same rows
+            let sql = format!(
+                "SELECT year, month, amount FROM read_parquet('{}/**/*.parquet', hive_partitioning = true) ORDER BY amount",
+                out.to_string_lossy()
+            );
+            let conn = duckdb::Connection::open_in_memory()?;
+            let read = df_db::select_raw(&conn, &sql, false, None)?;
+            let original = tabular::read_df(&path, DFOpts::empty())?;
+            for column in ["year", "month", "amount"] {
+                let read_col = read
+                    .column(column)?
+                    .cast(&polars::prelude::DataType::Int64)?;
+                let original_col = original
+                    .column(column)?
+                    .cast(&polars::prelude::DataType::Int64)?;
+                assert!(read_col.equals(&original_col), "{column} does not match");
+            }
+
+            // Partition columns have to exist
+            let mut opts = DFOpts::empty();
+            opts.output = Some(dir.join("missing"));
+            opts.partition_by = Some(String::from("day"));
+            assert!(command::df(&path, opts).is_err());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_command_df_explain_prints_plan() -> Result<(), OxenError> {
+        test::run_empty_dir_test(|dir| {
+            let path = dir.join("reviews.csv");
+            test::write_txt_file_to_path(&path, "text,score\nzebra_review,0.5\nyak_review,0.25\n")?;
+
+            let plan = command::df::explain(&path, "SELECT text FROM ${table} WHERE score > 0.3")?;
+            assert!(plan.contains("SEQ_SCAN"));
+            assert!(!plan.contains("zebra_review"));
+            assert!(!plan.contains("yak_review"));
+
+            // Same rules as --sql-file, only SELECT queries
+            assert!(command::df::explain(&path, "DELETE FROM df").is_err());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_command_df_assert_lists_failed_assertions() -> Result<(), OxenError> {
+        test::run_empty_dir_test(|dir| {
+            let path = dir.join("users.csv");
+            test::write_txt_file_to_path(&path, "name,email\nada,ada@oxen.ai\nbob,\n")?;
+
+            let passing = vec![String::from("count > 0")];
+            assert!(command::df::assert(&path, &passing).is_ok());
+
+            let mixed = vec![
+                String::from("count > 0"),
+                String::from("null_rate(email) < 0.01"),
+            ];
+            let err = command::df::assert(&path, &mixed).unwrap_err().to_string();
+            assert!(err.contains("1 of 2 assertions failed"), "{err}");
+            assert!(err.contains("null_rate(email) < 0.01"), "{err}");
+            assert!(!err.contains("count > 0"), "{err}");
+
+            // An assertion spread over lines still counts once
+            let multiline = vec![String::from("count\n  > 5")];
+            let err = command::df::assert(&path, &multiline)
+                .unwrap_err()
+                .to_string();
+            assert!(err.contains("1 of 1 assertions failed"), "{err}");
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_command_df_infer_schema_mixed_columns() -> Result<(), OxenError> {
+        test::run_empty_dir_test(|dir| {
+            let path = dir.join("members.csv");
+            test::write_txt_file_to_path(
+                &path,
+                "id,name,score,active,joined\n1,ada,0.5,true,2023-01-02\n2,bob,1.25,false,2023-02-03\n",
+            )?;
+
+            let schema = command::df::infer_schema(&path)?;
+            assert_eq!(
+                schema.to_string(),
+                "id:i64, name:str, score:f64, active:bool, joined:date"
+            );
+            assert_eq!(schema.name, Some(String::from("members.csv")));
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_command_df_to_arrow_stream_round_trips() -> Result<(), OxenError> {
+        test::run_empty_dir_test(|dir| {
+            let path = dir.join("scores.csv");
+            test::write_txt_file_to_path(
+                &path,
+                "name,score,label\nada,0.5,cat\nbob,1.25,dog\ncy,,cat\n",
+            )?;
+
+            let mut buf: Vec<u8> = vec![];
+            let written = command::df::df_to_arrow_stream(&path, DFOpts::empty(), &mut buf)?;
+            let read = IpcStreamReader::new(Cursor::new(buf)).finish()?;
+
+            let source = tabular::read_df(&path, DFOpts::empty())?;
+            assert!(read.equals_missing(&source));
+            assert!(written.equals_missing(&source));
+
+            // Transforms are applied before the stream is written
+            let mut opts = DFOpts::empty();
+            opts.columns = Some(String::from("name,label"));
+            let mut buf: Vec<u8> = vec![];
+            command::df::df_to_arrow_stream(&path, opts, &mut buf)?;
+            let read = IpcStreamReader::new(Cursor::new(buf)).finish()?;
+            assert_eq!(read.get_column_names(), vec!["name", "label"]);
+            assert_eq!(read.height(), 3);
+
+            Ok(())
+        })
+    }
+}
@@ -10,11 +10,13 @@
 
 use std::path::{Path, PathBuf};
 
+use crate::core::df::tabular;
 use crate::core::index::MergeConflictReader;
 use crate::error::OxenError;
-use crate::model::diff::DiffResult;
+use crate::model::diff::{DiffResult, TabularDiff};
 use crate::model::entry::commit_entry::CommitPath;
 use crate::model::LocalRepository;
+use crate::opts::DiffOpts;
 use crate::{api, util};
 
 pub fn diff(
@@ -25,17 +27,130 @@ pub fn diff(
     repo_dir: Option<PathBuf>,
     revision_1: Option<String>,
     revision_2: Option<String>,
+) -> Result<DiffResult, OxenError> {
+    let reverse = false;
+    let null_equals_null = false;
+    diff_resources(
+        path_1,
+        path_2,
+        keys,
+        targets,
+        repo_dir,
+        revision_1,
+        revision_2,
+        reverse,
+        null_equals_null,
+    )
+}
+
+/// Diff given the options from the command line, swapping the two resources if `reverse` is set
+/// so that additions and removals are inverted.
+pub fn diff_with_opts(opts: &DiffOpts) -> Result<DiffResult, OxenError> {
+    diff_resources(
+        &opts.path_1,
+        opts.path_2.clone(),
+        opts.keys.clone(),
+        opts.targets.clone(),
+        opts.repo_dir.clone(),
+        opts.revision_1.clone(),
+        opts.revision_2.clone(),
+        opts.reverse,
+        opts.null_equals_null,
+    )
+}
+
+pub const TABULAR_OUTPUT_FORMATS: [&str; 4] = ["csv", "parquet", "json", "jsonl"];
+
+/// Write the changed rows of a tabular diff to disk, returning the path written to.
+///
+/// Without a format the file type comes from the extension of `output`. With a format, a
+/// directory `output` gets a `diff.{format}` file and any other path has its extension replaced.
+pub fn save_tabular(
+    diff: &TabularDiff,
+    output: impl AsRef<Path>,
+    output_format: Option<&str>,
+) -> Result<PathBuf, OxenError> {
+    let output = output.as_ref();
+    let path = match output_format {
+        Some(format) => {
+            if !TABULAR_OUTPUT_FORMATS.contains(&format) {
+                return Err(OxenError::basic_str(format!(
+                    "Unsupported diff output format '{format}', must be one of: {}",
+                    TABULAR_OUTPUT_FORMATS.join(", ")
+                )));
+            }
+            if output.is_dir() || output.extension().is_none() {
+                util::fs::create_dir_all(output)?;
+                output.join(format!("diff.{format}"))
+            } else {
+                output.with_extension(format)
+            }
+        }
+        None => output.to_path_buf(),
+    };
+
+    let mut df = diff.contents.clone();
+    tabular::write_df(&mut df, &path)?;
+    Ok(path)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn diff_resources(
+    path_1: impl AsRef<Path>,
+    path_2: Option<PathBuf>,
+    keys: Vec<String>,
+    targets: Vec<String>,
+    repo_dir: Option<PathBuf>,
+    revision_1: Option<String>,
+    revision_2: Option<String>,
+    reverse: bool,
+    null_equals_null: bool,
 ) -> Result<DiffResult, OxenError> {
     log::debug!(
-        "diff called with keys: {:?} and targets: {:?}",
+        "diff called with keys: {:?} and targets: {:?} reverse: {} null_equals_null: {}",
         keys,
         targets,
+        reverse,
+        null_equals_null
     );
 
     // If the user specifies two files without revisions, we will compare the files on disk
     if revision_1.is_none() && revision_2.is_none() && path_2.is_some() {
         // If we do not have revisions set, just compare the files on disk
-        let result = api::local::diff::diff_files(path_1, path_2.unwrap(), keys, targets, vec![])?;
+        let path_1 = path_1.as_ref().to_path_buf();
+        let path_2 = path_2.unwrap();
+        let (path_1, path_2) = if reverse {
+            (path_2, path_1)
+        } else {
+            (path_1, path_2)
+        };
+        let result = match repo_dir {
+            // Inside a repository the working copies still follow its .oxenattributes
+            Some(repo_dir) => {
+                let repository = LocalRepository::new(&repo_dir)?;
+                let relative_1 = working_path_relative_to_repo(&repository, &path_1)?;
+                let relative_2 = working_path_relative_to_repo(&repository, &path_2)?;
+                api::local::diff::diff_repo_files(
+                    &repository,
+                    relative_1,
+                    relative_2,
+                    path_1,
+                    path_2,
+                    keys,
+                    targets,
+                    vec![],
+                    null_equals_null,
+                )?
+            }
+            None => api::local::diff::diff_files(
+                path_1,
+                path_2,
+                keys,
+                targets,
+                vec![],
+                null_equals_null,
+            )?,
+        };
 
         return Ok(result);
     }
@@ -95,7 +210,21 @@ pub fn diff(
         )
     };
 
-    let result = diff_commits(&repository, cpath_1, cpath_2, keys, targets, vec![])?;
+    let (cpath_1, cpath_2) = if reverse {
+        (cpath_2, cpath_1)
+    } else {
+        (cpath_1, cpath_2)
+    };
+
+    let result = diff_commit_paths(
+        &repository,
+        cpath_1,
+        cpath_2,
+        keys,
+        targets,
+        vec![],
+        null_equals_null,
+    )?;
 
     Ok(result)
 }
@@ -107,6 +236,27 @@ pub fn diff_commits(
     keys: Vec<String>,
     targets: Vec<String>,
     display: Vec<String>,
+) -> Result<DiffResult, OxenError> {
+    let null_equals_null = false;
+    diff_commit_paths(
+        repo,
+        cpath_1,
+        cpath_2,
+        keys,
+        targets,
+        display,
+        null_equals_null,
+    )
+}
+
+fn diff_commit_paths(
+    repo: &LocalRepository,
+    cpath_1: CommitPath,
+    cpath_2: CommitPath,
+    keys: Vec<String>,
+    targets: Vec<String>,
+    display: Vec<String>,
+    null_equals_null: bool,
 ) -> Result<DiffResult, OxenError> {
     log::debug!(
         "Compare command called with: {:?} and {:?}",
@@ -147,13 +297,35 @@ pub fn diff_commits(
         path_2 = util::fs::version_path(repo, &entry_2);
     };
 
-    let compare_result = api::local::diff::diff_files(path_1, path_2, keys, targets, display)?;
+    let relative_1 = util::fs::path_relative_to_dir(&cpath_1.path, &repo.path)?;
+    let relative_2 = util::fs::path_relative_to_dir(&cpath_2.path, &repo.path)?;
+    let compare_result = api::local::diff::diff_repo_files(
+        repo,
+        relative_1,
+        relative_2,
+        path_1,
+        path_2,
+        keys,
+        targets,
+        display,
+        null_equals_null,
+    )?;
 
     log::debug!("compare result: {:?}", compare_result);
 
     Ok(compare_result)
 }
 
+// Files given on the command line may be relative to the current dir rather than the repo
+fn working_path_relative_to_repo(
+    repo: &LocalRepository,
+    path: &Path,
+) -> Result<PathBuf, OxenError> {
+    let path = path.canonicalize().unwrap_or(path.to_path_buf());
+    let repo_path = repo.path.canonicalize().unwrap_or(repo.path.to_path_buf());
+    util::fs::path_relative_to_dir(path, repo_path)
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -162,13 +334,140 @@ mod tests {
     use polars::lazy::dsl::{col, lit};
     use polars::lazy::frame::IntoLazy;
 
+    use crate::api;
     use crate::command;
+    use crate::constants;
+    use crate::core::df::tabular;
     use crate::error::OxenError;
     use crate::model::diff::{ChangeType, DiffResult};
     use crate::model::entry::commit_entry::CommitPath;
+    use crate::opts::{DFOpts, DiffOpts};
     use crate::test;
     use crate::util;
 
+    #[test]
+    fn test_command_diff_images_of_different_sizes() -> Result<(), OxenError> {
+        test::run_empty_dir_test(|dir| {
+            let file1 = dir.join("small.png");
+            let file2 = dir.join("large.png");
+            image::RgbImage::from_pixel(4, 3, image::Rgb([255, 0, 0])).save(&file1)?;
+            image::RgbImage::from_pixel(10, 5, image::Rgb([255, 0, 0])).save(&file2)?;
+
+            let diff = command::diff(&file1, Some(file2), vec![], vec![], None, None, None)?;
+
+            match &diff {
+                DiffResult::Image(result) => {
+                    assert_eq!(result.width_delta(), 6);
+                    assert_eq!(result.height_delta(), 2);
+                    assert_eq!((result.left.width, result.left.height), (4, 3));
+                    assert_eq!((result.right.width, result.right.height), (10, 5));
+                    assert!(!result.format_changed());
+                    assert!(result.pixels_changed());
+
+                    // Side by side composite fits both images
+                    let output = dir.join("composite.png");
+                    api::local::diff::image_diff::write_composite(result, &output)?;
+                    let composite = image::open(&output)?;
+                    assert_eq!((composite.width(), composite.height()), (14, 5));
+                }
+                _ => panic!("expected image result"),
+            }
+            assert!(diff.format(false).contains("4x3 -> 10x5 (+6x+2)"));
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_command_diff_oxenattributes_binary_is_not_diffed_as_text() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            util::fs::write_to_path(repo.path.join(".oxenattributes"), "*.bin binary\n")?;
+
+            // The files happen to be valid utf8, but only the .bin one is declared binary
+            let bin_path = repo.path.join("weights.bin");
+            let txt_path = repo.path.join("weights.txt");
+            util::fs::write_to_path(&bin_path, "hello\nworld")?;
+            util::fs::write_to_path(&txt_path, "hello\nworld")?;
+            command::add(&repo, &repo.path)?;
+            let first = command::commit(&repo, "Adding weights")?;
+
+            util::fs::write_to_path(&bin_path, "hello\nthere\nworld")?;
+            util::fs::write_to_path(&txt_path, "hello\nthere\nworld")?;
+            command::add(&repo, &repo.path)?;
+            let second = command::commit(&repo, "Changing weights")?;
+
+            let diff = command::diff(
+                "weights.bin",
+                Some(PathBuf::from("weights.bin")),
+                vec![],
+                vec![],
+                Some(repo.path.clone()),
+                Some(first.id.clone()),
+                Some(second.id.clone()),
+            )?;
+            match &diff {
+                DiffResult::Binary(result) => {
+                    assert_eq!(result.num_bytes_delta(), 6);
+                    assert!(result.contents_changed());
+                }
+                _ => panic!("expected binary result"),
+            }
+            assert!(diff.format(false).contains("contents: changed"));
+
+            // Without the attribute the same change is a text diff
+            let diff = command::diff(
+                "weights.txt",
+                Some(PathBuf::from("weights.txt")),
+                vec![],
+                vec![],
+                Some(repo.path.clone()),
+                Some(first.id),
+                Some(second.id),
+            )?;
+            assert!(matches!(diff, DiffResult::Text(_)));
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_command_diff_oxenattributes_applies_to_working_files() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            util::fs::write_to_path(repo.path.join(".oxenattributes"), "*.bin -diff\n")?;
+
+            let file1 = repo.path.join("before.bin");
+            let file2 = repo.path.join("after.bin");
+            util::fs::write_to_path(&file1, "hello\nworld")?;
+            util::fs::write_to_path(&file2, "hello\nthere\nworld")?;
+
+            let mut opts = DiffOpts {
+                path_1: file1,
+                path_2: Some(file2),
+                keys: vec![],
+                targets: vec![],
+                repo_dir: None,
+                revision_1: None,
+                revision_2: None,
+                output: None,
+                output_format: None,
+                reverse: false,
+                no_color: false,
+                null_equals_null: false,
+            };
+
+            // Outside of a repo the files are plain text
+            let diff = command::diff::diff_with_opts(&opts)?;
+            assert!(matches!(diff, DiffResult::Text(_)));
+
+            // Within the repo the -diff attribute keeps the contents from being diffed
+            opts.repo_dir = Some(repo.path.clone());
+            let diff = command::diff::diff_with_opts(&opts)?;
+            assert!(matches!(diff, DiffResult::Binary(_)));
+
+            Ok(())
+        })
+    }
+
     #[test]
     fn test_command_diff_txt_files() -> Result<(), OxenError> {
         test::run_empty_dir_test(|dir| {
@@ -209,6 +508,165 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_command_diff_txt_files_reverse() -> Result<(), OxenError> {
+        test::run_empty_dir_test(|dir| {
+            let file1 = dir.join("file1.txt");
+            let file2 = dir.join("file2.txt");
+
+            util::fs::write_to_path(&file1, "hello\nhow are you?")?;
+            util::fs::write_to_path(&file2, "hello\nhow are you doing?")?;
+
+            let opts = DiffOpts {
+                path_1: file1,
+                path_2: Some(file2),
+                keys: vec![],
+                targets: vec![],
+                repo_dir: None,
+                revision_1: None,
+                revision_2: None,
+                output: None,
+                output_format: None,
+                reverse: true,
+                no_color: false,
+                null_equals_null: false,
+            };
+            let diff = command::diff::diff_with_opts(&opts)?;
+
+            match diff {
+                DiffResult::Text(result) => {
+                    let lines = result.lines;
+                    assert_eq!(lines.len(), 3);
+                    assert_eq!(lines[0].modification, ChangeType::Unchanged);
+                    // The added and removed lines are swapped
+                    assert_eq!(lines[1].modification, ChangeType::Removed);
+                    assert_eq!(&lines[1].text, "how are you doing?");
+                    assert_eq!(lines[2].modification, ChangeType::Added);
+                    assert_eq!(&lines[2].text, "how are you?");
+                }
+                _ => panic!("expected text result"),
+            }
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_command_diff_tabular_reverse() -> Result<(), OxenError> {
+        test::run_empty_dir_test(|dir| {
+            let file1 = dir.join("file1.csv");
+            let file2 = dir.join("file2.csv");
+
+            util::fs::write_to_path(&file1, "a,b\n1,2\n3,4\n")?;
+            util::fs::write_to_path(&file2, "a,b\n1,2\n3,4\n5,6\n")?;
+
+            let mut opts = DiffOpts {
+                path_1: file1,
+                path_2: Some(file2),
+                keys: vec![],
+                targets: vec![],
+                repo_dir: None,
+                revision_1: None,
+                revision_2: None,
+                output: None,
+                output_format: None,
+                reverse: false,
+                no_color: false,
+                null_equals_null: false,
+            };
+            let forward = command::diff::diff_with_opts(&opts)?;
+            opts.reverse = true;
+            let reversed = command::diff::diff_with_opts(&opts)?;
+
+            match (forward, reversed) {
+                (DiffResult::Tabular(forward), DiffResult::Tabular(reversed)) => {
+                    let counts = &forward.summary.modifications.row_counts;
+                    assert_eq!(counts.added, 1);
+                    assert_eq!(counts.removed, 0);
+                    let counts = &reversed.summary.modifications.row_counts;
+                    assert_eq!(counts.added, 0);
+                    assert_eq!(counts.removed, 1);
+
+                    // Reversing an existing result, as the remote diff does, swaps the same way
+                    let swapped = forward.reversed()?;
+                    let counts = &swapped.summary.modifications.row_counts;
+                    assert_eq!(counts.added, 0);
+                    assert_eq!(counts.removed, 1);
+                    let status = swapped.contents.column(constants::DIFF_STATUS_COL)?;
+                    assert_eq!(status.str()?.get(0), Some("removed"));
+                }
+                _ => panic!("expected tabular result"),
+            }
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_command_diff_tabular_null_keys() -> Result<(), OxenError> {
+        test::run_empty_dir_test(|dir| {
+            let file1 = dir.join("file1.csv");
+            let file2 = dir.join("file2.csv");
+
+            util::fs::write_to_path(&file1, "id,label\n1,cat\n,dog\n,fish\n")?;
+            util::fs::write_to_path(&file2, "id,label\n1,cat\n,dog\n,bird\n")?;
+
+            let mut opts = DiffOpts {
+                path_1: file1,
+                path_2: Some(file2),
+                keys: vec![String::from("id"), String::from("label")],
+                targets: vec![],
+                repo_dir: None,
+                revision_1: None,
+                revision_2: None,
+                output: None,
+                output_format: None,
+                reverse: false,
+                no_color: false,
+                null_equals_null: false,
+            };
+
+            // SQL semantics, none of the rows with a null id can match
+            let DiffResult::Tabular(diff) = command::diff::diff_with_opts(&opts)? else {
+                panic!("expected tabular result");
+            };
+            let counts = diff.summary.modifications.row_counts;
+            assert_eq!(counts.added, 2);
+            assert_eq!(counts.removed, 2);
+            assert_eq!(counts.modified, 0);
+
+            // Null ids match, so only the fish -> bird rows differ
+            opts.null_equals_null = true;
+            let DiffResult::Tabular(diff) = command::diff::diff_with_opts(&opts)? else {
+                panic!("expected tabular result");
+            };
+            let counts = diff.summary.modifications.row_counts;
+            assert_eq!(counts.added, 1);
+            assert_eq!(counts.removed, 1);
+            assert_eq!(counts.modified, 0);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_command_diff_no_color() -> Result<(), OxenError> {
+        test::run_empty_dir_test(|dir| {
+            let file1 = dir.join("file1.txt");
+            let file2 = dir.join("file2.txt");
+
+            util::fs::write_to_path(&file1, "hello\nhow are you?")?;
+            util::fs::write_to_path(&file2, "hello\nhow are you doing?")?;
+
+            let diff = command::diff(&file1, Some(file2), vec![], vec![], None, None, None)?;
+            let output = diff.format(false);
+            assert!(!output.contains('\x1b'));
+            assert!(output.contains("how are you doing?"));
+
+            Ok(())
+        })
+    }
+
     // #[tokio::test]
     // async fn test_compare_same_dataframe_no_keys_no_targets() -> Result<(), OxenError> {
     //     test::run_empty_local_repo_test_async(|repo| async move {
@@ -654,4 +1112,50 @@ mod tests {
         })
         .await
     }
+
+    #[test]
+    fn test_command_diff_save_tabular_parquet() -> Result<(), OxenError> {
+        test::run_empty_dir_test(|dir| {
+            let file1 = dir.join("file1.csv");
+            let file2 = dir.join("file2.csv");
+
+            util::fs::write_to_path(&file1, "id,label\n1,cat\n2,dog\n3,fish\n")?;
+            util::fs::write_to_path(&file2, "id,label\n1,cat\n2,wolf\n4,bird\n")?;
+
+            let DiffResult::Tabular(diff) = command::diff(
+                &file1,
+                Some(file2),
+                vec![String::from("id")],
+                vec![String::from("label")],
+                None,
+                None,
+                None,
+            )?
+            else {
+                panic!("expected tabular result");
+            };
+
+            let output_dir = dir.join("results");
+            let path = command::diff::save_tabular(&diff, &output_dir, Some("parquet"))?;
+            assert_eq!(path, output_dir.join("diff.parquet"));
+
+            let df = tabular::read_df(&path, DFOpts::empty())?;
+            assert_eq!(df.height(), 3);
+
+            let changed = df
+                .lazy()
+                .filter(col(constants::DIFF_STATUS_COL).eq(lit("modified")))
+                .collect()?;
+            assert_eq!(changed.height(), 1);
+            let ids = changed
+                .column("id")?
+                .cast(&polars::prelude::DataType::Int64)?;
+            assert_eq!(ids.i64()?.get(0), Some(2));
+
+            let result = command::diff::save_tabular(&diff, &output_dir, Some("xlsx"));
+            assert!(result.is_err());
+
+            Ok(())
+        })
+    }
 }
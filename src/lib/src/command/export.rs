@@ -0,0 +1,25 @@
+//! # oxen export
+//!
+//! Export the metadata of a commit in a schema other dataset tools understand
+//!
+
+use crate::api;
+use crate::core::export::ExportFormat;
+use crate::error::OxenError;
+use crate::model::LocalRepository;
+
+/// # Export the dataset metadata at a revision
+/// Describes the files, schemas and splits committed at `revision`, or at HEAD if it is None,
+/// in the given format.
+pub fn export(
+    repo: &LocalRepository,
+    revision: Option<&str>,
+    format: ExportFormat,
+) -> Result<String, OxenError> {
+    let commit = match revision {
+        Some(revision) => api::local::revisions::get(repo, revision)?
+            .ok_or_else(|| OxenError::revision_not_found(revision.into()))?,
+        None => api::local::commits::head_commit(repo)?,
+    };
+    format.exporter().export(repo, &commit)
+}
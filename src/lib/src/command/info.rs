@@ -3,10 +3,14 @@
 //! Get information about a path in the oxen repository
 //!
 
+use std::collections::HashMap;
+
+use crate::core::index::CommitEntryReader;
 use crate::error::OxenError;
-use crate::model::entry::metadata_entry::CLIMetadataEntry;
-use crate::model::LocalRepository;
+use crate::model::entry::metadata_entry::{CLIDirMetadataEntry, CLIMetadataEntry};
+use crate::model::{EntryDataType, LocalRepository};
 use crate::opts::InfoOpts;
+use crate::view::DataTypeCount;
 use crate::{api, util};
 
 /// # Get info about a file or directory
@@ -39,3 +43,117 @@ pub fn info(repository: &LocalRepository, opts: InfoOpts) -> Result<CLIMetadataE
     // get file metadata
     api::local::metadata::get_cli(repository, &path, &path)
 }
+
+/// # Get aggregate info about all the files in a directory
+/// Sums up the size and counts the files per data type
+pub fn info_recursive(
+    repository: &LocalRepository,
+    opts: InfoOpts,
+) -> Result<CLIDirMetadataEntry, OxenError> {
+    let path = opts.path;
+
+    let mut size: u64 = 0;
+    let mut counts: HashMap<EntryDataType, usize> = HashMap::new();
+    if let Some(revision) = opts.revision {
+        let commit = api::local::revisions::get(repository, &revision)?
+            .ok_or(OxenError::revision_not_found(revision.to_owned().into()))?;
+
+        let reader = CommitEntryReader::new(repository, &commit)?;
+        let entries = reader.list_directory(&path)?;
+        if entries.is_empty() {
+            eprintln!(
+                "Directory does not exist in revision: {}:{}",
+                revision,
+                path.to_string_lossy()
+            );
+            return Err(OxenError::path_does_not_exist(path));
+        }
+
+        for entry in entries {
+            let version_path = util::fs::version_path(repository, &entry);
+            size += entry.num_bytes;
+            *counts
+                .entry(util::fs::file_data_type(&version_path))
+                .or_insert(0) += 1;
+        }
+    } else {
+        if !path.is_dir() {
+            eprintln!("Directory does not exist: {:?}", path);
+            return Err(OxenError::path_does_not_exist(path));
+        }
+
+        for file in util::fs::rlist_files_in_dir(&path) {
+            if util::fs::is_in_oxen_hidden_dir(&file) {
+                continue;
+            }
+            size += api::local::metadata::get_file_size(&file)?;
+            *counts.entry(util::fs::file_data_type(&file)).or_insert(0) += 1;
+        }
+    }
+
+    let num_files = counts.values().sum();
+    let mut data_types: Vec<DataTypeCount> = counts
+        .into_iter()
+        .map(|(data_type, count)| DataTypeCount {
+            count,
+            data_type: data_type.to_string(),
+        })
+        .collect();
+    data_types.sort_by(|a, b| a.data_type.cmp(&b.data_type));
+
+    Ok(CLIDirMetadataEntry {
+        dirname: path.to_string_lossy().to_string(),
+        size,
+        num_files,
+        data_types,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::command;
+    use crate::error::OxenError;
+    use crate::opts::InfoOpts;
+    use crate::test;
+    use crate::util;
+
+    #[test]
+    fn test_command_info_recursive() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let dir = repo.path.join("data");
+            util::fs::create_dir_all(dir.join("nested"))?;
+            util::fs::write_to_path(dir.join("a.txt"), "hello")?;
+            util::fs::write_to_path(dir.join("nested").join("b.txt"), "world!")?;
+            util::fs::write_to_path(dir.join("labels.csv"), "file,label\n1,dog\n")?;
+
+            let mut opts = InfoOpts {
+                path: dir.clone(),
+                revision: None,
+                verbose: false,
+                output_as_json: false,
+                recursive: true,
+            };
+            let info = command::info::info_recursive(&repo, opts.clone())?;
+            assert_eq!(info.num_files, 3);
+            assert_eq!(info.size, 5 + 6 + 17);
+            assert_eq!(info.data_types.len(), 2);
+            assert_eq!(info.data_types[0].data_type, "tabular");
+            assert_eq!(info.data_types[0].count, 1);
+            assert_eq!(info.data_types[1].data_type, "text");
+            assert_eq!(info.data_types[1].count, 2);
+
+            // Same summary from the committed version
+            command::add(&repo, &dir)?;
+            let commit = command::commit(&repo, "Adding data")?;
+            opts.path = PathBuf::from("data");
+            opts.revision = Some(commit.id);
+            let committed = command::info::info_recursive(&repo, opts)?;
+            assert_eq!(committed.num_files, 3);
+            assert_eq!(committed.size, info.size);
+
+            Ok(())
+        })
+    }
+}
@@ -5,6 +5,7 @@
 
 use std::path::Path;
 
+use crate::core::index::{CommitEntryWriter, RefWriter};
 use crate::error::OxenError;
 use crate::model::LocalRepository;
 use crate::{api, constants, util};
@@ -31,6 +32,10 @@ pub fn init(path: &Path) -> Result<LocalRepository, OxenError> {
         return Err(OxenError::basic_str(err));
     }
 
+    // Keep the .oxen dir in OXEN_DIR if it is set, the hidden dir has to be resolved again after
+    util::fs::link_oxen_dir(path)?;
+    let hidden_dir = util::fs::oxen_hidden_dir(path);
+
     // Cleanup the .oxen dir if init fails
     match p_init(path) {
         Ok(result) => Ok(result),
@@ -54,6 +59,41 @@ fn p_init(path: &Path) -> Result<LocalRepository, OxenError> {
     Ok(repo)
 }
 
+/// # Initialize a Bare Oxen Repository
+/// A bare repository only has the .oxen dir, with no initial commit and no working files, so that
+/// it can be pushed into and served like the repositories the server creates.
+pub fn init_bare(path: &Path) -> Result<LocalRepository, OxenError> {
+    let hidden_dir = util::fs::oxen_hidden_dir(path);
+    if hidden_dir.exists() {
+        let err = format!("Oxen repository already exists: {path:?}");
+        return Err(OxenError::basic_str(err));
+    }
+
+    match p_init_bare(path) {
+        Ok(result) => Ok(result),
+        Err(error) => {
+            util::fs::remove_dir_all(hidden_dir)?;
+            Err(error)
+        }
+    }
+}
+
+fn p_init_bare(path: &Path) -> Result<LocalRepository, OxenError> {
+    let hidden_dir = util::fs::oxen_hidden_dir(path);
+    std::fs::create_dir_all(hidden_dir.join(constants::HISTORY_DIR))?;
+
+    let config_path = util::fs::config_filepath(path);
+    let mut repo = LocalRepository::new(path)?;
+    repo.set_bare(true);
+    repo.save(&config_path)?;
+
+    CommitEntryWriter::create_objects_dbs(&repo)?;
+    let ref_writer = RefWriter::new(&repo)?;
+    ref_writer.set_head(constants::DEFAULT_BRANCH_NAME);
+
+    Ok(repo)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::api;
@@ -61,6 +101,7 @@ mod tests {
     use crate::constants;
     use crate::core::index::CommitEntryReader;
     use crate::error::OxenError;
+    use crate::model::LocalRepository;
     use crate::test;
     use crate::util;
 
@@ -86,6 +127,30 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_command_init_bare() -> Result<(), OxenError> {
+        test::run_empty_dir_test(|repo_dir| {
+            command::init_bare(repo_dir)?;
+
+            // Only the .oxen dir, no working files
+            let names: Vec<_> = std::fs::read_dir(repo_dir)?
+                .map(|entry| entry.map(|e| e.file_name()))
+                .collect::<Result<_, _>>()?;
+            assert_eq!(names, vec![constants::OXEN_HIDDEN_DIR]);
+
+            // Bare is persisted, and there are no commits to conflict with the first push
+            let repo = LocalRepository::from_dir(repo_dir)?;
+            assert!(repo.is_bare());
+            assert!(api::local::commits::list_all(&repo)?.is_empty());
+            assert!(api::local::branches::list(&repo)?.is_empty());
+
+            // Can't init over an existing repo
+            assert!(command::init_bare(repo_dir).is_err());
+
+            Ok(())
+        })
+    }
+
     #[test]
     fn test_do_not_commit_any_files_on_init() -> Result<(), OxenError> {
         test::run_empty_dir_test(|dir| {
@@ -101,4 +166,88 @@ mod tests {
             Ok(())
         })
     }
+
+    // Env var holding the data dir of the repository initialized in the child process
+    const OXEN_DIR_TEST_DATA_DIR: &str = "OXEN_DIR_TEST_DATA_DIR";
+
+    #[test]
+    fn test_command_init_with_oxen_dir_outside_read_only_root() -> Result<(), OxenError> {
+        test::run_empty_dir_test(|dir| {
+            let data_dir = dir.join("data");
+            let oxen_dir = dir.join("metadata");
+            util::fs::create_dir_all(&data_dir)?;
+            util::fs::write_to_path(data_dir.join("labels.txt"), "cat\ndog")?;
+
+            // OXEN_DIR applies to the whole process, so set it on a child process running only
+            // the test body rather than on the tests running in parallel with this one
+            let output = std::process::Command::new(std::env::current_exe()?)
+                .args([
+                    "--exact",
+                    "command::init::tests::oxen_dir_outside_read_only_root",
+                    "--ignored",
+                    "--test-threads=1",
+                ])
+                .env(constants::OXEN_DIR_ENV, &oxen_dir)
+                .env(OXEN_DIR_TEST_DATA_DIR, &data_dir)
+                .output()?;
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            assert!(
+                output.status.success() && stdout.contains("1 passed"),
+                "{stdout}\n{}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+
+            // The .oxen dir lives in OXEN_DIR instead of the repository root
+            assert!(!data_dir.join(constants::OXEN_HIDDEN_DIR).exists());
+            assert!(oxen_dir.join(constants::REPO_CONFIG_FILENAME).exists());
+
+            Ok(())
+        })
+    }
+
+    // Run by test_command_init_with_oxen_dir_outside_read_only_root in its own process
+    #[test]
+    #[ignore]
+    fn oxen_dir_outside_read_only_root() -> Result<(), OxenError> {
+        let (Ok(_), Ok(data_dir)) = (
+            std::env::var(constants::OXEN_DIR_ENV),
+            std::env::var(OXEN_DIR_TEST_DATA_DIR),
+        ) else {
+            return Ok(());
+        };
+        test::init_test_env();
+        let data_dir = std::path::PathBuf::from(data_dir);
+
+        let repo = command::init(&data_dir)?;
+        assert!(!data_dir.join(constants::OXEN_HIDDEN_DIR).exists());
+        assert_eq!(
+            util::fs::get_repo_root(&data_dir),
+            Some(data_dir.canonicalize()?)
+        );
+
+        // Nothing needs to be written to the data dir to add and commit
+        let mut perms = std::fs::metadata(&data_dir)?.permissions();
+        perms.set_readonly(true);
+        std::fs::set_permissions(&data_dir, perms.clone())?;
+
+        let result = (|| -> Result<(), OxenError> {
+            command::add(&repo, data_dir.join("labels.txt"))?;
+            command::commit(&repo, "Adding labels")?;
+
+            let status = command::status(&repo)?;
+            assert!(status.is_clean());
+
+            // Permissions are not enforced when running as root, so check directly
+            let names: Vec<String> = std::fs::read_dir(&data_dir)?
+                .map(|entry| entry.map(|e| e.file_name().to_string_lossy().to_string()))
+                .collect::<Result<_, _>>()?;
+            assert_eq!(names, vec!["labels.txt"]);
+            Ok(())
+        })();
+
+        #[allow(clippy::permissions_set_readonly_false)]
+        perms.set_readonly(false);
+        std::fs::set_permissions(&data_dir, perms)?;
+        result
+    }
 }
@@ -1,12 +1,16 @@
 use crate::command;
 use flate2::read::GzDecoder;
+use std::io::{BufRead, BufReader, Read};
 use std::path::PathBuf;
 use std::{fs::File, path::Path};
 use tar::Archive;
 
 use crate::opts::RestoreOpts;
+use crate::util::compression::ArchiveCompression;
 use crate::{error::OxenError, model::LocalRepository};
 
+/// Load a repository from an archive written by `oxen save`, detecting whether it is
+/// a plain, gzipped, or zstd compressed tarball
 pub fn load(src_path: &Path, dest_path: &Path, no_working_dir: bool) -> Result<(), OxenError> {
     let done_msg: String = format!(
         "✅ Loaded {:?} to an oxen repo at {:?}",
@@ -25,8 +29,14 @@ pub fn load(src_path: &Path, dest_path: &Path, no_working_dir: bool) -> Result<(
         dest_path.to_path_buf()
     };
 
-    let file = File::open(src_path)?;
-    let tar = GzDecoder::new(file);
+    let mut reader = BufReader::new(File::open(src_path)?);
+    let compression = ArchiveCompression::from_magic(reader.fill_buf()?);
+    log::debug!("command::load detected {} archive", compression);
+    let tar: Box<dyn Read> = match compression {
+        ArchiveCompression::None => Box::new(reader),
+        ArchiveCompression::Gzip => Box::new(GzDecoder::new(reader)),
+        ArchiveCompression::Zstd => Box::new(zstd::Decoder::with_buffer(reader)?),
+    };
     println!("🐂 Decompressing oxen repo into {:?}", dest_path);
     let mut archive = Archive::new(tar);
     archive.unpack(&dest_path)?;
@@ -63,13 +73,15 @@ pub fn load(src_path: &Path, dest_path: &Path, no_working_dir: bool) -> Result<(
 
 #[cfg(test)]
 mod tests {
-    use std::path::Path;
+    use std::path::{Path, PathBuf};
 
+    use crate::api;
     use crate::command;
     use crate::error::OxenError;
     use crate::model::LocalRepository;
     use crate::test;
     use crate::util;
+    use crate::util::compression::ArchiveCompression;
 
     #[test]
     fn test_command_save_repo() -> Result<(), OxenError> {
@@ -216,4 +228,44 @@ mod tests {
             })
         })
     }
+
+    #[test]
+    fn test_command_save_load_repo_uncompressed_and_zstd() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            test::run_empty_dir_test(|dir| {
+                let hello_file = repo.path.join("hello.txt");
+                let nested_file = repo.path.join("nested").join("world.txt");
+                util::fs::write_to_path(&hello_file, "Hello World")?;
+                util::fs::write_to_path(&nested_file, "World")?;
+                command::add(&repo, &hello_file)?;
+                command::add(&repo, &nested_file)?;
+                command::commit(&repo, "Adding hello and world")?;
+                let og_commits = api::local::commits::list(&repo)?;
+
+                for compression in [ArchiveCompression::None, ArchiveCompression::Zstd] {
+                    let save_path = dir.join(format!("backup.{}", compression.extension()));
+                    command::save_with_compression(&repo, &save_path, compression)?;
+
+                    let loaded_repo_path = dir.join(format!("loaded_{compression}"));
+                    command::load(&save_path, &loaded_repo_path, false)?;
+
+                    let loaded_repo = LocalRepository::from_dir(&loaded_repo_path)?;
+                    assert_eq!(api::local::commits::list(&loaded_repo)?, og_commits);
+                    for path in [
+                        PathBuf::from("hello.txt"),
+                        Path::new("nested").join("world.txt"),
+                    ] {
+                        assert_eq!(
+                            util::fs::read_from_path(loaded_repo.path.join(&path))?,
+                            util::fs::read_from_path(repo.path.join(&path))?
+                        );
+                    }
+                    let status = command::status(&loaded_repo)?;
+                    assert!(status.is_clean());
+                }
+
+                Ok(())
+            })
+        })
+    }
 }
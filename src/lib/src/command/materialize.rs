@@ -0,0 +1,175 @@
+//! # oxen materialize
+//!
+//! Download files that were left as placeholders by `oxen clone --filter-lfs-only`
+//!
+
+use std::path::{Path, PathBuf};
+
+use crate::api;
+use crate::core::index::{self, placeholders, puller, CommitEntryReader};
+use crate::error::OxenError;
+use crate::model::entry::commit_entry::Entry;
+use crate::model::LocalRepository;
+use crate::opts::RestoreOpts;
+
+/// # Download the placeholder files at or under a path into the working directory
+/// The path is relative to the repo root, returns the paths that were materialized
+pub async fn materialize(
+    repo: &LocalRepository,
+    path: impl AsRef<Path>,
+) -> Result<Vec<PathBuf>, OxenError> {
+    let path = path.as_ref();
+    let paths: Vec<PathBuf> = placeholders::list(repo)?
+        .into_iter()
+        .filter(|placeholder| placeholder.starts_with(path))
+        .collect();
+    if paths.is_empty() {
+        let err = format!("No placeholder files found at {path:?}");
+        return Err(OxenError::basic_str(err));
+    }
+
+    let head_commit = api::local::commits::head_commit(repo)?;
+    let reader = CommitEntryReader::new(repo, &head_commit)?;
+    let mut entries: Vec<Entry> = vec![];
+    for path in paths.iter() {
+        match reader.get_entry(path)? {
+            Some(entry) => entries.push(Entry::from(entry)),
+            None => log::warn!("Placeholder {:?} is not in commit {}", path, head_commit.id),
+        }
+    }
+
+    let remote_repo = api::remote::repositories::get_default_remote(repo).await?;
+    println!("🐂 Materializing {} files", entries.len());
+    puller::pull_entries_to_versions_dir(&remote_repo, &entries, &repo.path).await?;
+    for entry in entries.iter() {
+        index::restore(repo, RestoreOpts::from_path(entry.path()))?;
+    }
+
+    placeholders::remove(repo, &paths)?;
+    index::commit_sync_status::remove_placeholders(repo, &head_commit, &paths)?;
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::api;
+    use crate::command;
+    use crate::core::index::{commit_sync_status, placeholders, CommitEntryReader};
+    use crate::error::OxenError;
+    use crate::opts::CloneOpts;
+    use crate::test;
+    use crate::util;
+
+    #[tokio::test]
+    async fn test_clone_lfs_threshold_then_materialize() -> Result<(), OxenError> {
+        test::run_training_data_fully_sync_remote(|local_repo, remote_repo| async move {
+            // Commit a file that is over the threshold
+            let large_path = local_repo.path.join("large").join("blob.bin");
+            util::fs::create_dir_all(large_path.parent().unwrap())?;
+            let contents = "0123456789abcdef".repeat(128 * 1024);
+            util::fs::write_to_path(&large_path, &contents)?;
+            command::add(&local_repo, &large_path)?;
+            command::commit(&local_repo, "Adding large blob")?;
+            command::push(&local_repo).await?;
+
+            let cloned_remote = remote_repo.clone();
+            test::run_empty_dir_test_async(|dir| async move {
+                let mut opts = CloneOpts::new(remote_repo.remote.url.to_owned(), dir.join("lfs"));
+                opts.lfs_threshold = Some(1_000_000);
+                let cloned_repo = command::clone(&opts).await?;
+
+                // The large file is a placeholder, the small files are downloaded
+                let large_rel = Path::new("large").join("blob.bin");
+                assert!(!cloned_repo.path.join(&large_rel).exists());
+                assert!(placeholders::is_placeholder(&cloned_repo, &large_rel)?);
+                assert!(cloned_repo.path.join("README.md").exists());
+                assert!(command::status(&cloned_repo)?.is_clean());
+
+                let materialized = command::materialize(&cloned_repo, "large").await?;
+                assert_eq!(materialized, vec![large_rel.clone()]);
+
+                let cloned_path = cloned_repo.path.join(&large_rel);
+                assert_eq!(util::fs::read_from_path(&cloned_path)?, contents);
+                let head = api::local::commits::head_commit(&cloned_repo)?;
+                let entry = CommitEntryReader::new(&cloned_repo, &head)?
+                    .get_entry(&large_rel)?
+                    .unwrap();
+                assert_eq!(util::hasher::hash_file_contents(&cloned_path)?, entry.hash);
+                assert!(!placeholders::is_placeholder(&cloned_repo, &large_rel)?);
+                assert!(command::status(&cloned_repo)?.is_clean());
+
+                Ok(dir)
+            })
+            .await?;
+
+            Ok(cloned_remote)
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_clone_lfs_threshold_checkout_commit_touching_placeholder() -> Result<(), OxenError>
+    {
+        test::run_training_data_fully_sync_remote(|local_repo, remote_repo| async move {
+            // Two versions of a file that is over the threshold
+            let large_path = local_repo.path.join("large").join("blob.bin");
+            util::fs::create_dir_all(large_path.parent().unwrap())?;
+            let first_contents = "0123456789abcdef".repeat(128 * 1024);
+            util::fs::write_to_path(&large_path, &first_contents)?;
+            command::add(&local_repo, &large_path)?;
+            let first_commit = command::commit(&local_repo, "Adding large blob")?;
+            let second_contents = "fedcba9876543210".repeat(128 * 1024);
+            util::fs::write_to_path(&large_path, &second_contents)?;
+            command::add(&local_repo, &large_path)?;
+            command::commit(&local_repo, "Changing large blob")?;
+            command::push(&local_repo).await?;
+
+            let cloned_remote = remote_repo.clone();
+            test::run_empty_dir_test_async(|dir| async move {
+                let mut opts = CloneOpts::new(remote_repo.remote.url.to_owned(), dir.join("lfs"));
+                opts.lfs_threshold = Some(1_000_000);
+                let cloned_repo = command::clone(&opts).await?;
+
+                // The placeholder version was never downloaded, so the commit is not synced
+                let large_rel = Path::new("large").join("blob.bin");
+                let head = api::local::commits::head_commit(&cloned_repo)?;
+                assert!(placeholders::is_placeholder(&cloned_repo, &large_rel)?);
+                assert!(!commit_sync_status::commit_is_synced(&cloned_repo, &head));
+
+                // Checking out the commit with the other version keeps it a placeholder
+                command::checkout(&cloned_repo, &first_commit.id).await?;
+                assert!(!cloned_repo.path.join(&large_rel).exists());
+                assert!(placeholders::is_placeholder(&cloned_repo, &large_rel)?);
+                assert!(command::status(&cloned_repo)?.is_clean());
+                assert!(!commit_sync_status::commit_is_synced(
+                    &cloned_repo,
+                    &first_commit
+                ));
+
+                // Materializing fetches the version from the checked out commit
+                command::materialize(&cloned_repo, "large").await?;
+                assert_eq!(
+                    util::fs::read_from_path(cloned_repo.path.join(&large_rel))?,
+                    first_contents
+                );
+                assert!(commit_sync_status::commit_is_synced(
+                    &cloned_repo,
+                    &first_commit
+                ));
+
+                // Going back to the head commit leaves its version as a placeholder again
+                command::checkout(&cloned_repo, &head.id).await?;
+                assert!(!cloned_repo.path.join(&large_rel).exists());
+                assert!(command::status(&cloned_repo)?.is_clean());
+
+                Ok(dir)
+            })
+            .await?;
+
+            Ok(cloned_remote)
+        })
+        .await
+    }
+}
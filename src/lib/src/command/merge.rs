@@ -4,7 +4,7 @@
 //!
 
 use crate::api;
-use crate::core::index::Merger;
+use crate::core::index::{reflog, Merger};
 use crate::error::OxenError;
 use crate::model::{Commit, LocalRepository};
 
@@ -33,6 +33,13 @@ pub fn merge<S: AsRef<str>>(
             merge_branch_name, base_branch.name
         );
         println!("HEAD -> {}", commit.id);
+        let message = format!("merge {merge_branch_name}");
+        reflog::record(
+            repo,
+            Some(base_branch.commit_id.to_owned()),
+            &commit.id,
+            message,
+        );
         Ok(Some(commit))
     } else {
         eprintln!("Automatic merge failed; fix conflicts and then commit the result.");
@@ -9,7 +9,7 @@ use crate::core::index::{CommitEntryWriter, CommitReader, CommitWriter};
 use crate::error::OxenError;
 use crate::model::LocalRepository;
 use crate::util::progress_bar::{oxen_progress_bar, ProgressBarType};
-use crate::{api, constants};
+use crate::{api, constants, util};
 
 pub struct CreateMerkleTreesMigration;
 impl Migrate for CreateMerkleTreesMigration {
@@ -37,10 +37,7 @@ impl Migrate for CreateMerkleTreesMigration {
         Ok(())
     }
     fn is_needed(&self, repo: &LocalRepository) -> Result<bool, OxenError> {
-        let objects_dir = repo
-            .path
-            .join(constants::OXEN_HIDDEN_DIR)
-            .join(constants::OBJECTS_DIR);
+        let objects_dir = util::fs::oxen_hidden_dir(&repo.path).join(constants::OBJECTS_DIR);
         if !objects_dir.exists() {
             return Ok(true);
         }
@@ -45,10 +45,7 @@ impl Migrate for UpdateVersionFilesMigration {
     }
 
     fn is_needed(&self, repo: &LocalRepository) -> Result<bool, OxenError> {
-        let versions_dir = repo
-            .path
-            .join(constants::OXEN_HIDDEN_DIR)
-            .join(constants::VERSIONS_DIR);
+        let versions_dir = util::fs::oxen_hidden_dir(&repo.path).join(constants::VERSIONS_DIR);
         if !versions_dir.exists() {
             return Ok(false);
         }
@@ -0,0 +1,186 @@
+//! # oxen mv
+//!
+//! Move or rename a committed file, staging the change as a move
+//!
+
+use std::path::{Path, PathBuf};
+
+use crate::api;
+use crate::command;
+use crate::core::index::{CommitEntryReader, Stager};
+use crate::error::OxenError;
+use crate::model::LocalRepository;
+use crate::util;
+
+/// Moves `src` to `dst` in the working directory and stages both sides of the move.
+///
+/// The removal of `src` and the addition of `dst` share the same content hash, so
+/// status shows the pair as moved and `api::local::diff::list_moved_entries` can follow the
+/// file across the resulting commit. Relative paths are relative to the repository root,
+/// absolute paths must be within it. If `dst` is an existing directory the file is moved into
+/// it. If staging the move fails the file is moved back.
+pub fn mv(
+    repo: &LocalRepository,
+    src: impl AsRef<Path>,
+    dst: impl AsRef<Path>,
+) -> Result<(), OxenError> {
+    let src = &repo_relative_path(repo, src.as_ref())?;
+    let dst = &repo_relative_path(repo, dst.as_ref())?;
+
+    let commit = api::local::commits::head_commit(repo)?;
+    let commit_reader = CommitEntryReader::new(repo, &commit)?;
+    if commit_reader.has_dir(src) {
+        let error = format!("`oxen mv` on directory {src:?} is not supported yet");
+        return Err(OxenError::basic_str(error));
+    }
+    if !commit_reader.has_file(src) {
+        let error = format!("File {src:?} must be committed to use `oxen mv`");
+        return Err(OxenError::basic_str(error));
+    }
+
+    let full_src = repo.path.join(src);
+    if !full_src.exists() {
+        return Err(OxenError::path_does_not_exist(full_src));
+    }
+
+    // The path the file ends up at, relative to the repo root
+    let mut dst_path = dst.to_path_buf();
+    if repo.path.join(dst).is_dir() {
+        if let Some(file_name) = src.file_name() {
+            dst_path = dst_path.join(file_name);
+        }
+    }
+    let full_dst = repo.path.join(&dst_path);
+    if full_dst.exists() {
+        let error = format!("Destination {dst:?} already exists");
+        return Err(OxenError::basic_str(error));
+    }
+
+    if let Some(parent) = full_dst.parent() {
+        util::fs::create_dir_all(parent)?;
+    }
+    log::debug!("mv {:?} -> {:?}", full_src, full_dst);
+    util::fs::rename(&full_src, &full_dst)?;
+
+    // Adding the missing source stages its removal, adding the destination stages the new path
+    let staged = command::add(repo, &full_src).and_then(|_| command::add(repo, &full_dst));
+    if let Err(err) = staged {
+        log::debug!("mv could not stage {:?}, moving it back: {}", full_dst, err);
+        util::fs::rename(&full_dst, &full_src)?;
+        let stager = Stager::new(repo)?;
+        for path in [src, &dst_path] {
+            if stager.has_entry(path) {
+                stager.remove_staged_file(path)?;
+            }
+        }
+        return Err(err);
+    }
+
+    Ok(())
+}
+
+// Absolute paths, ie resolved against the current dir by the CLI, relative to the repo root.
+// `..` and symlinks are resolved through the closest ancestor that exists.
+fn repo_relative_path(repo: &LocalRepository, path: &Path) -> Result<PathBuf, OxenError> {
+    if path.is_relative() {
+        return Ok(path.to_path_buf());
+    }
+
+    let mut existing = path;
+    let mut rest: Vec<&std::ffi::OsStr> = vec![];
+    while !existing.exists() {
+        match (existing.file_name(), existing.parent()) {
+            (Some(name), Some(parent)) => {
+                rest.push(name);
+                existing = parent;
+            }
+            _ => break,
+        }
+    }
+    let mut resolved = existing.canonicalize()?;
+    resolved.extend(rest.iter().rev());
+
+    let repo_root = repo.path.canonicalize()?;
+    match resolved.strip_prefix(&repo_root) {
+        Ok(relative) => Ok(relative.to_path_buf()),
+        Err(_) => Err(OxenError::basic_str(format!(
+            "Path {path:?} is outside of the repository {:?}",
+            repo.path
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::api;
+    use crate::command;
+    use crate::error::OxenError;
+    use crate::test;
+
+    #[test]
+    fn test_mv_committed_file_is_recorded_as_move() -> Result<(), OxenError> {
+        test::run_training_data_repo_test_fully_committed(|repo| {
+            let base_commit = api::local::commits::head_commit(&repo)?;
+            let src = PathBuf::from("README.md");
+            let dst = PathBuf::from("docs").join("README.md");
+
+            command::mv(&repo, &src, &dst)?;
+
+            assert!(!repo.path.join(&src).exists());
+            assert!(repo.path.join(&dst).exists());
+
+            let status = command::status(&repo)?;
+            assert_eq!(status.moved_files.len(), 1);
+            let (moved_to, moved_from, _hash) = &status.moved_files[0];
+            assert_eq!(moved_from, &src);
+            assert_eq!(moved_to, &dst);
+
+            let head_commit = command::commit(&repo, "Moving the README")?;
+
+            let moved = api::local::diff::list_moved_entries(&repo, &base_commit, &head_commit)?;
+            assert_eq!(moved, vec![(src.to_owned(), dst.to_owned())]);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_mv_uncommitted_file_errors() -> Result<(), OxenError> {
+        test::run_training_data_repo_test_fully_committed(|repo| {
+            let new_file = repo.path.join("new.txt");
+            test::write_txt_file_to_path(&new_file, "not committed yet")?;
+
+            let result = command::mv(&repo, "new.txt", "renamed.txt");
+            assert!(result.is_err());
+            assert!(new_file.exists());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_mv_absolute_paths_within_repo() -> Result<(), OxenError> {
+        test::run_training_data_repo_test_fully_committed(|repo| {
+            let src = repo.path.join("README.md");
+            let dst = repo
+                .path
+                .join("annotations")
+                .join("..")
+                .join("README-moved.md");
+
+            command::mv(&repo, &src, &dst)?;
+
+            assert!(!src.exists());
+            assert!(repo.path.join("README-moved.md").exists());
+            let status = command::status(&repo)?;
+            assert_eq!(status.moved_files.len(), 1);
+            let (moved_to, moved_from, _hash) = &status.moved_files[0];
+            assert_eq!(moved_from, &PathBuf::from("README.md"));
+            assert_eq!(moved_to, &PathBuf::from("README-moved.md"));
+
+            Ok(())
+        })
+    }
+}
@@ -3,17 +3,17 @@
 //! Pull data from a remote branch
 //!
 
+use crate::api;
 use crate::core::index::EntryIndexer;
 use crate::error::OxenError;
 use crate::model::{LocalRepository, RemoteBranch};
 use crate::opts::PullOpts;
 
-/// Pull a repository's data from default branches origin/main
-/// Defaults defined in
-/// `constants::DEFAULT_REMOTE_NAME` and `constants::DEFAULT_BRANCH_NAME`
+/// Pull a repository's data from the upstream of the current branch, or the default branch of
+/// the `constants::DEFAULT_REMOTE_NAME` remote
 pub async fn pull(repo: &LocalRepository) -> Result<(), OxenError> {
     let indexer = EntryIndexer::new(repo)?;
-    let rb = RemoteBranch::default();
+    let rb = upstream_or_default(repo).await?;
     indexer
         .pull(
             &rb,
@@ -27,7 +27,7 @@ pub async fn pull(repo: &LocalRepository) -> Result<(), OxenError> {
 
 pub async fn pull_shallow(repo: &LocalRepository) -> Result<(), OxenError> {
     let indexer = EntryIndexer::new(repo)?;
-    let rb = RemoteBranch::default();
+    let rb = default_remote_branch(repo).await?;
     indexer
         .pull(
             &rb,
@@ -41,7 +41,7 @@ pub async fn pull_shallow(repo: &LocalRepository) -> Result<(), OxenError> {
 
 pub async fn pull_all(repo: &LocalRepository) -> Result<(), OxenError> {
     let indexer = EntryIndexer::new(repo)?;
-    let rb = RemoteBranch::default();
+    let rb = default_remote_branch(repo).await?;
     indexer
         .pull(
             &rb,
@@ -53,6 +53,42 @@ pub async fn pull_all(repo: &LocalRepository) -> Result<(), OxenError> {
         .await
 }
 
+/// The upstream of the current branch if it is tracking one, otherwise the default remote branch
+pub async fn upstream_or_default(repo: &LocalRepository) -> Result<RemoteBranch, OxenError> {
+    let upstream =
+        api::local::branches::current_branch(repo)?.and_then(|branch| repo.upstream(&branch.name));
+    match upstream {
+        Some(upstream) => Ok(upstream),
+        None => default_remote_branch(repo).await,
+    }
+}
+
+/// The default branch of the `constants::DEFAULT_REMOTE_NAME` remote, as reported by the server
+pub async fn default_remote_branch(repo: &LocalRepository) -> Result<RemoteBranch, OxenError> {
+    let remote_repo = api::remote::repositories::get_default_remote(repo).await?;
+    Ok(RemoteBranch {
+        remote: remote_repo.remote.name.to_owned(),
+        branch: remote_repo.default_branch().to_string(),
+    })
+}
+
+/// The default branch of the named remote, as reported by the server
+pub async fn remote_default_branch(
+    repo: &LocalRepository,
+    remote: &str,
+) -> Result<RemoteBranch, OxenError> {
+    let remote = repo
+        .get_remote(remote)
+        .ok_or(OxenError::remote_not_set(remote))?;
+    let remote_repo = api::remote::repositories::get_by_remote(&remote)
+        .await?
+        .ok_or(OxenError::remote_repo_not_found(&remote.url))?;
+    Ok(RemoteBranch {
+        remote: remote.name.to_owned(),
+        branch: remote_repo.default_branch().to_string(),
+    })
+}
+
 /// Pull a specific remote and branch
 pub async fn pull_remote_branch(
     repo: &LocalRepository,
@@ -84,12 +120,12 @@ mod tests {
     use crate::command;
     use crate::constants;
     use crate::constants::DEFAULT_BRANCH_NAME;
-    use crate::constants::OXEN_HIDDEN_DIR;
     use crate::core::df::tabular;
     use crate::core::index;
     use crate::core::index::CommitEntryReader;
     use crate::core::index::CommitReader;
     use crate::error::OxenError;
+    use crate::model::RepoNew;
     use crate::opts::CloneOpts;
     use crate::opts::DFOpts;
     use crate::test;
@@ -132,7 +168,7 @@ mod tests {
                 let new_repo_dir = new_repo_dir.join("new_repo");
                 let cloned_repo =
                     command::shallow_clone_url(&remote_repo.remote.url, &new_repo_dir).await?;
-                let oxen_dir = cloned_repo.path.join(OXEN_HIDDEN_DIR);
+                let oxen_dir = util::fs::oxen_hidden_dir(&cloned_repo.path);
                 assert!(oxen_dir.exists());
                 command::pull(&cloned_repo).await?;
 
@@ -590,6 +626,8 @@ mod tests {
                     branch: branch_name.to_owned(),
                     shallow: false,
                     all: false,
+                    into: false,
+                    lfs_threshold: None,
                 };
                 let cloned_repo = command::clone(&opts).await?;
 
@@ -668,6 +706,8 @@ mod tests {
                     branch: DEFAULT_BRANCH_NAME.to_string(),
                     shallow: false,
                     all: false,
+                    into: false,
+                    lfs_threshold: None,
                 };
                 let cloned_repo = command::clone(&opts).await?;
 
@@ -1453,4 +1493,66 @@ mod tests {
         })
         .await
     }
+
+    #[tokio::test]
+    async fn test_bare_pull_uses_remote_default_branch() -> Result<(), OxenError> {
+        test::run_training_data_repo_test_no_commits_async(|mut repo| async move {
+            let train_path = repo.path.join("train");
+            command::add(&repo, &train_path)?;
+            command::commit(&repo, "Adding train dir")?;
+
+            // Work on master instead of main locally
+            let default_branch = "master";
+            api::local::branches::create_checkout(&repo, default_branch)?;
+
+            // Create a remote whose default branch is master
+            let remote = test::repo_remote_url_from(&repo.dirname());
+            command::config::set_remote(&mut repo, constants::DEFAULT_REMOTE_NAME, &remote)?;
+            let mut repo_new = RepoNew::from_namespace_name_host(
+                constants::DEFAULT_NAMESPACE,
+                repo.dirname(),
+                test::test_host(),
+            );
+            repo_new.default_branch = Some(default_branch.to_string());
+            let remote_repo = api::remote::repositories::create_from_local(&repo, repo_new).await?;
+            command::push_remote_branch(&repo, constants::DEFAULT_REMOTE_NAME, default_branch)
+                .await?;
+
+            let found = api::remote::repositories::get_default_remote(&repo).await?;
+            assert_eq!(found.default_branch(), default_branch);
+
+            let remote_repo_copy = remote_repo.clone();
+            test::run_empty_dir_test_async(|new_repo_dir| async move {
+                let mut opts = CloneOpts::new(
+                    remote_repo.remote.url.to_owned(),
+                    new_repo_dir.join("new_repo"),
+                );
+                opts.branch = default_branch.to_string();
+                let cloned_repo = command::clone(&opts).await?;
+
+                // Advance master on the remote
+                let new_file = "new_file.txt";
+                let new_file_path = repo.path.join(new_file);
+                test::write_txt_file_to_path(&new_file_path, "hello from master")?;
+                command::add(&repo, &new_file_path)?;
+                command::commit(&repo, "Adding new file")?;
+                command::push_remote_branch(&repo, constants::DEFAULT_REMOTE_NAME, default_branch)
+                    .await?;
+
+                // A bare pull targets master, not main
+                let rb = command::pull::upstream_or_default(&cloned_repo).await?;
+                assert_eq!(rb.branch, default_branch);
+                command::pull(&cloned_repo).await?;
+                assert!(cloned_repo.path.join(new_file).exists());
+
+                Ok(new_repo_dir)
+            })
+            .await?;
+
+            api::remote::repositories::delete(&remote_repo_copy).await?;
+
+            Ok(())
+        })
+        .await
+    }
 }
@@ -3,11 +3,15 @@
 //! Push data from your local machine to a remote.
 //!
 
+use std::io::Write;
+use std::time::Duration;
+
 use crate::api;
 use crate::constants::DEFAULT_BRANCH_NAME;
+use crate::core::cache::cacher_status::CacherStatus;
 use crate::core::index::{pusher, EntryIndexer};
 use crate::error::OxenError;
-use crate::model::{Branch, LocalRepository, RemoteBranch, RemoteRepository};
+use crate::model::{Branch, Commit, LocalRepository, RemoteBranch, RemoteRepository};
 
 /// # Get a log of all the commits
 ///
@@ -50,16 +54,17 @@ use crate::model::{Branch, LocalRepository, RemoteBranch, RemoteRepository};
 /// ```
 pub async fn push(repo: &LocalRepository) -> Result<Branch, OxenError> {
     let indexer = EntryIndexer::new(repo)?;
-    let mut remote_branch = RemoteBranch::default();
 
     // Push the currently checked out branch
     let Some(local_branch) = api::local::branches::current_branch(repo)? else {
         return Err(OxenError::local_branch_not_found(DEFAULT_BRANCH_NAME));
     };
 
-    let local_branch_cpy = local_branch.clone();
-    remote_branch.branch = local_branch_cpy.clone().name;
-    indexer.push(local_branch_cpy, remote_branch).await?;
+    // Push to the tracked upstream if there is one, otherwise the same name on the default remote
+    let remote_branch = repo
+        .upstream(&local_branch.name)
+        .unwrap_or_else(|| RemoteBranch::from_branch(&local_branch.name));
+    indexer.push(local_branch.clone(), remote_branch).await?;
     Ok(local_branch)
 }
 
@@ -82,6 +87,71 @@ pub async fn push_remote_branch(
     Ok(local_branch)
 }
 
+/// Push to a specific remote branch and record it as the upstream of the local branch,
+/// so that a bare push or pull on this branch syncs with it
+pub async fn push_set_upstream(
+    repo: &mut LocalRepository,
+    remote: &str,
+    branch_name: &str,
+) -> Result<Branch, OxenError> {
+    let branch = push_remote_branch(repo, remote, branch_name).await?;
+
+    repo.set_upstream(
+        branch_name,
+        RemoteBranch {
+            remote: String::from(remote),
+            branch: String::from(branch_name),
+        },
+    );
+    repo.save_default()?;
+    println!("Branch '{branch_name}' set up to track '{remote}/{branch_name}'");
+
+    Ok(branch)
+}
+
+/// The commits on the local branch that are missing on the remote branch, in the order a push
+/// of the branch would sync them
+pub async fn unsynced_commits(
+    repo: &LocalRepository,
+    remote: &str,
+    branch_name: &str,
+) -> Result<Vec<Commit>, OxenError> {
+    let Some(local_branch) = api::local::branches::get_by_name(repo, branch_name)? else {
+        return Err(OxenError::local_branch_not_found(branch_name));
+    };
+    let remote = repo
+        .get_remote(remote)
+        .ok_or(OxenError::remote_not_set(remote))?;
+    let remote_repo = api::remote::repositories::get_by_remote(&remote)
+        .await?
+        .ok_or(OxenError::remote_repo_not_found(&remote.url))?;
+    let head_commit = api::local::commits::get_by_id(repo, &local_branch.commit_id)?
+        .ok_or(OxenError::must_be_on_valid_branch())?;
+    pusher::get_commit_objects_to_sync(repo, &remote_repo, &head_commit, &local_branch).await
+}
+
+/// Writes the id and first line of the message of each commit that is missing on the remote,
+/// one per line in the order they will be synced. Writes nothing if there are none.
+pub fn write_unsynced_commits(
+    writer: &mut impl Write,
+    commits: &[Commit],
+) -> Result<(), OxenError> {
+    if commits.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(
+        writer,
+        "🐂 Pushing {} commit(s) missing on the remote",
+        commits.len()
+    )?;
+    for commit in commits {
+        let summary = commit.message.lines().next().unwrap_or("");
+        writeln!(writer, "  {} {}", commit.id, summary)?;
+    }
+    Ok(())
+}
+
 /// Push to a specific remote repository
 pub async fn push_remote_repo_branch(
     local_repo: LocalRepository,
@@ -102,22 +172,80 @@ pub async fn push_remote_repo_branch_name(
     push_remote_repo_branch(local_repo, remote_repo, branch).await
 }
 
+/// Block until the server has finished caching the head commit of a pushed branch.
+/// Errors if caching failed or did not finish within `timeout`.
+pub async fn wait_for_remote_cache(
+    repo: &LocalRepository,
+    remote: &str,
+    branch: &Branch,
+    timeout: Duration,
+) -> Result<CacherStatus, OxenError> {
+    let remote = repo
+        .get_remote(remote)
+        .ok_or(OxenError::remote_not_set(remote))?;
+    let remote_repo = api::remote::repositories::get_by_remote(&remote)
+        .await?
+        .ok_or(OxenError::remote_repo_not_found(&remote.url))?;
+    api::remote::commits::wait_for_cache(&remote_repo, &branch.commit_id, timeout).await
+}
+
 #[cfg(test)]
 mod tests {
+    use std::io::Write;
     use std::path::PathBuf;
+    use std::time::Duration;
 
     use crate::api;
     use crate::command;
     use crate::constants;
 
     use crate::constants::DEFAULT_BRANCH_NAME;
-    use crate::core::index::CommitEntryReader;
+    use crate::core::cache::cacher_status::CacherStatusType;
+    use crate::core::index::{pusher, CommitEntryReader};
 
     use crate::error::OxenError;
+    use crate::model::LocalRepository;
     use crate::test;
     use crate::util;
+    use crate::util::compression::CompressionLevel;
     use futures::future;
 
+    #[tokio::test]
+    async fn test_command_push_wait_for_remote_cache() -> Result<(), OxenError> {
+        test::run_training_data_repo_test_fully_committed_async(|repo| async {
+            let mut repo = repo;
+
+            // Set the proper remote
+            let remote = test::repo_remote_url_from(&repo.dirname());
+            command::config::set_remote(&mut repo, constants::DEFAULT_REMOTE_NAME, &remote)?;
+
+            // Create the repo
+            let remote_repo = test::create_remote_repo(&repo).await?;
+
+            // Push and wait for the server to process the commit
+            let branch = command::push(&repo).await?;
+            let status = command::push::wait_for_remote_cache(
+                &repo,
+                constants::DEFAULT_REMOTE_NAME,
+                &branch,
+                Duration::from_secs(120),
+            )
+            .await?;
+            assert_eq!(status.status, CacherStatusType::Success);
+
+            // Only returns once the server reports the cache is done
+            let status = api::remote::commits::cache_status(&remote_repo, &branch.commit_id)
+                .await?
+                .unwrap();
+            assert_eq!(status.status, CacherStatusType::Success);
+
+            api::remote::repositories::delete(&remote_repo).await?;
+
+            future::ok::<(), OxenError>(()).await
+        })
+        .await
+    }
+
     #[tokio::test]
     async fn test_command_push_one_commit() -> Result<(), OxenError> {
         test::run_training_data_repo_test_no_commits_async(|repo| async {
@@ -155,6 +283,63 @@ mod tests {
         .await
     }
 
+    #[tokio::test]
+    async fn test_command_push_with_compression_levels() -> Result<(), OxenError> {
+        test::run_training_data_repo_test_no_commits_async(|repo| async {
+            let mut repo = repo;
+
+            // Set the proper remote
+            let remote = test::repo_remote_url_from(&repo.dirname());
+            command::config::set_remote(&mut repo, constants::DEFAULT_REMOTE_NAME, &remote)?;
+            let remote_repo = test::create_remote_repo(&repo).await?;
+
+            // Push one commit favoring speed, and one favoring ratio
+            command::add(&repo, repo.path.join("train"))?;
+            command::commit(&repo, "Adding training data")?;
+            let branch = api::local::branches::current_branch(&repo)?.unwrap();
+            let remote_repo = pusher::push_remote_repo_with_compression(
+                &repo,
+                remote_repo,
+                branch,
+                CompressionLevel::Fast.to_gz(),
+            )
+            .await?;
+
+            command::add(&repo, repo.path.join("test"))?;
+            command::commit(&repo, "Adding test data")?;
+            let branch = api::local::branches::current_branch(&repo)?.unwrap();
+            let remote_repo = pusher::push_remote_repo_with_compression(
+                &repo,
+                remote_repo,
+                branch,
+                CompressionLevel::Best.to_gz(),
+            )
+            .await?;
+
+            let num_files = util::fs::rcount_files_in_dir(&repo.path.join("train"))
+                + util::fs::rcount_files_in_dir(&repo.path.join("test"));
+
+            // Both commits should be restorable from the remote
+            test::run_empty_dir_test_async(|new_repo_dir| async move {
+                let cloned_repo =
+                    command::clone_url(&remote_repo.remote.url, &new_repo_dir.join("new_repo"))
+                        .await?;
+                let cloned_num_files =
+                    util::fs::rcount_files_in_dir(&cloned_repo.path.join("train"))
+                        + util::fs::rcount_files_in_dir(&cloned_repo.path.join("test"));
+                assert_eq!(num_files, cloned_num_files);
+
+                api::remote::repositories::delete(&remote_repo).await?;
+
+                Ok(new_repo_dir)
+            })
+            .await?;
+
+            future::ok::<(), OxenError>(()).await
+        })
+        .await
+    }
+
     #[tokio::test]
     async fn test_command_push_one_commit_check_is_synced() -> Result<(), OxenError> {
         test::run_training_data_repo_test_no_commits_async(|repo| async {
@@ -445,6 +630,73 @@ mod tests {
         .await
     }
 
+    #[tokio::test]
+    async fn test_push_set_upstream_then_bare_pull() -> Result<(), OxenError> {
+        test::run_training_data_repo_test_no_commits_async(|mut repo| async move {
+            // Track a dir
+            let train_path = repo.path.join("train");
+            command::add(&repo, &train_path)?;
+            command::commit(&repo, "Adding train dir")?;
+
+            // Set the proper remote
+            let remote = test::repo_remote_url_from(&repo.dirname());
+            command::config::set_remote(&mut repo, constants::DEFAULT_REMOTE_NAME, &remote)?;
+
+            // Create Remote
+            let remote_repo = test::create_remote_repo(&repo).await?;
+            command::push(&repo).await?;
+
+            // Create a new branch with a commit, and push it with -u
+            let new_branch_name = "my-upstream-branch";
+            api::local::branches::create_checkout(&repo, new_branch_name)?;
+            let test_path = repo.path.join("test");
+            command::add(&repo, &test_path)?;
+            command::commit(&repo, "Adding test dir")?;
+            command::push_set_upstream(&mut repo, constants::DEFAULT_REMOTE_NAME, new_branch_name)
+                .await?;
+
+            // The config on disk should record the upstream
+            let reloaded = LocalRepository::from_dir(&repo.path)?;
+            let upstream = reloaded.upstream(new_branch_name).unwrap();
+            assert_eq!(upstream.remote, constants::DEFAULT_REMOTE_NAME);
+            assert_eq!(upstream.branch, new_branch_name);
+
+            // Someone else pushes a commit to the tracked branch
+            let remote_url = remote_repo.remote.url.clone();
+            test::run_empty_dir_test_async(|other_dir| async move {
+                let other_repo = command::clone_url(&remote_url, &other_dir.join("other")).await?;
+                command::fetch(&other_repo).await?;
+                command::checkout(&other_repo, new_branch_name).await?;
+                let upstream_file =
+                    test::write_txt_file_to_path(other_repo.path.join("upstream.txt"), "upstream")?;
+                command::add(&other_repo, &upstream_file)?;
+                let pushed_commit = command::commit(&other_repo, "Adding upstream file")?;
+                command::push_remote_branch(
+                    &other_repo,
+                    constants::DEFAULT_REMOTE_NAME,
+                    new_branch_name,
+                )
+                .await?;
+
+                // Bare pull should pull that commit from the tracked branch, main does not have it
+                command::pull(&reloaded).await?;
+                let current = api::local::branches::current_branch(&reloaded)?.unwrap();
+                assert_eq!(current.name, new_branch_name);
+                assert_eq!(current.commit_id, pushed_commit.id);
+                let upstream_file = reloaded.path.join("upstream.txt");
+                assert_eq!(util::fs::read_from_path(&upstream_file)?, "upstream");
+
+                Ok(other_dir)
+            })
+            .await?;
+
+            api::remote::repositories::delete(&remote_repo).await?;
+
+            Ok(())
+        })
+        .await
+    }
+
     #[tokio::test]
     async fn test_cannot_push_two_separate_empty_roots() -> Result<(), OxenError> {
         test::run_no_commit_remote_repo_test(|remote_repo| async move {
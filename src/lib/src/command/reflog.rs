@@ -0,0 +1,82 @@
+//! # oxen reflog
+//!
+//! Show where HEAD has pointed after commits, checkouts and merges
+//!
+
+use crate::core::index::reflog;
+use crate::error::OxenError;
+use crate::model::{LocalRepository, ReflogEntry};
+
+/// List the movements of HEAD, oldest first
+pub fn reflog(repo: &LocalRepository) -> Result<Vec<ReflogEntry>, OxenError> {
+    reflog::list(repo)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api;
+    use crate::command;
+    use crate::error::OxenError;
+    use crate::test;
+    use crate::util;
+
+    #[tokio::test]
+    async fn test_command_reflog_lists_head_movements_in_order() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test_async(|repo| async move {
+            let initial = api::local::commits::head_commit(&repo)?;
+            let hello_file = repo.path.join("hello.txt");
+            util::fs::write_to_path(&hello_file, "Hello")?;
+            command::add(&repo, &hello_file)?;
+            let first = command::commit(&repo, "Adding hello")?;
+
+            command::create_checkout(&repo, "feature")?;
+            let world_file = repo.path.join("world.txt");
+            util::fs::write_to_path(&world_file, "World")?;
+            command::add(&repo, &world_file)?;
+            let second = command::commit(&repo, "Adding world")?;
+
+            command::checkout(&repo, "main").await?;
+            command::checkout(&repo, &second.id).await?;
+
+            let entries = command::reflog(&repo)?;
+            let transitions: Vec<(Option<String>, String, String)> = entries
+                .into_iter()
+                .map(|entry| (entry.old_commit_id, entry.new_commit_id, entry.message))
+                .collect();
+
+            assert_eq!(
+                transitions,
+                vec![
+                    (
+                        Some(initial.id.to_owned()),
+                        first.id.to_owned(),
+                        String::from("commit: Adding hello")
+                    ),
+                    (
+                        Some(first.id.to_owned()),
+                        first.id.to_owned(),
+                        String::from("checkout: moving from main to feature")
+                    ),
+                    (
+                        Some(first.id.to_owned()),
+                        second.id.to_owned(),
+                        String::from("commit: Adding world")
+                    ),
+                    (
+                        Some(second.id.to_owned()),
+                        first.id.to_owned(),
+                        String::from("checkout: moving from feature to main")
+                    ),
+                    (
+                        Some(first.id.to_owned()),
+                        second.id.to_owned(),
+                        format!("checkout: moving from main to {}", second.id)
+                    ),
+                ]
+            );
+
+            Ok(())
+        })
+        .await
+    }
+}
@@ -7,7 +7,7 @@ use std::path::{Path, PathBuf};
 
 use crate::api;
 use crate::config::UserConfig;
-use crate::constants::DEFAULT_REMOTE_NAME;
+use crate::constants::{AVG_CHUNK_SIZE, DEFAULT_REMOTE_NAME};
 use crate::core::index::oxenignore;
 use crate::error::OxenError;
 use crate::model::{LocalRepository, RemoteBranch};
@@ -27,7 +27,7 @@ pub async fn add<P: AsRef<Path>>(
     }
 
     // * make sure file is not in .oxenignore
-    let ignore = oxenignore::create(repo);
+    let ignore = oxenignore::create_with_excludes(repo, &opts.excludes);
     if let Some(ignore) = ignore {
         if ignore.matched(path, path.is_dir()).is_ignore() {
             return Ok(());
@@ -56,14 +56,28 @@ pub async fn add<P: AsRef<Path>>(
     let directory_name = remote_directory.to_string_lossy().to_string();
 
     let user_id = UserConfig::identifier()?;
-    let result = api::remote::staging::add_file(
-        &remote_repo,
-        &branch.name,
-        &user_id,
-        &directory_name,
-        resolved_path,
-    )
-    .await?;
+    let result = if opts.chunked {
+        api::remote::staging::add_file_chunked(
+            &remote_repo,
+            &branch.name,
+            &user_id,
+            &directory_name,
+            resolved_path,
+            AVG_CHUNK_SIZE,
+            opts.force,
+        )
+        .await?
+    } else {
+        api::remote::staging::add_file(
+            &remote_repo,
+            &branch.name,
+            &user_id,
+            &directory_name,
+            resolved_path,
+            opts.force,
+        )
+        .await?
+    };
 
     println!("{}", result.to_string_lossy());
 
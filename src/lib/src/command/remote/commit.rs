@@ -23,6 +23,8 @@ pub async fn commit(repo: &LocalRepository, message: &str) -> Result<Option<Comm
         message: message.to_string(),
         author: cfg.name,
         email: cfg.email,
+        expected_parent_id: None,
+        idempotency_key: Some(uuid::Uuid::new_v4().to_string()),
     };
     let user_id = UserConfig::identifier()?;
     let commit = api::remote::staging::commit(&remote_repo, &branch.name, &user_id, &body).await?;
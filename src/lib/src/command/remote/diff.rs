@@ -13,10 +13,12 @@ use crate::error::OxenError;
 use crate::model::diff::DiffResult;
 use crate::model::LocalRepository;
 
+/// Diff the remotely staged changes to `path` against the branch, with `reverse` swapping the sides
 pub async fn diff(
     repo: &LocalRepository,
     branch_name: Option<impl AsRef<str>>,
     path: &Path,
+    reverse: bool,
 ) -> Result<DiffResult, OxenError> {
     let branch = api::local::branches::get_by_name_or_current(repo, branch_name)?;
     let remote_repo = api::remote::repositories::get_default_remote(repo).await?;
@@ -30,5 +32,8 @@ pub async fn diff(
         DEFAULT_PAGE_SIZE,
     )
     .await?;
-    Ok(diff)
+    match diff {
+        DiffResult::Tabular(diff) if reverse => Ok(DiffResult::Tabular(diff.reversed()?)),
+        diff => Ok(diff),
+    }
 }
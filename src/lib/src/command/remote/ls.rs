@@ -7,7 +7,8 @@ use std::path::Path;
 
 use crate::api;
 use crate::error::OxenError;
-use crate::model::{Branch, RemoteRepository};
+use crate::model::metadata::generic_metadata::GenericMetadata;
+use crate::model::{Branch, MetadataEntry, RemoteRepository};
 use crate::opts::PaginateOpts;
 use crate::view::PaginatedDirEntries;
 
@@ -27,6 +28,34 @@ pub async fn ls(
     .await
 }
 
+/// Format an entry as a single line for `ls -l`
+/// Shows the data type, size in bytes, file count for directories, the commit that last modified it, and the name
+pub fn long_format(entry: &MetadataEntry) -> String {
+    let commit_id = match &entry.latest_commit {
+        Some(commit) => commit.id.to_owned(),
+        None => String::from("-"),
+    };
+
+    if entry.is_dir {
+        let num_files: usize = match &entry.metadata {
+            Some(GenericMetadata::MetadataDir(metadata)) => {
+                metadata.dir.data_types.iter().map(|t| t.count).sum()
+            }
+            _ => 0,
+        };
+        let num_files = format!("{} files", num_files);
+        format!(
+            "{:<8} {:>12} {:>8} {} {}/",
+            entry.data_type, entry.size, num_files, commit_id, entry.filename
+        )
+    } else {
+        format!(
+            "{:<8} {:>12} {:>8} {} {}",
+            entry.data_type, entry.size, "", commit_id, entry.filename
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::api;
@@ -93,6 +122,66 @@ mod tests {
         .await
     }
 
+    #[tokio::test]
+    async fn test_remote_ls_long_format() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test_async(|mut repo| async move {
+            // One file at the root and one nested in a directory
+            let contents = "hello world";
+            util::fs::write(repo.path.join("labels.txt"), contents)?;
+            let dir_path = repo.path.join("data");
+            util::fs::create_dir_all(&dir_path)?;
+            util::fs::write(dir_path.join("data.txt"), "Hi")?;
+
+            command::add(&repo, &repo.path)?;
+            let commit = command::commit(&repo, "Adding all the data")?;
+
+            // Set the proper remote
+            let remote = test::repo_remote_url_from(&repo.dirname());
+            command::config::set_remote(&mut repo, constants::DEFAULT_REMOTE_NAME, &remote)?;
+
+            // Create Remote
+            let remote_repo = test::create_remote_repo(&repo).await?;
+
+            // Push it real good
+            command::push(&repo).await?;
+
+            // Now list the remote
+            let branch = api::local::branches::current_branch(&repo)?.unwrap();
+            let opts = PaginateOpts {
+                page_num: 1,
+                page_size: 10,
+            };
+            let paginated =
+                command::remote::ls(&remote_repo, &branch, Path::new(""), &opts).await?;
+
+            let file = paginated
+                .entries
+                .iter()
+                .find(|e| e.filename == "labels.txt")
+                .unwrap();
+            let line = command::remote::ls::long_format(file);
+            assert!(line.starts_with("text"));
+            assert!(line.contains(&format!(" {} ", contents.len())));
+            assert!(line.contains(&commit.id));
+            assert!(line.ends_with("labels.txt"));
+
+            let dir = paginated
+                .entries
+                .iter()
+                .find(|e| e.filename == "data")
+                .unwrap();
+            let line = command::remote::ls::long_format(dir);
+            assert!(line.starts_with("dir"));
+            assert!(line.contains("1 files"));
+            assert!(line.ends_with("data/"));
+
+            api::remote::repositories::delete(&remote_repo).await?;
+
+            Ok(())
+        })
+        .await
+    }
+
     #[tokio::test]
     async fn test_remote_ls_return_data_types() -> Result<(), OxenError> {
         test::run_empty_local_repo_test_async(|mut repo| async move {
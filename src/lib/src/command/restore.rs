@@ -7,10 +7,14 @@ use std::collections::HashSet;
 use std::path::PathBuf;
 
 use crate::api;
+use crate::api::local::diff::utf8_diff;
+use crate::api::local::resource;
 use crate::core::index::{self, CommitEntryReader};
 use crate::error::OxenError;
+use crate::model::diff::TextHunk;
 use crate::model::LocalRepository;
 use crate::opts::RestoreOpts;
+use crate::util;
 
 use glob::Pattern;
 
@@ -102,6 +106,66 @@ pub fn restore(repo: &LocalRepository, opts: RestoreOpts) -> Result<(), OxenErro
     Ok(())
 }
 
+/// # List the hunks that differ between the working file and the restore source
+/// Only works on a single text file, used to pick which hunks to pass to `restore_hunks`
+pub fn list_hunks(repo: &LocalRepository, opts: &RestoreOpts) -> Result<Vec<TextHunk>, OxenError> {
+    let (current, source) = read_patch_contents(repo, opts)?;
+    Ok(utf8_diff::hunks(&current, &source))
+}
+
+/// # Restore only the selected hunks of a text file
+/// Hunk indices come from `list_hunks`, every line outside of them is left as is in the working file
+pub fn restore_hunks(
+    repo: &LocalRepository,
+    opts: &RestoreOpts,
+    indices: &[usize],
+) -> Result<(), OxenError> {
+    let (current, source) = read_patch_contents(repo, opts)?;
+    let num_hunks = utf8_diff::hunks(&current, &source).len();
+    if let Some(index) = indices.iter().find(|i| **i >= num_hunks) {
+        let err = format!(
+            "Invalid hunk index {index} for {:?}, it only has {num_hunks} hunks",
+            opts.path
+        );
+        return Err(OxenError::basic_str(err));
+    }
+
+    let restored = utf8_diff::apply_hunks(&current, &source, indices);
+    util::fs::write_to_path(repo.path.join(&opts.path), restored)?;
+    Ok(())
+}
+
+fn read_patch_contents(
+    repo: &LocalRepository,
+    opts: &RestoreOpts,
+) -> Result<(String, String), OxenError> {
+    if opts.staged {
+        return Err(OxenError::basic_str(
+            "Cannot restore hunks of a staged file, use --staged without --patch",
+        ));
+    }
+
+    let commit = resource::get_commit_or_head(repo, opts.source_ref.clone())?;
+    let reader = CommitEntryReader::new(repo, &commit)?;
+    let Some(entry) = reader.get_entry(&opts.path)? else {
+        let err = format!("Could not restore file: {:?} does not exist", opts.path);
+        return Err(OxenError::basic_str(err));
+    };
+
+    let working_path = repo.path.join(&opts.path);
+    let version_path = util::fs::version_path(repo, &entry);
+    if !util::fs::is_utf8(&working_path) || !util::fs::is_utf8(&version_path) {
+        return Err(OxenError::invalid_file_type(format!(
+            "Can only restore hunks of text files, found {:?}",
+            opts.path
+        )));
+    }
+
+    let current = util::fs::read_from_path(&working_path)?;
+    let source = util::fs::read_from_path(&version_path)?;
+    Ok((current, source))
+}
+
 #[cfg(test)]
 mod tests {
     use std::path::Path;
@@ -554,6 +618,7 @@ mod tests {
                 path: PathBuf::from("images/*"),
                 recursive: false,
                 staged: false,
+                cached: false,
                 remote: false,
             };
 
@@ -656,4 +721,38 @@ mod tests {
             Ok(())
         })
     }
+
+    #[test]
+    fn test_command_restore_patch_selected_hunks() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let hello_filename = "hello.txt";
+            let hello_file = repo.path.join(hello_filename);
+            util::fs::write_to_path(&hello_file, "one\ntwo\nthree\nfour\nfive\n")?;
+            command::add(&repo, &hello_file)?;
+            command::commit(&repo, "Adding hello")?;
+
+            // Change the start and the end of the file
+            util::fs::write_to_path(&hello_file, "ONE\ntwo\nthree\nfour\nFIVE\nsix\n")?;
+
+            let opts = RestoreOpts::from_path(hello_filename);
+            let hunks = command::restore::list_hunks(&repo, &opts)?;
+            assert_eq!(hunks.len(), 2);
+            assert_eq!(hunks[0].start, 1);
+            assert_eq!(hunks[0].current, vec!["ONE"]);
+            assert_eq!(hunks[0].source, vec!["one"]);
+            assert_eq!(hunks[1].start, 5);
+
+            // Only restore the last hunk
+            command::restore::restore_hunks(&repo, &opts, &[1])?;
+            let content = util::fs::read_from_path(&hello_file)?;
+            assert_eq!(content, "ONE\ntwo\nthree\nfour\nfive\n");
+
+            // Out of range hunks are an error and leave the file alone
+            let result = command::restore::restore_hunks(&repo, &opts, &[1]);
+            assert!(result.is_err());
+            assert_eq!(util::fs::read_from_path(&hello_file)?, content);
+
+            Ok(())
+        })
+    }
 }
@@ -79,6 +79,7 @@ mod tests {
                 path: rm_dir.to_owned(),
                 recursive: true,
                 staged: false,
+                cached: false,
                 remote: false,
             };
             command::rm(&repo, &opts).await?;
@@ -339,6 +340,7 @@ mod tests {
                 path: PathBuf::from("images/*"),
                 recursive: false,
                 staged: false,
+                cached: false,
                 remote: false,
             };
 
@@ -355,6 +357,7 @@ mod tests {
                 path: PathBuf::from("images/*"),
                 recursive: false,
                 staged: true,
+                cached: false,
                 remote: false,
             };
 
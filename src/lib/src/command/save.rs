@@ -1,47 +1,69 @@
+use std::io::Write;
 use std::{fs::File, path::Path};
 
 use bytesize::ByteSize;
 use flate2::write::GzEncoder;
 use flate2::Compression;
-use std::io::Write;
 
+use crate::util::compression::ArchiveCompression;
 use crate::{constants::OXEN_HIDDEN_DIR, error::OxenError, model::LocalRepository, util};
 
+/// Save the repository to a gzipped tarball
 pub fn save(repo: &LocalRepository, dst_path: &Path) -> Result<(), OxenError> {
+    save_with_compression(repo, dst_path, ArchiveCompression::Gzip)
+}
+
+/// Save the repository to a tarball compressed with `compression`, `oxen load` detects the format
+pub fn save_with_compression(
+    repo: &LocalRepository,
+    dst_path: &Path,
+    compression: ArchiveCompression,
+) -> Result<(), OxenError> {
     let output_path = if !dst_path.exists() {
         dst_path.to_path_buf()
     } else {
         match (dst_path.is_file(), dst_path.is_dir()) {
             (true, false) => dst_path.to_path_buf(),
-            (false, true) => dst_path.join("oxen-archive.tar.gz"),
+            (false, true) => dst_path.join(format!("oxen-archive.{}", compression.extension())),
             _ => return Err(OxenError::basic_str(dst_path.to_str().unwrap())),
         }
     };
 
     let oxen_dir = util::fs::oxen_hidden_dir(&repo.path);
-    let tar_subdir = Path::new(OXEN_HIDDEN_DIR);
-
-    let enc = GzEncoder::new(Vec::new(), Compression::default());
-    let mut tar = tar::Builder::new(enc);
 
     log::debug!(
-        "command::save compressing oxen dir at {:?} into tarball",
-        oxen_dir
+        "command::save compressing oxen dir at {:?} into {} tarball",
+        oxen_dir,
+        compression
     );
 
     println!("🐂 Compressing oxen repo at {:?}", repo.path);
 
-    tar.append_dir_all(tar_subdir, &oxen_dir)?;
-    tar.finish()?;
+    let file = File::create(&output_path)?;
+    let mut file = match compression {
+        ArchiveCompression::None => append_oxen_dir(file, &oxen_dir)?,
+        ArchiveCompression::Gzip => {
+            let enc = GzEncoder::new(file, Compression::default());
+            append_oxen_dir(enc, &oxen_dir)?.finish()?
+        }
+        ArchiveCompression::Zstd => {
+            let enc = zstd::Encoder::new(file, zstd::DEFAULT_COMPRESSION_LEVEL)?;
+            append_oxen_dir(enc, &oxen_dir)?.finish()?
+        }
+    };
+    file.flush()?;
 
-    let buffer: Vec<u8> = tar.into_inner()?.finish()?;
-    let total_size: u64 = u64::try_from(buffer.len()).unwrap_or(u64::MAX);
+    let total_size = file.metadata()?.len();
     log::debug!("command::save tarball size is {}", ByteSize(total_size));
 
-    let mut file = File::create(output_path.clone())?;
-    file.write_all(&buffer)?;
-
     println!("\n\n✅ Saved oxen repo to {:?}\n\n", output_path);
 
     Ok(())
 }
+
+fn append_oxen_dir<W: Write>(writer: W, oxen_dir: &Path) -> Result<W, OxenError> {
+    let mut tar = tar::Builder::new(writer);
+    tar.append_dir_all(Path::new(OXEN_HIDDEN_DIR), oxen_dir)?;
+    tar.finish()?;
+    Ok(tar.into_inner()?)
+}
@@ -4,12 +4,12 @@
 //!
 
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::api;
 use crate::core::index::Stager;
 use crate::error::OxenError;
-use crate::model::{LocalRepository, Schema};
+use crate::model::{LocalRepository, Schema, SchemaChange};
 
 /// List the saved off schemas for a commit id
 pub fn list(
@@ -90,6 +90,14 @@ pub fn show(
     Ok(results)
 }
 
+/// List the commits that changed the schema of a file, most recent first
+pub fn history(
+    repo: &LocalRepository,
+    path: impl AsRef<Path>,
+) -> Result<Vec<SchemaChange>, OxenError> {
+    api::local::schemas::history(repo, path)
+}
+
 /// Set the name of a schema
 pub fn set_name(repo: &LocalRepository, hash: &str, val: &str) -> Result<(), OxenError> {
     let stager = Stager::new(repo)?;
@@ -814,4 +822,55 @@ mod tests {
         })
         .await
     }
+
+    #[test]
+    fn test_command_schemas_history_lists_each_change() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let csv_file = Path::new("data.csv");
+            let csv_path = repo.path.join(csv_file);
+            let readme_path = repo.path.join("README.md");
+
+            util::fs::write_to_path(&csv_path, "a,b\n1,2\n")?;
+            command::add(&repo, &csv_path)?;
+            let first = command::commit(&repo, "Adding data")?;
+
+            util::fs::write_to_path(&csv_path, "a,b,c\n1,2,3\n")?;
+            command::add(&repo, &csv_path)?;
+            let added = command::commit(&repo, "Adding column c")?;
+
+            // Commits that do not touch the schema are skipped
+            util::fs::write_to_path(&readme_path, "Hello")?;
+            command::add(&repo, &readme_path)?;
+            command::commit(&repo, "Adding README")?;
+
+            util::fs::write_to_path(&csv_path, "a,c\n1,3\n")?;
+            command::add(&repo, &csv_path)?;
+            let dropped = command::commit(&repo, "Dropping column b")?;
+
+            let history = command::schemas::history(&repo, csv_file)?;
+            assert_eq!(history.len(), 3);
+
+            assert_eq!(history[0].commit.id, dropped.id);
+            assert!(history[0].diff.added.is_empty());
+            assert_eq!(history[0].diff.removed.len(), 1);
+            assert_eq!(history[0].diff.removed[0].name, "b");
+
+            assert_eq!(history[1].commit.id, added.id);
+            assert_eq!(history[1].diff.added.len(), 1);
+            assert_eq!(history[1].diff.added[0].name, "c");
+            assert!(history[1].diff.removed.is_empty());
+
+            assert_eq!(history[2].commit.id, first.id);
+            let names: Vec<String> = history[2]
+                .diff
+                .added
+                .iter()
+                .map(|f| f.name.clone())
+                .collect();
+            assert_eq!(names, vec!["a", "b"]);
+            assert!(history[2].diff.removed.is_empty());
+
+            Ok(())
+        })
+    }
 }
@@ -0,0 +1,283 @@
+//! # oxen stash
+//!
+//! Save uncommitted changes off to the side and restore them later
+//!
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use time::OffsetDateTime;
+
+use crate::constants::{STASH_DIR, STASH_ENTRY_FILE};
+use crate::core::index::{self, CommitEntryReader, Stager};
+use crate::error::OxenError;
+use crate::model::{LocalRepository, StagedEntryStatus, StashEntry, StashedFile};
+use crate::opts::RestoreOpts;
+use crate::{api, command, util};
+
+const STASH_FILES_DIR: &str = "files";
+
+/// Directory where all the stash entries live, .oxen/stash
+pub fn stash_dir(repo: &LocalRepository) -> PathBuf {
+    util::fs::oxen_hidden_dir(&repo.path).join(STASH_DIR)
+}
+
+fn stash_entry_dir(repo: &LocalRepository, id: &str) -> PathBuf {
+    stash_dir(repo).join(id)
+}
+
+/// # Stash the staged and unstaged changes
+/// Saves a copy of every modified, added, or removed file relative to HEAD into .oxen/stash,
+/// then resets the working directory and staging area back to HEAD.
+/// Untracked files are left in place.
+pub fn stash(repo: &LocalRepository, message: Option<&str>) -> Result<StashEntry, OxenError> {
+    let status = command::status(repo)?;
+    let head_commit = api::local::commits::head_commit(repo)?;
+
+    // Collect every change, a path could be both staged and modified again after staging
+    let mut files: HashMap<PathBuf, StashedFile> = HashMap::new();
+    for (path, entry) in status.staged_files.iter() {
+        files.insert(
+            path.to_owned(),
+            StashedFile {
+                path: path.to_owned(),
+                status: entry.status.clone(),
+                staged: true,
+            },
+        );
+    }
+
+    for path in status.modified_files.iter() {
+        files.entry(path.to_owned()).or_insert_with(|| StashedFile {
+            path: path.to_owned(),
+            status: StagedEntryStatus::Modified,
+            staged: false,
+        });
+    }
+
+    for path in status.removed_files.iter() {
+        files.insert(
+            path.to_owned(),
+            StashedFile {
+                path: path.to_owned(),
+                status: StagedEntryStatus::Removed,
+                staged: false,
+            },
+        );
+    }
+
+    if files.is_empty() {
+        return Err(OxenError::basic_str("No local changes to stash"));
+    }
+
+    let branch = api::local::branches::current_branch(repo)?.map(|b| b.name);
+    let message = match message {
+        Some(message) => message.to_string(),
+        None => format!(
+            "WIP on {}",
+            branch.clone().unwrap_or(head_commit.id.clone())
+        ),
+    };
+
+    let mut files: Vec<StashedFile> = files.into_values().collect();
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let entry = StashEntry {
+        id: uuid::Uuid::new_v4().to_string(),
+        message,
+        branch,
+        commit_id: head_commit.id.clone(),
+        files,
+        timestamp: OffsetDateTime::now_utc(),
+    };
+
+    // Save the contents of the working files
+    let entry_dir = stash_entry_dir(repo, &entry.id);
+    let files_dir = entry_dir.join(STASH_FILES_DIR);
+    util::fs::create_dir_all(&files_dir)?;
+    for file in entry.files.iter() {
+        if file.status != StagedEntryStatus::Removed {
+            util::fs::copy_mkdir(repo.path.join(&file.path), files_dir.join(&file.path))?;
+        }
+    }
+    let json = serde_json::to_string(&entry)?;
+    util::fs::write_to_path(entry_dir.join(STASH_ENTRY_FILE), json)?;
+
+    // Reset the staging area and working files back to HEAD
+    let stager = Stager::new(repo)?;
+    stager.unstage()?;
+    let reader = CommitEntryReader::new(repo, &head_commit)?;
+    for file in entry.files.iter() {
+        if reader.has_file(&file.path) {
+            index::restore(repo, RestoreOpts::from_path(&file.path))?;
+        } else {
+            let full_path = repo.path.join(&file.path);
+            if full_path.exists() {
+                util::fs::remove_file(&full_path)?;
+            }
+        }
+    }
+
+    println!("Saved working directory: {}", entry);
+    Ok(entry)
+}
+
+/// # List the stash entries
+/// Most recent stash first
+pub fn list(repo: &LocalRepository) -> Result<Vec<StashEntry>, OxenError> {
+    let dir = stash_dir(repo);
+    if !dir.exists() {
+        return Ok(vec![]);
+    }
+
+    let mut entries: Vec<StashEntry> = vec![];
+    for entry_dir in std::fs::read_dir(&dir)?.flatten() {
+        let entry_dir = entry_dir.path();
+        let entry_file = entry_dir.join(STASH_ENTRY_FILE);
+        if !entry_file.exists() {
+            log::warn!("Skipping stash dir without entry: {:?}", entry_dir);
+            continue;
+        }
+        let contents = util::fs::read_from_path(&entry_file)?;
+        let entry: StashEntry = serde_json::from_str(&contents)?;
+        entries.push(entry);
+    }
+    entries.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    Ok(entries)
+}
+
+/// # Apply the most recent stash entry and remove it from the stack
+pub fn pop(repo: &LocalRepository) -> Result<StashEntry, OxenError> {
+    let entries = list(repo)?;
+    let Some(entry) = entries.into_iter().next() else {
+        return Err(OxenError::basic_str("No stash entries found"));
+    };
+
+    apply(repo, &entry)?;
+    util::fs::remove_dir_all(stash_entry_dir(repo, &entry.id))?;
+
+    println!("Dropped stash: {}", entry);
+    Ok(entry)
+}
+
+/// # Write the files in a stash entry back to the working directory
+/// Changes that were staged when stashed get re-staged.
+pub fn apply(repo: &LocalRepository, entry: &StashEntry) -> Result<(), OxenError> {
+    // Do not clobber changes that were made after stashing
+    let status = command::status(repo)?;
+    for file in entry.files.iter() {
+        if status.staged_files.contains_key(&file.path)
+            || status.modified_files.contains(&file.path)
+            || status.untracked_files.contains(&file.path)
+        {
+            let err = format!(
+                "Local changes to {:?} would be overwritten by stash, commit or restore them first",
+                file.path
+            );
+            return Err(OxenError::basic_str(err));
+        }
+    }
+
+    let files_dir = stash_entry_dir(repo, &entry.id).join(STASH_FILES_DIR);
+    for file in entry.files.iter() {
+        let full_path = repo.path.join(&file.path);
+        match file.status {
+            StagedEntryStatus::Added | StagedEntryStatus::Modified => {
+                util::fs::copy_mkdir(files_dir.join(&file.path), &full_path)?;
+            }
+            StagedEntryStatus::Removed => {
+                if full_path.exists() {
+                    util::fs::remove_file(&full_path)?;
+                }
+            }
+        }
+    }
+
+    for file in entry.files.iter().filter(|f| f.staged) {
+        command::add(repo, repo.path.join(&file.path))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::api;
+    use crate::command;
+    use crate::error::OxenError;
+    use crate::test;
+    use crate::util;
+
+    #[tokio::test]
+    async fn test_command_stash_switch_branch_pop() -> Result<(), OxenError> {
+        test::run_training_data_repo_test_fully_committed_async(|repo| async move {
+            let og_branch = api::local::branches::current_branch(&repo)?.unwrap();
+
+            // Create a branch to switch to
+            let branch_name = "feature/stash";
+            api::local::branches::create_from_head(&repo, branch_name)?;
+
+            // Modify a file
+            let readme_path = repo.path.join("README.md");
+            let readme_path = test::append_line_txt_file(readme_path, "Stash me")?;
+            let modified = util::fs::read_from_path(&readme_path)?;
+
+            // Stash and make sure the working dir is clean
+            let entry = command::stash::stash(&repo, None)?;
+            assert_eq!(entry.files.len(), 1);
+            assert!(command::status(&repo)?.is_clean());
+            assert_eq!(entry.files[0].path, Path::new("README.md"));
+            assert_ne!(util::fs::read_from_path(&readme_path)?, modified);
+
+            // Switch away and back
+            command::checkout(&repo, branch_name).await?;
+            command::checkout(&repo, &og_branch.name).await?;
+
+            // Pop and make sure the modification is back
+            command::stash::pop(&repo)?;
+            assert_eq!(util::fs::read_from_path(&readme_path)?, modified);
+            assert!(command::stash::list(&repo)?.is_empty());
+
+            let status = command::status(&repo)?;
+            assert_eq!(status.modified_files.len(), 1);
+
+            Ok(())
+        })
+        .await
+    }
+
+    #[test]
+    fn test_command_stash_staged_file_is_restaged() -> Result<(), OxenError> {
+        test::run_training_data_repo_test_fully_committed(|repo| {
+            let new_file = repo.path.join("new_file.txt");
+            util::fs::write_to_path(&new_file, "I am new")?;
+            command::add(&repo, &new_file)?;
+
+            command::stash::stash(&repo, Some("adding new file"))?;
+            assert!(!new_file.exists());
+            assert!(command::status(&repo)?.is_clean());
+
+            let entries = command::stash::list(&repo)?;
+            assert_eq!(entries.len(), 1);
+            assert_eq!(entries[0].message, "adding new file");
+
+            command::stash::pop(&repo)?;
+            assert!(new_file.exists());
+            let status = command::status(&repo)?;
+            assert_eq!(status.staged_files.len(), 1);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_command_stash_nothing_to_stash() -> Result<(), OxenError> {
+        test::run_training_data_repo_test_fully_committed(|repo| {
+            let result = command::stash::stash(&repo, None);
+            assert!(result.is_err());
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,203 @@
+//! # oxen verify
+//!
+//! Check the integrity of the commit history and the version files backing it
+//!
+
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+use crate::api;
+use crate::core::index::{puller, CommitEntryReader, CommitReader};
+use crate::error::OxenError;
+use crate::model::entry::commit_entry::Entry;
+use crate::model::{CommitEntry, LocalRepository, VerifyIssue, VerifyReport};
+use crate::util;
+
+/// # Verify the repository
+/// Walks every commit, checks that its parents exist, and that every entry has a version file
+/// that hashes to the entry hash. If `fix` is true, missing or corrupt version files are
+/// re-downloaded from the remote.
+pub async fn verify(repo: &LocalRepository, fix: bool) -> Result<VerifyReport, OxenError> {
+    let (mut report, broken_entries) = verify_local(repo)?;
+
+    if fix && !broken_entries.is_empty() {
+        fix_entries(repo, &broken_entries).await?;
+
+        // Check the entries again, anything that is good now has been fixed
+        let mut issues = vec![];
+        for issue in report.issues {
+            let still_broken = broken_entries
+                .iter()
+                .find(|(commit_id, entry)| {
+                    issue.commit_id() == commit_id && issue.path() == Some(&entry.path)
+                })
+                .map(|(commit_id, entry)| check_entry(repo, commit_id, entry).is_some())
+                .unwrap_or(true);
+
+            if still_broken {
+                issues.push(issue);
+            } else {
+                report.fixed.push(issue);
+            }
+        }
+        report.issues = issues;
+    }
+
+    Ok(report)
+}
+
+fn verify_local(
+    repo: &LocalRepository,
+) -> Result<(VerifyReport, Vec<(String, CommitEntry)>), OxenError> {
+    let commit_reader = CommitReader::new(repo)?;
+    let mut commits = commit_reader.list_all()?;
+    commits.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+
+    let mut report = VerifyReport {
+        num_commits: commits.len(),
+        ..VerifyReport::default()
+    };
+
+    // Many commits share the same version files, only hash each one once
+    let mut checked: HashMap<PathBuf, Option<VerifyIssue>> = HashMap::new();
+    let mut broken_entries: Vec<(String, CommitEntry)> = vec![];
+    let mut seen_broken: HashSet<PathBuf> = HashSet::new();
+    for commit in commits.iter() {
+        for parent_id in commit.parent_ids.iter() {
+            if !commit_reader.commit_id_exists(parent_id) {
+                log::debug!("{}", OxenError::local_parent_link_broken(parent_id));
+                report.issues.push(VerifyIssue::BrokenParentLink {
+                    commit_id: commit.id.to_owned(),
+                    parent_id: parent_id.to_owned(),
+                });
+            }
+        }
+
+        let entry_reader = CommitEntryReader::new(repo, commit)?;
+        for entry in entry_reader.list_entries()? {
+            report.num_entries += 1;
+
+            let version_path = util::fs::version_path(repo, &entry);
+            let issue = checked
+                .entry(version_path.clone())
+                .or_insert_with(|| check_entry(repo, &commit.id, &entry));
+
+            if let Some(issue) = issue {
+                if seen_broken.insert(version_path) {
+                    report.issues.push(issue.clone());
+                    broken_entries.push((commit.id.to_owned(), entry));
+                }
+            }
+        }
+    }
+
+    Ok((report, broken_entries))
+}
+
+fn check_entry(
+    repo: &LocalRepository,
+    commit_id: &str,
+    entry: &CommitEntry,
+) -> Option<VerifyIssue> {
+    let version_path = util::fs::version_path(repo, entry);
+    if !version_path.exists() {
+        return Some(VerifyIssue::MissingVersionFile {
+            commit_id: commit_id.to_owned(),
+            path: entry.path.to_owned(),
+        });
+    }
+
+    let actual = match util::hasher::hash_file_contents_with_retry(&version_path) {
+        Ok(hash) => hash,
+        Err(err) => {
+            log::error!("Could not hash version file {:?}: {}", version_path, err);
+            String::from("")
+        }
+    };
+    if actual != entry.hash {
+        return Some(VerifyIssue::HashMismatch {
+            commit_id: commit_id.to_owned(),
+            path: entry.path.to_owned(),
+            expected: entry.hash.to_owned(),
+            actual,
+        });
+    }
+    None
+}
+
+async fn fix_entries(
+    repo: &LocalRepository,
+    broken_entries: &[(String, CommitEntry)],
+) -> Result<(), OxenError> {
+    let remote_repo = api::remote::repositories::get_default_remote(repo).await?;
+
+    // Clear out the corrupt files so they get replaced
+    let mut entries: Vec<Entry> = vec![];
+    for (_, entry) in broken_entries.iter() {
+        let version_path = util::fs::version_path(repo, entry);
+        if version_path.exists() {
+            util::fs::remove_file(&version_path)?;
+        }
+        entries.push(Entry::from(entry.to_owned()));
+    }
+
+    println!(
+        "Downloading {} files from {}",
+        entries.len(),
+        remote_repo.remote.url
+    );
+    puller::pull_entries_to_versions_dir(&remote_repo, &entries, &repo.path).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api;
+    use crate::command;
+    use crate::core::index::CommitEntryReader;
+    use crate::error::OxenError;
+    use crate::model::VerifyIssue;
+    use crate::test;
+    use crate::util;
+
+    #[test]
+    fn test_command_verify_clean_repo() -> Result<(), OxenError> {
+        test::run_training_data_repo_test_fully_committed(|repo| {
+            let (report, _) = command::verify::verify_local(&repo)?;
+            assert!(report.is_ok());
+            assert!(report.num_commits > 0);
+            assert!(report.num_entries > 0);
+            Ok(())
+        })
+    }
+
+    #[tokio::test]
+    async fn test_command_verify_fix_missing_version_file() -> Result<(), OxenError> {
+        test::run_training_data_fully_sync_remote(|repo, remote_repo| async move {
+            let head = api::local::commits::head_commit(&repo)?;
+            let reader = CommitEntryReader::new(&repo, &head)?;
+            let entry = reader.list_entries()?.into_iter().next().unwrap();
+
+            // Corrupt the repo by removing a version file
+            let version_path = util::fs::version_path(&repo, &entry);
+            util::fs::remove_file(&version_path)?;
+
+            let report = command::verify(&repo, false).await?;
+            assert!(!report.is_ok());
+            assert!(report.issues.iter().any(|issue| matches!(
+                issue,
+                VerifyIssue::MissingVersionFile { path, .. } if *path == entry.path
+            )));
+            assert!(!version_path.exists());
+
+            // Fix it from the remote
+            let report = command::verify(&repo, true).await?;
+            assert!(report.is_ok());
+            assert_eq!(report.fixed.len(), 1);
+            assert!(version_path.exists());
+
+            Ok(remote_repo)
+        })
+        .await
+    }
+}
@@ -2,10 +2,12 @@
 //!
 
 pub mod auth_config;
+pub mod column_access;
 pub mod endpoint;
 pub mod remote_config;
 pub mod user_config;
 
+pub use crate::config::column_access::ColumnAccess;
 pub use crate::config::remote_config::RemoteConfig;
 
 pub use crate::config::user_config::UserConfig;
@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+
+/// Columns a token scope can read through the data frame endpoints, stored in the repository
+/// config under `[column_access.<scope>]`
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ColumnAccess {
+    // Only these columns are returned when set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allow: Option<Vec<String>>,
+    // These columns are never returned, even if they are allowed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deny: Vec<String>,
+}
+
+impl ColumnAccess {
+    pub fn is_allowed(&self, column: &str) -> bool {
+        let allowed = match &self.allow {
+            Some(allow) => allow.iter().any(|c| c == column),
+            None => true,
+        };
+        allowed && !self.deny.iter().any(|c| c == column)
+    }
+
+    /// The columns that have to be projected out before returning data
+    pub fn hidden_columns<S: AsRef<str>>(&self, columns: &[S]) -> Vec<String> {
+        columns
+            .iter()
+            .map(|c| c.as_ref())
+            .filter(|c| !self.is_allowed(c))
+            .map(String::from)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::ColumnAccess;
+
+    #[test]
+    fn test_column_access_hidden_columns() {
+        let columns = ["name", "email", "age"];
+
+        let access = ColumnAccess {
+            allow: None,
+            deny: vec![String::from("email")],
+        };
+        assert_eq!(access.hidden_columns(&columns), vec!["email"]);
+
+        let access = ColumnAccess {
+            allow: Some(vec![String::from("name"), String::from("email")]),
+            deny: vec![String::from("email")],
+        };
+        assert_eq!(access.hidden_columns(&columns), vec!["email", "age"]);
+
+        assert!(ColumnAccess::default().hidden_columns(&columns).is_empty());
+    }
+}
@@ -1,14 +1,32 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::Path;
 
+use crate::config::ColumnAccess;
 use crate::error::OxenError;
-use crate::model::Remote;
+use crate::model::{Remote, RemoteBranch};
+use crate::opts::DFDefaults;
 use crate::util;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct RemoteConfig {
     pub remote_name: Option<String>, // this is the current remote name
     pub remotes: Vec<Remote>,
+    // local branch name -> remote branch it tracks
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub upstreams: BTreeMap<String, RemoteBranch>,
+    // run the scripts in .oxen/hooks, off unless turned on with `oxen config --hooks true`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub hooks_enabled: bool,
+    // default page size and display rows for `oxen df` and the data frame endpoints
+    #[serde(default, skip_serializing_if = "DFDefaults::is_empty")]
+    pub df_defaults: DFDefaults,
+    // repo made with `oxen init --bare`, it only holds the .oxen dir and never has working files
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub bare: bool,
+    // token scope -> the columns it can read from the data frame endpoints
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub column_access: BTreeMap<String, ColumnAccess>,
 }
 
 impl Default for RemoteConfig {
@@ -22,6 +40,11 @@ impl RemoteConfig {
         RemoteConfig {
             remote_name: None,
             remotes: Vec::new(),
+            upstreams: BTreeMap::new(),
+            hooks_enabled: false,
+            df_defaults: DFDefaults::default(),
+            bare: false,
+            column_access: BTreeMap::new(),
         }
     }
 
@@ -15,8 +15,14 @@ pub const TMP_DIR: &str = ".cache";
 pub const CONFIG_DIR: &str = ".config";
 /// .oxenignore is the name of the file that contains the ignore patterns
 pub const OXEN_IGNORE_FILE: &str = ".oxenignore";
+/// .oxenattributes declares per path handling such as binary files and push chunk sizes
+pub const OXEN_ATTRIBUTES_FILE: &str = ".oxenattributes";
 /// Config file for the repository
 pub const REPO_CONFIG_FILENAME: &str = "config.toml";
+/// Env var to keep the .oxen dir outside of the repository root, ie. for read-only data dirs
+pub const OXEN_DIR_ENV: &str = "OXEN_DIR";
+/// File within an OXEN_DIR that records the repository root it belongs to
+pub const WORK_TREE_FILE: &str = "WORK_TREE";
 /// HEAD file holds onto where the head commit is (commit_id or branch name)
 pub const HEAD_FILE: &str = "HEAD";
 /// refs/ is a key,val store of branch names to commit ids
@@ -31,6 +37,10 @@ pub const SCHEMAS_DIR: &str = "schemas";
 pub const SCHEMAS_TREE_PREFIX: &str = ".oxen";
 // name of dir for locking branches during push
 pub const BRANCH_LOCKS_DIR: &str = "locks";
+/// idempotency_keys/ records which commit a remote staging commit request with an idempotency key created
+pub const IDEMPOTENCY_KEYS_DIR: &str = "idempotency_keys";
+/// an idempotency key still in progress after this many seconds is from a crashed commit and can be reclaimed
+pub const IDEMPOTENCY_KEY_TIMEOUT_SECS: u64 = 3600;
 // name of file for locking repository during push
 pub const REPOSITORY_LOCK_FILE: &str = "LOCK";
 /// prefix for the commit rows
@@ -70,10 +80,16 @@ pub const DUCKDB_CACHE_DIR: &str = "duckdb";
 pub const DUCKDB_DF_TABLE_NAME: &str = "df";
 /// Max number of rows to query from a dataframe
 pub const MAX_QUERYABLE_ROWS: usize = 1_000_000;
+/// Max number of cells in a dataframe we are willing to transpose
+pub const MAX_TRANSPOSE_CELLS: usize = 100_000;
 /// prefix for the sync status dirs to tell if commits are synced locally
 pub const SYNC_STATUS_DIR: &str = "sync_status";
 /// Flag for if the repository was cloned in a shallow fashion
 pub const SHALLOW_FLAG: &str = "SHALLOW";
+/// Files larger than the number of bytes in this file are not downloaded until materialized
+pub const LFS_THRESHOLD_FILE: &str = "LFS_THRESHOLD";
+/// Paths that were cloned as placeholders and have not been materialized yet
+pub const PLACEHOLDERS_FILE: &str = "placeholders.json";
 /// prefix for the commit indices
 pub const INDICES_DIR: &str = "indices";
 /// prefix for the schema fields that are indexed
@@ -94,6 +110,26 @@ pub const OBJECT_SCHEMAS_DIR: &str = "schemas";
 pub const VERSION_FILE_NAME: &str = "data";
 /// merge/ is where any merge conflicts are stored so that we can get rid of them
 pub const MERGE_DIR: &str = "merge";
+/// stash/ is where uncommitted changes are saved with `oxen stash`
+pub const STASH_DIR: &str = "stash";
+/// logs/ holds the history of where refs have pointed
+pub const LOGS_DIR: &str = "logs";
+/// File in logs/ recording every movement of HEAD, read with `oxen reflog`
+pub const HEAD_LOG_FILE: &str = "HEAD";
+/// Metadata file for each stash entry
+pub const STASH_ENTRY_FILE: &str = "stash.json";
+/// Template that prefills the commit message when an editor is opened
+pub const COMMIT_TEMPLATE_FILE: &str = "commit_template";
+/// Scratch file the editor writes the commit message to
+pub const COMMIT_EDITMSG_FILE: &str = "COMMIT_EDITMSG";
+/// File in a remote staging dir that records which identifier it belongs to
+pub const STAGING_IDENTIFIER_FILE: &str = "IDENTIFIER";
+/// hooks/ is where the pre-commit and post-commit scripts live
+pub const HOOKS_DIR: &str = "hooks";
+/// Hook run before committing, a nonzero exit aborts the commit
+pub const PRE_COMMIT_HOOK: &str = "pre-commit";
+/// Hook run after a commit is created
+pub const POST_COMMIT_HOOK: &str = "post-commit";
 /// mods/ is where we can stage appends, modifications, deletions to files to be merged later
 pub const MODS_DIR: &str = "mods";
 /// data.arrow
@@ -130,6 +166,20 @@ pub const ROW_NUM_COL_NAME: &str = "_row_num";
 pub const ROW_HASH_COL_NAME: &str = "_row_hash";
 /// Internal Name When Performing Computation
 pub const FILE_ROW_NUM_COL_NAME: &str = "_file_row_num";
+/// Default name of the column `oxen df --coalesce` writes to
+pub const COALESCE_COL_NAME: &str = "coalesced";
+/// Column `oxen df --fuzzy-join` writes the similarity of each matched pair to
+pub const FUZZY_SIMILARITY_COL_NAME: &str = "similarity";
+/// Suffix for columns of the `oxen df --fuzzy-join` file that clash with the input columns
+pub const FUZZY_JOIN_RIGHT_SUFFIX: &str = "_right";
+/// Column `oxen df --detect-duplicates` numbers each group of duplicate rows in
+pub const DUPLICATE_GROUP_COL_NAME: &str = "duplicate_group";
+/// Column `oxen df --detect-duplicates` writes the number of rows in each group to
+pub const DUPLICATE_COUNT_COL_NAME: &str = "duplicate_count";
+/// Minimum jaro winkler similarity for `oxen df --fuzzy-join` to match two rows
+pub const DEFAULT_FUZZY_THRESHOLD: f64 = 0.9;
+/// Column `oxen df --seed-split` writes the split of each row to
+pub const SPLIT_COL_NAME: &str = "split";
 // Internal Name When Performing Computation
 pub const TARGETS_HASH_COL: &str = "_targets_hash";
 // Internal Name When Performing Computation
@@ -144,9 +194,22 @@ pub const DIFF_HASH_COL: &str = ".oxen.diff.hash";
 /// Average chunk size of ~4mb when chunking and sending data
 // pub const AVG_CHUNK_SIZE: u64 = 1024 * 1024 * 4;
 pub const AVG_CHUNK_SIZE: u64 = 1024 * 1024 * 4;
+/// Max number of content ids to request from the server in a single request
+pub const MAX_CONTENT_IDS_PER_REQUEST: usize = 1_000;
+/// Max size in bytes of the content ids sent to the server in a single request
+pub const MAX_CONTENT_IDS_REQUEST_BYTES: usize = 1024 * 256;
 // Retry and back off of requests N times
 /// Retry and back off of requests N times
 pub const NUM_HTTP_RETRIES: u64 = 10;
+/// Seconds to wait to connect to a remote, override with OXEN_CONNECT_TIMEOUT_SECS
+pub const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 30;
+/// Seconds to wait without receiving any data from a remote, override with OXEN_READ_TIMEOUT_SECS.
+/// Not a cap on the whole request, so large transfers that keep making progress never time out
+pub const DEFAULT_READ_TIMEOUT_SECS: u64 = 300;
+/// Seconds `oxen push --wait` waits for the server to finish caching the pushed commit
+pub const DEFAULT_CACHE_WAIT_TIMEOUT_SECS: u64 = 1800;
+/// Milliseconds between checks of the commit cache status while waiting on it
+pub const CACHE_STATUS_POLL_INTERVAL_MS: u64 = 1000;
 /// Number of workers
 pub const DEFAULT_NUM_WORKERS: usize = 8;
 
@@ -155,8 +218,14 @@ pub const DEFAULT_PAGE_SIZE: usize = 100;
 /// Pagination page number of 1
 pub const DEFAULT_PAGE_NUM: usize = 1;
 
-/// Redis queue name for post commit actions
-pub const COMMIT_QUEUE_NAME: &str = "commit_queue";
+/// Redis sorted set for post commit actions, scored by task priority then insertion order
+pub const COMMIT_QUEUE_NAME: &str = "commit_priority_queue";
+/// Redis counter used to keep tasks of equal priority in FIFO order
+pub const COMMIT_QUEUE_SEQ_NAME: &str = "commit_priority_queue_seq";
+/// Redis list older servers queued post commit actions on, drained into the priority queue on startup
+pub const LEGACY_COMMIT_QUEUE_NAME: &str = "commit_queue";
+/// Redis key prefix for the per token or per ip request counters of the server rate limiter
+pub const RATE_LIMIT_KEY_PREFIX: &str = "rate_limit";
 pub const DEFAULT_REDIS_URL: &str = "redis://localhost:6379";
 
 /// Data Types
@@ -4,4 +4,6 @@
 pub mod cache;
 pub mod db;
 pub mod df;
+pub mod export;
+pub mod hooks;
 pub mod index;
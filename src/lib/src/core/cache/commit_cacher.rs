@@ -82,6 +82,12 @@ pub fn get_failures(
     Ok(vals)
 }
 
+/// Whether every cacher has already been run successfully on the commit
+pub fn is_cached(repo: &LocalRepository, commit: &Commit) -> Result<bool, OxenError> {
+    let vals = get_all_statuses(repo, commit)?;
+    Ok(vals.len() == CACHERS.len() && vals.iter().all(|v| CacherStatusType::Success == v.status))
+}
+
 /// Return all the statuses from cacher processes that were run
 pub fn get_all_statuses(
     repo: &LocalRepository,
@@ -6,7 +6,7 @@ use crate::constants::{
 };
 
 use crate::core::df::tabular;
-use crate::error::OxenError;
+use crate::error::{OxenError, StringError};
 use crate::model::schema::Field;
 use crate::model::Schema;
 use crate::opts::DFOpts;
@@ -16,8 +16,9 @@ use arrow_json::WriterBuilder;
 use duckdb::arrow::record_batch::RecordBatch;
 use duckdb::{params, ToSql};
 use polars::prelude::*;
+use std::collections::BTreeMap;
 use std::io::Cursor;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use sql_query_builder as sql;
 
@@ -212,10 +213,17 @@ pub fn select_str(
     let empty_opts = DFOpts::empty();
     let opts = opts.unwrap_or(&empty_opts);
 
-    if let Some(sort_by) = &opts.sort_by {
-        sql.push_str(&format!(" ORDER BY \"{}\"", sort_by));
-        if opts.should_reverse {
-            sql.push_str(" DESC");
+    if let Some(sort_by) = opts.sort_by_columns()? {
+        if !sort_by.is_empty() {
+            let order_by = sort_by
+                .iter()
+                .map(|(column, descending)| {
+                    let dir = if *descending { "DESC" } else { "ASC" };
+                    format!("\"{}\" {}", column, dir)
+                })
+                .collect::<Vec<String>>()
+                .join(", ");
+            sql.push_str(&format!(" ORDER BY {}", order_by));
         }
     }
     let pagination_clause = if let Some(page) = opts.page {
@@ -375,43 +383,26 @@ pub fn modify_row_with_polars_df(
 
 pub fn index_file(path: &Path, conn: &duckdb::Connection) -> Result<(), OxenError> {
     log::debug!("df_db:index_file() at path {:?}", path);
-    let extension: &str = &util::fs::extension_from_path(path);
-    let path_str = path.to_string_lossy().to_string();
-    match extension {
-        "csv" => {
-            let query = format!(
-                "CREATE TABLE {} AS SELECT * FROM read_csv('{}')",
-                DUCKDB_DF_TABLE_NAME, path_str
-            );
-            conn.execute(&query, [])?;
-        }
-        "tsv" => {
-            let query = format!(
-                "CREATE TABLE {} AS SELECT * FROM read_csv('{}')",
-                DUCKDB_DF_TABLE_NAME, path_str
-            );
-            conn.execute(&query, [])?;
-        }
-        "parquet" => {
-            let query = format!(
-                "CREATE TABLE {} AS SELECT * FROM read_parquet('{}')",
-                DUCKDB_DF_TABLE_NAME, path_str
-            );
-            conn.execute(&query, [])?;
-        }
-        "jsonl" | "json" | "ndjson" => {
-            let query = format!(
-                "CREATE TABLE {} AS SELECT * FROM read_json('{}')",
-                DUCKDB_DF_TABLE_NAME, path_str
-            );
-            conn.execute(&query, [])?;
-        }
-        _ => {
-            return Err(OxenError::basic_str(
-                "Invalid file type: expected .csv, .tsv, .parquet, .jsonl, .json, .ndjson",
-            ))
-        }
+    if util::fs::compression_from_path(path).is_some()
+        && util::fs::inner_extension_from_path(path) == "parquet"
+    {
+        // DuckDB cannot read compressed parquet, but the table is a copy so a temp file is fine
+        let tmp_dir =
+            std::env::temp_dir().join(format!("oxen_decompress_{}", uuid::Uuid::new_v4()));
+        util::fs::create_dir_all(&tmp_dir)?;
+        let tmp_path = tmp_dir.join(path.file_stem().unwrap_or_default());
+        let result =
+            util::fs::decompress_file(path, &tmp_path).and_then(|_| index_file(&tmp_path, conn));
+        util::fs::remove_dir_all(&tmp_dir)?;
+        return result;
     }
+
+    let from = from_clause_from_disk_path(path)?;
+    let query = format!(
+        "CREATE TABLE {} AS SELECT * FROM {}",
+        DUCKDB_DF_TABLE_NAME, from
+    );
+    conn.execute(&query, [])?;
     Ok(())
 }
 
@@ -419,8 +410,6 @@ pub fn index_file(path: &Path, conn: &duckdb::Connection) -> Result<(), OxenErro
 // For now, just treat the extension as law
 pub fn index_file_with_id(path: &Path, conn: &duckdb::Connection) -> Result<(), OxenError> {
     log::debug!("df_db:index_file() at path {:?} into path {:?}", path, conn);
-    let extension: &str = &util::fs::extension_from_path(path);
-    let path_str = path.to_string_lossy().to_string();
     let counter = "counter";
     // Drop sequence if exists
     let drop_sequence_query = format!("DROP SEQUENCE IF EXISTS {}", counter);
@@ -429,32 +418,13 @@ pub fn index_file_with_id(path: &Path, conn: &duckdb::Connection) -> Result<(),
     let add_row_id_sequence_query = format!("CREATE SEQUENCE {} START 1", counter);
     conn.execute(&add_row_id_sequence_query, [])?;
 
-    match extension {
-        "csv" => {
-            let query = format!("CREATE TABLE {} AS SELECT *, CAST(uuid() AS VARCHAR) AS {} FROM read_csv('{}', AUTO_DETECT=TRUE, header=True);", DUCKDB_DF_TABLE_NAME, OXEN_ID_COL, path.to_string_lossy());
-            conn.execute(&query, [])?;
-        }
-        "tsv" => {
-            let query = format!("CREATE TABLE {} AS SELECT *, CAST(uuid() AS VARCHAR) AS {} FROM read_csv('{}', AUTO_DETECT=TRUE, header=True);", DUCKDB_DF_TABLE_NAME, OXEN_ID_COL, path.to_string_lossy());
-            conn.execute(&query, [])?;
-        }
-        "parquet" => {
-            let query = format!("CREATE TABLE {} AS SELECT *, CAST(uuid() AS VARCHAR) AS {} FROM read_parquet('{}');", DUCKDB_DF_TABLE_NAME, OXEN_ID_COL, path.to_string_lossy());
-            conn.execute(&query, [])?;
-        }
-        "jsonl" | "json" | "ndjson" => {
-            let query = format!(
-                "CREATE TABLE {} AS SELECT *, CAST(uuid() AS VARCHAR) AS {} FROM read_json('{}');",
-                DUCKDB_DF_TABLE_NAME, OXEN_ID_COL, path_str
-            );
-            conn.execute(&query, [])?;
-        }
-        _ => {
-            return Err(OxenError::basic_str(
-                "Invalid file type: expected .csv, .tsv, .parquet, .jsonl, .json, .ndjson",
-            ))
-        }
-    }
+    let query = format!(
+        "CREATE TABLE {} AS SELECT *, CAST(uuid() AS VARCHAR) AS {} FROM {};",
+        DUCKDB_DF_TABLE_NAME,
+        OXEN_ID_COL,
+        auto_detect_read_clause(path)?
+    );
+    conn.execute(&query, [])?;
 
     let add_default_query = format!(
         "ALTER TABLE {} ALTER COLUMN {} SET DEFAULT CAST(uuid() AS VARCHAR);",
@@ -472,28 +442,372 @@ pub fn index_file_with_id(path: &Path, conn: &duckdb::Connection) -> Result<(),
     Ok(())
 }
 
-pub fn from_clause_from_disk_path(path: &Path) -> Result<String, OxenError> {
+/// The DuckDB reader for a file with types auto detected, the same way files are indexed for editing
+fn auto_detect_read_clause(path: &Path) -> Result<String, OxenError> {
     let extension: &str = &util::fs::extension_from_path(path);
+    let path_str = path.to_string_lossy().to_string();
     match extension {
-        "csv" => {
-            let str_path = path.to_string_lossy().to_string();
-            Ok(format!("read_csv('{}')", str_path))
+        "csv" | "tsv" => Ok(format!(
+            "read_csv('{}', AUTO_DETECT=TRUE, header=True)",
+            path_str
+        )),
+        "parquet" => Ok(format!("read_parquet('{}')", path_str)),
+        "jsonl" | "json" | "ndjson" => Ok(format!("read_json('{}')", path_str)),
+        _ => Err(OxenError::basic_str(
+            "Invalid file type: expected .csv, .tsv, .parquet, .jsonl, .json, .ndjson",
+        )),
+    }
+}
+
+/// Run DuckDB type detection on a file and return the schema it would be indexed with,
+/// without creating a table or any of the oxen id columns
+pub fn infer_schema(path: &Path) -> Result<Schema, OxenError> {
+    let conn = duckdb::Connection::open_in_memory()?;
+    let sql = format!("DESCRIBE SELECT * FROM {}", auto_detect_read_clause(path)?);
+    let mut stmt = conn.prepare(&sql)?;
+
+    let mut fields = vec![];
+    let rows = stmt.query_map([], |row| {
+        let column_name: String = row.get(0)?;
+        let data_type: String = row.get(1)?;
+
+        Ok((column_name, data_type))
+    })?;
+
+    for row in rows {
+        let (column_name, data_type) = row?;
+        fields.push(Field::new(
+            &column_name,
+            model::schema::DataType::from_sql(data_type).as_str(),
+        ));
+    }
+
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    Ok(Schema::new(name, fields))
+}
+
+pub fn from_clause_from_disk_path(path: &Path) -> Result<String, OxenError> {
+    // DuckDB reads gzip and zstd compressed csv and json, ie data.csv.gz
+    let extension: &str = &util::fs::inner_extension_from_path(path);
+    let compression = util::fs::compression_from_path(path);
+    let str_path = path.to_string_lossy().to_string();
+    let args = match compression {
+        Some(compression) => format!("'{}', compression='{}'", str_path, compression),
+        None => format!("'{}'", str_path),
+    };
+    match extension {
+        "csv" | "tsv" => Ok(format!("read_csv({})", args)),
+        "parquet" if compression.is_some() => Err(OxenError::basic_str(format!(
+            "Cannot query compressed parquet file {path:?}, decompress it first"
+        ))),
+        "parquet" => Ok(format!("read_parquet({})", args)),
+        "jsonl" | "json" | "ndjson" => Ok(format!("read_json({})", args)),
+        _ => Err(OxenError::basic_str(
+            "Invalid file type: expected .csv, .tsv, .parquet, .jsonl, .json, .ndjson",
+        )),
+    }
+}
+
+/// Stack the rows of the files with `UNION ALL BY NAME`, so columns line up by name not position.
+/// Errors if the files do not have the same columns and types, unless `coerce` is true, in which
+/// case DuckDB casts to a common type and fills missing columns with nulls. With `merge_schema`
+/// the columns are unioned and missing ones filled with nulls, but shared columns must match.
+pub fn concat_files(
+    conn: &duckdb::Connection,
+    paths: &[PathBuf],
+    coerce: bool,
+    merge_schema: bool,
+) -> Result<DataFrame, OxenError> {
+    let mut selects: Vec<String> = vec![];
+    let mut first: Option<(&PathBuf, BTreeMap<String, String>)> = None;
+    for path in paths.iter() {
+        if !path.exists() {
+            return Err(OxenError::entry_does_not_exist(path));
+        }
+        let from = from_clause_from_disk_path(path)?;
+
+        if !coerce {
+            let columns = describe_columns(conn, &from)?;
+            match &mut first {
+                Some((first_path, first_columns)) => {
+                    check_concat_columns(*first_path, first_columns, path, &columns, merge_schema)?;
+                    if merge_schema {
+                        // Later files are checked against every column seen so far
+                        for (name, dtype) in columns {
+                            first_columns.entry(name).or_insert(dtype);
+                        }
+                    }
+                }
+                None => first = Some((path, columns)),
+            }
+        }
+
+        selects.push(format!("SELECT * FROM {from}"));
+    }
+
+    let sql = selects.join(" UNION ALL BY NAME ");
+    log::debug!("concat_files sql: {}", sql);
+    select_raw(conn, &sql, false, None)
+}
+
+// Column name -> DuckDB type of the rows returned by the from clause
+fn describe_columns(
+    conn: &duckdb::Connection,
+    from: &str,
+) -> Result<BTreeMap<String, String>, OxenError> {
+    let mut stmt = conn.prepare(&format!("DESCRIBE SELECT * FROM {from}"))?;
+    let rows = stmt.query_map([], |row| {
+        let column_name: String = row.get(0)?;
+        let column_type: String = row.get(1)?;
+        Ok((column_name, column_type))
+    })?;
+
+    let mut columns = BTreeMap::new();
+    for row in rows {
+        let (column_name, column_type) = row?;
+        columns.insert(column_name, column_type);
+    }
+    Ok(columns)
+}
+
+fn check_concat_columns(
+    first_path: &Path,
+    first_columns: &BTreeMap<String, String>,
+    path: &Path,
+    columns: &BTreeMap<String, String>,
+    allow_missing: bool,
+) -> Result<(), OxenError> {
+    let mut errors: Vec<String> = vec![];
+    for (name, dtype) in first_columns.iter() {
+        match columns.get(name) {
+            Some(other) if other != dtype => errors.push(format!(
+                "column '{name}' is {dtype} in {first_path:?} but {other} in {path:?}"
+            )),
+            Some(_) => {}
+            None if allow_missing => {}
+            None => errors.push(format!("column '{name}' is missing from {path:?}")),
+        }
+    }
+    for name in columns.keys() {
+        if !allow_missing && !first_columns.contains_key(name) {
+            errors.push(format!("column '{name}' is missing from {first_path:?}"));
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(OxenError::IncompatibleSchemas(StringError::from(format!(
+            "Could not concat, incompatible schemas:\n  {}\n\nUse --coerce to cast the columns to a common type.",
+            errors.join("\n  ")
+        ))))
+    }
+}
+
+/// Run a read only query against a file on disk, loaded into the `df` table.
+/// `${table}` in the query is replaced with the name of the table.
+pub fn query_file(path: &Path, sql: &str) -> Result<DataFrame, OxenError> {
+    let sql = prepare_file_query(sql)?;
+    let conn = duckdb::Connection::open_in_memory()?;
+    index_file(path, &conn)?;
+    select_raw(&conn, &sql, false, None)
+}
+
+/// Get the DuckDB query plan for a read only query against a file on disk without running it.
+/// The query is written the same way as for [`query_file`].
+pub fn explain_file(path: &Path, sql: &str) -> Result<String, OxenError> {
+    let sql = prepare_file_query(sql)?;
+    let conn = duckdb::Connection::open_in_memory()?;
+    index_file(path, &conn)?;
+    let df = select_raw(&conn, &format!("EXPLAIN {sql}"), false, None)?;
+    let plan = df
+        .column("explain_value")?
+        .str()?
+        .into_iter()
+        .flatten()
+        .collect::<Vec<&str>>()
+        .join("\n");
+    Ok(plan)
+}
+
+fn prepare_file_query(sql: &str) -> Result<String, OxenError> {
+    let sql = sql
+        .trim()
+        .trim_end_matches(';')
+        .replace("${table}", DUCKDB_DF_TABLE_NAME);
+    if !is_select_query(&sql) {
+        return Err(OxenError::basic_str(
+            "Only a single SELECT query can be run on a data frame",
+        ));
+    }
+    Ok(sql)
+}
+
+/// A single SELECT statement, optionally with common table expressions
+pub fn is_select_query(sql: &str) -> bool {
+    let sql = sql.trim().trim_end_matches(';');
+    let first_word = sql
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    (first_word == "select" || first_word == "with") && !has_statement_separator(sql)
+}
+
+// Whether a `;` separates statements in `sql`, ignoring any inside string literals, quoted
+// identifiers and comments
+fn has_statement_separator(sql: &str) -> bool {
+    let mut chars = sql.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            ';' => return true,
+            '\'' | '"' => {
+                // A doubled quote escapes it, which reads as closing and reopening the string
+                for q in chars.by_ref() {
+                    if q == c {
+                        break;
+                    }
+                }
+            }
+            '-' if chars.peek() == Some(&'-') => {
+                for q in chars.by_ref() {
+                    if q == '\n' {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = ' ';
+                for q in chars.by_ref() {
+                    if prev == '*' && q == '/' {
+                        break;
+                    }
+                    prev = q;
+                }
+            }
+            _ => {}
         }
-        "tsv" => {
-            let str_path = path.to_string_lossy().to_string();
-            Ok(format!("read_csv('{}')", str_path))
+    }
+    false
+}
+
+/// Evaluate data validation assertions against a file on disk and return the ones that failed.
+/// Each assertion is a DuckDB boolean expression over aggregates of the rows, where `count` is
+/// the number of rows, `null_count(col)` the number of nulls in a column and `null_rate(col)`
+/// the fraction of nulls in a column. Ie: `count > 0` or `null_rate(email) < 0.01`
+pub fn failed_assertions(path: &Path, assertions: &[String]) -> Result<Vec<String>, OxenError> {
+    let conn = duckdb::Connection::open_in_memory()?;
+    index_file(path, &conn)?;
+    conn.execute_batch(
+        "CREATE TEMP MACRO null_count(col) AS count(*) - count(col);
+         CREATE TEMP MACRO null_rate(col) AS (count(*) - count(col))::DOUBLE / greatest(count(*), 1);",
+    )?;
+
+    let mut failed = vec![];
+    for assertion in assertions {
+        if has_statement_separator(assertion) {
+            return Err(OxenError::basic_str(format!(
+                "Invalid assertion {assertion:?}, must be a single expression"
+            )));
         }
-        "parquet" => {
-            let str_path = path.to_string_lossy().to_string();
-            Ok(format!("read_parquet('{}')", str_path))
+        let sql = format!(
+            "SELECT CAST(({}) AS BOOLEAN) FROM {}",
+            expand_assertion(assertion),
+            DUCKDB_DF_TABLE_NAME
+        );
+        log::debug!("failed_assertions running sql: {}", sql);
+        let passed: Option<bool> = conn.query_row(&sql, [], |row| row.get(0)).map_err(|err| {
+            OxenError::basic_str(format!("Could not evaluate assertion {assertion:?}: {err}"))
+        })?;
+        if passed != Some(true) {
+            failed.push(assertion.to_owned());
         }
-        "jsonl" | "json" | "ndjson" => {
-            let str_path = path.to_string_lossy().to_string();
-            Ok(format!("read_json('{}')", str_path))
+    }
+    Ok(failed)
+}
+
+// A bare `count` is the number of rows, everything else is passed through to DuckDB as is
+fn expand_assertion(assertion: &str) -> String {
+    let mut expanded = String::new();
+    let mut chars = assertion.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\'' || c == '"' {
+            // Copy quoted strings and identifiers through untouched
+            expanded.push(c);
+            for q in chars.by_ref() {
+                expanded.push(q);
+                if q == c {
+                    break;
+                }
+            }
+        } else if c.is_alphabetic() || c == '_' {
+            let mut word = String::from(c);
+            while let Some(&next) = chars.peek() {
+                if next.is_alphanumeric() || next == '_' {
+                    word.push(next);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            let rest: String = chars.clone().collect();
+            if word.eq_ignore_ascii_case("count") && !rest.trim_start().starts_with('(') {
+                expanded.push_str("count(*)");
+            } else {
+                expanded.push_str(&word);
+            }
+        } else {
+            expanded.push(c);
         }
-        _ => Err(OxenError::basic_str(
-            "Invalid file type: expected .csv, .tsv, .parquet, .jsonl, .json, .ndjson",
-        )),
+    }
+    expanded
+}
+
+/// Find the rows of a file on disk where `column` contains `query`, paginated by `opts.page`
+/// and `opts.page_size`. Returns the page of matches and the total number of matches.
+/// If `regex` is true the query is matched as a regular expression instead of a substring.
+pub fn search_file(
+    conn: &duckdb::Connection,
+    path: &Path,
+    column: &str,
+    query: &str,
+    regex: bool,
+    opts: &DFOpts,
+) -> Result<(DataFrame, usize), OxenError> {
+    let from = from_clause_from_disk_path(path)?;
+    let columns = describe_columns(conn, &from)?;
+    if !columns.contains_key(column) {
+        return Err(OxenError::basic_str(format!(
+            "Column '{column}' does not exist in {path:?}"
+        )));
+    }
+
+    let where_clause = contains_where_clause(column, query, regex);
+    let total = count_where(conn, &from, &where_clause)?;
+
+    let sql = format!("SELECT * FROM {from} WHERE {where_clause}");
+    let df = select_str(conn, sql, false, None, Some(opts))?;
+    Ok((df, total))
+}
+
+/// `WHERE` clause for rows where the column contains the query. For substring matches `%` and `_`
+/// in the query are escaped so they match literally instead of acting as wildcards.
+pub fn contains_where_clause(column: &str, query: &str, regex: bool) -> String {
+    let column = format!("CAST(\"{}\" AS VARCHAR)", column.replace('"', "\"\""));
+    if regex {
+        let pattern = query.replace('\'', "''");
+        format!("regexp_matches({column}, '{pattern}')")
+    } else {
+        let pattern = query
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_")
+            .replace('\'', "''");
+        format!("{column} LIKE '%{pattern}%' ESCAPE '\\'")
     }
 }
 
@@ -602,6 +916,83 @@ mod tests {
     }
      */
 
+    #[test]
+    fn test_df_db_contains_where_clause_escapes_wildcards() {
+        assert_eq!(
+            contains_where_clause("label", "50%_off", false),
+            r#"CAST("label" AS VARCHAR) LIKE '%50\%\_off%' ESCAPE '\'"#
+        );
+        assert_eq!(
+            contains_where_clause("my \"col\"", "it's", false),
+            r#"CAST("my ""col""" AS VARCHAR) LIKE '%it''s%' ESCAPE '\'"#
+        );
+        assert_eq!(
+            contains_where_clause("label", "^d.g$", true),
+            r#"regexp_matches(CAST("label" AS VARCHAR), '^d.g$')"#
+        );
+    }
+
+    #[test]
+    fn test_df_db_is_select_query_ignores_quoted_semicolons() {
+        assert!(is_select_query("SELECT * FROM df WHERE note = 'a;b'"));
+        assert!(is_select_query("SELECT \"a;b\" FROM df"));
+        assert!(is_select_query("SELECT 'it''s;' FROM df -- done; really"));
+        assert!(is_select_query("SELECT /* x; y */ 1;"));
+        assert!(!is_select_query("SELECT 1; DROP TABLE df"));
+        assert!(!is_select_query("SELECT 'a;b'; DELETE FROM df"));
+        assert!(!is_select_query("DELETE FROM df"));
+    }
+
+    #[test]
+    fn test_df_db_failed_assertions() -> Result<(), OxenError> {
+        test::run_empty_dir_test(|data_dir| {
+            let csv = data_dir.join("users.csv");
+            std::fs::write(
+                &csv,
+                "name,email\nada,ada@oxen.ai\nbob,\ncat,cat@oxen.ai\ndan,dan@oxen.ai\n",
+            )?;
+
+            let assertions = vec![
+                String::from("count > 0"),
+                String::from("count = 4"),
+                String::from("null_count(email) = 1"),
+                String::from("null_rate(email) < 0.01"),
+                String::from("count(DISTINCT name) = count"),
+            ];
+            let failed = failed_assertions(&csv, &assertions)?;
+            assert_eq!(failed, vec![String::from("null_rate(email) < 0.01")]);
+
+            // Invalid expressions are errors rather than failures
+            let assertions = vec![String::from("null_rate(missing) < 0.5")];
+            assert!(failed_assertions(&csv, &assertions).is_err());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_df_db_select_str_sorts_multiple_columns() -> Result<(), OxenError> {
+        test::run_empty_dir_test(|data_dir| {
+            let csv = data_dir.join("data.csv");
+            std::fs::write(
+                &csv,
+                "image,label,score\n0001.jpg,dog,0.5\n0002.jpg,cat,0.7\n0003.jpg,dog,0.9\n0004.jpg,cat,0.6\n",
+            )?;
+            let conn = get_connection(data_dir.join("data.db"))?;
+
+            let mut opts = DFOpts::empty();
+            opts.sort_by = Some(String::from("label:asc,score:desc"));
+            let sql = format!("SELECT * FROM read_csv_auto('{}')", csv.to_string_lossy());
+            let df = select_str(&conn, sql, false, None, Some(&opts))?;
+
+            let images = df.column("image")?.str()?;
+            let images: Vec<&str> = images.into_iter().flatten().collect();
+            assert_eq!(images, vec!["0002.jpg", "0004.jpg", "0003.jpg", "0001.jpg"]);
+
+            Ok(())
+        })
+    }
+
     #[test]
     fn test_df_db_create() -> Result<(), OxenError> {
         test::run_empty_dir_test(|data_dir| {
@@ -634,4 +1025,73 @@ mod tests {
             Ok(())
         })
     }
+
+    #[test]
+    fn test_df_db_query_file_with_table_placeholder() -> Result<(), OxenError> {
+        test::run_empty_dir_test(|data_dir| {
+            let csv = data_dir.join("data.csv");
+            std::fs::write(
+                &csv,
+                "image,label,score\n0001.jpg,cat,0.5\n0002.jpg,dog,0.9\n0003.jpg,cat,0.7\n",
+            )?;
+            let query = data_dir.join("query.sql");
+            std::fs::write(
+                &query,
+                "SELECT label, count(*) AS num FROM ${table}\nWHERE score > 0.6\nGROUP BY label ORDER BY label;\n",
+            )?;
+
+            let sql = std::fs::read_to_string(&query)?;
+            let df = query_file(&csv, &sql)?;
+            assert_eq!(df.height(), 2);
+            let labels = df.column("label")?.str()?;
+            assert_eq!(labels.get(0), Some("cat"));
+            assert_eq!(labels.get(1), Some("dog"));
+            let nums = df.column("num")?.cast(&polars::prelude::DataType::Int64)?;
+            assert_eq!(nums.i64()?.get(0), Some(1));
+            assert_eq!(nums.i64()?.get(1), Some(1));
+
+            // Only reads are allowed
+            assert!(query_file(&csv, "DELETE FROM ${table}").is_err());
+            assert!(query_file(&csv, "SELECT * FROM ${table}; DROP TABLE df").is_err());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_df_db_index_compressed_files() -> Result<(), OxenError> {
+        test::run_empty_dir_test(|data_dir| {
+            use std::io::Write;
+
+            // gzip csv is read natively by DuckDB
+            let csv_gz = data_dir.join("data.csv.gz");
+            let mut encoder = flate2::write::GzEncoder::new(
+                std::fs::File::create(&csv_gz)?,
+                flate2::Compression::default(),
+            );
+            encoder.write_all(b"image,label\n0001.jpg,cat\n0002.jpg,dog\n0003.jpg,cat\n")?;
+            encoder.finish()?;
+
+            let conn = duckdb::Connection::open_in_memory()?;
+            index_file(&csv_gz, &conn)?;
+            assert_eq!(count(&conn, DUCKDB_DF_TABLE_NAME)?, 3);
+
+            // zstd parquet is decompressed first
+            let parquet = data_dir.join("data.parquet");
+            let mut df = polars::df!("image" => &["0001.jpg", "0002.jpg"]).unwrap();
+            crate::core::df::tabular::write_df_parquet(&mut df, &parquet)?;
+            let parquet_zst = data_dir.join("data.parquet.zst");
+            zstd::stream::copy_encode(
+                std::fs::File::open(&parquet)?,
+                std::fs::File::create(&parquet_zst)?,
+                0,
+            )?;
+
+            let conn = duckdb::Connection::open_in_memory()?;
+            index_file(&parquet_zst, &conn)?;
+            assert_eq!(count(&conn, DUCKDB_DF_TABLE_NAME)?, 2);
+
+            Ok(())
+        })
+    }
 }
@@ -1,10 +1,10 @@
 use crate::constants::{
     OBJECTS_DIR, OBJECT_DIRS_DIR, OBJECT_FILES_DIR, OBJECT_SCHEMAS_DIR, OBJECT_VNODES_DIR,
-    OXEN_HIDDEN_DIR, SCHEMAS_TREE_PREFIX,
+    SCHEMAS_TREE_PREFIX,
 };
 use crate::error::OxenError;
 use crate::model::{LocalRepository, StagedDirStats, StagedEntryStatus, StagedSchema};
-use crate::{core::db, model::CommitEntry};
+use crate::{core::db, model::CommitEntry, util};
 use core::panic;
 
 use os_path::OsPath;
@@ -272,7 +272,7 @@ impl TreeObject {
     }
 
     pub fn object_path(&self, repo: &LocalRepository) -> PathBuf {
-        let objects_dir = repo.path.join(OXEN_HIDDEN_DIR).join(OBJECTS_DIR);
+        let objects_dir = util::fs::oxen_hidden_dir(&repo.path).join(OBJECTS_DIR);
         let top_hash = &self.hash()[..2];
         let bottom_hash = &self.hash()[2..];
         let base_path = match self {
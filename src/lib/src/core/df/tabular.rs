@@ -1,13 +1,15 @@
 use duckdb::ToSql;
+use polars::chunked_array::ops::SortMultipleOptions;
 use polars::prelude::*;
 use std::fs::File;
 
 use crate::constants;
+use crate::core::db::df_db;
 use crate::core::df::pretty_print;
 use crate::error::OxenError;
 use crate::model::schema::DataType;
-use crate::model::{ContentType, DataFrameSize};
-use crate::opts::{CountLinesOpts, DFOpts, PaginateOpts};
+use crate::model::{ColumnProfile, ContentType, DataFrameProfile, DataFrameSize};
+use crate::opts::{CountLinesOpts, DFOpts, PaginateOpts, PivotOpts, WindowAgg};
 use crate::util::{fs, hasher};
 
 use colored::Colorize;
@@ -17,7 +19,7 @@ use rand::prelude::SliceRandom;
 use rand::thread_rng;
 use std::ffi::OsStr;
 use std::io::Cursor;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 const DEFAULT_INFER_SCHEMA_LEN: usize = 10000;
 const DEFAULT_SAMPLE_SIZE: usize = 1024;
@@ -66,6 +68,25 @@ pub fn read_df_csv(path: impl AsRef<Path>, delimiter: u8) -> Result<DataFrame, O
     }
 }
 
+/// Read a csv without inferring dtypes, every column comes back as a string
+pub fn read_df_csv_untyped(path: impl AsRef<Path>, delimiter: u8) -> Result<DataFrame, OxenError> {
+    let path = path.as_ref();
+    log::debug!("read_df_csv_untyped path: {:?}", path);
+    let reader = CsvReader::from_path(path)
+        .map_err(|err| OxenError::basic_str(format!("{CSV_READ_ERROR}: {err:?}")))?;
+    reader
+        .infer_schema(Some(0))
+        .has_header(true)
+        .truncate_ragged_lines(true)
+        .with_separator(delimiter)
+        .with_end_of_line_char(b'\n')
+        .with_quote_char(Some(b'"'))
+        .with_rechunk(true)
+        .with_encoding(CsvEncoding::LossyUtf8)
+        .finish()
+        .map_err(|err| OxenError::basic_str(format!("{CSV_READ_ERROR}: {err:?}")))
+}
+
 pub fn scan_df_csv<P: AsRef<Path>>(
     path: P,
     delimiter: u8,
@@ -294,7 +315,7 @@ pub fn transform(df: DataFrame, opts: DFOpts) -> Result<DataFrame, OxenError> {
 
 pub fn transform_lazy(
     mut df: LazyFrame,
-    height: usize,
+    mut height: usize,
     opts: DFOpts,
 ) -> Result<DataFrame, OxenError> {
     log::debug!("transform_lazy Got transform ops {:?}", opts);
@@ -312,6 +333,12 @@ pub fn transform_lazy(
         }
     }
 
+    if let (Some(path), Some((on, threshold))) = (&opts.fuzzy_join, opts.fuzzy_join_on()?) {
+        log::debug!("transform_lazy Got file to fuzzy join {:?}", path);
+        let right = read_df(path, DFOpts::empty())?;
+        df = df_fuzzy_join(df.collect()?, right, &on, threshold)?.lazy();
+    }
+
     if let Some(data) = &opts.add_row {
         df = add_row(df, data.to_owned())?;
     }
@@ -320,21 +347,68 @@ pub fn transform_lazy(
         df = add_col_lazy(df, &col_vals.name, &col_vals.value, &col_vals.dtype)?;
     }
 
+    if let Some((column, values_path)) = opts.where_in_column()? {
+        let filtered = df_where_in(df.collect()?, &column, values_path)?;
+        height = filtered.height();
+        df = filtered.lazy();
+    }
+
     if let Some(columns) = opts.unique_columns() {
         df = unique_df(df, columns)?;
     }
 
+    if let Some(columns) = opts.dedup_columns() {
+        let deduped = df_dedup(df.collect()?, &columns)?;
+        height = deduped.height();
+        df = deduped.lazy();
+    }
+
+    if let Some(columns) = opts.detect_duplicates_columns() {
+        let duplicates = df_detect_duplicates(df.collect()?, &columns)?;
+        height = duplicates.height();
+        df = duplicates.lazy();
+    }
+
+    if let Some(columns) = opts.drop_nulls_columns() {
+        let dropped = df_drop_nulls(df.collect()?, &columns)?;
+        height = dropped.height();
+        df = dropped.lazy();
+    }
+
+    if let Some(pivot) = &opts.pivot {
+        let pivoted = df_pivot(df.collect()?, pivot)?;
+        height = pivoted.height();
+        df = pivoted.lazy();
+    }
+
+    if let Some(columns) = opts.group_by_columns() {
+        let aggs = opts.aggregations()?.unwrap_or_default();
+        let grouped = df_group_by(df.collect()?, &columns, &aggs)?;
+        height = grouped.height();
+        df = grouped.lazy();
+    }
+
+    if let Some(windows) = opts.windows()? {
+        let windowed = df_window(df.collect()?, &windows, opts.order_by.as_deref())?;
+        df = windowed.lazy();
+    }
+
     if opts.should_randomize {
         let mut rand_indices: Vec<u32> = (0..height as u32).collect();
         rand_indices.shuffle(&mut thread_rng());
         df = take(df, rand_indices)?.lazy();
     }
 
-    if let Some(sort_by) = &opts.sort_by {
-        df = df.sort([sort_by], Default::default());
-    }
-
-    if opts.should_reverse {
+    // --reverse is folded into the sort direction when sorting
+    if let Some(sort_by) = opts.sort_by_columns()? {
+        if !sort_by.is_empty() {
+            let (columns, descending): (Vec<String>, Vec<bool>) = sort_by.into_iter().unzip();
+            df = df.sort(
+                columns,
+                SortMultipleOptions::new().with_order_descending_multi(descending),
+            );
+        }
+    } else if opts.should_reverse {
         df = df.reverse();
     }
 
@@ -345,6 +419,34 @@ pub fn transform_lazy(
         }
     }
 
+    if let Some(renames) = opts.rename_columns()? {
+        df = df_rename(df.collect()?, &renames)?.lazy();
+    }
+
+    if let Some(casts) = opts.cast_columns()? {
+        df = df_cast(df.collect()?, &casts)?.lazy();
+    }
+
+    if let Some(scalings) = opts.normalize_columns()? {
+        df = df_normalize(df.collect()?, &scalings, opts.normalize_append)?.lazy();
+    }
+
+    if let Some(columns) = opts.coalesce_columns() {
+        let name = opts
+            .coalesce_as
+            .as_deref()
+            .unwrap_or(constants::COALESCE_COL_NAME);
+        df = df_coalesce(df.collect()?, &columns, name)?.lazy();
+    }
+
+    if let Some(columns) = opts.hash_rows_columns() {
+        df = df_hash_rows_stable(df.collect()?, &columns)?.lazy();
+    }
+
+    if let Some(fractions) = opts.seed_split_fractions()? {
+        df = df_seed_split(df.collect()?, &fractions, opts.seed.unwrap_or(0))?.lazy();
+    }
+
     // These ops should be the last ops since they depends on order
     if let Some(indices) = opts.take_indices() {
         match take(df.clone(), indices) {
@@ -391,12 +493,19 @@ pub fn transform_slice_lazy(
     }
 
     log::debug!("transform_slice_lazy before collect");
-    match df.collect() {
+    let df = match df.collect() {
         Ok(df) => {
             log::debug!("transform_lazy collected {:?}", df);
-            Ok(df)
+            df
         }
-        Err(err) => Err(OxenError::basic_str(format!("DataFrame Error: {}", err))),
+        Err(err) => return Err(OxenError::basic_str(format!("DataFrame Error: {}", err))),
+    };
+
+    // Transpose last so that it applies to the sliced down frame
+    if opts.transpose {
+        df_transpose(df)
+    } else {
+        Ok(df)
     }
 }
 
@@ -619,390 +728,1520 @@ pub fn df_hash_rows_on_cols(
     Ok(df)
 }
 
-fn sniff_db_csv_delimiter(path: impl AsRef<Path>, opts: &DFOpts) -> Result<u8, OxenError> {
-    if let Some(delimiter) = &opts.delimiter {
-        if delimiter.len() != 1 {
-            return Err(OxenError::basic_str("Delimiter must be a single character"));
+/// Append a `_row_hash` column that is stable across runs and column orderings.
+/// Hashes the given columns in the order they are passed in, or all the columns sorted by name if empty.
+pub fn df_hash_rows_stable(df: DataFrame, columns: &[String]) -> Result<DataFrame, OxenError> {
+    let schema = df.schema();
+    let columns: Vec<String> = if columns.is_empty() {
+        let mut names: Vec<String> = schema
+            .iter_names()
+            .map(|name| name.to_string())
+            .filter(|name| name != constants::ROW_HASH_COL_NAME)
+            .collect();
+        names.sort();
+        names
+    } else {
+        for column in columns.iter() {
+            if schema.get(column).is_none() {
+                return Err(OxenError::basic_str(format!(
+                    "Could not hash rows, column not found: {column}"
+                )));
+            }
         }
-        return Ok(delimiter.as_bytes()[0]);
-    }
+        columns.to_vec()
+    };
 
-    match qsv_sniffer::Sniffer::new().sniff_path(&path) {
-        Ok(metadata) => Ok(metadata.dialect.delimiter),
-        Err(err) => {
-            let err = format!("Error sniffing csv {:?} -> {:?}", path.as_ref(), err);
-            log::warn!("{}", err);
-            Ok(b',')
-        }
+    if columns.is_empty() {
+        return Err(OxenError::basic_str(
+            "Could not hash rows, no columns to hash",
+        ));
     }
+
+    let col_names = columns.iter().map(|c| col(c)).collect::<Vec<Expr>>();
+    let df = df
+        .lazy()
+        .with_column(
+            as_struct(col_names)
+                .apply(
+                    move |s| {
+                        let ca = s.struct_()?;
+                        let out: StringChunked = ca
+                            .into_iter()
+                            .map(|row| {
+                                let mut buffer: Vec<u8> = vec![];
+                                for elem in row.iter() {
+                                    buffer.extend(any_val_to_bytes(elem));
+                                    // Separate the values so ("ab", "c") and ("a", "bc") hash differently
+                                    buffer.push(0x1f);
+                                }
+                                Some(hasher::hash_buffer(&buffer))
+                            })
+                            .collect();
+
+                        Ok(Some(out.into_series()))
+                    },
+                    GetOutput::from_type(polars::prelude::DataType::String),
+                )
+                .alias(constants::ROW_HASH_COL_NAME),
+        )
+        .collect()?;
+    Ok(df)
 }
 
-pub fn read_df(path: impl AsRef<Path>, opts: DFOpts) -> Result<DataFrame, OxenError> {
-    let path = path.as_ref();
-    if !path.exists() {
-        return Err(OxenError::entry_does_not_exist(path));
+/// Append a `split` column assigning each row to one of the named splits. Rows are hashed with
+/// the seed over all their columns sorted by name, so the assignment only depends on the row
+/// contents and the seed, not on the order of the rows or the machine it runs on.
+pub fn df_seed_split(
+    df: DataFrame,
+    fractions: &[(String, f64)],
+    seed: u64,
+) -> Result<DataFrame, OxenError> {
+    let schema = df.schema();
+    if schema.get(constants::SPLIT_COL_NAME).is_some() {
+        return Err(OxenError::basic_str(format!(
+            "Could not split rows, column '{}' already exists",
+            constants::SPLIT_COL_NAME
+        )));
     }
 
-    let extension = path.extension().and_then(OsStr::to_str);
-    let err = format!("Unknown file type read_df {path:?} -> {extension:?}");
-
-    let df = match extension {
-        Some(extension) => match extension {
-            "ndjson" => read_df_jsonl(path),
-            "jsonl" => read_df_jsonl(path),
-            "json" => read_df_json(path),
-            "csv" | "data" => {
-                let delimiter = sniff_db_csv_delimiter(path, &opts)?;
-                read_df_csv(path, delimiter)
-            }
-            "tsv" => read_df_csv(path, b'\t'),
-            "parquet" => read_df_parquet(path),
-            "arrow" => read_df_arrow(path),
-            _ => Err(OxenError::basic_str(err)),
-        },
-        None => Err(OxenError::basic_str(err)),
-    }?;
+    let mut columns: Vec<String> = schema
+        .iter_names()
+        .map(|name| name.to_string())
+        .filter(|name| name != constants::ROW_HASH_COL_NAME)
+        .collect();
+    columns.sort();
+    if columns.is_empty() {
+        return Err(OxenError::basic_str(
+            "Could not split rows, data frame has no columns",
+        ));
+    }
 
-    if opts.has_transform() {
-        let df = transform(df, opts)?;
-        Ok(df)
-    } else {
-        Ok(df)
+    // Upper bound of each split in [0, 1), the last split takes whatever rounding leaves over
+    let mut bounds: Vec<(String, f64)> = vec![];
+    let mut total = 0.0;
+    for (name, fraction) in fractions.iter() {
+        total += fraction;
+        bounds.push((name.to_owned(), total));
     }
+    let last = bounds
+        .last()
+        .map(|(name, _)| name.to_owned())
+        .unwrap_or_default();
+
+    let col_names = columns.iter().map(|c| col(c)).collect::<Vec<Expr>>();
+    let df = df
+        .lazy()
+        .with_column(
+            as_struct(col_names)
+                .apply(
+                    move |s| {
+                        let ca = s.struct_()?;
+                        let out: StringChunked = ca
+                            .into_iter()
+                            .map(|row| {
+                                let mut buffer: Vec<u8> = vec![];
+                                for elem in row.iter() {
+                                    buffer.extend(any_val_to_bytes(elem));
+                                    buffer.push(0x1f);
+                                }
+                                let hash = hasher::hash_buffer_with_seed(&buffer, seed);
+                                // Top 53 bits fill the mantissa of a float in [0, 1)
+                                let position = (hash >> 11) as f64 / (1u64 << 53) as f64;
+                                let split = bounds
+                                    .iter()
+                                    .find(|(_, bound)| position < *bound)
+                                    .map(|(name, _)| name.as_str())
+                                    .unwrap_or(last.as_str());
+                                Some(split.to_string())
+                            })
+                            .collect();
+
+                        Ok(Some(out.into_series()))
+                    },
+                    GetOutput::from_type(polars::prelude::DataType::String),
+                )
+                .alias(constants::SPLIT_COL_NAME),
+        )
+        .collect()?;
+    Ok(df)
 }
 
-pub fn scan_df(
-    path: impl AsRef<Path>,
-    opts: &DFOpts,
-    total_rows: usize,
-) -> Result<LazyFrame, OxenError> {
-    let input_path = path.as_ref();
-    let extension = input_path.extension().and_then(OsStr::to_str);
-    let err = format!("Unknown file type scan_df {input_path:?} {extension:?}");
+/// Reshape a long DataFrame to wide with DuckDB's PIVOT.
+/// One row per distinct `index` value, one column per distinct `columns` value,
+/// filled in with `agg(values)`.
+pub fn df_pivot(mut df: DataFrame, opts: &PivotOpts) -> Result<DataFrame, OxenError> {
+    let schema = df.schema();
+    for column in [&opts.index, &opts.columns, &opts.values] {
+        if schema.get(column).is_none() {
+            return Err(OxenError::basic_str(format!(
+                "Could not pivot, column not found: {column}"
+            )));
+        }
+    }
 
-    match extension {
-        Some(extension) => match extension {
-            "ndjson" => scan_df_jsonl(path, total_rows),
-            "jsonl" => scan_df_jsonl(path, total_rows),
-            "json" => scan_df_json(path),
-            "csv" | "data" => {
-                let delimiter = sniff_db_csv_delimiter(&path, opts)?;
-                scan_df_csv(path, delimiter, total_rows)
-            }
-            "tsv" => scan_df_csv(path, b'\t', total_rows),
-            "parquet" => scan_df_parquet(path, total_rows),
-            "arrow" => scan_df_arrow(path),
-            _ => Err(OxenError::basic_str(err)),
-        },
-        None => Err(OxenError::basic_str(err)),
+    // The aggregation gets interpolated into the query, so only allow plain function names
+    if opts.agg.is_empty()
+        || !opts
+            .agg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+    {
+        return Err(OxenError::basic_str(format!(
+            "Could not pivot, invalid aggregation: {}",
+            opts.agg
+        )));
     }
+
+    let quote = |name: &str| format!("\"{}\"", name.replace('"', "\"\""));
+    let sql = format!(
+        "PIVOT {{table}} ON {} USING {}({}) GROUP BY {index} ORDER BY {index}",
+        quote(&opts.columns),
+        opts.agg,
+        quote(&opts.values),
+        index = quote(&opts.index)
+    );
+    query_df_with_duckdb(&mut df, "pivot", &sql)
 }
 
-pub fn get_size(path: impl AsRef<Path>) -> Result<DataFrameSize, OxenError> {
-    // Don't need that many rows to get the width
-    let num_scan_rows = constants::DEFAULT_PAGE_SIZE;
-    let lazy_df = scan_df(&path, &DFOpts::empty(), num_scan_rows)?;
-    let schema = lazy_df.schema()?;
-    let width = schema.len();
+/// Group the rows by `columns` and compute each (function, column) aggregation with DuckDB.
+/// Output columns are named `{function}_{column}`, ie: `sum_amount`, and rows are ordered by the groups.
+pub fn df_group_by(
+    mut df: DataFrame,
+    columns: &[String],
+    aggs: &[(String, String)],
+) -> Result<DataFrame, OxenError> {
+    let schema = df.schema();
+    for column in columns.iter().chain(aggs.iter().map(|(_, column)| column)) {
+        if schema.get(column).is_none() {
+            return Err(OxenError::basic_str(format!(
+                "Could not group by, column not found: {column}"
+            )));
+        }
+    }
 
-    let input_path = path.as_ref();
-    let extension = input_path.extension().and_then(OsStr::to_str);
-    let err = format!("Unknown file type get_size {input_path:?} {extension:?}");
+    let sql = group_by_sql(columns, aggs, "{table}")?;
+    query_df_with_duckdb(&mut df, "group_by", &sql)
+}
 
-    match extension {
-        Some(extension) => match extension {
-            "csv" | "tsv" => {
-                let mut opts = CountLinesOpts::empty();
-                opts.remove_trailing_blank_line = true;
+/// Union every tabular file under `dir` and group the combined rows by `columns` in a single
+/// DuckDB query, so the files are scanned by DuckDB instead of being loaded one by one.
+/// Files with different columns are unioned by name, missing columns are null.
+pub fn df_group_by_over_dir(
+    dir: impl AsRef<Path>,
+    columns: &[String],
+    aggs: &[(String, String)],
+) -> Result<DataFrame, OxenError> {
+    let dir = dir.as_ref();
+    if !dir.is_dir() {
+        return Err(OxenError::basic_str(format!(
+            "Could not aggregate over dirs, {dir:?} is not a directory"
+        )));
+    }
 
-                // Remove one line to account for CSV/TSV headers
-                let (mut height, _) = fs::count_lines(path, opts)?;
-                height -= 1; // Adjusting for header
+    let mut paths: Vec<PathBuf> = fs::rlist_files_in_dir(dir)
+        .into_iter()
+        .filter(|path| !fs::is_in_oxen_hidden_dir(path) && fs::is_tabular(path))
+        .collect();
+    paths.sort();
+    if paths.is_empty() {
+        return Err(OxenError::basic_str(format!(
+            "Could not aggregate over dirs, no tabular files found in {dir:?}"
+        )));
+    }
 
-                Ok(DataFrameSize { width, height })
-            }
-            "data" | "jsonl" | "ndjson" => {
-                let mut opts = CountLinesOpts::empty();
-                opts.remove_trailing_blank_line = true;
+    let mut selects: Vec<String> = vec![];
+    for path in paths.iter() {
+        let from = df_db::from_clause_from_disk_path(path).map_err(|err| {
+            OxenError::basic_str(format!("Could not aggregate over {path:?}: {err}"))
+        })?;
+        selects.push(format!("SELECT * FROM {from}"));
+    }
+    let from = format!("({})", selects.join(" UNION ALL BY NAME "));
+    let sql = group_by_sql(columns, aggs, &from)?;
+    log::debug!("df_group_by_over_dir sql: {}", sql);
 
-                let (height, _) = fs::count_lines(path, opts)?;
+    let conn = duckdb::Connection::open_in_memory()?;
+    df_db::select_raw(&conn, &sql, false, None)
+}
 
-                Ok(DataFrameSize { width, height })
-            }
-            "parquet" => {
-                let file = File::open(input_path)?;
-                let mut reader = ParquetReader::new(file);
-                let height = reader.num_rows()?;
-                Ok(DataFrameSize { width, height })
-            }
-            "arrow" => {
-                let file = File::open(input_path)?;
-                // arrow is fast to .finish() so we can just do it here
-                let reader = IpcReader::new(file);
-                let height = reader.finish().unwrap().height();
-                Ok(DataFrameSize { width, height })
-            }
-            "json" => {
-                let df = lazy_df
-                    .collect()
-                    .map_err(|_| OxenError::basic_str("Could not collect json df"))?;
-                let height = df.height();
-                Ok(DataFrameSize { width, height })
-            }
-            _ => Err(OxenError::basic_str(err)),
-        },
-        None => Err(OxenError::basic_str(err)),
+// GROUP BY query over `from`, shared by the single file and directory group bys
+fn group_by_sql(
+    columns: &[String],
+    aggs: &[(String, String)],
+    from: &str,
+) -> Result<String, OxenError> {
+    if columns.is_empty() {
+        return Err(OxenError::basic_str(
+            "Could not group by, must supply at least one column",
+        ));
+    }
+    if aggs.is_empty() {
+        return Err(OxenError::basic_str(
+            "Could not group by, must supply aggregations with --agg 'fn:col'",
+        ));
+    }
+
+    let quote = |name: &str| format!("\"{}\"", name.replace('"', "\"\""));
+    let mut projection: Vec<String> = columns.iter().map(|c| quote(c)).collect();
+    for (func, column) in aggs {
+        let Some(sql_func) = sql_aggregate(func) else {
+            return Err(OxenError::basic_str(format!(
+                "Could not group by, invalid aggregation: {func}"
+            )));
+        };
+        projection.push(format!(
+            "{sql_func}({}) AS {}",
+            quote(column),
+            quote(&format!("{func}_{column}"))
+        ));
     }
+
+    let group_by = columns
+        .iter()
+        .map(|c| quote(c))
+        .collect::<Vec<String>>()
+        .join(", ");
+    Ok(format!(
+        "SELECT {} FROM {from} GROUP BY {group_by} ORDER BY {group_by}",
+        projection.join(", ")
+    ))
 }
 
-pub fn write_df_json<P: AsRef<Path>>(df: &mut DataFrame, output: P) -> Result<(), OxenError> {
-    let output = output.as_ref();
-    let error_str = format!("Could not save tabular data to path: {output:?}");
-    log::debug!("Writing file {:?}", output);
-    log::debug!("{:?}", df);
-    let f = std::fs::File::create(output).unwrap();
-    JsonWriter::new(f)
-        .with_json_format(JsonFormat::Json)
-        .finish(df)
-        .expect(&error_str);
-    Ok(())
+// DuckDB aggregate function for the name used in --agg and --window
+fn sql_aggregate(func: &str) -> Option<&'static str> {
+    match func.to_lowercase().as_str() {
+        "sum" => Some("sum"),
+        "mean" | "avg" => Some("avg"),
+        "min" => Some("min"),
+        "max" => Some("max"),
+        "count" => Some("count"),
+        "median" => Some("median"),
+        "std" => Some("stddev"),
+        "first" => Some("first"),
+        "last" => Some("last"),
+        _ => None,
+    }
 }
 
-pub fn write_df_jsonl<P: AsRef<Path>>(df: &mut DataFrame, output: P) -> Result<(), OxenError> {
-    let output = output.as_ref();
-    let error_str = format!("Could not save tabular data to path: {output:?}");
-    log::debug!("Writing file {:?}", output);
-    let f = std::fs::File::create(output).unwrap();
-    JsonWriter::new(f)
-        .with_json_format(JsonFormat::JsonLines)
-        .finish(df)
-        .expect(&error_str);
-    Ok(())
+/// Append a rolling aggregation column for each window, computed with DuckDB window functions
+/// over the current row and the `size - 1` rows before it. Rows are ordered by `order_by`, or
+/// kept in their original order if it is None. Output columns are named `{function}_{column}_{size}`.
+pub fn df_window(
+    df: DataFrame,
+    windows: &[WindowAgg],
+    order_by: Option<&str>,
+) -> Result<DataFrame, OxenError> {
+    let schema = df.schema();
+    let columns = windows
+        .iter()
+        .map(|window| window.column.as_str())
+        .chain(order_by);
+    for column in columns {
+        if schema.get(column).is_none() {
+            return Err(OxenError::basic_str(format!(
+                "Could not compute window, column not found: {column}"
+            )));
+        }
+    }
+
+    let quote = |name: &str| format!("\"{}\"", name.replace('"', "\"\""));
+    // Break ties in the order column by the original row order
+    let row_idx = "_oxen_window_row_idx";
+    let order = match order_by {
+        Some(column) => format!("{}, \"{row_idx}\"", quote(column)),
+        None => format!("\"{row_idx}\""),
+    };
+
+    let mut projection: Vec<String> = vec![];
+    for window in windows {
+        let Some(sql_func) = sql_aggregate(&window.func) else {
+            return Err(OxenError::basic_str(format!(
+                "Could not compute window, invalid aggregation: {}",
+                window.func
+            )));
+        };
+        projection.push(format!(
+            "{sql_func}({}) OVER (ORDER BY {order} ROWS BETWEEN {} PRECEDING AND CURRENT ROW) AS {}",
+            quote(&window.column),
+            window.size - 1,
+            quote(&format!("{}_{}_{}", window.func, window.column, window.size))
+        ));
+    }
+
+    let mut df = df.with_row_index(row_idx, Some(0))?;
+    let sql = format!(
+        "SELECT * EXCLUDE (\"{row_idx}\") FROM (SELECT *, {} FROM {{table}}) ORDER BY {order}",
+        projection.join(", ")
+    );
+    query_df_with_duckdb(&mut df, "window", &sql)
 }
 
-pub fn write_df_csv<P: AsRef<Path>>(
-    df: &mut DataFrame,
-    output: P,
-    delimiter: u8,
-) -> Result<(), OxenError> {
-    let output = output.as_ref();
-    let error_str = format!("Could not save tabular data to path: {output:?}");
-    log::debug!("Writing file {:?}", output);
-    let f = std::fs::File::create(output).unwrap();
-    CsvWriter::new(f)
-        .include_header(true)
-        .with_separator(delimiter)
-        .finish(df)
-        .expect(&error_str);
-    Ok(())
+/// Swap the rows and columns of a small data frame. The original column names become the
+/// first column, named `column`, and values are cast to a common type.
+pub fn df_transpose(mut df: DataFrame) -> Result<DataFrame, OxenError> {
+    let num_cells = df.height() * df.width();
+    if num_cells > constants::MAX_TRANSPOSE_CELLS {
+        return Err(OxenError::basic_str(format!(
+            "Could not transpose, data frame has {num_cells} cells which is over the limit of {}. Try slicing it down with --head or --slice first.",
+            constants::MAX_TRANSPOSE_CELLS
+        )));
+    }
+
+    df.transpose(Some("column"), None)
+        .map_err(|err| OxenError::basic_str(format!("Could not transpose: {err}")))
 }
 
-pub fn write_df_parquet<P: AsRef<Path>>(df: &mut DataFrame, output: P) -> Result<(), OxenError> {
-    let output = output.as_ref();
-    let error_str = format!("Could not save tabular data to path: {output:?}");
-    log::debug!("Writing file {:?}", output);
-    match std::fs::File::create(output) {
-        Ok(f) => {
-            ParquetWriter::new(f).finish(df).expect(&error_str);
-            Ok(())
-        }
-        Err(err) => {
-            let error_str = format!("Could not create file {:?}", err);
-            Err(OxenError::basic_str(error_str))
+/// Drop duplicate rows, keeping the first occurrence in the original order.
+/// If `columns` is empty the rows have to match on every column.
+pub fn df_dedup(df: DataFrame, columns: &[String]) -> Result<DataFrame, OxenError> {
+    let schema = df.schema();
+    for column in columns {
+        if schema.get(column).is_none() {
+            return Err(OxenError::basic_str(format!(
+                "Could not dedup, column not found: {column}"
+            )));
         }
     }
-}
 
-pub fn write_df_arrow<P: AsRef<Path>>(df: &mut DataFrame, output: P) -> Result<(), OxenError> {
-    let output = output.as_ref();
-    let error_str = format!("Could not save tabular data to path: {output:?}");
-    log::debug!("Writing file {:?}", output);
-    let f = std::fs::File::create(output).unwrap();
-    IpcWriter::new(f).finish(df).expect(&error_str);
-    Ok(())
+    let key_columns: Vec<String> = if columns.is_empty() {
+        schema.iter_names().map(|name| name.to_string()).collect()
+    } else {
+        columns.to_vec()
+    };
+    let distinct_on = key_columns
+        .iter()
+        .map(|c| format!("\"{}\"", c.replace('"', "\"\"")))
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    // Number the rows so DISTINCT ON keeps the first one and we can restore the order after
+    let row_idx = "_oxen_dedup_row_idx";
+    let mut df = df.with_row_index(row_idx, Some(0))?;
+    let sql = format!(
+        "SELECT * EXCLUDE (\"{row_idx}\") FROM (SELECT DISTINCT ON ({distinct_on}) * FROM {{table}} ORDER BY \"{row_idx}\") ORDER BY \"{row_idx}\""
+    );
+    query_df_with_duckdb(&mut df, "dedup", &sql)
 }
 
-pub fn write_df(df: &mut DataFrame, path: impl AsRef<Path>) -> Result<(), OxenError> {
-    let path = path.as_ref();
-    let extension = path.extension().and_then(OsStr::to_str);
-    let err = format!("Unknown file type write_df {path:?} {extension:?}");
-
-    match extension {
-        Some(extension) => match extension {
-            "ndjson" => write_df_jsonl(df, path),
-            "jsonl" => write_df_jsonl(df, path),
-            "json" => write_df_json(df, path),
-            "tsv" => write_df_csv(df, path, b'\t'),
-            "csv" => write_df_csv(df, path, b','),
-            "parquet" => write_df_parquet(df, path),
-            "arrow" => write_df_arrow(df, path),
-            _ => Err(OxenError::basic_str(err)),
-        },
-        None => Err(OxenError::basic_str(err)),
+/// Keep the rows whose `column` is one of the values listed in `values_path`, in their original order.
+/// The values are one per line, or the column of the same name (else the first column) of a
+/// tabular file. Values are compared as strings and joined against a temporary table in DuckDB.
+pub fn df_where_in(
+    df: DataFrame,
+    column: &str,
+    values_path: impl AsRef<Path>,
+) -> Result<DataFrame, OxenError> {
+    if df.schema().get(column).is_none() {
+        return Err(OxenError::basic_str(format!(
+            "Could not filter --where-in, column not found: {column}"
+        )));
     }
+    let values = read_where_in_values(values_path.as_ref(), column)?;
+    log::debug!("df_where_in {} against {} values", column, values.len());
+    let values = DataFrame::new(vec![values.with_name("value")])?;
+
+    let quoted = format!("\"{}\"", column.replace('"', "\"\""));
+    let row_idx = "_oxen_where_in_row_idx";
+    let mut df = df.with_row_index(row_idx, Some(0))?;
+    let sql = format!(
+        "SELECT * EXCLUDE (\"{row_idx}\") FROM {{table}} \
+         WHERE CAST({quoted} AS VARCHAR) IN (SELECT value FROM where_in_values) ORDER BY \"{row_idx}\""
+    );
+    query_df_with_duckdb_tables(
+        &mut df,
+        "where_in",
+        &mut [("where_in_values", values)],
+        &sql,
+    )
 }
 
-pub fn copy_df(input: impl AsRef<Path>, output: impl AsRef<Path>) -> Result<DataFrame, OxenError> {
-    let mut df = read_df(input, DFOpts::empty())?;
-    write_df_arrow(&mut df, output)?;
-    Ok(df)
-}
+// Distinct string values for --where-in from a list or tabular file
+fn read_where_in_values(path: &Path, column: &str) -> Result<Series, OxenError> {
+    if !path.exists() {
+        return Err(OxenError::entry_does_not_exist(path));
+    }
 
-pub fn copy_df_add_row_num(
-    input: impl AsRef<Path>,
-    output: impl AsRef<Path>,
-) -> Result<DataFrame, OxenError> {
-    let df = read_df(input, DFOpts::empty())?;
-    let mut df = df
-        .lazy()
-        .with_row_index("_row_num", Some(0))
-        .collect()
-        .expect("Could not add row count");
-    write_df_arrow(&mut df, output)?;
-    Ok(df)
+    let values = if fs::is_tabular(path) {
+        let df = read_df(path, DFOpts::empty())?;
+        let series = match df.column(column) {
+            Ok(series) => series.clone(),
+            Err(_) => df.get_columns().first().cloned().ok_or_else(|| {
+                OxenError::basic_str(format!("Could not filter --where-in, {path:?} is empty"))
+            })?,
+        };
+        series.cast(&polars::prelude::DataType::String)?
+    } else {
+        let contents = fs::read_from_path(path)?;
+        let lines: Vec<&str> = contents
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .collect();
+        Series::new("value", lines)
+    };
+    Ok(values.drop_nulls().unique()?)
 }
 
-pub fn show_path(input: impl AsRef<Path>, opts: DFOpts) -> Result<DataFrame, OxenError> {
-    log::debug!("Got opts {:?}", opts);
-    let df = read_df(input, opts.clone())?;
-    if opts.column_at().is_some() {
-        for val in df.get(0).unwrap() {
-            match val {
-                polars::prelude::AnyValue::List(vals) => {
-                    for val in vals.iter() {
-                        println!("{val}")
-                    }
-                }
-                _ => {
-                    println!("{val}")
-                }
-            }
+/// Drop the rows that have a null in any of `columns`, or in any column if `columns` is empty.
+pub fn df_drop_nulls(df: DataFrame, columns: &[String]) -> Result<DataFrame, OxenError> {
+    let schema = df.schema();
+    for column in columns {
+        if schema.get(column).is_none() {
+            return Err(OxenError::basic_str(format!(
+                "Could not drop nulls, column not found: {column}"
+            )));
         }
+    }
+
+    if columns.is_empty() {
+        Ok(df.drop_nulls::<String>(None)?)
     } else {
-        let pretty_df = pretty_print::df_to_str(&df);
-        println!("{pretty_df}");
+        Ok(df.drop_nulls(Some(columns))?)
     }
-    Ok(df)
 }
 
-pub fn get_schema(input: impl AsRef<Path>) -> Result<crate::model::Schema, OxenError> {
-    let opts = DFOpts::empty();
-    // don't need many rows to get schema
-    let total_rows = constants::DEFAULT_PAGE_SIZE;
-    let df = scan_df(input, &opts, total_rows)?;
-    let schema = df.schema().expect("Could not get schema");
+/// Coerce a data frame to a committed schema. The columns are reordered to match the schema and
+/// cast to its dtypes, erroring on missing or extra columns and on values that cannot be cast.
+pub fn apply_schema(df: DataFrame, schema: &crate::model::Schema) -> Result<DataFrame, OxenError> {
+    let df_schema = df.schema();
+    if let Some(name) = df_schema
+        .iter_names()
+        .find(|name| !schema.has_field_name(name.as_str()))
+    {
+        return Err(OxenError::basic_str(format!(
+            "Column '{name}' is not in the schema"
+        )));
+    }
 
-    Ok(crate::model::Schema::from_polars(&schema))
+    let mut columns: Vec<Series> = vec![];
+    for field in schema.fields.iter() {
+        let series = df.column(&field.name).map_err(|_| {
+            OxenError::basic_str(format!(
+                "Column '{}' from the schema is missing in the data frame",
+                field.name
+            ))
+        })?;
+
+        let dtype = DataType::from_string(&field.dtype);
+        if dtype == DataType::Unknown {
+            columns.push(series.clone());
+            continue;
+        }
+
+        // Casting is lenient and nulls out values it cannot convert, find the first one
+        let cast = series.cast(&dtype.to_polars())?;
+        let failed = cast.is_null() & series.is_not_null();
+        if let Some(i) = failed.into_iter().position(|v| v == Some(true)) {
+            return Err(OxenError::basic_str(format!(
+                "Could not cast column '{}' to {}, invalid value {} at row {i}",
+                field.name,
+                field.dtype,
+                series.get(i)?
+            )));
+        }
+        columns.push(cast);
+    }
+
+    Ok(DataFrame::new(columns)?)
 }
 
-pub fn schema_to_string<P: AsRef<Path>>(
-    input: P,
-    flatten: bool,
-    opts: &DFOpts,
-) -> Result<String, OxenError> {
-    let df = scan_df(input, opts, constants::DEFAULT_PAGE_SIZE)?;
-    let schema = df.schema().expect("Could not get schema");
+/// Keep only the rows that have a duplicate, with a `duplicate_group` numbering each set of
+/// matching rows from 1 in order of first occurrence and a `duplicate_count` of the rows in it.
+/// Rows of a group are listed together in their original order. If `columns` is empty the rows
+/// have to match on every column.
+pub fn df_detect_duplicates(df: DataFrame, columns: &[String]) -> Result<DataFrame, OxenError> {
+    let schema = df.schema();
+    for column in columns {
+        if schema.get(column).is_none() {
+            return Err(OxenError::basic_str(format!(
+                "Could not detect duplicates, column not found: {column}"
+            )));
+        }
+    }
 
-    if flatten {
-        let result = polars_schema_to_flat_str(&schema);
-        Ok(result)
+    let key_columns: Vec<String> = if columns.is_empty() {
+        schema.iter_names().map(|name| name.to_string()).collect()
     } else {
-        let mut table = Table::new();
-        table.set_header(vec!["column", "dtype"]);
+        columns.to_vec()
+    };
+    let partition_by = key_columns
+        .iter()
+        .map(|c| format!("\"{}\"", c.replace('"', "\"\"")))
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    // Count each group and remember where it first occurs to number and order the groups
+    let row_idx = "_oxen_duplicates_row_idx";
+    let first_idx = "_oxen_duplicates_first_idx";
+    let group = constants::DUPLICATE_GROUP_COL_NAME;
+    let count = constants::DUPLICATE_COUNT_COL_NAME;
+    let mut df = df.with_row_index(row_idx, Some(0))?;
+    let sql = format!(
+        "SELECT * EXCLUDE (\"{row_idx}\", \"{first_idx}\", \"{count}\"), DENSE_RANK() OVER (ORDER BY \"{first_idx}\") AS \"{group}\", \"{count}\" \
+         FROM (SELECT *, COUNT(*) OVER (PARTITION BY {partition_by}) AS \"{count}\", MIN(\"{row_idx}\") OVER (PARTITION BY {partition_by}) AS \"{first_idx}\" FROM {{table}}) \
+         WHERE \"{count}\" > 1 ORDER BY \"{first_idx}\", \"{row_idx}\""
+    );
+    query_df_with_duckdb(&mut df, "detect_duplicates", &sql)
+}
 
-        for field in schema.iter_fields() {
-            let dtype = DataType::from_polars(field.data_type());
-            let field_str = field.name().to_string();
-            let dtype_str = String::from(DataType::as_str(&dtype));
-            table.add_row(vec![field_str, dtype_str]);
-        }
+/// Count the nulls in each column with DuckDB, one row per column with `column` and `null_count`.
+pub fn df_null_counts(df: &DataFrame) -> Result<DataFrame, OxenError> {
+    let schema = df.schema();
+    if schema.is_empty() {
+        return Err(OxenError::basic_str(
+            "Could not count nulls, data frame has no columns",
+        ));
+    }
 
-        Ok(format!("{table}"))
+    let aggregates: Vec<String> = schema
+        .iter_names()
+        .enumerate()
+        .map(|(i, name)| {
+            format!(
+                "count(*) - count(\"{}\") AS \"null_{i}\"",
+                name.replace('"', "\"\"")
+            )
+        })
+        .collect();
+    let mut df = df.clone();
+    let sql = format!("SELECT {} FROM {{table}}", aggregates.join(", "));
+    let stats = query_df_with_duckdb(&mut df, "null_counts", &sql)?;
+
+    let mut names: Vec<String> = vec![];
+    let mut null_counts: Vec<u64> = vec![];
+    for (i, name) in schema.iter_names().enumerate() {
+        let null_count = stats
+            .column(&format!("null_{i}"))?
+            .cast(&polars::prelude::DataType::UInt64)?
+            .u64()?
+            .get(0)
+            .unwrap_or(0);
+        names.push(name.to_string());
+        null_counts.push(null_count);
     }
+
+    Ok(DataFrame::new(vec![
+        Series::new("column", names),
+        Series::new("null_count", null_counts),
+    ])?)
 }
 
-pub fn polars_schema_to_flat_str(schema: &Schema) -> String {
-    let mut result = String::new();
-    for (i, field) in schema.iter_fields().enumerate() {
-        if i != 0 {
-            result = format!("{result},");
+/// Rename columns from the (old, new) pairs, keeping the column order.
+/// Errors if any of the old names is not a column.
+pub fn df_rename(df: DataFrame, renames: &[(String, String)]) -> Result<DataFrame, OxenError> {
+    let schema = df.schema();
+    for (old, _) in renames {
+        if schema.get(old).is_none() {
+            return Err(OxenError::basic_str(format!(
+                "Could not rename, column not found: {old}"
+            )));
         }
-
-        let dtype = DataType::from_polars(field.data_type());
-        let field_str = field.name().to_string();
-        let dtype_str = String::from(DataType::as_str(&dtype));
-        result = format!("{result}{field_str}:{dtype_str}");
     }
 
-    result
+    let quote = |name: &str| format!("\"{}\"", name.replace('"', "\"\""));
+    let projection = schema
+        .iter_names()
+        .map(
+            |name| match renames.iter().find(|(old, _)| old == name.as_str()) {
+                Some((old, new)) => format!("{} AS {}", quote(old), quote(new)),
+                None => quote(name),
+            },
+        )
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    let mut df = df;
+    let sql = format!("SELECT {projection} FROM {{table}}");
+    query_df_with_duckdb(&mut df, "rename", &sql)
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::core::df::tabular;
-    use crate::view::JsonDataFrameView;
-    use crate::{error::OxenError, opts::DFOpts};
-    use polars::prelude::*;
+/// Scale numeric columns from the (method, column) pairs with DuckDB, where the method is
+/// `minmax` to rescale into [0, 1] or `zscore` to subtract the mean and divide by the sample
+/// standard deviation. Scaled columns replace the originals in place, or with `append` are added
+/// as `{column}_{method}`. Constant columns scale to null rather than dividing by zero.
+pub fn df_normalize(
+    df: DataFrame,
+    scalings: &[(String, String)],
+    append: bool,
+) -> Result<DataFrame, OxenError> {
+    if scalings.is_empty() {
+        return Err(OxenError::basic_str(
+            "Could not normalize, no columns given",
+        ));
+    }
+    let schema = df.schema();
+    let quote = |name: &str| format!("\"{}\"", name.replace('"', "\"\""));
+    let mut expressions: Vec<String> = vec![];
+    for (method, column) in scalings {
+        let Some(dtype) = schema.get(column) else {
+            return Err(OxenError::basic_str(format!(
+                "Could not normalize, column not found: {column}"
+            )));
+        };
+        if !dtype.is_numeric() {
+            return Err(OxenError::basic_str(format!(
+                "Could not normalize column '{column}', expected a numeric column but got {dtype}"
+            )));
+        }
 
-    #[test]
-    fn test_unique_single_field() -> Result<(), OxenError> {
-        let fields = "label";
-        let df = df!(
-            "image" => &["0000.jpg", "0001.jpg", "0002.jpg"],
-            "label" => &["dog", "dog", "unknown"],
-            "min_x" => &[0.0, 1.0, 2.0],
-            "max_x" => &[3.0, 4.0, 5.0],
-            "is_correct" => &[true, false, false],
+        let value = format!("CAST({} AS DOUBLE)", quote(column));
+        let stat = |func: &str| format!("(SELECT {func}({}) FROM {{table}})", quote(column));
+        let method = method.to_lowercase();
+        let scaled = match method.as_str() {
+            "minmax" => format!(
+                "({value} - {min}) / NULLIF({max} - {min}, 0)",
+                min = stat("MIN"),
+                max = stat("MAX")
+            ),
+            "zscore" => format!(
+                "({value} - {mean}) / NULLIF({stddev}, 0)",
+                mean = stat("AVG"),
+                stddev = stat("STDDEV_SAMP")
+            ),
+            _ => {
+                return Err(OxenError::basic_str(format!(
+                    "Could not normalize column '{column}', unknown method: {method}. Expected minmax or zscore"
+                )));
+            }
+        };
+        let name = if append {
+            format!("{column}_{method}")
+        } else {
+            column.to_owned()
+        };
+        expressions.push(format!("{scaled} AS {}", quote(&name)));
+    }
+
+    let sql = if append {
+        format!("SELECT *, {} FROM {{table}}", expressions.join(", "))
+    } else {
+        format!(
+            "SELECT * REPLACE ({}) FROM {{table}}",
+            expressions.join(", ")
         )
-        .unwrap();
+    };
 
-        let mut opts = DFOpts::from_unique(fields);
-        // sort for tests because it comes back random
-        opts.sort_by = Some(String::from("image"));
-        let filtered_df = tabular::transform(df, opts)?;
+    let mut df = df;
+    query_df_with_duckdb(&mut df, "normalize", &sql)
+}
 
-        println!("{filtered_df}");
+/// Add a column `name` holding the first non-null value across `columns` with DuckDB `COALESCE`.
+/// If `name` is already a column it is replaced in place. Errors if any of the columns is missing.
+pub fn df_coalesce(df: DataFrame, columns: &[String], name: &str) -> Result<DataFrame, OxenError> {
+    if columns.is_empty() {
+        return Err(OxenError::basic_str("Could not coalesce, no columns given"));
+    }
+    let schema = df.schema();
+    for column in columns {
+        if schema.get(column).is_none() {
+            return Err(OxenError::basic_str(format!(
+                "Could not coalesce, column not found: {column}"
+            )));
+        }
+    }
 
-        assert_eq!(
-            r"shape: (2, 5)
-┌──────────┬─────────┬───────┬───────┬────────────┐
-│ image    ┆ label   ┆ min_x ┆ max_x ┆ is_correct │
-│ ---      ┆ ---     ┆ ---   ┆ ---   ┆ ---        │
-│ str      ┆ str     ┆ f64   ┆ f64   ┆ bool       │
-╞══════════╪═════════╪═══════╪═══════╪════════════╡
-│ 0000.jpg ┆ dog     ┆ 0.0   ┆ 3.0   ┆ true       │
-│ 0002.jpg ┆ unknown ┆ 2.0   ┆ 5.0   ┆ false      │
-└──────────┴─────────┴───────┴───────┴────────────┘",
-            format!("{filtered_df}")
-        );
+    let quote = |name: &str| format!("\"{}\"", name.replace('"', "\"\""));
+    let coalesce = format!(
+        "COALESCE({}) AS {}",
+        columns
+            .iter()
+            .map(|c| quote(c))
+            .collect::<Vec<String>>()
+            .join(", "),
+        quote(name)
+    );
+    let sql = if schema.get(name).is_some() {
+        format!("SELECT * REPLACE ({coalesce}) FROM {{table}}")
+    } else {
+        format!("SELECT *, {coalesce} FROM {{table}}")
+    };
 
-        Ok(())
+    let mut df = df;
+    query_df_with_duckdb(&mut df, "coalesce", &sql)
+}
+
+/// Join the rows of `right` where the jaro winkler similarity of the `on` columns is at least
+/// `threshold`, with the similarity of each pair in a `similarity` column. Columns of `right`
+/// that are also in `df` get a `_right` suffix.
+pub fn df_fuzzy_join(
+    df: DataFrame,
+    right: DataFrame,
+    on: &str,
+    threshold: f64,
+) -> Result<DataFrame, OxenError> {
+    let schema = df.schema();
+    let right_schema = right.schema();
+    if schema.get(on).is_none() || right_schema.get(on).is_none() {
+        return Err(OxenError::basic_str(format!(
+            "Could not fuzzy join, column must be in both files: {on}"
+        )));
     }
 
-    #[test]
-    fn test_unique_multi_field() -> Result<(), OxenError> {
-        let fields = "image,label";
-        let df = df!(
-            "image" => &["0000.jpg", "0000.jpg", "0002.jpg"],
-            "label" => &["dog", "dog", "dog"],
-            "min_x" => &[0.0, 1.0, 2.0],
-            "max_x" => &[3.0, 4.0, 5.0],
-            "is_correct" => &[true, false, false],
-        )
-        .unwrap();
+    let quote = |name: &str| format!("\"{}\"", name.replace('"', "\"\""));
+    let right_columns = right_schema
+        .iter_names()
+        .map(|name| {
+            if schema.get(name).is_some() {
+                let alias = format!("{name}{}", constants::FUZZY_JOIN_RIGHT_SUFFIX);
+                format!("r.{} AS {}", quote(name), quote(&alias))
+            } else {
+                format!("r.{}", quote(name))
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(", ");
 
-        let mut opts = DFOpts::from_unique(fields);
-        // sort for tests because it comes back random
-        opts.sort_by = Some(String::from("image"));
-        let filtered_df = tabular::transform(df, opts)?;
+    let similarity = format!(
+        "jaro_winkler_similarity(CAST(l.{on} AS VARCHAR), CAST(r.{on} AS VARCHAR))",
+        on = quote(on)
+    );
 
-        println!("{filtered_df}");
+    let sql = format!(
+        "SELECT l.*, {right_columns}, {similarity} AS {} FROM {{table}} l JOIN fuzzy_join_right r ON {similarity} >= {threshold} ORDER BY l.{}, {} DESC",
+        quote(constants::FUZZY_SIMILARITY_COL_NAME),
+        quote(on),
+        quote(constants::FUZZY_SIMILARITY_COL_NAME),
+    );
 
-        assert_eq!(
-            r"shape: (2, 5)
-┌──────────┬───────┬───────┬───────┬────────────┐
-│ image    ┆ label ┆ min_x ┆ max_x ┆ is_correct │
-│ ---      ┆ ---   ┆ ---   ┆ ---   ┆ ---        │
+    let mut df = df;
+    query_df_with_duckdb_tables(
+        &mut df,
+        "fuzzy_join",
+        &mut [("fuzzy_join_right", right)],
+        &sql,
+    )
+}
+
+/// Cast columns from the (column, type) pairs with DuckDB, keeping the column order.
+/// Types are `int`, `bigint`, `float`, `double`, `bool`, `str`, `date` or `timestamp`.
+pub fn df_cast(df: DataFrame, casts: &[(String, String)]) -> Result<DataFrame, OxenError> {
+    let schema = df.schema();
+    let mut sql_types: Vec<(&str, &str)> = vec![];
+    for (column, dtype) in casts {
+        if schema.get(column).is_none() {
+            return Err(OxenError::basic_str(format!(
+                "Could not cast, column not found: {column}"
+            )));
+        }
+        let sql_type = match dtype.to_lowercase().as_str() {
+            "int" | "integer" | "i32" => "INTEGER",
+            "bigint" | "long" | "i64" => "BIGINT",
+            "float" | "f32" => "FLOAT",
+            "double" | "f64" => "DOUBLE",
+            "bool" | "boolean" => "BOOLEAN",
+            "str" | "string" | "varchar" => "VARCHAR",
+            "date" => "DATE",
+            "timestamp" | "datetime" => "TIMESTAMP",
+            _ => {
+                return Err(OxenError::basic_str(format!(
+                    "Could not cast column '{column}', unknown type: {dtype}"
+                )));
+            }
+        };
+        sql_types.push((column, sql_type));
+    }
+
+    let quote = |name: &str| format!("\"{}\"", name.replace('"', "\"\""));
+    let projection = schema
+        .iter_names()
+        .map(|name| {
+            match sql_types
+                .iter()
+                .find(|(column, _)| *column == name.as_str())
+            {
+                Some((column, sql_type)) => {
+                    format!(
+                        "CAST({} AS {}) AS {}",
+                        quote(column),
+                        sql_type,
+                        quote(column)
+                    )
+                }
+                None => quote(name),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    let mut df = df;
+    let sql = format!("SELECT {projection} FROM {{table}}");
+    // DuckDB reports the value that could not be converted
+    query_df_with_duckdb(&mut df, "cast", &sql)
+        .map_err(|err| OxenError::basic_str(format!("Could not cast columns: {err}")))
+}
+
+/// Compute a data quality report with DuckDB aggregates: per column null rate, distinct count,
+/// min and max, and anomalies such as text columns that mix numbers and words.
+pub fn df_profile(df: &DataFrame) -> Result<DataFrameProfile, OxenError> {
+    let schema = df.schema();
+    let quote = |name: &str| format!("\"{}\"", name.replace('"', "\"\""));
+
+    let mut aggregates: Vec<String> = vec![String::from("count(*) AS \"num_rows\"")];
+    for (i, (name, dtype)) in schema.iter().enumerate() {
+        let column = quote(name);
+        aggregates.push(format!("count(*) - count({column}) AS \"null_{i}\""));
+        aggregates.push(format!("count(DISTINCT {column}) AS \"distinct_{i}\""));
+        aggregates.push(format!("CAST(min({column}) AS VARCHAR) AS \"min_{i}\""));
+        aggregates.push(format!("CAST(max({column}) AS VARCHAR) AS \"max_{i}\""));
+        if *dtype == polars::prelude::DataType::String {
+            aggregates.push(format!(
+                "count(TRY_CAST({column} AS DOUBLE)) AS \"numeric_{i}\""
+            ));
+        }
+    }
+
+    let mut df = df.clone();
+    let sql = format!("SELECT {} FROM {{table}}", aggregates.join(", "));
+    let stats = query_df_with_duckdb(&mut df, "profile", &sql)?;
+
+    let count = |name: &str| -> Result<usize, OxenError> {
+        let value = stats
+            .column(name)?
+            .cast(&polars::prelude::DataType::Int64)?
+            .i64()?
+            .get(0)
+            .unwrap_or(0);
+        Ok(value as usize)
+    };
+    let text = |name: &str| -> Result<Option<String>, OxenError> {
+        let value = stats
+            .column(name)?
+            .cast(&polars::prelude::DataType::String)?
+            .str()?
+            .get(0)
+            .map(String::from);
+        Ok(value)
+    };
+
+    let num_rows = count("num_rows")?;
+    let mut columns: Vec<ColumnProfile> = vec![];
+    for (i, (name, dtype)) in schema.iter().enumerate() {
+        let null_count = count(&format!("null_{i}"))?;
+        let null_rate = if num_rows == 0 {
+            0.0
+        } else {
+            null_count as f64 / num_rows as f64
+        };
+
+        let mut anomalies: Vec<String> = vec![];
+        if num_rows > 0 && null_count == num_rows {
+            anomalies.push(String::from("all values are null"));
+        }
+        if *dtype == polars::prelude::DataType::String {
+            let num_values = num_rows - null_count;
+            let num_numeric = count(&format!("numeric_{i}"))?;
+            if num_numeric > 0 && num_numeric < num_values {
+                anomalies.push(format!(
+                    "mixed types: {num_numeric} of {num_values} values are numeric"
+                ));
+            }
+        }
+
+        columns.push(ColumnProfile {
+            name: name.to_string(),
+            dtype: dtype.to_string(),
+            null_count,
+            null_rate,
+            distinct_count: count(&format!("distinct_{i}"))?,
+            min: text(&format!("min_{i}"))?,
+            max: text(&format!("max_{i}"))?,
+            anomalies,
+        });
+    }
+
+    Ok(DataFrameProfile { num_rows, columns })
+}
+
+/// Run a query against the data frame with DuckDB, `{table}` in the sql is replaced with the frame
+fn query_df_with_duckdb(df: &mut DataFrame, name: &str, sql: &str) -> Result<DataFrame, OxenError> {
+    query_df_with_duckdb_tables(df, name, &mut [], sql)
+}
+
+// Same as query_df_with_duckdb, with each of `tables` first loaded into a temporary table of
+// that name, so large lookups are joined against instead of being inlined in the sql
+fn query_df_with_duckdb_tables(
+    df: &mut DataFrame,
+    name: &str,
+    tables: &mut [(&str, DataFrame)],
+    sql: &str,
+) -> Result<DataFrame, OxenError> {
+    // DuckDB reads the frames back from temporary parquet files, removed when the guard drops
+    let tmp_parquet =
+        || std::env::temp_dir().join(format!("oxen_{}_{}.parquet", name, uuid::Uuid::new_v4()));
+    let mut tmp_files = TmpParquetFiles(vec![]);
+    let tmp_path = tmp_parquet();
+    tmp_files.0.push(tmp_path.clone());
+    write_df_parquet(df, &tmp_path)?;
+
+    let mut setup: Vec<String> = vec![];
+    for (table_name, table_df) in tables.iter_mut() {
+        let table_path = tmp_parquet();
+        tmp_files.0.push(table_path.clone());
+        write_df_parquet(table_df, &table_path)?;
+        setup.push(format!(
+            "CREATE TEMP TABLE \"{table_name}\" AS SELECT * FROM read_parquet('{}')",
+            table_path.to_string_lossy()
+        ));
+    }
+
+    let table = format!("read_parquet('{}')", tmp_path.to_string_lossy());
+    let sql = sql.replace("{table}", &table);
+    log::debug!("df_{} running sql: {}", name, sql);
+
+    let conn = duckdb::Connection::open_in_memory()?;
+    if !setup.is_empty() {
+        conn.execute_batch(&setup.join(";\n"))?;
+    }
+    df_db::select_raw(&conn, &sql, false, None)
+}
+
+// Temporary parquet files handed to DuckDB, removed on drop so an early error does not leak them
+struct TmpParquetFiles(Vec<PathBuf>);
+
+impl Drop for TmpParquetFiles {
+    fn drop(&mut self) {
+        for path in self.0.iter().filter(|path| path.exists()) {
+            if let Err(err) = std::fs::remove_file(path) {
+                log::warn!("Could not remove temporary file {:?}: {}", path, err);
+            }
+        }
+    }
+}
+
+fn sniff_db_csv_delimiter(path: impl AsRef<Path>, opts: &DFOpts) -> Result<u8, OxenError> {
+    if let Some(delimiter) = &opts.delimiter {
+        if delimiter.len() != 1 {
+            return Err(OxenError::basic_str("Delimiter must be a single character"));
+        }
+        return Ok(delimiter.as_bytes()[0]);
+    }
+
+    match qsv_sniffer::Sniffer::new().sniff_path(&path) {
+        Ok(metadata) => Ok(metadata.dialect.delimiter),
+        Err(err) => {
+            let err = format!("Error sniffing csv {:?} -> {:?}", path.as_ref(), err);
+            log::warn!("{}", err);
+            Ok(b',')
+        }
+    }
+}
+
+/// Decompress a .gz or .zst file into a temp dir, keeping the inner file name so the
+/// extension is still known, and call `f` with the decompressed path.
+fn with_decompressed<T>(
+    path: &Path,
+    f: impl FnOnce(&Path) -> Result<T, OxenError>,
+) -> Result<T, OxenError> {
+    let file_name = path
+        .file_stem()
+        .ok_or_else(|| OxenError::basic_str(format!("Invalid path {path:?}")))?;
+    let tmp_dir = std::env::temp_dir().join(format!("oxen_decompress_{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(&tmp_dir)?;
+
+    let tmp_path = tmp_dir.join(file_name);
+    log::debug!("Decompressing {:?} to {:?}", path, tmp_path);
+    let result = fs::decompress_file(path, &tmp_path).and_then(|_| f(&tmp_path));
+    fs::remove_dir_all(&tmp_dir)?;
+    result
+}
+
+pub fn read_df(path: impl AsRef<Path>, opts: DFOpts) -> Result<DataFrame, OxenError> {
+    let path = path.as_ref();
+    if !path.exists() {
+        return Err(OxenError::entry_does_not_exist(path));
+    }
+
+    let extension = path.extension().and_then(OsStr::to_str);
+    let err = format!("Unknown file type read_df {path:?} -> {extension:?}");
+
+    if opts.aggregate_over_dirs {
+        let Some(columns) = opts.group_by_columns() else {
+            return Err(OxenError::basic_str(
+                "Could not aggregate over dirs, must supply --group-by",
+            ));
+        };
+        let aggs = opts.aggregations()?.unwrap_or_default();
+        let df = df_group_by_over_dir(path, &columns, &aggs)?;
+        // The remaining transforms apply to the aggregated rows
+        let opts = DFOpts {
+            aggregate_over_dirs: false,
+            group_by: None,
+            agg: None,
+            ..opts
+        };
+        return transform(df, opts);
+    }
+
+    if fs::compression_from_path(path).is_some() {
+        return with_decompressed(path, |path| read_df(path, opts));
+    }
+
+    if let Some(concat) = &opts.concat {
+        let mut paths = vec![path.to_path_buf()];
+        paths.extend(concat.iter().cloned());
+        let conn = duckdb::Connection::open_in_memory()?;
+        let df = df_db::concat_files(&conn, &paths, opts.coerce, opts.merge_schema)?;
+        return transform(df, opts);
+    }
+
+    let df = match extension {
+        Some(extension) => match extension {
+            "ndjson" => read_df_jsonl(path),
+            "jsonl" => read_df_jsonl(path),
+            "json" => read_df_json(path),
+            "csv" | "data" => {
+                let delimiter = sniff_db_csv_delimiter(path, &opts)?;
+                read_df_csv(path, delimiter)
+            }
+            "tsv" => read_df_csv(path, b'\t'),
+            "parquet" => read_df_parquet(path),
+            "arrow" => read_df_arrow(path),
+            _ => Err(OxenError::basic_str(err)),
+        },
+        None => Err(OxenError::basic_str(err)),
+    }?;
+
+    if opts.has_transform() {
+        let df = transform(df, opts)?;
+        Ok(df)
+    } else {
+        Ok(df)
+    }
+}
+
+/// Read a DataFrame for casting to a known schema. Delimited text is read with every column as
+/// a string, so values like "02134" keep their leading zeros instead of going through an
+/// inferred integer first. Formats that store their dtypes are read as usual.
+pub fn read_df_untyped(path: impl AsRef<Path>, opts: &DFOpts) -> Result<DataFrame, OxenError> {
+    let path = path.as_ref();
+    if !path.exists() {
+        return Err(OxenError::entry_does_not_exist(path));
+    }
+    if fs::compression_from_path(path).is_some() {
+        return with_decompressed(path, |path| read_df_untyped(path, opts));
+    }
+
+    match path.extension().and_then(OsStr::to_str) {
+        Some("csv") | Some("data") => {
+            let delimiter = sniff_db_csv_delimiter(path, opts)?;
+            read_df_csv_untyped(path, delimiter)
+        }
+        Some("tsv") => read_df_csv_untyped(path, b'\t'),
+        _ => read_df(path, DFOpts::empty()),
+    }
+}
+
+pub fn scan_df(
+    path: impl AsRef<Path>,
+    opts: &DFOpts,
+    total_rows: usize,
+) -> Result<LazyFrame, OxenError> {
+    let input_path = path.as_ref();
+    if fs::compression_from_path(input_path).is_some() {
+        // Compressed files cannot be scanned lazily, read them into memory instead
+        let mut read_opts = DFOpts::empty();
+        read_opts.delimiter = opts.delimiter.clone();
+        read_opts.head = Some(total_rows);
+        return with_decompressed(input_path, |path| read_df(path, read_opts)).map(|df| df.lazy());
+    }
+
+    let extension = input_path.extension().and_then(OsStr::to_str);
+    let err = format!("Unknown file type scan_df {input_path:?} {extension:?}");
+
+    match extension {
+        Some(extension) => match extension {
+            "ndjson" => scan_df_jsonl(path, total_rows),
+            "jsonl" => scan_df_jsonl(path, total_rows),
+            "json" => scan_df_json(path),
+            "csv" | "data" => {
+                let delimiter = sniff_db_csv_delimiter(&path, opts)?;
+                scan_df_csv(path, delimiter, total_rows)
+            }
+            "tsv" => scan_df_csv(path, b'\t', total_rows),
+            "parquet" => scan_df_parquet(path, total_rows),
+            "arrow" => scan_df_arrow(path),
+            _ => Err(OxenError::basic_str(err)),
+        },
+        None => Err(OxenError::basic_str(err)),
+    }
+}
+
+pub fn get_size(path: impl AsRef<Path>) -> Result<DataFrameSize, OxenError> {
+    // Don't need that many rows to get the width
+    let num_scan_rows = constants::DEFAULT_PAGE_SIZE;
+    let lazy_df = scan_df(&path, &DFOpts::empty(), num_scan_rows)?;
+    let schema = lazy_df.schema()?;
+    let width = schema.len();
+
+    let input_path = path.as_ref();
+    let extension = input_path.extension().and_then(OsStr::to_str);
+    let err = format!("Unknown file type get_size {input_path:?} {extension:?}");
+
+    match extension {
+        Some(extension) => match extension {
+            "csv" | "tsv" => {
+                let mut opts = CountLinesOpts::empty();
+                opts.remove_trailing_blank_line = true;
+
+                // Remove one line to account for CSV/TSV headers
+                let (mut height, _) = fs::count_lines(path, opts)?;
+                height -= 1; // Adjusting for header
+
+                Ok(DataFrameSize { width, height })
+            }
+            "data" | "jsonl" | "ndjson" => {
+                let mut opts = CountLinesOpts::empty();
+                opts.remove_trailing_blank_line = true;
+
+                let (height, _) = fs::count_lines(path, opts)?;
+
+                Ok(DataFrameSize { width, height })
+            }
+            "parquet" => {
+                let file = File::open(input_path)?;
+                let mut reader = ParquetReader::new(file);
+                let height = reader.num_rows()?;
+                Ok(DataFrameSize { width, height })
+            }
+            "arrow" => {
+                let file = File::open(input_path)?;
+                // arrow is fast to .finish() so we can just do it here
+                let reader = IpcReader::new(file);
+                let height = reader.finish().unwrap().height();
+                Ok(DataFrameSize { width, height })
+            }
+            "json" => {
+                let df = lazy_df
+                    .collect()
+                    .map_err(|_| OxenError::basic_str("Could not collect json df"))?;
+                let height = df.height();
+                Ok(DataFrameSize { width, height })
+            }
+            _ => Err(OxenError::basic_str(err)),
+        },
+        None => Err(OxenError::basic_str(err)),
+    }
+}
+
+pub fn write_df_json<P: AsRef<Path>>(df: &mut DataFrame, output: P) -> Result<(), OxenError> {
+    let output = output.as_ref();
+    let error_str = format!("Could not save tabular data to path: {output:?}");
+    log::debug!("Writing file {:?}", output);
+    log::debug!("{:?}", df);
+    let f = std::fs::File::create(output).unwrap();
+    JsonWriter::new(f)
+        .with_json_format(JsonFormat::Json)
+        .finish(df)
+        .expect(&error_str);
+    Ok(())
+}
+
+pub fn write_df_jsonl<P: AsRef<Path>>(df: &mut DataFrame, output: P) -> Result<(), OxenError> {
+    let output = output.as_ref();
+    let error_str = format!("Could not save tabular data to path: {output:?}");
+    log::debug!("Writing file {:?}", output);
+    let f = std::fs::File::create(output).unwrap();
+    JsonWriter::new(f)
+        .with_json_format(JsonFormat::JsonLines)
+        .finish(df)
+        .expect(&error_str);
+    Ok(())
+}
+
+pub fn write_df_csv<P: AsRef<Path>>(
+    df: &mut DataFrame,
+    output: P,
+    delimiter: u8,
+) -> Result<(), OxenError> {
+    let output = output.as_ref();
+    let error_str = format!("Could not save tabular data to path: {output:?}");
+    log::debug!("Writing file {:?}", output);
+    let f = std::fs::File::create(output).unwrap();
+    CsvWriter::new(f)
+        .include_header(true)
+        .with_separator(delimiter)
+        .finish(df)
+        .expect(&error_str);
+    Ok(())
+}
+
+pub fn write_df_parquet<P: AsRef<Path>>(df: &mut DataFrame, output: P) -> Result<(), OxenError> {
+    let output = output.as_ref();
+    let error_str = format!("Could not save tabular data to path: {output:?}");
+    log::debug!("Writing file {:?}", output);
+    match std::fs::File::create(output) {
+        Ok(f) => {
+            ParquetWriter::new(f).finish(df).expect(&error_str);
+            Ok(())
+        }
+        Err(err) => {
+            let error_str = format!("Could not create file {:?}", err);
+            Err(OxenError::basic_str(error_str))
+        }
+    }
+}
+
+pub fn write_df_arrow<P: AsRef<Path>>(df: &mut DataFrame, output: P) -> Result<(), OxenError> {
+    let output = output.as_ref();
+    let error_str = format!("Could not save tabular data to path: {output:?}");
+    log::debug!("Writing file {:?}", output);
+    let f = std::fs::File::create(output).unwrap();
+    IpcWriter::new(f).finish(df).expect(&error_str);
+    Ok(())
+}
+
+/// Write the data frame as an Arrow IPC stream, so it can be piped into other Arrow readers
+pub fn write_df_arrow_stream<W: std::io::Write>(
+    df: &mut DataFrame,
+    writer: W,
+) -> Result<(), OxenError> {
+    IpcStreamWriter::new(writer).finish(df)?;
+    Ok(())
+}
+
+pub fn write_df(df: &mut DataFrame, path: impl AsRef<Path>) -> Result<(), OxenError> {
+    let path = path.as_ref();
+    let extension = path.extension().and_then(OsStr::to_str);
+    let err = format!("Unknown file type write_df {path:?} {extension:?}");
+
+    match extension {
+        Some(extension) => match extension {
+            "ndjson" => write_df_jsonl(df, path),
+            "jsonl" => write_df_jsonl(df, path),
+            "json" => write_df_json(df, path),
+            "tsv" => write_df_csv(df, path, b'\t'),
+            "csv" => write_df_csv(df, path, b','),
+            "parquet" => write_df_parquet(df, path),
+            "arrow" => write_df_arrow(df, path),
+            _ => Err(OxenError::basic_str(err)),
+        },
+        None => Err(OxenError::basic_str(err)),
+    }
+}
+
+/// Write a hive partitioned parquet dataset to the `dir` directory with DuckDB, ie.
+/// `dir/year=2024/month=1/data_0.parquet`. The partition columns are only stored in the paths.
+pub fn write_df_partitioned(
+    df: &mut DataFrame,
+    dir: impl AsRef<Path>,
+    partition_by: &[String],
+) -> Result<(), OxenError> {
+    let dir = dir.as_ref();
+    if partition_by.is_empty() {
+        return Err(OxenError::basic_str("Must supply columns to partition by"));
+    }
+    let schema = df.schema();
+    if let Some(column) = partition_by.iter().find(|c| schema.get(c).is_none()) {
+        return Err(OxenError::basic_str(format!(
+            "Could not partition by {column:?}, column not found"
+        )));
+    }
+    if dir.is_file() {
+        return Err(OxenError::basic_str(format!(
+            "Partitioned output must be a directory: {dir:?}"
+        )));
+    }
+
+    let tmp_path = std::env::temp_dir().join(format!(
+        "oxen_partition_by_{}.parquet",
+        uuid::Uuid::new_v4()
+    ));
+    write_df_parquet(df, &tmp_path)?;
+
+    let partition_by = partition_by
+        .iter()
+        .map(|c| format!("\"{}\"", c.replace('"', "\"\"")))
+        .collect::<Vec<String>>()
+        .join(", ");
+    let sql = format!(
+        "COPY (SELECT * FROM read_parquet('{}')) TO '{}' (FORMAT PARQUET, PARTITION_BY ({partition_by}), OVERWRITE_OR_IGNORE)",
+        tmp_path.to_string_lossy(),
+        dir.to_string_lossy(),
+    );
+    log::debug!("write_df_partitioned running sql: {}", sql);
+
+    let result = duckdb::Connection::open_in_memory()
+        .and_then(|conn| conn.execute(&sql, []))
+        .map_err(OxenError::from);
+    fs::remove_file(&tmp_path)?;
+    result?;
+    Ok(())
+}
+
+pub fn copy_df(input: impl AsRef<Path>, output: impl AsRef<Path>) -> Result<DataFrame, OxenError> {
+    let mut df = read_df(input, DFOpts::empty())?;
+    write_df_arrow(&mut df, output)?;
+    Ok(df)
+}
+
+pub fn copy_df_add_row_num(
+    input: impl AsRef<Path>,
+    output: impl AsRef<Path>,
+) -> Result<DataFrame, OxenError> {
+    let df = read_df(input, DFOpts::empty())?;
+    let mut df = df
+        .lazy()
+        .with_row_index("_row_num", Some(0))
+        .collect()
+        .expect("Could not add row count");
+    write_df_arrow(&mut df, output)?;
+    Ok(df)
+}
+
+pub fn show_path(input: impl AsRef<Path>, opts: DFOpts) -> Result<DataFrame, OxenError> {
+    log::debug!("Got opts {:?}", opts);
+    let df = read_df(input, opts.clone())?;
+    let df = if let Some(page_size) = opts.page_size {
+        let page_opts = PaginateOpts {
+            page_num: opts.page.unwrap_or(constants::DEFAULT_PAGE_NUM).max(1),
+            page_size,
+        };
+        paginate_df(df, &page_opts)?
+    } else {
+        df
+    };
+    if opts.column_at().is_some() {
+        for val in df.get(0).unwrap() {
+            match val {
+                polars::prelude::AnyValue::List(vals) => {
+                    for val in vals.iter() {
+                        println!("{val}")
+                    }
+                }
+                _ => {
+                    println!("{val}")
+                }
+            }
+        }
+    } else {
+        let pretty_df = pretty_print::df_to_str(&df);
+        println!("{pretty_df}");
+    }
+    Ok(df)
+}
+
+pub fn get_schema(input: impl AsRef<Path>) -> Result<crate::model::Schema, OxenError> {
+    let opts = DFOpts::empty();
+    // don't need many rows to get schema
+    let total_rows = constants::DEFAULT_PAGE_SIZE;
+    let df = scan_df(input, &opts, total_rows)?;
+    let schema = df.schema().expect("Could not get schema");
+
+    Ok(crate::model::Schema::from_polars(&schema))
+}
+
+pub fn schema_to_string<P: AsRef<Path>>(
+    input: P,
+    flatten: bool,
+    opts: &DFOpts,
+) -> Result<String, OxenError> {
+    let df = scan_df(input, opts, constants::DEFAULT_PAGE_SIZE)?;
+    let schema = df.schema().expect("Could not get schema");
+
+    if flatten {
+        let result = polars_schema_to_flat_str(&schema);
+        Ok(result)
+    } else {
+        let mut table = Table::new();
+        table.set_header(vec!["column", "dtype"]);
+
+        for field in schema.iter_fields() {
+            let dtype = DataType::from_polars(field.data_type());
+            let field_str = field.name().to_string();
+            let dtype_str = String::from(DataType::as_str(&dtype));
+            table.add_row(vec![field_str, dtype_str]);
+        }
+
+        Ok(format!("{table}"))
+    }
+}
+
+pub fn polars_schema_to_flat_str(schema: &Schema) -> String {
+    let mut result = String::new();
+    for (i, field) in schema.iter_fields().enumerate() {
+        if i != 0 {
+            result = format!("{result},");
+        }
+
+        let dtype = DataType::from_polars(field.data_type());
+        let field_str = field.name().to_string();
+        let dtype_str = String::from(DataType::as_str(&dtype));
+        result = format!("{result}{field_str}:{dtype_str}");
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::constants;
+    use crate::core::df::tabular;
+    use crate::error::OxenError;
+    use crate::opts::{DFOpts, PivotOpts};
+    use crate::test;
+    use crate::util;
+    use crate::view::JsonDataFrameView;
+    use polars::prelude::*;
+
+    #[test]
+    fn test_unique_single_field() -> Result<(), OxenError> {
+        let fields = "label";
+        let df = df!(
+            "image" => &["0000.jpg", "0001.jpg", "0002.jpg"],
+            "label" => &["dog", "dog", "unknown"],
+            "min_x" => &[0.0, 1.0, 2.0],
+            "max_x" => &[3.0, 4.0, 5.0],
+            "is_correct" => &[true, false, false],
+        )
+        .unwrap();
+
+        let mut opts = DFOpts::from_unique(fields);
+        // sort for tests because it comes back random
+        opts.sort_by = Some(String::from("image"));
+        let filtered_df = tabular::transform(df, opts)?;
+
+        println!("{filtered_df}");
+
+        assert_eq!(
+            r"shape: (2, 5)
+┌──────────┬─────────┬───────┬───────┬────────────┐
+│ image    ┆ label   ┆ min_x ┆ max_x ┆ is_correct │
+│ ---      ┆ ---     ┆ ---   ┆ ---   ┆ ---        │
+│ str      ┆ str     ┆ f64   ┆ f64   ┆ bool       │
+╞══════════╪═════════╪═══════╪═══════╪════════════╡
+│ 0000.jpg ┆ dog     ┆ 0.0   ┆ 3.0   ┆ true       │
+│ 0002.jpg ┆ unknown ┆ 2.0   ┆ 5.0   ┆ false      │
+└──────────┴─────────┴───────┴───────┴────────────┘",
+            format!("{filtered_df}")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unique_multi_field() -> Result<(), OxenError> {
+        let fields = "image,label";
+        let df = df!(
+            "image" => &["0000.jpg", "0000.jpg", "0002.jpg"],
+            "label" => &["dog", "dog", "dog"],
+            "min_x" => &[0.0, 1.0, 2.0],
+            "max_x" => &[3.0, 4.0, 5.0],
+            "is_correct" => &[true, false, false],
+        )
+        .unwrap();
+
+        let mut opts = DFOpts::from_unique(fields);
+        // sort for tests because it comes back random
+        opts.sort_by = Some(String::from("image"));
+        let filtered_df = tabular::transform(df, opts)?;
+
+        println!("{filtered_df}");
+
+        assert_eq!(
+            r"shape: (2, 5)
+┌──────────┬───────┬───────┬───────┬────────────┐
+│ image    ┆ label ┆ min_x ┆ max_x ┆ is_correct │
+│ ---      ┆ ---   ┆ ---   ┆ ---   ┆ ---        │
 │ str      ┆ str   ┆ f64   ┆ f64   ┆ bool       │
 ╞══════════╪═══════╪═══════╪═══════╪════════════╡
 │ 0000.jpg ┆ dog   ┆ 0.0   ┆ 3.0   ┆ true       │
@@ -1014,6 +2253,956 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_hash_rows_stable() -> Result<(), OxenError> {
+        let df = df!(
+            "image" => &["0000.jpg", "0000.jpg", "0002.jpg"],
+            "label" => &["dog", "dog", "dog"],
+            "min_x" => &[0.0, 0.0, 2.0],
+        )
+        .unwrap();
+
+        let mut opts = DFOpts::empty();
+        opts.hash_rows = Some(String::from(""));
+        let hashed_df = tabular::transform(df.clone(), opts.clone())?;
+        let hashes = hashed_df.column(constants::ROW_HASH_COL_NAME)?.str()?;
+
+        // Identical rows get identical hashes, a changed value gets a different one
+        assert_eq!(hashes.get(0), hashes.get(1));
+        assert_ne!(hashes.get(0), hashes.get(2));
+
+        // Stable across runs and column order
+        let reordered = df.select(["min_x", "label", "image"])?;
+        let reordered_df = tabular::transform(reordered, opts)?;
+        let reordered_hashes = reordered_df.column(constants::ROW_HASH_COL_NAME)?.str()?;
+        assert_eq!(hashes.get(0), reordered_hashes.get(0));
+        assert_eq!(hashes.get(2), reordered_hashes.get(2));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_seed_split_is_reproducible() -> Result<(), OxenError> {
+        let ids: Vec<i64> = (0..10_000).collect();
+        let labels: Vec<&str> = ids
+            .iter()
+            .map(|i| if i % 2 == 0 { "cat" } else { "dog" })
+            .collect();
+        let df = df!("id" => &ids, "label" => &labels).unwrap();
+
+        let mut opts = DFOpts::empty();
+        opts.seed_split = Some(String::from("0.8,0.1,0.1"));
+        opts.seed = Some(42);
+        let first = tabular::transform(df.clone(), opts.clone())?;
+        let second = tabular::transform(df.clone(), opts.clone())?;
+        let splits = first.column(constants::SPLIT_COL_NAME)?.str()?;
+        assert!(splits
+            .equal(second.column(constants::SPLIT_COL_NAME)?.str()?)
+            .all());
+
+        // The split follows the row, not its position
+        let reversed = tabular::transform(df.reverse(), opts.clone())?;
+        let reversed_splits = reversed.column(constants::SPLIT_COL_NAME)?.str()?;
+        assert_eq!(splits.get(0), reversed_splits.get(ids.len() - 1));
+
+        let count = |name: &str| splits.into_iter().filter(|s| *s == Some(name)).count() as f64;
+        let total = ids.len() as f64;
+        assert!((count("train") / total - 0.8).abs() < 0.02);
+        assert!((count("val") / total - 0.1).abs() < 0.02);
+        assert!((count("test") / total - 0.1).abs() < 0.02);
+
+        // A different seed shuffles the assignment
+        opts.seed = Some(7);
+        let reseeded = tabular::transform(df, opts)?;
+        let reseeded_splits = reseeded.column(constants::SPLIT_COL_NAME)?.str()?;
+        assert!(!splits.equal(reseeded_splits).all());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_seed_split_invalid_fractions() {
+        let df = df!("label" => &["dog"]).unwrap();
+
+        let mut opts = DFOpts::empty();
+        opts.seed_split = Some(String::from("0.8,0.3"));
+        assert!(tabular::transform(df.clone(), opts.clone()).is_err());
+
+        opts.seed_split = Some(String::from("1.0"));
+        assert!(tabular::transform(df, opts).is_err());
+    }
+
+    #[test]
+    fn test_hash_rows_on_cols() -> Result<(), OxenError> {
+        let df = df!(
+            "image" => &["0000.jpg", "0001.jpg"],
+            "label" => &["dog", "dog"],
+        )
+        .unwrap();
+
+        let mut opts = DFOpts::empty();
+        opts.hash_rows = Some(String::from("label"));
+        let hashed_df = tabular::transform(df, opts)?;
+        let hashes = hashed_df.column(constants::ROW_HASH_COL_NAME)?.str()?;
+
+        // Only hashing the label, so the rows are the same
+        assert_eq!(hashes.get(0), hashes.get(1));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_hash_rows_unknown_col() {
+        let df = df!("label" => &["dog"]).unwrap();
+
+        let mut opts = DFOpts::empty();
+        opts.hash_rows = Some(String::from("not_a_col"));
+        assert!(tabular::transform(df, opts).is_err());
+    }
+
+    #[test]
+    fn test_pivot_long_to_wide() -> Result<(), OxenError> {
+        let df = df!(
+            "image" => &["0000.jpg", "0000.jpg", "0001.jpg", "0001.jpg"],
+            "metric" => &["width", "height", "width", "height"],
+            "value" => &[640i64, 480, 1024, 768],
+        )
+        .unwrap();
+
+        let mut opts = DFOpts::empty();
+        opts.pivot = Some(PivotOpts {
+            index: String::from("image"),
+            columns: String::from("metric"),
+            values: String::from("value"),
+            agg: String::from("first"),
+        });
+        let wide = tabular::transform(df, opts)?;
+
+        assert_eq!(wide.height(), 2);
+        let mut names: Vec<&str> = wide.get_column_names();
+        names.sort();
+        assert_eq!(names, vec!["height", "image", "width"]);
+
+        let images = wide.column("image")?.str()?;
+        assert_eq!(images.get(0), Some("0000.jpg"));
+        assert_eq!(images.get(1), Some("0001.jpg"));
+        let widths = wide.column("width")?.i64()?;
+        assert_eq!(widths.get(0), Some(640));
+        assert_eq!(widths.get(1), Some(1024));
+        let heights = wide.column("height")?.i64()?;
+        assert_eq!(heights.get(0), Some(480));
+        assert_eq!(heights.get(1), Some(768));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pivot_quoted_column_names() -> Result<(), OxenError> {
+        let df = df!(
+            "the \"image\"" => &["0000.jpg", "0000.jpg"],
+            "metric" => &["width", "height"],
+            "value" => &[640i64, 480],
+        )
+        .unwrap();
+
+        let mut opts = DFOpts::empty();
+        opts.pivot = Some(PivotOpts {
+            index: String::from("the \"image\""),
+            columns: String::from("metric"),
+            values: String::from("value"),
+            agg: String::from("first"),
+        });
+        let wide = tabular::transform(df, opts)?;
+
+        assert_eq!(wide.height(), 1);
+        let images = wide.column("the \"image\"")?.str()?;
+        assert_eq!(images.get(0), Some("0000.jpg"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_group_by_aggregates() -> Result<(), OxenError> {
+        let df = df!(
+            "store" => &["north", "south", "north", "south", "north"],
+            "item" => &["apple", "apple", "pear", "apple", "apple"],
+            "amount" => &[1.0, 2.0, 3.0, 4.0, 5.0],
+            "price" => &[10.0, 20.0, 30.0, 40.0, 60.0],
+        )
+        .unwrap();
+
+        let mut opts = DFOpts::empty();
+        opts.group_by = Some(String::from("store,item"));
+        opts.agg = Some(String::from("sum:amount,mean:price"));
+        let grouped = tabular::transform(df, opts)?;
+
+        assert_eq!(
+            grouped.get_column_names(),
+            vec!["store", "item", "sum_amount", "mean_price"]
+        );
+        assert_eq!(grouped.height(), 3);
+
+        let stores = grouped.column("store")?.str()?;
+        let items = grouped.column("item")?.str()?;
+        let sums = grouped.column("sum_amount")?.f64()?;
+        let means = grouped.column("mean_price")?.f64()?;
+        assert_eq!(
+            (stores.get(0), items.get(0)),
+            (Some("north"), Some("apple"))
+        );
+        assert_eq!(sums.get(0), Some(6.0));
+        assert_eq!(means.get(0), Some(35.0));
+        assert_eq!((stores.get(1), items.get(1)), (Some("north"), Some("pear")));
+        assert_eq!(sums.get(1), Some(3.0));
+        assert_eq!(means.get(1), Some(30.0));
+        assert_eq!(
+            (stores.get(2), items.get(2)),
+            (Some("south"), Some("apple"))
+        );
+        assert_eq!(sums.get(2), Some(6.0));
+        assert_eq!(means.get(2), Some(30.0));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_group_by_invalid_aggregation() {
+        let df = df!("label" => &["dog"], "value" => &[1.0]).unwrap();
+
+        let mut opts = DFOpts::empty();
+        opts.group_by = Some(String::from("label"));
+        opts.agg = Some(String::from("explode:value"));
+        assert!(tabular::transform(df, opts).is_err());
+    }
+
+    #[test]
+    fn test_transpose_flips_shape() -> Result<(), OxenError> {
+        let df = df!(
+            "a" => &["1", "2", "3"],
+            "b" => &["4", "5", "6"],
+            "c" => &["7", "8", "9"],
+        )
+        .unwrap();
+
+        let mut opts = DFOpts::empty();
+        opts.transpose = true;
+        let transposed = tabular::transform(df, opts)?;
+
+        // The header becomes the first column, and each row becomes a column
+        assert_eq!(transposed.height(), 3);
+        assert_eq!(transposed.width(), 4);
+        let names = transposed.column("column")?.str()?;
+        assert_eq!(
+            names.into_iter().collect::<Vec<_>>(),
+            vec![Some("a"), Some("b"), Some("c")]
+        );
+        let first_row = transposed.select_at_idx(1).unwrap().str()?;
+        assert_eq!(
+            first_row.into_iter().collect::<Vec<_>>(),
+            vec![Some("1"), Some("4"), Some("7")]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_null_counts() -> Result<(), OxenError> {
+        let df = df!(
+            "label" => &[Some("cat"), None, None, Some("dog")],
+            "score" => &[Some(1.0), None, Some(3.0), Some(4.0)],
+            "file" => &["a.jpg", "b.jpg", "c.jpg", "d.jpg"],
+        )
+        .unwrap();
+
+        let counts = tabular::df_null_counts(&df)?;
+        let columns = counts.column("column")?.str()?;
+        let null_counts = counts.column("null_count")?.u64()?;
+        assert_eq!(
+            columns.into_iter().collect::<Vec<_>>(),
+            vec![Some("label"), Some("score"), Some("file")]
+        );
+        assert_eq!(
+            null_counts.into_iter().collect::<Vec<_>>(),
+            vec![Some(2), Some(1), Some(0)]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_drop_nulls() -> Result<(), OxenError> {
+        let df = df!(
+            "label" => &[Some("cat"), None, None, Some("dog")],
+            "score" => &[Some(1.0), None, Some(3.0), None],
+        )
+        .unwrap();
+
+        // Only check the label column
+        let mut opts = DFOpts::empty();
+        opts.drop_nulls = Some(String::from("label"));
+        let dropped = tabular::transform(df.clone(), opts)?;
+        let labels = dropped.column("label")?.str()?;
+        assert_eq!(
+            labels.into_iter().collect::<Vec<_>>(),
+            vec![Some("cat"), Some("dog")]
+        );
+
+        // Check every column
+        let mut opts = DFOpts::empty();
+        opts.drop_nulls = Some(String::from(""));
+        let dropped = tabular::transform(df.clone(), opts)?;
+        assert_eq!(dropped.height(), 1);
+        assert_eq!(dropped.column("label")?.str()?.get(0), Some("cat"));
+
+        let mut opts = DFOpts::empty();
+        opts.drop_nulls = Some(String::from("missing"));
+        assert!(tabular::transform(df, opts).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_profile_null_rates_and_distinct_counts() -> Result<(), OxenError> {
+        let df = df!(
+            "label" => &[Some("cat"), Some("dog"), None, Some("cat")],
+            "score" => &[Some(1.5), None, None, Some(4.0)],
+            "code" => &["1", "2", "x", "4"],
+        )
+        .unwrap();
+
+        let profile = tabular::df_profile(&df)?;
+        assert_eq!(profile.num_rows, 4);
+
+        let label = profile.column("label").unwrap();
+        assert_eq!(label.null_count, 1);
+        assert_eq!(label.null_rate, 0.25);
+        assert_eq!(label.distinct_count, 2);
+        assert_eq!(label.min.as_deref(), Some("cat"));
+        assert_eq!(label.max.as_deref(), Some("dog"));
+        assert!(label.anomalies.is_empty());
+
+        let score = profile.column("score").unwrap();
+        assert_eq!(score.null_rate, 0.5);
+        assert_eq!(score.distinct_count, 2);
+        assert_eq!(score.min.as_deref(), Some("1.5"));
+        assert_eq!(score.max.as_deref(), Some("4.0"));
+
+        // Three of the codes are numbers and one is not
+        let code = profile.column("code").unwrap();
+        assert_eq!(code.null_rate, 0.0);
+        assert_eq!(code.distinct_count, 4);
+        assert_eq!(code.anomalies.len(), 1);
+        assert!(profile.has_anomalies());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pivot_unknown_col() {
+        let df = df!("label" => &["dog"], "value" => &[1i64]).unwrap();
+
+        let mut opts = DFOpts::empty();
+        opts.pivot = Some(PivotOpts {
+            index: String::from("not_a_col"),
+            columns: String::from("label"),
+            values: String::from("value"),
+            agg: String::from("first"),
+        });
+        assert!(tabular::transform(df, opts).is_err());
+    }
+
+    #[test]
+    fn test_dedup_all_columns_keeps_first_occurrence() -> Result<(), OxenError> {
+        let df = df!(
+            "image" => &["0003.jpg", "0001.jpg", "0003.jpg", "0002.jpg", "0001.jpg"],
+            "label" => &["cat", "dog", "cat", "dog", "cat"],
+        )
+        .unwrap();
+
+        let mut opts = DFOpts::empty();
+        opts.dedup = Some(String::from(""));
+        let deduped = tabular::transform(df, opts)?;
+
+        // Only the exact duplicate of the first row is dropped
+        assert_eq!(deduped.height(), 4);
+        assert_eq!(deduped.get_column_names(), vec!["image", "label"]);
+        let images = deduped.column("image")?.str()?;
+        let labels = deduped.column("label")?.str()?;
+        let rows: Vec<(Option<&str>, Option<&str>)> = images.into_iter().zip(labels).collect();
+        assert_eq!(
+            rows,
+            vec![
+                (Some("0003.jpg"), Some("cat")),
+                (Some("0001.jpg"), Some("dog")),
+                (Some("0002.jpg"), Some("dog")),
+                (Some("0001.jpg"), Some("cat")),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_dedup_subset_columns_keeps_first_occurrence() -> Result<(), OxenError> {
+        let df = df!(
+            "image" => &["0003.jpg", "0001.jpg", "0003.jpg", "0002.jpg", "0001.jpg"],
+            "label" => &["cat", "dog", "cat", "dog", "cat"],
+        )
+        .unwrap();
+
+        let mut opts = DFOpts::empty();
+        opts.dedup = Some(String::from("image"));
+        let deduped = tabular::transform(df, opts)?;
+
+        assert_eq!(deduped.height(), 3);
+        let images = deduped.column("image")?.str()?;
+        let labels = deduped.column("label")?.str()?;
+        let rows: Vec<(Option<&str>, Option<&str>)> = images.into_iter().zip(labels).collect();
+        assert_eq!(
+            rows,
+            vec![
+                (Some("0003.jpg"), Some("cat")),
+                (Some("0001.jpg"), Some("dog")),
+                (Some("0002.jpg"), Some("dog")),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rename_columns_keeps_order() -> Result<(), OxenError> {
+        let df = df!(
+            "image" => &["0001.jpg", "0002.jpg"],
+            "label" => &["cat", "dog"],
+            "score" => &[0.5, 0.9],
+        )
+        .unwrap();
+
+        let mut opts = DFOpts::empty();
+        opts.rename = Some(String::from("image:path,score:confidence"));
+        let renamed = tabular::transform(df, opts)?;
+
+        assert_eq!(
+            renamed.get_column_names(),
+            vec!["path", "label", "confidence"]
+        );
+        assert_eq!(renamed.height(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rename_missing_column_errors() -> Result<(), OxenError> {
+        let df = df!(
+            "image" => &["0001.jpg", "0002.jpg"],
+            "label" => &["cat", "dog"],
+        )
+        .unwrap();
+
+        let mut opts = DFOpts::empty();
+        opts.rename = Some(String::from("file:path"));
+        assert!(tabular::transform(df, opts).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cast_string_column_to_int() -> Result<(), OxenError> {
+        let df = df!(
+            "image" => &["0001.jpg", "0002.jpg", "0003.jpg"],
+            "count" => &["1", "22", "333"],
+        )
+        .unwrap();
+
+        let mut opts = DFOpts::empty();
+        opts.cast = Some(String::from("count:int"));
+        let casted = tabular::transform(df, opts)?;
+
+        assert_eq!(casted.get_column_names(), vec!["image", "count"]);
+        assert_eq!(casted.column("count")?.dtype(), &DataType::Int32);
+        assert_eq!(casted.column("image")?.dtype(), &DataType::String);
+        let counts: Vec<Option<i32>> = casted.column("count")?.i32()?.into_iter().collect();
+        assert_eq!(counts, vec![Some(1), Some(22), Some(333)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_coalesce_fills_nulls_across_columns() -> Result<(), OxenError> {
+        let df = df!(
+            "id" => &[1i64, 2, 3, 4],
+            "a" => &[Some(10i64), None, None, None],
+            "b" => &[Some(20i64), Some(21), None, None],
+            "c" => &[None, Some(31i64), Some(32), None],
+        )
+        .unwrap();
+
+        let mut opts = DFOpts::empty();
+        opts.coalesce = Some(String::from("a,b,c"));
+        opts.coalesce_as = Some(String::from("value"));
+        let coalesced = tabular::transform(df.clone(), opts)?;
+
+        assert_eq!(
+            coalesced.get_column_names(),
+            vec!["id", "a", "b", "c", "value"]
+        );
+        let values: Vec<Option<i64>> = coalesced.column("value")?.i64()?.into_iter().collect();
+        assert_eq!(values, vec![Some(10), Some(21), Some(32), None]);
+
+        // Order picks which value wins, and an existing column is replaced in place
+        let mut opts = DFOpts::empty();
+        opts.coalesce = Some(String::from("c,b,a"));
+        opts.coalesce_as = Some(String::from("a"));
+        let coalesced = tabular::transform(df.clone(), opts)?;
+        assert_eq!(coalesced.get_column_names(), vec!["id", "a", "b", "c"]);
+        let values: Vec<Option<i64>> = coalesced.column("a")?.i64()?.into_iter().collect();
+        assert_eq!(values, vec![Some(20), Some(31), Some(32), None]);
+
+        // Missing columns are an error
+        let mut opts = DFOpts::empty();
+        opts.coalesce = Some(String::from("a,d"));
+        assert!(tabular::transform(df, opts).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_detect_duplicates_reports_groups_and_counts() -> Result<(), OxenError> {
+        let df = df!(
+            "image" => &["0003.jpg", "0001.jpg", "0003.jpg", "0002.jpg", "0001.jpg", "0003.jpg"],
+            "label" => &["cat", "dog", "cat", "dog", "cat", "cat"],
+        )
+        .unwrap();
+
+        // Every column has to match, 0003.jpg/cat is the only duplicated row
+        let mut opts = DFOpts::empty();
+        opts.detect_duplicates = Some(String::from(""));
+        let duplicates = tabular::transform(df.clone(), opts)?;
+        assert_eq!(
+            duplicates.get_column_names(),
+            vec!["image", "label", "duplicate_group", "duplicate_count"]
+        );
+        assert_eq!(duplicates.height(), 3);
+        let groups: Vec<Option<i64>> = duplicates
+            .column("duplicate_group")?
+            .i64()?
+            .into_iter()
+            .collect();
+        assert_eq!(groups, vec![Some(1), Some(1), Some(1)]);
+        let counts: Vec<Option<i64>> = duplicates
+            .column("duplicate_count")?
+            .i64()?
+            .into_iter()
+            .collect();
+        assert_eq!(counts, vec![Some(3), Some(3), Some(3)]);
+
+        // Only comparing the image, groups are numbered by first occurrence
+        let mut opts = DFOpts::empty();
+        opts.detect_duplicates = Some(String::from("image"));
+        let duplicates = tabular::transform(df.clone(), opts)?;
+        let images: Vec<Option<&str>> = duplicates.column("image")?.str()?.into_iter().collect();
+        assert_eq!(
+            images,
+            vec![
+                Some("0003.jpg"),
+                Some("0003.jpg"),
+                Some("0003.jpg"),
+                Some("0001.jpg"),
+                Some("0001.jpg"),
+            ]
+        );
+        let labels: Vec<Option<&str>> = duplicates.column("label")?.str()?.into_iter().collect();
+        assert_eq!(
+            labels,
+            vec![
+                Some("cat"),
+                Some("cat"),
+                Some("cat"),
+                Some("dog"),
+                Some("cat")
+            ]
+        );
+        let groups: Vec<Option<i64>> = duplicates
+            .column("duplicate_group")?
+            .i64()?
+            .into_iter()
+            .collect();
+        assert_eq!(groups, vec![Some(1), Some(1), Some(1), Some(2), Some(2)]);
+        let counts: Vec<Option<i64>> = duplicates
+            .column("duplicate_count")?
+            .i64()?
+            .into_iter()
+            .collect();
+        assert_eq!(counts, vec![Some(3), Some(3), Some(3), Some(2), Some(2)]);
+
+        // Missing columns are an error
+        let mut opts = DFOpts::empty();
+        opts.detect_duplicates = Some(String::from("path"));
+        assert!(tabular::transform(df, opts).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_normalize_minmax_and_zscore() -> Result<(), OxenError> {
+        let df = df!(
+            "id" => &[1i64, 2, 3, 4],
+            "price" => &[10.0f64, 20.0, 30.0, 50.0],
+            "age" => &[20i64, 30, 40, 50],
+        )
+        .unwrap();
+
+        let mut opts = DFOpts::empty();
+        opts.normalize = Some(String::from("minmax:price,zscore:age"));
+        let normalized = tabular::transform(df.clone(), opts)?;
+
+        assert_eq!(normalized.get_column_names(), vec!["id", "price", "age"]);
+        let prices: Vec<f64> = normalized
+            .column("price")?
+            .f64()?
+            .into_no_null_iter()
+            .collect();
+        assert!(prices.iter().all(|p| (0.0..=1.0).contains(p)));
+        assert_eq!(prices, vec![0.0, 0.25, 0.5, 1.0]);
+        let ages: Vec<f64> = normalized
+            .column("age")?
+            .f64()?
+            .into_no_null_iter()
+            .collect();
+        assert!(ages.iter().sum::<f64>().abs() < 1e-9);
+        assert!((ages[3] - 1.161895003862225).abs() < 1e-9);
+
+        // Appending keeps the original column next to the scaled one
+        let mut opts = DFOpts::empty();
+        opts.normalize = Some(String::from("minmax:price"));
+        opts.normalize_append = true;
+        let normalized = tabular::transform(df.clone(), opts)?;
+        assert_eq!(
+            normalized.get_column_names(),
+            vec!["id", "price", "age", "price_minmax"]
+        );
+        let prices: Vec<f64> = normalized
+            .column("price")?
+            .f64()?
+            .into_no_null_iter()
+            .collect();
+        assert_eq!(prices, vec![10.0, 20.0, 30.0, 50.0]);
+
+        // Unknown methods and missing columns are errors
+        let mut opts = DFOpts::empty();
+        opts.normalize = Some(String::from("log:price"));
+        assert!(tabular::transform(df.clone(), opts).is_err());
+        let mut opts = DFOpts::empty();
+        opts.normalize = Some(String::from("minmax:missing"));
+        assert!(tabular::transform(df, opts).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fuzzy_join_matches_names_with_typos() -> Result<(), OxenError> {
+        test::run_empty_dir_test(|dir| {
+            let people = dir.join("people.csv");
+            let cities = dir.join("cities.csv");
+            util::fs::write_to_path(
+                &people,
+                "name,age\nJonathan Smith,34\nMary Jones,28\nRobert Brown,51\n",
+            )?;
+            util::fs::write_to_path(
+                &cities,
+                "name,city\nJonathon Smith,Boston\nMary Jone,Denver\nAlice Green,Austin\n",
+            )?;
+
+            let mut opts = DFOpts::empty();
+            opts.fuzzy_join = Some(cities.clone());
+            opts.fuzzy_on = Some(String::from("name"));
+            opts.fuzzy_threshold = Some(0.9);
+            let df = tabular::read_df(&people, opts.clone())?;
+
+            assert_eq!(
+                df.get_column_names(),
+                vec!["name", "age", "name_right", "city", "similarity"]
+            );
+            let names = df.column("name")?.str()?;
+            let matches = df.column("name_right")?.str()?;
+            let pairs: Vec<(Option<&str>, Option<&str>)> = names.into_iter().zip(matches).collect();
+            assert_eq!(
+                pairs,
+                vec![
+                    (Some("Jonathan Smith"), Some("Jonathon Smith")),
+                    (Some("Mary Jones"), Some("Mary Jone")),
+                ]
+            );
+            for similarity in df.column("similarity")?.f64()?.into_iter() {
+                assert!(similarity.unwrap() >= 0.9);
+            }
+
+            // A column to join on is required
+            opts.fuzzy_on = None;
+            assert!(tabular::read_df(&people, opts).is_err());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_cast_non_castable_value_errors() -> Result<(), OxenError> {
+        let df = df!(
+            "image" => &["0001.jpg", "0002.jpg"],
+            "count" => &["1", "lots"],
+        )
+        .unwrap();
+
+        let mut opts = DFOpts::empty();
+        opts.cast = Some(String::from("count:int"));
+        let result = tabular::transform(df, opts);
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("lots"), "error should name the value: {err}");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_aggregate_over_dirs_sums_across_files() -> Result<(), OxenError> {
+        test::run_empty_dir_test(|dir| {
+            let data_dir = dir.join("sales");
+            util::fs::create_dir_all(data_dir.join("q1"))?;
+            util::fs::write_to_path(
+                data_dir.join("january.csv"),
+                "store,amount\nnorth,1.5\nsouth,2.0\n",
+            )?;
+            util::fs::write_to_path(
+                data_dir.join("february.csv"),
+                "amount,store\n3.0,north\n4.5,north\n",
+            )?;
+            util::fs::write_to_path(
+                data_dir.join("q1").join("march.csv"),
+                "store,amount\nsouth,5.0\neast,6.0\n",
+            )?;
+            util::fs::write_to_path(data_dir.join("README.md"), "Not tabular")?;
+
+            let mut opts = DFOpts::empty();
+            opts.group_by = Some(String::from("store"));
+            opts.agg = Some(String::from("sum:amount,count:amount"));
+            opts.aggregate_over_dirs = true;
+            let df = tabular::read_df(&data_dir, opts)?;
+
+            assert_eq!(
+                df.get_column_names(),
+                vec!["store", "sum_amount", "count_amount"]
+            );
+            assert_eq!(df.height(), 3);
+            let stores = df.column("store")?.str()?;
+            let sums = df.column("sum_amount")?.f64()?;
+            let counts = df.column("count_amount")?.cast(&DataType::Int64)?;
+            let counts = counts.i64()?;
+            assert_eq!(stores.get(0), Some("east"));
+            assert_eq!(sums.get(0), Some(6.0));
+            assert_eq!(counts.get(0), Some(1));
+            assert_eq!(stores.get(1), Some("north"));
+            assert_eq!(sums.get(1), Some(9.0));
+            assert_eq!(counts.get(1), Some(3));
+            assert_eq!(stores.get(2), Some("south"));
+            assert_eq!(sums.get(2), Some(7.0));
+            assert_eq!(counts.get(2), Some(2));
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_where_in_filters_by_large_value_list() -> Result<(), OxenError> {
+        test::run_empty_dir_test(|dir| {
+            let ids: Vec<i64> = (0..20_000).collect();
+            let labels: Vec<String> = ids.iter().map(|id| format!("label_{id}")).collect();
+            let df = df!("id" => &ids, "label" => &labels).unwrap();
+
+            // 10k values, half of them match every other row, the rest match nothing
+            let values_path = dir.join("ids.txt");
+            let values: Vec<String> = (0..5_000)
+                .map(|i| (i * 4).to_string())
+                .chain((0..5_000).map(|i| (100_000 + i).to_string()))
+                .collect();
+            util::fs::write_to_path(&values_path, values.join("\n"))?;
+
+            let mut opts = DFOpts::empty();
+            opts.where_in = Some(format!("id:{}", values_path.to_string_lossy()));
+            let filtered = tabular::transform(df, opts)?;
+
+            assert_eq!(filtered.height(), 5_000);
+            assert_eq!(filtered.get_column_names(), vec!["id", "label"]);
+            let filtered_ids = filtered.column("id")?.i64()?;
+            let expected: Vec<Option<i64>> = (0..5_000).map(|i| Some(i * 4)).collect();
+            assert_eq!(filtered_ids.into_iter().collect::<Vec<_>>(), expected);
+            let filtered_labels = filtered.column("label")?.str()?;
+            assert_eq!(filtered_labels.get(1), Some("label_4"));
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_concat_csvs_aligns_columns_by_name() -> Result<(), OxenError> {
+        test::run_empty_dir_test(|dir| {
+            // Same columns, different orders
+            let first = dir.join("first.csv");
+            let second = dir.join("second.csv");
+            let third = dir.join("third.csv");
+            util::fs::write_to_path(&first, "image,label\n0001.jpg,cat\n0002.jpg,dog\n")?;
+            util::fs::write_to_path(&second, "label,image\ndog,0003.jpg\n")?;
+            util::fs::write_to_path(
+                &third,
+                "image,label\n0004.jpg,cat\n0005.jpg,cat\n0006.jpg,dog\n",
+            )?;
+
+            let mut opts = DFOpts::empty();
+            opts.concat = Some(vec![second, third]);
+            let df = tabular::read_df(&first, opts)?;
+
+            assert_eq!(df.height(), 2 + 1 + 3);
+            assert_eq!(df.get_column_names(), vec!["image", "label"]);
+            let images = df.column("image")?.str()?;
+            let labels = df.column("label")?.str()?;
+            let rows: Vec<(Option<&str>, Option<&str>)> = images.into_iter().zip(labels).collect();
+            assert!(rows.contains(&(Some("0003.jpg"), Some("dog"))));
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_concat_incompatible_schemas_needs_coerce() -> Result<(), OxenError> {
+        test::run_empty_dir_test(|dir| {
+            let first = dir.join("first.csv");
+            let second = dir.join("second.csv");
+            util::fs::write_to_path(&first, "image,score\n0001.jpg,1\n")?;
+            util::fs::write_to_path(&second, "image,score\n0002.jpg,high\n")?;
+
+            let mut opts = DFOpts::empty();
+            opts.concat = Some(vec![second.clone()]);
+            assert!(tabular::read_df(&first, opts.clone()).is_err());
+
+            opts.coerce = true;
+            let df = tabular::read_df(&first, opts)?;
+            assert_eq!(df.height(), 2);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_concat_merge_schema_unions_columns() -> Result<(), OxenError> {
+        test::run_empty_dir_test(|dir| {
+            let first = dir.join("first.csv");
+            let second = dir.join("second.csv");
+            util::fs::write_to_path(&first, "image,label\n0001.jpg,cat\n0002.jpg,dog\n")?;
+            util::fs::write_to_path(&second, "image,width\n0003.jpg,64\n")?;
+
+            // Different columns need a mode that allows it
+            let mut opts = DFOpts::empty();
+            opts.concat = Some(vec![second.clone()]);
+            assert!(tabular::read_df(&first, opts.clone()).is_err());
+
+            opts.merge_schema = true;
+            let df = tabular::read_df(&first, opts)?;
+            assert_eq!(df.height(), 3);
+            assert_eq!(df.get_column_names(), vec!["image", "label", "width"]);
+
+            let images = df.column("image")?.str()?;
+            let labels = df.column("label")?;
+            let widths = df.column("width")?;
+            for (i, image) in images.into_iter().enumerate() {
+                match image {
+                    Some("0003.jpg") => {
+                        assert_eq!(labels.get(i)?, AnyValue::Null);
+                        assert_ne!(widths.get(i)?, AnyValue::Null);
+                    }
+                    _ => {
+                        assert_ne!(labels.get(i)?, AnyValue::Null);
+                        assert_eq!(widths.get(i)?, AnyValue::Null);
+                    }
+                }
+            }
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_concat_merge_schema_rejects_mismatched_types() -> Result<(), OxenError> {
+        test::run_empty_dir_test(|dir| {
+            let first = dir.join("first.csv");
+            let second = dir.join("second.csv");
+            util::fs::write_to_path(&first, "image,score\n0001.jpg,1\n")?;
+            util::fs::write_to_path(&second, "image,score,label\n0002.jpg,high,cat\n")?;
+
+            let mut opts = DFOpts::empty();
+            opts.concat = Some(vec![second]);
+            opts.merge_schema = true;
+            assert!(tabular::read_df(&first, opts).is_err());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_window_rolling_sum_ordered() -> Result<(), OxenError> {
+        // Out of order so the window has to follow --order-by
+        let df = df!(
+            "day" => &[3, 1, 4, 2, 5],
+            "price" => &[30.0, 10.0, 40.0, 20.0, 50.0]
+        )?;
+
+        let mut opts = DFOpts::empty();
+        opts.window = Some(String::from("sum:price:3,mean:price:2"));
+        opts.order_by = Some(String::from("day"));
+        let df = tabular::transform(df, opts)?;
+
+        assert_eq!(
+            df.get_column_names(),
+            vec!["day", "price", "sum_price_3", "mean_price_2"]
+        );
+        let days: Vec<Option<i32>> = df.column("day")?.i32()?.into_iter().collect();
+        assert_eq!(days, vec![Some(1), Some(2), Some(3), Some(4), Some(5)]);
+        let sums: Vec<Option<f64>> = df.column("sum_price_3")?.f64()?.into_iter().collect();
+        assert_eq!(
+            sums,
+            vec![Some(10.0), Some(30.0), Some(60.0), Some(90.0), Some(120.0)]
+        );
+        let means: Vec<Option<f64>> = df.column("mean_price_2")?.f64()?.into_iter().collect();
+        assert_eq!(
+            means,
+            vec![Some(10.0), Some(15.0), Some(25.0), Some(35.0), Some(45.0)]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_window_invalid_spec() {
+        let df = df!("price" => &[1, 2, 3]).unwrap();
+
+        let mut opts = DFOpts::empty();
+        opts.window = Some(String::from("sum:price"));
+        assert!(tabular::transform(df.clone(), opts.clone()).is_err());
+
+        opts.window = Some(String::from("sum:price:0"));
+        assert!(tabular::transform(df.clone(), opts.clone()).is_err());
+
+        opts.window = Some(String::from("sum:not_a_col:2"));
+        assert!(tabular::transform(df, opts).is_err());
+    }
+
+    #[test]
+    fn test_dedup_unknown_col() {
+        let df = df!("label" => &["dog"]).unwrap();
+
+        let mut opts = DFOpts::empty();
+        opts.dedup = Some(String::from("not_a_col"));
+        assert!(tabular::transform(df, opts).is_err());
+    }
+
     #[test]
     fn test_read_json() -> Result<(), OxenError> {
         let df = tabular::read_df_json("data/test/text/test.json")?;
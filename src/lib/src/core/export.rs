@@ -0,0 +1,56 @@
+//! Export the metadata of a committed dataset into the schemas other tools understand
+//!
+
+pub mod croissant;
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::OxenError;
+use crate::model::{Commit, LocalRepository};
+
+pub use croissant::CroissantExporter;
+
+/// Serializes the files, schemas and splits of a commit into a metadata document
+pub trait MetadataExporter {
+    fn export(&self, repo: &LocalRepository, commit: &Commit) -> Result<String, OxenError>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    // https://mlcommons.org/croissant
+    Croissant,
+}
+
+impl ExportFormat {
+    pub fn exporter(&self) -> Box<dyn MetadataExporter> {
+        match self {
+            ExportFormat::Croissant => Box::new(CroissantExporter),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExportFormat::Croissant => "croissant",
+        }
+    }
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = OxenError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "croissant" => Ok(ExportFormat::Croissant),
+            _ => Err(OxenError::basic_str(format!(
+                "Unsupported export format '{s}', must be one of: croissant"
+            ))),
+        }
+    }
+}
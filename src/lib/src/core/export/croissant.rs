@@ -0,0 +1,338 @@
+//! Describes a commit as an MLCommons Croissant dataset
+//!
+//! Every committed file becomes a `cr:FileObject` in the distribution, every tabular file with a
+//! schema becomes a `cr:RecordSet` with one `cr:Field` per column, and directories or files named
+//! after a split (train, test, validation...) are listed in a `cr:Split` record set.
+//!
+
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+
+use serde_json::{json, Value};
+
+use super::MetadataExporter;
+use crate::core::index::{CommitEntryReader, SchemaReader};
+use crate::error::OxenError;
+use crate::model::schema::DataType;
+use crate::model::{Commit, CommitEntry, LocalRepository, Schema};
+use crate::util;
+
+pub const CROISSANT_CONFORMS_TO: &str = "http://mlcommons.org/croissant/1.0";
+
+// Record set holding the split names
+const SPLITS_RECORD_SET: &str = "splits";
+
+const SPLIT_NAMES: [&str; 8] = [
+    "train",
+    "training",
+    "test",
+    "testing",
+    "val",
+    "valid",
+    "validation",
+    "dev",
+];
+
+pub struct CroissantExporter;
+
+impl MetadataExporter for CroissantExporter {
+    fn export(&self, repo: &LocalRepository, commit: &Commit) -> Result<String, OxenError> {
+        let metadata = dataset(repo, commit)?;
+        Ok(serde_json::to_string_pretty(&metadata)?)
+    }
+}
+
+/// Build the Croissant JSON-LD document for the commit
+pub fn dataset(repo: &LocalRepository, commit: &Commit) -> Result<Value, OxenError> {
+    let reader = CommitEntryReader::new(repo, commit)?;
+    let mut entries = reader.list_entries()?;
+    entries.sort_by(|a, b| a.path.cmp(&b.path));
+    let schemas = SchemaReader::new(repo, &commit.id)?.list_schemas()?;
+
+    let mut distribution: Vec<Value> = vec![];
+    for entry in entries.iter() {
+        distribution.push(file_object(repo, entry)?);
+    }
+
+    let mut record_sets: Vec<Value> = entries
+        .iter()
+        .filter_map(|entry| {
+            schemas
+                .get(&entry.path)
+                .map(|schema| record_set(&entry.path, schema))
+        })
+        .collect();
+
+    let splits = splits(&entries);
+    if !splits.is_empty() {
+        record_sets.push(splits_record_set(&splits));
+    }
+
+    let date_published = commit
+        .timestamp
+        .format(&time::format_description::well_known::Rfc3339)
+        .map_err(|err| OxenError::basic_str(format!("Could not format commit date: {err}")))?;
+
+    Ok(json!({
+        "@context": context(),
+        "@type": "sc:Dataset",
+        "conformsTo": CROISSANT_CONFORMS_TO,
+        "name": repo.dirname(),
+        "description": commit.message,
+        "version": commit.id,
+        "datePublished": date_published,
+        "creator": {
+            "@type": "sc:Person",
+            "name": commit.author,
+            "email": commit.email,
+        },
+        "distribution": distribution,
+        "recordSet": record_sets,
+    }))
+}
+
+fn file_object(repo: &LocalRepository, entry: &CommitEntry) -> Result<Value, OxenError> {
+    let path = path_id(&entry.path);
+    let version_path = util::fs::version_path(repo, entry);
+    Ok(json!({
+        "@type": "cr:FileObject",
+        "@id": path,
+        "name": path,
+        "contentUrl": path,
+        "contentSize": format!("{} B", entry.num_bytes),
+        "encodingFormat": encoding_format(&entry.path, &version_path),
+        "sha256": util::hasher::hash_file_sha256(&version_path)?,
+    }))
+}
+
+fn record_set(path: &Path, schema: &Schema) -> Value {
+    let path = path_id(path);
+    let fields: Vec<Value> = schema
+        .fields
+        .iter()
+        .map(|field| {
+            json!({
+                "@type": "cr:Field",
+                "@id": format!("{path}/{}", field.name),
+                "name": field.name,
+                "dataType": data_type(&field.dtype),
+                "source": {
+                    "fileObject": { "@id": path },
+                    "extract": { "column": field.name },
+                },
+            })
+        })
+        .collect();
+
+    json!({
+        "@type": "cr:RecordSet",
+        "@id": path,
+        "name": path,
+        "field": fields,
+    })
+}
+
+fn splits_record_set(splits: &BTreeSet<String>) -> Value {
+    let name_field = format!("{SPLITS_RECORD_SET}/name");
+    let data: Vec<Value> = splits
+        .iter()
+        .map(|split| {
+            let mut row = serde_json::Map::new();
+            row.insert(name_field.clone(), json!(split));
+            Value::Object(row)
+        })
+        .collect();
+
+    json!({
+        "@type": "cr:RecordSet",
+        "@id": SPLITS_RECORD_SET,
+        "name": SPLITS_RECORD_SET,
+        "dataType": "cr:Split",
+        "key": { "@id": name_field },
+        "field": [{
+            "@type": "cr:Field",
+            "@id": name_field,
+            "name": "name",
+            "dataType": "sc:Text",
+        }],
+        "data": data,
+    })
+}
+
+/// Split names found in the directory or file names of the entries
+fn splits(entries: &[CommitEntry]) -> BTreeSet<String> {
+    let mut splits = BTreeSet::new();
+    for entry in entries.iter() {
+        let stem = entry.path.with_extension("");
+        for component in stem.iter() {
+            let name = component.to_string_lossy().to_lowercase();
+            if SPLIT_NAMES.contains(&name.as_str()) {
+                splits.insert(name);
+            }
+        }
+    }
+    splits
+}
+
+fn data_type(dtype: &str) -> &'static str {
+    match DataType::from_string(dtype) {
+        DataType::Boolean => "sc:Boolean",
+        DataType::UInt8
+        | DataType::UInt16
+        | DataType::UInt32
+        | DataType::UInt64
+        | DataType::Int8
+        | DataType::Int16
+        | DataType::Int32
+        | DataType::Int64 => "sc:Integer",
+        DataType::Float32 | DataType::Float64 => "sc:Float",
+        DataType::Date => "sc:Date",
+        DataType::Time => "sc:Time",
+        _ => "sc:Text",
+    }
+}
+
+fn encoding_format(path: &Path, version_path: &Path) -> String {
+    let extension = util::fs::extension_from_path(path).to_lowercase();
+    let known: HashMap<&str, &str> = HashMap::from([
+        ("csv", "text/csv"),
+        ("tsv", "text/tab-separated-values"),
+        ("parquet", "application/x-parquet"),
+        ("arrow", "application/vnd.apache.arrow.file"),
+        ("json", "application/json"),
+        ("jsonl", "application/jsonlines"),
+        ("ndjson", "application/jsonlines"),
+    ]);
+    match known.get(extension.as_str()) {
+        Some(mime_type) => mime_type.to_string(),
+        None => util::fs::file_mime_type(version_path),
+    }
+}
+
+// Croissant ids use forward slashes on every platform
+fn path_id(path: &Path) -> String {
+    path.iter()
+        .map(|component| component.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn context() -> Value {
+    json!({
+        "@language": "en",
+        "@vocab": "https://schema.org/",
+        "citeAs": "cr:citeAs",
+        "column": "cr:column",
+        "conformsTo": "dct:conformsTo",
+        "cr": "http://mlcommons.org/croissant/",
+        "data": { "@id": "cr:data", "@type": "@json" },
+        "dataType": { "@id": "cr:dataType", "@type": "@vocab" },
+        "dct": "http://purl.org/dc/terms/",
+        "examples": { "@id": "cr:examples", "@type": "@json" },
+        "extract": "cr:extract",
+        "field": "cr:field",
+        "fileObject": "cr:fileObject",
+        "fileProperty": "cr:fileProperty",
+        "fileSet": "cr:fileSet",
+        "format": "cr:format",
+        "includes": "cr:includes",
+        "isLiveDataset": "cr:isLiveDataset",
+        "jsonPath": "cr:jsonPath",
+        "key": "cr:key",
+        "md5": "cr:md5",
+        "parentField": "cr:parentField",
+        "path": "cr:path",
+        "recordSet": "cr:recordSet",
+        "references": "cr:references",
+        "regex": "cr:regex",
+        "repeated": "cr:repeated",
+        "replace": "cr:replace",
+        "sc": "https://schema.org/",
+        "separator": "cr:separator",
+        "source": "cr:source",
+        "subField": "cr:subField",
+        "transform": "cr:transform",
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::command;
+    use crate::core::export::{ExportFormat, MetadataExporter};
+    use crate::error::OxenError;
+    use crate::test;
+    use crate::util;
+
+    #[test]
+    fn test_export_croissant_record_sets_and_fields() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let train_dir = repo.path.join("train");
+            let test_dir = repo.path.join("test");
+            util::fs::create_dir_all(&train_dir)?;
+            util::fs::create_dir_all(&test_dir)?;
+            test::write_txt_file_to_path(
+                train_dir.join("data.csv"),
+                "text,label,score\nhello,1,0.5\nworld,0,0.25\n",
+            )?;
+            test::write_txt_file_to_path(
+                test_dir.join("data.csv"),
+                "text,label,score\nmoo,1,0.75\n",
+            )?;
+            test::write_txt_file_to_path(repo.path.join("README.md"), "# Sentiment\n")?;
+            command::add(&repo, &repo.path)?;
+            let commit = command::commit(&repo, "Adding the splits")?;
+
+            let exported = ExportFormat::Croissant.exporter().export(&repo, &commit)?;
+            let metadata: serde_json::Value = serde_json::from_str(&exported)?;
+
+            assert_eq!(metadata["@type"], "sc:Dataset");
+            assert_eq!(metadata["conformsTo"], super::CROISSANT_CONFORMS_TO);
+            assert_eq!(metadata["version"], commit.id);
+            assert!(metadata["@context"].is_object());
+
+            let distribution = metadata["distribution"].as_array().unwrap();
+            assert_eq!(distribution.len(), 3);
+            for file in distribution.iter() {
+                assert_eq!(file["@type"], "cr:FileObject");
+                assert!(file["contentUrl"].is_string());
+                assert_eq!(file["sha256"].as_str().unwrap().len(), 64);
+            }
+
+            let record_sets = metadata["recordSet"].as_array().unwrap();
+            let train = record_sets
+                .iter()
+                .find(|record_set| record_set["@id"] == "train/data.csv")
+                .unwrap();
+            let fields = train["field"].as_array().unwrap();
+            let names: Vec<&str> = fields
+                .iter()
+                .map(|field| field["name"].as_str().unwrap())
+                .collect();
+            assert_eq!(names, vec!["text", "label", "score"]);
+            assert_eq!(fields[0]["dataType"], "sc:Text");
+            assert_eq!(fields[1]["dataType"], "sc:Integer");
+            assert_eq!(fields[2]["dataType"], "sc:Float");
+            assert_eq!(fields[1]["source"]["fileObject"]["@id"], "train/data.csv");
+            assert_eq!(fields[1]["source"]["extract"]["column"], "label");
+
+            // The README has no schema so it is only in the distribution
+            assert!(!record_sets
+                .iter()
+                .any(|record_set| record_set["@id"] == "README.md"));
+
+            let splits = record_sets
+                .iter()
+                .find(|record_set| record_set["dataType"] == "cr:Split")
+                .unwrap();
+            let split_names: Vec<&str> = splits["data"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|row| row["splits/name"].as_str().unwrap())
+                .collect();
+            assert_eq!(split_names, vec!["test", "train"]);
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,79 @@
+//! Run the user scripts in .oxen/hooks, similar to git hooks
+//!
+//! Hooks are opt-in with `oxen config --hooks true` so that cloning a repo never
+//! runs scripts by surprise.
+//!
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::constants::HOOKS_DIR;
+use crate::error::OxenError;
+use crate::model::LocalRepository;
+use crate::util;
+
+/// Directory the hook scripts live in, .oxen/hooks
+pub fn hooks_dir(repo: &LocalRepository) -> PathBuf {
+    util::fs::oxen_hidden_dir(&repo.path).join(HOOKS_DIR)
+}
+
+/// Run the hook `name` if hooks are enabled and the script exists and is executable.
+/// The staged paths are passed newline separated on stdin, which has no size limit unlike an env var.
+/// Errors if the hook exits nonzero.
+pub fn run(repo: &LocalRepository, name: &str, staged_files: &[PathBuf]) -> Result<(), OxenError> {
+    if !repo.hooks_enabled() {
+        return Ok(());
+    }
+
+    let path = hooks_dir(repo).join(name);
+    if !path.exists() {
+        return Ok(());
+    }
+    if !is_executable(&path) {
+        log::warn!("Skipping {name} hook, {path:?} is not executable");
+        return Ok(());
+    }
+
+    let staged = staged_files
+        .iter()
+        .map(|path| path.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    log::debug!("Running {name} hook {path:?}");
+    let mut child = Command::new(&path)
+        .current_dir(&repo.path)
+        .env("OXEN_HOOK", name)
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|err| OxenError::basic_str(format!("Could not run {name} hook: {err}")))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // The hook may exit without reading stdin, which is fine
+        if let Err(err) = writeln!(stdin, "{staged}") {
+            log::debug!("{name} hook did not read stdin: {err}");
+        }
+    }
+
+    let status = child.wait()?;
+    if !status.success() {
+        return Err(OxenError::basic_str(format!(
+            "{name} hook exited with {status}"
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path)
+        .map(|metadata| metadata.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
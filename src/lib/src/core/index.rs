@@ -5,9 +5,11 @@ pub mod commit_entry_writer;
 pub mod commit_metadata_db;
 pub mod commit_reader;
 pub mod commit_sync_status;
+pub mod commit_tarball;
 pub mod commit_validator;
 pub mod commit_writer;
 pub mod entry_indexer;
+pub mod idempotency_keys;
 pub mod legacy_commit_dir_entry_reader;
 pub mod legacy_commit_entry_reader;
 pub mod legacy_schema_reader;
@@ -17,12 +19,15 @@ pub mod merge_conflict_writer;
 pub mod merger;
 pub mod mod_stager;
 pub mod object_db_reader;
+pub mod oxenattributes;
 pub mod oxenignore;
+pub mod placeholders;
 pub mod puller;
 pub mod pusher;
 pub mod ref_db_reader;
 pub mod ref_reader;
 pub mod ref_writer;
+pub mod reflog;
 pub mod remote_df_stager;
 pub mod remote_dir_stager;
 pub mod remote_stager;
@@ -47,6 +47,11 @@ impl CommitReader {
         CommitDBReader::list_all(&self.db)
     }
 
+    /// Sequence number of the most recent write to the commit db, changes whenever a commit is added
+    pub fn sequence_number(&self) -> u64 {
+        self.db.latest_sequence_number()
+    }
+
     /// Return the latest commit by timestamp
     pub fn latest_commit(&self) -> Result<Commit, OxenError> {
         CommitDBReader::latest_commit(&self.db)
@@ -1,16 +1,20 @@
 use crate::constants;
 use crate::error::OxenError;
 use crate::model::{Commit, LocalRepository};
+use crate::util;
 
-use std::path::PathBuf;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
 
+/// A commit is synced once all of its versions are downloaded, so one pulled with files left as
+/// placeholders is not synced until they are materialized
 pub fn commit_is_synced(repo: &LocalRepository, commit: &Commit) -> bool {
     let is_synced_path = commit_is_synced_file_path(repo, commit);
     log::debug!("Checking if commit is synced: {is_synced_path:?}");
     match std::fs::read_to_string(&is_synced_path) {
         Ok(value) => {
             log::debug!("Is synced value: {value}");
-            "true" == value
+            "true" == value && !commit_placeholders_file_path(repo, commit).exists()
         }
         Err(err) => {
             log::debug!("Could not read is_synced file {is_synced_path:?}: {}", err);
@@ -42,9 +46,69 @@ pub fn mark_commit_as_synced(repo: &LocalRepository, commit: &Commit) -> Result<
     }
 }
 
+/// Record the paths in `commit` that were left as placeholders, their versions were not downloaded
+pub fn add_placeholders(
+    repo: &LocalRepository,
+    commit: &Commit,
+    paths: &[PathBuf],
+) -> Result<(), OxenError> {
+    if paths.is_empty() {
+        return Ok(());
+    }
+    let mut placeholders = list_placeholders(repo, commit)?;
+    placeholders.extend(paths.iter().cloned());
+    write_placeholders(repo, commit, &placeholders)
+}
+
+/// Forget the placeholders in `commit` whose versions have since been downloaded
+pub fn remove_placeholders(
+    repo: &LocalRepository,
+    commit: &Commit,
+    paths: &[PathBuf],
+) -> Result<(), OxenError> {
+    let mut placeholders = list_placeholders(repo, commit)?;
+    for path in paths.iter() {
+        placeholders.remove(path);
+    }
+    write_placeholders(repo, commit, &placeholders)
+}
+
+fn list_placeholders(
+    repo: &LocalRepository,
+    commit: &Commit,
+) -> Result<BTreeSet<PathBuf>, OxenError> {
+    let path = commit_placeholders_file_path(repo, commit);
+    if !path.exists() {
+        return Ok(BTreeSet::new());
+    }
+    let contents = util::fs::read_from_path(&path)?;
+    Ok(serde_json::from_str(&contents)?)
+}
+
+fn write_placeholders(
+    repo: &LocalRepository,
+    commit: &Commit,
+    placeholders: &BTreeSet<PathBuf>,
+) -> Result<(), OxenError> {
+    let path = commit_placeholders_file_path(repo, commit);
+    if placeholders.is_empty() {
+        if path.exists() {
+            util::fs::remove_file(&path)?;
+        }
+        return Ok(());
+    }
+    if let Some(parent) = path.parent() {
+        util::fs::create_dir_all(parent)?;
+    }
+    util::fs::write_to_path(&path, serde_json::to_string(placeholders)?)
+}
+
+fn commit_placeholders_file_path(repo: &LocalRepository, commit: &Commit) -> PathBuf {
+    commit_is_synced_file_path(repo, commit).with_file_name(Path::new(constants::PLACEHOLDERS_FILE))
+}
+
 fn commit_is_synced_file_path(repo: &LocalRepository, commit: &Commit) -> PathBuf {
-    repo.path
-        .join(constants::OXEN_HIDDEN_DIR)
+    util::fs::oxen_hidden_dir(&repo.path)
         .join(constants::SYNC_STATUS_DIR)
         .join(constants::COMMITS_DIR)
         .join(&commit.id)
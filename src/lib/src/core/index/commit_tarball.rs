@@ -0,0 +1,320 @@
+//! # Commit tarballs
+//!
+//! A gzipped tarball of everything needed to load a single commit into another repository:
+//! the commit object, its history dbs, the tree objects reachable from the commit and the version
+//! files added in the commit. Loading the tarballs of a branch backs up a repository one commit
+//! at a time, tarballs can be loaded in any order.
+//!
+
+use std::collections::HashSet;
+use std::io::{Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use rocksdb::{DBWithThreadMode, MultiThreaded};
+use serde::{Deserialize, Serialize};
+
+use crate::api;
+use crate::constants::{
+    DIRS_DIR, DIR_HASHES_DIR, HISTORY_DIR, OBJECTS_DIR, OBJECT_DIRS_DIR, OBJECT_FILES_DIR,
+    OBJECT_SCHEMAS_DIR, OBJECT_VNODES_DIR, VERSIONS_DIR,
+};
+use crate::core::db::tree_db::{self, TreeObject, TreeObjectChild};
+use crate::core::db::{self, path_db};
+use crate::core::index::{commit_sync_status, CommitEntryReader, CommitWriter, ObjectDBReader};
+use crate::error::OxenError;
+use crate::model::{Commit, LocalRepository};
+use crate::util;
+
+/// Name of the commit json at the root of the tarball, it is always the first entry
+pub const COMMIT_FILE: &str = "commit.json";
+
+/// Name of the json holding the commit's tree objects, every other path is relative to .oxen
+pub const OBJECTS_FILE: &str = "objects.json";
+
+/// The tree objects reachable from a commit, by objects db
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct CommitObjects {
+    dirs: Vec<TreeObject>,
+    vnodes: Vec<TreeObject>,
+    files: Vec<TreeObject>,
+    schemas: Vec<TreeObject>,
+}
+
+/// Write the tarball for `commit` to `writer`, returning the writer once the gzip stream is finished
+pub fn write<W: Write>(repo: &LocalRepository, commit: &Commit, writer: W) -> Result<W, OxenError> {
+    let oxen_dir = util::fs::oxen_hidden_dir(&repo.path);
+    let enc = GzEncoder::new(writer, Compression::default());
+    let mut tar = tar::Builder::new(enc);
+
+    append_json(&mut tar, COMMIT_FILE, commit)?;
+    append_json(&mut tar, OBJECTS_FILE, &commit_objects(repo, commit)?)?;
+
+    let history_dir = Path::new(HISTORY_DIR).join(&commit.id);
+    for dir in [DIRS_DIR, DIR_HASHES_DIR] {
+        let tar_path = history_dir.join(dir);
+        let full_path = oxen_dir.join(&tar_path);
+        if full_path.exists() {
+            tar.append_dir_all(&tar_path, full_path)?;
+        }
+    }
+
+    let version_paths = added_version_paths(repo, commit)?;
+    log::debug!(
+        "commit_tarball::write {} with {} version files",
+        commit.id,
+        version_paths.len()
+    );
+    for version_path in version_paths {
+        if !version_path.exists() {
+            return Err(OxenError::basic_str(format!(
+                "Could not write tarball for commit {}, missing version file {:?}",
+                commit.id, version_path
+            )));
+        }
+        let tar_path = util::fs::path_relative_to_dir(&version_path, &oxen_dir)?;
+        tar.append_path_with_name(&version_path, tar_path)?;
+    }
+
+    tar.finish()?;
+    Ok(tar.into_inner()?.finish()?)
+}
+
+/// Unpack a tarball written by [`write`] into `repo` and add the commit to its history.
+/// Only the commit's own history dir and version files are unpacked, anything else is rejected.
+pub fn load<R: Read>(repo: &LocalRepository, reader: R) -> Result<Commit, OxenError> {
+    let oxen_dir = util::fs::oxen_hidden_dir(&repo.path);
+    let mut archive = tar::Archive::new(GzDecoder::new(reader));
+
+    let mut commit: Option<Commit> = None;
+    let mut exists = false;
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.to_path_buf();
+        if path == Path::new(COMMIT_FILE) {
+            let mut commit_json = String::new();
+            entry.read_to_string(&mut commit_json)?;
+            let loaded: Commit = serde_json::from_str(&commit_json)?;
+            exists = api::local::commits::get_by_id(repo, &loaded.id)?.is_some();
+            commit = Some(loaded);
+            continue;
+        }
+
+        let Some(commit) = &commit else {
+            return Err(OxenError::basic_str(format!(
+                "Invalid commit tarball, {COMMIT_FILE} must be the first entry"
+            )));
+        };
+        if path == Path::new(OBJECTS_FILE) {
+            let mut objects_json = String::new();
+            entry.read_to_string(&mut objects_json)?;
+            put_commit_objects(repo, &serde_json::from_str(&objects_json)?)?;
+            continue;
+        }
+
+        let history_dir = Path::new(HISTORY_DIR).join(&commit.id);
+        let is_file_or_dir =
+            entry.header().entry_type().is_file() || entry.header().entry_type().is_dir();
+        let is_normal = path.components().all(|c| matches!(c, Component::Normal(_)));
+        let is_allowed = path.starts_with(&history_dir) || path.starts_with(VERSIONS_DIR);
+        if !is_file_or_dir || !is_normal || !is_allowed {
+            return Err(OxenError::basic_str(format!(
+                "Invalid commit tarball, unexpected entry {path:?}"
+            )));
+        }
+
+        // The history dbs of a commit never change, keep the ones we already have open
+        if exists && path.starts_with(&history_dir) {
+            continue;
+        }
+        entry.unpack_in(&oxen_dir)?;
+    }
+
+    let Some(commit) = commit else {
+        return Err(OxenError::basic_str(format!(
+            "Invalid commit tarball, missing {COMMIT_FILE}"
+        )));
+    };
+    if !exists {
+        CommitWriter::new(repo)?.add_commit_to_db(&commit)?;
+    }
+    commit_sync_status::mark_commit_as_synced(repo, &commit)?;
+    log::debug!("commit_tarball::load loaded commit {}", commit);
+    Ok(commit)
+}
+
+fn append_json<W: Write, T: Serialize>(
+    tar: &mut tar::Builder<W>,
+    path: &str,
+    value: &T,
+) -> Result<(), OxenError> {
+    let json = serde_json::to_vec(value)?;
+    let mut header = tar::Header::new_gnu();
+    header.set_size(json.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    tar.append_data(&mut header, path, json.as_slice())?;
+    Ok(())
+}
+
+// Walk the tree of the commit from every dir in its dir_hashes db. The objects dbs are opened
+// read only, which reads a point in time snapshot even if another process is writing to them.
+fn commit_objects(repo: &LocalRepository, commit: &Commit) -> Result<CommitObjects, OxenError> {
+    let object_reader = ObjectDBReader::new(repo)?;
+    let dir_hashes_path = ObjectDBReader::commit_dir_hash_db(&repo.path, &commit.id);
+    let dir_hashes_db: DBWithThreadMode<MultiThreaded> = DBWithThreadMode::open_for_read_only(
+        &db::opts::default(),
+        dunce::simplified(&dir_hashes_path),
+        false,
+    )?;
+    let dir_hashes: Vec<String> = path_db::list_entries(&dir_hashes_db)?;
+
+    let mut objects = CommitObjects::default();
+    let mut seen: HashSet<(&str, String)> = HashSet::new();
+    let mut children: Vec<TreeObjectChild> = dir_hashes
+        .into_iter()
+        .map(|hash| TreeObjectChild::Dir {
+            path: PathBuf::new(),
+            hash,
+        })
+        .collect();
+    while let Some(child) = children.pop() {
+        let kind = match child {
+            TreeObjectChild::Dir { .. } => OBJECT_DIRS_DIR,
+            TreeObjectChild::VNode { .. } => OBJECT_VNODES_DIR,
+            TreeObjectChild::File { .. } => OBJECT_FILES_DIR,
+            TreeObjectChild::Schema { .. } => OBJECT_SCHEMAS_DIR,
+        };
+        if !seen.insert((kind, child.hash().to_owned())) {
+            continue;
+        }
+        let Some(node) = object_reader.get_node_from_child(&child)? else {
+            return Err(OxenError::basic_str(format!(
+                "Could not write tarball for commit {}, missing tree object {}",
+                commit.id,
+                child.hash()
+            )));
+        };
+        match child {
+            TreeObjectChild::Dir { .. } => {
+                children.extend(node.children().iter().cloned());
+                objects.dirs.push(node);
+            }
+            TreeObjectChild::VNode { .. } => {
+                children.extend(node.children().iter().cloned());
+                objects.vnodes.push(node);
+            }
+            TreeObjectChild::File { .. } => objects.files.push(node),
+            TreeObjectChild::Schema { .. } => objects.schemas.push(node),
+        }
+    }
+    Ok(objects)
+}
+
+// Objects are content addressed, so putting them never clobbers what another commit wrote
+fn put_commit_objects(repo: &LocalRepository, objects: &CommitObjects) -> Result<(), OxenError> {
+    let objects_dir = util::fs::oxen_hidden_dir(&repo.path).join(OBJECTS_DIR);
+    let opts = db::opts::default();
+    for (dir, objects) in [
+        (OBJECT_DIRS_DIR, &objects.dirs),
+        (OBJECT_VNODES_DIR, &objects.vnodes),
+        (OBJECT_FILES_DIR, &objects.files),
+        (OBJECT_SCHEMAS_DIR, &objects.schemas),
+    ] {
+        let db: DBWithThreadMode<MultiThreaded> =
+            DBWithThreadMode::open(&opts, dunce::simplified(&objects_dir.join(dir)))?;
+        for object in objects {
+            tree_db::put_tree_object(&db, object.hash(), object)?;
+        }
+    }
+    Ok(())
+}
+
+// Version files of the commit that are not already in its first parent, all of them for a root commit
+fn added_version_paths(repo: &LocalRepository, commit: &Commit) -> Result<Vec<PathBuf>, OxenError> {
+    let entries = CommitEntryReader::new(repo, commit)?.list_entries()?;
+    let parent = match commit.parent_ids.first() {
+        Some(parent_id) => api::local::commits::get_by_id(repo, parent_id)?,
+        None => None,
+    };
+    let parent_versions: HashSet<PathBuf> = match parent {
+        Some(parent) => CommitEntryReader::new(repo, &parent)?
+            .list_entries()?
+            .iter()
+            .map(|entry| util::fs::version_path(repo, entry))
+            .collect(),
+        None => HashSet::new(),
+    };
+
+    let mut version_paths: Vec<PathBuf> = entries
+        .iter()
+        .map(|entry| util::fs::version_path(repo, entry))
+        .filter(|path| !parent_versions.contains(path))
+        .collect();
+    version_paths.sort();
+    version_paths.dedup();
+    Ok(version_paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use crate::api;
+    use crate::command;
+    use crate::core::index::commit_tarball;
+    use crate::error::OxenError;
+    use crate::test;
+    use crate::util;
+
+    #[test]
+    fn test_load_commit_tarballs_out_of_order_into_non_empty_repo() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let hello_path = repo.path.join("hello.txt");
+            util::fs::write_to_path(&hello_path, "hello")?;
+            command::add(&repo, &hello_path)?;
+            let first = command::commit(&repo, "Adding hello")?;
+
+            let world_path = repo.path.join("data").join("world.txt");
+            util::fs::create_dir_all(repo.path.join("data"))?;
+            util::fs::write_to_path(&world_path, "world")?;
+            util::fs::write_to_path(&hello_path, "hello again")?;
+            command::add(&repo, &repo.path)?;
+            let second = command::commit(&repo, "Adding world")?;
+
+            test::run_empty_dir_test(|dir| {
+                let backup = command::init(dir.join("backup"))?;
+                let existing_path = backup.path.join("existing.txt");
+                util::fs::write_to_path(&existing_path, "already here")?;
+                command::add(&backup, &existing_path)?;
+                let existing = command::commit(&backup, "Existing commit")?;
+
+                // Newest first, the second tarball carries its whole tree
+                for commit in [&second, &first] {
+                    let tarball = dir.join(format!("{}.tar.gz", commit.id));
+                    commit_tarball::write(&repo, commit, File::create(&tarball)?)?;
+                    let loaded = commit_tarball::load(&backup, File::open(&tarball)?)?;
+                    assert_eq!(loaded.id, commit.id);
+                }
+
+                assert!(api::local::commits::get_by_id(&backup, &existing.id)?.is_some());
+                for (commit, num_entries) in [(&first, 1), (&second, 2)] {
+                    let entries = api::local::entries::list_all(&backup, commit)?;
+                    assert_eq!(entries.len(), num_entries);
+                    for entry in entries.iter() {
+                        assert!(util::fs::version_path(&backup, entry).exists());
+                    }
+                }
+
+                // Loading a commit twice is a no-op
+                let tarball = dir.join(format!("{}.tar.gz", second.id));
+                commit_tarball::load(&backup, File::open(&tarball)?)?;
+                assert_eq!(api::local::entries::list_all(&backup, &second)?.len(), 2);
+
+                Ok(())
+            })
+        })
+    }
+}
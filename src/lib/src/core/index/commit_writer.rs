@@ -4,9 +4,9 @@ use crate::core::db::path_db;
 
 use crate::core::db;
 use crate::core::index::{
-    self, mod_stager, remote_df_stager, remote_dir_stager, CommitDBReader, CommitDirEntryReader,
-    CommitEntryReader, CommitEntryWriter, CommitReader, EntryIndexer, ObjectDBReader, RefReader,
-    RefWriter,
+    self, mod_stager, placeholders, remote_df_stager, remote_dir_stager, CommitDBReader,
+    CommitDirEntryReader, CommitEntryReader, CommitEntryWriter, CommitReader, EntryIndexer,
+    ObjectDBReader, RefReader, RefWriter,
 };
 use crate::error::OxenError;
 use crate::model::{Branch, Commit, CommitEntry, NewCommit, StagedData, StagedEntry};
@@ -612,6 +612,9 @@ impl CommitWriter {
         let bar = oxen_progress_bar(size, ProgressBarType::Counter);
 
         let dir_entries = self.group_entries_to_dirs(entries);
+        let placeholders = placeholders::list(&self.repository)?;
+        let mut new_placeholders: Vec<PathBuf> = vec![];
+        let lfs_threshold = self.repository.lfs_threshold();
 
         // TODO: don't need to group to dirs anymore
         for (_dir, entries) in dir_entries.iter() {
@@ -623,6 +626,26 @@ impl CommitWriter {
                 let dst_path = self.repository.path.join(path);
                 let version_path = util::fs::version_path(&self.repository, entry);
 
+                // Large files cloned as placeholders have no version to restore from, they stay
+                // missing until materialized rather than keeping the contents of the commit we
+                // are switching away from
+                let is_placeholder = placeholders.contains(path)
+                    || lfs_threshold.is_some_and(|threshold| entry.num_bytes > threshold);
+                if is_placeholder && !version_path.exists() {
+                    let is_up_to_date = dst_path.exists()
+                        && util::hasher::hash_file_contents(&dst_path)
+                            .map(|hash| hash == entry.hash)
+                            .unwrap_or(false);
+                    if !is_up_to_date {
+                        log::debug!("set_working_repo_to_commit_id placeholder {:?}", path);
+                        if dst_path.exists() {
+                            util::fs::remove_file(&dst_path)?;
+                        }
+                        new_placeholders.push(path.to_owned());
+                        continue;
+                    }
+                }
+
                 // If we do not have the file, restore it from our versioned history
                 if !dst_path.exists() {
                     log::debug!(
@@ -705,6 +728,9 @@ impl CommitWriter {
             }
         }
         bar.finish();
+        if !new_placeholders.is_empty() {
+            placeholders::add(&self.repository, &new_placeholders)?;
+        }
         Ok(())
     }
 
@@ -953,6 +979,8 @@ mod tests {
                 author: user.name.to_owned(),
                 email: user.email,
                 message: "Appending tabular data".to_string(),
+                expected_parent_id: None,
+                idempotency_key: None,
             };
 
             let commit =
@@ -5,7 +5,7 @@ use indicatif::ProgressBar;
 use jwalk::WalkDirGeneric;
 use rayon::prelude::*;
 use rocksdb::{DBWithThreadMode, MultiThreaded};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -13,7 +13,7 @@ use std::sync::Arc;
 use crate::constants::{self, DEFAULT_REMOTE_NAME, HISTORY_DIR};
 use crate::core::db;
 use crate::core::index::pusher::UnsyncedCommitEntries;
-use crate::core::index::{self, puller, versioner, Merger, ObjectDBReader, Stager};
+use crate::core::index::{self, placeholders, puller, versioner, Merger, ObjectDBReader, Stager};
 use crate::core::index::{CommitDirEntryReader, CommitEntryReader, RefWriter};
 use crate::error::OxenError;
 use crate::model::entry::commit_entry::{Entry, SchemaEntry};
@@ -598,6 +598,9 @@ impl EntryIndexer {
         let commit_reader = CommitReader::new(&self.repository)?;
 
         let mut unsynced_entries: Vec<UnsyncedCommitEntries> = Vec::new();
+        let mut placeholder_entries: Vec<Entry> = Vec::new();
+        // Placeholder paths per commit id, those commits are not synced until they are materialized
+        let mut commit_placeholders: HashMap<String, Vec<PathBuf>> = HashMap::new();
 
         log::debug!("gathering entries");
         for commit in &commits {
@@ -626,6 +629,13 @@ impl EntryIndexer {
                 let mut entries: Vec<Entry> = entries.into_iter().map(Entry::from).collect();
                 entries.extend(schemas.into_iter().map(Entry::from));
 
+                let (entries, skipped) = placeholders::partition_entries(&self.repository, entries);
+                commit_placeholders
+                    .entry(commit.id.clone())
+                    .or_default()
+                    .extend(skipped.iter().map(|entry| entry.path()));
+                placeholder_entries.extend(skipped);
+
                 unsynced_entries.push(UnsyncedCommitEntries {
                     commit: commit.clone(),
                     entries,
@@ -668,8 +678,15 @@ impl EntryIndexer {
                 &commit_with_entries.entries,
                 &bar,
             )?;
+            self.record_placeholders(&commit_with_entries.entries, &[])?;
             self.pull_complete(&commit_with_entries.commit).unwrap();
         }
+        for commit in commits.iter() {
+            if let Some(paths) = commit_placeholders.get(&commit.id) {
+                index::commit_sync_status::add_placeholders(&self.repository, commit, paths)?;
+            }
+        }
+        self.record_placeholders(&[], &placeholder_entries)?;
 
         Ok(())
     }
@@ -707,6 +724,10 @@ impl EntryIndexer {
         let mut entries: Vec<Entry> = entries.into_iter().map(Entry::from).collect();
         entries.extend(schema_entries.into_iter().map(Entry::from));
 
+        // Files over the lfs threshold are left as placeholders until materialized
+        let (entries, placeholder_entries) =
+            placeholders::partition_entries(&self.repository, entries);
+
         let n_entries_to_pull = entries.len();
         log::debug!("got {} entries to pull", n_entries_to_pull);
 
@@ -719,10 +740,16 @@ impl EntryIndexer {
 
         println!("🐂 Unpacking files...");
         self.unpack_version_files_to_working_dir(&commit, &entries, &bar)?;
+        self.record_placeholders(&entries, &placeholder_entries)?;
 
         if limit == 0 {
             self.pull_complete(&commit).unwrap();
         }
+        let placeholder_paths: Vec<PathBuf> = placeholder_entries
+            .iter()
+            .map(|entry| entry.path())
+            .collect();
+        index::commit_sync_status::add_placeholders(&self.repository, &commit, &placeholder_paths)?;
 
         Ok(())
     }
@@ -733,6 +760,12 @@ impl EntryIndexer {
         entries: &[Entry],
         bar: &Arc<ProgressBar>,
     ) -> Result<(), OxenError> {
+        if self.repository.is_bare() {
+            log::debug!("unpack_version_files_to_working_dir skipping bare repository");
+            bar.inc(entries.len() as u64);
+            return Ok(());
+        }
+
         // TODO: Don't need to group anymore
         let dir_entries = api::local::entries::group_entries_to_parent_dirs(entries);
         let opts = db::opts::default();
@@ -788,6 +821,30 @@ impl EntryIndexer {
         Ok(())
     }
 
+    // Files that were unpacked are no longer placeholders, even if an older version was
+    fn record_placeholders(
+        &self,
+        unpacked: &[Entry],
+        placeholder_entries: &[Entry],
+    ) -> Result<(), OxenError> {
+        let unpacked: Vec<PathBuf> = unpacked
+            .iter()
+            .filter(|entry| matches!(entry, Entry::CommitEntry(_)))
+            .map(|entry| entry.path())
+            .collect();
+        placeholders::remove(&self.repository, &unpacked)?;
+
+        if !placeholder_entries.is_empty() {
+            let paths: Vec<PathBuf> = placeholder_entries.iter().map(|e| e.path()).collect();
+            println!(
+                "🐂 Skipped {} large files, run `oxen materialize <path>` to download them",
+                paths.len()
+            );
+            placeholders::add(&self.repository, &paths)?;
+        }
+        Ok(())
+    }
+
     fn pull_complete(&self, commit: &Commit) -> Result<(), OxenError> {
         // This is so that we know when we switch commits that we don't need to pull versions again
         index::commit_sync_status::mark_commit_as_synced(&self.repository, commit)?;
@@ -0,0 +1,218 @@
+//! Track the idempotency keys sent with remote staging commits, so a retried request
+//! returns the commit the first request created instead of committing twice.
+//!
+//! Each key is a file in `.oxen/idempotency_keys` named by the hash of the staging identifier,
+//! branch and key, so two users or branches never share a key. The file is created empty when
+//! a commit starts and holds the commit id once the commit is written. An empty file older than
+//! `IDEMPOTENCY_KEY_TIMEOUT_SECS` was left by a commit that never finished and is reclaimed.
+//!
+
+use std::fs::OpenOptions;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::constants::{IDEMPOTENCY_KEYS_DIR, IDEMPOTENCY_KEY_TIMEOUT_SECS};
+use crate::error::OxenError;
+use crate::model::LocalRepository;
+use crate::util;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IdempotencyKeyState {
+    /// The key was unused and is now held by the caller, who must `record` or `release` it
+    Reserved,
+    /// Another request with the key is still committing
+    InProgress,
+    /// A request with the key already created this commit id
+    Committed(String),
+}
+
+fn key_path(repo: &LocalRepository, identifier: &str, branch_name: &str, key: &str) -> PathBuf {
+    util::fs::oxen_hidden_dir(&repo.path)
+        .join(IDEMPOTENCY_KEYS_DIR)
+        .join(util::hasher::hash_str(format!(
+            "{identifier}/{branch_name}/{key}"
+        )))
+}
+
+/// Claim the key for a new commit, or find out what an earlier request with it did
+pub fn reserve(
+    repo: &LocalRepository,
+    identifier: &str,
+    branch_name: &str,
+    key: &str,
+) -> Result<IdempotencyKeyState, OxenError> {
+    reserve_with_timeout(
+        repo,
+        identifier,
+        branch_name,
+        key,
+        Duration::from_secs(IDEMPOTENCY_KEY_TIMEOUT_SECS),
+    )
+}
+
+fn reserve_with_timeout(
+    repo: &LocalRepository,
+    identifier: &str,
+    branch_name: &str,
+    key: &str,
+    timeout: Duration,
+) -> Result<IdempotencyKeyState, OxenError> {
+    let path = key_path(repo, identifier, branch_name, key);
+    if let Some(parent) = path.parent() {
+        util::fs::create_dir_all(parent)?;
+    }
+
+    // create_new fails if the file exists, so only one request can reserve a key
+    match OpenOptions::new().write(true).create_new(true).open(&path) {
+        Ok(_) => Ok(IdempotencyKeyState::Reserved),
+        Err(err) if err.kind() == ErrorKind::AlreadyExists => {
+            let commit_id = util::fs::read_from_path(&path)?;
+            let commit_id = commit_id.trim();
+            if !commit_id.is_empty() {
+                return Ok(IdempotencyKeyState::Committed(commit_id.to_string()));
+            }
+            if !is_stale(&path, timeout)? {
+                return Ok(IdempotencyKeyState::InProgress);
+            }
+
+            // Move the stale reservation aside rather than deleting it, so that of two retries
+            // reclaiming it at once only the one whose rename succeeds gets to create it again
+            log::debug!("idempotency_keys::reserve reclaiming stale key {key}");
+            let stale_path = path.with_extension(uuid::Uuid::new_v4().to_string());
+            match std::fs::rename(&path, &stale_path) {
+                Ok(_) => {
+                    util::fs::remove_file(&stale_path)?;
+                    reserve_with_timeout(repo, identifier, branch_name, key, timeout)
+                }
+                Err(err) if err.kind() == ErrorKind::NotFound => {
+                    Ok(IdempotencyKeyState::InProgress)
+                }
+                Err(err) => Err(OxenError::IO(err)),
+            }
+        }
+        Err(err) => Err(OxenError::IO(err)),
+    }
+}
+
+fn is_stale(path: &Path, timeout: Duration) -> Result<bool, OxenError> {
+    let modified = std::fs::metadata(path)?.modified()?;
+    Ok(modified
+        .elapsed()
+        .map(|age| age >= timeout)
+        .unwrap_or(false))
+}
+
+/// Save the commit created for a reserved key
+pub fn record(
+    repo: &LocalRepository,
+    identifier: &str,
+    branch_name: &str,
+    key: &str,
+    commit_id: &str,
+) -> Result<(), OxenError> {
+    util::fs::write_to_path(key_path(repo, identifier, branch_name, key), commit_id)
+}
+
+/// Give up a reserved key after the commit failed, so the request can be retried
+pub fn release(
+    repo: &LocalRepository,
+    identifier: &str,
+    branch_name: &str,
+    key: &str,
+) -> Result<(), OxenError> {
+    let path = key_path(repo, identifier, branch_name, key);
+    if path.exists() {
+        util::fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::core::index::idempotency_keys::{self, IdempotencyKeyState};
+    use crate::error::OxenError;
+    use crate::test;
+
+    const USER: &str = "user-a";
+    const BRANCH: &str = "main";
+
+    #[test]
+    fn test_idempotency_keys_reserve_record_release() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let key = "retry-me";
+            assert_eq!(
+                idempotency_keys::reserve(&repo, USER, BRANCH, key)?,
+                IdempotencyKeyState::Reserved
+            );
+            assert_eq!(
+                idempotency_keys::reserve(&repo, USER, BRANCH, key)?,
+                IdempotencyKeyState::InProgress
+            );
+
+            idempotency_keys::record(&repo, USER, BRANCH, key, "abc123")?;
+            assert_eq!(
+                idempotency_keys::reserve(&repo, USER, BRANCH, key)?,
+                IdempotencyKeyState::Committed(String::from("abc123"))
+            );
+
+            let other = "failed-commit";
+            idempotency_keys::reserve(&repo, USER, BRANCH, other)?;
+            idempotency_keys::release(&repo, USER, BRANCH, other)?;
+            assert_eq!(
+                idempotency_keys::reserve(&repo, USER, BRANCH, other)?,
+                IdempotencyKeyState::Reserved
+            );
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_idempotency_keys_scoped_by_identifier_and_branch() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let key = "same-key";
+            idempotency_keys::reserve(&repo, USER, BRANCH, key)?;
+            idempotency_keys::record(&repo, USER, BRANCH, key, "abc123")?;
+
+            assert_eq!(
+                idempotency_keys::reserve(&repo, "user-b", BRANCH, key)?,
+                IdempotencyKeyState::Reserved
+            );
+            assert_eq!(
+                idempotency_keys::reserve(&repo, USER, "other-branch", key)?,
+                IdempotencyKeyState::Reserved
+            );
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_idempotency_keys_reclaim_stale_in_progress() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let key = "crashed-commit";
+            idempotency_keys::reserve(&repo, USER, BRANCH, key)?;
+
+            // Still fresh with the default timeout
+            assert_eq!(
+                idempotency_keys::reserve(&repo, USER, BRANCH, key)?,
+                IdempotencyKeyState::InProgress
+            );
+
+            // Stale once the timeout passes
+            assert_eq!(
+                idempotency_keys::reserve_with_timeout(&repo, USER, BRANCH, key, Duration::ZERO)?,
+                IdempotencyKeyState::Reserved
+            );
+            assert_eq!(
+                idempotency_keys::reserve(&repo, USER, BRANCH, key)?,
+                IdempotencyKeyState::InProgress
+            );
+
+            Ok(())
+        })
+    }
+}
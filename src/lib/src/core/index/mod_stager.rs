@@ -10,7 +10,7 @@ use polars::frame::DataFrame;
 
 use rocksdb::{DBWithThreadMode, MultiThreaded, SingleThreaded};
 
-use crate::constants::{FILES_DIR, MODS_DIR, OXEN_HIDDEN_DIR, STAGED_DIR};
+use crate::constants::{FILES_DIR, MODS_DIR, STAGED_DIR};
 use crate::core::db::{self, df_db, staged_df_db, str_json_db};
 use crate::core::df::tabular;
 use crate::core::index::remote_df_stager;
@@ -31,12 +31,13 @@ pub fn mods_db_path(
 ) -> PathBuf {
     let path_hash = util::hasher::hash_str(path.as_ref().to_string_lossy());
 
-    remote_dir_stager::branch_staging_dir(repo, branch, identifier)
-        .join(OXEN_HIDDEN_DIR)
-        .join(STAGED_DIR)
-        .join(MODS_DIR)
-        .join(MODS_DIR)
-        .join(path_hash)
+    util::fs::oxen_hidden_dir(remote_dir_stager::branch_staging_dir(
+        repo, branch, identifier,
+    ))
+    .join(STAGED_DIR)
+    .join(MODS_DIR)
+    .join(MODS_DIR)
+    .join(path_hash)
 }
 
 pub fn mods_df_db_path(
@@ -46,13 +47,14 @@ pub fn mods_df_db_path(
     path: impl AsRef<Path>,
 ) -> PathBuf {
     let path_hash = util::hasher::hash_str(path.as_ref().to_string_lossy());
-    remote_dir_stager::branch_staging_dir(repo, branch, identifier)
-        .join(OXEN_HIDDEN_DIR)
-        .join(STAGED_DIR)
-        .join(MODS_DIR)
-        .join("duckdb")
-        .join(path_hash)
-        .join("db")
+    util::fs::oxen_hidden_dir(remote_dir_stager::branch_staging_dir(
+        repo, branch, identifier,
+    ))
+    .join(STAGED_DIR)
+    .join(MODS_DIR)
+    .join("duckdb")
+    .join(path_hash)
+    .join("db")
 }
 
 pub fn mods_commit_ref_path(
@@ -62,21 +64,23 @@ pub fn mods_commit_ref_path(
     path: impl AsRef<Path>,
 ) -> PathBuf {
     let path_hash = util::hasher::hash_str(path.as_ref().to_string_lossy());
-    remote_dir_stager::branch_staging_dir(repo, branch, identifier)
-        .join(OXEN_HIDDEN_DIR)
-        .join(STAGED_DIR)
-        .join(MODS_DIR)
-        .join("duckdb")
-        .join(path_hash)
-        .join("COMMIT_ID")
+    util::fs::oxen_hidden_dir(remote_dir_stager::branch_staging_dir(
+        repo, branch, identifier,
+    ))
+    .join(STAGED_DIR)
+    .join(MODS_DIR)
+    .join("duckdb")
+    .join(path_hash)
+    .join("COMMIT_ID")
 }
 
 fn files_db_path(repo: &LocalRepository, branch: &Branch, identifier: &str) -> PathBuf {
-    remote_dir_stager::branch_staging_dir(repo, branch, identifier)
-        .join(OXEN_HIDDEN_DIR)
-        .join(STAGED_DIR)
-        .join(MODS_DIR)
-        .join(FILES_DIR)
+    util::fs::oxen_hidden_dir(remote_dir_stager::branch_staging_dir(
+        repo, branch, identifier,
+    ))
+    .join(STAGED_DIR)
+    .join(MODS_DIR)
+    .join(FILES_DIR)
 }
 
 pub fn add_row(
@@ -570,6 +574,8 @@ mod tests {
                 author: "author".to_string(),
                 email: "email".to_string(),
                 message: "Deleting a row allegedly".to_string(),
+                expected_parent_id: None,
+                idempotency_key: None,
             };
             let commit_2 =
                 remote_dir_stager::commit(&repo, &branch_repo, &branch, &new_commit, &identity)?;
@@ -584,7 +590,8 @@ mod tests {
                 &file_path,
             )?;
 
-            let diff_result = api::local::diff::diff_files(file_1, file_2, vec![], vec![], vec![])?;
+            let diff_result =
+                api::local::diff::diff_files(file_1, file_2, vec![], vec![], vec![], false)?;
 
             match diff_result {
                 DiffResult::Tabular(tabular_diff) => {
@@ -0,0 +1,208 @@
+//! # .oxenattributes
+//!
+//! Per path handling declared in a gitattributes style file at the root of the repository.
+//! Each line is a pattern followed by attributes, for example:
+//!
+//! ```text
+//! # Never diff these as text, even if they happen to be valid utf8
+//! *.bin binary
+//! logs/*.txt -diff
+//! # Push large checkpoints in 64mb chunks
+//! *.ckpt chunk_size=67108864
+//! ```
+//!
+//! `binary` also unsets `diff`. An attribute can be unset with a `-` prefix or reset to its
+//! default with a `!` prefix. When several lines match a path the later lines win.
+//!
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::path::Path;
+
+use crate::constants;
+use crate::model::LocalRepository;
+
+/// The attributes that apply to a single path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathAttributes {
+    pub binary: bool,
+    pub diff: bool,
+    pub chunk_size: Option<u64>,
+}
+
+impl Default for PathAttributes {
+    fn default() -> Self {
+        PathAttributes {
+            binary: false,
+            diff: true,
+            chunk_size: None,
+        }
+    }
+}
+
+impl PathAttributes {
+    /// Whether the contents can be compared as text, tabular data or images
+    pub fn should_diff_contents(&self) -> bool {
+        !self.binary && self.diff
+    }
+}
+
+#[derive(Debug, Clone)]
+enum AttributeSetting {
+    Binary(bool),
+    Diff(bool),
+    ChunkSize(Option<u64>),
+}
+
+#[derive(Debug)]
+struct AttributeRule {
+    matcher: Gitignore,
+    settings: Vec<AttributeSetting>,
+}
+
+#[derive(Debug, Default)]
+pub struct OxenAttributes {
+    rules: Vec<AttributeRule>,
+}
+
+impl OxenAttributes {
+    /// Parse the contents of an .oxenattributes file, patterns are relative to `root`.
+    /// Invalid lines are skipped with a warning, the same as a broken .oxenignore line.
+    pub fn parse(root: impl AsRef<Path>, contents: &str) -> OxenAttributes {
+        let root = root.as_ref();
+        let mut rules: Vec<AttributeRule> = vec![];
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut parts = line.split_whitespace();
+            let Some(pattern) = parts.next() else {
+                continue;
+            };
+            let settings: Vec<AttributeSetting> = parts.flat_map(parse_setting).collect();
+            if settings.is_empty() {
+                continue;
+            }
+
+            let mut builder = GitignoreBuilder::new(root);
+            let matcher = builder
+                .add_line(None, pattern)
+                .map_err(|err| err.to_string())
+                .and_then(|builder| builder.build().map_err(|err| err.to_string()));
+            match matcher {
+                Ok(matcher) => rules.push(AttributeRule { matcher, settings }),
+                Err(err) => {
+                    log::warn!("Invalid .oxenattributes pattern {:?}: {}", pattern, err);
+                }
+            }
+        }
+        OxenAttributes { rules }
+    }
+
+    /// The attributes for `path`, relative to the repository root
+    pub fn for_path(&self, path: impl AsRef<Path>) -> PathAttributes {
+        let path = path.as_ref();
+        let mut attributes = PathAttributes::default();
+        for rule in self.rules.iter() {
+            if !rule.matcher.matched(path, false).is_ignore() {
+                continue;
+            }
+
+            for setting in rule.settings.iter() {
+                match setting {
+                    AttributeSetting::Binary(binary) => {
+                        attributes.binary = *binary;
+                        if *binary {
+                            attributes.diff = false;
+                        }
+                    }
+                    AttributeSetting::Diff(diff) => attributes.diff = *diff,
+                    AttributeSetting::ChunkSize(chunk_size) => attributes.chunk_size = *chunk_size,
+                }
+            }
+        }
+        attributes
+    }
+}
+
+/// Load the .oxenattributes from the root of the working directory, empty if there is none
+pub fn load(repo: &LocalRepository) -> OxenAttributes {
+    let path = repo.path.join(constants::OXEN_ATTRIBUTES_FILE);
+    match std::fs::read_to_string(&path) {
+        Ok(contents) => OxenAttributes::parse(&repo.path, &contents),
+        Err(err) => {
+            log::debug!("Could not open .oxenattributes file. Reason: {}", err);
+            OxenAttributes::default()
+        }
+    }
+}
+
+fn parse_setting(setting: &str) -> Option<AttributeSetting> {
+    let default = PathAttributes::default();
+    let (name, value) = if let Some(name) = setting.strip_prefix('-') {
+        (name, Some(false))
+    } else if let Some(name) = setting.strip_prefix('!') {
+        (name, None)
+    } else {
+        (setting, Some(true))
+    };
+
+    if let Some(chunk_size) = name.strip_prefix("chunk_size=") {
+        return match chunk_size.parse::<u64>() {
+            Ok(chunk_size) if chunk_size > 0 => Some(AttributeSetting::ChunkSize(Some(chunk_size))),
+            _ => {
+                log::warn!("Invalid .oxenattributes chunk_size {:?}", chunk_size);
+                None
+            }
+        };
+    }
+
+    match name {
+        "binary" => Some(AttributeSetting::Binary(value.unwrap_or(default.binary))),
+        "diff" => Some(AttributeSetting::Diff(value.unwrap_or(default.diff))),
+        "chunk_size" if value != Some(true) => {
+            Some(AttributeSetting::ChunkSize(default.chunk_size))
+        }
+        _ => {
+            log::debug!("Unknown .oxenattributes attribute {:?}", setting);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::core::index::oxenattributes::{OxenAttributes, PathAttributes};
+
+    #[test]
+    fn test_oxenattributes_later_lines_win() {
+        let contents = "\
+# comment
+*.bin binary
+data/*.txt -diff chunk_size=1024
+data/keep.txt !diff !chunk_size
+*.weird unknown_attribute
+";
+        let attributes = OxenAttributes::parse(Path::new("/repo"), contents);
+
+        let bin = attributes.for_path("nested/model.bin");
+        assert!(bin.binary);
+        assert!(!bin.diff);
+        assert!(!bin.should_diff_contents());
+
+        let txt = attributes.for_path("data/notes.txt");
+        assert!(!txt.binary);
+        assert!(!txt.should_diff_contents());
+        assert_eq!(txt.chunk_size, Some(1024));
+
+        assert_eq!(
+            attributes.for_path("data/keep.txt"),
+            PathAttributes::default()
+        );
+        assert_eq!(attributes.for_path("notes.txt"), PathAttributes::default());
+        assert_eq!(attributes.for_path("file.weird"), PathAttributes::default());
+    }
+}
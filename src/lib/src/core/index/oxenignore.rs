@@ -1,4 +1,4 @@
-use ignore::gitignore::Gitignore;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
 
 use crate::constants;
 use crate::model::LocalRepository;
@@ -17,3 +17,31 @@ pub fn create(repo: &LocalRepository) -> Option<Gitignore> {
         }
     }
 }
+
+/// Load the .oxenignore and add extra glob patterns to skip, for example from `oxen add --exclude`
+pub fn create_with_excludes(repo: &LocalRepository, excludes: &[String]) -> Option<Gitignore> {
+    if excludes.is_empty() {
+        return create(repo);
+    }
+
+    let mut builder = GitignoreBuilder::new(&repo.path);
+    let path = repo.path.join(constants::OXEN_IGNORE_FILE);
+    if path.exists() {
+        if let Some(err) = builder.add(path) {
+            log::debug!("Could not open .oxenignore file. Reason: {}", err);
+        }
+    }
+    for exclude in excludes {
+        if let Err(err) = builder.add_line(None, exclude) {
+            log::warn!("Invalid exclude pattern {:?}: {}", exclude, err);
+        }
+    }
+
+    match builder.build() {
+        Ok(gitignore) => Some(gitignore),
+        Err(err) => {
+            log::debug!("Could not build ignore patterns. Reason: {}", err);
+            None
+        }
+    }
+}
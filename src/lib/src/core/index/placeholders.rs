@@ -0,0 +1,72 @@
+//! Track the files that were left out of the working directory because they are larger than
+//! the repository's lfs threshold. They are downloaded on demand with `oxen materialize`.
+//!
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use crate::constants::PLACEHOLDERS_FILE;
+use crate::error::OxenError;
+use crate::model::entry::commit_entry::Entry;
+use crate::model::LocalRepository;
+use crate::util;
+
+fn placeholders_path(repo: &LocalRepository) -> PathBuf {
+    util::fs::oxen_hidden_dir(&repo.path).join(PLACEHOLDERS_FILE)
+}
+
+/// List the paths that are placeholders, relative to the repo root
+pub fn list(repo: &LocalRepository) -> Result<BTreeSet<PathBuf>, OxenError> {
+    let path = placeholders_path(repo);
+    if !path.exists() {
+        return Ok(BTreeSet::new());
+    }
+    let contents = util::fs::read_from_path(&path)?;
+    Ok(serde_json::from_str(&contents)?)
+}
+
+pub fn is_placeholder(repo: &LocalRepository, path: impl AsRef<Path>) -> Result<bool, OxenError> {
+    Ok(list(repo)?.contains(path.as_ref()))
+}
+
+pub fn add(repo: &LocalRepository, paths: &[PathBuf]) -> Result<(), OxenError> {
+    let mut placeholders = list(repo)?;
+    placeholders.extend(paths.iter().cloned());
+    write(repo, &placeholders)
+}
+
+pub fn remove(repo: &LocalRepository, paths: &[PathBuf]) -> Result<(), OxenError> {
+    let mut placeholders = list(repo)?;
+    let num_placeholders = placeholders.len();
+    for path in paths.iter() {
+        placeholders.remove(path);
+    }
+    if placeholders.len() == num_placeholders {
+        return Ok(());
+    }
+    write(repo, &placeholders)
+}
+
+/// Split the entries into the ones to download now and the ones to leave as placeholders,
+/// based on the repository's lfs threshold. Schemas are always downloaded.
+pub fn partition_entries(repo: &LocalRepository, entries: Vec<Entry>) -> (Vec<Entry>, Vec<Entry>) {
+    let Some(threshold) = repo.lfs_threshold() else {
+        return (entries, vec![]);
+    };
+
+    entries
+        .into_iter()
+        .partition(|entry| !matches!(entry, Entry::CommitEntry(e) if e.num_bytes > threshold))
+}
+
+fn write(repo: &LocalRepository, placeholders: &BTreeSet<PathBuf>) -> Result<(), OxenError> {
+    let path = placeholders_path(repo);
+    if placeholders.is_empty() {
+        if path.exists() {
+            util::fs::remove_file(&path)?;
+        }
+        return Ok(());
+    }
+    util::fs::write_to_path(&path, serde_json::to_string(placeholders)?)?;
+    Ok(())
+}
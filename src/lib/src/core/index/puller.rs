@@ -4,15 +4,13 @@
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
-use indicatif::ProgressBar;
-
 use crate::api;
 use crate::constants::AVG_CHUNK_SIZE;
 use crate::error::OxenError;
 use crate::model::entry::commit_entry::Entry;
 use crate::model::RemoteRepository;
 use crate::util::concurrency;
-use crate::util::progress_bar::{oxen_progress_bar, ProgressBarType};
+use crate::util::progress_bar::ProgressReporter;
 use crate::{current_function, util};
 
 pub async fn pull_entries(
@@ -54,8 +52,8 @@ pub async fn pull_entries(
         .map(|e| e.to_owned())
         .collect();
 
-    // Progress bar to be shared between small and large entries
-    let bar = oxen_progress_bar(total_size, ProgressBarType::Bytes);
+    // Progress to be shared between small and large entries
+    let progress = ProgressReporter::new("pull", total_size, missing_entries.len() as u64);
 
     // Either download to the working directory or the versions directory
     let (small_entry_paths, large_entry_paths) = if to_working_dir {
@@ -70,15 +68,25 @@ pub async fn pull_entries(
         (small_entry_paths, large_entry_paths)
     };
 
-    let large_entries_sync =
-        pull_large_entries(remote_repo, larger_entries, &dst, large_entry_paths, &bar);
-    let small_entries_sync =
-        pull_small_entries(remote_repo, smaller_entries, &dst, small_entry_paths, &bar);
+    let large_entries_sync = pull_large_entries(
+        remote_repo,
+        larger_entries,
+        &dst,
+        large_entry_paths,
+        &progress,
+    );
+    let small_entries_sync = pull_small_entries(
+        remote_repo,
+        smaller_entries,
+        &dst,
+        small_entry_paths,
+        &progress,
+    );
 
     match tokio::join!(large_entries_sync, small_entries_sync) {
         (Ok(_), Ok(_)) => {
             log::debug!("Successfully synced entries!");
-            bar.finish_and_clear();
+            progress.finish();
         }
         (Err(err), Ok(_)) => {
             let err = format!("Error syncing large entries: {err}");
@@ -119,33 +127,68 @@ fn version_dir_paths_from_large_entries(entries: &[Entry], dst: &Path) -> Vec<Pa
     paths
 }
 
+/// Entries that are not in the versions dir yet. An entry that was already unpacked to the
+/// working dir, say by a pull that was interrupted, is copied back into the versions dir instead
+/// of being downloaded again.
 fn get_missing_entries(entries: &[Entry], dst: impl AsRef<Path>) -> Vec<Entry> {
     let dst = dst.as_ref();
     let mut missing_entries: Vec<Entry> = vec![];
 
     for entry in entries {
         let version_path = util::fs::version_path_from_dst_generic(dst, entry);
-        if !version_path.exists() {
-            missing_entries.push(entry.to_owned())
+        if version_path.exists() {
+            continue;
+        }
+
+        if working_file_matches(entry, dst) {
+            match util::fs::copy_mkdir(dst.join(entry.path()), &version_path) {
+                Ok(_) => continue,
+                Err(err) => log::warn!(
+                    "Could not restore version file from {:?}, downloading it: {}",
+                    entry.path(),
+                    err
+                ),
+            }
         }
+        missing_entries.push(entry.to_owned())
     }
 
     missing_entries
 }
 
+fn working_file_matches(entry: &Entry, dst: &Path) -> bool {
+    match entry {
+        Entry::CommitEntry(commit_entry) => {
+            let path = dst.join(&commit_entry.path);
+            path.is_file()
+                && util::hasher::hash_file_contents(&path)
+                    .map(|hash| hash == commit_entry.hash)
+                    .unwrap_or(false)
+        }
+        // Schemas are never unpacked to the working dir
+        Entry::SchemaEntry(_) => false,
+    }
+}
+
 async fn pull_large_entries(
     remote_repo: &RemoteRepository,
     entries: Vec<Entry>,
     dst: impl AsRef<Path>,
     download_paths: Vec<PathBuf>,
-    bar: &Arc<ProgressBar>,
+    progress: &Arc<ProgressReporter>,
 ) -> Result<(), OxenError> {
     if entries.is_empty() {
         return Ok(());
     }
     // Pull the large entries in parallel
     use tokio::time::{sleep, Duration};
-    type PieceOfWork = (RemoteRepository, Entry, PathBuf, PathBuf, Arc<ProgressBar>);
+    type PieceOfWork = (
+        RemoteRepository,
+        Entry,
+        PathBuf,
+        PathBuf,
+        Arc<ProgressReporter>,
+    );
     type TaskQueue = deadqueue::limited::Queue<PieceOfWork>;
     type FinishedTaskQueue = deadqueue::limited::Queue<bool>;
 
@@ -159,7 +202,7 @@ async fn pull_large_entries(
                 e.to_owned(),
                 dst.as_ref().to_owned(),
                 path.to_owned(),
-                bar.to_owned(),
+                progress.to_owned(),
             )
         })
         .collect();
@@ -184,7 +227,7 @@ async fn pull_large_entries(
         let finished_queue = finished_queue.clone();
         tokio::spawn(async move {
             loop {
-                let (remote_repo, entry, _dst, download_path, bar) = queue.pop().await;
+                let (remote_repo, entry, _dst, download_path, progress) = queue.pop().await;
 
                 log::debug!("worker[{}] processing task...", worker);
 
@@ -198,12 +241,13 @@ async fn pull_large_entries(
                     &download_path,
                     &entry.commit_id(),
                     entry.num_bytes(),
-                    bar,
+                    progress.bar(),
                 )
                 .await
                 {
                     Ok(_) => {
                         // log::debug!("Downloaded large entry {:?} to versions dir", remote_path);
+                        progress.inc_entries(1);
                     }
                     Err(err) => {
                         log::error!("Could not download chunk... {}", err)
@@ -229,7 +273,7 @@ async fn pull_small_entries(
     entries: Vec<Entry>,
     dst: impl AsRef<Path>,
     content_ids: Vec<(String, PathBuf)>,
-    bar: &Arc<ProgressBar>,
+    progress: &Arc<ProgressReporter>,
 ) -> Result<(), OxenError> {
     if content_ids.is_empty() {
         return Ok(());
@@ -256,7 +300,7 @@ async fn pull_small_entries(
         RemoteRepository,
         Vec<(String, PathBuf)>,
         PathBuf,
-        Arc<ProgressBar>,
+        Arc<ProgressReporter>,
     );
     type TaskQueue = deadqueue::limited::Queue<PieceOfWork>;
     type FinishedTaskQueue = deadqueue::limited::Queue<bool>;
@@ -269,7 +313,7 @@ async fn pull_small_entries(
                 remote_repo.to_owned(),
                 chunk.to_owned(),
                 dst.as_ref().to_owned(),
-                bar.to_owned(),
+                progress.to_owned(),
             )
         })
         .collect();
@@ -287,18 +331,15 @@ async fn pull_small_entries(
         let finished_queue = finished_queue.clone();
         tokio::spawn(async move {
             loop {
-                let (remote_repo, chunk, path, bar) = queue.pop().await;
+                let (remote_repo, chunk, path, progress) = queue.pop().await;
                 log::debug!("worker[{}] processing task...", worker);
 
-                match api::remote::entries::download_data_from_version_paths(
-                    &remote_repo,
-                    &chunk,
-                    &path,
-                )
-                .await
+                match api::remote::entries::download_content_by_ids(&remote_repo, &chunk, &path)
+                    .await
                 {
                     Ok(download_size) => {
-                        bar.inc(download_size);
+                        progress.inc_bytes(download_size);
+                        progress.inc_entries(chunk.len() as u64);
                     }
                     Err(err) => {
                         log::error!("Could not download entries... {}", err)
@@ -363,3 +404,137 @@ pub async fn pull_entries_to_working_dir(
     pull_entries(remote_repo, entries, dst, to_working_dir).await?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+
+    use crate::api;
+    use crate::command;
+    use crate::constants;
+    use crate::core::index::CommitEntryReader;
+    use crate::error::OxenError;
+    use crate::model::entry::commit_entry::Entry;
+    use crate::test;
+    use crate::util;
+
+    #[test]
+    fn test_get_missing_entries_skips_unpacked_files() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            for name in ["a.txt", "b.txt", "c.txt", "d.txt"] {
+                test::write_txt_file_to_path(repo.path.join(name), name)?;
+            }
+            command::add(&repo, &repo.path)?;
+            let commit = command::commit(&repo, "Adding files")?;
+            let entries = CommitEntryReader::new(&repo, &commit)?.list_entries()?;
+
+            // A pull interrupted while unpacking, the versions dir has lost the files and only
+            // some of them made it to the working dir intact
+            for entry in entries.iter() {
+                util::fs::remove_file(util::fs::version_path(&repo, entry))?;
+            }
+            util::fs::remove_file(repo.path.join("c.txt"))?;
+            test::write_txt_file_to_path(repo.path.join("d.txt"), "partially written")?;
+
+            let entries: Vec<Entry> = entries.into_iter().map(Entry::from).collect();
+            let missing = super::get_missing_entries(&entries, &repo.path);
+            let mut missing: Vec<String> = missing
+                .iter()
+                .map(|entry| entry.path().to_string_lossy().to_string())
+                .collect();
+            missing.sort();
+            assert_eq!(missing, vec!["c.txt", "d.txt"]);
+
+            // The unpacked files are back in the versions dir
+            let entries = CommitEntryReader::new(&repo, &commit)?.list_entries()?;
+            for entry in entries.iter() {
+                let version_path = util::fs::version_path(&repo, entry);
+                let unpacked = entry.path.to_string_lossy() == "a.txt"
+                    || entry.path.to_string_lossy() == "b.txt";
+                assert_eq!(version_path.exists(), unpacked);
+            }
+
+            Ok(())
+        })
+    }
+
+    #[tokio::test]
+    async fn test_pull_entries_resumes_after_partial_unpack() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test_async(|repo| async move {
+            for name in ["a.txt", "b.txt", "c.txt", "d.txt"] {
+                test::write_txt_file_to_path(repo.path.join(name), name)?;
+            }
+            command::add(&repo, &repo.path)?;
+            let commit = command::commit(&repo, "Adding files")?;
+            let commit_entries = CommitEntryReader::new(&repo, &commit)?.list_entries()?;
+            let entries: Vec<Entry> = commit_entries.iter().cloned().map(Entry::from).collect();
+
+            let mut server = mockito::Server::new_async().await;
+            let server_url = server.url();
+
+            let namespace = constants::DEFAULT_NAMESPACE;
+            let name = repo.dirname();
+
+            let mut remote_repo = test::create_remote_repo(&repo).await?;
+            let original_remote_url = remote_repo.remote.url;
+            remote_repo.remote.url = format!("{server_url}/{namespace}/{name}");
+
+            // Only the entries that did not make it to the working dir should be requested
+            let remaining: Vec<Entry> = entries
+                .iter()
+                .filter(|entry| ["c.txt", "d.txt"].contains(&entry.path().to_str().unwrap()))
+                .cloned()
+                .collect();
+            let content_ids = super::version_dir_paths_from_small_entries(&remaining, &repo.path);
+
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+            for (content_id, _) in content_ids.iter() {
+                encoder.write_all(format!("{content_id}\n").as_bytes())?;
+            }
+            let request_body = encoder.finish()?;
+
+            let mut tar = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+            for (content_id, version_path) in content_ids.iter() {
+                tar.append_path_with_name(repo.path.join(version_path), content_id)?;
+            }
+            let response_body = tar.into_inner()?.finish()?;
+
+            let mock_versions = server
+                .mock(
+                    "GET",
+                    &format!("/api/repos/{namespace}/{name}/versions")[..],
+                )
+                .match_body(request_body)
+                .with_body(response_body)
+                .expect(1)
+                .create_async()
+                .await;
+
+            // Interrupt the pull part way through unpacking, the versions dir was cleaned up
+            // while c.txt never got written and d.txt was only partially written
+            for entry in commit_entries.iter() {
+                util::fs::remove_file(util::fs::version_path(&repo, entry))?;
+            }
+            util::fs::remove_file(repo.path.join("c.txt"))?;
+            test::write_txt_file_to_path(repo.path.join("d.txt"), "partially written")?;
+
+            // Restart the pull
+            super::pull_entries_to_versions_dir(&remote_repo, &entries, &repo.path).await?;
+            mock_versions.assert_async().await;
+
+            for entry in commit_entries.iter() {
+                let version_path = util::fs::version_path(&repo, entry);
+                assert_eq!(util::hasher::hash_file_contents(&version_path)?, entry.hash);
+            }
+
+            // cleanup
+            remote_repo.remote.url = original_remote_url;
+            api::remote::repositories::delete(&remote_repo).await?;
+            Ok(())
+        })
+        .await
+    }
+}
@@ -5,7 +5,7 @@ use crate::api::local::entries::compute_generic_entries_size;
 use crate::api::remote::commits::ChunkParams;
 use crate::model::entry::commit_entry::{Entry, SchemaEntry};
 use crate::util::concurrency;
-use crate::util::progress_bar::{oxen_progress_bar_with_msg, spinner_with_msg, ProgressBarType};
+use crate::util::progress_bar::{oxen_progress_bar_with_msg, spinner_with_msg, ProgressReporter};
 
 use flate2::write::GzEncoder;
 use flate2::Compression;
@@ -20,11 +20,10 @@ use tokio::time::Duration;
 
 use crate::constants::{self, AVG_CHUNK_SIZE, NUM_HTTP_RETRIES};
 
-use crate::core::index::{self, CommitReader, Merger};
+use crate::core::index::{self, oxenattributes, CommitReader, Merger};
 use crate::error::OxenError;
 use crate::model::{Branch, Commit, LocalRepository, RemoteBranch, RemoteRepository};
 
-use crate::util::progress_bar::oxen_progress_bar;
 use crate::{api, util};
 
 #[derive(Debug)]
@@ -65,11 +64,18 @@ async fn validate_repo_is_pushable(
     branch: &Branch,
     commit_reader: &CommitReader,
     head_commit: &Commit,
+    compression: Compression,
 ) -> Result<bool, OxenError> {
     if remote_is_ahead_of_local(head_commit, remote_repo, commit_reader, branch).await? {
         log::debug!("remote is ahead of local for commit {:#?}", head_commit);
-        if api::remote::commits::can_push(remote_repo, &branch.name, local_repo, head_commit)
-            .await?
+        if api::remote::commits::can_push(
+            remote_repo,
+            &branch.name,
+            local_repo,
+            head_commit,
+            compression,
+        )
+        .await?
         {
             // log::debug!("can_push is true for commit {:#?}", head_commit);
             return Ok(true); // We need a merge commit
@@ -86,6 +92,17 @@ pub async fn push_remote_repo(
     local_repo: &LocalRepository,
     remote_repo: RemoteRepository,
     branch: Branch,
+) -> Result<RemoteRepository, OxenError> {
+    let compression = util::compression::push_compression();
+    push_remote_repo_with_compression(local_repo, remote_repo, branch, compression).await
+}
+
+/// Push the branch, building the tarballs sent to the remote with the given gzip compression
+pub async fn push_remote_repo_with_compression(
+    local_repo: &LocalRepository,
+    remote_repo: RemoteRepository,
+    branch: Branch,
+    compression: Compression,
 ) -> Result<RemoteRepository, OxenError> {
     // Lock the branch at the top, to avoid collisions from true simultaneous push
     // Returns a `remote_branch_locked` error if lock is already held
@@ -107,6 +124,7 @@ pub async fn push_remote_repo(
         &branch,
         &commit_reader,
         &head_commit,
+        compression,
     )
     .await
     {
@@ -136,7 +154,7 @@ pub async fn push_remote_repo(
     // IF we've added commits to the queue, should we cede control of lock removal to when the queue is finished processing?
     let head_commit_clone = head_commit.clone();
     tokio::select! {
-        result = try_push_remote_repo(local_repo, &remote_repo, branch, head_commit, requires_merge) => {
+        result = try_push_remote_repo(local_repo, &remote_repo, branch, head_commit, requires_merge, compression) => {
             match result {
                 Ok(_) => {
                     // Unlock the branch
@@ -172,6 +190,7 @@ pub async fn try_push_remote_repo(
     branch: Branch,
     mut head_commit: Commit,
     requires_merge: bool,
+    compression: Compression,
 ) -> Result<(), OxenError> {
     let commits_to_push =
         get_commit_objects_to_sync(local_repo, remote_repo, &head_commit, &branch).await?;
@@ -196,10 +215,15 @@ pub async fn try_push_remote_repo(
     let unsynced_db_commits =
         api::remote::commits::get_commits_with_unsynced_dbs(remote_repo, &branch).await?;
 
-    api::remote::commits::post_tree_objects_to_server(local_repo, remote_repo, &head_commit)
-        .await?;
+    api::remote::commits::post_tree_objects_to_server(
+        local_repo,
+        remote_repo,
+        &head_commit,
+        compression,
+    )
+    .await?;
 
-    push_missing_commit_dbs(local_repo, remote_repo, unsynced_db_commits).await?;
+    push_missing_commit_dbs(local_repo, remote_repo, unsynced_db_commits, compression).await?;
 
     // Get commits with unsynced entries
     let mut unsynced_entries_commits =
@@ -215,6 +239,7 @@ pub async fn try_push_remote_repo(
         remote_repo,
         &unsynced_entries_commits,
         unsynced_entries,
+        compression,
     )
     .await?;
 
@@ -273,7 +298,7 @@ pub async fn try_push_remote_repo(
     Ok(())
 }
 
-async fn get_commit_objects_to_sync(
+pub async fn get_commit_objects_to_sync(
     local_repo: &LocalRepository,
     remote_repo: &RemoteRepository,
     local_commit: &Commit,
@@ -567,6 +592,7 @@ async fn push_missing_commit_dbs(
     local_repo: &LocalRepository,
     remote_repo: &RemoteRepository,
     unsynced_commits: Vec<Commit>,
+    compression: Compression,
 ) -> Result<(), OxenError> {
     let pieces_of_work = unsynced_commits.len();
 
@@ -629,6 +655,7 @@ async fn push_missing_commit_dbs(
                         &local_repo,
                         &remote_repo,
                         commit,
+                        compression,
                     )
                     .await
                     {
@@ -666,6 +693,7 @@ async fn push_missing_commit_entries(
     remote_repo: &RemoteRepository,
     commits: &Vec<Commit>,
     mut unsynced_entries: Vec<UnsyncedCommitEntries>,
+    compression: Compression,
 ) -> Result<(), OxenError> {
     // If no commits, nothing to do here. If no entries, but still commits to sync, need to do this step
     // TODO: maybe factor validation into a separate fourth step so that this can be skipped if no entries
@@ -734,15 +762,17 @@ async fn push_missing_commit_entries(
             entries: unsynced_entries,
         };
 
-        let bar = oxen_progress_bar(total_size, ProgressBarType::Bytes);
+        let progress = ProgressReporter::new("push", total_size, all_entries.entries.len() as u64);
         push_entries(
             local_repo,
             remote_repo,
             &all_entries.entries,
             &all_entries.commit,
-            &bar,
+            &progress,
+            compression,
         )
         .await?;
+        progress.finish();
     } else {
         println!("🐂 No entries to push");
     }
@@ -756,7 +786,8 @@ async fn push_entries(
     remote_repo: &RemoteRepository,
     entries: &[Entry],
     commit: &Commit,
-    bar: &Arc<ProgressBar>,
+    progress: &Arc<ProgressReporter>,
+    compression: Compression,
 ) -> Result<(), OxenError> {
     log::debug!(
         "PUSH ENTRIES {} -> {} -> '{}'",
@@ -767,35 +798,39 @@ async fn push_entries(
     // Some files may be much larger than others....so we can't just zip them up and send them
     // since bodies will be too big. Hence we chunk and send the big ones, and bundle and send the small ones
 
-    // For files smaller than AVG_CHUNK_SIZE, we are going to group them, zip them up, and transfer them
+    // The chunk size defaults to AVG_CHUNK_SIZE but can be set per path in .oxenattributes
+    let attributes = oxenattributes::load(local_repo);
+    let chunk_size = |e: &Entry| {
+        attributes
+            .for_path(e.path())
+            .chunk_size
+            .unwrap_or(AVG_CHUNK_SIZE)
+    };
+
+    // For files up to their chunk size, we are going to group them, zip them up, and transfer them
     let smaller_entries: Vec<Entry> = entries
         .iter()
-        .filter(|e| e.num_bytes() < AVG_CHUNK_SIZE)
+        .filter(|e| e.num_bytes() <= chunk_size(e))
         .map(|e| e.to_owned())
         .collect();
 
-    // For files larger than AVG_CHUNK_SIZE, we are going break them into chunks and send the chunks in parallel
-    let larger_entries: Vec<Entry> = entries
+    // For files larger than their chunk size, we are going break them into chunks and send the chunks in parallel
+    let larger_entries: Vec<(Entry, u64)> = entries
         .iter()
-        .filter(|e| e.num_bytes() > AVG_CHUNK_SIZE)
-        .map(|e| e.to_owned())
+        .filter(|e| e.num_bytes() > chunk_size(e))
+        .map(|e| (e.to_owned(), chunk_size(e)))
         .collect();
 
-    let large_entries_sync = chunk_and_send_large_entries(
-        local_repo,
-        remote_repo,
-        larger_entries,
-        commit,
-        AVG_CHUNK_SIZE,
-        bar,
-    );
+    let large_entries_sync =
+        chunk_and_send_large_entries(local_repo, remote_repo, larger_entries, commit, progress);
     let small_entries_sync = bundle_and_send_small_entries(
         local_repo,
         remote_repo,
         smaller_entries,
         commit,
         AVG_CHUNK_SIZE,
-        bar,
+        progress,
+        compression,
     );
 
     match tokio::join!(large_entries_sync, small_entries_sync) {
@@ -818,10 +853,9 @@ async fn push_entries(
 async fn chunk_and_send_large_entries(
     local_repo: &LocalRepository,
     remote_repo: &RemoteRepository,
-    entries: Vec<Entry>,
+    entries: Vec<(Entry, u64)>,
     commit: &Commit,
-    chunk_size: u64,
-    bar: &Arc<ProgressBar>,
+    progress: &Arc<ProgressReporter>,
 ) -> Result<(), OxenError> {
     if entries.is_empty() {
         return Ok(());
@@ -830,10 +864,11 @@ async fn chunk_and_send_large_entries(
     use tokio::time::sleep;
     type PieceOfWork = (
         Entry,
+        u64, // chunk size
         LocalRepository,
         Commit,
         RemoteRepository,
-        Arc<ProgressBar>,
+        Arc<ProgressReporter>,
     );
     type TaskQueue = deadqueue::limited::Queue<PieceOfWork>;
     type FinishedTaskQueue = deadqueue::limited::Queue<bool>;
@@ -841,13 +876,14 @@ async fn chunk_and_send_large_entries(
     log::debug!("Chunking and sending {} larger files", entries.len());
     let entries: Vec<PieceOfWork> = entries
         .iter()
-        .map(|e| {
+        .map(|(e, chunk_size)| {
             (
                 e.to_owned(),
+                *chunk_size,
                 local_repo.to_owned(),
                 commit.to_owned(),
                 remote_repo.to_owned(),
-                bar.to_owned(),
+                progress.to_owned(),
             )
         })
         .collect();
@@ -870,10 +906,12 @@ async fn chunk_and_send_large_entries(
         let finished_queue = finished_queue.clone();
         tokio::spawn(async move {
             loop {
-                let (entry, repo, commit, remote_repo, bar) = queue.pop().await;
+                let (entry, chunk_size, repo, commit, remote_repo, progress) = queue.pop().await;
                 log::debug!("worker[{}] processing task...", worker);
 
-                upload_large_file_chunks(entry, repo, commit, remote_repo, chunk_size, &bar).await;
+                upload_large_file_chunks(entry, repo, commit, remote_repo, chunk_size, &progress)
+                    .await;
+                progress.inc_entries(1);
 
                 finished_queue.pop().await;
             }
@@ -899,7 +937,7 @@ async fn upload_large_file_chunks(
     commit: Commit,
     remote_repo: RemoteRepository,
     chunk_size: u64,
-    bar: &Arc<ProgressBar>,
+    progress: &Arc<ProgressReporter>,
 ) {
     // Open versioned file
     let version_path = util::fs::version_path_for_entry(&repo, &entry);
@@ -921,7 +959,6 @@ async fn upload_large_file_chunks(
     // Create queues for sending data to workers
     type PieceOfWork = (
         Vec<u8>,
-        u64,   // chunk size
         usize, // chunk num
         usize, // total chunks
         u64,   // total size
@@ -937,8 +974,8 @@ async fn upload_large_file_chunks(
     let sub_chunk_size = constants::DEFAULT_NUM_WORKERS;
 
     // Just get the progress bar on the screen
-    bar.enable_steady_tick(Duration::from_secs(1));
-    bar.inc(0);
+    progress.enable_steady_tick(Duration::from_secs(1));
+    progress.inc_bytes(0);
 
     let mut total_chunk_idx = 0;
     let mut processed_chunk_idx = 0;
@@ -1002,7 +1039,6 @@ async fn upload_large_file_chunks(
         for buffer in sub_buffers.iter() {
             tasks.push((
                 buffer.to_owned(),
-                chunk_size,
                 processed_chunk_idx, // Needs to be the overall chunk num
                 total_chunks,
                 total_bytes,
@@ -1020,7 +1056,6 @@ async fn upload_large_file_chunks(
             .map(|item| async move {
                 let (
                     buffer,
-                    chunk_size,
                     chunk_num,
                     total_chunks,
                     total_size,
@@ -1061,7 +1096,7 @@ async fn upload_large_file_chunks(
                             chunk_num,
                             total_chunks
                         );
-                        Ok(chunk_size)
+                        Ok(size)
                     }
                     Err(err) => {
                         log::error!("Error uploading chunk: {:?}", err);
@@ -1075,8 +1110,8 @@ async fn upload_large_file_chunks(
         bodies
             .for_each(|b| async {
                 match b {
-                    Ok(_) => {
-                        bar.inc(chunk_size);
+                    Ok(size) => {
+                        progress.inc_bytes(size);
                     }
                     Err(err) => {
                         log::error!("Error uploading chunk: {:?}", err)
@@ -1096,7 +1131,8 @@ async fn bundle_and_send_small_entries(
     entries: Vec<Entry>,
     commit: &Commit,
     avg_chunk_size: u64,
-    bar: &Arc<ProgressBar>,
+    progress: &Arc<ProgressReporter>,
+    compression: Compression,
 ) -> Result<(), OxenError> {
     if entries.is_empty() {
         return Ok(());
@@ -1118,7 +1154,7 @@ async fn bundle_and_send_small_entries(
         LocalRepository,
         Commit,
         RemoteRepository,
-        Arc<ProgressBar>,
+        Arc<ProgressReporter>,
     );
     type TaskQueue = deadqueue::limited::Queue<PieceOfWork>;
     type FinishedTaskQueue = deadqueue::limited::Queue<bool>;
@@ -1132,7 +1168,7 @@ async fn bundle_and_send_small_entries(
                 local_repo.to_owned(),
                 commit.to_owned(),
                 remote_repo.to_owned(),
-                bar.to_owned(),
+                progress.to_owned(),
             )
         })
         .collect();
@@ -1150,10 +1186,11 @@ async fn bundle_and_send_small_entries(
         let finished_queue = finished_queue.clone();
         tokio::spawn(async move {
             loop {
-                let (chunk, repo, commit, remote_repo, bar) = queue.pop().await;
+                let (chunk, repo, commit, remote_repo, progress) = queue.pop().await;
                 log::debug!("worker[{}] processing task...", worker);
+                let num_entries = chunk.len() as u64;
 
-                let enc = GzEncoder::new(Vec::new(), Compression::default());
+                let enc = GzEncoder::new(Vec::new(), compression);
                 let mut tar = tar::Builder::new(enc);
                 log::debug!("Chunk size {}", chunk.len());
                 let chunk_size = match compute_generic_entries_size(&chunk) {
@@ -1212,7 +1249,8 @@ async fn bundle_and_send_small_entries(
                         log::error!("Error uploading chunk: {:?}", err)
                     }
                 }
-                bar.inc(chunk_size);
+                progress.inc_bytes(chunk_size);
+                progress.inc_entries(num_entries);
                 finished_queue.pop().await;
             }
         });
@@ -1231,6 +1269,8 @@ async fn bundle_and_send_small_entries(
 
 #[cfg(test)]
 mod tests {
+    use flate2::Compression;
+
     use crate::api;
     use crate::command;
     use crate::constants;
@@ -1240,6 +1280,7 @@ mod tests {
 
     use crate::opts::RmOpts;
     use crate::util;
+    use crate::util::progress_bar::{ProgressEvent, ProgressMode, ProgressReporter};
 
     use crate::test;
 
@@ -1316,6 +1357,57 @@ mod tests {
         .await
     }
 
+    #[tokio::test]
+    async fn test_push_reports_unsynced_commits() -> Result<(), OxenError> {
+        test::run_training_data_fully_sync_remote(|local_repo, remote_repo| async move {
+            let branch = api::local::branches::current_branch(&local_repo)?.unwrap();
+
+            // Everything is synced, nothing to report
+            let head_commit = api::local::commits::head_commit(&local_repo)?;
+            let unsynced_commits = pusher::get_commit_objects_to_sync(
+                &local_repo,
+                &remote_repo,
+                &head_commit,
+                &branch,
+            )
+            .await?;
+            let mut writer = test::SharedWriter::default();
+            command::push::write_unsynced_commits(&mut writer, &unsynced_commits)?;
+            assert_eq!(writer.contents(), "");
+
+            // Two new local commits are the only ones reported
+            let readme_path = local_repo.path.join("README.md");
+            let readme_path = test::modify_txt_file(readme_path, "I am the readme now.")?;
+            command::add(&local_repo, &readme_path)?;
+            let first = command::commit(&local_repo, "Changed the readme")?;
+            let notes_path = local_repo.path.join("notes.txt");
+            util::fs::write_to_path(&notes_path, "some notes")?;
+            command::add(&local_repo, &notes_path)?;
+            let second = command::commit(&local_repo, "Adding notes\n\nWith a longer body")?;
+
+            let unsynced_commits =
+                pusher::get_commit_objects_to_sync(&local_repo, &remote_repo, &second, &branch)
+                    .await?;
+            let mut writer = test::SharedWriter::default();
+            command::push::write_unsynced_commits(&mut writer, &unsynced_commits)?;
+
+            let contents = writer.contents();
+            let lines: Vec<&str> = contents.lines().collect();
+            assert_eq!(lines[0], "🐂 Pushing 2 commit(s) missing on the remote");
+            let reported: Vec<&str> = lines[1..]
+                .iter()
+                .map(|line| line.split_whitespace().next().unwrap())
+                .collect();
+            let expected: Vec<&str> = unsynced_commits.iter().map(|c| c.id.as_str()).collect();
+            assert_eq!(reported, expected);
+            assert_eq!(reported, vec![first.id.as_str(), second.id.as_str()]);
+            assert_eq!(lines[2], format!("  {} Adding notes", second.id));
+
+            Ok(remote_repo)
+        })
+        .await
+    }
+
     #[tokio::test]
     async fn test_push_missing_commit_dbs() -> Result<(), OxenError> {
         test::run_training_data_repo_test_fully_committed_async(|mut repo| async move {
@@ -1344,7 +1436,13 @@ mod tests {
             assert_eq!(unsynced_db_commits.len(), 1);
 
             // Push to the remote
-            pusher::push_missing_commit_dbs(&repo, &remote_repo, unsynced_db_commits).await?;
+            pusher::push_missing_commit_dbs(
+                &repo,
+                &remote_repo,
+                unsynced_db_commits,
+                Compression::default(),
+            )
+            .await?;
 
             // All commits should now have dbs
             let unsynced_db_commits =
@@ -1381,7 +1479,13 @@ mod tests {
             // Get missing commit dbs and push
             let unsynced_db_commits =
                 api::remote::commits::get_commits_with_unsynced_dbs(&remote_repo, &branch).await?;
-            pusher::push_missing_commit_dbs(&repo, &remote_repo, unsynced_db_commits).await?;
+            pusher::push_missing_commit_dbs(
+                &repo,
+                &remote_repo,
+                unsynced_db_commits,
+                Compression::default(),
+            )
+            .await?;
 
             // 2 commit should be missing - commit object and db created on repo creation, but entries not synced
             let unsynced_entries_commits =
@@ -1510,4 +1614,63 @@ mod tests {
         })
         .await
     }
+
+    #[tokio::test]
+    async fn test_push_entries_json_progress() -> Result<(), OxenError> {
+        test::run_training_data_fully_sync_remote(|local_repo, remote_repo| async move {
+            // Add a file larger than a chunk and a small one so both upload paths report
+            let large_path = local_repo.path.join("large.txt");
+            let line = "all work and no play makes oxen a dull ox\n";
+            let contents = line.repeat((constants::AVG_CHUNK_SIZE as usize / line.len()) + 100);
+            util::fs::write_to_path(&large_path, &contents)?;
+            let small_path = local_repo.path.join("small.txt");
+            util::fs::write_to_path(&small_path, "just a little one")?;
+            command::add(&local_repo, &large_path)?;
+            command::add(&local_repo, &small_path)?;
+            let commit = command::commit(&local_repo, "Adding a large and a small file")?;
+
+            let commit_reader = CommitReader::new(&local_repo)?;
+            let (unsynced, _) =
+                pusher::get_unsynced_entries_for_commit(&local_repo, &commit, &commit_reader)?;
+            let entries: Vec<_> = unsynced.into_iter().flat_map(|u| u.entries).collect();
+            let total_size = api::local::entries::compute_generic_entries_size(&entries)?;
+
+            let writer = test::SharedWriter::default();
+            let progress = ProgressReporter::with_writer(
+                "push",
+                total_size,
+                entries.len() as u64,
+                ProgressMode::Json,
+                Box::new(writer.clone()),
+            );
+            pusher::push_entries(
+                &local_repo,
+                &remote_repo,
+                &entries,
+                &commit,
+                &progress,
+                Compression::default(),
+            )
+            .await?;
+            progress.finish();
+
+            let events: Vec<ProgressEvent> = writer
+                .contents()
+                .lines()
+                .map(serde_json::from_str)
+                .collect::<Result<_, _>>()?;
+            assert!(!events.is_empty());
+            for pair in events.windows(2) {
+                assert!(pair[0].bytes_done <= pair[1].bytes_done);
+            }
+            let last = events.last().unwrap();
+            assert_eq!(last.phase, "push");
+            assert_eq!(last.bytes_total, total_size);
+            assert_eq!(last.bytes_done, total_size);
+            assert_eq!(last.entries_done, entries.len() as u64);
+
+            Ok(remote_repo)
+        })
+        .await
+    }
 }
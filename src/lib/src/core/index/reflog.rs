@@ -0,0 +1,77 @@
+//! Record where HEAD has pointed, so commits and branches that were moved away from can be
+//! found again with `oxen reflog`. Each movement is a json line in `.oxen/logs/HEAD`.
+//!
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+
+use time::OffsetDateTime;
+
+use crate::api;
+use crate::constants::{HEAD_LOG_FILE, LOGS_DIR};
+use crate::error::OxenError;
+use crate::model::{LocalRepository, ReflogEntry};
+use crate::util;
+
+fn log_path(repo: &LocalRepository) -> PathBuf {
+    util::fs::oxen_hidden_dir(&repo.path)
+        .join(LOGS_DIR)
+        .join(HEAD_LOG_FILE)
+}
+
+/// The commit HEAD points at, None if there are no commits yet
+pub fn head_commit_id(repo: &LocalRepository) -> Option<String> {
+    api::local::commits::head_commit(repo)
+        .ok()
+        .map(|commit| commit.id)
+}
+
+/// Append a movement of HEAD to the log
+pub fn append(
+    repo: &LocalRepository,
+    old_commit_id: Option<String>,
+    new_commit_id: impl AsRef<str>,
+    message: impl AsRef<str>,
+) -> Result<(), OxenError> {
+    let entry = ReflogEntry {
+        old_commit_id,
+        new_commit_id: new_commit_id.as_ref().to_string(),
+        message: message.as_ref().to_string(),
+        timestamp: OffsetDateTime::now_utc(),
+    };
+
+    let path = log_path(repo);
+    if let Some(parent) = path.parent() {
+        util::fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(&entry)?)?;
+    Ok(())
+}
+
+/// Same as `append` but only warns on failure, for when HEAD has already moved
+pub fn record(
+    repo: &LocalRepository,
+    old_commit_id: Option<String>,
+    new_commit_id: impl AsRef<str>,
+    message: impl AsRef<str>,
+) {
+    if let Err(err) = append(repo, old_commit_id, new_commit_id, message) {
+        log::warn!("Could not write to the reflog: {err}");
+    }
+}
+
+/// Every movement of HEAD, oldest first
+pub fn list(repo: &LocalRepository) -> Result<Vec<ReflogEntry>, OxenError> {
+    let path = log_path(repo);
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    let contents = util::fs::read_from_path(&path)?;
+    let mut entries: Vec<ReflogEntry> = vec![];
+    for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+        entries.push(serde_json::from_str(line)?);
+    }
+    Ok(entries)
+}
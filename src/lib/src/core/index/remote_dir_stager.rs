@@ -6,7 +6,7 @@ use time::OffsetDateTime;
 
 use crate::api;
 use crate::constants;
-use crate::constants::{OXEN_HIDDEN_DIR, STAGED_DIR};
+use crate::constants::STAGED_DIR;
 use crate::core::index;
 use crate::core::index::CommitEntryReader;
 use crate::core::index::SchemaReader;
@@ -19,6 +19,7 @@ use crate::model::NewCommit;
 use crate::model::NewCommitBody;
 use crate::model::StagedData;
 use crate::util;
+use crate::view::remote_staged_status::{IdentifierStagedStatus, StagingConflict};
 
 use super::CommitWriter;
 
@@ -29,8 +30,7 @@ pub fn branch_staging_dir(repo: &LocalRepository, branch: &Branch, user_id: &str
     // Just in case they pass in the email or some other random string, hash it for nice dir name
     // This does double-hash right now, since `identifier` is already hashed
     let user_id_hash = util::hasher::hash_str_sha256(user_id);
-    repo.path
-        .join(OXEN_HIDDEN_DIR)
+    util::fs::oxen_hidden_dir(&repo.path)
         .join(STAGED_DIR)
         .join(&branch.name)
         .join(user_id_hash)
@@ -70,7 +70,7 @@ pub fn p_init_or_get(
     user_id: &str,
 ) -> Result<LocalRepository, OxenError> {
     let staging_dir = branch_staging_dir(repo, branch, user_id);
-    let oxen_dir = staging_dir.join(OXEN_HIDDEN_DIR);
+    let oxen_dir = util::fs::oxen_hidden_dir(&staging_dir);
     let branch_repo = if oxen_dir.exists() {
         log::debug!("stage_file Already have oxen repo 🐂");
         if local_staging_dir_is_up_to_date(repo, &staging_dir, branch)? {
@@ -90,6 +90,12 @@ pub fn p_init_or_get(
         api::local::branches::create_checkout(&branch_repo, &branch.name)?;
     }
 
+    // The dir name is a hash, so remember who it belongs to for list_all_staged
+    let identifier_file = oxen_dir.join(constants::STAGING_IDENTIFIER_FILE);
+    if !identifier_file.exists() {
+        util::fs::write_to_path(&identifier_file, user_id)?;
+    }
+
     Ok(branch_repo)
 }
 
@@ -117,8 +123,8 @@ pub fn init_local_repo_staging_dir(
     staging_dir: &Path,
     should_clear: bool,
 ) -> Result<LocalRepository, OxenError> {
-    let oxen_hidden_dir = repo.path.join(constants::OXEN_HIDDEN_DIR);
-    let staging_oxen_dir = staging_dir.join(constants::OXEN_HIDDEN_DIR);
+    let oxen_hidden_dir = util::fs::oxen_hidden_dir(&repo.path);
+    let staging_oxen_dir = util::fs::oxen_hidden_dir(staging_dir);
     log::debug!("Creating staging_oxen_dir: {staging_oxen_dir:?}");
     std::fs::create_dir_all(&staging_oxen_dir)?;
 
@@ -293,6 +299,104 @@ pub fn list_staged_data(
     }
 }
 
+/// List the files every identifier has staged on the branch, sorted by identifier.
+/// Identifiers with nothing staged are left out.
+pub fn list_all_staged(
+    repo: &LocalRepository,
+    branch: &Branch,
+) -> Result<Vec<IdentifierStagedStatus>, OxenError> {
+    let branch_dir = util::fs::oxen_hidden_dir(&repo.path)
+        .join(STAGED_DIR)
+        .join(&branch.name);
+    if !branch_dir.exists() {
+        return Ok(vec![]);
+    }
+
+    let mut statuses: Vec<IdentifierStagedStatus> = vec![];
+    for staging_dir in std::fs::read_dir(&branch_dir)?.flatten() {
+        let staging_dir = staging_dir.path();
+        let staging_oxen_dir = util::fs::oxen_hidden_dir(&staging_dir);
+        if !staging_oxen_dir.exists() {
+            continue;
+        }
+        let identifier_file = staging_oxen_dir.join(constants::STAGING_IDENTIFIER_FILE);
+        // Dirs staged before the identifier file existed only know the hash of their identifier
+        let identifier = if identifier_file.exists() {
+            util::fs::read_from_path(&identifier_file)?
+        } else {
+            staging_dir
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default()
+        };
+
+        let branch_repo = LocalRepository::new(&staging_dir)?;
+        let status = list_staged_data(repo, &branch_repo, branch, &identifier, Path::new("."))?;
+
+        let mut staged_files: Vec<PathBuf> = status.staged_files.keys().cloned().collect();
+        staged_files.sort();
+        let mut modified_files = status.modified_files;
+        modified_files.sort();
+        modified_files.dedup();
+        if staged_files.is_empty() && modified_files.is_empty() {
+            continue;
+        }
+
+        statuses.push(IdentifierStagedStatus {
+            identifier,
+            staged_files,
+            modified_files,
+        });
+    }
+    statuses.sort_by(|a, b| a.identifier.cmp(&b.identifier));
+    Ok(statuses)
+}
+
+/// Every file path `identifier` has staged on the branch, sorted so it can be paginated
+pub fn list_staged_files(
+    repo: &LocalRepository,
+    branch: &Branch,
+    identifier: &str,
+) -> Result<Vec<PathBuf>, OxenError> {
+    let branch_repo = init_or_get(repo, branch, identifier)?;
+    let status = list_staged_data(repo, &branch_repo, branch, identifier, Path::new("."))?;
+    let mut staged_files: Vec<PathBuf> = status.staged_files.keys().cloned().collect();
+    staged_files.sort();
+    Ok(staged_files)
+}
+
+/// Find the paths that identifiers other than `user_id` already have staged on the branch
+pub fn find_staging_conflicts(
+    repo: &LocalRepository,
+    branch: &Branch,
+    user_id: &str,
+    paths: &[PathBuf],
+) -> Result<Vec<StagingConflict>, OxenError> {
+    let user_id_hash = util::hasher::hash_str_sha256(user_id);
+    let others: Vec<IdentifierStagedStatus> = list_all_staged(repo, branch)?
+        .into_iter()
+        .filter(|status| status.identifier != user_id && status.identifier != user_id_hash)
+        .collect();
+
+    let mut conflicts: Vec<StagingConflict> = vec![];
+    for path in paths {
+        let identifiers: Vec<String> = others
+            .iter()
+            .filter(|status| {
+                status.staged_files.contains(path) || status.modified_files.contains(path)
+            })
+            .map(|status| status.identifier.to_owned())
+            .collect();
+        if !identifiers.is_empty() {
+            conflicts.push(StagingConflict {
+                path: path.to_owned(),
+                identifiers,
+            });
+        }
+    }
+    Ok(conflicts)
+}
+
 // Modifications to files are staged in a separate DB and applied on commit, so we fetch them from the mod_stager
 fn add_mod_entries(
     repo: &LocalRepository,
@@ -311,10 +415,11 @@ fn add_mod_entries(
 
 #[cfg(test)]
 mod tests {
-    use std::path::Path;
+    use std::path::{Path, PathBuf};
 
     use crate::api;
     use crate::config::UserConfig;
+    use crate::constants::{self, OXEN_HIDDEN_DIR};
     use crate::core::index;
     use crate::error::OxenError;
     use crate::model::NewCommitBody;
@@ -360,6 +465,65 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_remote_stager_list_all_staged() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let branch = api::local::branches::current_branch(&repo)?.unwrap();
+
+            // Two identifiers each stage their own file
+            let stage = |user_id: &str, path: &Path| -> Result<(), OxenError> {
+                let branch_dir =
+                    index::remote_dir_stager::branch_staging_dir(&repo, &branch, user_id);
+                let full_path = branch_dir.join(path);
+                std::fs::create_dir_all(full_path.parent().unwrap())?;
+                util::fs::write_to_path(&full_path, format!("Staged by {user_id}"))?;
+
+                let branch_repo = index::remote_dir_stager::init_or_get(&repo, &branch, user_id)?;
+                index::remote_dir_stager::stage_file(
+                    &repo,
+                    &branch_repo,
+                    &branch,
+                    user_id,
+                    &full_path,
+                )?;
+                Ok(())
+            };
+            stage("alice", Path::new("data/alice.txt"))?;
+            stage("bob", Path::new("bob.txt"))?;
+
+            let statuses = index::remote_dir_stager::list_all_staged(&repo, &branch)?;
+            assert_eq!(statuses.len(), 2);
+            assert_eq!(statuses[0].identifier, "alice");
+            assert_eq!(
+                statuses[0].staged_files,
+                vec![Path::new("data").join("alice.txt")]
+            );
+            assert_eq!(statuses[1].identifier, "bob");
+            assert_eq!(statuses[1].staged_files, vec![Path::new("bob.txt")]);
+
+            // Dirs from before the identifier file was written still count as conflicts
+            let bob_dir = index::remote_dir_stager::branch_staging_dir(&repo, &branch, "bob");
+            util::fs::remove_file(
+                bob_dir
+                    .join(OXEN_HIDDEN_DIR)
+                    .join(constants::STAGING_IDENTIFIER_FILE),
+            )?;
+            let conflicts = index::remote_dir_stager::find_staging_conflicts(
+                &repo,
+                &branch,
+                "alice",
+                &[PathBuf::from("bob.txt")],
+            )?;
+            assert_eq!(conflicts.len(), 1);
+            assert_eq!(
+                conflicts[0].identifiers,
+                vec![util::hasher::hash_str_sha256("bob")]
+            );
+
+            Ok(())
+        })
+    }
+
     #[test]
     fn test_remote_commit() -> Result<(), OxenError> {
         test::run_empty_local_repo_test(|repo| {
@@ -389,6 +553,8 @@ mod tests {
                 author: String::from("Test User"),
                 email: String::from("test@oxen.ai"),
                 message: String::from("I am committing this remote staged data"),
+                expected_parent_id: None,
+                idempotency_key: None,
             };
             index::remote_dir_stager::commit(&repo, &branch_repo, &branch, &new_commit, &user_id)?;
 
@@ -4,6 +4,7 @@
 //! (There is no option to remove a file only from the working tree and yet keep it in the index; use /bin/rm if you want to do that.)
 //! When --staged is given, the staged content has to match either the tip of the branch or the file on disk,
 //! allowing the file to be removed from just the index.
+//! When --cached is given, the removal of a committed file is staged but the working file is left on disk.
 
 use crate::api;
 use crate::command;
@@ -54,6 +55,10 @@ async fn rm_dir(repo: &LocalRepository, opts: &RmOpts) -> Result<(), OxenError>
         return Err(OxenError::basic_str(error));
     }
 
+    if opts.cached {
+        return remove_cached(repo, path);
+    }
+
     // Make sure there are no modified files in directory
     let modifications = list_modified_files_in_dir(repo, path)?;
     if !modifications.is_empty() {
@@ -122,6 +127,10 @@ fn rm_file(repo: &LocalRepository, opts: &RmOpts) -> Result<(), OxenError> {
         return Err(OxenError::basic_str(error));
     }
 
+    if opts.cached {
+        return remove_cached(repo, path);
+    }
+
     // Remove file from disk
     let full_path = repo.path.join(path);
     log::debug!("REMOVING FILE: {full_path:?}");
@@ -139,7 +148,11 @@ fn rm_file(repo: &LocalRepository, opts: &RmOpts) -> Result<(), OxenError> {
 async fn remove_remote(repo: &LocalRepository, opts: &RmOpts) -> Result<(), OxenError> {
     let path = opts.path.as_ref();
 
-    if opts.recursive {
+    if opts.cached {
+        Err(OxenError::basic_str(
+            "`oxen remote rm` does not support --cached",
+        ))
+    } else if opts.recursive {
         Err(OxenError::basic_str(
             "`oxen remote rm` does not support removing directories yet",
         ))
@@ -156,6 +169,14 @@ async fn remove_remote_staged_file(repo: &LocalRepository, path: &Path) -> Resul
     api::remote::staging::rm_file(&remote_repo, &branch_name, &user_id, path.to_path_buf()).await
 }
 
+/// Stage the removal without touching the working copy, so it shows up as untracked after commit
+fn remove_cached(repo: &LocalRepository, path: &Path) -> Result<(), OxenError> {
+    let commit = api::local::commits::head_commit(repo)?;
+    let commit_reader = CommitEntryReader::new(repo, &commit)?;
+    let stager = Stager::new(repo)?;
+    stager.add_removed_path(&repo.path.join(path), &commit_reader)
+}
+
 fn remove_staged(repo: &LocalRepository, opts: &RmOpts) -> Result<(), OxenError> {
     let path = opts.path.as_ref();
 
@@ -226,6 +247,7 @@ mod tests {
             let opts = RmOpts {
                 path: path.to_path_buf(),
                 staged: true,
+                cached: false,
                 recursive: false, // This should be an error
                 remote: false,
             };
@@ -251,6 +273,7 @@ mod tests {
             let opts = RmOpts {
                 path: path.to_path_buf(),
                 staged: true,
+                cached: false,
                 recursive: true, // make sure to pass in recursive
                 remote: false,
             };
@@ -279,6 +302,7 @@ mod tests {
             let opts = RmOpts {
                 path: path.to_path_buf(),
                 staged: true,
+                cached: false,
                 recursive: true, // make sure to pass in recursive
                 remote: false,
             };
@@ -327,6 +351,7 @@ mod tests {
             let opts = RmOpts {
                 path: path.to_path_buf(),
                 staged: false,
+                cached: false,
                 recursive: false, // This should be an error
                 remote: false,
             };
@@ -348,6 +373,7 @@ mod tests {
             let opts = RmOpts {
                 path: train_dir.to_path_buf(),
                 staged: false,
+                cached: false,
                 recursive: true, // Need to specify recursive
                 remote: false,
             };
@@ -369,6 +395,7 @@ mod tests {
             let opts = RmOpts {
                 path: train_dir.to_path_buf(),
                 staged: false,
+                cached: false,
                 recursive: true, // Need to specify recursive
                 remote: false,
             };
@@ -406,6 +433,7 @@ mod tests {
             let opts = RmOpts {
                 path: path.to_path_buf(),
                 staged: false,
+                cached: false,
                 recursive: true, // Must pass in recursive = true
                 remote: false,
             };
@@ -442,6 +470,7 @@ mod tests {
             let opts = RmOpts {
                 path: path.to_path_buf(),
                 staged: false,
+                cached: false,
                 recursive: true, // Must pass in recursive = true
                 remote: false,
             };
@@ -470,6 +499,7 @@ mod tests {
             let opts = RmOpts {
                 path,
                 staged: false,
+                cached: false,
                 recursive: true, // Must pass in recursive = true
                 remote: false,
             };
@@ -487,4 +517,36 @@ mod tests {
         })
         .await
     }
+
+    #[tokio::test]
+    async fn test_rm_cached_file_keeps_working_copy() -> Result<(), OxenError> {
+        test::run_select_data_repo_test_committed_async("README", |repo| async move {
+            let path = Path::new("README.md");
+            let full_path = repo.path.join(path);
+
+            let mut opts = RmOpts::from_path(path);
+            opts.cached = true;
+            rm::rm(&repo, &opts).await?;
+
+            // The removal is staged but the file is still on disk
+            let status = command::status(&repo)?;
+            assert_eq!(
+                status.staged_files.get(path).unwrap().status,
+                StagedEntryStatus::Removed
+            );
+            assert!(full_path.exists());
+
+            let commit = command::commit(&repo, "Stop tracking the README")?;
+            let commit_reader = CommitEntryReader::new(&repo, &commit)?;
+            assert!(!commit_reader.has_file(path));
+
+            // Still on disk, now untracked
+            assert!(full_path.exists());
+            let status = command::status(&repo)?;
+            assert!(status.untracked_files.contains(&path.to_path_buf()));
+
+            Ok(())
+        })
+        .await
+    }
 }
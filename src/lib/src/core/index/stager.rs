@@ -9,7 +9,9 @@ use crate::constants;
 use crate::core::db::path_db;
 use crate::core::db::{self, str_json_db};
 use crate::core::df::tabular;
+use crate::core::index::oxenattributes::{self, OxenAttributes};
 use crate::core::index::oxenignore;
+use crate::core::index::placeholders;
 use crate::core::index::ObjectDBReader;
 use crate::core::index::SchemaReader;
 use crate::core::index::{
@@ -60,6 +62,7 @@ pub struct Stager {
     schemas_db: DBWithThreadMode<MultiThreaded>,
     pub repository: LocalRepository,
     merger: Option<Merger>,
+    attributes: OxenAttributes,
 }
 
 impl Stager {
@@ -97,6 +100,7 @@ impl Stager {
             schemas_db: DBWithThreadMode::open(&opts, dunce::simplified(&schemas_db_path))?,
             repository: repository.clone(),
             merger: None,
+            attributes: oxenattributes::load(repository),
         })
     }
 
@@ -110,6 +114,7 @@ impl Stager {
             schemas_db: DBWithThreadMode::open(&opts, dunce::simplified(&schemas_db_path))?,
             repository: repository.clone(),
             merger: Some(Merger::new(&repository.clone())?),
+            attributes: oxenattributes::load(repository),
         })
     }
 
@@ -159,7 +164,7 @@ impl Stager {
 
         log::debug!("Stager.add() is_dir? {} path: {:?}", path.is_dir(), path);
         if path.is_dir() {
-            match self.add_dir(path, commit_reader) {
+            match self.add_dir_with_ignore(path, commit_reader, ignore) {
                 Ok(_) => Ok(()),
                 Err(err) => Err(err),
             }
@@ -350,6 +355,12 @@ impl Stager {
             )?;
         }
 
+        // Placeholders from a clone with an lfs threshold are missing on purpose
+        let placeholders = placeholders::list(&self.repository)?;
+        staged_data
+            .removed_files
+            .retain(|path| !placeholders.contains(path));
+
         // Make pairs from Added + Removed stage entries with same hash, store in staged_data.moved_entries
         self.find_moved_files(&mut staged_data)?;
 
@@ -859,6 +870,19 @@ impl Stager {
         Ok(())
     }
 
+    /// Stage the removal of a committed file or directory, leaving whatever is on disk in place
+    pub fn add_removed_path(
+        &self,
+        path: &Path,
+        commit_reader: &CommitEntryReader,
+    ) -> Result<(), OxenError> {
+        if self.repository.is_shallow_clone() {
+            return Err(OxenError::repo_is_shallow());
+        }
+
+        self.process_removed_file_or_dir(path, commit_reader)
+    }
+
     fn process_removed_file_or_dir(
         &self,
         path: &Path,
@@ -990,6 +1014,16 @@ impl Stager {
     }
 
     pub fn add_dir(&self, dir: &Path, entry_reader: &CommitEntryReader) -> Result<(), OxenError> {
+        self.add_dir_with_ignore(dir, entry_reader, &None)
+    }
+
+    /// Add the untracked and modified files in a dir, skipping any that match the ignore patterns
+    pub fn add_dir_with_ignore(
+        &self,
+        dir: &Path,
+        entry_reader: &CommitEntryReader,
+        ignore: &Option<Gitignore>,
+    ) -> Result<(), OxenError> {
         if !dir.exists() || !dir.is_dir() {
             let err = format!("Cannot stage non-existant dir: {dir:?}");
             return Err(OxenError::basic_str(err));
@@ -1002,7 +1036,14 @@ impl Stager {
         // log::debug!("Stager.add_dir added path {short_path:?}");
 
         // Add all untracked files and modified files
-        let (dir_paths, total) = self.list_unstaged_files_in_dir(dir);
+        let (mut dir_paths, mut total) = self.list_unstaged_files_in_dir(dir);
+        if let Some(ignore) = ignore {
+            total = 0;
+            for paths in dir_paths.values_mut() {
+                paths.retain(|path| !ignore.matched_path_or_any_parents(path, false).is_ignore());
+                total += paths.len();
+            }
+        }
         // log::debug!("Stager.add_dir {:?} -> {}", dir, total);
         let schema_reader = SchemaReader::new(&self.repository, &entry_reader.commit_id)?;
         // println!("Adding files in directory: {short_path:?}");
@@ -1283,8 +1324,8 @@ impl Stager {
                 }
             }
 
-            // If tabular, add schema
-            if util::fs::is_tabular(path) {
+            // If tabular, add schema, unless .oxenattributes says not to look inside the file
+            if util::fs::is_tabular(path) && self.attributes.for_path(path).should_diff_contents() {
                 log::debug!(
                     "add_staged_entry_to_db is tabular! compute schema {:?}",
                     path
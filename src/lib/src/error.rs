@@ -48,6 +48,7 @@ pub enum OxenError {
     IncompleteLocalHistory(StringError),
     RemoteBranchLocked(StringError),
     UpstreamMergeConflict(StringError),
+    RequestTimeout(StringError),
 
     // Branches/Commits
     BranchNotFound(Box<StringError>),
@@ -67,6 +68,7 @@ pub enum OxenError {
 
     // Entry
     CommitEntryNotFound(StringError),
+    HashMismatch(StringError),
 
     // Schema
     InvalidSchema(Box<Schema>),
@@ -169,12 +171,27 @@ impl OxenError {
         ))
     }
 
+    pub fn request_timeout(url: impl AsRef<str>) -> Self {
+        OxenError::RequestTimeout(StringError::from(format!(
+            "\nRequest to {} timed out. If the remote is just slow, increase the timeout with OXEN_CONNECT_TIMEOUT_SECS or OXEN_READ_TIMEOUT_SECS.\n",
+            url.as_ref()
+        )))
+    }
+
     pub fn remote_branch_locked() -> Self {
         OxenError::RemoteBranchLocked(StringError::from(
             "\nRemote branch is locked - another push is in progress. Wait a bit before pushing again, or try pushing to a new branch.\n",
         ))
     }
 
+    pub fn hash_mismatch(expected: impl AsRef<str>, actual: impl AsRef<str>) -> Self {
+        OxenError::HashMismatch(StringError::from(format!(
+            "Content hash mismatch: expected {} but received {}",
+            expected.as_ref(),
+            actual.as_ref()
+        )))
+    }
+
     pub fn operation_cancelled() -> Self {
         OxenError::OperationCancelled(StringError::from("\nOperation cancelled.\n"))
     }
@@ -576,6 +593,10 @@ impl From<glob::GlobError> for OxenError {
 
 impl From<reqwest::Error> for OxenError {
     fn from(error: reqwest::Error) -> Self {
+        if error.is_timeout() {
+            let url = error.url().map(|url| url.to_string()).unwrap_or_default();
+            return OxenError::request_timeout(url);
+        }
         OxenError::HTTP(error)
     }
 }
@@ -5,6 +5,7 @@ pub mod base_head;
 pub mod branch;
 pub mod commit;
 pub mod content_type;
+pub mod data_frame_profile;
 pub mod data_frame_size;
 pub mod diff;
 pub mod entry;
@@ -14,6 +15,7 @@ pub mod metadata;
 pub mod namespace;
 pub mod object_id;
 pub mod parsed_resource;
+pub mod reflog;
 pub mod remote;
 pub mod remote_branch;
 pub mod remote_dataset;
@@ -22,8 +24,10 @@ pub mod schema;
 pub mod staged_data;
 pub mod staged_dir_stats;
 pub mod staged_row_status;
+pub mod stash;
 pub mod summarized_staged_dir_stats;
 pub mod user;
+pub mod verify_report;
 
 // Repository
 pub use crate::model::repository::local_repository::LocalRepository;
@@ -56,21 +60,26 @@ pub use crate::model::entry::ContentHashable;
 // Metadata
 pub use crate::model::metadata::dir_metadata_item::DirMetadataItem;
 
+pub use crate::model::data_frame_profile::{ColumnProfile, DataFrameProfile};
 pub use crate::model::data_frame_size::DataFrameSize;
 
 pub use crate::model::user::User;
+pub use crate::model::verify_report::{VerifyIssue, VerifyReport};
 
 pub use crate::model::object_id::ObjectID;
 pub use crate::model::parsed_resource::ParsedResource;
+pub use crate::model::reflog::ReflogEntry;
 
 pub use crate::model::staged_data::StagedData;
 pub use crate::model::staged_dir_stats::StagedDirStats;
+pub use crate::model::stash::{StashEntry, StashedFile};
 pub use crate::model::summarized_staged_dir_stats::SummarizedStagedDirStats;
 
 pub use crate::model::remote::Remote;
 
 pub use crate::model::diff::data_frame_diff::DataFrameDiff;
 
+pub use crate::model::schema::schema_change::SchemaChange;
 pub use crate::model::schema::staged_schema::StagedSchema;
 pub use crate::model::schema::Schema;
 
@@ -14,6 +14,12 @@ pub struct NewCommitBody {
     pub message: String,
     pub author: String,
     pub email: String,
+    /// The branch head the client staged against, the commit is rejected if the branch has moved on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_parent_id: Option<String>,
+    /// A key unique to this commit request, a retry with the same key returns the original commit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idempotency_key: Option<String>,
 }
 
 /// NewCommit is to be used when creating a new Commit, but we don't know the id yet because we need to hash the contents
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+/// Data quality stats for a single column of a data frame
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ColumnProfile {
+    pub name: String,
+    pub dtype: String,
+    pub null_count: usize,
+    /// Fraction of rows that are null, 0.0 to 1.0
+    pub null_rate: f64,
+    pub distinct_count: usize,
+    pub min: Option<String>,
+    pub max: Option<String>,
+    /// Problems worth flagging, such as text columns with a mix of numbers and words
+    pub anomalies: Vec<String>,
+}
+
+/// Data quality report for a data frame, from `oxen df --profile`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct DataFrameProfile {
+    pub num_rows: usize,
+    pub columns: Vec<ColumnProfile>,
+}
+
+impl DataFrameProfile {
+    pub fn column(&self, name: &str) -> Option<&ColumnProfile> {
+        self.columns.iter().find(|column| column.name == name)
+    }
+
+    pub fn has_anomalies(&self) -> bool {
+        self.columns
+            .iter()
+            .any(|column| !column.anomalies.is_empty())
+    }
+}
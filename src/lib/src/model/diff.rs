@@ -1,6 +1,9 @@
 pub mod add_remove_modify_counts;
 pub use add_remove_modify_counts::AddRemoveModifyCounts;
 
+pub mod binary_diff;
+pub use binary_diff::BinaryDiff;
+
 pub mod change_type;
 pub use change_type::ChangeType;
 
@@ -16,6 +19,9 @@ pub use diff_result::DiffResult;
 pub mod generic_diff;
 pub mod generic_diff_summary;
 
+pub mod image_diff;
+pub use image_diff::ImageDiff;
+
 pub mod dir_diff;
 pub mod dir_diff_summary;
 
@@ -27,4 +33,4 @@ pub use tabular_diff::TabularDiff;
 pub mod tabular_diff_summary;
 
 pub mod text_diff;
-pub use text_diff::TextDiff;
+pub use text_diff::{TextDiff, TextHunk};
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// What we know about one side of a binary diff
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct BinarySummary {
+    pub path: PathBuf,
+    pub num_bytes: u64,
+    pub hash: String,
+}
+
+/// Files that are compared by size and hash only, never by their contents
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct BinaryDiff {
+    pub left: BinarySummary,
+    pub right: BinarySummary,
+}
+
+impl BinaryDiff {
+    pub fn num_bytes_delta(&self) -> i64 {
+        self.right.num_bytes as i64 - self.left.num_bytes as i64
+    }
+
+    pub fn contents_changed(&self) -> bool {
+        self.left.hash != self.right.hash
+    }
+}
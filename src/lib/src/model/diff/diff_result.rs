@@ -1,9 +1,173 @@
 // use crate::model::diff::dir_diff::DirDiff;
-use crate::model::diff::tabular_diff::TabularDiff;
+use colored::{ColoredString, Colorize};
+
+use crate::core::df::pretty_print;
+use crate::model::diff::binary_diff::BinaryDiff;
+use crate::model::diff::change_type::ChangeType;
+use crate::model::diff::image_diff::ImageDiff;
+use crate::model::diff::tabular_diff::{TabularDiff, TabularDiffMods};
 use crate::model::diff::text_diff::TextDiff;
 
 #[derive(Debug, Clone)]
 pub enum DiffResult {
     Tabular(TabularDiff),
     Text(TextDiff),
+    Image(ImageDiff),
+    Binary(BinaryDiff),
+}
+
+impl DiffResult {
+    /// Render the diff for the terminal, pass `color = false` to strip the ANSI colors
+    pub fn format(&self, color: bool) -> String {
+        let outputs = match self {
+            DiffResult::Tabular(result) => {
+                let mut outputs = column_changes(&result.summary.modifications);
+                outputs.extend(row_changes(&result.summary.modifications));
+                outputs.push(format!("{}\n", pretty_print::df_to_str(&result.contents)).normal());
+                outputs
+            }
+            DiffResult::Text(diff) => text_changes(diff),
+            DiffResult::Image(diff) => image_changes(diff),
+            DiffResult::Binary(diff) => binary_changes(diff),
+        };
+
+        outputs
+            .into_iter()
+            .map(|output| {
+                if color {
+                    output.to_string()
+                } else {
+                    output.clear().to_string()
+                }
+            })
+            .collect()
+    }
+}
+
+fn row_changes(mods: &TabularDiffMods) -> Vec<ColoredString> {
+    let mut outputs: Vec<ColoredString> = vec![];
+
+    if mods.row_counts.modified + mods.row_counts.added + mods.row_counts.removed == 0 {
+        outputs.push("\n".normal());
+        return outputs;
+    }
+
+    outputs.push("\nRow changes: \n".into());
+    if mods.row_counts.modified > 0 {
+        outputs.push(format!("   Δ {} (modified)\n", mods.row_counts.modified).yellow());
+    }
+
+    if mods.row_counts.added > 0 {
+        outputs.push(format!("   + {} (added)\n", mods.row_counts.added).green());
+    }
+
+    if mods.row_counts.removed > 0 {
+        outputs.push(format!("   - {} (removed)\n", mods.row_counts.removed).red());
+    }
+
+    outputs.push("\n".normal());
+    outputs
+}
+
+// TODO: Truncate to "and x more"
+fn column_changes(mods: &TabularDiffMods) -> Vec<ColoredString> {
+    let mut outputs: Vec<ColoredString> = vec![];
+
+    if !mods.col_changes.added.is_empty() || !mods.col_changes.removed.is_empty() {
+        outputs.push("Column changes:\n".into());
+    }
+
+    for col in &mods.col_changes.added {
+        outputs.push(format!("   + {} ({})\n", col.name, col.dtype).green());
+    }
+
+    for col in &mods.col_changes.removed {
+        outputs.push(format!("   - {} ({})\n", col.name, col.dtype).red());
+    }
+
+    outputs
+}
+
+fn text_changes(diff: &TextDiff) -> Vec<ColoredString> {
+    let mut outputs: Vec<ColoredString> = vec![];
+    for line in &diff.lines {
+        let text = line.text.as_str();
+        outputs.push(match line.modification {
+            ChangeType::Unchanged => text.normal(),
+            ChangeType::Added => text.green(),
+            ChangeType::Removed => text.red(),
+            ChangeType::Modified => text.yellow(),
+        });
+        outputs.push("\n".normal());
+    }
+    outputs
+}
+
+fn image_changes(diff: &ImageDiff) -> Vec<ColoredString> {
+    let (left, right) = (&diff.left, &diff.right);
+    let mut outputs: Vec<ColoredString> = vec!["Image changes:\n".into()];
+
+    let dimensions = format!(
+        "   dimensions: {}x{} -> {}x{} ({:+}x{:+})\n",
+        left.width,
+        left.height,
+        right.width,
+        right.height,
+        diff.width_delta(),
+        diff.height_delta()
+    );
+    if diff.width_delta() == 0 && diff.height_delta() == 0 {
+        outputs.push(dimensions.normal());
+    } else {
+        outputs.push(dimensions.yellow());
+    }
+
+    let format = format!("   format: {} -> {}\n", left.format, right.format);
+    if diff.format_changed() {
+        outputs.push(format.yellow());
+    } else {
+        outputs.push(format.normal());
+    }
+
+    let size = format!(
+        "   size: {} -> {} ({:+} bytes)\n",
+        bytesize::ByteSize::b(left.num_bytes),
+        bytesize::ByteSize::b(right.num_bytes),
+        diff.num_bytes_delta()
+    );
+    if diff.num_bytes_delta() == 0 {
+        outputs.push(size.normal());
+    } else {
+        outputs.push(size.yellow());
+    }
+
+    if diff.pixels_changed() {
+        outputs.push("   pixels: changed\n".yellow());
+    } else {
+        outputs.push("   pixels: unchanged\n".normal());
+    }
+    outputs
+}
+
+fn binary_changes(diff: &BinaryDiff) -> Vec<ColoredString> {
+    let mut outputs: Vec<ColoredString> = vec!["Binary changes:\n".into()];
+
+    let size = format!(
+        "   size: {} -> {} ({:+} bytes)\n",
+        bytesize::ByteSize::b(diff.left.num_bytes),
+        bytesize::ByteSize::b(diff.right.num_bytes),
+        diff.num_bytes_delta()
+    );
+    if diff.num_bytes_delta() == 0 {
+        outputs.push(size.normal());
+    } else {
+        outputs.push(size.yellow());
+    }
+
+    if diff.contents_changed() {
+        outputs.push("   contents: changed\n".yellow());
+    } else {
+        outputs.push("   contents: unchanged\n".normal());
+    }
+    outputs
 }
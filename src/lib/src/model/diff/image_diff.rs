@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// What we know about one side of an image diff
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ImageSummary {
+    pub path: PathBuf,
+    pub width: u32,
+    pub height: u32,
+    pub format: String,
+    pub num_bytes: u64,
+    /// Hash of the decoded pixels, so re-encoding the same picture is not a change
+    pub pixel_hash: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ImageDiff {
+    pub left: ImageSummary,
+    pub right: ImageSummary,
+}
+
+impl ImageDiff {
+    pub fn width_delta(&self) -> i64 {
+        self.right.width as i64 - self.left.width as i64
+    }
+
+    pub fn height_delta(&self) -> i64 {
+        self.right.height as i64 - self.left.height as i64
+    }
+
+    pub fn num_bytes_delta(&self) -> i64 {
+        self.right.num_bytes as i64 - self.left.num_bytes as i64
+    }
+
+    pub fn format_changed(&self) -> bool {
+        self.left.format != self.right.format
+    }
+
+    pub fn pixels_changed(&self) -> bool {
+        self.left.pixel_hash != self.right.pixel_hash
+    }
+}
@@ -1,8 +1,10 @@
 use crate::{
+    constants::DIFF_STATUS_COL,
     error::OxenError,
     model::schema::{Field, Schema},
 };
 use polars::frame::DataFrame;
+use polars::prelude::{NamedFrom, Series};
 use serde::{Deserialize, Serialize};
 
 use super::AddRemoveModifyCounts;
@@ -128,6 +130,76 @@ impl TabularDiff {
             || !self.summary.modifications.col_changes.removed.is_empty()
     }
 
+    /// Swap the left and right sides of the diff, so added rows and columns become removed ones
+    pub fn reversed(self) -> Result<TabularDiff, OxenError> {
+        let TabularDiff {
+            summary,
+            parameters,
+            mut contents,
+        } = self;
+
+        let names: Vec<String> = contents
+            .get_column_names()
+            .iter()
+            .map(|name| {
+                if let Some(stripped) = name.strip_suffix(".left") {
+                    format!("{stripped}.right")
+                } else if let Some(stripped) = name.strip_suffix(".right") {
+                    format!("{stripped}.left")
+                } else {
+                    name.to_string()
+                }
+            })
+            .collect();
+        contents.set_column_names(&names)?;
+
+        if contents.get_column_names().contains(&DIFF_STATUS_COL) {
+            let statuses: Vec<Option<&str>> = contents
+                .column(DIFF_STATUS_COL)?
+                .str()?
+                .into_iter()
+                .map(|status| match status {
+                    Some("added") => Some("removed"),
+                    Some("removed") => Some("added"),
+                    other => other,
+                })
+                .collect();
+            let statuses = Series::new(DIFF_STATUS_COL, statuses);
+            contents.with_column(statuses)?;
+        }
+
+        let row_counts = summary.modifications.row_counts;
+        let col_changes = summary.modifications.col_changes;
+        let summary = TabularDiffSummary {
+            modifications: TabularDiffMods {
+                row_counts: AddRemoveModifyCounts {
+                    added: row_counts.removed,
+                    removed: row_counts.added,
+                    modified: row_counts.modified,
+                },
+                col_changes: TabularSchemaDiff {
+                    added: col_changes.removed,
+                    removed: col_changes.added,
+                },
+            },
+            schemas: TabularDiffSchemas {
+                left: summary.schemas.right,
+                right: summary.schemas.left,
+                diff: Schema::from_polars(&contents.schema()),
+            },
+            dupes: TabularDiffDupes {
+                left: summary.dupes.right,
+                right: summary.dupes.left,
+            },
+        };
+
+        Ok(TabularDiff {
+            summary,
+            parameters,
+            contents,
+        })
+    }
+
     pub fn empty() -> Self {
         TabularDiff {
             summary: TabularDiffSummary::empty(),
@@ -11,3 +11,34 @@ pub struct LineDiff {
 pub struct TextDiff {
     pub lines: Vec<LineDiff>,
 }
+
+/// A contiguous block of changed lines between the working file and a source revision.
+/// Restoring the hunk replaces the `current` lines with the `source` lines.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct TextHunk {
+    pub index: usize,
+    /// 1-based line in the working file where the hunk starts
+    pub start: usize,
+    pub current: Vec<String>,
+    pub source: Vec<String>,
+}
+
+impl std::fmt::Display for TextHunk {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        writeln!(
+            f,
+            "@@ hunk {} line {} -{} +{} @@",
+            self.index,
+            self.start,
+            self.current.len(),
+            self.source.len()
+        )?;
+        for line in self.current.iter() {
+            writeln!(f, "-{line}")?;
+        }
+        for line in self.source.iter() {
+            writeln!(f, "+{line}")?;
+        }
+        Ok(())
+    }
+}
@@ -40,7 +40,13 @@ impl FromStr for EntryDataType {
             "audio" => Ok(EntryDataType::Audio),
             "tabular" => Ok(EntryDataType::Tabular),
             "binary" => Ok(EntryDataType::Binary),
-            _ => Err(()),
+            // Also accept mime types such as "audio/x-wav" or "video/mp4"
+            s => match s.split('/').next() {
+                Some("audio") => Ok(EntryDataType::Audio),
+                Some("video") => Ok(EntryDataType::Video),
+                Some("image") => Ok(EntryDataType::Image),
+                _ => Err(()),
+            },
         }
     }
 }
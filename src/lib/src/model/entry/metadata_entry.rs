@@ -4,6 +4,7 @@ use crate::api;
 use crate::model::metadata::generic_metadata::GenericMetadata;
 use crate::model::{Commit, CommitEntry, EntryDataType, LocalRepository};
 use crate::view::entry::ResourceVersion;
+use crate::view::DataTypeCount;
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct CLIMetadataEntry {
@@ -21,6 +22,17 @@ pub struct CLIMetadataEntry {
     pub extension: String,
 }
 
+/// Aggregate info about all the files under a directory
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct CLIDirMetadataEntry {
+    pub dirname: String,
+    // total size of all the files in bytes
+    pub size: u64,
+    pub num_files: usize,
+    // number of files per "image", "text", "video", "audio", "tabular", etc.
+    pub data_types: Vec<DataTypeCount>,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct MetadataEntry {
     pub filename: String,
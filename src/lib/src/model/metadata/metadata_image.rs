@@ -33,10 +33,30 @@ pub struct MetadataImageImpl {
     pub color_space: ImgColorSpace, // RGB, RGBA, etc.
 }
 
+/// Formats an image can be converted to when it is served
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ImgFormat {
+    Webp,
+    Jpeg,
+    Png,
+}
+
+impl ImgFormat {
+    pub fn extension(&self) -> &str {
+        match self {
+            ImgFormat::Webp => "webp",
+            ImgFormat::Jpeg => "jpeg",
+            ImgFormat::Png => "png",
+        }
+    }
+}
+
 #[derive(Deserialize, Debug)]
 pub struct ImgResize {
     pub width: Option<u32>,
     pub height: Option<u32>,
+    pub format: Option<ImgFormat>,
 }
 
 impl MetadataImage {
@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use time::OffsetDateTime;
+
+/// One movement of HEAD, appended to .oxen/logs/HEAD by commit, checkout and merge
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ReflogEntry {
+    // None when HEAD did not point at a commit yet
+    pub old_commit_id: Option<String>,
+    pub new_commit_id: String,
+    pub message: String,
+    #[serde(with = "time::serde::rfc3339")]
+    pub timestamp: OffsetDateTime,
+}
+
+impl fmt::Display for ReflogEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let old = self.old_commit_id.as_deref().unwrap_or("none");
+        write!(f, "{} -> {} {}", old, self.new_commit_id, self.message)
+    }
+}
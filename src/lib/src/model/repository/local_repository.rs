@@ -1,13 +1,15 @@
-use crate::config::RemoteConfig;
+use crate::config::{ColumnAccess, RemoteConfig};
 use crate::constants;
-use crate::constants::SHALLOW_FLAG;
+use crate::constants::{LFS_THRESHOLD_FILE, SHALLOW_FLAG};
 use crate::error;
 use crate::error::OxenError;
-use crate::model::{Remote, RemoteRepository};
+use crate::model::{Remote, RemoteBranch, RemoteRepository};
+use crate::opts::DFDefaults;
 use crate::util;
 use crate::view::RepositoryView;
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -16,6 +18,21 @@ pub struct LocalRepository {
     // Optional remotes to sync the data to
     remote_name: Option<String>, // name of the current remote ("origin" by default)
     pub remotes: Vec<Remote>,    // List of possible remotes
+    // Remote branch each local branch tracks, set with push --set-upstream
+    #[serde(default)]
+    upstreams: BTreeMap<String, RemoteBranch>,
+    // Whether to run the scripts in .oxen/hooks
+    #[serde(default)]
+    hooks_enabled: bool,
+    // Defaults for DFOpts the request or command line did not set
+    #[serde(default)]
+    df_defaults: DFDefaults,
+    // Bare repositories only have the .oxen dir, entries are never unpacked into a working tree
+    #[serde(default)]
+    bare: bool,
+    // Columns each token scope can read from the data frame endpoints
+    #[serde(default)]
+    column_access: BTreeMap<String, ColumnAccess>,
 }
 
 impl LocalRepository {
@@ -25,6 +42,11 @@ impl LocalRepository {
             path: path.to_path_buf(),
             remotes: vec![],
             remote_name: None,
+            upstreams: BTreeMap::new(),
+            hooks_enabled: false,
+            df_defaults: DFDefaults::default(),
+            bare: false,
+            column_access: BTreeMap::new(),
         })
     }
 
@@ -33,6 +55,11 @@ impl LocalRepository {
             path: std::env::current_dir()?.join(view.name),
             remotes: vec![],
             remote_name: None,
+            upstreams: BTreeMap::new(),
+            hooks_enabled: false,
+            df_defaults: DFDefaults::default(),
+            bare: false,
+            column_access: BTreeMap::new(),
         })
     }
 
@@ -41,6 +68,11 @@ impl LocalRepository {
             path: path.to_owned(),
             remotes: vec![repo.remote],
             remote_name: Some(String::from(constants::DEFAULT_REMOTE_NAME)),
+            upstreams: BTreeMap::new(),
+            hooks_enabled: false,
+            df_defaults: DFDefaults::default(),
+            bare: false,
+            column_access: BTreeMap::new(),
         })
     }
 
@@ -54,6 +86,11 @@ impl LocalRepository {
             path: dir.to_path_buf(),
             remotes: remote_cfg.remotes,
             remote_name: remote_cfg.remote_name,
+            upstreams: remote_cfg.upstreams,
+            hooks_enabled: remote_cfg.hooks_enabled,
+            df_defaults: remote_cfg.df_defaults,
+            bare: remote_cfg.bare,
+            column_access: remote_cfg.column_access,
         };
         Ok(repo)
     }
@@ -73,6 +110,11 @@ impl LocalRepository {
         let cfg = RemoteConfig {
             remote_name: self.remote_name.clone(),
             remotes: self.remotes.clone(),
+            upstreams: self.upstreams.clone(),
+            hooks_enabled: self.hooks_enabled,
+            df_defaults: self.df_defaults.clone(),
+            bare: self.bare,
+            column_access: self.column_access.clone(),
         };
         let toml = toml::to_string(&cfg)?;
         util::fs::write_to_path(path, toml)?;
@@ -115,6 +157,23 @@ impl LocalRepository {
         self.remotes = new_remotes;
     }
 
+    /// Rename a remote, keeping the current remote and any upstreams pointed at it
+    pub fn rename_remote(&mut self, old_name: &str, new_name: &str) {
+        for remote in self.remotes.iter_mut() {
+            if remote.name == old_name {
+                remote.name = String::from(new_name);
+            }
+        }
+        if self.remote_name.as_deref() == Some(old_name) {
+            self.remote_name = Some(String::from(new_name));
+        }
+        for upstream in self.upstreams.values_mut() {
+            if upstream.remote == old_name {
+                upstream.remote = String::from(new_name);
+            }
+        }
+    }
+
     pub fn has_remote(&self, name: &str) -> bool {
         for remote in self.remotes.iter() {
             if remote.name == name {
@@ -143,6 +202,74 @@ impl LocalRepository {
         }
     }
 
+    /// Track `remote_branch` as the upstream of the local `branch`, call save_default to persist it
+    pub fn set_upstream(&mut self, branch: &str, remote_branch: RemoteBranch) {
+        self.upstreams.insert(String::from(branch), remote_branch);
+    }
+
+    pub fn upstream(&self, branch: &str) -> Option<RemoteBranch> {
+        self.upstreams.get(branch).cloned()
+    }
+
+    /// Local branches whose upstream lives on the remote `name`
+    pub fn branches_tracking_remote(&self, name: &str) -> Vec<String> {
+        self.upstreams
+            .iter()
+            .filter(|(_, upstream)| upstream.remote == name)
+            .map(|(branch, _)| branch.to_owned())
+            .collect()
+    }
+
+    /// Stop tracking any upstreams on the remote `name`
+    pub fn remove_upstreams_for_remote(&mut self, name: &str) {
+        self.upstreams.retain(|_, upstream| upstream.remote != name);
+    }
+
+    /// Turn running the scripts in .oxen/hooks on or off, call save_default to persist it
+    pub fn set_hooks_enabled(&mut self, enabled: bool) {
+        self.hooks_enabled = enabled;
+    }
+
+    pub fn hooks_enabled(&self) -> bool {
+        self.hooks_enabled
+    }
+
+    /// Mark the repository as bare, call save_default to persist it
+    pub fn set_bare(&mut self, bare: bool) {
+        self.bare = bare;
+    }
+
+    pub fn is_bare(&self) -> bool {
+        self.bare
+    }
+
+    /// Restrict the columns a token scope can read, call save_default to persist it
+    pub fn set_column_access(&mut self, scope: impl AsRef<str>, access: ColumnAccess) {
+        self.column_access
+            .insert(scope.as_ref().to_string(), access);
+    }
+
+    /// Column access for a token scope, `None` if the scope can read every column
+    pub fn column_access(&self, scope: &str) -> Option<&ColumnAccess> {
+        self.column_access.get(scope)
+    }
+
+    /// Set the defaults applied to DFOpts in this repository, call save_default to persist them
+    pub fn set_df_defaults(&mut self, defaults: DFDefaults) {
+        self.df_defaults = defaults;
+    }
+
+    pub fn df_defaults(&self) -> &DFDefaults {
+        &self.df_defaults
+    }
+
+    /// Page size for data frames when the request does not set one
+    pub fn df_page_size(&self) -> usize {
+        self.df_defaults
+            .page_size
+            .unwrap_or(constants::DEFAULT_PAGE_SIZE)
+    }
+
     pub fn write_is_shallow(&self, shallow: bool) -> Result<(), OxenError> {
         let shallow_flag_path = util::fs::oxen_hidden_dir(&self.path).join(SHALLOW_FLAG);
         log::debug!("Write is shallow [{shallow}] to path: {shallow_flag_path:?}");
@@ -158,6 +285,25 @@ impl LocalRepository {
         let shallow_flag_path = util::fs::oxen_hidden_dir(&self.path).join(SHALLOW_FLAG);
         shallow_flag_path.exists()
     }
+
+    pub fn write_lfs_threshold(&self, threshold: Option<u64>) -> Result<(), OxenError> {
+        let threshold_path = util::fs::oxen_hidden_dir(&self.path).join(LFS_THRESHOLD_FILE);
+        log::debug!("Write lfs threshold [{threshold:?}] to path: {threshold_path:?}");
+        if let Some(threshold) = threshold {
+            util::fs::write_to_path(&threshold_path, threshold.to_string())?;
+        } else if threshold_path.exists() {
+            util::fs::remove_file(&threshold_path)?;
+        }
+        Ok(())
+    }
+
+    /// Files larger than this many bytes are left as placeholders when pulling
+    pub fn lfs_threshold(&self) -> Option<u64> {
+        let threshold_path = util::fs::oxen_hidden_dir(&self.path).join(LFS_THRESHOLD_FILE);
+        std::fs::read_to_string(threshold_path)
+            .ok()
+            .and_then(|threshold| threshold.trim().parse().ok())
+    }
 }
 
 #[cfg(test)]
@@ -1,4 +1,5 @@
 use crate::api;
+use crate::constants::DEFAULT_BRANCH_NAME;
 use crate::view::repository::RepositoryDataTypesView;
 use crate::view::RepositoryView;
 use crate::{error::OxenError, model::Remote};
@@ -10,6 +11,9 @@ pub struct RemoteRepository {
     pub namespace: String,
     pub name: String,
     pub remote: Remote,
+    // Branch the server reports as its default, see `default_branch()`
+    #[serde(default)]
+    pub default_branch: Option<String>,
 }
 
 impl RemoteRepository {
@@ -21,6 +25,7 @@ impl RemoteRepository {
             namespace: repository.namespace.clone(),
             name: repository.name.clone(),
             remote: remote.clone(),
+            default_branch: repository.default_branch.clone(),
         }
     }
 
@@ -29,9 +34,18 @@ impl RemoteRepository {
             namespace: repository.namespace.clone(),
             name: repository.name.clone(),
             remote: remote.clone(),
+            default_branch: repository.default_branch.clone(),
         }
     }
 
+    /// Branch to use when none is given, the default branch of the remote repository or
+    /// `main` if the server did not report one
+    pub fn default_branch(&self) -> &str {
+        self.default_branch
+            .as_deref()
+            .unwrap_or(DEFAULT_BRANCH_NAME)
+    }
+
     /// User friendly url for the remote repository
     /// Ex) http://localhost:3000/namespace/name
     pub fn url(&self) -> &str {
@@ -23,6 +23,8 @@ pub struct RepoNew {
     pub description: Option<String>,
     // Files that you want to seed the repo with
     pub files: Option<Vec<FileNew>>,
+    // Branch HEAD points to on the server, defaults to main
+    pub default_branch: Option<String>,
 }
 
 impl std::fmt::Display for RepoNew {
@@ -74,6 +76,7 @@ impl RepoNew {
             root_commit: None,
             description: None,
             files: None,
+            default_branch: None,
         })
     }
 
@@ -96,6 +99,7 @@ impl RepoNew {
             root_commit: None,
             description: None,
             files: None,
+            default_branch: None,
         }
     }
 
@@ -113,6 +117,7 @@ impl RepoNew {
             root_commit: None,
             description: None,
             files: None,
+            default_branch: None,
         }
     }
 
@@ -130,6 +135,7 @@ impl RepoNew {
             root_commit: Some(root_commit),
             description: None,
             files: None,
+            default_branch: None,
         }
     }
 
@@ -147,6 +153,7 @@ impl RepoNew {
             root_commit: None,
             description: None,
             files: Some(files),
+            default_branch: None,
         }
     }
 
@@ -170,6 +177,7 @@ impl RepoNew {
             root_commit: None,
             description: None,
             files: None,
+            default_branch: None,
         })
     }
 }
@@ -3,6 +3,7 @@ use crate::model::EntryDataType;
 use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct DataTypeStat {
@@ -15,4 +16,8 @@ pub struct DataTypeStat {
 pub struct RepoStats {
     pub data_size: u64,
     pub data_types: HashMap<EntryDataType, DataTypeStat>,
+    pub num_commits: usize,
+    pub num_branches: usize,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub last_updated: Option<OffsetDateTime>,
 }
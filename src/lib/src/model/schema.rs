@@ -1,6 +1,7 @@
 pub mod custom_data_type;
 pub mod data_type;
 pub mod field;
+pub mod schema_change;
 pub mod staged_schema;
 
 pub use custom_data_type::CustomDataType;
@@ -0,0 +1,12 @@
+use crate::model::diff::tabular_diff::TabularSchemaDiff;
+use crate::model::Commit;
+
+use super::Schema;
+
+/// A commit that changed the schema of a file, `schema` is `None` if the file was removed
+#[derive(Debug, Clone)]
+pub struct SchemaChange {
+    pub commit: Commit,
+    pub schema: Option<Schema>,
+    pub diff: TabularSchemaDiff,
+}
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::PathBuf;
+use time::OffsetDateTime;
+
+use crate::model::StagedEntryStatus;
+
+/// A file that was saved off in a stash entry
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct StashedFile {
+    pub path: PathBuf,
+    pub status: StagedEntryStatus,
+    // Whether the change was staged when it was stashed, so we can re-stage it on pop
+    pub staged: bool,
+}
+
+/// Snapshot of the uncommitted changes against HEAD, stored in .oxen/stash/<id>
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct StashEntry {
+    pub id: String,
+    pub message: String,
+    pub branch: Option<String>,
+    pub commit_id: String,
+    pub files: Vec<StashedFile>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub timestamp: OffsetDateTime,
+}
+
+impl fmt::Display for StashEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let branch = self.branch.clone().unwrap_or(self.commit_id.clone());
+        write!(f, "On {}: {}", branch, self.message)
+    }
+}
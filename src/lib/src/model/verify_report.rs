@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::PathBuf;
+
+/// A single problem found while verifying the repository
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub enum VerifyIssue {
+    /// The commit points to a parent that is not in the commit db
+    BrokenParentLink {
+        commit_id: String,
+        parent_id: String,
+    },
+    /// The version file for an entry is not in .oxen/versions
+    MissingVersionFile { commit_id: String, path: PathBuf },
+    /// The version file exists but does not hash to the entry hash
+    HashMismatch {
+        commit_id: String,
+        path: PathBuf,
+        expected: String,
+        actual: String,
+    },
+}
+
+impl VerifyIssue {
+    pub fn commit_id(&self) -> &str {
+        match self {
+            VerifyIssue::BrokenParentLink { commit_id, .. } => commit_id,
+            VerifyIssue::MissingVersionFile { commit_id, .. } => commit_id,
+            VerifyIssue::HashMismatch { commit_id, .. } => commit_id,
+        }
+    }
+
+    /// The entry path the issue is about, if it is about an entry
+    pub fn path(&self) -> Option<&PathBuf> {
+        match self {
+            VerifyIssue::BrokenParentLink { .. } => None,
+            VerifyIssue::MissingVersionFile { path, .. } => Some(path),
+            VerifyIssue::HashMismatch { path, .. } => Some(path),
+        }
+    }
+}
+
+impl fmt::Display for VerifyIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VerifyIssue::BrokenParentLink {
+                commit_id,
+                parent_id,
+            } => write!(
+                f,
+                "commit {commit_id}: broken link to parent commit {parent_id}"
+            ),
+            VerifyIssue::MissingVersionFile { commit_id, path } => {
+                write!(f, "commit {commit_id}: missing version file for {path:?}")
+            }
+            VerifyIssue::HashMismatch {
+                commit_id,
+                path,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "commit {commit_id}: hash mismatch for {path:?}, expected {expected} got {actual}"
+            ),
+        }
+    }
+}
+
+/// Result of `oxen verify`
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct VerifyReport {
+    pub num_commits: usize,
+    pub num_entries: usize,
+    pub issues: Vec<VerifyIssue>,
+    // Issues that were repaired by re-downloading from the remote with --fix
+    pub fixed: Vec<VerifyIssue>,
+}
+
+impl VerifyReport {
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
@@ -20,7 +20,7 @@ pub mod upload_opts;
 pub use crate::opts::add_opts::AddOpts;
 pub use crate::opts::clone_opts::CloneOpts;
 pub use crate::opts::count_lines_opts::CountLinesOpts;
-pub use crate::opts::df_opts::DFOpts;
+pub use crate::opts::df_opts::{DFDefaults, DFOpts, PivotOpts, WindowAgg};
 pub use crate::opts::diff_opts::DiffOpts;
 pub use crate::opts::download_opts::DownloadOpts;
 pub use crate::opts::info_opts::InfoOpts;
@@ -5,4 +5,10 @@ pub struct AddOpts {
     pub paths: Vec<PathBuf>,
     pub directory: Option<PathBuf>,
     pub is_remote: bool,
+    // Globs to skip when adding directories, applied on top of .oxenignore
+    pub excludes: Vec<String>,
+    // Upload remote adds in parts so large files can be retried piece by piece
+    pub chunked: bool,
+    // Overwrite paths other identifiers have staged on the remote branch
+    pub force: bool,
 }
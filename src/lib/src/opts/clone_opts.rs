@@ -9,10 +9,15 @@ pub struct CloneOpts {
     pub branch: String,
     pub shallow: bool,
     pub all: bool,
+    // Clone into an existing directory, keeping any files that are already there
+    pub into: bool,
+    // Files larger than this many bytes are left as placeholders until materialized
+    pub lfs_threshold: Option<u64>,
 }
 
 impl CloneOpts {
-    /// Sets `branch` to `DEFAULT_BRANCH_NAME` and defaults `shallow` and `all` to `false`
+    /// Sets `branch` to `DEFAULT_BRANCH_NAME`, defaults `shallow`, `all`, and `into` to `false`,
+    /// and downloads every file regardless of size
     pub fn new(url: String, dst: impl AsRef<Path>) -> CloneOpts {
         CloneOpts {
             url,
@@ -20,6 +25,8 @@ impl CloneOpts {
             branch: DEFAULT_BRANCH_NAME.to_string(),
             shallow: false,
             all: false,
+            into: false,
+            lfs_threshold: None,
         }
     }
 }
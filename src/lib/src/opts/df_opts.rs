@@ -3,7 +3,11 @@ use std::path::PathBuf;
 use serde_derive::{Deserialize, Serialize};
 use serde_json::Value;
 
-use crate::constants::{DEFAULT_HOST, FILE_ROW_NUM_COL_NAME, ROW_HASH_COL_NAME, ROW_NUM_COL_NAME};
+use crate::constants::{
+    DEFAULT_FUZZY_THRESHOLD, DEFAULT_HOST, FILE_ROW_NUM_COL_NAME, ROW_HASH_COL_NAME,
+    ROW_NUM_COL_NAME,
+};
+use crate::error::OxenError;
 use crate::model::schema::Field;
 use crate::model::Schema;
 
@@ -20,20 +24,85 @@ pub struct IndexedItem {
     pub index: usize,
 }
 
+/// A rolling aggregation over the current row and the `size - 1` rows before it
+#[derive(Clone, Debug, PartialEq)]
+pub struct WindowAgg {
+    pub func: String,
+    pub column: String,
+    pub size: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct PivotOpts {
+    pub index: String,
+    pub columns: String,
+    pub values: String,
+    pub agg: String,
+}
+
 #[derive(Clone, Debug)]
 pub struct DFOpts {
     pub add_col: Option<String>,
     pub add_row: Option<String>,
+    // Comma separated fn:col aggregations to compute for each group_by group
+    pub agg: Option<String>,
+    // Run the group_by over every tabular file under the input directory
+    pub aggregate_over_dirs: bool,
+    // Comma separated col:type casts
+    pub cast: Option<String>,
+    // Comma separated columns to take the first non-null value of
+    pub coalesce: Option<String>,
+    // Name of the --coalesce column, an existing column is replaced in place
+    pub coalesce_as: Option<String>,
     pub columns: Option<String>,
+    // Other files to stack under the input, columns are matched by name
+    pub concat: Option<Vec<PathBuf>>,
+    // Allow concat of files with different columns or column types
+    pub coerce: bool,
+    // Allow concat of files with different columns, shared columns must have the same type
+    pub merge_schema: bool,
+    // Comma separated columns to dedup on, empty to compare every column
+    pub dedup: Option<String>,
+    // Comma separated columns to report duplicate groups on, empty to compare every column
+    pub detect_duplicates: Option<String>,
     pub delete_row: Option<String>,
     pub delimiter: Option<String>,
+    // Comma separated columns that must not be null, empty to check every column
+    pub drop_nulls: Option<String>,
+    // File to join rows to where the --on columns are similar, rather than equal
+    pub fuzzy_join: Option<PathBuf>,
+    // Column to compare for --fuzzy-join, must be in both files
+    pub fuzzy_on: Option<String>,
+    // Minimum jaro winkler similarity, between 0 and 1, for --fuzzy-join to match two rows
+    pub fuzzy_threshold: Option<f64>,
+    // Comma separated columns to group the rows by
+    pub group_by: Option<String>,
+    pub hash_rows: Option<String>,
     pub head: Option<usize>,
     pub host: Option<String>,
+    // Column to order the rows by before computing the windows
+    pub order_by: Option<String>,
     pub output: Option<PathBuf>,
+    // Comma separated columns to write the output as a hive partitioned parquet directory by
+    pub partition_by: Option<String>,
     pub page_size: Option<usize>,
     pub page: Option<usize>,
+    pub pivot: Option<PivotOpts>,
+    // Comma separated old:new column names
+    pub rename: Option<String>,
     pub row: Option<usize>,
     pub item: Option<String>,
+    // Max rows to print, the rest are elided in the middle. Polars only reads the limit from
+    // the POLARS_FMT_MAX_ROWS env var, so it is up to the binary to set it before printing
+    pub max_rows: Option<usize>,
+    // Comma separated method:col scalings, method is minmax or zscore
+    pub normalize: Option<String>,
+    // Append the --normalize results as col_method columns instead of replacing the columns
+    pub normalize_append: bool,
+    // Seed for the --seed-split hashes, defaults to 0
+    pub seed: Option<u64>,
+    // Comma separated train,val,test fractions to assign each row a split with
+    pub seed_split: Option<String>,
     pub should_randomize: bool,
     pub should_reverse: bool,
     pub slice: Option<String>,
@@ -42,9 +111,30 @@ pub struct DFOpts {
     pub text2sql: Option<String>,
     pub tail: Option<usize>,
     pub take: Option<String>,
+    // Swap rows and columns, the header becomes the first column
+    pub transpose: bool,
     pub unique: Option<String>,
     pub vstack: Option<Vec<PathBuf>>,
+    // col:path to keep the rows whose col is one of the values listed in the file
+    pub where_in: Option<String>,
+    // Comma separated fn:col:size rolling aggregations
+    pub window: Option<String>,
+}
+/// Per repository defaults for [`DFOpts`], stored in the repository config
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct DFDefaults {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_size: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_rows: Option<usize>,
 }
+
+impl DFDefaults {
+    pub fn is_empty(&self) -> bool {
+        self.page_size.is_none() && self.max_rows.is_none()
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct DFOptsView {
     pub opts: Vec<DFOptView>,
@@ -61,16 +151,41 @@ impl DFOpts {
         DFOpts {
             add_col: None,
             add_row: None,
+            agg: None,
+            aggregate_over_dirs: false,
+            cast: None,
+            coalesce: None,
+            coalesce_as: None,
             item: None,
             columns: None,
+            concat: None,
+            coerce: false,
+            merge_schema: false,
+            dedup: None,
+            detect_duplicates: None,
             delete_row: None,
             delimiter: None,
+            drop_nulls: None,
+            fuzzy_join: None,
+            fuzzy_on: None,
+            fuzzy_threshold: None,
+            group_by: None,
+            hash_rows: None,
             head: None,
             host: None,
+            order_by: None,
             output: None,
+            partition_by: None,
             page_size: None,
             page: None,
+            pivot: None,
+            rename: None,
             row: None,
+            max_rows: None,
+            normalize: None,
+            normalize_append: false,
+            seed: None,
+            seed_split: None,
             should_randomize: false,
             should_reverse: false,
             slice: None,
@@ -79,8 +194,29 @@ impl DFOpts {
             text2sql: None,
             tail: None,
             take: None,
+            transpose: false,
             unique: None,
             vstack: None,
+            where_in: None,
+            window: None,
+        }
+    }
+
+    /// Fill in the values the caller did not set from the repository defaults
+    pub fn apply_defaults(&mut self, defaults: &DFDefaults) {
+        // A default page size only limits what is printed, it never truncates a write or
+        // overrides rows the caller picked explicitly
+        let picks_rows = self.output.is_some()
+            || self.head.is_some()
+            || self.tail.is_some()
+            || self.slice.is_some()
+            || self.row.is_some()
+            || self.take.is_some();
+        if self.page_size.is_none() && !picks_rows {
+            self.page_size = defaults.page_size;
+        }
+        if self.max_rows.is_none() {
+            self.max_rows = defaults.max_rows;
         }
     }
 
@@ -127,11 +263,23 @@ impl DFOpts {
         self.add_col.is_some()
             || self.add_row.is_some()
             || self.item.is_some()
+            || self.cast.is_some()
+            || self.coalesce.is_some()
             || self.columns.is_some()
+            || self.dedup.is_some()
+            || self.detect_duplicates.is_some()
+            || self.drop_nulls.is_some()
+            || self.fuzzy_join.is_some()
+            || self.group_by.is_some()
+            || self.hash_rows.is_some()
             || self.head.is_some()
+            || self.normalize.is_some()
             || self.page_size.is_some()
             || self.page.is_some()
+            || self.pivot.is_some()
+            || self.rename.is_some()
             || self.row.is_some()
+            || self.seed_split.is_some()
             || self.should_randomize
             || self.should_reverse
             || self.sort_by.is_some()
@@ -140,8 +288,13 @@ impl DFOpts {
             || self.tail.is_some()
             || self.take.is_some()
             || self.text2sql.is_some()
+            || self.transpose
             || self.unique.is_some()
             || self.vstack.is_some()
+            || self.concat.is_some()
+            || self.aggregate_over_dirs
+            || self.where_in.is_some()
+            || self.window.is_some()
     }
 
     pub fn slice_indices(&self) -> Option<(i64, i64)> {
@@ -188,6 +341,230 @@ impl DFOpts {
         None
     }
 
+    /// Columns to dedup on, an empty list means rows must match on every column
+    pub fn dedup_columns(&self) -> Option<Vec<String>> {
+        self.dedup.as_ref().map(|columns| {
+            columns
+                .split(',')
+                .map(|c| c.trim())
+                .filter(|c| !c.is_empty())
+                .map(String::from)
+                .collect()
+        })
+    }
+
+    /// Columns to detect duplicates on, an empty list means rows must match on every column
+    pub fn detect_duplicates_columns(&self) -> Option<Vec<String>> {
+        self.detect_duplicates.as_ref().map(|columns| {
+            columns
+                .split(',')
+                .map(|c| c.trim())
+                .filter(|c| !c.is_empty())
+                .map(String::from)
+                .collect()
+        })
+    }
+
+    /// Columns to coalesce, in the order their values are picked
+    pub fn coalesce_columns(&self) -> Option<Vec<String>> {
+        self.coalesce.as_ref().map(|columns| {
+            columns
+                .split(',')
+                .map(|c| c.trim())
+                .filter(|c| !c.is_empty())
+                .map(String::from)
+                .collect()
+        })
+    }
+
+    /// Column to compare and the minimum similarity for --fuzzy-join
+    pub fn fuzzy_join_on(&self) -> Result<Option<(String, f64)>, OxenError> {
+        if self.fuzzy_join.is_none() {
+            return Ok(None);
+        }
+        let Some(column) = self.fuzzy_on.as_ref().filter(|c| !c.trim().is_empty()) else {
+            return Err(OxenError::basic_str(
+                "Must supply a column to --fuzzy-join on, ie: --on name",
+            ));
+        };
+        let threshold = self.fuzzy_threshold.unwrap_or(DEFAULT_FUZZY_THRESHOLD);
+        if !(0.0..=1.0).contains(&threshold) {
+            return Err(OxenError::basic_str(format!(
+                "Invalid --threshold {threshold}, must be between 0 and 1"
+            )));
+        }
+        Ok(Some((column.trim().to_string(), threshold)))
+    }
+
+    /// Column and value list file for --where-in, parsed from `col:path`
+    pub fn where_in_column(&self) -> Result<Option<(String, PathBuf)>, OxenError> {
+        let Some(where_in) = &self.where_in else {
+            return Ok(None);
+        };
+        match where_in.split_once(':') {
+            Some((column, path)) if !column.trim().is_empty() && !path.trim().is_empty() => Ok(
+                Some((column.trim().to_string(), PathBuf::from(path.trim()))),
+            ),
+            _ => Err(OxenError::basic_str(format!(
+                "Invalid --where-in '{where_in}', must be of the form col:path, ie: id:ids.txt"
+            ))),
+        }
+    }
+
+    /// Columns that must not be null, an empty list means every column
+    pub fn drop_nulls_columns(&self) -> Option<Vec<String>> {
+        self.drop_nulls.as_ref().map(|columns| {
+            columns
+                .split(',')
+                .map(|c| c.trim())
+                .filter(|c| !c.is_empty())
+                .map(String::from)
+                .collect()
+        })
+    }
+
+    /// Columns to partition the written parquet directory by, parsed from `year,month`
+    pub fn partition_by_columns(&self) -> Option<Vec<String>> {
+        self.partition_by.as_ref().map(|columns| {
+            columns
+                .split(',')
+                .map(|c| c.trim())
+                .filter(|c| !c.is_empty())
+                .map(String::from)
+                .collect()
+        })
+    }
+
+    /// Pairs of (old, new) column names parsed from `old:new,old2:new2`
+    pub fn rename_columns(&self) -> Result<Option<Vec<(String, String)>>, OxenError> {
+        self.rename
+            .as_ref()
+            .map(|rename| parse_pairs(rename, "rename", "old:new"))
+            .transpose()
+    }
+
+    /// Columns to group by, parsed from `col1,col2`
+    pub fn group_by_columns(&self) -> Option<Vec<String>> {
+        self.group_by.as_ref().map(|columns| {
+            columns
+                .split(',')
+                .map(|c| c.trim())
+                .filter(|c| !c.is_empty())
+                .map(String::from)
+                .collect()
+        })
+    }
+
+    /// Pairs of (function, column) parsed from `sum:amount,mean:price`
+    pub fn aggregations(&self) -> Result<Option<Vec<(String, String)>>, OxenError> {
+        self.agg
+            .as_ref()
+            .map(|agg| parse_pairs(agg, "agg", "fn:col"))
+            .transpose()
+    }
+
+    /// Rolling aggregations parsed from `mean:price:7,sum:amount:3`
+    pub fn windows(&self) -> Result<Option<Vec<WindowAgg>>, OxenError> {
+        let Some(window) = &self.window else {
+            return Ok(None);
+        };
+
+        let mut windows: Vec<WindowAgg> = vec![];
+        for spec in window.split(',').filter(|s| !s.trim().is_empty()) {
+            let parts: Vec<&str> = spec.split(':').map(|p| p.trim()).collect();
+            let size = match parts[..] {
+                [func, column, size] if !func.is_empty() && !column.is_empty() => {
+                    size.parse::<usize>().ok().filter(|size| *size > 0)
+                }
+                _ => None,
+            };
+            match size {
+                Some(size) => windows.push(WindowAgg {
+                    func: parts[0].to_string(),
+                    column: parts[1].to_string(),
+                    size,
+                }),
+                None => {
+                    return Err(OxenError::basic_str(format!(
+                        "Invalid window '{spec}', expected fn:col:size with a size of at least 1"
+                    )));
+                }
+            }
+        }
+        Ok(Some(windows))
+    }
+
+    /// Split fractions parsed from `0.8,0.1,0.1` as train,val,test or `0.8,0.2` as train,test
+    pub fn seed_split_fractions(&self) -> Result<Option<Vec<(String, f64)>>, OxenError> {
+        let Some(seed_split) = &self.seed_split else {
+            return Ok(None);
+        };
+
+        let err = || {
+            OxenError::basic_str(format!(
+                "Invalid seed split '{seed_split}', expected train,val,test or train,test fractions that add up to 1"
+            ))
+        };
+        let fractions = seed_split
+            .split(',')
+            .map(|f| f.trim().parse::<f64>())
+            .collect::<Result<Vec<f64>, _>>()
+            .map_err(|_| err())?;
+        let names = match fractions.len() {
+            2 => vec!["train", "test"],
+            3 => vec!["train", "val", "test"],
+            _ => return Err(err()),
+        };
+        let total: f64 = fractions.iter().sum();
+        if fractions.iter().any(|f| !(0.0..=1.0).contains(f)) || (total - 1.0).abs() > 1e-6 {
+            return Err(err());
+        }
+
+        Ok(Some(
+            names.into_iter().map(String::from).zip(fractions).collect(),
+        ))
+    }
+
+    /// Pairs of (column, descending) parsed from `a:asc,b:desc`, a column without a direction
+    /// sorts descending only when reversed
+    pub fn sort_by_columns(&self) -> Result<Option<Vec<(String, bool)>>, OxenError> {
+        let Some(sort_by) = &self.sort_by else {
+            return Ok(None);
+        };
+
+        let mut columns: Vec<(String, bool)> = vec![];
+        for spec in sort_by.split(',').filter(|s| !s.trim().is_empty()) {
+            let (column, descending) = match spec.rsplit_once(':') {
+                Some((column, dir)) if dir.trim().eq_ignore_ascii_case("asc") => (column, false),
+                Some((column, dir)) if dir.trim().eq_ignore_ascii_case("desc") => (column, true),
+                _ => (spec, self.should_reverse),
+            };
+            if column.trim().is_empty() {
+                return Err(OxenError::basic_str(format!(
+                    "Invalid sort '{spec}', expected col or col:asc or col:desc"
+                )));
+            }
+            columns.push((column.trim().to_string(), descending));
+        }
+        Ok(Some(columns))
+    }
+
+    /// Pairs of (column, type) parsed from `col:int,other:double`
+    pub fn cast_columns(&self) -> Result<Option<Vec<(String, String)>>, OxenError> {
+        self.cast
+            .as_ref()
+            .map(|cast| parse_pairs(cast, "cast", "col:type"))
+            .transpose()
+    }
+
+    /// The (method, column) pairs to scale for --normalize
+    pub fn normalize_columns(&self) -> Result<Option<Vec<(String, String)>>, OxenError> {
+        self.normalize
+            .as_ref()
+            .map(|normalize| parse_pairs(normalize, "normalize", "method:col"))
+            .transpose()
+    }
+
     pub fn unique_columns(&self) -> Option<Vec<String>> {
         if let Some(columns) = self.unique.clone() {
             let split = columns
@@ -199,6 +576,19 @@ impl DFOpts {
         None
     }
 
+    /// Columns to compute the row hash over, an empty list means all the columns
+    pub fn hash_rows_columns(&self) -> Option<Vec<String>> {
+        if let Some(columns) = self.hash_rows.clone() {
+            let split = columns
+                .split(',')
+                .map(|c| c.trim().to_string())
+                .filter(|c| !c.is_empty())
+                .collect::<Vec<String>>();
+            return Some(split);
+        }
+        None
+    }
+
     pub fn get_host(&self) -> String {
         match &self.host {
             Some(host) => host.to_owned(),
@@ -335,3 +725,21 @@ impl DFOptsView {
         DFOptsView { opts: ordered_opts }
     }
 }
+
+// Parse comma separated `a:b` pairs, erroring on any pair missing a side
+fn parse_pairs(value: &str, name: &str, usage: &str) -> Result<Vec<(String, String)>, OxenError> {
+    let mut pairs: Vec<(String, String)> = vec![];
+    for pair in value.split(',').filter(|p| !p.trim().is_empty()) {
+        match pair.split_once(':') {
+            Some((a, b)) if !a.trim().is_empty() && !b.trim().is_empty() => {
+                pairs.push((a.trim().to_string(), b.trim().to_string()));
+            }
+            _ => {
+                return Err(OxenError::basic_str(format!(
+                    "Invalid {name} '{pair}', expected {usage}"
+                )));
+            }
+        }
+    }
+    Ok(pairs)
+}
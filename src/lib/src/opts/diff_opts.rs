@@ -10,4 +10,12 @@ pub struct DiffOpts {
     pub revision_1: Option<String>,
     pub revision_2: Option<String>,
     pub output: Option<PathBuf>,
+    // Format of the tabular diff written to `output`: csv, parquet, json or jsonl
+    pub output_format: Option<String>,
+    // Swap the two resources so additions and removals are inverted
+    pub reverse: bool,
+    // Print the diff without ANSI colors
+    pub no_color: bool,
+    // Match rows whose key columns are both null, by default null keys never match
+    pub null_equals_null: bool,
 }
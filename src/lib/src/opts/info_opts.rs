@@ -6,4 +6,5 @@ pub struct InfoOpts {
     pub revision: Option<String>, // commit id or branch
     pub verbose: bool,
     pub output_as_json: bool,
+    pub recursive: bool,
 }
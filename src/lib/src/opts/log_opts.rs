@@ -1,5 +1,9 @@
+use std::path::PathBuf;
+
 #[derive(Clone, Debug)]
 pub struct LogOpts {
     pub revision: Option<String>, // commit id or branch name
     pub remote: bool,
+    pub graph: bool,           // render the history as an ASCII graph
+    pub path: Option<PathBuf>, // only the commits that added, modified or removed this path
 }
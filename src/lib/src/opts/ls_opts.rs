@@ -9,6 +9,8 @@ pub struct ListOpts {
     pub revision: String,
     pub page_num: usize,
     pub page_size: usize,
+    // Show sizes, data types, and last modified commit for each entry
+    pub long: bool,
 }
 
 impl ListOpts {
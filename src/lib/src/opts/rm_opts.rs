@@ -4,6 +4,8 @@ use std::path::{Path, PathBuf};
 pub struct RmOpts {
     pub path: PathBuf,
     pub staged: bool,
+    // Stage the removal of a committed path but keep the working copy on disk
+    pub cached: bool,
     pub recursive: bool,
     pub remote: bool,
     // TODO: add `force` flag
@@ -15,6 +17,7 @@ impl RmOpts {
         RmOpts {
             path: path.as_ref().to_owned(),
             staged: false,
+            cached: false,
             recursive: false,
             remote: false,
         }
@@ -25,6 +28,7 @@ impl RmOpts {
         RmOpts {
             path: path.as_ref().to_owned(),
             staged: true,
+            cached: false,
             recursive: false,
             remote: false,
         }
@@ -35,6 +39,7 @@ impl RmOpts {
         RmOpts {
             path: path.as_ref().to_owned(),
             staged: opts.staged,
+            cached: opts.cached,
             recursive: opts.recursive,
             remote: opts.remote,
         }
@@ -24,6 +24,7 @@ use std::fs::OpenOptions;
 use std::future::Future;
 use std::io::prelude::*;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
 pub const DEFAULT_TEST_HOST: &str = "localhost:3000";
 
@@ -347,6 +348,7 @@ where
         path: PathBuf::from("test"),
         recursive: true,
         staged: false,
+        cached: false,
         remote: false,
     };
 
@@ -1588,3 +1590,23 @@ pub fn add_img_file_to_dir(dir: &Path, file_path: &Path) -> Result<PathBuf, Oxen
         Err(OxenError::basic_str(err))
     }
 }
+
+/// Writer that keeps everything written to it, to check output such as progress events
+#[derive(Clone, Default)]
+pub struct SharedWriter(Arc<Mutex<Vec<u8>>>);
+
+impl SharedWriter {
+    pub fn contents(&self) -> String {
+        String::from_utf8_lossy(&self.0.lock().unwrap()).to_string()
+    }
+}
+
+impl Write for SharedWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
@@ -1,6 +1,8 @@
 //! Various utility functions
 //!
 
+pub mod commit_graph;
+pub mod compression;
 pub mod concurrency;
 pub mod fs;
 pub mod hasher;
@@ -12,7 +14,7 @@ pub mod read_progress;
 pub mod str;
 
 pub use crate::util::read_progress::ReadProgress;
-pub use paginate::{paginate, paginate_with_total};
+pub use paginate::{paginate, paginate_with_cursor, paginate_with_total};
 
 pub mod oxen_date_format {
     use chrono::{DateTime, Local};
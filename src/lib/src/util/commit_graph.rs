@@ -0,0 +1,183 @@
+//! Render commit history as an ASCII graph, like `git log --graph`
+//!
+
+use std::collections::{HashMap, HashSet};
+
+use crate::model::Commit;
+
+/// Render the commits as lines of an ASCII graph, newest first.
+/// Each commit gets a `*` in its column, merges fork with `\` and branches join back with `/`.
+pub fn render(commits: &[Commit]) -> Vec<String> {
+    let mut lines: Vec<String> = vec![];
+    // Each column is the id of the commit the line is waiting on
+    let mut columns: Vec<String> = vec![];
+
+    for commit in topological_sort(commits) {
+        let col = match columns.iter().position(|id| *id == commit.id) {
+            Some(col) => col,
+            None => {
+                columns.push(commit.id.to_owned());
+                columns.len() - 1
+            }
+        };
+
+        let graph: Vec<&str> = (0..columns.len())
+            .map(|i| if i == col { "*" } else { "|" })
+            .collect();
+        lines.push(format!(
+            "{} {} {}",
+            graph.join(" "),
+            commit.id,
+            commit.message
+        ));
+
+        // Replace the commit with its parents, and join any lines waiting on the same commit
+        let mut next_columns: Vec<String> = vec![];
+        let mut edges: Vec<(usize, usize)> = vec![];
+        for (i, id) in columns.iter().enumerate() {
+            let ids = if i == col {
+                commit.parent_ids.clone()
+            } else {
+                vec![id.to_owned()]
+            };
+            for id in ids {
+                let n = match next_columns.iter().position(|next| *next == id) {
+                    Some(n) => n,
+                    None => {
+                        next_columns.push(id);
+                        next_columns.len() - 1
+                    }
+                };
+                edges.push((i, n));
+            }
+        }
+
+        if edges.iter().any(|(i, n)| i != n) {
+            lines.push(render_edges(&edges));
+        }
+        columns = next_columns;
+    }
+
+    lines
+}
+
+fn render_edges(edges: &[(usize, usize)]) -> String {
+    let width = edges
+        .iter()
+        .map(|(i, n)| 2 * i.max(n) + 2)
+        .max()
+        .unwrap_or(0);
+    let mut line = vec![' '; width];
+    for (i, n) in edges {
+        let (pos, glyph) = if n == i {
+            (2 * i, '|')
+        } else if n < i {
+            (2 * i - 1, '/')
+        } else {
+            (2 * i + 1, '\\')
+        };
+        if line[pos] == ' ' {
+            line[pos] = glyph;
+        }
+    }
+    line.into_iter().collect::<String>().trim_end().to_string()
+}
+
+// Children always come before their parents, otherwise newest first
+fn topological_sort(commits: &[Commit]) -> Vec<&Commit> {
+    let ids: HashSet<&str> = commits.iter().map(|c| c.id.as_str()).collect();
+    let mut num_children: HashMap<&str, usize> = HashMap::new();
+    for commit in commits.iter() {
+        for parent_id in commit.parent_ids.iter() {
+            if ids.contains(parent_id.as_str()) {
+                *num_children.entry(parent_id.as_str()).or_default() += 1;
+            }
+        }
+    }
+
+    let by_id: HashMap<&str, &Commit> = commits.iter().map(|c| (c.id.as_str(), c)).collect();
+    let mut ready: Vec<&Commit> = commits
+        .iter()
+        .filter(|c| !num_children.contains_key(c.id.as_str()))
+        .collect();
+
+    let mut sorted: Vec<&Commit> = vec![];
+    while !ready.is_empty() {
+        // Pop the newest commit that has no children left to render
+        ready.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+        let commit = ready.pop().unwrap();
+        sorted.push(commit);
+
+        for parent_id in commit.parent_ids.iter() {
+            if let Some(count) = num_children.get_mut(parent_id.as_str()) {
+                *count -= 1;
+                if *count == 0 {
+                    ready.push(by_id[parent_id.as_str()]);
+                }
+            }
+        }
+    }
+
+    sorted
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api;
+    use crate::command;
+    use crate::error::OxenError;
+    use crate::opts::LogOpts;
+    use crate::test;
+    use crate::util;
+
+    #[tokio::test]
+    async fn test_render_commit_graph_with_merge() -> Result<(), OxenError> {
+        test::run_training_data_repo_test_fully_committed_async(|repo| async move {
+            let og_branch = api::local::branches::current_branch(&repo)?.unwrap();
+            let base = api::local::commits::head_commit(&repo)?;
+
+            // Commit on a branch
+            let branch_name = "add-branch-file";
+            api::local::branches::create_checkout(&repo, branch_name)?;
+            let branch_file = repo.path.join("branch_file.txt");
+            util::fs::write_to_path(&branch_file, "branch")?;
+            command::add(&repo, &branch_file)?;
+            let branch_commit = command::commit(&repo, "Adding branch file")?;
+
+            // Commit on main
+            command::checkout(&repo, &og_branch.name).await?;
+            let main_file = repo.path.join("main_file.txt");
+            util::fs::write_to_path(&main_file, "main")?;
+            command::add(&repo, &main_file)?;
+            let main_commit = command::commit(&repo, "Adding main file")?;
+
+            let merge_commit = command::merge(&repo, branch_name)?.unwrap();
+            assert_eq!(merge_commit.parent_ids.len(), 2);
+
+            let opts = LogOpts {
+                revision: None,
+                remote: false,
+                graph: true,
+                path: None,
+            };
+            let commits = api::local::commits::list_with_opts(&repo, &opts).await?;
+            let lines = util::commit_graph::render(&commits);
+
+            let find = |pattern: &str| lines.iter().position(|line| line.starts_with(pattern));
+            let merge_idx = find(&format!("* {}", merge_commit.id)).unwrap();
+            let main_idx = find(&format!("* | {}", main_commit.id)).unwrap();
+            let branch_idx = find(&format!("| * {}", branch_commit.id)).unwrap();
+            let base_idx = find(&format!("* {}", base.id)).unwrap();
+
+            // Fork out to both parents, then join back at the base
+            assert_eq!(lines[merge_idx + 1], "|\\");
+            assert!(merge_idx < main_idx);
+            assert!(main_idx < branch_idx);
+            assert_eq!(lines[branch_idx + 1], "|/");
+            assert_eq!(branch_idx + 2, base_idx);
+
+            Ok(())
+        })
+        .await
+    }
+}
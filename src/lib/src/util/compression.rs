@@ -0,0 +1,201 @@
+//! Compression settings for the tarballs we push to a remote and the archives from `oxen save`
+//!
+
+use std::fmt;
+use std::str::FromStr;
+
+use flate2::Compression;
+
+use crate::error::OxenError;
+
+/// Environment variable to override the compression level, one of "fast", "default", "best", or 0-9
+pub const OXEN_COMPRESSION_ENV: &str = "OXEN_COMPRESSION";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionLevel {
+    // Favor CPU, good for fast networks
+    Fast,
+    Default,
+    // Favor ratio, good for slow networks
+    Best,
+    Level(u32),
+}
+
+impl CompressionLevel {
+    /// Read the compression level from OXEN_COMPRESSION, falling back to the default if it is unset or invalid
+    pub fn from_env() -> CompressionLevel {
+        match std::env::var(OXEN_COMPRESSION_ENV) {
+            Ok(val) => match CompressionLevel::from_str(&val) {
+                Ok(level) => level,
+                Err(err) => {
+                    log::warn!("{err}, using default compression");
+                    CompressionLevel::Default
+                }
+            },
+            Err(_) => CompressionLevel::Default,
+        }
+    }
+
+    pub fn to_gz(&self) -> Compression {
+        match self {
+            CompressionLevel::Fast => Compression::fast(),
+            CompressionLevel::Default => Compression::default(),
+            CompressionLevel::Best => Compression::best(),
+            CompressionLevel::Level(level) => Compression::new(*level),
+        }
+    }
+}
+
+impl FromStr for CompressionLevel {
+    type Err = OxenError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "fast" => Ok(CompressionLevel::Fast),
+            "default" => Ok(CompressionLevel::Default),
+            "best" => Ok(CompressionLevel::Best),
+            level => match level.parse::<u32>() {
+                Ok(level) if level <= 9 => Ok(CompressionLevel::Level(level)),
+                _ => Err(OxenError::basic_str(format!(
+                    "Invalid compression level '{s}', must be one of fast, default, best, or 0-9"
+                ))),
+            },
+        }
+    }
+}
+
+impl fmt::Display for CompressionLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CompressionLevel::Fast => write!(f, "fast"),
+            CompressionLevel::Default => write!(f, "default"),
+            CompressionLevel::Best => write!(f, "best"),
+            CompressionLevel::Level(level) => write!(f, "{level}"),
+        }
+    }
+}
+
+/// How the tarball written by `oxen save` is compressed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveCompression {
+    // Plain tar, for data that is already compressed
+    None,
+    Gzip,
+    Zstd,
+}
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+impl ArchiveCompression {
+    /// File extension for archives written with this compression
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ArchiveCompression::None => "tar",
+            ArchiveCompression::Gzip => "tar.gz",
+            ArchiveCompression::Zstd => "tar.zst",
+        }
+    }
+
+    /// Detect the compression from the first bytes of an archive, anything unrecognized is treated as plain tar
+    pub fn from_magic(bytes: &[u8]) -> ArchiveCompression {
+        if bytes.starts_with(&ZSTD_MAGIC) {
+            ArchiveCompression::Zstd
+        } else if bytes.starts_with(&GZIP_MAGIC) {
+            ArchiveCompression::Gzip
+        } else {
+            ArchiveCompression::None
+        }
+    }
+}
+
+impl FromStr for ArchiveCompression {
+    type Err = OxenError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "none" => Ok(ArchiveCompression::None),
+            "gzip" | "gz" => Ok(ArchiveCompression::Gzip),
+            "zstd" | "zst" => Ok(ArchiveCompression::Zstd),
+            _ => Err(OxenError::basic_str(format!(
+                "Invalid archive compression '{s}', must be one of none, gzip, or zstd"
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for ArchiveCompression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArchiveCompression::None => write!(f, "none"),
+            ArchiveCompression::Gzip => write!(f, "gzip"),
+            ArchiveCompression::Zstd => write!(f, "zstd"),
+        }
+    }
+}
+
+/// The gzip compression to use when building tarballs to push, can be overridden with OXEN_COMPRESSION
+pub fn push_compression() -> Compression {
+    let level = CompressionLevel::from_env();
+    log::debug!("Using {} compression for push", level);
+    level.to_gz()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use flate2::Compression;
+
+    use crate::error::OxenError;
+    use crate::util::compression::{ArchiveCompression, CompressionLevel};
+
+    #[test]
+    fn test_compression_level_from_str() -> Result<(), OxenError> {
+        assert_eq!(CompressionLevel::from_str("fast")?, CompressionLevel::Fast);
+        assert_eq!(CompressionLevel::from_str("BEST")?, CompressionLevel::Best);
+        assert_eq!(
+            CompressionLevel::from_str("default")?,
+            CompressionLevel::Default
+        );
+        assert_eq!(CompressionLevel::from_str("3")?, CompressionLevel::Level(3));
+        assert!(CompressionLevel::from_str("10").is_err());
+        assert!(CompressionLevel::from_str("zip").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_compression_level_to_gz() {
+        assert_eq!(CompressionLevel::Fast.to_gz(), Compression::fast());
+        assert_eq!(CompressionLevel::Best.to_gz(), Compression::best());
+        assert_eq!(CompressionLevel::Default.to_gz(), Compression::default());
+        assert_eq!(CompressionLevel::Level(4).to_gz(), Compression::new(4));
+    }
+
+    #[test]
+    fn test_archive_compression_from_str_and_magic() -> Result<(), OxenError> {
+        assert_eq!(
+            ArchiveCompression::from_str("none")?,
+            ArchiveCompression::None
+        );
+        assert_eq!(
+            ArchiveCompression::from_str("ZSTD")?,
+            ArchiveCompression::Zstd
+        );
+        assert!(ArchiveCompression::from_str("bz2").is_err());
+
+        assert_eq!(
+            ArchiveCompression::from_magic(&[0x1f, 0x8b, 0x08, 0x00]),
+            ArchiveCompression::Gzip
+        );
+        assert_eq!(
+            ArchiveCompression::from_magic(&[0x28, 0xb5, 0x2f, 0xfd]),
+            ArchiveCompression::Zstd
+        );
+        assert_eq!(
+            ArchiveCompression::from_magic(b".oxen"),
+            ArchiveCompression::None
+        );
+        Ok(())
+    }
+}
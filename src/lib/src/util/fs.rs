@@ -5,13 +5,14 @@
 use jwalk::WalkDir;
 
 use simdutf8::compat::from_utf8;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::fs::OpenOptions;
 use std::io::prelude::*;
 use std::io::BufReader;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::Mutex;
 
 use crate::constants;
 use crate::constants::CACHE_DIR;
@@ -34,7 +35,119 @@ use crate::{api, util};
 
 // Deprecated
 pub fn oxen_hidden_dir(repo_path: impl AsRef<Path>) -> PathBuf {
-    PathBuf::from(repo_path.as_ref()).join(Path::new(constants::OXEN_HIDDEN_DIR))
+    let repo_path = repo_path.as_ref();
+    if let Some(oxen_dir) = overridden_oxen_dir(repo_path) {
+        return oxen_dir;
+    }
+    PathBuf::from(repo_path).join(Path::new(constants::OXEN_HIDDEN_DIR))
+}
+
+/// The .oxen dir set by the OXEN_DIR env var and the repository root it was initialized for.
+/// Only that repository uses the override, every other repo keeps its own .oxen dir.
+#[derive(Debug, Clone)]
+struct OxenDirOverride {
+    oxen_dir: PathBuf,
+    work_tree: PathBuf,
+    // Whether a repo path resolves to the work tree, so each path is only canonicalized once
+    is_work_tree: HashMap<PathBuf, bool>,
+}
+
+// Keyed by the OXEN_DIR value it was read for, so changing the env var is still picked up
+static OXEN_DIR_OVERRIDE: Mutex<Option<(String, OxenDirOverride)>> = Mutex::new(None);
+
+fn oxen_dir_override() -> Option<(PathBuf, PathBuf)> {
+    with_oxen_dir_override(|dir_override| {
+        (
+            dir_override.oxen_dir.clone(),
+            dir_override.work_tree.clone(),
+        )
+    })
+}
+
+// Reads the work tree file the first time it is needed for an OXEN_DIR, and does not touch
+// the filesystem at all when OXEN_DIR is unset. Nothing is cached until the work tree file
+// exists, so a repository initialized later in the process still sees it.
+fn with_oxen_dir_override<T>(f: impl FnOnce(&mut OxenDirOverride) -> T) -> Option<T> {
+    let env_oxen_dir = std::env::var(constants::OXEN_DIR_ENV).ok()?;
+    let mut cache = OXEN_DIR_OVERRIDE
+        .lock()
+        .unwrap_or_else(|err| err.into_inner());
+    match cache.as_mut() {
+        Some((cached_env, dir_override)) if *cached_env == env_oxen_dir => Some(f(dir_override)),
+        _ => {
+            let oxen_dir = PathBuf::from(&env_oxen_dir);
+            let work_tree =
+                std::fs::read_to_string(oxen_dir.join(constants::WORK_TREE_FILE)).ok()?;
+            let mut dir_override = OxenDirOverride {
+                oxen_dir,
+                work_tree: PathBuf::from(work_tree.trim()),
+                is_work_tree: HashMap::new(),
+            };
+            let result = f(&mut dir_override);
+            *cache = Some((env_oxen_dir, dir_override));
+            Some(result)
+        }
+    }
+}
+
+// The overridden .oxen dir if `path` is the repository it was initialized for
+fn overridden_oxen_dir(path: &Path) -> Option<PathBuf> {
+    with_oxen_dir_override(|dir_override| {
+        if path == dir_override.work_tree {
+            return Some(dir_override.oxen_dir.clone());
+        }
+        let work_tree = &dir_override.work_tree;
+        // Relative paths depend on the current dir, so only absolute ones are remembered
+        let matches = if path.is_absolute() {
+            *dir_override
+                .is_work_tree
+                .entry(path.to_path_buf())
+                .or_insert_with(|| is_work_tree(path, work_tree))
+        } else {
+            is_work_tree(path, work_tree)
+        };
+        matches.then(|| dir_override.oxen_dir.clone())
+    })
+    .flatten()
+}
+
+fn is_work_tree(path: &Path, work_tree: &Path) -> bool {
+    path == work_tree
+        || path
+            .canonicalize()
+            .map(|path| path == work_tree)
+            .unwrap_or(false)
+}
+
+/// Link the OXEN_DIR env var to the repository root at `repo_path` so that the .oxen dir lives
+/// there instead of within the repository. Does nothing if OXEN_DIR is not set, or if it has
+/// already been linked to another repository.
+pub fn link_oxen_dir(repo_path: &Path) -> Result<(), OxenError> {
+    let Ok(oxen_dir) = std::env::var(constants::OXEN_DIR_ENV) else {
+        return Ok(());
+    };
+
+    if let Some((oxen_dir, work_tree)) = oxen_dir_override() {
+        if !is_work_tree(repo_path, &work_tree) {
+            log::warn!(
+                "{} {:?} already belongs to {:?}, using {:?} for {:?}",
+                constants::OXEN_DIR_ENV,
+                oxen_dir,
+                work_tree,
+                constants::OXEN_HIDDEN_DIR,
+                repo_path
+            );
+        }
+        return Ok(());
+    }
+
+    let oxen_dir = PathBuf::from(oxen_dir);
+    let work_tree = repo_path.canonicalize()?;
+    create_dir_all(&oxen_dir)?;
+    write_to_path(
+        oxen_dir.join(constants::WORK_TREE_FILE),
+        work_tree.to_string_lossy(),
+    )
 }
 
 pub fn oxen_tmp_dir() -> Result<PathBuf, OxenError> {
@@ -211,9 +324,25 @@ pub fn version_path_from_hash_and_file(
     let version_dir = version_dir_from_hash(dst, hash);
     let extension = extension_from_path(&filename);
     if extension.is_empty() {
-        version_dir.join(VERSION_FILE_NAME)
+        return version_dir.join(VERSION_FILE_NAME);
+    }
+
+    let legacy_path = version_dir.join(format!("{}.{}", VERSION_FILE_NAME, extension));
+    let inner_extension = inner_extension_from_path(&filename);
+    if compression_from_path(&filename).is_none() || inner_extension.is_empty() {
+        return legacy_path;
+    }
+
+    // Keep the inner extension of compressed files, ie data.csv.gz, so the version file
+    // can be read as a data frame. Versions written before this only have data.gz
+    let version_path = version_dir.join(format!(
+        "{}.{}.{}",
+        VERSION_FILE_NAME, inner_extension, extension
+    ));
+    if !version_path.exists() && legacy_path.exists() {
+        legacy_path
     } else {
-        version_dir.join(format!("{}.{}", VERSION_FILE_NAME, extension))
+        version_path
     }
 }
 
@@ -236,6 +365,43 @@ pub fn extension_from_path(path: &Path) -> String {
     }
 }
 
+/// Compression of a file from its last extension, `gzip` for .gz and `zstd` for .zst
+pub fn compression_from_path(path: &Path) -> Option<&'static str> {
+    match extension_from_path(path).as_str() {
+        "gz" => Some("gzip"),
+        "zst" => Some("zstd"),
+        _ => None,
+    }
+}
+
+/// Extension of the data in a compressed file, ie `csv` for data.csv.gz.
+/// Same as extension_from_path for files that are not compressed.
+pub fn inner_extension_from_path(path: &Path) -> String {
+    if compression_from_path(path).is_some() {
+        if let Some(stem) = path.file_stem() {
+            return extension_from_path(Path::new(stem));
+        }
+    }
+    extension_from_path(path)
+}
+
+/// Decompress a .gz or .zst file from `src` into `dst`
+pub fn decompress_file(src: &Path, dst: &Path) -> Result<(), OxenError> {
+    let file = File::open(src)?;
+    let mut reader: Box<dyn Read> = match compression_from_path(src) {
+        Some("gzip") => Box::new(flate2::read::GzDecoder::new(BufReader::new(file))),
+        Some("zstd") => Box::new(zstd::stream::read::Decoder::new(file)?),
+        _ => {
+            return Err(OxenError::basic_str(format!(
+                "Unknown compression {src:?}, expected .gz or .zst"
+            )))
+        }
+    };
+    let mut writer = File::create(dst)?;
+    std::io::copy(&mut reader, &mut writer)?;
+    Ok(())
+}
+
 pub fn version_dir_from_hash(dst: impl AsRef<Path>, hash: String) -> PathBuf {
     let topdir = &hash[..2];
     let subdir = &hash[2..];
@@ -539,6 +705,13 @@ pub fn rlist_dirs_in_repo(repo: &LocalRepository) -> Vec<PathBuf> {
 
 /// Recursively tries to traverse up for an .oxen directory, returns None if not found
 pub fn get_repo_root(path: &Path) -> Option<PathBuf> {
+    if let Some((_, work_tree)) = oxen_dir_override() {
+        let full_path = path.canonicalize().unwrap_or(path.to_path_buf());
+        if full_path.starts_with(&work_tree) {
+            return Some(work_tree);
+        }
+    }
+
     if path.join(OXEN_HIDDEN_DIR).exists() {
         return Some(path.to_path_buf());
     }
@@ -798,13 +971,35 @@ pub fn is_markdown(path: &Path) -> bool {
 }
 
 pub fn is_video(path: &Path) -> bool {
-    let exts: HashSet<String> = vec!["mp4"].into_iter().map(String::from).collect();
-    contains_ext(path, &exts)
+    video_mime_type_from_ext(path).is_some()
 }
 
 pub fn is_audio(path: &Path) -> bool {
-    let exts: HashSet<String> = vec!["mp3", "wav"].into_iter().map(String::from).collect();
-    contains_ext(path, &exts)
+    audio_mime_type_from_ext(path).is_some()
+}
+
+fn video_mime_type_from_ext(path: &Path) -> Option<&'static str> {
+    match file_extension(path).to_lowercase().as_str() {
+        "mp4" => Some("video/mp4"),
+        "m4v" => Some("video/x-m4v"),
+        "mov" => Some("video/quicktime"),
+        "avi" => Some("video/x-msvideo"),
+        "mkv" => Some("video/x-matroska"),
+        "webm" => Some("video/webm"),
+        _ => None,
+    }
+}
+
+fn audio_mime_type_from_ext(path: &Path) -> Option<&'static str> {
+    match file_extension(path).to_lowercase().as_str() {
+        "mp3" => Some("audio/mpeg"),
+        "wav" => Some("audio/x-wav"),
+        "flac" => Some("audio/x-flac"),
+        "ogg" => Some("audio/ogg"),
+        "m4a" => Some("audio/m4a"),
+        "aac" => Some("audio/aac"),
+        _ => None,
+    }
 }
 
 pub fn is_utf8(path: &Path) -> bool {
@@ -819,8 +1014,13 @@ pub fn file_mime_type(path: &Path) -> String {
     match infer::get_from_path(path) {
         Ok(Some(kind)) => String::from(kind.mime_type()),
         _ => {
+            // Magic bytes were not recognized, fall back to the extension for media files
             if is_markdown(path) {
                 String::from("text/markdown")
+            } else if let Some(mime_type) = video_mime_type_from_ext(path) {
+                String::from(mime_type)
+            } else if let Some(mime_type) = audio_mime_type_from_ext(path) {
+                String::from(mime_type)
             } else if is_utf8(path) {
                 String::from("text/plain")
             } else if path.is_dir() {
@@ -866,8 +1066,10 @@ pub fn datatype_from_mimetype(path: &Path, mime_type: &str) -> EntryDataType {
         "audio/mpeg" => EntryDataType::Audio,
         "audio/m4a" => EntryDataType::Audio,
         "audio/x-wav" => EntryDataType::Audio,
+        "audio/wav" => EntryDataType::Audio,
         "audio/ogg" => EntryDataType::Audio,
         "audio/x-flac" => EntryDataType::Audio,
+        "audio/flac" => EntryDataType::Audio,
         "audio/aac" => EntryDataType::Audio,
         "audio/x-aiff" => EntryDataType::Audio,
         "audio/x-dsf" => EntryDataType::Audio,
@@ -1409,6 +1611,48 @@ mod tests {
         })
     }
 
+    #[test]
+    fn detect_file_type_audio_video() -> Result<(), OxenError> {
+        test::run_empty_dir_test(|dir| {
+            let video_dir = Path::new("data").join("test").join("video");
+            let audio_dir = Path::new("data").join("test").join("audio");
+
+            // Detected from the magic bytes
+            assert_eq!(
+                EntryDataType::Video,
+                util::fs::file_data_type(&video_dir.join("basketball.mp4"))
+            );
+            assert_eq!(
+                EntryDataType::Video,
+                util::fs::file_data_type(&video_dir.join("dog_skatez.mov"))
+            );
+            assert_eq!(
+                EntryDataType::Audio,
+                util::fs::file_data_type(&audio_dir.join("121-121726-0005.wav"))
+            );
+            assert_eq!(
+                EntryDataType::Audio,
+                util::fs::file_data_type(&audio_dir.join("121-121726-0005.flac"))
+            );
+
+            // Falls back to the extension when the header is not recognized
+            let truncated_mp4 = dir.join("truncated.mp4");
+            let truncated_wav = dir.join("truncated.wav");
+            util::fs::write_to_path(&truncated_mp4, "not a real header")?;
+            util::fs::write_to_path(&truncated_wav, "not a real header")?;
+            assert_eq!(
+                EntryDataType::Video,
+                util::fs::file_data_type(&truncated_mp4)
+            );
+            assert_eq!(
+                EntryDataType::Audio,
+                util::fs::file_data_type(&truncated_wav)
+            );
+
+            Ok(())
+        })
+    }
+
     #[test]
     fn detect_file_type_json_array() -> Result<(), OxenError> {
         test::run_empty_dir_test(|_| {
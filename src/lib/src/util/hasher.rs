@@ -25,10 +25,33 @@ pub fn hash_str_sha256<S: AsRef<str>>(str: S) -> String {
     format!("{result:x}")
 }
 
+/// Streams the file through sha256, for formats that need a standard checksum rather than our xxh3 hashes
+pub fn hash_file_sha256(path: &Path) -> Result<String, OxenError> {
+    let file = File::open(path).map_err(|err| {
+        OxenError::basic_str(format!("Could not open file {path:?} due to {err:?}"))
+    })?;
+    let mut reader = BufReader::new(file);
+    let mut hasher = Sha256::new();
+    let mut buffer = [0; 4096];
+    loop {
+        let count = reader.read(&mut buffer)?;
+        if count == 0 {
+            break;
+        }
+        hasher.update(&buffer[..count]);
+    }
+    let result = hasher.finalize();
+    Ok(format!("{result:x}"))
+}
+
 pub fn hash_buffer_128bit(buffer: &[u8]) -> u128 {
     xxh3_128(buffer)
 }
 
+pub fn hash_buffer_with_seed(buffer: &[u8], seed: u64) -> u64 {
+    xxhash_rust::xxh3::xxh3_64_with_seed(buffer, seed)
+}
+
 pub fn compute_commit_hash<E>(commit_data: &NewCommit, entries: &[E]) -> String
 where
     E: ContentHashable + std::fmt::Debug,
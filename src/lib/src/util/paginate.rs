@@ -1,3 +1,6 @@
+use std::fmt::Write;
+
+use crate::error::OxenError;
 use crate::view::Pagination;
 
 /// Returns a vector of entries and the total number of pages.
@@ -197,9 +200,77 @@ pub fn paginate_files_assuming_dirs<T: Clone>(
     (result, pagination)
 }
 
+/// Returns a page of entries after the opaque `cursor`, and the cursor for the next page if there is one.
+/// Entries are ordered by `key`, and an empty cursor starts from the beginning.
+/// The cursor is the hex encoded key of the last entry in the page, so it stays stable as entries are added.
+pub fn paginate_with_cursor<T: Clone>(
+    mut entries: Vec<T>,
+    cursor: &str,
+    page_size: usize,
+    key: impl Fn(&T) -> String,
+) -> Result<(Vec<T>, Pagination, Option<String>), OxenError> {
+    if page_size == 0 {
+        return Err(OxenError::basic_str("page_size must be greater than 0"));
+    }
+
+    entries.sort_by_key(&key);
+    let total_entries = entries.len();
+    let total_pages = (total_entries as f64 / page_size as f64).ceil() as usize;
+
+    let start = if cursor.is_empty() {
+        0
+    } else {
+        let last_key = decode_cursor(cursor)?;
+        entries.partition_point(|entry| key(entry) <= last_key)
+    };
+    let end = (start + page_size).min(total_entries);
+    let page = entries[start..end].to_vec();
+
+    let next_cursor = if end < total_entries {
+        page.last().map(|entry| encode_cursor(&key(entry)))
+    } else {
+        None
+    };
+
+    let pagination = Pagination {
+        page_size,
+        page_number: start / page_size + 1,
+        total_pages,
+        total_entries,
+    };
+
+    Ok((page, pagination, next_cursor))
+}
+
+/// Encode a key into an opaque cursor token
+pub fn encode_cursor(key: &str) -> String {
+    let mut cursor = String::with_capacity(key.len() * 2);
+    for b in key.bytes() {
+        // Writing to a String cannot fail
+        let _ = write!(cursor, "{:02x}", b);
+    }
+    cursor
+}
+
+/// Decode an opaque cursor token back into the key it was created from
+pub fn decode_cursor(cursor: &str) -> Result<String, OxenError> {
+    let invalid = || OxenError::basic_str(format!("Invalid pagination cursor: {cursor}"));
+    if cursor.len() % 2 != 0 {
+        return Err(invalid());
+    }
+
+    let bytes = (0..cursor.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&cursor[i..i + 2], 16))
+        .collect::<Result<Vec<u8>, _>>()
+        .map_err(|_| invalid())?;
+    String::from_utf8(bytes).map_err(|_| invalid())
+}
+
 #[cfg(test)]
 mod tests {
-    use super::paginate_dirs_and_files;
+    use super::{paginate_dirs_and_files, paginate_with_cursor};
+    use crate::error::OxenError;
     use std::path::PathBuf;
 
     #[test]
@@ -358,4 +429,34 @@ mod tests {
             vec![PathBuf::from("file3"), PathBuf::from("file4")]
         );
     }
+
+    #[test]
+    fn test_paginate_with_cursor_walks_all_pages() -> Result<(), OxenError> {
+        let entries: Vec<String> = (0..25).map(|i| format!("branch-{i:02}")).collect();
+
+        let mut cursor = String::new();
+        let mut seen: Vec<String> = vec![];
+        let mut num_pages = 0;
+        loop {
+            let (page, _, next_cursor) =
+                paginate_with_cursor(entries.clone(), &cursor, 10, |e| e.to_owned())?;
+            seen.extend(page);
+            num_pages += 1;
+            match next_cursor {
+                Some(next_cursor) => cursor = next_cursor,
+                None => break,
+            }
+        }
+
+        assert_eq!(num_pages, 3);
+        assert_eq!(seen, entries);
+        Ok(())
+    }
+
+    #[test]
+    fn test_paginate_with_cursor_invalid_cursor() {
+        let entries = vec!["a".to_string()];
+        let result = paginate_with_cursor(entries, "not a cursor", 10, |e| e.to_owned());
+        assert!(result.is_err());
+    }
 }
@@ -1,7 +1,14 @@
-use std::sync::Arc;
+use std::io::Write;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+use std::sync::{Arc, Mutex};
 use tokio::time::Duration;
 
-use indicatif::{ProgressBar, ProgressStyle};
+use bytesize::ByteSize;
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+use serde::{Deserialize, Serialize};
+
+use crate::error::OxenError;
 
 pub enum ProgressBarType {
     Counter,
@@ -14,9 +21,17 @@ pub fn spinner_with_msg(msg: String) -> ProgressBar {
     spinner.set_message(msg);
     spinner.set_style(ProgressStyle::default_spinner());
     spinner.enable_steady_tick(Duration::from_millis(100));
+    hide_unless_bar_mode(&spinner);
     spinner
 }
 
+// Keep bars from drawing over the plain or json progress on stderr
+fn hide_unless_bar_mode(bar: &ProgressBar) {
+    if progress_mode() != ProgressMode::Bar {
+        bar.set_draw_target(ProgressDrawTarget::hidden());
+    }
+}
+
 pub fn oxen_progress_bar(size: u64, progress_type: ProgressBarType) -> Arc<ProgressBar> {
     let bar = Arc::new(ProgressBar::new(size));
     bar.set_style(
@@ -25,6 +40,7 @@ pub fn oxen_progress_bar(size: u64, progress_type: ProgressBarType) -> Arc<Progr
             .unwrap()
             .progress_chars("🌾🐂➖"),
     );
+    hide_unless_bar_mode(&bar);
     bar
 }
 
@@ -51,6 +67,7 @@ pub fn oxen_progress_bar_with_msg(size: u64, msg: impl AsRef<str>) -> Arc<Progre
             .unwrap()
             .progress_chars("🌾🐂➖"),
     );
+    hide_unless_bar_mode(&bar);
     bar
 }
 
@@ -78,3 +95,226 @@ pub fn progress_type_to_template(progress_type: ProgressBarType) -> String {
         ProgressBarType::None => "{spinner:.green} [{elapsed_precise}] [{wide_bar}]".to_string(),
     }
 }
+
+/// How push and pull report their progress, set from the cli with --progress
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProgressMode {
+    /// Draw a progress bar in the terminal
+    Bar,
+    /// Write human readable progress lines to stderr
+    Plain,
+    /// Write newline delimited json progress events to stderr
+    Json,
+}
+
+static PROGRESS_MODE: AtomicU8 = AtomicU8::new(0);
+
+impl ProgressMode {
+    fn to_u8(self) -> u8 {
+        match self {
+            ProgressMode::Bar => 0,
+            ProgressMode::Plain => 1,
+            ProgressMode::Json => 2,
+        }
+    }
+
+    fn from_u8(value: u8) -> ProgressMode {
+        match value {
+            1 => ProgressMode::Plain,
+            2 => ProgressMode::Json,
+            _ => ProgressMode::Bar,
+        }
+    }
+}
+
+impl FromStr for ProgressMode {
+    type Err = OxenError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bar" => Ok(ProgressMode::Bar),
+            "plain" => Ok(ProgressMode::Plain),
+            "json" => Ok(ProgressMode::Json),
+            _ => Err(OxenError::basic_str(format!(
+                "Invalid progress mode '{s}', must be one of bar, plain, json"
+            ))),
+        }
+    }
+}
+
+/// Set how progress is reported for the rest of the process
+pub fn set_progress_mode(mode: ProgressMode) {
+    PROGRESS_MODE.store(mode.to_u8(), Ordering::Relaxed);
+}
+
+pub fn progress_mode() -> ProgressMode {
+    ProgressMode::from_u8(PROGRESS_MODE.load(Ordering::Relaxed))
+}
+
+/// A single progress update, written as one line of json in ProgressMode::Json
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProgressEvent {
+    pub phase: String,
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+    pub entries_done: u64,
+    pub entries_total: u64,
+}
+
+/// Tracks bytes and entries transferred for a phase, drawing a bar or writing events
+/// to stderr depending on the ProgressMode
+pub struct ProgressReporter {
+    phase: String,
+    mode: ProgressMode,
+    // Hidden unless in ProgressMode::Bar, still keeps the byte count
+    bar: Arc<ProgressBar>,
+    entries_done: AtomicU64,
+    entries_total: u64,
+    writer: Mutex<Box<dyn Write + Send>>,
+}
+
+impl ProgressReporter {
+    /// Reports with the process wide progress_mode() to stderr
+    pub fn new(phase: impl AsRef<str>, bytes_total: u64, entries_total: u64) -> Arc<Self> {
+        ProgressReporter::with_writer(
+            phase,
+            bytes_total,
+            entries_total,
+            progress_mode(),
+            Box::new(std::io::stderr()),
+        )
+    }
+
+    pub fn with_writer(
+        phase: impl AsRef<str>,
+        bytes_total: u64,
+        entries_total: u64,
+        mode: ProgressMode,
+        writer: Box<dyn Write + Send>,
+    ) -> Arc<Self> {
+        let bar = match mode {
+            ProgressMode::Bar => oxen_progress_bar(bytes_total, ProgressBarType::Bytes),
+            ProgressMode::Plain | ProgressMode::Json => {
+                let bar = Arc::new(ProgressBar::hidden());
+                bar.set_length(bytes_total);
+                bar
+            }
+        };
+        Arc::new(ProgressReporter {
+            phase: phase.as_ref().to_string(),
+            mode,
+            bar,
+            entries_done: AtomicU64::new(0),
+            entries_total,
+            writer: Mutex::new(writer),
+        })
+    }
+
+    /// The underlying bar, for apis that increment the byte count themselves
+    pub fn bar(&self) -> Arc<ProgressBar> {
+        self.bar.clone()
+    }
+
+    pub fn inc_bytes(&self, n: u64) {
+        self.bar.inc(n);
+        self.report();
+    }
+
+    pub fn inc_entries(&self, n: u64) {
+        self.entries_done.fetch_add(n, Ordering::Relaxed);
+        self.report();
+    }
+
+    pub fn enable_steady_tick(&self, interval: Duration) {
+        if self.mode == ProgressMode::Bar {
+            self.bar.enable_steady_tick(interval);
+        }
+    }
+
+    /// Clear the bar, or write the final event
+    pub fn finish(&self) {
+        self.bar.finish_and_clear();
+        self.report();
+    }
+
+    fn report(&self) {
+        if self.mode == ProgressMode::Bar {
+            return;
+        }
+
+        // Hold the lock while reading the counts so events are written in order
+        let mut writer = self.writer.lock().unwrap();
+        let event = ProgressEvent {
+            phase: self.phase.clone(),
+            bytes_done: self.bar.position(),
+            bytes_total: self.bar.length().unwrap_or(0),
+            entries_done: self.entries_done.load(Ordering::Relaxed),
+            entries_total: self.entries_total,
+        };
+        let line = match self.mode {
+            ProgressMode::Json => match serde_json::to_string(&event) {
+                Ok(line) => line,
+                Err(err) => {
+                    log::error!("Could not serialize progress event: {err}");
+                    return;
+                }
+            },
+            _ => format!(
+                "{}: {}/{} {}/{} entries",
+                event.phase,
+                ByteSize::b(event.bytes_done),
+                ByteSize::b(event.bytes_total),
+                event.entries_done,
+                event.entries_total
+            ),
+        };
+        if let Err(err) = writeln!(writer, "{line}") {
+            log::error!("Could not write progress: {err}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::OxenError;
+    use crate::test::SharedWriter;
+    use crate::util::progress_bar::{ProgressEvent, ProgressMode, ProgressReporter};
+
+    #[test]
+    fn test_progress_mode_from_str() -> Result<(), OxenError> {
+        assert_eq!("json".parse::<ProgressMode>()?, ProgressMode::Json);
+        assert_eq!("plain".parse::<ProgressMode>()?, ProgressMode::Plain);
+        assert!("fancy".parse::<ProgressMode>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_progress_reporter_json_events() -> Result<(), OxenError> {
+        let writer = SharedWriter::default();
+        let reporter = ProgressReporter::with_writer(
+            "push",
+            10,
+            2,
+            ProgressMode::Json,
+            Box::new(writer.clone()),
+        );
+        reporter.inc_bytes(4);
+        reporter.inc_entries(1);
+        reporter.inc_bytes(6);
+        reporter.inc_entries(1);
+        reporter.finish();
+
+        let output = writer.contents();
+        let events: Vec<ProgressEvent> = output
+            .lines()
+            .map(serde_json::from_str)
+            .collect::<Result<_, _>>()?;
+        assert_eq!(events.len(), 5);
+        assert_eq!(events[0].bytes_done, 4);
+        let last = events.last().unwrap();
+        assert_eq!(last.phase, "push");
+        assert_eq!(last.bytes_done, 10);
+        assert_eq!(last.entries_done, 2);
+        Ok(())
+    }
+}
@@ -2,6 +2,7 @@
 //!
 
 pub mod branch;
+pub mod chunked_upload;
 pub mod commit;
 pub mod compare;
 pub mod data_type_count;
@@ -14,6 +15,7 @@ pub mod health;
 pub mod http;
 pub mod json_data_frame;
 pub mod json_data_frame_view;
+pub mod line_range;
 pub mod merge;
 pub mod mime_type_count;
 pub mod namespace;
@@ -22,11 +24,13 @@ pub mod pagination;
 pub mod remote_staged_status;
 pub mod repository;
 pub mod schema;
+pub mod signed_url;
 pub mod sql_parse_error;
 pub mod status_message;
 pub mod tabular_diff_view;
 pub mod version;
 
+pub use crate::view::chunked_upload::{ChunkedUploadRequest, ChunkedUploadResponse};
 pub use crate::view::compare::CompareEntriesResponse;
 pub use crate::view::data_type_count::DataTypeCount;
 pub use crate::view::file_metadata::{FileMetadata, FileMetadataResponse, FilePathsResponse};
@@ -48,31 +52,37 @@ pub use crate::view::repository::{
 };
 
 pub use crate::view::entry::{
-    CommitEntryVersion, EntryResponse, PaginatedDirEntries, PaginatedDirEntriesResponse,
-    PaginatedEntries, PaginatedEntryVersions, PaginatedEntryVersionsResponse, RemoteEntryResponse,
+    CommitEntryVersion, EntryResponse, PaginatedCommitEntries, PaginatedCommitEntriesResponse,
+    PaginatedDirEntries, PaginatedDirEntriesResponse, PaginatedEntries, PaginatedEntryVersions,
+    PaginatedEntryVersionsResponse, RemoteEntryResponse,
 };
 
 pub use crate::view::commit::{
-    CommitResponse, CommitStatsResponse, ListCommitResponse, PaginatedCommits,
+    CommitCacheStatusResponse, CommitResponse, CommitStatsResponse, CursorPaginatedCommits,
+    ListCommitResponse, PaginatedCommits,
 };
 
 pub use crate::view::branch::{
     BranchLockResponse, BranchNew, BranchNewFromExisting, BranchRemoteMerge, BranchResponse,
-    BranchUpdate, ListBranchesResponse,
+    BranchUpdate, CursorPaginatedBranches, ListBranchesResponse,
 };
 
 pub use crate::view::compare::CompareResult;
 
 pub use crate::view::entry_metadata::MetadataEntryResponse;
+pub use crate::view::line_range::LineRangeResponse;
+pub use crate::view::signed_url::SignedUrlResponse;
 
 pub use crate::view::pagination::Pagination;
 
-pub use crate::view::health::HealthResponse;
+pub use crate::view::health::{HealthChecks, HealthResponse, SubsystemHealth, SubsystemStatus};
 pub use crate::view::oxen_response::OxenResponse;
 
 pub use crate::view::remote_staged_status::{
-    ListStagedFileModResponseDF, ListStagedFileModResponseRaw, RemoteStagedStatus,
-    RemoteStagedStatusResponse, StagedFileModResponse,
+    AllStagedStatusResponse, IdentifierStagedStatus, ListStagedFileModResponseDF,
+    ListStagedFileModResponseRaw, PaginatedStagedFiles, PaginatedStagedFilesResponse,
+    RemoteStagedStatus, RemoteStagedStatusResponse, StagedFileModResponse, StagingConflict,
+    StagingConflictResponse,
 };
 
 pub use crate::view::sql_parse_error::SQLParseError;
@@ -51,3 +51,11 @@ pub struct ListBranchesResponse {
     pub status: StatusMessage,
     pub branches: Vec<Branch>,
 }
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CursorPaginatedBranches {
+    #[serde(flatten)]
+    pub status: StatusMessage,
+    pub branches: Vec<Branch>,
+    pub next_cursor: Option<String>,
+}
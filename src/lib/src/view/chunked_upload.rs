@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+use super::StatusMessage;
+
+/// Body to start a chunked upload of a single file into the remote staging area
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ChunkedUploadRequest {
+    pub branch: String,
+    pub directory: String,
+    pub file_name: String,
+    pub total_size: u64,
+    pub total_chunks: usize,
+    // Hash of the full file, checked once the parts are put back together
+    pub hash: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct ChunkedUploadResponse {
+    #[serde(flatten)]
+    pub status: StatusMessage,
+    pub upload_id: String,
+    // Repo relative uris to PUT each part to, in order
+    pub part_uris: Vec<String>,
+}
@@ -1,3 +1,4 @@
+use crate::core::cache::cacher_status::CacherStatus;
 use crate::model::{Commit, CommitStats};
 use serde::{Deserialize, Serialize};
 
@@ -32,6 +33,14 @@ pub struct CommitSyncStatusResponse {
     pub num_unsynced: usize,
 }
 
+/// Status of the cachers the server runs on a commit after it is pushed, `None` if they have not started
+#[derive(Deserialize, Serialize, Debug)]
+pub struct CommitCacheStatusResponse {
+    #[serde(flatten)]
+    pub status: StatusMessage,
+    pub cache_status: Option<CacherStatus>,
+}
+
 #[derive(Deserialize, Serialize, Debug)]
 pub struct CommitTreeValidationResponse {
     #[serde(flatten)]
@@ -56,3 +65,13 @@ pub struct PaginatedCommits {
     #[serde(flatten)]
     pub pagination: Pagination,
 }
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct CursorPaginatedCommits {
+    #[serde(flatten)]
+    pub status: StatusMessage,
+    pub commits: Vec<Commit>,
+    #[serde(flatten)]
+    pub pagination: Pagination,
+    pub next_cursor: Option<String>,
+}
@@ -45,6 +45,22 @@ pub struct PaginatedEntries {
     pub total_entries: usize,
 }
 
+/// Metadata of the files in a commit, without their contents
+#[derive(Deserialize, Serialize, Debug)]
+pub struct PaginatedCommitEntries {
+    pub entries: Vec<CommitEntry>,
+    #[serde(flatten)]
+    pub pagination: Pagination,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct PaginatedCommitEntriesResponse {
+    #[serde(flatten)]
+    pub status: StatusMessage,
+    #[serde(flatten)]
+    pub entries: PaginatedCommitEntries,
+}
+
 #[derive(Deserialize, Serialize, Debug)]
 pub struct PaginatedMetadataEntries {
     pub entries: Vec<MetadataEntry>,
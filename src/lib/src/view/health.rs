@@ -7,6 +7,9 @@ pub struct HealthResponse {
     #[serde(flatten)]
     pub status: StatusMessage,
     pub disk_usage: DiskUsage,
+    // Only filled in when the deep health check is requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checks: Option<HealthChecks>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -16,3 +19,55 @@ pub struct DiskUsage {
     pub free_gb: f64,
     pub percent_used: f64,
 }
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HealthChecks {
+    pub sync_dir: SubsystemHealth,
+    pub repo_db: SubsystemHealth,
+    pub redis: SubsystemHealth,
+}
+
+impl HealthChecks {
+    pub fn is_healthy(&self) -> bool {
+        self.sync_dir.status != SubsystemStatus::Down
+            && self.repo_db.status != SubsystemStatus::Down
+            && self.redis.status != SubsystemStatus::Down
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SubsystemHealth {
+    pub status: SubsystemStatus,
+    pub message: Option<String>,
+}
+
+impl SubsystemHealth {
+    pub fn up() -> SubsystemHealth {
+        SubsystemHealth {
+            status: SubsystemStatus::Up,
+            message: None,
+        }
+    }
+
+    pub fn down(message: impl AsRef<str>) -> SubsystemHealth {
+        SubsystemHealth {
+            status: SubsystemStatus::Down,
+            message: Some(message.as_ref().to_string()),
+        }
+    }
+
+    pub fn skipped(message: impl AsRef<str>) -> SubsystemHealth {
+        SubsystemHealth {
+            status: SubsystemStatus::Skipped,
+            message: Some(message.as_ref().to_string()),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SubsystemStatus {
+    Up,
+    Down,
+    Skipped,
+}
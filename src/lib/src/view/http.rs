@@ -14,3 +14,7 @@ pub const MSG_FAILED_PROCESS: &str = "failed_process";
 pub const MSG_INTERNAL_SERVER_ERROR: &str = "internal_server_error";
 pub const MSG_NOT_IMPLEMENTED: &str = "not_implemented";
 pub const MSG_UPDATE_REQUIRED: &str = "update_required";
+pub const MSG_TOO_MANY_REQUESTS: &str = "too_many_requests";
+pub const MSG_PAYLOAD_TOO_LARGE: &str = "payload_too_large";
+pub const MSG_FORBIDDEN: &str = "forbidden";
+pub const MSG_HASH_MISMATCH: &str = "hash_mismatch";
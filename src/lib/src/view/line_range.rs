@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+use super::StatusMessage;
+
+/// A window of lines `[start, start + lines.len())` from a committed text file
+#[derive(Deserialize, Serialize, Debug)]
+pub struct LineRangeResponse {
+    #[serde(flatten)]
+    pub status: StatusMessage,
+    pub lines: Vec<String>,
+    pub start: usize,
+    pub total_lines: usize,
+}
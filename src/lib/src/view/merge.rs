@@ -30,4 +30,14 @@ pub struct MergeSuccessResponse {
     pub status: StatusMessage,
     pub head_commit: String,
     pub base_commit: String,
+    // The merge commit, or the new head of base if it was fast forwarded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<Commit>,
+}
+
+/// Result of asking the server to merge head into base
+#[derive(Debug, Clone)]
+pub enum MergeResult {
+    Merged(Commit),
+    Conflicts(Vec<MergeConflictFile>),
 }
@@ -9,7 +9,7 @@ use crate::{
     util,
 };
 
-use super::{JsonDataFrame, PaginatedDirEntries, StatusMessage};
+use super::{JsonDataFrame, PaginatedDirEntries, Pagination, StatusMessage};
 
 #[derive(Deserialize, Serialize, Debug)]
 pub struct DFIsEditableResponse {
@@ -44,6 +44,51 @@ pub struct RemoteStagedStatus {
     pub modified_files: PaginatedDirEntries,
 }
 
+/// What a single identifier has staged on a branch
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct IdentifierStagedStatus {
+    pub identifier: String,
+    pub staged_files: Vec<PathBuf>,
+    pub modified_files: Vec<PathBuf>,
+}
+
+/// A path that other identifiers already have staged on the branch
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct StagingConflict {
+    pub path: PathBuf,
+    pub identifiers: Vec<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct StagingConflictResponse {
+    #[serde(flatten)]
+    pub status: StatusMessage,
+    pub conflicts: Vec<StagingConflict>,
+}
+
+/// One page of the file paths an identifier has staged on a branch
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct PaginatedStagedFiles {
+    pub paths: Vec<PathBuf>,
+    #[serde(flatten)]
+    pub pagination: Pagination,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct PaginatedStagedFilesResponse {
+    #[serde(flatten)]
+    pub status: StatusMessage,
+    #[serde(flatten)]
+    pub staged: PaginatedStagedFiles,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct AllStagedStatusResponse {
+    #[serde(flatten)]
+    pub status: StatusMessage,
+    pub identifiers: Vec<IdentifierStagedStatus>,
+}
+
 #[derive(Deserialize, Serialize, Debug)]
 pub struct RemoteStagedStatusResponse {
     #[serde(flatten)]
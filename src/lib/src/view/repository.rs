@@ -3,11 +3,15 @@ use serde::{Deserialize, Serialize};
 
 use super::{DataTypeCount, StatusMessage};
 use std::str::FromStr;
+use time::OffsetDateTime;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct RepositoryView {
     pub namespace: String,
     pub name: String,
+    // Branch clients use when none is given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_branch: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -16,6 +20,9 @@ pub struct RepositoryDataTypesView {
     pub name: String,
     pub size: u64,
     pub data_types: Vec<DataTypeCount>,
+    // Branch clients use when none is given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_branch: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -64,6 +71,12 @@ pub struct DataTypeView {
 pub struct RepositoryStatsView {
     pub data_size: u64,
     pub data_types: Vec<DataTypeView>,
+    #[serde(default)]
+    pub num_commits: usize,
+    #[serde(default)]
+    pub num_branches: usize,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub last_updated: Option<OffsetDateTime>,
 }
 
 impl RepositoryView {
@@ -71,6 +84,7 @@ impl RepositoryView {
         RepositoryView {
             namespace: repository.namespace.clone(),
             name: repository.name,
+            default_branch: repository.default_branch,
         }
     }
 }
@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+use super::StatusMessage;
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct SignedUrlResponse {
+    #[serde(flatten)]
+    pub status: StatusMessage,
+    // Repo relative uri anyone can GET the file from until it expires
+    pub uri: String,
+    // Seconds since the epoch
+    pub expires_at: u64,
+}
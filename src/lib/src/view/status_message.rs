@@ -44,6 +44,33 @@ impl StatusMessageDescription {
             status_description: String::from(description.as_ref()),
         }
     }
+
+    pub fn payload_too_large(description: impl AsRef<str>) -> StatusMessageDescription {
+        StatusMessageDescription {
+            status: String::from(view::http::STATUS_ERROR),
+            status_message: String::from(view::http::MSG_PAYLOAD_TOO_LARGE),
+            oxen_version: Some(OXEN_VERSION.to_string()),
+            status_description: String::from(description.as_ref()),
+        }
+    }
+
+    pub fn forbidden(description: impl AsRef<str>) -> StatusMessageDescription {
+        StatusMessageDescription {
+            status: String::from(view::http::STATUS_ERROR),
+            status_message: String::from(view::http::MSG_FORBIDDEN),
+            oxen_version: Some(OXEN_VERSION.to_string()),
+            status_description: String::from(description.as_ref()),
+        }
+    }
+
+    pub fn hash_mismatch(description: impl AsRef<str>) -> StatusMessageDescription {
+        StatusMessageDescription {
+            status: String::from(view::http::STATUS_ERROR),
+            status_message: String::from(view::http::MSG_HASH_MISMATCH),
+            oxen_version: Some(OXEN_VERSION.to_string()),
+            status_description: String::from(description.as_ref()),
+        }
+    }
 }
 
 impl StatusMessage {
@@ -134,4 +161,12 @@ impl StatusMessage {
             oxen_version: Some(OXEN_VERSION.to_string()),
         }
     }
+
+    pub fn too_many_requests() -> StatusMessage {
+        StatusMessage {
+            status: String::from(view::http::STATUS_ERROR),
+            status_message: String::from(view::http::MSG_TOO_MANY_REQUESTS),
+            oxen_version: Some(OXEN_VERSION.to_string()),
+        }
+    }
 }
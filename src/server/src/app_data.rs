@@ -6,11 +6,21 @@ use crate::queues::TaskQueue;
 use lru::LruCache;
 use std::sync::{Arc, RwLock};
 
+/// Caps on the uploads the staging controllers write to disk, None means no limit
+#[derive(Debug, Default, Clone, Copy)]
+pub struct UploadLimits {
+    // Max bytes of any single uploaded file
+    pub max_file_size: Option<u64>,
+    // Max bytes of all the files in one request
+    pub max_request_size: Option<u64>,
+}
+
 pub struct OxenAppData {
     pub path: PathBuf,
     pub queue: TaskQueue,
     // CommitEntryReaderLeastRecentlyUsed
     pub cder_lru: Arc<RwLock<LruCache<String, CommitDirEntryReader>>>,
+    pub upload_limits: UploadLimits,
 }
 
 impl OxenAppData {
@@ -22,8 +32,14 @@ impl OxenAppData {
             path,
             queue,
             cder_lru,
+            upload_limits: UploadLimits::default(),
         }
     }
+
+    pub fn with_upload_limits(mut self, upload_limits: UploadLimits) -> OxenAppData {
+        self.upload_limits = upload_limits;
+        self
+    }
 }
 
 impl Clone for OxenAppData {
@@ -32,6 +48,7 @@ impl Clone for OxenAppData {
             path: self.path.clone(),
             queue: self.queue.clone(),
             cder_lru: self.cder_lru.clone(),
+            upload_limits: self.upload_limits,
         }
     }
 }
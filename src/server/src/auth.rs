@@ -1,2 +1,4 @@
 pub mod access_keys;
+pub mod column_access;
+pub mod signed_urls;
 pub mod validator;
@@ -15,6 +15,47 @@ pub struct JWTClaim {
     id: String,
     name: String,
     email: String,
+    // Scope used to look up the columns the token can read in each repository config
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    scope: Option<String>,
+}
+
+impl JWTClaim {
+    pub fn scope(&self) -> Option<&str> {
+        self.scope.as_deref()
+    }
+}
+
+/// The key the server signs tokens with, generated the first time it is needed
+pub fn get_or_create_secret_key(sync_dir: &Path) -> Result<String, OxenError> {
+    let secret_file = AccessKeyManager::secret_key_path(sync_dir);
+    if !secret_file.exists() {
+        let hidden_dir = util::fs::oxen_hidden_dir(sync_dir);
+        if !hidden_dir.exists() {
+            std::fs::create_dir_all(&hidden_dir)?;
+        }
+
+        // Just generating a random UUID for now
+        let secret = uuid::Uuid::new_v4();
+        let key = hex::encode(secret.as_bytes());
+        log::debug!("Got secret key: {}", key);
+        util::fs::write_to_path(&secret_file, &key)?;
+    }
+    util::fs::read_from_path(secret_file)
+}
+
+/// Decode a token signed with `secret_key`, without looking it up in the access key db
+pub fn decode_claim(secret_key: &str, token: &str) -> Option<JWTClaim> {
+    let mut validator = Validation::new(Algorithm::HS256);
+    validator.set_required_spec_claims(&["email"]);
+    match decode::<JWTClaim>(
+        token,
+        &DecodingKey::from_secret(secret_key.as_ref()),
+        &validator,
+    ) {
+        Ok(token_data) => Some(token_data.claims),
+        Err(_) => None,
+    }
 }
 
 pub struct AccessKeyManager {
@@ -49,14 +90,7 @@ impl AccessKeyManager {
         opts.set_log_level(LogLevel::Fatal);
         opts.create_if_missing(true);
 
-        let secret_file = AccessKeyManager::secret_key_path(sync_dir);
-        if !secret_file.exists() {
-            // Just generating a random UUID for now
-            let secret = uuid::Uuid::new_v4();
-            let key = hex::encode(secret.as_bytes());
-            log::debug!("Got secret key: {}", key);
-            util::fs::write_to_path(&secret_file, &key)?;
-        }
+        get_or_create_secret_key(sync_dir)?;
 
         let db = if read_only {
             DBWithThreadMode::open_for_read_only(&opts, dunce::simplified(&db_dir), false)?
@@ -71,10 +105,21 @@ impl AccessKeyManager {
     }
 
     pub fn create(&self, user: &User) -> Result<(User, String), OxenError> {
+        self.create_with_scope(user, None)
+    }
+
+    /// Create a token whose data frame reads are limited by the repositories' column access
+    /// for `scope`, a token without a scope can read every column
+    pub fn create_with_scope(
+        &self,
+        user: &User,
+        scope: Option<&str>,
+    ) -> Result<(User, String), OxenError> {
         let user_claims = JWTClaim {
             id: format!("{}", uuid::Uuid::new_v4()),
             name: user.name.to_owned(),
             email: user.email.to_owned(),
+            scope: scope.map(String::from),
         };
 
         let secret_key = self.read_secret_key()?;
@@ -125,23 +170,14 @@ impl AccessKeyManager {
     pub fn token_is_valid(&self, token: &str) -> bool {
         match self.get_claim(token) {
             Ok(Some(claim)) => {
-                let secret = self.read_secret_key();
-                if secret.is_err() {
+                let Ok(secret) = self.read_secret_key() else {
                     return false;
-                }
+                };
 
-                let mut validator = Validation::new(Algorithm::HS256);
-                validator.set_required_spec_claims(&["email"]);
-                match decode::<JWTClaim>(
-                    token,
-                    &DecodingKey::from_secret(secret.unwrap().as_ref()),
-                    &validator,
-                ) {
-                    Ok(token_data) => {
-                        // Make sure we decoded the email is the one in our db
-                        token_data.claims == claim
-                    }
-                    _ => {
+                match decode_claim(&secret, token) {
+                    // Make sure we decoded the email is the one in our db
+                    Some(decoded_claim) => decoded_claim == claim,
+                    None => {
                         log::info!("auth token is not valid: {}", token);
                         false
                     }
@@ -0,0 +1,127 @@
+use crate::app_data::OxenAppData;
+use crate::auth::access_keys::AccessKeyManager;
+use crate::errors::OxenHttpError;
+
+use actix_web::http::header;
+use actix_web::HttpRequest;
+use liboxen::config::ColumnAccess;
+use liboxen::constants::OXEN_COLS;
+use liboxen::model::{LocalRepository, Schema};
+use liboxen::util;
+use polars::prelude::DataFrame;
+use std::path::Path;
+
+// Suffixes the compare endpoints add to the columns of the left and right data frames
+const COMPARE_SUFFIXES: [&str; 2] = [".left", ".right"];
+
+/// Scope of the bearer token on the request, `None` if there is no token or it has no scope
+pub fn token_scope(req: &HttpRequest) -> Result<Option<String>, OxenHttpError> {
+    let Some(token) = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+    else {
+        return Ok(None);
+    };
+
+    let Some(app_data) = req.app_data::<OxenAppData>() else {
+        return Err(OxenHttpError::AppDataDoesNotExist);
+    };
+    let keygen = AccessKeyManager::new_read_only(&app_data.path)?;
+    Ok(keygen
+        .get_claim(token.trim())?
+        .and_then(|claim| claim.scope().map(String::from)))
+}
+
+/// Column access rules of the request's token in `repo`, `None` if it can read every column
+pub fn column_rules(
+    req: &HttpRequest,
+    repo: &LocalRepository,
+) -> Result<Option<ColumnAccess>, OxenHttpError> {
+    let Some(scope) = token_scope(req)? else {
+        return Ok(None);
+    };
+    Ok(repo.column_access(&scope))
+}
+
+/// Columns of `schema` the request's token is not allowed to read in `repo`
+pub fn hidden_columns(
+    req: &HttpRequest,
+    repo: &LocalRepository,
+    schema: &Schema,
+) -> Result<Vec<String>, OxenHttpError> {
+    let Some(access) = column_rules(req, repo)? else {
+        return Ok(vec![]);
+    };
+
+    let columns: Vec<&str> = schema.fields.iter().map(|f| f.name.as_str()).collect();
+    Ok(access.hidden_columns(&columns))
+}
+
+/// Drop the columns of `df` the request's token is not allowed to read, including the
+/// `.left` and `.right` copies of them in compare results. Oxen's own row id and diff columns
+/// are always kept.
+pub fn visible_df(
+    req: &HttpRequest,
+    repo: &LocalRepository,
+    df: DataFrame,
+) -> Result<DataFrame, OxenHttpError> {
+    let Some(access) = column_rules(req, repo)? else {
+        return Ok(df);
+    };
+
+    let hidden: Vec<String> = df
+        .get_column_names()
+        .into_iter()
+        .filter(|name| {
+            let name: &str = name;
+            if OXEN_COLS.contains(&name) {
+                return false;
+            }
+            let base = COMPARE_SUFFIXES
+                .iter()
+                .find_map(|suffix| name.strip_suffix(suffix))
+                .unwrap_or(name);
+            !access.is_allowed(name) || !access.is_allowed(base)
+        })
+        .map(String::from)
+        .collect();
+    Ok(df.drop_many(&hidden))
+}
+
+/// Refuse to serve the raw bytes of a tabular file to a token with column access rules,
+/// those can only read it through the data frame endpoints
+pub fn check_raw_download(
+    req: &HttpRequest,
+    repo: &LocalRepository,
+    path: &Path,
+) -> Result<(), OxenHttpError> {
+    if util::fs::is_tabular(path) && column_rules(req, repo)?.is_some() {
+        return Err(OxenHttpError::Forbidden(
+            format!(
+                "Token is not allowed to download {:?}, read it through the data frame api",
+                path
+            )
+            .into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Refuse bulk downloads of version files to a token with column access rules
+pub fn check_bulk_download(req: &HttpRequest, repo: &LocalRepository) -> Result<(), OxenHttpError> {
+    if column_rules(req, repo)?.is_some() {
+        return Err(OxenHttpError::Forbidden(
+            "Token is not allowed to download version files of this repository".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// The schema without the hidden columns
+pub fn visible_schema(schema: &Schema, hidden: &[String]) -> Schema {
+    let mut schema = schema.clone();
+    schema.fields.retain(|f| !hidden.contains(&f.name));
+    schema
+}
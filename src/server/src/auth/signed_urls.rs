@@ -0,0 +1,85 @@
+//! Short lived urls to download a single version file without an access token
+//!
+
+use liboxen::error::OxenError;
+
+use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::auth::access_keys;
+
+pub const DEFAULT_EXPIRES_IN_SECS: u64 = 60 * 60;
+pub const MAX_EXPIRES_IN_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// Path segment the signed file route lives under, ie /api/repos/{namespace}/{repo_name}/signed_file/{token}
+pub const SIGNED_FILE_SEGMENT: &str = "signed_file";
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SignedUrlClaim {
+    pub namespace: String,
+    pub repo_name: String,
+    pub commit_id: String,
+    pub path: String,
+    // Seconds since the epoch, checked by jsonwebtoken on decode
+    pub exp: u64,
+}
+
+// Signed urls get their own key so they can never be mixed up with access tokens
+fn signing_key(sync_dir: &Path) -> Result<String, OxenError> {
+    let secret = access_keys::get_or_create_secret_key(sync_dir)?;
+    Ok(format!("{secret}:{SIGNED_FILE_SEGMENT}"))
+}
+
+pub fn sign(sync_dir: &Path, claim: &SignedUrlClaim) -> Result<String, OxenError> {
+    let key = signing_key(sync_dir)?;
+    encode(
+        &Header::default(),
+        claim,
+        &EncodingKey::from_secret(key.as_ref()),
+    )
+    .map_err(|err| OxenError::basic_str(format!("Could not sign url: {err}")))
+}
+
+/// Decode the token, errors if it was not signed by this server or has expired
+pub fn verify(sync_dir: &Path, token: &str) -> Result<SignedUrlClaim, OxenError> {
+    let key = signing_key(sync_dir)?;
+    let mut validation = Validation::new(Algorithm::HS256);
+    // No grace period, the url is dead as soon as it expires
+    validation.leeway = 0;
+    match decode::<SignedUrlClaim>(token, &DecodingKey::from_secret(key.as_ref()), &validation) {
+        Ok(data) => Ok(data.claims),
+        Err(err) => Err(OxenError::authentication(format!(
+            "Invalid signed url: {err}"
+        ))),
+    }
+}
+
+/// Whether the request path is exactly the signed file route, so the auth middleware can let it
+/// through without a bearer token. The controller checks the signature itself.
+pub fn is_signed_file_path(path: &str) -> bool {
+    let segments: Vec<&str> = path.split('/').collect();
+    matches!(
+        segments[..],
+        ["", "api", "repos", namespace, repo_name, SIGNED_FILE_SEGMENT, token]
+            if !namespace.is_empty() && !repo_name.is_empty() && !token.is_empty()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::auth::signed_urls;
+
+    #[test]
+    fn test_is_signed_file_path() {
+        assert!(signed_urls::is_signed_file_path(
+            "/api/repos/ox/data/signed_file/abc.def.ghi"
+        ));
+        assert!(!signed_urls::is_signed_file_path(
+            "/api/repos/ox/data/file/main/signed_file/abc"
+        ));
+        assert!(!signed_urls::is_signed_file_path(
+            "/api/repos/ox/data/signed_file/"
+        ));
+    }
+}
@@ -1,8 +1,9 @@
 use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
 use crate::errors::OxenHttpError;
 use crate::helpers::get_repo;
-use crate::params::{app_data, path_param, PageNumQuery};
+use crate::params::{app_data, path_param, CursorQuery, HeadPollQuery, PageNumQuery};
 
 use actix_web::{web, HttpRequest, HttpResponse};
 
@@ -12,12 +13,15 @@ use liboxen::util::{self, paginate};
 use liboxen::view::entry::ResourceVersion;
 use liboxen::view::{
     BranchLockResponse, BranchNewFromExisting, BranchRemoteMerge, BranchResponse, BranchUpdate,
-    CommitEntryVersion, CommitResponse, ListBranchesResponse, PaginatedEntryVersions,
-    PaginatedEntryVersionsResponse, StatusMessage,
+    CommitEntryVersion, CommitResponse, CursorPaginatedBranches, ListBranchesResponse,
+    PaginatedEntryVersions, PaginatedEntryVersionsResponse, StatusMessage,
 };
 use liboxen::{api, constants};
 
-pub async fn index(req: HttpRequest) -> actix_web::Result<HttpResponse, OxenHttpError> {
+pub async fn index(
+    req: HttpRequest,
+    query: web::Query<CursorQuery>,
+) -> actix_web::Result<HttpResponse, OxenHttpError> {
     let app_data = app_data(&req)?;
     let namespace = path_param(&req, "namespace")?;
     let name = path_param(&req, "repo_name")?;
@@ -25,6 +29,20 @@ pub async fn index(req: HttpRequest) -> actix_web::Result<HttpResponse, OxenHttp
 
     let branches = api::local::branches::list(&repo)?;
 
+    // Only page through the branches if the client asks for it with a cursor
+    if let Some(cursor) = &query.cursor {
+        let page_size = query.page_size.unwrap_or(constants::DEFAULT_PAGE_SIZE);
+        let (branches, _, next_cursor) =
+            paginate::paginate_with_cursor(branches, cursor, page_size, |b| b.name.to_owned())
+                .map_err(|err| OxenHttpError::BadRequest(format!("{err}").into()))?;
+        let view = CursorPaginatedBranches {
+            status: StatusMessage::resource_found(),
+            branches,
+            next_cursor,
+        };
+        return Ok(HttpResponse::Ok().json(view));
+    }
+
     let view = ListBranchesResponse {
         status: StatusMessage::resource_found(),
         branches,
@@ -50,6 +68,48 @@ pub async fn show(req: HttpRequest) -> actix_web::Result<HttpResponse, OxenHttpE
     Ok(HttpResponse::Ok().json(view))
 }
 
+const HEAD_POLL_INTERVAL: Duration = Duration::from_millis(250);
+const DEFAULT_HEAD_POLL_TIMEOUT_SECS: u64 = 30;
+const MAX_HEAD_POLL_TIMEOUT_SECS: u64 = 60;
+
+/// Long-poll the head of a branch. Returns the branch as soon as its head differs from
+/// `current`, or 304 Not Modified if it is still on `current` when the timeout runs out.
+pub async fn head(
+    req: HttpRequest,
+    query: web::Query<HeadPollQuery>,
+) -> actix_web::Result<HttpResponse, OxenHttpError> {
+    let app_data = app_data(&req)?;
+    let namespace = path_param(&req, "namespace")?;
+    let name = path_param(&req, "repo_name")?;
+    let branch_name = path_param(&req, "branch_name")?;
+    let repository = get_repo(&app_data.path, namespace, name)?;
+
+    let timeout = query
+        .timeout
+        .unwrap_or(DEFAULT_HEAD_POLL_TIMEOUT_SECS)
+        .min(MAX_HEAD_POLL_TIMEOUT_SECS);
+    let deadline = Instant::now() + Duration::from_secs(timeout);
+
+    loop {
+        let branch = api::local::branches::get_by_name(&repository, &branch_name)?
+            .ok_or(OxenError::remote_branch_not_found(&branch_name))?;
+
+        if query.current.as_deref() != Some(branch.commit_id.as_str()) {
+            let view = BranchResponse {
+                status: StatusMessage::resource_found(),
+                branch,
+            };
+            return Ok(HttpResponse::Ok().json(view));
+        }
+
+        let now = Instant::now();
+        if now >= deadline {
+            return Ok(HttpResponse::NotModified().finish());
+        }
+        tokio::time::sleep(HEAD_POLL_INTERVAL.min(deadline - now)).await;
+    }
+}
+
 pub async fn create_from_or_get(
     req: HttpRequest,
     body: String,
@@ -313,19 +373,24 @@ pub async fn list_entry_versions(
 mod tests {
 
     use actix_web::http::{self};
+    use actix_web::web;
 
     use actix_web::body::to_bytes;
+    use std::time::{Duration, Instant};
 
     use liboxen::api;
+    use liboxen::command;
     use liboxen::constants::DEFAULT_BRANCH_NAME;
     use liboxen::error::OxenError;
     use liboxen::util;
     use liboxen::view::http::STATUS_SUCCESS;
     use liboxen::view::{
-        BranchNewFromExisting, BranchResponse, CommitResponse, ListBranchesResponse,
+        BranchNewFromExisting, BranchResponse, CommitResponse, CursorPaginatedBranches,
+        ListBranchesResponse,
     };
 
     use crate::controllers;
+    use crate::params::{CursorQuery, HeadPollQuery};
     use crate::test;
 
     #[actix_web::test]
@@ -338,7 +403,8 @@ mod tests {
         let uri = format!("/oxen/{namespace}/{name}/branches");
         let req = test::repo_request(&sync_dir, queue, &uri, namespace, name);
 
-        let resp = controllers::branches::index(req).await.unwrap();
+        let query: web::Query<CursorQuery> = web::Query::from_query("").unwrap();
+        let resp = controllers::branches::index(req, query).await.unwrap();
         assert_eq!(resp.status(), http::StatusCode::OK);
         let body = to_bytes(resp.into_body()).await.unwrap();
         let text = std::str::from_utf8(&body).unwrap();
@@ -367,7 +433,8 @@ mod tests {
         let uri = format!("/oxen/{namespace}/{name}/branches");
         let req = test::repo_request(&sync_dir, queue, &uri, namespace, name);
 
-        let resp = controllers::branches::index(req).await.unwrap();
+        let query: web::Query<CursorQuery> = web::Query::from_query("").unwrap();
+        let resp = controllers::branches::index(req, query).await.unwrap();
         assert_eq!(resp.status(), http::StatusCode::OK);
         let body = to_bytes(resp.into_body()).await.unwrap();
         let text = std::str::from_utf8(&body).unwrap();
@@ -381,6 +448,45 @@ mod tests {
         Ok(())
     }
 
+    #[actix_web::test]
+    async fn test_controllers_branches_index_cursor_paginated() -> Result<(), OxenError> {
+        let sync_dir = test::get_sync_dir()?;
+        let queue = test::init_queue();
+        let namespace = "Testing-Namespace";
+        let name = "Testing-Branches-1";
+        let repo = test::create_local_repo(&sync_dir, namespace, name)?;
+        for i in 0..4 {
+            api::local::branches::create_from_head(&repo, &format!("branch-{i}"))?;
+        }
+
+        let uri = format!("/oxen/{namespace}/{name}/branches");
+        let req = test::repo_request(&sync_dir, queue.clone(), &uri, namespace, name);
+        let query: web::Query<CursorQuery> = web::Query::from_query("cursor=&page_size=3").unwrap();
+        let resp = controllers::branches::index(req, query).await.unwrap();
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        let body = to_bytes(resp.into_body()).await.unwrap();
+        let text = std::str::from_utf8(&body).unwrap();
+        let page: CursorPaginatedBranches = serde_json::from_str(text)?;
+        assert_eq!(page.branches.len(), 3);
+        let cursor = page.next_cursor.unwrap();
+
+        let req = test::repo_request(&sync_dir, queue, &uri, namespace, name);
+        let query: web::Query<CursorQuery> =
+            web::Query::from_query(&format!("cursor={cursor}&page_size=3")).unwrap();
+        let resp = controllers::branches::index(req, query).await.unwrap();
+        let body = to_bytes(resp.into_body()).await.unwrap();
+        let text = std::str::from_utf8(&body).unwrap();
+        let page: CursorPaginatedBranches = serde_json::from_str(text)?;
+        // main + branch-0..branch-3 is 5 branches, so 2 left on the last page
+        assert_eq!(page.branches.len(), 2);
+        assert!(page.next_cursor.is_none());
+
+        // cleanup
+        util::fs::remove_dir_all(sync_dir)?;
+
+        Ok(())
+    }
+
     #[actix_web::test]
     async fn test_controllers_branch_show() -> Result<(), OxenError> {
         let sync_dir = test::get_sync_dir()?;
@@ -488,4 +594,83 @@ mod tests {
 
         Ok(())
     }
+
+    #[actix_web::test]
+    async fn test_controllers_branches_head_returns_new_head_on_push() -> Result<(), OxenError> {
+        let sync_dir = test::get_sync_dir()?;
+        let queue = test::init_queue();
+        let namespace = "Testing-Namespace";
+        let name = "Testing-Branches-Head-Poll";
+        let repo = test::create_local_repo(&sync_dir, namespace, name)?;
+        let head = api::local::commits::head_commit(&repo)?;
+
+        let uri = format!("/oxen/{namespace}/{name}/branches/{DEFAULT_BRANCH_NAME}/head");
+        let req = test::repo_request_with_param(
+            &sync_dir,
+            queue,
+            &uri,
+            namespace,
+            name,
+            "branch_name",
+            DEFAULT_BRANCH_NAME,
+        );
+        let query: web::Query<HeadPollQuery> =
+            web::Query::from_query(&format!("current={}&timeout=10", head.id)).unwrap();
+
+        // Move the branch while the request is waiting on it
+        let push = async {
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            let path = repo.path.join("pushed.txt");
+            util::fs::write_to_path(&path, "pushed")?;
+            command::add(&repo, &path)?;
+            command::commit(&repo, "Adding pushed file")
+        };
+        let (resp, new_commit) = tokio::join!(controllers::branches::head(req, query), push);
+        let new_commit = new_commit?;
+
+        let resp = resp.unwrap();
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        let body = to_bytes(resp.into_body()).await.unwrap();
+        let text = std::str::from_utf8(&body).unwrap();
+        let branch_resp: BranchResponse = serde_json::from_str(text)?;
+        assert_eq!(branch_resp.branch.commit_id, new_commit.id);
+
+        // cleanup
+        util::fs::remove_dir_all(sync_dir)?;
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn test_controllers_branches_head_times_out_not_modified() -> Result<(), OxenError> {
+        let sync_dir = test::get_sync_dir()?;
+        let queue = test::init_queue();
+        let namespace = "Testing-Namespace";
+        let name = "Testing-Branches-Head-Timeout";
+        let repo = test::create_local_repo(&sync_dir, namespace, name)?;
+        let head = api::local::commits::head_commit(&repo)?;
+
+        let uri = format!("/oxen/{namespace}/{name}/branches/{DEFAULT_BRANCH_NAME}/head");
+        let req = test::repo_request_with_param(
+            &sync_dir,
+            queue,
+            &uri,
+            namespace,
+            name,
+            "branch_name",
+            DEFAULT_BRANCH_NAME,
+        );
+        let query: web::Query<HeadPollQuery> =
+            web::Query::from_query(&format!("current={}&timeout=1", head.id)).unwrap();
+
+        let start = Instant::now();
+        let resp = controllers::branches::head(req, query).await.unwrap();
+        assert_eq!(resp.status(), http::StatusCode::NOT_MODIFIED);
+        assert!(start.elapsed() >= Duration::from_secs(1));
+
+        // cleanup
+        util::fs::remove_dir_all(sync_dir)?;
+
+        Ok(())
+    }
 }
@@ -8,9 +8,10 @@ use liboxen::constants::HISTORY_DIR;
 use liboxen::constants::OBJECTS_DIR;
 use liboxen::constants::TREE_DIR;
 use liboxen::constants::VERSION_FILE_NAME;
-use liboxen::core::cache::cacher_status::CacherStatusType;
+use liboxen::core::cache::cacher_status::{CacherStatus, CacherStatusType};
 use liboxen::core::cache::cachers::content_validator;
 use liboxen::core::cache::commit_cacher;
+use liboxen::core::index::commit_tarball;
 use liboxen::core::index::CommitReader;
 use liboxen::core::index::CommitWriter;
 
@@ -21,31 +22,35 @@ use liboxen::model::RepoNew;
 use liboxen::model::{Commit, LocalRepository};
 use liboxen::util;
 use liboxen::view::branch::BranchName;
-use liboxen::view::commit::CommitSyncStatusResponse;
 use liboxen::view::commit::CommitTreeValidationResponse;
+use liboxen::view::commit::{CommitCacheStatusResponse, CommitSyncStatusResponse};
 use liboxen::view::http::MSG_CONTENT_IS_INVALID;
 use liboxen::view::http::MSG_FAILED_PROCESS;
 use liboxen::view::http::MSG_INTERNAL_SERVER_ERROR;
 use liboxen::view::http::MSG_RESOURCE_IS_PROCESSING;
 use liboxen::view::http::STATUS_ERROR;
 use liboxen::view::http::{MSG_RESOURCE_FOUND, STATUS_SUCCESS};
-use liboxen::view::PaginatedCommits;
 use liboxen::view::{CommitResponse, IsValidStatusMessage, ListCommitResponse, StatusMessage};
+use liboxen::view::{PaginatedCommitEntries, PaginatedCommitEntriesResponse, PaginatedCommits};
 use os_path::OsPath;
 
 use crate::app_data::OxenAppData;
+use crate::auth::column_access;
 use crate::errors::OxenHttpError;
 use crate::helpers::get_repo;
-use crate::params::PageNumQuery;
+use crate::middleware::request_id;
 use crate::params::{app_data, path_param};
+use crate::params::{CommitRangeQuery, CursorQuery, PageNumQuery};
 use crate::tasks;
 use crate::tasks::post_push_complete::PostPushComplete;
+use crate::tasks::TaskPriority;
 
 use actix_web::{web, Error, HttpRequest, HttpResponse};
 use bytesize::ByteSize;
 use flate2::read::GzDecoder;
 use flate2::write::GzEncoder;
 use flate2::Compression;
+use futures::SinkExt;
 use futures_util::stream::StreamExt as _;
 use serde::Deserialize;
 use std::collections::HashMap;
@@ -122,6 +127,7 @@ pub async fn commit_history(
 pub async fn list_all(
     req: HttpRequest,
     query: web::Query<PageNumQuery>,
+    cursor_query: web::Query<CursorQuery>,
 ) -> actix_web::Result<HttpResponse, OxenHttpError> {
     let app_data = app_data(&req)?;
     let namespace: Option<&str> = req.match_info().get("namespace");
@@ -133,6 +139,14 @@ pub async fn list_all(
     if let (Some(namespace), Some(repo_name)) = (namespace, repo_name) {
         let repo = get_repo(&app_data.path, namespace, repo_name)?;
 
+        // Page by cursor instead of page number if the client passes one
+        if let Some(cursor) = &cursor_query.cursor {
+            let paginated_commits =
+                api::local::commits::list_all_cursor_paginated(&repo, cursor, page_size)
+                    .map_err(|err| OxenHttpError::BadRequest(format!("{err}").into()))?;
+            return Ok(HttpResponse::Ok().json(paginated_commits));
+        }
+
         let paginated_commits = api::local::commits::list_all_paginated(&repo, page, page_size)?;
 
         Ok(HttpResponse::Ok().json(paginated_commits))
@@ -142,6 +156,34 @@ pub async fn list_all(
     }
 }
 
+/// List the commits after `base` up to and including `head`
+pub async fn list_between(
+    req: HttpRequest,
+    query: web::Query<CommitRangeQuery>,
+) -> actix_web::Result<HttpResponse, OxenHttpError> {
+    let app_data = app_data(&req)?;
+    let namespace = path_param(&req, "namespace")?;
+    let repo_name = path_param(&req, "repo_name")?;
+    let repo = get_repo(&app_data.path, namespace, repo_name)?;
+
+    let page: usize = query.page.unwrap_or(constants::DEFAULT_PAGE_NUM);
+    let page_size: usize = query.page_size.unwrap_or(constants::DEFAULT_PAGE_SIZE);
+
+    match api::local::commits::list_between_paginated(
+        &repo,
+        &query.base,
+        &query.head,
+        page,
+        page_size,
+    ) {
+        Ok(paginated_commits) => Ok(HttpResponse::Ok().json(paginated_commits)),
+        Err(err) => {
+            log::error!("api err: {}", err);
+            Err(OxenHttpError::NotFound)
+        }
+    }
+}
+
 pub async fn show(req: HttpRequest) -> actix_web::Result<HttpResponse, OxenHttpError> {
     let app_data = app_data(&req)?;
     let namespace = path_param(&req, "namespace")?;
@@ -378,6 +420,39 @@ pub async fn is_synced(req: HttpRequest) -> actix_web::Result<HttpResponse, Oxen
     Ok(response)
 }
 
+/// Status of the cachers run on a commit after it is pushed, so clients can wait for them to finish
+pub async fn cache_status(req: HttpRequest) -> actix_web::Result<HttpResponse, OxenHttpError> {
+    let app_data = app_data(&req)?;
+    let namespace = path_param(&req, "namespace")?;
+    let repo_name = path_param(&req, "repo_name")?;
+    let commit_or_branch = path_param(&req, "commit_or_branch")?;
+    let repository = get_repo(&app_data.path, namespace, &repo_name)?;
+
+    let commit = api::local::revisions::get(&repository, &commit_or_branch)?.ok_or(
+        OxenError::revision_not_found(commit_or_branch.clone().into()),
+    )?;
+
+    let cache_status = match commit_cacher::get_status(&repository, &commit)? {
+        Some(CacherStatusType::Success) => Some(CacherStatus::success()),
+        Some(CacherStatusType::Pending) => Some(CacherStatus::pending()),
+        Some(CacherStatusType::Failed) => {
+            let errors = commit_cacher::get_failures(&repository, &commit)?;
+            let error_str = errors
+                .into_iter()
+                .map(|e| e.status_message)
+                .collect::<Vec<String>>()
+                .join(", ");
+            Some(CacherStatus::failed(&error_str))
+        }
+        None => None,
+    };
+
+    Ok(HttpResponse::Ok().json(CommitCacheStatusResponse {
+        status: StatusMessage::resource_found(),
+        cache_status,
+    }))
+}
+
 pub async fn parents(req: HttpRequest) -> actix_web::Result<HttpResponse, OxenHttpError> {
     let app_data = app_data(&req)?;
     let namespace = path_param(&req, "namespace")?;
@@ -392,6 +467,36 @@ pub async fn parents(req: HttpRequest) -> actix_web::Result<HttpResponse, OxenHt
     }))
 }
 
+/// List the path, hash and size of every file in a commit, sorted by path, without any content
+pub async fn entries_manifest(
+    req: HttpRequest,
+    query: web::Query<PageNumQuery>,
+) -> actix_web::Result<HttpResponse, OxenHttpError> {
+    let app_data = app_data(&req)?;
+    let namespace = path_param(&req, "namespace")?;
+    let name = path_param(&req, "repo_name")?;
+    let commit_or_branch = path_param(&req, "commit_or_branch")?;
+    let repository = get_repo(&app_data.path, namespace, name)?;
+
+    let page = query.page.unwrap_or(constants::DEFAULT_PAGE_NUM);
+    let page_size = query.page_size.unwrap_or(constants::DEFAULT_PAGE_SIZE);
+
+    let commit = api::local::revisions::get(&repository, &commit_or_branch)?
+        .ok_or(OxenError::revision_not_found(commit_or_branch.into()))?;
+
+    let mut entries = api::local::entries::list_all(&repository, &commit)?;
+    entries.sort_by(|a, b| a.path.cmp(&b.path));
+    let (entries, pagination) = util::paginate(entries, page, page_size);
+
+    Ok(HttpResponse::Ok().json(PaginatedCommitEntriesResponse {
+        status: StatusMessage::resource_found(),
+        entries: PaginatedCommitEntries {
+            entries,
+            pagination,
+        },
+    }))
+}
+
 fn p_get_parents(
     repository: &LocalRepository,
     commit_or_branch: &str,
@@ -543,6 +648,67 @@ fn compress_commit(repository: &LocalRepository, commit: &Commit) -> Result<Vec<
     Ok(buffer)
 }
 
+/// Stream a gzipped tarball of a single commit, the objects db and the version files it added,
+/// so a remote repository can be backed up one commit at a time. See [`commit_tarball`].
+pub async fn download_commit_tarball(
+    req: HttpRequest,
+) -> actix_web::Result<HttpResponse, OxenHttpError> {
+    let app_data = app_data(&req)?;
+    let namespace = path_param(&req, "namespace")?;
+    let name = path_param(&req, "repo_name")?;
+    let commit_or_branch = path_param(&req, "commit_or_branch")?;
+    let repository = get_repo(&app_data.path, namespace, name)?;
+    column_access::check_bulk_download(&req, &repository)?;
+
+    let commit = api::local::revisions::get(&repository, &commit_or_branch)?
+        .ok_or(OxenError::revision_not_found(commit_or_branch.into()))?;
+
+    // The tarball is written on a blocking thread and sent to the response as it is compressed
+    let (sender, receiver) =
+        futures::channel::mpsc::channel::<Result<web::Bytes, std::io::Error>>(TARBALL_CHANNEL_SIZE);
+    tokio::task::spawn_blocking(move || {
+        let writer = std::io::BufWriter::with_capacity(
+            TARBALL_CHUNK_SIZE,
+            ChannelWriter {
+                sender: sender.clone(),
+            },
+        );
+        let result = commit_tarball::write(&repository, &commit, writer)
+            .and_then(|mut writer| writer.flush().map_err(OxenError::from));
+        if let Err(err) = result {
+            log::error!("Could not stream tarball for commit {}: {}", commit.id, err);
+            let err = std::io::Error::new(std::io::ErrorKind::Other, err.to_string());
+            let _ = futures::executor::block_on(sender.clone().send(Err(err)));
+        }
+    });
+
+    Ok(HttpResponse::Ok()
+        .content_type("application/gzip")
+        .streaming(receiver))
+}
+
+const TARBALL_CHUNK_SIZE: usize = 1024 * 1024;
+const TARBALL_CHANNEL_SIZE: usize = 8;
+
+// Sends everything written to it as chunks of a streaming response body
+struct ChannelWriter {
+    sender: futures::channel::mpsc::Sender<Result<web::Bytes, std::io::Error>>,
+}
+
+impl Write for ChannelWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let chunk = web::Bytes::copy_from_slice(buf);
+        futures::executor::block_on(self.sender.send(Ok(chunk))).map_err(|_| {
+            std::io::Error::new(std::io::ErrorKind::BrokenPipe, "Client disconnected")
+        })?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
 pub async fn create(
     req: HttpRequest,
     body: String,
@@ -1161,9 +1327,12 @@ pub async fn complete_bulk(req: HttpRequest, body: String) -> Result<HttpRespons
         if commit_cacher::get_status(&repo, &commit)? == Some(CacherStatusType::Pending) {
             // Need to force remove errantly left locks
             commit_cacher::force_remove_lock(&repo, &commit)?;
+            // Re-queued work should not hold up freshly pushed commits
             let task = PostPushComplete {
                 commit: commit.clone(),
                 repo: repo.clone(),
+                priority: TaskPriority::Low,
+                request_id: request_id::current(),
             };
             // Append a task to the queue
             log::debug!(
@@ -1187,6 +1356,8 @@ pub async fn complete_bulk(req: HttpRequest, body: String) -> Result<HttpRespons
         let task = PostPushComplete {
             commit: commit.clone(),
             repo: repo.clone(),
+            priority: TaskPriority::Normal,
+            request_id: request_id::current(),
         };
 
         queue.push(tasks::Task::PostPushComplete(task))
@@ -1323,9 +1494,13 @@ mod tests {
 
     use liboxen::api;
     use liboxen::command;
-    use liboxen::constants::OXEN_HIDDEN_DIR;
+    use liboxen::constants::{DEFAULT_BRANCH_NAME, OXEN_HIDDEN_DIR};
+    use liboxen::core::cache::cacher_status::CacherStatus;
+    use liboxen::core::cache::commit_cacher;
     use liboxen::error::OxenError;
+    use liboxen::model::commit::CommitWithBranchName;
     use liboxen::util;
+    use liboxen::view::commit::CommitCacheStatusResponse;
     use liboxen::view::{CommitResponse, ListCommitResponse};
 
     use crate::app_data::OxenAppData;
@@ -1360,6 +1535,61 @@ mod tests {
         Ok(())
     }
 
+    #[actix_web::test]
+    async fn test_controllers_commits_cache_status() -> Result<(), OxenError> {
+        let sync_dir = test::get_sync_dir()?;
+        let queue = test::init_queue();
+        let namespace = "Testing-Namespace";
+        let name = "Testing-Name";
+        let repo = test::create_local_repo(&sync_dir, namespace, name)?;
+
+        let path = liboxen::test::add_txt_file_to_dir(&repo.path, "hello")?;
+        command::add(&repo, path)?;
+        let commit = command::commit(&repo, "first commit")?;
+
+        // Nothing has been cached yet
+        let uri = format!(
+            "/oxen/{namespace}/{name}/commits/{}/cache_status",
+            commit.id
+        );
+        let req = test::repo_request_with_param(
+            &sync_dir,
+            queue.clone(),
+            &uri,
+            namespace,
+            name,
+            "commit_or_branch",
+            &commit.id,
+        );
+        let resp = controllers::commits::cache_status(req).await.unwrap();
+        let body = to_bytes(resp.into_body()).await.unwrap();
+        let text = std::str::from_utf8(&body).unwrap();
+        let response: CommitCacheStatusResponse = serde_json::from_str(text)?;
+        assert!(response.cache_status.is_none());
+
+        commit_cacher::run_all(&repo, &commit, false)?;
+
+        let req = test::repo_request_with_param(
+            &sync_dir,
+            queue,
+            &uri,
+            namespace,
+            name,
+            "commit_or_branch",
+            &commit.id,
+        );
+        let resp = controllers::commits::cache_status(req).await.unwrap();
+        let body = to_bytes(resp.into_body()).await.unwrap();
+        let text = std::str::from_utf8(&body).unwrap();
+        let response: CommitCacheStatusResponse = serde_json::from_str(text)?;
+        assert_eq!(response.cache_status, Some(CacherStatus::success()));
+
+        // cleanup
+        util::fs::remove_dir_all(sync_dir)?;
+
+        Ok(())
+    }
+
     #[actix_web::test]
     async fn test_controllers_commits_list_two_commits() -> Result<(), OxenError> {
         let sync_dir = test::get_sync_dir()?;
@@ -1491,6 +1721,43 @@ mod tests {
         Ok(())
     }
 
+    #[actix_web::test]
+    async fn test_controllers_commits_create_in_bare_repo() -> Result<(), OxenError> {
+        let sync_dir = test::get_sync_dir()?;
+        let queue = test::init_queue();
+        let namespace = "Testing-Namespace";
+        let name = "Testing-Bare";
+        let bare_dir = sync_dir.join(namespace).join(name);
+        std::fs::create_dir_all(&bare_dir)?;
+        let bare = command::init_bare(&bare_dir)?;
+
+        // Push the root commit of another repo into the bare one
+        let repo = test::create_local_repo(&sync_dir, namespace, "Testing-Local")?;
+        let commit = api::local::commits::head_commit(&repo)?;
+        let body = serde_json::to_string(&CommitWithBranchName::from_commit(
+            &commit,
+            0,
+            DEFAULT_BRANCH_NAME.to_string(),
+        ))?;
+
+        let uri = format!("/oxen/{namespace}/{name}/commits");
+        let req = test::repo_request(&sync_dir, queue, &uri, namespace, name);
+        let resp = controllers::commits::create(req, body).await.unwrap();
+        assert_eq!(resp.status(), actix_web::http::StatusCode::OK);
+        assert!(api::local::commits::get_by_id(&bare, &commit.id)?.is_some());
+
+        // Still no working files
+        let names: Vec<_> = std::fs::read_dir(&bare_dir)?
+            .map(|entry| entry.map(|e| e.file_name()))
+            .collect::<Result<_, _>>()?;
+        assert_eq!(names, vec![OXEN_HIDDEN_DIR]);
+
+        // cleanup
+        util::fs::remove_dir_all(sync_dir)?;
+
+        Ok(())
+    }
+
     #[actix_web::test]
     async fn test_controllers_commits_upload() -> Result<(), OxenError> {
         let sync_dir = test::get_sync_dir()?;
@@ -1572,4 +1839,43 @@ mod tests {
 
         Ok(())
     }
+
+    #[actix_web::test]
+    async fn test_controllers_commits_tarball_forbidden_with_hidden_columns(
+    ) -> Result<(), OxenError> {
+        let sync_dir = test::get_sync_dir()?;
+        let namespace = "Testing-Namespace";
+        let name = "Testing-Tarball-Column-Access";
+        let (_repo, analyst_token, admin_token) =
+            test::create_column_access_repo(&sync_dir, namespace, name)?;
+
+        let app = actix_web::test::init_service(
+            App::new()
+                .app_data(OxenAppData::new(sync_dir.clone(), test::init_queue()))
+                .route(
+                    "/oxen/{namespace}/{repo_name}/commits/{commit_or_branch:.*}/tarball",
+                    web::get().to(controllers::commits::download_commit_tarball),
+                ),
+        )
+        .await;
+
+        for (token, status) in [
+            (analyst_token, actix_web::http::StatusCode::FORBIDDEN),
+            (admin_token, actix_web::http::StatusCode::OK),
+        ] {
+            let req = actix_web::test::TestRequest::get()
+                .uri(&format!("/oxen/{namespace}/{name}/commits/main/tarball"))
+                .insert_header(("Authorization", format!("Bearer {token}")))
+                .to_request();
+            let resp = actix_web::test::call_service(&app, req).await;
+            assert_eq!(resp.status(), status);
+            // Drain the stream so the writer thread finishes before cleanup
+            to_bytes(resp.into_body()).await.unwrap();
+        }
+
+        // cleanup
+        util::fs::remove_dir_all(sync_dir)?;
+
+        Ok(())
+    }
 }
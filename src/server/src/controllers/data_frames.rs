@@ -1,7 +1,8 @@
+use crate::auth::column_access;
 use crate::errors::OxenHttpError;
 use crate::helpers::get_repo;
 use crate::params::df_opts_query::{self, DFOptsQuery};
-use crate::params::{app_data, parse_resource, path_param};
+use crate::params::{app_data, parse_resource, path_param, DFSearchQuery};
 
 use liboxen::api;
 use liboxen::constants::DUCKDB_DF_TABLE_NAME;
@@ -9,7 +10,7 @@ use liboxen::core::cache::cachers;
 use liboxen::core::db::df_db;
 use liboxen::core::index::CommitEntryReader;
 use liboxen::error::OxenError;
-use liboxen::model::{DataFrameSize, ParsedResource, Schema};
+use liboxen::model::{DataFrameSize, LocalRepository, ParsedResource, Schema};
 use liboxen::opts::df_opts::DFOptsView;
 use liboxen::view::entry::ResourceVersion;
 use liboxen::view::json_data_frame_view::JsonDataFrameSource;
@@ -24,6 +25,7 @@ use liboxen::view::{
 
 use liboxen::util;
 use polars::frame::DataFrame;
+use polars::prelude::{col, Expr};
 
 // TODO: This is getting long...condense and factor out whatever possible here
 pub async fn get(
@@ -72,7 +74,7 @@ pub async fn get(
     // Paginate or slice, after we do the original transform
     let mut page_opts = PaginateOpts {
         page_num: constants::DEFAULT_PAGE_NUM,
-        page_size: constants::DEFAULT_PAGE_SIZE,
+        page_size: repo.df_page_size(),
     };
 
     // Block big big dfs
@@ -101,7 +103,7 @@ pub async fn get(
     } else {
         // Otherwise use the query params for pagination
         let page = query.page.unwrap_or(constants::DEFAULT_PAGE_NUM);
-        let page_size = query.page_size.unwrap_or(constants::DEFAULT_PAGE_SIZE);
+        let page_size = query.page_size.unwrap_or(repo.df_page_size());
 
         page_opts.page_num = page;
         page_opts.page_size = page_size;
@@ -116,6 +118,7 @@ pub async fn get(
     if let Some(sql) = opts.sql.clone() {
         let mut conn = sql::get_conn(&repo, &entry)?;
         let db_schema = df_db::get_schema(&conn, DUCKDB_DF_TABLE_NAME)?;
+        check_sql_column_access(&req, &repo, &db_schema)?;
         let df = sql::query_df(&repo, &entry, sql, &mut conn)?;
 
         let json_df = format_sql_df_response(
@@ -134,6 +137,7 @@ pub async fn get(
     if let Some(text2sql) = opts.text2sql.clone() {
         let mut conn = sql::get_conn(&repo, &entry)?;
         let db_schema = df_db::get_schema(&conn, DUCKDB_DF_TABLE_NAME)?;
+        check_sql_column_access(&req, &repo, &db_schema)?;
 
         let df = sql::text2sql_df(
             &repo,
@@ -174,6 +178,16 @@ pub async fn get(
         version_path
     );
 
+    // Project out the columns the token is not allowed to read
+    let hidden = column_access::hidden_columns(&req, &repo, &og_schema)?;
+    let (df, og_schema) = if hidden.is_empty() {
+        (df, og_schema)
+    } else {
+        let og_schema = column_access::visible_schema(&og_schema, &hidden);
+        let columns: Vec<Expr> = og_schema.fields.iter().map(|f| col(&f.name)).collect();
+        (df.select(columns), og_schema)
+    };
+
     // We have to run the query param transforms, then paginate separately
     let og_df_json = JsonDataFrameSource::from_df_size(&data_frame_size, &og_schema);
 
@@ -272,6 +286,106 @@ pub async fn index(req: HttpRequest) -> actix_web::Result<HttpResponse, OxenHttp
     Ok(HttpResponse::Ok().json(StatusMessage::resource_updated()))
 }
 
+/// Find the rows of a committed data frame where a column contains the query string
+pub async fn search(
+    req: HttpRequest,
+    query: web::Query<DFSearchQuery>,
+) -> actix_web::Result<HttpResponse, OxenHttpError> {
+    let app_data = app_data(&req)?;
+    let namespace = path_param(&req, "namespace")?;
+    let repo_name = path_param(&req, "repo_name")?;
+    let repo = get_repo(&app_data.path, namespace, &repo_name)?;
+    let resource = parse_resource(&req, &repo)?;
+    let entry_reader = CommitEntryReader::new(&repo, &resource.commit)?;
+    let entry = entry_reader
+        .get_entry(&resource.file_path)?
+        .ok_or(OxenHttpError::NotFound)?;
+
+    log::debug!(
+        "{} resource {}/{} column {} query {}",
+        current_function!(),
+        repo_name,
+        resource,
+        query.column,
+        query.query
+    );
+
+    let version_path = util::fs::version_path(&repo, &entry);
+    let data_frame_size =
+        cachers::df_size::get_cache_for_version(&repo, &resource.commit, &version_path)?;
+
+    let og_schema = if let Some(schema) =
+        api::local::schemas::get_by_path_from_ref(&repo, &resource.commit.id, &resource.file_path)?
+    {
+        schema
+    } else {
+        let df = tabular::scan_df(&version_path, &DFOpts::empty(), data_frame_size.height)?;
+        match df.schema() {
+            Ok(schema) => Ok(Schema::from_polars(&schema.to_owned())),
+            Err(e) => {
+                log::error!("Error reading df: {}", e);
+                Err(OxenHttpError::InternalServerError)
+            }
+        }?
+    };
+
+    // Project out the columns the token is not allowed to read
+    let hidden = column_access::hidden_columns(&req, &repo, &og_schema)?;
+    if hidden.contains(&query.column) {
+        return Err(OxenHttpError::BadRequest(
+            format!("Column not found: {}", query.column).into(),
+        ));
+    }
+    let og_schema = column_access::visible_schema(&og_schema, &hidden);
+
+    let mut opts = DFOpts::empty();
+    opts.page = Some(query.page.unwrap_or(constants::DEFAULT_PAGE_NUM));
+    opts.page_size = Some(query.page_size.unwrap_or(repo.df_page_size()));
+
+    let conn = duckdb::Connection::open_in_memory().map_err(OxenError::from)?;
+    let (df, total) = df_db::search_file(
+        &conn,
+        &version_path,
+        &query.column,
+        &query.query,
+        query.regex.unwrap_or(false),
+        &opts,
+    )
+    .map_err(|err| OxenHttpError::BadRequest(format!("{err}").into()))?;
+    let df = df.drop_many(&hidden);
+
+    let resource_version = ResourceVersion {
+        path: resource.file_path.to_string_lossy().into(),
+        version: resource.version().to_owned(),
+    };
+    let response = JsonDataFrameViewResponse {
+        status: StatusMessage::resource_found(),
+        data_frame: JsonDataFrameViews {
+            source: JsonDataFrameSource::from_df_size(&data_frame_size, &og_schema),
+            view: JsonDataFrameView::from_df_opts_unpaginated(df, og_schema, total, &opts),
+        },
+        commit: Some(resource.commit.clone()),
+        resource: Some(resource_version),
+        derived_resource: None,
+    };
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// SQL can read any column, so it is only allowed for tokens that can read all of them
+fn check_sql_column_access(
+    req: &HttpRequest,
+    repo: &LocalRepository,
+    schema: &Schema,
+) -> Result<(), OxenHttpError> {
+    if column_access::hidden_columns(req, repo, schema)?.is_empty() {
+        Ok(())
+    } else {
+        Err(OxenHttpError::BadRequest(
+            "SQL queries are not allowed for tokens with restricted columns".into(),
+        ))
+    }
+}
+
 fn format_sql_df_response(
     df: DataFrame,
     opts: &DFOpts,
@@ -308,3 +422,177 @@ fn format_sql_df_response(
     };
     Ok(response)
 }
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{web, App};
+
+    use liboxen::command;
+    use liboxen::config::ColumnAccess;
+    use liboxen::error::OxenError;
+    use liboxen::model::User;
+    use liboxen::util;
+    use liboxen::view::JsonDataFrameViewResponse;
+
+    use crate::app_data::OxenAppData;
+    use crate::auth::access_keys::AccessKeyManager;
+    use crate::controllers;
+    use crate::test;
+
+    #[actix_web::test]
+    async fn test_controllers_data_frames_search() -> Result<(), OxenError> {
+        test::init_test_env();
+
+        let sync_dir = test::get_sync_dir()?;
+        let queue = test::init_queue();
+        let namespace = "Testing-Namespace";
+        let name = "Testing-Name";
+        let repo = test::create_local_repo(&sync_dir, namespace, name)?;
+
+        // 5 dogs out of 8 rows, and a literal % that should not act as a wildcard
+        let csv_path = repo.path.join("labels.csv");
+        util::fs::write_to_path(
+            &csv_path,
+            "file,label\n1.jpg,dog\n2.jpg,cat\n3.jpg,hotdog\n4.jpg,dog\n5.jpg,50% dog\n6.jpg,bird\n7.jpg,doge\n8.jpg,500 cats\n",
+        )?;
+        command::add(&repo, &csv_path)?;
+        command::commit(&repo, "adding labels")?;
+
+        let app = actix_web::test::init_service(
+            App::new()
+                .app_data(OxenAppData::new(sync_dir.clone(), queue))
+                .route(
+                    "/oxen/{namespace}/{repo_name}/data_frame/search/{resource:.*}",
+                    web::get().to(controllers::data_frames::search),
+                ),
+        )
+        .await;
+
+        let search = |query: &str, page: usize| {
+            actix_web::test::TestRequest::get()
+                .uri(&format!(
+                    "/oxen/{namespace}/{name}/data_frame/search/main/labels.csv?column=label&query={query}&page={page}&page_size=2"
+                ))
+                .to_request()
+        };
+
+        // Second page of the matches
+        let resp = actix_web::test::call_service(&app, search("dog", 2)).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::OK);
+        let bytes = actix_http::body::to_bytes(resp.into_body()).await.unwrap();
+        let body = std::str::from_utf8(&bytes).unwrap();
+        let resp: JsonDataFrameViewResponse = serde_json::from_str(body)?;
+
+        let view = &resp.data_frame.view;
+        assert_eq!(view.pagination.total_entries, 5);
+        assert_eq!(view.pagination.total_pages, 3);
+        assert_eq!(view.pagination.page_number, 2);
+        assert_eq!(view.size.height, 2);
+        let labels: Vec<String> = view
+            .data
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|row| row["label"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(labels, vec!["dog", "50% dog"]);
+
+        // The % is matched literally, so "500 cats" is not a match
+        let resp = actix_web::test::call_service(&app, search("0%25", 1)).await;
+        let bytes = actix_http::body::to_bytes(resp.into_body()).await.unwrap();
+        let body = std::str::from_utf8(&bytes).unwrap();
+        let resp: JsonDataFrameViewResponse = serde_json::from_str(body)?;
+        assert_eq!(resp.data_frame.view.pagination.total_entries, 1);
+
+        // cleanup
+        util::fs::remove_dir_all(sync_dir)?;
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn test_controllers_data_frames_get_hides_denied_columns() -> Result<(), OxenError> {
+        test::init_test_env();
+
+        let sync_dir = test::get_sync_dir()?;
+        let queue = test::init_queue();
+        let namespace = "Testing-Namespace";
+        let name = "Testing-Column-Access";
+        let mut repo = test::create_local_repo(&sync_dir, namespace, name)?;
+
+        let csv_path = repo.path.join("users.csv");
+        util::fs::write_to_path(
+            &csv_path,
+            "name,email,age\nada,ada@oxen.ai,36\nbob,bob@oxen.ai,40\n",
+        )?;
+        command::add(&repo, &csv_path)?;
+        command::commit(&repo, "adding users")?;
+
+        // Analysts can't see emails
+        repo.set_column_access(
+            "analyst",
+            ColumnAccess {
+                allow: None,
+                deny: vec![String::from("email")],
+            },
+        );
+        repo.save_default()?;
+
+        let (analyst_token, admin_token) = {
+            let keygen = AccessKeyManager::new(&sync_dir)?;
+            let user = User {
+                name: String::from("Ox"),
+                email: String::from("ox@oxen.ai"),
+            };
+            let (_, analyst_token) = keygen.create_with_scope(&user, Some("analyst"))?;
+            let (_, admin_token) = keygen.create(&user)?;
+            (analyst_token, admin_token)
+        };
+
+        let app = actix_web::test::init_service(
+            App::new()
+                .app_data(OxenAppData::new(sync_dir.clone(), queue))
+                .route(
+                    "/oxen/{namespace}/{repo_name}/data_frame/{resource:.*}",
+                    web::get().to(controllers::data_frames::get),
+                ),
+        )
+        .await;
+
+        let columns_for = |token: String| {
+            actix_web::test::TestRequest::get()
+                .uri(&format!(
+                    "/oxen/{namespace}/{name}/data_frame/main/users.csv"
+                ))
+                .insert_header(("Authorization", format!("Bearer {token}")))
+                .to_request()
+        };
+
+        let mut seen: Vec<Vec<String>> = vec![];
+        for token in [analyst_token, admin_token] {
+            let resp = actix_web::test::call_service(&app, columns_for(token)).await;
+            assert_eq!(resp.status(), actix_web::http::StatusCode::OK);
+            let bytes = actix_http::body::to_bytes(resp.into_body()).await.unwrap();
+            let body = std::str::from_utf8(&bytes).unwrap();
+            let resp: JsonDataFrameViewResponse = serde_json::from_str(body)?;
+
+            let view = &resp.data_frame.view;
+            assert_eq!(view.size.height, 2);
+            let row = view.data.as_array().unwrap()[0].as_object().unwrap();
+            let mut columns: Vec<String> = row.keys().cloned().collect();
+            columns.sort();
+            let schema_columns: Vec<String> =
+                view.schema.fields.iter().map(|f| f.name.clone()).collect();
+            assert_eq!(schema_columns.len(), columns.len());
+            seen.push(columns);
+        }
+
+        assert_eq!(seen[0], vec!["age", "name"]);
+        assert_eq!(seen[1], vec!["age", "email", "name"]);
+
+        // cleanup
+        util::fs::remove_dir_all(sync_dir)?;
+
+        Ok(())
+    }
+}
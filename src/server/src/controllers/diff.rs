@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
+use crate::auth::column_access;
 use crate::errors::OxenHttpError;
 
 use actix_web::{web, HttpRequest, HttpResponse};
@@ -255,6 +256,14 @@ pub async fn file(
     let base_entry = base_entry_reader.get_entry(&resource)?;
     let head_entry = head_entry_reader.get_entry(&resource)?;
 
+    let version_paths: Vec<PathBuf> = [&base_entry, &head_entry]
+        .into_iter()
+        .flatten()
+        .filter(|entry| util::fs::is_tabular(&entry.path))
+        .map(|entry| util::fs::version_path(&repository, entry))
+        .collect();
+    check_compare_column_access(&req, &repository, &version_paths)?;
+
     let mut opts = DFOpts::empty();
     opts = df_opts_query::parse_opts(&query, &mut opts);
 
@@ -347,12 +356,17 @@ pub async fn create_df_diff(
         &commit_2.id,
         &resource_2,
     )?;
-    let diff_result = api::local::diff::diff_files(
+    check_compare_column_access(&req, &repository, &[&file_1, &file_2])?;
+    let diff_result = api::local::diff::diff_repo_files(
+        &repository,
+        &resource_1,
+        &resource_2,
         file_1,
         file_2,
         keys,
         targets,
         display_by_column, // TODONOW: add display handling here
+        false,
     )?;
 
     let view = match diff_result {
@@ -449,12 +463,17 @@ pub async fn update_df_diff(
         &commit_2.id,
         &resource_2,
     )?;
-    let diff_result = api::local::diff::diff_files(
+    check_compare_column_access(&req, &repository, &[&file_1, &file_2])?;
+    let diff_result = api::local::diff::diff_repo_files(
+        &repository,
+        &resource_1,
+        &resource_2,
         file_1,
         file_2,
         keys,
         targets,
         display_by_column, // TODONOW: add display handling here
+        false,
     )?;
 
     let view = match diff_result {
@@ -524,6 +543,15 @@ pub async fn get_df_diff(
         OxenError::ResourceNotFound(format!("{}@{}", data.right.path, right_commit).into())
     })?;
 
+    check_compare_column_access(
+        &req,
+        &repository,
+        &[
+            util::fs::version_path(&repository, &left_entry),
+            util::fs::version_path(&repository, &right_entry),
+        ],
+    )?;
+
     let maybe_cached_diff = api::local::diff::get_cached_diff(
         &repository,
         &compare_id,
@@ -586,6 +614,7 @@ pub async fn get_derived_df(
     // controllers::df::get logic
 
     let df = tabular::read_df(derived_df_path, DFOpts::empty())?;
+    let df = column_access::visible_df(&req, &repo, df)?;
     let og_schema = Schema::from_polars(&df.schema());
 
     let mut opts = DFOpts::empty();
@@ -691,6 +720,28 @@ pub async fn get_derived_df(
     }
 }
 
+/// Compare results can contain every column of both data frames, so tokens that can't read
+/// some of their columns are not allowed to compare them
+fn check_compare_column_access(
+    req: &HttpRequest,
+    repo: &LocalRepository,
+    version_paths: &[impl AsRef<Path>],
+) -> Result<(), OxenHttpError> {
+    if column_access::column_rules(req, repo)?.is_none() {
+        return Ok(());
+    }
+
+    for path in version_paths {
+        let schema = tabular::get_schema(path)?;
+        if !column_access::hidden_columns(req, repo, &schema)?.is_empty() {
+            return Err(OxenHttpError::Forbidden(
+                "Token is not allowed to compare data frames with restricted columns".into(),
+            ));
+        }
+    }
+    Ok(())
+}
+
 fn parse_base_head_resource(
     repo: &LocalRepository,
     base_head: &str,
@@ -822,8 +873,14 @@ fn group_dir_diffs_by_dir(dir_diffs: Vec<(PathBuf, DiffEntryStatus)>) -> Vec<Dir
 
 #[cfg(test)]
 mod tests {
-    use liboxen::{command, error::OxenError};
+    use actix_web::{http, web, App};
+    use liboxen::view::compare::{
+        TabularCompareBody, TabularCompareFieldBody, TabularCompareResourceBody,
+    };
+    use liboxen::{command, error::OxenError, util};
 
+    use crate::app_data::OxenAppData;
+    use crate::controllers;
     use crate::test;
 
     #[actix_web::test]
@@ -852,4 +909,59 @@ mod tests {
 
         Ok(())
     }
+
+    #[actix_web::test]
+    async fn test_controllers_compare_df_forbidden_with_hidden_columns() -> Result<(), OxenError> {
+        let sync_dir = test::get_sync_dir()?;
+        let namespace = "testing-namespace";
+        let repo_name = "testing-compare-column-access";
+        let (_repo, analyst_token, admin_token) =
+            test::create_column_access_repo(&sync_dir, namespace, repo_name)?;
+
+        let app = actix_web::test::init_service(
+            App::new()
+                .app_data(OxenAppData::new(sync_dir.clone(), test::init_queue()))
+                .route(
+                    "/oxen/{namespace}/{repo_name}/compare/data_frame",
+                    web::post().to(controllers::diff::create_df_diff),
+                ),
+        )
+        .await;
+
+        let resource = TabularCompareResourceBody {
+            path: String::from("users.csv"),
+            version: String::from("main"),
+        };
+        let body = TabularCompareBody {
+            compare_id: String::from("users-compare"),
+            left: resource.clone(),
+            right: resource,
+            keys: vec![TabularCompareFieldBody {
+                left: String::from("name"),
+                right: String::from("name"),
+                alias_as: None,
+                compare_method: None,
+            }],
+            compare: vec![],
+            display: vec![],
+        };
+
+        for (token, status) in [
+            (analyst_token, http::StatusCode::FORBIDDEN),
+            (admin_token, http::StatusCode::OK),
+        ] {
+            let req = actix_web::test::TestRequest::post()
+                .uri(&format!("/oxen/{namespace}/{repo_name}/compare/data_frame"))
+                .insert_header(("Authorization", format!("Bearer {token}")))
+                .set_payload(serde_json::to_string(&body)?)
+                .to_request();
+            let resp = actix_web::test::call_service(&app, req).await;
+            assert_eq!(resp.status(), status);
+        }
+
+        // cleanup
+        util::fs::remove_dir_all(sync_dir)?;
+
+        Ok(())
+    }
 }
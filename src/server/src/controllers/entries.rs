@@ -1,6 +1,8 @@
+use crate::app_data::UploadLimits;
+use crate::auth::column_access;
 use crate::errors::OxenHttpError;
 use crate::helpers::get_repo;
-use crate::params::{app_data, parse_resource, path_param, PageNumQuery};
+use crate::params::{app_data, parse_resource, path_param, LineRangeQuery, PageNumQuery};
 use crate::view::PaginatedLinesResponse;
 
 use liboxen::constants::AVG_CHUNK_SIZE;
@@ -9,7 +11,7 @@ use liboxen::util::fs::replace_file_name_keep_extension;
 use liboxen::util::paginate;
 use liboxen::view::entry::{PaginatedMetadataEntries, PaginatedMetadataEntriesResponse};
 use liboxen::view::http::{MSG_RESOURCE_FOUND, STATUS_SUCCESS};
-use liboxen::view::StatusMessage;
+use liboxen::view::{LineRangeResponse, StatusMessage};
 use liboxen::{api, util};
 use liboxen::{constants, current_function};
 
@@ -22,6 +24,13 @@ use serde::Deserialize;
 
 use std::fs::File;
 use std::io::prelude::*;
+use std::path::{Path, PathBuf};
+use xxhash_rust::xxh3::Xxh3;
+
+#[derive(Deserialize, Debug)]
+pub struct UploadVersionQuery {
+    pub filename: Option<String>,
+}
 
 #[derive(Deserialize, Debug)]
 pub struct ChunkQuery {
@@ -37,6 +46,7 @@ pub async fn download_data_from_version_paths(
     let namespace = path_param(&req, "namespace")?;
     let repo_name = path_param(&req, "repo_name")?;
     let repo = get_repo(&app_data.path, namespace, &repo_name)?;
+    column_access::check_bulk_download(&req, &repo)?;
 
     let mut bytes = web::BytesMut::new();
     while let Some(item) = body.next().await {
@@ -93,6 +103,89 @@ pub async fn download_data_from_version_paths(
     Ok(HttpResponse::Ok().body(buffer))
 }
 
+/// Upload a single file into the versions directory, verifying the body hashes to `{hash}`
+pub async fn upload_version(
+    req: HttpRequest,
+    query: web::Query<UploadVersionQuery>,
+    mut body: web::Payload,
+) -> actix_web::Result<HttpResponse, OxenHttpError> {
+    let app_data = app_data(&req)?;
+    let namespace = path_param(&req, "namespace")?;
+    let repo_name = path_param(&req, "repo_name")?;
+    let hash = path_param(&req, "hash")?;
+    let repo = get_repo(&app_data.path, namespace, &repo_name)?;
+
+    if hash.len() < 3 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(OxenHttpError::BadRequest(
+            format!("Invalid version hash: {hash}").into(),
+        ));
+    }
+
+    // Stream the body to a temp file while hashing it, so large versions are never held in
+    // memory and nothing lands in the versions dir until the hash checks out
+    let tmp_dir = util::fs::oxen_hidden_dir(&repo.path)
+        .join("tmp")
+        .join("versions")
+        .join(uuid::Uuid::new_v4().to_string());
+    util::fs::create_dir_all(&tmp_dir)?;
+    let tmp_path = tmp_dir.join(&hash);
+    let result = receive_version(&app_data.upload_limits, &tmp_path, &mut body).await;
+    let result = match result {
+        Ok((size, computed_hash)) => {
+            log::debug!(
+                "{} got repo [{}] version {} size {} computed {}",
+                current_function!(),
+                repo_name,
+                hash,
+                size,
+                computed_hash
+            );
+            if computed_hash != hash {
+                Err(OxenError::hash_mismatch(&hash, &computed_hash).into())
+            } else {
+                let filename = PathBuf::from(query.filename.clone().unwrap_or_default());
+                let version_path =
+                    util::fs::version_path_from_hash_and_file(&repo.path, hash, filename);
+                if let Some(parent) = version_path.parent() {
+                    util::fs::create_dir_all(parent)?;
+                }
+                util::fs::rename(&tmp_path, &version_path).map_err(OxenHttpError::from)
+            }
+        }
+        Err(err) => Err(err),
+    };
+    util::fs::remove_dir_all(&tmp_dir)?;
+    result?;
+
+    Ok(HttpResponse::Ok().json(StatusMessage::resource_created()))
+}
+
+// Write the payload to `path`, returning its size and hash, or PayloadTooLarge once it goes
+// over the max file size
+async fn receive_version(
+    limits: &UploadLimits,
+    path: &Path,
+    body: &mut web::Payload,
+) -> Result<(u64, String), OxenHttpError> {
+    let mut file = File::create(path)?;
+    let mut hasher = Xxh3::new();
+    let mut size: u64 = 0;
+    while let Some(chunk) = body.next().await {
+        let chunk = chunk.map_err(actix_web::Error::from)?;
+        size += chunk.len() as u64;
+        if let Some(max) = limits.max_file_size {
+            if size > max {
+                return Err(OxenHttpError::PayloadTooLarge(
+                    format!("Version is larger than the max upload size of {max} bytes").into(),
+                ));
+            }
+        }
+        hasher.update(&chunk);
+        file.write_all(&chunk)?;
+    }
+    Ok((size, format!("{:x}", hasher.digest128())))
+}
+
 /// Download a chunk of a larger file
 pub async fn download_chunk(
     req: HttpRequest,
@@ -103,6 +196,7 @@ pub async fn download_chunk(
     let repo_name = path_param(&req, "repo_name")?;
     let repo = get_repo(&app_data.path, namespace, &repo_name)?;
     let resource = parse_resource(&req, &repo)?;
+    column_access::check_raw_download(&req, &repo, &resource.file_path)?;
 
     log::debug!(
         "{} resource {}/{}",
@@ -133,6 +227,7 @@ pub async fn list_lines_in_file(
     let repo_name = path_param(&req, "repo_name")?;
     let repo = get_repo(&app_data.path, namespace, &repo_name)?;
     let resource = parse_resource(&req, &repo)?;
+    column_access::check_raw_download(&req, &repo, &resource.file_path)?;
 
     log::debug!(
         "{} resource {}/{}",
@@ -170,6 +265,42 @@ pub async fn list_lines_in_file(
     }))
 }
 
+/// Read the lines `[start, start + len)` from a committed text file without downloading it
+pub async fn read_line_range(
+    req: HttpRequest,
+    query: web::Query<LineRangeQuery>,
+) -> actix_web::Result<HttpResponse, OxenHttpError> {
+    let app_data = app_data(&req)?;
+    let namespace = path_param(&req, "namespace")?;
+    let repo_name = path_param(&req, "repo_name")?;
+    let repo = get_repo(&app_data.path, namespace, &repo_name)?;
+    let resource = parse_resource(&req, &repo)?;
+    column_access::check_raw_download(&req, &repo, &resource.file_path)?;
+
+    let start: usize = query.start.unwrap_or(0);
+    let len: usize = query.len.unwrap_or(constants::DEFAULT_PAGE_SIZE);
+
+    log::debug!(
+        "{} resource {}/{} start {} len {}",
+        current_function!(),
+        repo_name,
+        resource,
+        start,
+        len
+    );
+
+    let version_path =
+        util::fs::version_path_for_commit_id(&repo, &resource.commit.id, &resource.file_path)?;
+    let (lines, total_lines) = util::fs::read_lines_paginated_ret_size(&version_path, start, len);
+
+    Ok(HttpResponse::Ok().json(LineRangeResponse {
+        status: StatusMessage::resource_found(),
+        lines,
+        start,
+        total_lines,
+    }))
+}
+
 pub async fn list_tabular(
     req: HttpRequest,
     query: web::Query<PageNumQuery>,
@@ -204,3 +335,203 @@ pub async fn list_tabular(
         },
     }))
 }
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{http, web, App};
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+
+    use liboxen::command;
+    use liboxen::error::OxenError;
+    use liboxen::util;
+    use liboxen::view::http::MSG_HASH_MISMATCH;
+    use liboxen::view::{LineRangeResponse, StatusMessageDescription};
+
+    use crate::app_data::{OxenAppData, UploadLimits};
+    use crate::controllers;
+    use crate::test;
+
+    #[actix_web::test]
+    async fn test_controllers_entries_read_line_range() -> Result<(), OxenError> {
+        test::init_test_env();
+
+        let sync_dir = test::get_sync_dir()?;
+        let queue = test::init_queue();
+        let namespace = "Testing-Namespace";
+        let name = "Testing-Name";
+        let repo = test::create_local_repo(&sync_dir, namespace, name)?;
+
+        // Write a big log file and commit it
+        let contents: Vec<String> = (0..500).map(|i| format!("log line {i}")).collect();
+        let log_path = repo.path.join("server.log");
+        util::fs::write_to_path(&log_path, contents.join("\n"))?;
+        command::add(&repo, &log_path)?;
+        command::commit(&repo, "adding log file")?;
+
+        let uri = format!("/oxen/{namespace}/{name}/line_range/main/server.log?start=100&len=10");
+        let app = actix_web::test::init_service(
+            App::new()
+                .app_data(OxenAppData::new(sync_dir.clone(), queue))
+                .route(
+                    "/oxen/{namespace}/{repo_name}/line_range/{resource:.*}",
+                    web::get().to(controllers::entries::read_line_range),
+                ),
+        )
+        .await;
+
+        let req = actix_web::test::TestRequest::get().uri(&uri).to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        let bytes = actix_http::body::to_bytes(resp.into_body()).await.unwrap();
+        let body = std::str::from_utf8(&bytes).unwrap();
+        let range_resp: LineRangeResponse = serde_json::from_str(body)?;
+
+        assert_eq!(range_resp.start, 100);
+        assert_eq!(range_resp.total_lines, 500);
+        assert_eq!(range_resp.lines, contents[100..110].to_vec());
+
+        // cleanup
+        util::fs::remove_dir_all(sync_dir)?;
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn test_controllers_entries_upload_version_rejects_hash_mismatch() -> Result<(), OxenError>
+    {
+        test::init_test_env();
+
+        let sync_dir = test::get_sync_dir()?;
+        let queue = test::init_queue();
+        let namespace = "Testing-Namespace";
+        let name = "Testing-Upload-Version";
+        let repo = test::create_local_repo(&sync_dir, namespace, name)?;
+
+        let app = actix_web::test::init_service(
+            App::new()
+                .app_data(OxenAppData::new(sync_dir.clone(), queue))
+                .route(
+                    "/oxen/{namespace}/{repo_name}/versions/{hash}",
+                    web::post().to(controllers::entries::upload_version),
+                ),
+        )
+        .await;
+
+        // Claim the hash of one blob but send the bytes of another
+        let expected = "the original contents";
+        let corrupted = "the corrupted contents";
+        let hash = util::hasher::hash_buffer(expected.as_bytes());
+        let uri = format!("/oxen/{namespace}/{name}/versions/{hash}?filename=data.txt");
+        let req = actix_web::test::TestRequest::post()
+            .uri(&uri)
+            .set_payload(corrupted)
+            .to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::BAD_REQUEST);
+        let bytes = actix_http::body::to_bytes(resp.into_body()).await.unwrap();
+        let body = std::str::from_utf8(&bytes).unwrap();
+        let resp: StatusMessageDescription = serde_json::from_str(body)?;
+        assert_eq!(resp.status_message, MSG_HASH_MISMATCH);
+
+        let version_path =
+            util::fs::version_path_from_hash_and_file(&repo.path, hash, "data.txt".into());
+        assert!(!version_path.exists());
+
+        // The matching blob is written to the versions directory
+        let req = actix_web::test::TestRequest::post()
+            .uri(&uri)
+            .set_payload(expected)
+            .to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::OK);
+        assert_eq!(util::fs::read_from_path(&version_path)?, expected);
+
+        // cleanup
+        util::fs::remove_dir_all(sync_dir)?;
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn test_controllers_entries_upload_version_over_limit() -> Result<(), OxenError> {
+        let sync_dir = test::get_sync_dir()?;
+        let namespace = "Testing-Namespace";
+        let name = "Testing-Upload-Version-Limit";
+        let repo = test::create_local_repo(&sync_dir, namespace, name)?;
+
+        let limits = UploadLimits {
+            max_file_size: Some(16),
+            max_request_size: None,
+        };
+        let app = actix_web::test::init_service(
+            App::new()
+                .app_data(
+                    OxenAppData::new(sync_dir.clone(), test::init_queue())
+                        .with_upload_limits(limits),
+                )
+                .route(
+                    "/oxen/{namespace}/{repo_name}/versions/{hash}",
+                    web::post().to(controllers::entries::upload_version),
+                ),
+        )
+        .await;
+
+        let contents = "this version is over the sixteen byte limit";
+        let hash = util::hasher::hash_buffer(contents.as_bytes());
+        let uri = format!("/oxen/{namespace}/{name}/versions/{hash}?filename=data.txt");
+        let req = actix_web::test::TestRequest::post()
+            .uri(&uri)
+            .set_payload(contents)
+            .to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::PAYLOAD_TOO_LARGE);
+
+        let version_path =
+            util::fs::version_path_from_hash_and_file(&repo.path, hash, "data.txt".into());
+        assert!(!version_path.exists());
+
+        // cleanup
+        util::fs::remove_dir_all(sync_dir)?;
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn test_controllers_entries_versions_forbidden_with_hidden_columns(
+    ) -> Result<(), OxenError> {
+        let sync_dir = test::get_sync_dir()?;
+        let namespace = "Testing-Namespace";
+        let name = "Testing-Versions-Column-Access";
+        let (_repo, analyst_token, admin_token) =
+            test::create_column_access_repo(&sync_dir, namespace, name)?;
+
+        let app = actix_web::test::init_service(
+            App::new()
+                .app_data(OxenAppData::new(sync_dir.clone(), test::init_queue()))
+                .route(
+                    "/oxen/{namespace}/{repo_name}/versions",
+                    web::get().to(controllers::entries::download_data_from_version_paths),
+                ),
+        )
+        .await;
+
+        for (token, status) in [
+            (analyst_token, http::StatusCode::FORBIDDEN),
+            (admin_token, http::StatusCode::OK),
+        ] {
+            let body = GzEncoder::new(Vec::new(), Compression::default()).finish()?;
+            let req = actix_web::test::TestRequest::get()
+                .uri(&format!("/oxen/{namespace}/{name}/versions"))
+                .insert_header(("Authorization", format!("Bearer {token}")))
+                .set_payload(body)
+                .to_request();
+            let resp = actix_web::test::call_service(&app, req).await;
+            assert_eq!(resp.status(), status);
+        }
+
+        // cleanup
+        util::fs::remove_dir_all(sync_dir)?;
+
+        Ok(())
+    }
+}
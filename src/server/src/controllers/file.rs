@@ -1,15 +1,21 @@
+use crate::auth::column_access;
+use crate::auth::signed_urls::{self, SignedUrlClaim};
 use crate::errors::OxenHttpError;
 use crate::helpers::get_repo;
-use crate::params::{app_data, parse_resource, path_param};
+use crate::params::{app_data, parse_resource, path_param, SignedUrlQuery};
 
+use liboxen::api;
 use liboxen::core::index::ObjectDBReader;
 use liboxen::error::OxenError;
 use liboxen::model::metadata::metadata_image::ImgResize;
 use liboxen::model::CommitEntry;
 use liboxen::util;
+use liboxen::view::{SignedUrlResponse, StatusMessage};
 
 use actix_files::NamedFile;
 use actix_web::{http::header, web, HttpRequest, HttpResponse};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Download file content
 pub async fn get(
@@ -28,6 +34,7 @@ pub async fn get(
         "{} resource {namespace}/{repo_name}/{resource}",
         liboxen::current_function!()
     );
+    column_access::check_raw_download(&req, &repo, &resource.file_path)?;
 
     // TODO: CLEANUP and refactor so we can use the CderLRUCache in other places that might need it.
 
@@ -160,3 +167,131 @@ pub async fn get(
 
     Ok(response)
 }
+
+/// Mint a short lived url to download a single version file without an access token
+pub async fn create_signed_url(
+    req: HttpRequest,
+    query: web::Query<SignedUrlQuery>,
+) -> actix_web::Result<HttpResponse, OxenHttpError> {
+    let app_data = app_data(&req)?;
+    let namespace = path_param(&req, "namespace")?;
+    let repo_name = path_param(&req, "repo_name")?;
+    let repo = get_repo(&app_data.path, &namespace, &repo_name)?;
+    let resource = parse_resource(&req, &repo)?;
+    column_access::check_raw_download(&req, &repo, &resource.file_path)?;
+
+    let expires_in = query
+        .expires_in
+        .unwrap_or(signed_urls::DEFAULT_EXPIRES_IN_SECS);
+    if expires_in == 0 || expires_in > signed_urls::MAX_EXPIRES_IN_SECS {
+        return Err(OxenHttpError::BadRequest(
+            format!(
+                "expires_in must be between 1 and {} seconds",
+                signed_urls::MAX_EXPIRES_IN_SECS
+            )
+            .into(),
+        ));
+    }
+
+    // Sign the exact version, so moving the branch later does not change what the url serves
+    api::local::entries::get_commit_entry(&repo, &resource.commit, &resource.file_path)?
+        .ok_or(OxenError::path_does_not_exist(&resource.file_path))?;
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|_| OxenHttpError::InternalServerError)?
+        .as_secs();
+    let claim = SignedUrlClaim {
+        namespace,
+        repo_name,
+        commit_id: resource.commit.id.to_owned(),
+        path: resource.file_path.to_string_lossy().to_string(),
+        exp: now + expires_in,
+    };
+    let token = signed_urls::sign(&app_data.path, &claim)?;
+
+    Ok(HttpResponse::Ok().json(SignedUrlResponse {
+        status: StatusMessage::resource_created(),
+        uri: format!("/{}/{token}", signed_urls::SIGNED_FILE_SEGMENT),
+        expires_at: claim.exp,
+    }))
+}
+
+/// Download the file a signed url points to, no access token needed until it expires
+pub async fn get_signed(req: HttpRequest) -> actix_web::Result<HttpResponse, OxenHttpError> {
+    let app_data = app_data(&req)?;
+    let namespace = path_param(&req, "namespace")?;
+    let repo_name = path_param(&req, "repo_name")?;
+    let token = path_param(&req, "token")?;
+
+    let claim = match signed_urls::verify(&app_data.path, &token) {
+        Ok(claim) if claim.namespace == namespace && claim.repo_name == repo_name => claim,
+        Ok(_) => {
+            return Ok(HttpResponse::Unauthorized().json(StatusMessage::error(
+                "Signed url is for a different repository",
+            )));
+        }
+        Err(err) => {
+            log::debug!("get_signed rejected url: {}", err);
+            return Ok(HttpResponse::Unauthorized().json(StatusMessage::error(err.to_string())));
+        }
+    };
+
+    let repo = get_repo(&app_data.path, &namespace, &repo_name)?;
+    let commit = api::local::commits::get_by_id(&repo, &claim.commit_id)?
+        .ok_or(OxenError::revision_not_found(claim.commit_id.into()))?;
+    let path = Path::new(&claim.path);
+    let entry = api::local::entries::get_commit_entry(&repo, &commit, path)?
+        .ok_or(OxenError::path_does_not_exist(path))?;
+
+    let version_path = util::fs::version_path(&repo, &entry);
+    Ok(NamedFile::open(version_path)?.into_response(&req))
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{http, web, App};
+
+    use liboxen::error::OxenError;
+    use liboxen::util;
+
+    use crate::app_data::OxenAppData;
+    use crate::controllers;
+    use crate::test;
+
+    #[actix_web::test]
+    async fn test_controllers_file_get_forbidden_with_hidden_columns() -> Result<(), OxenError> {
+        let sync_dir = test::get_sync_dir()?;
+        let namespace = "Testing-Namespace";
+        let name = "Testing-File-Column-Access";
+        let (_repo, analyst_token, admin_token) =
+            test::create_column_access_repo(&sync_dir, namespace, name)?;
+
+        let app = actix_web::test::init_service(
+            App::new()
+                .app_data(OxenAppData::new(sync_dir.clone(), test::init_queue()))
+                .route(
+                    "/oxen/{namespace}/{repo_name}/file/{resource:.*}",
+                    web::get().to(controllers::file::get),
+                ),
+        )
+        .await;
+
+        for (token, status) in [
+            (analyst_token, http::StatusCode::FORBIDDEN),
+            (admin_token, http::StatusCode::OK),
+        ] {
+            let req = actix_web::test::TestRequest::get()
+                .uri(&format!("/oxen/{namespace}/{name}/file/main/users.csv"))
+                .insert_header(("Authorization", format!("Bearer {token}")))
+                .to_request();
+            let resp = actix_web::test::call_service(&app, req).await;
+            assert_eq!(resp.status(), status);
+        }
+
+        // cleanup
+        util::fs::remove_dir_all(sync_dir)?;
+
+        Ok(())
+    }
+}
@@ -1,22 +1,170 @@
+use std::path::Path;
+
 use crate::errors::OxenHttpError;
-use crate::params::app_data;
-use actix_web::{HttpRequest, HttpResponse};
+use crate::params::{app_data, HealthQuery};
+use crate::queues::TaskQueue;
+
+use actix_web::{web, HttpRequest, HttpResponse};
+use liboxen::api;
+use liboxen::core::index::CommitReader;
 use liboxen::util;
-use liboxen::view::{HealthResponse, StatusMessage};
+use liboxen::view::{HealthChecks, HealthResponse, StatusMessage, SubsystemHealth};
+
+const HEALTH_CHECK_FILE: &str = ".oxen_health_check";
 
-pub async fn index(req: HttpRequest) -> actix_web::Result<HttpResponse, OxenHttpError> {
+pub async fn index(
+    req: HttpRequest,
+    query: web::Query<HealthQuery>,
+) -> actix_web::Result<HttpResponse, OxenHttpError> {
     let app_data = app_data(&req)?;
-    match util::fs::disk_usage_for_path(&app_data.path) {
-        Ok(disk_usage) => {
-            let response = HealthResponse {
-                status: StatusMessage::resource_found(),
-                disk_usage,
-            };
-            Ok(HttpResponse::Ok().json(response))
-        }
+    let disk_usage = match util::fs::disk_usage_for_path(&app_data.path) {
+        Ok(disk_usage) => disk_usage,
         Err(err) => {
             log::error!("Error getting disk usage: {:?}", err);
-            Err(OxenHttpError::InternalServerError)
+            return Err(OxenHttpError::InternalServerError);
         }
+    };
+
+    if !query.deep.unwrap_or(false) {
+        let response = HealthResponse {
+            status: StatusMessage::resource_found(),
+            disk_usage,
+            checks: None,
+        };
+        return Ok(HttpResponse::Ok().json(response));
+    }
+
+    let checks = HealthChecks {
+        sync_dir: check_sync_dir(&app_data.path),
+        repo_db: check_repo_db(&app_data.path),
+        redis: check_redis(&app_data.queue),
+    };
+
+    if checks.is_healthy() {
+        let response = HealthResponse {
+            status: StatusMessage::resource_found(),
+            disk_usage,
+            checks: Some(checks),
+        };
+        Ok(HttpResponse::Ok().json(response))
+    } else {
+        log::error!("Deep health check failed: {:?}", checks);
+        let response = HealthResponse {
+            status: StatusMessage::error("One or more subsystems are down"),
+            disk_usage,
+            checks: Some(checks),
+        };
+        Ok(HttpResponse::ServiceUnavailable().json(response))
+    }
+}
+
+/// Make sure we can write and remove a file in the sync dir
+fn check_sync_dir(sync_dir: &Path) -> SubsystemHealth {
+    let path = sync_dir.join(HEALTH_CHECK_FILE);
+    if let Err(err) = util::fs::write_to_path(&path, "ok") {
+        return SubsystemHealth::down(format!("Could not write to sync dir: {err}"));
+    }
+    if let Err(err) = util::fs::remove_file(&path) {
+        return SubsystemHealth::down(format!("Could not remove file from sync dir: {err}"));
+    }
+    SubsystemHealth::up()
+}
+
+/// Open the commit db of the first repo we find to make sure rocksdb is usable
+fn check_repo_db(sync_dir: &Path) -> SubsystemHealth {
+    let namespaces = match api::local::repositories::list_namespaces(sync_dir) {
+        Ok(namespaces) => namespaces,
+        Err(err) => return SubsystemHealth::down(format!("Could not list namespaces: {err}")),
+    };
+
+    let repo = namespaces.iter().find_map(|namespace| {
+        api::local::repositories::list_repos_in_namespace(&sync_dir.join(namespace))
+            .into_iter()
+            .next()
+    });
+    let Some(repo) = repo else {
+        return SubsystemHealth::skipped("No repositories to check");
+    };
+
+    match CommitReader::new(&repo) {
+        Ok(_) => SubsystemHealth::up(),
+        Err(err) => SubsystemHealth::down(format!(
+            "Could not open commit db for {:?}: {err}",
+            repo.path
+        )),
+    }
+}
+
+fn check_redis(queue: &TaskQueue) -> SubsystemHealth {
+    let TaskQueue::Redis(queue) = queue else {
+        return SubsystemHealth::skipped("Redis is not configured");
+    };
+
+    let mut conn = match queue.pool.get() {
+        Ok(conn) => conn,
+        Err(err) => return SubsystemHealth::down(format!("Could not connect to redis: {err}")),
+    };
+    match redis::cmd("PING").query::<String>(&mut conn) {
+        Ok(_) => SubsystemHealth::up(),
+        Err(err) => SubsystemHealth::down(format!("Redis did not respond to ping: {err}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use actix_web::body::to_bytes;
+    use actix_web::http;
+    use actix_web::web;
+
+    use liboxen::error::OxenError;
+    use liboxen::util;
+    use liboxen::view::{HealthResponse, SubsystemStatus};
+
+    use crate::controllers;
+    use crate::params::HealthQuery;
+    use crate::queues::{RedisTaskQueue, TaskQueue};
+    use crate::test;
+
+    fn unreachable_redis_queue() -> Result<TaskQueue, OxenError> {
+        // Nothing listens on port 1, so every connection attempt fails quickly
+        let client = redis::Client::open("redis://127.0.0.1:1")?;
+        let pool = r2d2::Pool::builder()
+            .connection_timeout(Duration::from_millis(100))
+            .build_unchecked(client);
+        Ok(TaskQueue::Redis(RedisTaskQueue::new(pool)))
+    }
+
+    #[actix_web::test]
+    async fn test_controllers_health_deep_reports_redis_down() -> Result<(), OxenError> {
+        let sync_dir = test::get_sync_dir()?;
+        test::create_local_repo(&sync_dir, "Testing-Namespace", "Testing-Health")?;
+
+        // Basic check does not touch redis
+        let req = test::request(&sync_dir, unreachable_redis_queue()?, "/api/health");
+        let query: web::Query<HealthQuery> = web::Query::from_query("").unwrap();
+        let resp = controllers::health::index(req, query).await.unwrap();
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        let body = to_bytes(resp.into_body()).await.unwrap();
+        let response: HealthResponse = serde_json::from_slice(&body)?;
+        assert!(response.checks.is_none());
+
+        // Deep check reports redis as down
+        let req = test::request(&sync_dir, unreachable_redis_queue()?, "/api/health");
+        let query: web::Query<HealthQuery> = web::Query::from_query("deep=true").unwrap();
+        let resp = controllers::health::index(req, query).await.unwrap();
+        assert_eq!(resp.status(), http::StatusCode::SERVICE_UNAVAILABLE);
+        let body = to_bytes(resp.into_body()).await.unwrap();
+        let response: HealthResponse = serde_json::from_slice(&body)?;
+        let checks = response.checks.unwrap();
+        assert_eq!(checks.redis.status, SubsystemStatus::Down);
+        assert_eq!(checks.sync_dir.status, SubsystemStatus::Up);
+        assert_eq!(checks.repo_db.status, SubsystemStatus::Up);
+
+        // cleanup
+        util::fs::remove_dir_all(sync_dir)?;
+
+        Ok(())
     }
 }
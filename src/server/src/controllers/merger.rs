@@ -72,18 +72,49 @@ pub async fn merge(req: HttpRequest) -> actix_web::Result<HttpResponse, OxenHttp
     // Check if mergeable
     let merger = Merger::new(&repository)?;
     match merger.merge_into_base(&head, &base) {
-        Ok(Some(_merge_commit)) => {
+        Ok(Some(merge_commit)) => {
             let response = MergeSuccessResponse {
                 status: StatusMessage::resource_found(),
                 base_commit: base.commit_id,
                 head_commit: head.commit_id,
+                commit: Some(merge_commit),
             };
 
             Ok(HttpResponse::Ok().json(response))
         }
         Ok(None) => {
+            let commit_reader = CommitReader::new(&repository)?;
+            let conflicts: Vec<MergeConflictFile> = merger
+                .list_conflicts_between_branches(&commit_reader, &base, &head)?
+                .iter()
+                .map(|p| MergeConflictFile {
+                    path: p.to_string_lossy().to_string(),
+                })
+                .collect();
+
+            if conflicts.is_empty() {
+                // Nothing to merge, base already has head
+                log::debug!("Merge has nothing to merge");
+                let commit = commit_reader.get_commit_by_id(&base.commit_id)?;
+                let response = MergeSuccessResponse {
+                    status: StatusMessage::resource_found(),
+                    base_commit: base.commit_id,
+                    head_commit: head.commit_id,
+                    commit,
+                };
+                return Ok(HttpResponse::Ok().json(response));
+            }
+
             log::debug!("Merge has conflicts");
-            Ok(HttpResponse::BadRequest().json(StatusMessage::bad_request()))
+            let commits = merger.list_commits_between_branches(&commit_reader, &base, &head)?;
+            Ok(HttpResponse::BadRequest().json(MergeableResponse {
+                status: StatusMessage::bad_request(),
+                mergeable: Mergeable {
+                    is_mergeable: false,
+                    conflicts,
+                    commits,
+                },
+            }))
         }
         Err(err) => {
             log::debug!("Err merging branches {:?}", err);
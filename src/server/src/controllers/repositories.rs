@@ -3,6 +3,7 @@ use crate::helpers::get_repo;
 use crate::params::{app_data, path_param};
 
 use liboxen::api;
+use liboxen::constants::DEFAULT_BRANCH_NAME;
 use liboxen::error::OxenError;
 use liboxen::util;
 use liboxen::view::http::{MSG_RESOURCE_FOUND, MSG_RESOURCE_UPDATED, STATUS_SUCCESS};
@@ -32,6 +33,7 @@ pub async fn index(req: HttpRequest) -> actix_web::Result<HttpResponse, OxenHttp
             .map(|repo| RepositoryView {
                 name: repo.dirname(),
                 namespace: namespace.to_string(),
+                default_branch: None,
             })
             .collect();
     let view = ListRepositoryResponse {
@@ -47,7 +49,8 @@ pub async fn show(req: HttpRequest) -> actix_web::Result<HttpResponse, OxenHttpE
     let name = path_param(&req, "repo_name")?;
 
     // Get the repository or return error
-    let _repository = get_repo(&app_data.path, &namespace, &name)?;
+    let repository = get_repo(&app_data.path, &namespace, &name)?;
+    let default_branch = api::local::branches::default_branch(&repository)?.map(|b| b.name);
 
     // Return the repository view
     Ok(HttpResponse::Ok().json(RepositoryDataTypesResponse {
@@ -60,10 +63,23 @@ pub async fn show(req: HttpRequest) -> actix_web::Result<HttpResponse, OxenHttpE
             // Hacking around it to not show in CLI unless you go through hub for now
             size: 0,
             data_types: vec![],
+            default_branch,
         },
     }))
 }
 
+/// HEAD check for whether a repository exists, without building the repository body
+pub async fn exists(req: HttpRequest) -> actix_web::Result<HttpResponse, OxenHttpError> {
+    let app_data = app_data(&req)?;
+    let namespace = path_param(&req, "namespace")?;
+    let name = path_param(&req, "repo_name")?;
+
+    match api::local::repositories::get_by_namespace_and_name(&app_data.path, &namespace, &name)? {
+        Some(_) => Ok(HttpResponse::Ok().finish()),
+        None => Ok(HttpResponse::NotFound().finish()),
+    }
+}
+
 // Need this endpoint to get the size and data types for a repo from the UI
 pub async fn stats(req: HttpRequest) -> HttpResponse {
     let app_data = app_data(&req).unwrap();
@@ -88,6 +104,9 @@ pub async fn stats(req: HttpRequest) -> HttpResponse {
                     repository: RepositoryStatsView {
                         data_size: stats.data_size,
                         data_types,
+                        num_commits: stats.num_commits,
+                        num_branches: stats.num_branches,
+                        last_updated: stats.last_updated,
                     },
                 })
             }
@@ -118,6 +137,10 @@ pub async fn create(req: HttpRequest, body: String) -> HttpResponse {
                 repository: RepositoryView {
                     namespace: data.namespace.clone(),
                     name: data.name,
+                    default_branch: Some(
+                        data.default_branch
+                            .unwrap_or_else(|| DEFAULT_BRANCH_NAME.to_string()),
+                    ),
                 },
             }),
             Err(OxenError::RepoAlreadyExists(path)) => {
@@ -182,6 +205,7 @@ pub async fn transfer_namespace(
         repository: RepositoryView {
             namespace: to_namespace,
             name,
+            default_branch: None,
         },
     }))
 }
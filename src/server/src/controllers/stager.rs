@@ -1,7 +1,9 @@
+use crate::app_data::UploadLimits;
+use crate::auth::column_access;
 use crate::errors::OxenHttpError;
 use crate::helpers::get_repo;
 use crate::params::{
-    app_data, df_opts_query, parse_resource, path_param, DFOptsQuery, PageNumQuery,
+    app_data, df_opts_query, parse_resource, path_param, DFOptsQuery, ForceQuery, PageNumQuery,
 };
 
 use actix_files::NamedFile;
@@ -9,6 +11,7 @@ use actix_files::NamedFile;
 use liboxen::constants::TABLE_NAME;
 use liboxen::core::cache::commit_cacher;
 use liboxen::core::db::{df_db, staged_df_db};
+use liboxen::core::index::idempotency_keys::{self, IdempotencyKeyState};
 use liboxen::core::index::mod_stager;
 use liboxen::core::index::remote_df_stager::{get_row_id, get_row_idx};
 use liboxen::error::OxenError;
@@ -17,7 +20,8 @@ use liboxen::model::entry::mod_entry::NewMod;
 use liboxen::model::metadata::metadata_image::ImgResize;
 use liboxen::model::CommitEntry;
 use liboxen::model::{
-    entry::mod_entry::ModType, Branch, ContentType, LocalRepository, NewCommitBody, Schema,
+    entry::mod_entry::ModType, Branch, ContentType, EntryDataType, LocalRepository, NewCommitBody,
+    Schema,
 };
 use liboxen::opts::DFOpts;
 use liboxen::util::{self, paginate};
@@ -28,8 +32,10 @@ use liboxen::view::entry::{
 use liboxen::view::json_data_frame_view::{JsonDataFrameRowResponse, JsonDataFrameSource};
 use liboxen::view::remote_staged_status::{DFIsEditableResponse, RemoteStagedStatus};
 use liboxen::view::{
-    CommitResponse, FilePathsResponse, JsonDataFrameView, JsonDataFrameViewResponse,
-    JsonDataFrameViews, RemoteStagedStatusResponse, StatusMessage,
+    AllStagedStatusResponse, ChunkedUploadRequest, ChunkedUploadResponse, CommitResponse,
+    FilePathsResponse, JsonDataFrameView, JsonDataFrameViewResponse, JsonDataFrameViews,
+    PaginatedStagedFiles, PaginatedStagedFilesResponse, RemoteStagedStatusResponse,
+    StagingConflictResponse, StatusMessage,
 };
 use liboxen::{api, constants, core::index};
 
@@ -39,7 +45,7 @@ use actix_multipart::Multipart;
 use actix_web::Error;
 use futures_util::TryStreamExt as _;
 use std::io::Write;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use uuid::Uuid;
 
 pub async fn status_dir(
@@ -70,6 +76,49 @@ pub async fn status_dir(
     )
 }
 
+/// Every identifier with staged changes on the branch, and the paths they staged
+pub async fn status_all(req: HttpRequest) -> actix_web::Result<HttpResponse, OxenHttpError> {
+    let app_data = app_data(&req)?;
+    let namespace = path_param(&req, "namespace")?;
+    let repo_name = path_param(&req, "repo_name")?;
+    let branch_name = path_param(&req, "branch")?;
+    let repo = get_repo(&app_data.path, namespace, repo_name)?;
+
+    let branch = api::local::branches::get_by_name(&repo, &branch_name)?
+        .ok_or(OxenError::remote_branch_not_found(&branch_name))?;
+    let identifiers = index::remote_dir_stager::list_all_staged(&repo, &branch)?;
+
+    Ok(HttpResponse::Ok().json(AllStagedStatusResponse {
+        status: StatusMessage::resource_found(),
+        identifiers,
+    }))
+}
+
+/// The file paths an identifier has staged on the branch, one page at a time
+pub async fn list_staged_files(
+    req: HttpRequest,
+    query: web::Query<PageNumQuery>,
+) -> actix_web::Result<HttpResponse, OxenHttpError> {
+    let app_data = app_data(&req)?;
+    let namespace = path_param(&req, "namespace")?;
+    let repo_name = path_param(&req, "repo_name")?;
+    let identifier = path_param(&req, "identifier")?;
+    let branch_name = path_param(&req, "branch")?;
+    let repo = get_repo(&app_data.path, namespace, repo_name)?;
+    let page_num = query.page.unwrap_or(constants::DEFAULT_PAGE_NUM);
+    let page_size = query.page_size.unwrap_or(constants::DEFAULT_PAGE_SIZE);
+
+    let branch = api::local::branches::get_by_name(&repo, &branch_name)?
+        .ok_or(OxenError::remote_branch_not_found(&branch_name))?;
+    let staged_files = index::remote_dir_stager::list_staged_files(&repo, &branch, &identifier)?;
+    let (paths, pagination) = paginate(staged_files, page_num, page_size);
+
+    Ok(HttpResponse::Ok().json(PaginatedStagedFilesResponse {
+        status: StatusMessage::resource_found(),
+        staged: PaginatedStagedFiles { paths, pagination },
+    }))
+}
+
 pub async fn diff_file(req: HttpRequest) -> actix_web::Result<HttpResponse, OxenHttpError> {
     let app_data = app_data(&req)?;
     let namespace = path_param(&req, "namespace")?;
@@ -99,7 +148,7 @@ pub async fn diff_file(req: HttpRequest) -> actix_web::Result<HttpResponse, Oxen
         }
     };
     // TODO expensive clone
-    let diff_df = diff.contents.clone();
+    let diff_df = column_access::visible_df(&req, &repo, diff.contents.clone())?;
     let diff_view = CompareTabular::from(diff);
 
     // TODO: Oxen schema vs polars inferred schema
@@ -136,7 +185,7 @@ pub async fn diff_df(
     opts = df_opts_query::parse_opts(&query, &mut opts);
 
     opts.page = Some(query.page.unwrap_or(constants::DEFAULT_PAGE_NUM));
-    opts.page_size = Some(query.page_size.unwrap_or(constants::DEFAULT_PAGE_SIZE));
+    opts.page_size = Some(query.page_size.unwrap_or(repo.df_page_size()));
 
     // Remote staged calls must be on a branch
     let branch = resource
@@ -152,8 +201,11 @@ pub async fn diff_df(
     let conn = df_db::get_connection(staged_db_path)?;
 
     let diff_df = staged_df_db::df_diff(&conn)?;
+    let diff_df = column_access::visible_df(&req, &repo, diff_df)?;
 
     let df_schema = df_db::get_schema(&conn, TABLE_NAME)?;
+    let hidden = column_access::hidden_columns(&req, &repo, &df_schema)?;
+    let df_schema = column_access::visible_schema(&df_schema, &hidden);
 
     let df_views = JsonDataFrameViews::from_df_and_opts(diff_df, df_schema, &opts);
 
@@ -193,19 +245,36 @@ pub async fn get_file(
     let branch_repo = index::remote_dir_stager::init_or_get(&repo, &branch, &identifier)?;
 
     // The path in a remote staged context is just the working path of the branch repo
-    let file_path = branch_repo.path.join(resource.file_path);
+    let file_path = branch_repo.path.join(&resource.file_path);
 
     log::debug!("got staged file path {:?}", file_path);
 
     let img_resize = query.into_inner();
 
-    if img_resize.width.is_some() || img_resize.height.is_some() {
-        let resized_path = util::fs::resized_path_for_staged_entry(
+    if img_resize.width.is_some() || img_resize.height.is_some() || img_resize.format.is_some() {
+        // Sniff the contents rather than trusting the extension
+        let mime_type = util::fs::file_mime_type(&file_path);
+        let data_type = util::fs::datatype_from_mimetype(&file_path, &mime_type);
+        if data_type != EntryDataType::Image {
+            return Err(OxenHttpError::BadRequest(
+                format!(
+                    "Cannot resize or convert {:?}, it is {} not an image",
+                    resource.file_path, mime_type
+                )
+                .into(),
+            ));
+        }
+
+        let mut resized_path = util::fs::resized_path_for_staged_entry(
             repo,
             &file_path,
             img_resize.width,
             img_resize.height,
         )?;
+        // The image is saved in the format of the extension
+        if let Some(format) = &img_resize.format {
+            resized_path = resized_path.with_extension(format.extension());
+        }
 
         util::fs::resize_cache_image(&file_path, &resized_path, img_resize)?;
         return Ok(NamedFile::open(resized_path)?);
@@ -213,16 +282,39 @@ pub async fn get_file(
     Ok(NamedFile::open(file_path)?)
 }
 
+// A file from a multipart upload, written to a tmp dir until it is moved into the staging dir
+struct UploadedPart {
+    tmp_path: PathBuf,
+    // Relative to the staging dir
+    path: PathBuf,
+}
+
+fn upload_tmp_dir(repo: &LocalRepository) -> PathBuf {
+    util::fs::oxen_hidden_dir(&repo.path)
+        .join("tmp")
+        .join("uploads")
+        .join(Uuid::new_v4().to_string())
+}
+
+fn remove_upload_dir(upload_dir: &Path) -> Result<(), OxenError> {
+    if upload_dir.exists() {
+        util::fs::remove_dir_all(upload_dir)?;
+    }
+    Ok(())
+}
+
+/// Write the parts of the upload to `upload_dir`, nothing in the staging dir is touched so
+/// that a rejected upload can't clobber what is already staged there
 async fn save_parts(
-    repo: &LocalRepository,
-    branch: &Branch,
-    user_id: &str,
+    upload_dir: &Path,
     directory: &Path,
+    limits: UploadLimits,
     mut payload: Multipart,
-) -> Result<Vec<PathBuf>, Error> {
-    let mut files: Vec<PathBuf> = vec![];
+) -> Result<Vec<UploadedPart>, OxenHttpError> {
+    let mut parts: Vec<UploadedPart> = vec![];
+    let mut request_size: u64 = 0;
     // iterate over multipart stream
-    while let Some(mut field) = payload.try_next().await? {
+    while let Some(mut field) = payload.try_next().await.map_err(Error::from)? {
         // A multipart/form-data stream has to contain `content_disposition`
         let content_disposition = field.content_disposition();
 
@@ -232,29 +324,63 @@ async fn save_parts(
 
         log::debug!("Got uploaded file name: {upload_filename:?}");
 
-        let staging_dir = index::remote_dir_stager::branch_staging_dir(repo, branch, user_id);
-        let full_dir = staging_dir.join(directory);
-
-        if !full_dir.exists() {
-            std::fs::create_dir_all(&full_dir)?;
-        }
-
-        let filepath = full_dir.join(&upload_filename);
-        let filepath_cpy = full_dir.join(&upload_filename);
-        log::debug!("stager::save_file writing file to {:?}", filepath);
+        let part_dir = upload_dir.join(parts.len().to_string());
+        util::fs::create_dir_all(&part_dir)?;
+        let tmp_path = part_dir.join(&upload_filename);
+        log::debug!("stager::save_file writing file to {:?}", tmp_path);
 
         // File::create is blocking operation, use threadpool
-        let mut f = web::block(|| std::fs::File::create(filepath)).await??;
+        let create_path = tmp_path.clone();
+        let mut f = web::block(|| std::fs::File::create(create_path))
+            .await
+            .map_err(Error::from)??;
+        parts.push(UploadedPart {
+            tmp_path,
+            path: directory.join(&upload_filename),
+        });
 
         // Field in turn is stream of *Bytes* object
-        while let Some(chunk) = field.try_next().await? {
+        let mut file_size: u64 = 0;
+        while let Some(chunk) = field.try_next().await.map_err(Error::from)? {
+            file_size += chunk.len() as u64;
+            request_size += chunk.len() as u64;
+            if let Some(err) =
+                upload_limit_error(&limits, &upload_filename, file_size, request_size)
+            {
+                return Err(err);
+            }
+
             // filesystem operations are blocking, we have to use threadpool
-            f = web::block(move || f.write_all(&chunk).map(|_| f)).await??;
+            f = web::block(move || f.write_all(&chunk).map(|_| f))
+                .await
+                .map_err(Error::from)??;
         }
-        files.push(filepath_cpy);
     }
 
-    Ok(files)
+    Ok(parts)
+}
+
+fn upload_limit_error(
+    limits: &UploadLimits,
+    filename: &str,
+    file_size: u64,
+    request_size: u64,
+) -> Option<OxenHttpError> {
+    if let Some(max) = limits.max_file_size {
+        if file_size > max {
+            return Some(OxenHttpError::PayloadTooLarge(
+                format!("File {filename} is larger than the max upload size of {max} bytes").into(),
+            ));
+        }
+    }
+    if let Some(max) = limits.max_request_size {
+        if request_size > max {
+            return Some(OxenHttpError::PayloadTooLarge(
+                format!("Upload is larger than the max request size of {max} bytes").into(),
+            ));
+        }
+    }
+    None
 }
 
 fn get_content_type(req: &HttpRequest) -> Option<&str> {
@@ -293,6 +419,7 @@ pub async fn df_get_row(req: HttpRequest) -> Result<HttpResponse, OxenHttpError>
     let row_index = get_row_idx(&row_df)?;
 
     let opts = DFOpts::empty();
+    let row_df = column_access::visible_df(&req, &repo, row_df)?;
     let row_schema = Schema::from_polars(&row_df.schema().clone());
     let row_df_source = JsonDataFrameSource::from_df(&row_df, &row_schema);
     let row_df_view = JsonDataFrameView::from_df_opts(row_df, row_schema, &opts);
@@ -388,6 +515,7 @@ pub async fn df_add_row(req: HttpRequest, bytes: Bytes) -> Result<HttpResponse,
     let row_index: Option<usize> = get_row_idx(&row_df)?;
 
     let opts = DFOpts::empty();
+    let row_df = column_access::visible_df(&req, &repo, row_df)?;
     let row_schema = Schema::from_polars(&row_df.schema().clone());
     let row_df_source = JsonDataFrameSource::from_df(&row_df, &row_schema);
     let row_df_view = JsonDataFrameView::from_df_opts(row_df, row_schema, &opts);
@@ -436,6 +564,7 @@ pub async fn df_restore_row(req: HttpRequest) -> Result<HttpResponse, OxenHttpEr
     let row_id = get_row_id(&restored_row)?;
 
     log::debug!("Restored row in controller is {:?}", restored_row);
+    let restored_row = column_access::visible_df(&req, &repo, restored_row)?;
     let schema = Schema::from_polars(&restored_row.schema());
     Ok(HttpResponse::Ok().json(JsonDataFrameRowResponse {
         data_frame: JsonDataFrameViews {
@@ -527,6 +656,7 @@ pub async fn df_modify_row(req: HttpRequest, bytes: Bytes) -> Result<HttpRespons
     let row_id = get_row_id(&modified_row)?;
 
     log::debug!("Modified row in controller is {:?}", modified_row);
+    let modified_row = column_access::visible_df(&req, &repo, modified_row)?;
     let schema = Schema::from_polars(&modified_row.schema());
     Ok(HttpResponse::Ok().json(JsonDataFrameRowResponse {
         data_frame: JsonDataFrameViews {
@@ -562,10 +692,11 @@ pub async fn df_delete_row(req: HttpRequest, _bytes: Bytes) -> Result<HttpRespon
     let entry = api::local::entries::get_commit_entry(&repo, &commit, &resource.file_path)?
         .ok_or(OxenError::entry_does_not_exist(resource.file_path.clone()))?;
 
-    delete_row(&repo, &branch, user_id, &entry, row_id.to_string())
+    delete_row(&req, &repo, &branch, user_id, &entry, row_id.to_string())
 }
 
 fn delete_row(
+    req: &HttpRequest,
     repo: &LocalRepository,
     branch: &Branch,
     user_id: &str,
@@ -580,6 +711,7 @@ fn delete_row(
     };
     match liboxen::core::index::mod_stager::delete_row(repo, branch, user_id, &uuid, &new_mod) {
         Ok(df) => {
+            let df = column_access::visible_df(req, repo, df)?;
             let schema = Schema::from_polars(&df.schema());
             Ok(HttpResponse::Ok().json(JsonDataFrameRowResponse {
                 data_frame: JsonDataFrameViews {
@@ -617,7 +749,11 @@ fn delete_row(
     }
 }
 
-pub async fn add_file(req: HttpRequest, payload: Multipart) -> Result<HttpResponse, OxenHttpError> {
+pub async fn add_file(
+    req: HttpRequest,
+    query: web::Query<ForceQuery>,
+    payload: Multipart,
+) -> Result<HttpResponse, OxenHttpError> {
     let app_data = app_data(&req)?;
 
     let namespace = path_param(&req, "namespace")?;
@@ -638,7 +774,52 @@ pub async fn add_file(req: HttpRequest, payload: Multipart) -> Result<HttpRespon
         repo.path
     );
 
-    let files = save_parts(&repo, &branch, &user_id, &resource.file_path, payload).await?;
+    // Receive the whole upload before checking it against the staging dir
+    let upload_dir = upload_tmp_dir(&repo);
+    let result = save_parts(
+        &upload_dir,
+        &resource.file_path,
+        app_data.upload_limits,
+        payload,
+    )
+    .await;
+    let parts = match result {
+        Ok(parts) => parts,
+        Err(err) => {
+            remove_upload_dir(&upload_dir)?;
+            return Err(err);
+        }
+    };
+
+    // Do not clobber a path someone else is in the middle of changing unless forced
+    if !query.force.unwrap_or(false) {
+        let paths: Vec<PathBuf> = parts.iter().map(|part| part.path.clone()).collect();
+        let conflicts =
+            index::remote_dir_stager::find_staging_conflicts(&repo, &branch, &user_id, &paths)?;
+        if !conflicts.is_empty() {
+            log::debug!("stager::stage found conflicts {:?}", conflicts);
+            remove_upload_dir(&upload_dir)?;
+            return Ok(HttpResponse::Conflict().json(StagingConflictResponse {
+                status: StatusMessage::error(
+                    "Another identifier has staged changes to these paths, add with force=true to overwrite",
+                ),
+                conflicts,
+            }));
+        }
+    }
+
+    let staging_dir = index::remote_dir_stager::branch_staging_dir(&repo, &branch, &user_id);
+    let mut files: Vec<PathBuf> = vec![];
+    for part in parts.iter() {
+        let filepath = staging_dir.join(&part.path);
+        if let Some(parent) = filepath.parent() {
+            util::fs::create_dir_all(parent)?;
+        }
+        util::fs::rename(&part.tmp_path, &filepath)?;
+        files.push(filepath);
+    }
+    remove_upload_dir(&upload_dir)?;
+
     let mut ret_files = vec![];
 
     for file in files.iter() {
@@ -654,6 +835,277 @@ pub async fn add_file(req: HttpRequest, payload: Multipart) -> Result<HttpRespon
     }))
 }
 
+const CHUNKED_UPLOAD_FILE: &str = "upload.json";
+// Identifier that created the upload, only it can upload parts and complete it
+const CHUNKED_UPLOAD_OWNER_FILE: &str = "owner";
+
+fn chunked_upload_dir(repo: &LocalRepository, upload_id: &str) -> PathBuf {
+    util::fs::oxen_hidden_dir(&repo.path)
+        .join("tmp")
+        .join("chunked_uploads")
+        .join(upload_id)
+}
+
+fn chunked_part_path(upload_dir: &Path, part_num: usize) -> PathBuf {
+    upload_dir.join(format!("part_{part_num:016}"))
+}
+
+fn read_chunked_upload(
+    repo: &LocalRepository,
+    upload_id: &str,
+    user_id: &str,
+) -> Result<ChunkedUploadRequest, OxenHttpError> {
+    // The id ends up in a path, so don't let it walk out of the upload dir
+    if Uuid::parse_str(upload_id).is_err() {
+        return Err(OxenHttpError::BadRequest(
+            format!("Invalid upload id: {upload_id}").into(),
+        ));
+    }
+    let upload_dir = chunked_upload_dir(repo, upload_id);
+    let upload_file = upload_dir.join(CHUNKED_UPLOAD_FILE);
+    let owner_file = upload_dir.join(CHUNKED_UPLOAD_OWNER_FILE);
+    if !upload_file.exists() || !owner_file.exists() {
+        return Err(OxenHttpError::NotFound);
+    }
+    // Someone else's upload looks the same as one that does not exist
+    if util::fs::read_from_path(&owner_file)? != user_id {
+        log::debug!(
+            "stager::read_chunked_upload {} is not owned by {}",
+            upload_id,
+            user_id
+        );
+        return Err(OxenHttpError::NotFound);
+    }
+    let contents = util::fs::read_from_path(&upload_file)?;
+    let upload: ChunkedUploadRequest = serde_json::from_str(&contents)?;
+    validate_upload_directory(&upload.directory)?;
+    Ok(upload)
+}
+
+// The directory is joined onto the staging dir, so it must stay inside of it
+fn validate_upload_directory(directory: &str) -> Result<(), OxenHttpError> {
+    let is_contained = Path::new(directory)
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+    if is_contained {
+        Ok(())
+    } else {
+        Err(OxenHttpError::BadRequest(
+            format!("Invalid upload directory: {directory}").into(),
+        ))
+    }
+}
+
+/// Start a chunked upload, the client then PUTs each part and calls complete
+pub async fn create_chunked_upload(
+    req: HttpRequest,
+    body: String,
+) -> Result<HttpResponse, OxenHttpError> {
+    let app_data = app_data(&req)?;
+    let namespace = path_param(&req, "namespace")?;
+    let repo_name = path_param(&req, "repo_name")?;
+    let user_id = path_param(&req, "identifier")?;
+    let repo = get_repo(&app_data.path, namespace, &repo_name)?;
+
+    let upload: ChunkedUploadRequest = serde_json::from_str(&body)?;
+    validate_upload_directory(&upload.directory)?;
+    api::local::branches::get_by_name(&repo, &upload.branch)?
+        .ok_or(OxenError::remote_branch_not_found(&upload.branch))?;
+    if upload.total_chunks == 0 {
+        return Err(OxenHttpError::BadRequest(
+            "Chunked upload must have at least one part".into(),
+        ));
+    }
+    // Every part holds at least one byte, so more parts than bytes can only be abuse
+    if upload.total_chunks as u64 > upload.total_size.max(1) {
+        return Err(OxenHttpError::BadRequest(
+            format!(
+                "Chunked upload of {} bytes cannot have {} parts",
+                upload.total_size, upload.total_chunks
+            )
+            .into(),
+        ));
+    }
+    if let Some(max) = app_data.upload_limits.max_file_size {
+        if upload.total_size > max {
+            return Err(OxenHttpError::PayloadTooLarge(
+                format!(
+                    "File {} is larger than the max upload size of {max} bytes",
+                    upload.file_name
+                )
+                .into(),
+            ));
+        }
+    }
+
+    let upload_id = Uuid::new_v4().to_string();
+    let upload_dir = chunked_upload_dir(&repo, &upload_id);
+    util::fs::create_dir_all(&upload_dir)?;
+    util::fs::write_to_path(
+        upload_dir.join(CHUNKED_UPLOAD_FILE),
+        serde_json::to_string(&upload)?,
+    )?;
+    util::fs::write_to_path(upload_dir.join(CHUNKED_UPLOAD_OWNER_FILE), &user_id)?;
+    log::debug!(
+        "stager::create_chunked_upload {} for {:?} in {} parts",
+        upload_id,
+        upload.file_name,
+        upload.total_chunks
+    );
+
+    let part_uris = (0..upload.total_chunks)
+        .map(|i| format!("/staging/{user_id}/chunked_uploads/{upload_id}/parts/{i}"))
+        .collect();
+    Ok(HttpResponse::Ok().json(ChunkedUploadResponse {
+        status: StatusMessage::resource_created(),
+        upload_id,
+        part_uris,
+    }))
+}
+
+/// Save one part of a chunked upload, uploading the same part again overwrites it
+pub async fn upload_chunked_part(
+    req: HttpRequest,
+    mut payload: web::Payload,
+) -> Result<HttpResponse, OxenHttpError> {
+    let app_data = app_data(&req)?;
+    let namespace = path_param(&req, "namespace")?;
+    let repo_name = path_param(&req, "repo_name")?;
+    let upload_id = path_param(&req, "upload_id")?;
+    let user_id = path_param(&req, "identifier")?;
+    let part_num = path_param(&req, "part_num")?;
+    let repo = get_repo(&app_data.path, namespace, &repo_name)?;
+
+    let upload = read_chunked_upload(&repo, &upload_id, &user_id)?;
+    let part_num: usize = part_num
+        .parse()
+        .map_err(|_| OxenHttpError::BadRequest(format!("Invalid part: {part_num}").into()))?;
+    if part_num >= upload.total_chunks {
+        return Err(OxenHttpError::BadRequest(
+            format!(
+                "Part {part_num} out of range, upload has {} parts",
+                upload.total_chunks
+            )
+            .into(),
+        ));
+    }
+
+    let part_path = chunked_part_path(&chunked_upload_dir(&repo, &upload_id), part_num);
+    // Stream the part to disk, parts can be larger than the default body limit
+    let mut file = std::fs::File::create(&part_path)?;
+    let mut part_size: u64 = 0;
+    while let Some(chunk) = payload.try_next().await.map_err(actix_web::Error::from)? {
+        part_size += chunk.len() as u64;
+        if let Some(max) = app_data.upload_limits.max_request_size {
+            if part_size > max {
+                drop(file);
+                util::fs::remove_file(&part_path)?;
+                return Err(OxenHttpError::PayloadTooLarge(
+                    format!("Part {part_num} is larger than the max request size of {max} bytes")
+                        .into(),
+                ));
+            }
+        }
+        file.write_all(&chunk)?;
+    }
+    log::debug!(
+        "stager::upload_chunked_part wrote part {}/{} of {} to {:?}",
+        part_num,
+        upload.total_chunks,
+        upload_id,
+        part_path
+    );
+
+    Ok(HttpResponse::Ok().json(StatusMessage::resource_created()))
+}
+
+/// Put the parts back together, check the hash and stage the file
+pub async fn complete_chunked_upload(
+    req: HttpRequest,
+    query: web::Query<ForceQuery>,
+) -> Result<HttpResponse, OxenHttpError> {
+    let app_data = app_data(&req)?;
+    let namespace = path_param(&req, "namespace")?;
+    let repo_name = path_param(&req, "repo_name")?;
+    let user_id = path_param(&req, "identifier")?;
+    let upload_id = path_param(&req, "upload_id")?;
+    let repo = get_repo(&app_data.path, namespace, &repo_name)?;
+
+    let upload = read_chunked_upload(&repo, &upload_id, &user_id)?;
+    let upload_dir = chunked_upload_dir(&repo, &upload_id);
+    let missing: Vec<usize> = (0..upload.total_chunks)
+        .filter(|i| !chunked_part_path(&upload_dir, *i).exists())
+        .collect();
+    if !missing.is_empty() {
+        return Err(OxenHttpError::BadRequest(
+            format!("Chunked upload is missing parts {missing:?}").into(),
+        ));
+    }
+
+    let branch = api::local::branches::get_by_name(&repo, &upload.branch)?
+        .ok_or(OxenError::remote_branch_not_found(&upload.branch))?;
+    let branch_repo = index::remote_dir_stager::init_or_get(&repo, &branch, &user_id)?;
+
+    // Same conflict check as add_file, the parts are kept so the client can retry with force
+    let file_name = sanitize_filename::sanitize(&upload.file_name);
+    if !query.force.unwrap_or(false) {
+        let paths = vec![Path::new(&upload.directory).join(&file_name)];
+        let conflicts =
+            index::remote_dir_stager::find_staging_conflicts(&repo, &branch, &user_id, &paths)?;
+        if !conflicts.is_empty() {
+            log::debug!(
+                "stager::complete_chunked_upload found conflicts {:?}",
+                conflicts
+            );
+            return Ok(HttpResponse::Conflict().json(StagingConflictResponse {
+                status: StatusMessage::error(
+                    "Another identifier has staged changes to these paths, add with force=true to overwrite",
+                ),
+                conflicts,
+            }));
+        }
+    }
+
+    // Assemble the parts into the staging dir, same place a multipart add_file writes to
+    let staging_dir = index::remote_dir_stager::branch_staging_dir(&repo, &branch, &user_id);
+    let full_dir = staging_dir.join(&upload.directory);
+    util::fs::create_dir_all(&full_dir)?;
+    let filepath = full_dir.join(file_name);
+    {
+        let mut file = std::fs::File::create(&filepath)?;
+        for i in 0..upload.total_chunks {
+            let mut part = std::fs::File::open(chunked_part_path(&upload_dir, i))?;
+            std::io::copy(&mut part, &mut file)?;
+        }
+    }
+
+    let size = std::fs::metadata(&filepath)?.len();
+    let hash = util::hasher::hash_file_contents(&filepath)?;
+    if size != upload.total_size || hash != upload.hash {
+        util::fs::remove_file(&filepath)?;
+        return Err(OxenHttpError::BadRequest(
+            format!(
+                "Chunked upload does not match, expected {} bytes with hash {} got {} bytes with hash {}",
+                upload.total_size, upload.hash, size, hash
+            )
+            .into(),
+        ));
+    }
+
+    let file_path =
+        index::remote_dir_stager::stage_file(&repo, &branch_repo, &branch, &user_id, &filepath)?;
+    util::fs::remove_dir_all(&upload_dir)?;
+    log::debug!(
+        "stager::complete_chunked_upload ✅ success! staged file {:?}",
+        file_path
+    );
+
+    Ok(HttpResponse::Ok().json(FilePathsResponse {
+        status: StatusMessage::resource_created(),
+        paths: vec![file_path],
+    }))
+}
+
 pub async fn commit(req: HttpRequest, body: String) -> Result<HttpResponse, Error> {
     let app_data = app_data(&req)?;
 
@@ -679,12 +1131,73 @@ pub async fn commit(req: HttpRequest, body: String) -> Result<HttpResponse, Erro
     {
         Ok(Some(repo)) => match api::local::branches::get_by_name(&repo, branch_name) {
             Ok(Some(branch)) => {
+                // A retry of a request that already committed gets the original commit back
+                if let Some(key) = &data.idempotency_key {
+                    match idempotency_keys::reserve(&repo, user_id, branch_name, key) {
+                        Ok(IdempotencyKeyState::Reserved) => {}
+                        Ok(IdempotencyKeyState::InProgress) => {
+                            return Ok(HttpResponse::Conflict().json(StatusMessage::error(
+                                format!("A commit with idempotency key {key} is still in progress"),
+                            )));
+                        }
+                        Ok(IdempotencyKeyState::Committed(commit_id)) => {
+                            log::debug!("stager::commit key {key} already created {commit_id}");
+                            return match api::local::commits::get_by_id(&repo, &commit_id) {
+                                Ok(Some(commit)) => Ok(HttpResponse::Ok().json(CommitResponse {
+                                    status: StatusMessage::resource_found(),
+                                    commit,
+                                })),
+                                Ok(None) => Ok(HttpResponse::NotFound()
+                                    .json(StatusMessage::resource_not_found())),
+                                Err(err) => {
+                                    log::error!("Could not get commit {commit_id}: {:?}", err);
+                                    Ok(HttpResponse::InternalServerError()
+                                        .json(StatusMessage::internal_server_error()))
+                                }
+                            };
+                        }
+                        Err(err) => {
+                            log::error!("Could not reserve idempotency key {key}: {:?}", err);
+                            return Ok(HttpResponse::InternalServerError()
+                                .json(StatusMessage::internal_server_error()));
+                        }
+                    }
+                }
+
+                // Reject the commit if the branch moved since the client staged against it
+                if let Some(expected_parent_id) = &data.expected_parent_id {
+                    if expected_parent_id != &branch.commit_id {
+                        log::debug!(
+                            "stager::commit branch {} is at {} but expected {}",
+                            branch_name,
+                            branch.commit_id,
+                            expected_parent_id
+                        );
+                        release_idempotency_key(&repo, user_id, branch_name, &data);
+                        return Ok(HttpResponse::Conflict().json(StatusMessage::error(format!(
+                            "Branch {} has moved to commit {}, expected {}",
+                            branch_name, branch.commit_id, expected_parent_id
+                        ))));
+                    }
+                }
+
                 let branch_repo =
                     index::remote_dir_stager::init_or_get(&repo, &branch, user_id).unwrap();
                 match index::remote_dir_stager::commit(&repo, &branch_repo, &branch, &data, user_id)
                 {
                     Ok(commit) => {
                         log::debug!("stager::commit ✅ success! commit {:?}", commit);
+                        if let Some(key) = &data.idempotency_key {
+                            if let Err(err) = idempotency_keys::record(
+                                &repo,
+                                user_id,
+                                branch_name,
+                                key,
+                                &commit.id,
+                            ) {
+                                log::error!("Could not record idempotency key {key}: {:?}", err);
+                            }
+                        }
 
                         // Clone the commit so we can move it into the thread
                         let ret_commit = commit.clone();
@@ -719,6 +1232,7 @@ pub async fn commit(req: HttpRequest, body: String) -> Result<HttpResponse, Erro
                     }
                     Err(err) => {
                         log::error!("unable to commit branch {:?}. Err: {}", branch_name, err);
+                        release_idempotency_key(&repo, user_id, branch_name, &data);
                         Ok(HttpResponse::UnprocessableEntity()
                             .json(StatusMessage::error(format!("{err:?}"))))
                     }
@@ -747,6 +1261,20 @@ pub async fn commit(req: HttpRequest, body: String) -> Result<HttpResponse, Erro
     }
 }
 
+// Free the key when the commit was not made, so the client can retry with it
+fn release_idempotency_key(
+    repo: &LocalRepository,
+    user_id: &str,
+    branch_name: &str,
+    data: &NewCommitBody,
+) {
+    if let Some(key) = &data.idempotency_key {
+        if let Err(err) = idempotency_keys::release(repo, user_id, branch_name, key) {
+            log::error!("Could not release idempotency key {key}: {:?}", err);
+        }
+    }
+}
+
 pub async fn clear_modifications(req: HttpRequest) -> HttpResponse {
     let app_data = app_data(&req).unwrap();
     let namespace: &str = req.match_info().get("namespace").unwrap();
@@ -952,7 +1480,7 @@ pub async fn get_staged_df(
     opts = df_opts_query::parse_opts(&query, &mut opts);
 
     opts.page = Some(query.page.unwrap_or(constants::DEFAULT_PAGE_NUM));
-    opts.page_size = Some(query.page_size.unwrap_or(constants::DEFAULT_PAGE_SIZE));
+    opts.page_size = Some(query.page_size.unwrap_or(repo.df_page_size()));
 
     if index::remote_df_stager::dataset_is_indexed(
         &repo,
@@ -969,6 +1497,7 @@ pub async fn get_staged_df(
 
         let df =
             index::remote_df_stager::query_staged_df(&repo, &entry, &branch, &identifier, &opts)?;
+        let df = column_access::visible_df(&req, &repo, df)?;
 
         let df_schema = Schema::from_polars(&df.schema());
 
@@ -1193,3 +1722,550 @@ fn get_dir_status_for_branch(
     };
     Ok(HttpResponse::Ok().json(response))
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use actix_web::body::to_bytes;
+    use actix_web::{http, web, App};
+
+    use liboxen::api;
+    use liboxen::core::index;
+    use liboxen::error::OxenError;
+    use liboxen::model::{LocalRepository, NewCommitBody};
+    use liboxen::util;
+    use liboxen::view::http::MSG_PAYLOAD_TOO_LARGE;
+    use liboxen::view::{
+        ChunkedUploadRequest, ChunkedUploadResponse, CommitResponse, JsonDataFrameViewResponse,
+        StagingConflictResponse, StatusMessageDescription,
+    };
+
+    use crate::app_data::{OxenAppData, UploadLimits};
+    use crate::controllers;
+    use crate::test;
+
+    const USER_ID: &str = "test-user";
+
+    fn commit_request(
+        sync_dir: &Path,
+        namespace: &str,
+        name: &str,
+        branch: &str,
+    ) -> actix_web::HttpRequest {
+        let uri = format!("/oxen/{namespace}/{name}/staging/{USER_ID}/commit/{branch}");
+        actix_web::test::TestRequest::with_uri(&uri)
+            .app_data(OxenAppData::new(sync_dir.to_path_buf(), test::init_queue()))
+            .param("namespace", namespace.to_string())
+            .param("repo_name", name.to_string())
+            .param("identifier", USER_ID)
+            .param("branch", branch.to_string())
+            .to_http_request()
+    }
+
+    fn stage_readme(repo: &LocalRepository) -> Result<(), OxenError> {
+        stage_file(repo, "Readme.md", None)
+    }
+
+    // Writes "Hello World" if there is no src file to copy
+    fn stage_file(
+        repo: &LocalRepository,
+        file_name: &str,
+        src: Option<&Path>,
+    ) -> Result<(), OxenError> {
+        let branch = api::local::branches::current_branch(repo)?.unwrap();
+        let branch_dir = index::remote_dir_stager::branch_staging_dir(repo, &branch, USER_ID);
+        let full_path = branch_dir.join(file_name);
+        util::fs::create_dir_all(&branch_dir)?;
+        match src {
+            Some(src) => util::fs::copy(src, &full_path)?,
+            None => util::fs::write_to_path(&full_path, "Hello World")?,
+        }
+        let branch_repo = index::remote_dir_stager::init_or_get(repo, &branch, USER_ID)?;
+        index::remote_dir_stager::stage_file(repo, &branch_repo, &branch, USER_ID, &full_path)?;
+        Ok(())
+    }
+
+    fn commit_body(expected_parent_id: &str) -> Result<String, OxenError> {
+        let body = NewCommitBody {
+            message: String::from("Add readme"),
+            author: String::from("Test User"),
+            email: String::from("test@oxen.ai"),
+            expected_parent_id: Some(expected_parent_id.to_string()),
+            idempotency_key: None,
+        };
+        Ok(serde_json::to_string(&body)?)
+    }
+
+    #[actix_web::test]
+    async fn test_controllers_stager_commit_rejects_stale_parent() -> Result<(), OxenError> {
+        let sync_dir = test::get_sync_dir()?;
+        let namespace = "Testing-Namespace";
+        let name = "Testing-Stager-Commit";
+        let repo = test::create_local_repo(&sync_dir, namespace, name)?;
+        let branch = api::local::branches::current_branch(&repo)?.unwrap();
+        stage_readme(&repo)?;
+
+        // Client thinks the branch is somewhere else
+        let req = commit_request(&sync_dir, namespace, name, &branch.name);
+        let resp = controllers::stager::commit(req, commit_body("stale-commit-id")?)
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), http::StatusCode::CONFLICT);
+        let head = api::local::branches::get_by_name(&repo, &branch.name)?.unwrap();
+        assert_eq!(head.commit_id, branch.commit_id);
+
+        // Client is up to date with the branch head
+        let req = commit_request(&sync_dir, namespace, name, &branch.name);
+        let resp = controllers::stager::commit(req, commit_body(&branch.commit_id)?)
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        let head = api::local::branches::get_by_name(&repo, &branch.name)?.unwrap();
+        assert_ne!(head.commit_id, branch.commit_id);
+
+        // cleanup
+        util::fs::remove_dir_all(sync_dir)?;
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn test_controllers_stager_commit_with_idempotency_key_commits_once(
+    ) -> Result<(), OxenError> {
+        let sync_dir = test::get_sync_dir()?;
+        let namespace = "Testing-Namespace";
+        let name = "Testing-Stager-Commit-Idempotent";
+        let repo = test::create_local_repo(&sync_dir, namespace, name)?;
+        let branch = api::local::branches::current_branch(&repo)?.unwrap();
+        let og_num_commits = api::local::commits::list(&repo)?.len();
+        stage_readme(&repo)?;
+
+        let body = serde_json::to_string(&NewCommitBody {
+            message: String::from("Add readme"),
+            author: String::from("Test User"),
+            email: String::from("test@oxen.ai"),
+            expected_parent_id: Some(branch.commit_id.to_owned()),
+            idempotency_key: Some(String::from("add-readme-1")),
+        })?;
+
+        let mut commit_ids: Vec<String> = vec![];
+        // The retry still says the old parent, but the key wins over the stale parent check
+        for _ in 0..2 {
+            let req = commit_request(&sync_dir, namespace, name, &branch.name);
+            let resp = controllers::stager::commit(req, body.to_owned())
+                .await
+                .unwrap();
+            assert_eq!(resp.status(), http::StatusCode::OK);
+            let bytes = to_bytes(resp.into_body()).await.unwrap();
+            let response: CommitResponse = serde_json::from_slice(&bytes)?;
+            commit_ids.push(response.commit.id);
+        }
+
+        assert_eq!(commit_ids[0], commit_ids[1]);
+        assert_eq!(api::local::commits::list(&repo)?.len(), og_num_commits + 1);
+        let head = api::local::branches::get_by_name(&repo, &branch.name)?.unwrap();
+        assert_eq!(head.commit_id, commit_ids[0]);
+
+        // cleanup
+        util::fs::remove_dir_all(sync_dir)?;
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn test_controllers_stager_get_file_resize_and_convert() -> Result<(), OxenError> {
+        test::init_test_env();
+
+        let sync_dir = test::get_sync_dir()?;
+        let namespace = "Testing-Namespace";
+        let name = "Testing-Stager-Get-File";
+        let repo = test::create_local_repo(&sync_dir, namespace, name)?;
+        stage_file(&repo, "notes.txt", None)?;
+        stage_file(
+            &repo,
+            "image.jpeg",
+            Some(
+                &Path::new("data")
+                    .join("test")
+                    .join("images")
+                    .join("cat_1.jpg"),
+            ),
+        )?;
+
+        let app = actix_web::test::init_service(
+            App::new()
+                .app_data(OxenAppData::new(sync_dir.clone(), test::init_queue()))
+                .route(
+                    "/oxen/{namespace}/{repo_name}/staging/{identifier}/file/{resource:.*}",
+                    web::get().to(controllers::stager::get_file),
+                ),
+        )
+        .await;
+
+        // Resizing a text file is a bad request
+        let uri =
+            format!("/oxen/{namespace}/{name}/staging/{USER_ID}/file/main/notes.txt?width=32");
+        let req = actix_web::test::TestRequest::get().uri(&uri).to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::BAD_REQUEST);
+
+        // Converting a real image changes the content type
+        let uri = format!(
+            "/oxen/{namespace}/{name}/staging/{USER_ID}/file/main/image.jpeg?width=32&format=png"
+        );
+        let req = actix_web::test::TestRequest::get().uri(&uri).to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        let content_type = resp.headers().get(http::header::CONTENT_TYPE).unwrap();
+        assert_eq!(content_type.to_str().unwrap(), "image/png");
+
+        // cleanup
+        util::fs::remove_dir_all(sync_dir)?;
+
+        Ok(())
+    }
+
+    fn add_file_request(
+        namespace: &str,
+        name: &str,
+        identifier: &str,
+        force: bool,
+        contents: &str,
+    ) -> actix_http::Request {
+        let boundary = "oxen-test-boundary";
+        let body = format!(
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"notes.txt\"\r\nContent-Type: text/plain\r\n\r\n{contents}\r\n--{boundary}--\r\n"
+        );
+        let uri = format!(
+            "/oxen/{namespace}/{name}/staging/{identifier}/entries/main/data?force={force}"
+        );
+        actix_web::test::TestRequest::post()
+            .uri(&uri)
+            .insert_header((
+                http::header::CONTENT_TYPE,
+                format!("multipart/form-data; boundary={boundary}"),
+            ))
+            .set_payload(body)
+            .to_request()
+    }
+
+    #[actix_web::test]
+    async fn test_controllers_stager_add_file_conflicts_across_identifiers() -> Result<(), OxenError>
+    {
+        test::init_test_env();
+
+        let sync_dir = test::get_sync_dir()?;
+        let namespace = "Testing-Namespace";
+        let name = "Testing-Stager-Add-Conflict";
+        let repo = test::create_local_repo(&sync_dir, namespace, name)?;
+
+        let app = actix_web::test::init_service(
+            App::new()
+                .app_data(OxenAppData::new(sync_dir.clone(), test::init_queue()))
+                .route(
+                    "/oxen/{namespace}/{repo_name}/staging/{identifier}/entries/{resource:.*}",
+                    web::post().to(controllers::stager::add_file),
+                ),
+        )
+        .await;
+
+        // The first identifier stages the path
+        let req = add_file_request(namespace, name, "user-a", false, "from a");
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::OK);
+
+        // The same identifier can keep changing it
+        let req = add_file_request(namespace, name, "user-a", false, "from a again");
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::OK);
+
+        // A second identifier staging the same path gets a conflict
+        let req = add_file_request(namespace, name, "user-b", false, "from b");
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::CONFLICT);
+        let body: StagingConflictResponse = actix_web::test::read_body_json(resp).await;
+        assert_eq!(body.conflicts.len(), 1);
+        assert_eq!(body.conflicts[0].path, Path::new("data").join("notes.txt"));
+        assert_eq!(body.conflicts[0].identifiers, vec!["user-a"]);
+
+        // Unless it forces the change
+        let req = add_file_request(namespace, name, "user-b", true, "from b");
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::OK);
+
+        // A rejected upload leaves what the identifier already staged untouched
+        let req = add_file_request(namespace, name, "user-a", false, "from a, rejected");
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::CONFLICT);
+        let branch = api::local::branches::current_branch(&repo)?.unwrap();
+        let staged_path = index::remote_dir_stager::branch_staging_dir(&repo, &branch, "user-a")
+            .join("data")
+            .join("notes.txt");
+        assert_eq!(util::fs::read_from_path(&staged_path)?, "from a again");
+
+        // cleanup
+        util::fs::remove_dir_all(sync_dir)?;
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn test_controllers_stager_chunked_upload_is_owned_and_contained() -> Result<(), OxenError>
+    {
+        test::init_test_env();
+
+        let sync_dir = test::get_sync_dir()?;
+        let namespace = "Testing-Namespace";
+        let name = "Testing-Stager-Chunked-Owner";
+        let repo = test::create_local_repo(&sync_dir, namespace, name)?;
+        let branch = api::local::branches::current_branch(&repo)?.unwrap();
+
+        let app = actix_web::test::init_service(
+            App::new()
+                .app_data(OxenAppData::new(sync_dir.clone(), test::init_queue()))
+                .route(
+                    "/oxen/{namespace}/{repo_name}/staging/{identifier}/chunked_uploads",
+                    web::post().to(controllers::stager::create_chunked_upload),
+                )
+                .route(
+                    "/oxen/{namespace}/{repo_name}/staging/{identifier}/chunked_uploads/{upload_id}/parts/{part_num}",
+                    web::put().to(controllers::stager::upload_chunked_part),
+                )
+                .route(
+                    "/oxen/{namespace}/{repo_name}/staging/{identifier}/chunked_uploads/{upload_id}/complete",
+                    web::post().to(controllers::stager::complete_chunked_upload),
+                ),
+        )
+        .await;
+
+        let contents = "Hello World";
+        let upload = |directory: &str| ChunkedUploadRequest {
+            branch: branch.name.to_owned(),
+            directory: directory.to_string(),
+            file_name: String::from("notes.txt"),
+            total_size: contents.len() as u64,
+            total_chunks: 1,
+            hash: util::hasher::hash_buffer(contents.as_bytes()),
+        };
+        let base = format!("/oxen/{namespace}/{name}/staging");
+
+        // More parts than bytes is rejected before any part uris are built
+        let mut too_many_parts = upload("data");
+        too_many_parts.total_chunks = usize::MAX;
+        let req = actix_web::test::TestRequest::post()
+            .uri(&format!("{base}/user-a/chunked_uploads"))
+            .set_json(too_many_parts)
+            .to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::BAD_REQUEST);
+
+        // Directories that leave the staging dir are rejected up front
+        for directory in ["../../escape", "/tmp/escape", "data/../../escape"] {
+            let req = actix_web::test::TestRequest::post()
+                .uri(&format!("{base}/user-a/chunked_uploads"))
+                .set_json(upload(directory))
+                .to_request();
+            let resp = actix_web::test::call_service(&app, req).await;
+            assert_eq!(resp.status(), http::StatusCode::BAD_REQUEST);
+        }
+
+        let req = actix_web::test::TestRequest::post()
+            .uri(&format!("{base}/user-a/chunked_uploads"))
+            .set_json(upload("data"))
+            .to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        let created: ChunkedUploadResponse = actix_web::test::read_body_json(resp).await;
+        let upload_id = created.upload_id;
+
+        // Another identifier cannot touch the upload
+        let req = actix_web::test::TestRequest::put()
+            .uri(&format!(
+                "{base}/user-b/chunked_uploads/{upload_id}/parts/0"
+            ))
+            .set_payload("Evil World")
+            .to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::NOT_FOUND);
+        let req = actix_web::test::TestRequest::post()
+            .uri(&format!(
+                "{base}/user-b/chunked_uploads/{upload_id}/complete"
+            ))
+            .to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::NOT_FOUND);
+
+        // The owner can finish it
+        let req = actix_web::test::TestRequest::put()
+            .uri(&format!(
+                "{base}/user-a/chunked_uploads/{upload_id}/parts/0"
+            ))
+            .set_payload(contents)
+            .to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        let req = actix_web::test::TestRequest::post()
+            .uri(&format!(
+                "{base}/user-a/chunked_uploads/{upload_id}/complete"
+            ))
+            .to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        let staging_dir = index::remote_dir_stager::branch_staging_dir(&repo, &branch, "user-a");
+        assert_eq!(
+            util::fs::read_from_path(staging_dir.join("data").join("notes.txt"))?,
+            contents
+        );
+
+        // Another identifier completing an upload to the same path gets a conflict
+        let req = actix_web::test::TestRequest::post()
+            .uri(&format!("{base}/user-b/chunked_uploads"))
+            .set_json(upload("data"))
+            .to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        let created: ChunkedUploadResponse = actix_web::test::read_body_json(resp).await;
+        let upload_id = created.upload_id;
+        let req = actix_web::test::TestRequest::put()
+            .uri(&format!(
+                "{base}/user-b/chunked_uploads/{upload_id}/parts/0"
+            ))
+            .set_payload(contents)
+            .to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        let req = actix_web::test::TestRequest::post()
+            .uri(&format!(
+                "{base}/user-b/chunked_uploads/{upload_id}/complete"
+            ))
+            .to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::CONFLICT);
+        let body: StagingConflictResponse = actix_web::test::read_body_json(resp).await;
+        assert_eq!(body.conflicts.len(), 1);
+        assert_eq!(body.conflicts[0].path, Path::new("data").join("notes.txt"));
+        assert_eq!(body.conflicts[0].identifiers, vec!["user-a"]);
+
+        // Unless it forces the change
+        let req = actix_web::test::TestRequest::post()
+            .uri(&format!(
+                "{base}/user-b/chunked_uploads/{upload_id}/complete?force=true"
+            ))
+            .to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::OK);
+
+        // cleanup
+        util::fs::remove_dir_all(sync_dir)?;
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn test_controllers_stager_add_file_over_upload_limit() -> Result<(), OxenError> {
+        test::init_test_env();
+
+        let sync_dir = test::get_sync_dir()?;
+        let namespace = "Testing-Namespace";
+        let name = "Testing-Stager-Add-Limit";
+        let repo = test::create_local_repo(&sync_dir, namespace, name)?;
+        let branch = api::local::branches::current_branch(&repo)?.unwrap();
+
+        let limits = UploadLimits {
+            max_file_size: Some(16),
+            max_request_size: None,
+        };
+        let app = actix_web::test::init_service(
+            App::new()
+                .app_data(
+                    OxenAppData::new(sync_dir.clone(), test::init_queue())
+                        .with_upload_limits(limits),
+                )
+                .route(
+                    "/oxen/{namespace}/{repo_name}/staging/{identifier}/entries/{resource:.*}",
+                    web::post().to(controllers::stager::add_file),
+                ),
+        )
+        .await;
+
+        // Past the limit is rejected and the partial file is cleaned up
+        let contents = "x".repeat(64);
+        let req = add_file_request(namespace, name, USER_ID, false, &contents);
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::PAYLOAD_TOO_LARGE);
+        let body: StatusMessageDescription = actix_web::test::read_body_json(resp).await;
+        assert_eq!(body.status_message, MSG_PAYLOAD_TOO_LARGE);
+        assert!(body.status_description.contains("16 bytes"));
+        let staging_dir = index::remote_dir_stager::branch_staging_dir(&repo, &branch, USER_ID);
+        assert!(!staging_dir.join("data").join("notes.txt").exists());
+
+        // Under the limit is staged as usual
+        let req = add_file_request(namespace, name, USER_ID, false, "small");
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        assert!(staging_dir.join("data").join("notes.txt").exists());
+
+        // An upload over the limit does not touch the file that is already staged
+        let req = add_file_request(namespace, name, USER_ID, false, &contents);
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(
+            util::fs::read_from_path(staging_dir.join("data").join("notes.txt"))?,
+            "small"
+        );
+
+        // cleanup
+        util::fs::remove_dir_all(sync_dir)?;
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn test_controllers_stager_get_staged_df_hides_denied_columns() -> Result<(), OxenError> {
+        let sync_dir = test::get_sync_dir()?;
+        let namespace = "Testing-Namespace";
+        let name = "Testing-Staged-Column-Access";
+        let (repo, analyst_token, admin_token) =
+            test::create_column_access_repo(&sync_dir, namespace, name)?;
+
+        let identifier = "user-a";
+        let branch = api::local::branches::current_branch(&repo)?.unwrap();
+        index::remote_dir_stager::init_or_get(&repo, &branch, identifier)?;
+        index::remote_df_stager::index_dataset(&repo, &branch, Path::new("users.csv"), identifier)?;
+
+        let app = actix_web::test::init_service(
+            App::new()
+                .app_data(OxenAppData::new(sync_dir.clone(), test::init_queue()))
+                .route(
+                    "/oxen/{namespace}/{repo_name}/staging/{identifier}/df/{resource:.*}",
+                    web::get().to(controllers::stager::get_staged_df),
+                ),
+        )
+        .await;
+
+        let mut seen: Vec<bool> = vec![];
+        for token in [analyst_token, admin_token] {
+            let req = actix_web::test::TestRequest::get()
+                .uri(&format!(
+                    "/oxen/{namespace}/{name}/staging/{identifier}/df/main/users.csv"
+                ))
+                .insert_header(("Authorization", format!("Bearer {token}")))
+                .to_request();
+            let resp = actix_web::test::call_service(&app, req).await;
+            assert_eq!(resp.status(), http::StatusCode::OK);
+            let body: JsonDataFrameViewResponse = actix_web::test::read_body_json(resp).await;
+            let view = &body.data_frame.view;
+            let row = view.data.as_array().unwrap()[0].as_object().unwrap();
+            let has_email =
+                row.contains_key("email") || view.schema.fields.iter().any(|f| f.name == "email");
+            seen.push(has_email);
+        }
+        assert_eq!(seen, vec![false, true]);
+
+        // cleanup
+        util::fs::remove_dir_all(sync_dir)?;
+
+        Ok(())
+    }
+}
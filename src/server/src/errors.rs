@@ -19,6 +19,8 @@ pub enum OxenHttpError {
     NotQueryable,
     DatasetNotIndexed,
     UpdateRequired(StringError),
+    PayloadTooLarge(StringError),
+    Forbidden(StringError),
 
     // Translate OxenError to OxenHttpError
     InternalOxenError(OxenError),
@@ -80,6 +82,11 @@ impl error::ResponseError for OxenHttpError {
             }
             OxenHttpError::BadRequest(desc) => HttpResponse::BadRequest()
                 .json(StatusMessageDescription::bad_request(desc.to_string())),
+            OxenHttpError::PayloadTooLarge(desc) => HttpResponse::PayloadTooLarge().json(
+                StatusMessageDescription::payload_too_large(desc.to_string()),
+            ),
+            OxenHttpError::Forbidden(desc) => HttpResponse::Forbidden()
+                .json(StatusMessageDescription::forbidden(desc.to_string())),
             OxenHttpError::SQLParseError(query) => {
                 HttpResponse::BadRequest().json(SQLParseError::new(query.to_string()))
             }
@@ -223,6 +230,12 @@ impl error::ResponseError for OxenHttpError {
                         HttpResponse::BadRequest()
                             .json(StatusMessageDescription::bad_request(format!("{}", desc)))
                     }
+                    OxenError::HashMismatch(desc) => {
+                        log::error!("Hash mismatch: {}", desc);
+
+                        HttpResponse::BadRequest()
+                            .json(StatusMessageDescription::hash_mismatch(format!("{}", desc)))
+                    }
                     OxenError::DUCKDB(error) => {
                         log::error!("DuckDB error: {}", error);
 
@@ -260,6 +273,8 @@ impl error::ResponseError for OxenHttpError {
             OxenHttpError::NotQueryable => StatusCode::BAD_REQUEST,
             OxenHttpError::DatasetNotIndexed => StatusCode::BAD_REQUEST,
             OxenHttpError::UpdateRequired(_) => StatusCode::UPGRADE_REQUIRED,
+            OxenHttpError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            OxenHttpError::Forbidden(_) => StatusCode::FORBIDDEN,
             OxenHttpError::ActixError(_) => StatusCode::INTERNAL_SERVER_ERROR,
             OxenHttpError::SerdeError(_) => StatusCode::BAD_REQUEST,
             OxenHttpError::RedisError(_) => StatusCode::INTERNAL_SERVER_ERROR,
@@ -269,6 +284,7 @@ impl error::ResponseError for OxenHttpError {
                 OxenError::RevisionNotFound(_) => StatusCode::NOT_FOUND,
                 OxenError::InvalidSchema(_) => StatusCode::BAD_REQUEST,
                 OxenError::ParsingError(_) => StatusCode::BAD_REQUEST,
+                OxenError::HashMismatch(_) => StatusCode::BAD_REQUEST,
                 _ => StatusCode::INTERNAL_SERVER_ERROR,
             },
         }
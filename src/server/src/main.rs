@@ -1,7 +1,5 @@
 use liboxen::config::UserConfig;
 
-use liboxen::core::cache::cacher_status::CacherStatus;
-use liboxen::core::cache::commit_cacher;
 use liboxen::model::User;
 
 pub mod app_data;
@@ -31,10 +29,11 @@ use std::io::Write;
 
 use std::path::{Path, PathBuf};
 use std::time::Duration;
-use tokio::time::sleep;
+use tokio_util::sync::CancellationToken;
 
+use crate::middleware::rate_limit::{InMemoryRateLimiter, RateLimit, RateLimiter};
+use crate::middleware::request_id::RequestId;
 use crate::queues::{InMemoryTaskQueue, RedisTaskQueue, TaskQueue};
-use crate::tasks::{Runnable, Task};
 
 const VERSION: &str = liboxen::constants::OXEN_VERSION;
 
@@ -49,11 +48,16 @@ const INVALID_PORT_MSG: &str = "Port must a valid number between 0-65535";
 async fn main() -> std::io::Result<()> {
     env_logger::Builder::from_env(Env::default().default_filter_or("info,debug"))
         .format(|buf, record| {
+            // Tag the lines logged while handling a request, or a task it queued
+            let request_id = middleware::request_id::current()
+                .map(|id| format!(" [request_id={id}]"))
+                .unwrap_or_default();
             writeln!(
                 buf,
-                "{} [{}] - {}: {}",
+                "{} [{}]{} - {}: {}",
                 chrono::Local::now().format("%Y-%m-%dT%H:%M:%S%.3f"),
                 record.level(),
+                request_id,
                 record.target(),
                 record.args()
             )
@@ -67,57 +71,18 @@ async fn main() -> std::io::Result<()> {
         Err(_) => String::from("data"),
     };
 
-    // Polling worker setup
-    async fn poll_queue(mut queue: TaskQueue) {
-        log::debug!("Starting queue poller");
-        loop {
-            match queue.pop() {
-                Some(task) => {
-                    log::debug!("Got queue item: {:?}", task);
-                    let result = std::panic::catch_unwind(|| {
-                        task.run();
-                    });
-                    if let Err(e) = result {
-                        log::error!("Error or panic processing commit {:?}", e);
-                        // Set the task to failed
-                        match task {
-                            Task::PostPushComplete(post_push_complete) => {
-                                let repo = post_push_complete.repo;
-                                let commit = post_push_complete.commit;
-
-                                match commit_cacher::set_all_cachers_status(
-                                    &repo,
-                                    &commit,
-                                    CacherStatus::failed("Panic in commit cache"),
-                                ) {
-                                    Ok(_) => {
-                                        log::debug!("Set all cachers to failed status");
-                                    }
-                                    Err(e) => {
-                                        log::error!(
-                                            "Error setting all cachers to failed status: {:?}",
-                                            e
-                                        );
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-                None => {
-                    // log::debug!("No queue items found, sleeping");
-                    sleep(Duration::from_millis(1000)).await;
-                }
-            }
-        }
-    }
-
     // If redis connection is available, use redis queue, else in-memory
     pub fn init_queue() -> TaskQueue {
         match helpers::get_redis_connection() {
             Ok(pool) => {
                 println!("connecting to redis established, initializing queue");
-                TaskQueue::Redis(RedisTaskQueue { pool })
+                let mut queue = RedisTaskQueue::new(pool);
+                match queue.migrate_legacy_queue() {
+                    Ok(0) => {}
+                    Ok(n) => println!("Moved {n} tasks from the legacy commit queue"),
+                    Err(err) => log::error!("Could not migrate the legacy commit queue: {err:?}"),
+                }
+                TaskQueue::Redis(queue)
             }
             Err(_) => {
                 println!("Failed to connect to Redis. Falling back to in-memory queue.");
@@ -159,6 +124,42 @@ async fn main() -> std::io::Result<()> {
                         .short('a')
                         .help("Start the server with token-based authentication enforced")
                         .action(clap::ArgAction::SetTrue),
+                )
+                .arg(
+                    Arg::new("rate-limit")
+                        .long("rate-limit")
+                        .help("Max requests per valid bearer token, or per ip otherwise, in each --rate-limit-window. Off by default")
+                        .value_parser(clap::value_parser!(u64))
+                        .action(clap::ArgAction::Set),
+                )
+                .arg(
+                    Arg::new("trusted-proxy")
+                        .long("trusted-proxy")
+                        .help("Ip of a reverse proxy whose X-Forwarded-For header is used to rate limit by client ip. Can be passed multiple times")
+                        .value_parser(clap::value_parser!(std::net::IpAddr))
+                        .action(clap::ArgAction::Append),
+                )
+                .arg(
+                    Arg::new("rate-limit-window")
+                        .long("rate-limit-window")
+                        .default_value("60")
+                        .help("Length of the rate limit window in seconds")
+                        .value_parser(clap::value_parser!(u64))
+                        .action(clap::ArgAction::Set),
+                )
+                .arg(
+                    Arg::new("max-upload-file-size")
+                        .long("max-upload-file-size")
+                        .help("Max bytes of a single file uploaded to the staging area, larger files get a 413. Off by default")
+                        .value_parser(clap::value_parser!(u64))
+                        .action(clap::ArgAction::Set),
+                )
+                .arg(
+                    Arg::new("max-upload-request-size")
+                        .long("max-upload-request-size")
+                        .help("Max bytes of all the files in one upload to the staging area, larger requests get a 413. Off by default")
+                        .value_parser(clap::value_parser!(u64))
+                        .action(clap::ArgAction::Set),
                 ),
         )
         .subcommand(
@@ -188,6 +189,12 @@ async fn main() -> std::io::Result<()> {
                         .default_missing_value("always")
                         .help("Where to write the output config file to give to the user")
                         .action(clap::ArgAction::Set),
+                )
+                .arg(
+                    Arg::new("scope")
+                        .long("scope")
+                        .help("Scope of the token, limits the data frame columns it can read to the [column_access.<scope>] of each repository config")
+                        .action(clap::ArgAction::Set),
                 ),
         );
     let matches = command.get_matches();
@@ -204,16 +211,66 @@ async fn main() -> std::io::Result<()> {
                     println!("Running on {host}:{port}");
                     println!("Syncing to directory: {sync_dir}");
                     let enable_auth = sub_matches.get_flag("auth");
+                    let rate_limit = sub_matches.get_one::<u64>("rate-limit").copied();
+                    let rate_limit_window = sub_matches
+                        .get_one::<u64>("rate-limit-window")
+                        .copied()
+                        .unwrap_or(60);
+                    let trusted_proxies: Vec<std::net::IpAddr> = sub_matches
+                        .get_many::<std::net::IpAddr>("trusted-proxy")
+                        .map(|proxies| proxies.copied().collect())
+                        .unwrap_or_default();
+                    let limiter = rate_limit.map(|max_requests| {
+                        println!(
+                            "Rate limiting to {max_requests} requests every {rate_limit_window}s"
+                        );
+                        RateLimiter::new(max_requests, Duration::from_secs(rate_limit_window))
+                    });
+                    // Read once so the rate limiter can check tokens without touching disk
+                    let secret_key: Option<std::sync::Arc<str>> = match limiter {
+                        Some(_) => {
+                            match auth::access_keys::get_or_create_secret_key(Path::new(&sync_dir))
+                            {
+                                Ok(secret_key) => Some(secret_key.into()),
+                                Err(err) => {
+                                    log::error!("Could not read the secret key for rate limiting, limiting by ip: {err}");
+                                    None
+                                }
+                            }
+                        }
+                        None => None,
+                    };
 
                     log::debug!("initializing queue");
                     let queue = init_queue();
                     log::debug!("initialized queue");
-                    let data = app_data::OxenAppData::new(PathBuf::from(sync_dir), queue.clone());
+                    let upload_limits = app_data::UploadLimits {
+                        max_file_size: sub_matches.get_one::<u64>("max-upload-file-size").copied(),
+                        max_request_size: sub_matches
+                            .get_one::<u64>("max-upload-request-size")
+                            .copied(),
+                    };
+                    let data = app_data::OxenAppData::new(PathBuf::from(sync_dir), queue.clone())
+                        .with_upload_limits(upload_limits);
                     // Poll for post-commit tasks in background
                     log::debug!("initialized app data, spawning polling worker");
-                    tokio::spawn(async move { poll_queue(queue.clone()).await });
+                    let shutdown = CancellationToken::new();
+                    let poller =
+                        tokio::spawn(queues::poller::poll_queue(queue.clone(), shutdown.clone()));
 
-                    HttpServer::new(move || {
+                    let result = HttpServer::new(move || {
+                        // Condition needs a middleware even when off, it never gets called
+                        let rate_limit = RateLimit::new(limiter.clone().unwrap_or_else(|| {
+                            RateLimiter::InMemory(InMemoryRateLimiter::new(
+                                u64::MAX,
+                                Duration::from_secs(rate_limit_window),
+                            ))
+                        }))
+                        .with_trusted_proxies(trusted_proxies.clone());
+                        let rate_limit = match &secret_key {
+                            Some(secret_key) => rate_limit.with_secret_key(secret_key.clone()),
+                            None => rate_limit,
+                        };
                         App::new()
                             .app_data(data.clone())
                             .route("/api/version", web::get().to(controllers::version::index))
@@ -236,16 +293,26 @@ async fn main() -> std::io::Result<()> {
                             )
                             .wrap(Condition::new(
                                 enable_auth,
-                                HttpAuthentication::bearer(auth::validator::validate),
+                                HttpAuthentication::with_fn(auth::validator::validate),
                             ))
+                            .wrap(Condition::new(limiter.is_some(), rate_limit))
                             .service(web::scope("/api/repos").configure(routes::config))
                             .default_service(web::route().to(controllers::not_found::index))
                             .wrap(Logger::default())
                             .wrap(Logger::new("user agent is %a %{User-Agent}i"))
+                            .wrap(RequestId)
                     })
                     .bind((host.to_owned(), port))?
                     .run()
-                    .await
+                    .await;
+
+                    // The server stops on SIGINT / SIGTERM, let the poller finish the task it is running
+                    log::info!("Server stopped, waiting for the queue poller to finish");
+                    shutdown.cancel();
+                    if let Err(err) = poller.await {
+                        log::error!("Queue poller did not shut down cleanly: {:?}", err);
+                    }
+                    result
                 }
                 _ => {
                     eprintln!("{START_SERVER_USAGE}");
@@ -267,7 +334,8 @@ async fn main() -> std::io::Result<()> {
                             name: name.to_string(),
                             email: email.to_string(),
                         };
-                        match keygen.create(&new_user) {
+                        let scope = sub_matches.get_one::<String>("scope").map(String::as_str);
+                        match keygen.create_with_scope(&new_user, scope) {
                             Ok((user, token)) => {
                                 let cfg = UserConfig::from_user(&user);
                                 match cfg.save(Path::new(output)) {
@@ -1 +1,2 @@
-
+pub mod rate_limit;
+pub mod request_id;
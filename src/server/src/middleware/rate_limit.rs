@@ -0,0 +1,435 @@
+//! Limits how many requests each valid bearer token, or ip for every other request,
+//! can make in a fixed window. Counters live in redis when it is available so
+//! that multiple servers share them, otherwise in memory.
+//!
+//! The ip is the peer address of the connection. Forwarded headers are only
+//! honored when the peer is one of the configured trusted proxies.
+//!
+
+use std::collections::HashMap;
+use std::future::{ready, Ready};
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use actix_web::body::EitherBody;
+use actix_web::dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::header;
+use actix_web::{Error, HttpResponse};
+use futures_util::future::LocalBoxFuture;
+
+use liboxen::constants::RATE_LIMIT_KEY_PREFIX;
+use liboxen::util;
+use liboxen::view::StatusMessage;
+
+use crate::auth::access_keys;
+use crate::helpers;
+
+// Drop the in memory counters from past windows once we are tracking this many keys
+const MAX_IN_MEMORY_KEYS: usize = 10_000;
+
+#[derive(Clone)]
+pub enum RateLimiter {
+    InMemory(InMemoryRateLimiter),
+    Redis(RedisRateLimiter),
+}
+
+impl RateLimiter {
+    /// Use redis if a connection is available, else count in memory
+    pub fn new(max_requests: u64, window: Duration) -> RateLimiter {
+        match helpers::get_redis_connection() {
+            Ok(pool) => {
+                println!("connecting to redis established, initializing rate limiter");
+                RateLimiter::Redis(RedisRateLimiter::new(pool, max_requests, window))
+            }
+            Err(_) => {
+                println!("Failed to connect to Redis. Falling back to in-memory rate limiter.");
+                RateLimiter::InMemory(InMemoryRateLimiter::new(max_requests, window))
+            }
+        }
+    }
+
+    /// Count a request for `key`, returning the seconds until the next window if over the limit
+    pub fn check(&self, key: &str) -> Result<(), u64> {
+        match self {
+            RateLimiter::InMemory(limiter) => limiter.check(key),
+            RateLimiter::Redis(limiter) => limiter.check(key),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct InMemoryRateLimiter {
+    max_requests: u64,
+    window: Duration,
+    // key -> (window index, requests in that window)
+    counts: Arc<Mutex<HashMap<String, (u64, u64)>>>,
+}
+
+impl InMemoryRateLimiter {
+    pub fn new(max_requests: u64, window: Duration) -> Self {
+        InMemoryRateLimiter {
+            max_requests,
+            window,
+            counts: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    fn check(&self, key: &str) -> Result<(), u64> {
+        let (window_idx, retry_after) = current_window(self.window);
+        let mut counts = self.counts.lock().unwrap();
+        if counts.len() > MAX_IN_MEMORY_KEYS {
+            counts.retain(|_, (idx, _)| *idx == window_idx);
+        }
+
+        let count = counts.entry(key.to_string()).or_insert((window_idx, 0));
+        if count.0 != window_idx {
+            *count = (window_idx, 0);
+        }
+        count.1 += 1;
+
+        if count.1 > self.max_requests {
+            Err(retry_after)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RedisRateLimiter {
+    pub pool: r2d2::Pool<redis::Client>,
+    max_requests: u64,
+    window: Duration,
+}
+
+impl RedisRateLimiter {
+    pub fn new(pool: r2d2::Pool<redis::Client>, max_requests: u64, window: Duration) -> Self {
+        RedisRateLimiter {
+            pool,
+            max_requests,
+            window,
+        }
+    }
+
+    fn check(&self, key: &str) -> Result<(), u64> {
+        let (window_idx, retry_after) = current_window(self.window);
+        let redis_key = format!("{RATE_LIMIT_KEY_PREFIX}:{key}:{window_idx}");
+
+        // Let requests through rather than take the server down with redis
+        let count = match self.incr(&redis_key) {
+            Ok(count) => count,
+            Err(err) => {
+                log::error!("Could not check rate limit in redis: {err}");
+                return Ok(());
+            }
+        };
+
+        if count > self.max_requests {
+            Err(retry_after)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn incr(&self, redis_key: &str) -> Result<u64, String> {
+        let mut conn = self.pool.get().map_err(|err| err.to_string())?;
+        let count: u64 = redis::cmd("INCR")
+            .arg(redis_key)
+            .query(&mut conn)
+            .map_err(|err| err.to_string())?;
+        if count == 1 {
+            // The counter only needs to outlive its window
+            let _: i64 = redis::cmd("EXPIRE")
+                .arg(redis_key)
+                .arg(self.window.as_secs().max(1))
+                .query(&mut conn)
+                .map_err(|err| err.to_string())?;
+        }
+        Ok(count)
+    }
+}
+
+/// The index of the fixed window we are in and the seconds until it ends
+fn current_window(window: Duration) -> (u64, u64) {
+    let window_secs = window.as_secs().max(1);
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    (now / window_secs, window_secs - (now % window_secs))
+}
+
+/// Requests are counted per valid bearer token, falling back to the client ip, so
+/// sending a made up token with each request does not get a fresh count
+fn rate_limit_key(
+    req: &ServiceRequest,
+    secret_key: Option<&str>,
+    trusted_proxies: &[IpAddr],
+) -> String {
+    let token = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(|token| token.trim())
+        .filter(|token| !token.is_empty());
+
+    let is_valid_token = |token: &&str| {
+        secret_key.is_some_and(|secret_key| access_keys::decode_claim(secret_key, token).is_some())
+    };
+    match token.filter(is_valid_token) {
+        // Hash so we do not keep raw tokens around in redis
+        Some(token) => format!("token:{}", util::hasher::hash_str(token)),
+        None => match client_ip(req, trusted_proxies) {
+            Some(ip) => format!("ip:{ip}"),
+            None => String::from("ip:unknown"),
+        },
+    }
+}
+
+/// The peer address, or when the peer is a trusted proxy the right most address in
+/// X-Forwarded-For that is not one of the trusted proxies
+fn client_ip(req: &ServiceRequest, trusted_proxies: &[IpAddr]) -> Option<IpAddr> {
+    let peer = req.peer_addr()?.ip();
+    if !trusted_proxies.contains(&peer) {
+        return Some(peer);
+    }
+
+    let forwarded: Vec<IpAddr> = req
+        .headers()
+        .get_all("x-forwarded-for")
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(|ip| ip.trim().parse().ok())
+        .collect();
+    Some(
+        forwarded
+            .into_iter()
+            .rev()
+            .find(|ip| !trusted_proxies.contains(ip))
+            .unwrap_or(peer),
+    )
+}
+
+/// Middleware that responds with 429 Too Many Requests and a Retry-After header
+/// once a client goes over the limit
+#[derive(Clone)]
+pub struct RateLimit {
+    limiter: RateLimiter,
+    secret_key: Option<Arc<str>>,
+    trusted_proxies: Vec<IpAddr>,
+}
+
+impl RateLimit {
+    pub fn new(limiter: RateLimiter) -> Self {
+        RateLimit {
+            limiter,
+            secret_key: None,
+            trusted_proxies: vec![],
+        }
+    }
+
+    /// Key the tokens were signed with, without it every request is counted by ip
+    pub fn with_secret_key(mut self, secret_key: impl Into<Arc<str>>) -> Self {
+        self.secret_key = Some(secret_key.into());
+        self
+    }
+
+    /// Proxies whose X-Forwarded-For header is used to find the client ip
+    pub fn with_trusted_proxies(mut self, trusted_proxies: Vec<IpAddr>) -> Self {
+        self.trusted_proxies = trusted_proxies;
+        self
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RateLimit
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = RateLimitMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RateLimitMiddleware {
+            service,
+            limiter: self.limiter.clone(),
+            secret_key: self.secret_key.clone(),
+            trusted_proxies: self.trusted_proxies.clone(),
+        }))
+    }
+}
+
+pub struct RateLimitMiddleware<S> {
+    service: S,
+    limiter: RateLimiter,
+    secret_key: Option<Arc<str>>,
+    trusted_proxies: Vec<IpAddr>,
+}
+
+impl<S, B> Service<ServiceRequest> for RateLimitMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let key = rate_limit_key(&req, self.secret_key.as_deref(), &self.trusted_proxies);
+        match self.limiter.check(&key) {
+            Ok(_) => {
+                let fut = self.service.call(req);
+                Box::pin(async move { Ok(fut.await?.map_into_left_body()) })
+            }
+            Err(retry_after) => {
+                log::debug!("Rate limited {key}, retry after {retry_after}s");
+                let response = HttpResponse::TooManyRequests()
+                    .insert_header((header::RETRY_AFTER, retry_after.to_string()))
+                    .json(StatusMessage::too_many_requests());
+                Box::pin(async move { Ok(req.into_response(response).map_into_right_body()) })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+    use std::time::Duration;
+
+    use actix_web::http::{header, StatusCode};
+    use actix_web::{web, App, HttpResponse};
+
+    use liboxen::error::OxenError;
+    use liboxen::model::User;
+    use liboxen::util;
+
+    use crate::app_data::OxenAppData;
+    use crate::auth::access_keys::{self, AccessKeyManager};
+    use crate::middleware::rate_limit::{InMemoryRateLimiter, RateLimit, RateLimiter};
+    use crate::test;
+
+    fn limiter(max_requests: u64) -> RateLimiter {
+        RateLimiter::InMemory(InMemoryRateLimiter::new(
+            max_requests,
+            Duration::from_secs(60),
+        ))
+    }
+
+    #[actix_web::test]
+    async fn test_rate_limit_returns_429_with_retry_after() -> Result<(), OxenError> {
+        let sync_dir = test::get_sync_dir()?;
+        let (token_a, token_b) = {
+            let keygen = AccessKeyManager::new(&sync_dir)?;
+            let user = User {
+                name: String::from("Ox"),
+                email: String::from("ox@oxen.ai"),
+            };
+            let (_, token_a) = keygen.create(&user)?;
+            let (_, token_b) = keygen.create(&user)?;
+            (token_a, token_b)
+        };
+
+        let app = actix_web::test::init_service(
+            App::new()
+                .app_data(OxenAppData::new(sync_dir.clone(), test::init_queue()))
+                .wrap(
+                    RateLimit::new(limiter(2))
+                        .with_secret_key(access_keys::get_or_create_secret_key(&sync_dir)?),
+                )
+                .route("/ping", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        let request = |token: &str| {
+            actix_web::test::TestRequest::get()
+                .uri("/ping")
+                .insert_header((header::AUTHORIZATION, format!("Bearer {token}")))
+                .to_request()
+        };
+
+        for _ in 0..2 {
+            let resp = actix_web::test::call_service(&app, request(&token_a)).await;
+            assert_eq!(resp.status(), StatusCode::OK);
+        }
+
+        let resp = actix_web::test::call_service(&app, request(&token_a)).await;
+        assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+        let retry_after: u64 = resp
+            .headers()
+            .get(header::RETRY_AFTER)
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!(retry_after > 0 && retry_after <= 60);
+
+        // Other tokens have their own count
+        let resp = actix_web::test::call_service(&app, request(&token_b)).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        // Made up tokens all count against the ip
+        for (i, status) in [
+            StatusCode::OK,
+            StatusCode::OK,
+            StatusCode::TOO_MANY_REQUESTS,
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let resp = actix_web::test::call_service(&app, request(&format!("fake-{i}"))).await;
+            assert_eq!(resp.status(), status);
+        }
+
+        util::fs::remove_dir_all(sync_dir)?;
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn test_rate_limit_only_trusts_forwarded_for_from_proxies() {
+        let proxy: SocketAddr = "10.0.0.1:4000".parse().unwrap();
+        let app = actix_web::test::init_service(
+            App::new()
+                .wrap(RateLimit::new(limiter(1)).with_trusted_proxies(vec![proxy.ip()]))
+                .route("/ping", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        let request = |peer: &str, forwarded_for: &str| {
+            actix_web::test::TestRequest::get()
+                .uri("/ping")
+                .peer_addr(peer.parse().unwrap())
+                .insert_header(("X-Forwarded-For", forwarded_for))
+                .to_request()
+        };
+
+        // A client that is not a proxy can't pick its ip
+        let resp = actix_web::test::call_service(&app, request("1.1.1.1:80", "2.2.2.2")).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let resp = actix_web::test::call_service(&app, request("1.1.1.1:80", "3.3.3.3")).await;
+        assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+
+        // Behind the proxy, the address it appended is used, not the one the client sent
+        let resp =
+            actix_web::test::call_service(&app, request("10.0.0.1:4000", "9.9.9.9, 4.4.4.4")).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let resp =
+            actix_web::test::call_service(&app, request("10.0.0.1:4000", "8.8.8.8, 4.4.4.4")).await;
+        assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+        let resp =
+            actix_web::test::call_service(&app, request("10.0.0.1:4000", "4.4.4.4, 5.5.5.5")).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,196 @@
+//! Gives every request an id, taken from the `x-request-id` header when the client sends a
+//! valid one, so that the log lines from a controller and the background tasks it enqueues
+//! can be correlated. The id is echoed back in the response headers.
+//!
+
+use std::cell::RefCell;
+use std::future::{ready, Ready};
+
+use actix_web::dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::header::{HeaderName, HeaderValue};
+use actix_web::Error;
+use futures_util::future::LocalBoxFuture;
+
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+// Longest id we accept from a client before generating our own
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+tokio::task_local! {
+    // Set for the duration of a request
+    static REQUEST_ID: String;
+}
+
+thread_local! {
+    // Set while a queued task runs on the polling worker
+    static TASK_REQUEST_ID: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// The id of the request, or queued task, currently being processed
+pub fn current() -> Option<String> {
+    REQUEST_ID
+        .try_with(|id| id.clone())
+        .ok()
+        .or_else(|| TASK_REQUEST_ID.with(|id| id.borrow().clone()))
+}
+
+/// Run a queued task with the id of the request that enqueued it
+pub fn with_request_id<T>(request_id: Option<String>, f: impl FnOnce() -> T) -> T {
+    let _guard = TaskRequestIdGuard::set(request_id);
+    f()
+}
+
+// Restores the previous id even if the task panics
+struct TaskRequestIdGuard {
+    previous: Option<String>,
+}
+
+impl TaskRequestIdGuard {
+    fn set(request_id: Option<String>) -> Self {
+        let previous = TASK_REQUEST_ID.with(|id| id.replace(request_id));
+        TaskRequestIdGuard { previous }
+    }
+}
+
+impl Drop for TaskRequestIdGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        TASK_REQUEST_ID.with(|id| *id.borrow_mut() = previous);
+    }
+}
+
+fn request_id_from(req: &ServiceRequest) -> String {
+    req.headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|id| {
+            !id.is_empty()
+                && id.len() <= MAX_REQUEST_ID_LEN
+                && id
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        })
+        .map(String::from)
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string())
+}
+
+/// Middleware that sets the request id for everything that runs while handling the request.
+/// Wrap it outermost so that the other middleware logs with the id too.
+#[derive(Clone, Default)]
+pub struct RequestId;
+
+impl<S, B> Transform<S, ServiceRequest> for RequestId
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = RequestIdMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequestIdMiddleware { service }))
+    }
+}
+
+pub struct RequestIdMiddleware<S> {
+    service: S,
+}
+
+impl<S, B> Service<ServiceRequest> for RequestIdMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let request_id = request_id_from(&req);
+        let fut = REQUEST_ID.sync_scope(request_id.clone(), || self.service.call(req));
+        Box::pin(REQUEST_ID.scope(request_id.clone(), async move {
+            let mut res = fut.await?;
+            if let Ok(value) = HeaderValue::from_str(&request_id) {
+                res.headers_mut()
+                    .insert(HeaderName::from_static(REQUEST_ID_HEADER), value);
+            }
+            Ok(res)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{web, App};
+
+    use liboxen::command;
+    use liboxen::error::OxenError;
+    use liboxen::util;
+
+    use crate::app_data::OxenAppData;
+    use crate::controllers;
+    use crate::middleware::request_id::{RequestId, REQUEST_ID_HEADER};
+    use crate::queues::{InMemoryTaskQueue, TaskQueue};
+    use crate::tasks::Runnable;
+    use crate::test;
+
+    #[actix_web::test]
+    async fn test_request_id_in_controller_and_task_logs() -> Result<(), OxenError> {
+        test::init_test_env();
+        let sync_dir = test::get_sync_dir()?;
+        let namespace = "Testing-Namespace";
+        let name = "Testing-Request-Id";
+        let repo = test::create_local_repo(&sync_dir, namespace, name)?;
+        let path = liboxen::test::add_txt_file_to_dir(&repo.path, "hello")?;
+        command::add(&repo, path)?;
+        let commit = command::commit(&repo, "first commit")?;
+
+        let mut queue = TaskQueue::InMemory(InMemoryTaskQueue::new());
+        let app = actix_web::test::init_service(
+            App::new()
+                .app_data(OxenAppData::new(sync_dir.clone(), queue.clone()))
+                .route(
+                    "/api/repos/{namespace}/{repo_name}/commits/complete",
+                    web::post().to(controllers::commits::complete_bulk),
+                )
+                .wrap(RequestId),
+        )
+        .await;
+
+        let req = actix_web::test::TestRequest::post()
+            .uri(&format!("/api/repos/{namespace}/{name}/commits/complete"))
+            .set_payload(serde_json::to_string(&vec![commit.clone()])?)
+            .to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+        let request_id = resp
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_string();
+
+        // Process the task the push enqueued, as the polling worker would
+        let task = queue.pop().unwrap();
+        task.run();
+
+        let logs = test::logs_for_request(&request_id);
+        assert!(logs.iter().any(|line| line.contains("Checking commit")));
+        assert!(logs
+            .iter()
+            .any(|line| line.contains("Running cachers for commit")));
+
+        // cleanup
+        util::fs::remove_dir_all(sync_dir)?;
+
+        Ok(())
+    }
+}
@@ -18,9 +18,33 @@ pub use aggregate_query::AggregateQuery;
 pub mod page_num_query;
 pub use page_num_query::PageNumQuery;
 
+pub mod commit_range_query;
+pub use commit_range_query::CommitRangeQuery;
+
+pub mod cursor_query;
+pub use cursor_query::CursorQuery;
+
 pub mod df_opts_query;
 pub use df_opts_query::DFOptsQuery;
 
+pub mod df_search_query;
+pub use df_search_query::DFSearchQuery;
+
+pub mod force_query;
+pub use force_query::ForceQuery;
+
+pub mod head_poll_query;
+pub use head_poll_query::HeadPollQuery;
+
+pub mod health_query;
+pub use health_query::HealthQuery;
+
+pub mod line_range_query;
+pub use line_range_query::LineRangeQuery;
+
+pub mod signed_url_query;
+pub use signed_url_query::SignedUrlQuery;
+
 pub fn app_data(req: &HttpRequest) -> Result<&OxenAppData, OxenHttpError> {
     log::debug!(
         "Get user agent from app data (app_data) {:?}",
@@ -0,0 +1,11 @@
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug)]
+pub struct CommitRangeQuery {
+    /// Branch or commit id to start after, it is not included in the results
+    pub base: String,
+    /// Branch or commit id to end at
+    pub head: String,
+    pub page: Option<usize>,
+    pub page_size: Option<usize>,
+}
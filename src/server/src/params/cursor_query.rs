@@ -0,0 +1,8 @@
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug)]
+pub struct CursorQuery {
+    /// Opaque token returned as `next_cursor` from the previous page, empty for the first page
+    pub cursor: Option<String>,
+    pub page_size: Option<usize>,
+}
@@ -0,0 +1,10 @@
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug)]
+pub struct DFSearchQuery {
+    pub column: String,
+    pub query: String,
+    pub regex: Option<bool>,
+    pub page: Option<usize>,
+    pub page_size: Option<usize>,
+}
@@ -0,0 +1,7 @@
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug)]
+pub struct ForceQuery {
+    /// Go ahead even if the request conflicts with someone else's changes
+    pub force: Option<bool>,
+}
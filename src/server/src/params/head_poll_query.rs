@@ -0,0 +1,9 @@
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug)]
+pub struct HeadPollQuery {
+    /// The head commit id the client already has, the request is held until the branch moves off it
+    pub current: Option<String>,
+    /// Seconds to hold the request before giving up with a 304
+    pub timeout: Option<u64>,
+}
@@ -0,0 +1,7 @@
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug)]
+pub struct HealthQuery {
+    /// Run the more expensive checks against the sync dir, repo db, and redis
+    pub deep: Option<bool>,
+}
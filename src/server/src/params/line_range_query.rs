@@ -0,0 +1,7 @@
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug)]
+pub struct LineRangeQuery {
+    pub start: Option<usize>,
+    pub len: Option<usize>,
+}
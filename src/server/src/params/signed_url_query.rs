@@ -0,0 +1,6 @@
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug)]
+pub struct SignedUrlQuery {
+    pub expires_in: Option<u64>,
+}
@@ -1,9 +1,17 @@
+pub mod poller;
+
 use std::sync::Mutex;
 use std::{collections::VecDeque, sync::Arc};
 
 use crate::tasks::post_push_complete::PostPushComplete;
 use crate::tasks::Task;
-use liboxen::constants::COMMIT_QUEUE_NAME;
+use liboxen::constants::{COMMIT_QUEUE_NAME, COMMIT_QUEUE_SEQ_NAME, LEGACY_COMMIT_QUEUE_NAME};
+use liboxen::error::OxenError;
+use liboxen::model::{Commit, LocalRepository};
+use serde::Deserialize;
+
+// Sequence numbers stay below this so they never spill into the next priority band of the score
+const PRIORITY_SCORE_BAND: f64 = (1u64 << 40) as f64;
 
 #[derive(Clone)]
 pub enum TaskQueue {
@@ -19,6 +27,7 @@ impl TaskQueue {
         }
     }
 
+    /// Pops the highest priority task, tasks of equal priority come out in the order they were pushed
     pub fn pop(&mut self) -> Option<Task> {
         match self {
             TaskQueue::InMemory(queue) => queue.pop(),
@@ -27,6 +36,13 @@ impl TaskQueue {
     }
 }
 
+// Task layout older servers pushed onto the legacy list, before tasks had a priority
+#[derive(Deserialize)]
+struct LegacyPostPushComplete {
+    commit: Commit,
+    repo: LocalRepository,
+}
+
 #[derive(Clone)]
 pub struct RedisTaskQueue {
     pub pool: r2d2::Pool<redis::Client>,
@@ -37,31 +53,74 @@ impl RedisTaskQueue {
         RedisTaskQueue { pool }
     }
 
+    /// Moves tasks left on the legacy list queue into the priority queue, oldest first,
+    /// returning how many were moved
+    pub fn migrate_legacy_queue(&mut self) -> Result<usize, OxenError> {
+        let mut conn = self.pool.get()?;
+
+        let mut migrated = 0;
+        // Older servers LPUSH'd, so the oldest task is at the right end of the list
+        while let Some(data) = redis::cmd("RPOP")
+            .arg(LEGACY_COMMIT_QUEUE_NAME)
+            .query::<Option<Vec<u8>>>(&mut conn)?
+        {
+            match bincode::deserialize::<LegacyPostPushComplete>(&data) {
+                Ok(legacy) => {
+                    self.push(Task::PostPushComplete(PostPushComplete {
+                        commit: legacy.commit,
+                        repo: legacy.repo,
+                        priority: Default::default(),
+                        request_id: None,
+                    }));
+                    migrated += 1;
+                }
+                Err(err) => {
+                    log::error!("Dropping legacy queue task that could not be read: {err:?}");
+                }
+            }
+        }
+        Ok(migrated)
+    }
+
     fn push(&mut self, task: Task) {
         let mut conn = self.pool.get().unwrap();
 
+        let priority = task.priority();
         let data: Vec<u8> = match task {
             Task::PostPushComplete(task) => bincode::serialize(&task).unwrap(),
         };
 
-        let _: isize = redis::cmd("LPUSH")
+        // Lowest score pops first, so higher priorities get pushed further down
+        let seq: u64 = redis::cmd("INCR")
+            .arg(COMMIT_QUEUE_SEQ_NAME)
+            .query(&mut conn)
+            .unwrap();
+        let score = -(priority.rank() as f64) * PRIORITY_SCORE_BAND + seq as f64;
+
+        // Prefix with the sequence number so identical tasks are still distinct set members
+        let mut member = seq.to_be_bytes().to_vec();
+        member.extend(data);
+
+        let _: isize = redis::cmd("ZADD")
             .arg(COMMIT_QUEUE_NAME)
-            .arg(data)
+            .arg(score)
+            .arg(member)
             .query(&mut conn)
             .unwrap();
     }
 
     fn pop(&mut self) -> Option<Task> {
         let mut conn = self.pool.get().unwrap();
-        let outcome: Option<Vec<u8>> = redis::cmd("LPOP")
+        let outcome: Vec<(Vec<u8>, f64)> = redis::cmd("ZPOPMIN")
             .arg(COMMIT_QUEUE_NAME)
             .query(&mut conn)
             .unwrap();
 
-        match outcome {
-            Some(data) => {
+        match outcome.into_iter().next() {
+            Some((member, _score)) => {
                 // TODO: Support multiple task types
-                let task: PostPushComplete = bincode::deserialize(&data).unwrap();
+                let data = &member[std::mem::size_of::<u64>()..];
+                let task: PostPushComplete = bincode::deserialize(data).unwrap();
                 Some(Task::PostPushComplete(task))
             }
             None => None,
@@ -83,7 +142,12 @@ impl InMemoryTaskQueue {
 
     fn push(&mut self, task: Task) {
         let mut queue = self.queue.lock().unwrap();
-        queue.push_back(task);
+        // Keep the queue sorted by priority, behind any tasks of the same priority
+        let index = queue
+            .iter()
+            .position(|queued| queued.priority() < task.priority())
+            .unwrap_or(queue.len());
+        queue.insert(index, task);
     }
 
     fn pop(&mut self) -> Option<Task> {
@@ -96,3 +160,54 @@ impl Default for InMemoryTaskQueue {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use liboxen::error::OxenError;
+    use liboxen::model::{Commit, LocalRepository, NewCommit};
+    use time::OffsetDateTime;
+
+    use crate::queues::{InMemoryTaskQueue, TaskQueue};
+    use crate::tasks::post_push_complete::PostPushComplete;
+    use crate::tasks::{Task, TaskPriority};
+
+    fn post_push_task(commit_id: &str, priority: TaskPriority) -> Result<Task, OxenError> {
+        let new_commit = NewCommit {
+            parent_ids: vec![],
+            message: String::from("test"),
+            author: String::from("Ox"),
+            email: String::from("ox@oxen.ai"),
+            timestamp: OffsetDateTime::now_utc(),
+        };
+        Ok(Task::PostPushComplete(PostPushComplete {
+            commit: Commit::from_new_and_id(&new_commit, commit_id.to_string()),
+            repo: LocalRepository::new(std::path::Path::new("data/test/queue"))?,
+            priority,
+            request_id: None,
+        }))
+    }
+
+    fn popped_commit_id(queue: &mut TaskQueue) -> Option<String> {
+        queue.pop().map(|task| match task {
+            Task::PostPushComplete(task) => task.commit.id,
+        })
+    }
+
+    #[test]
+    fn test_queue_pops_highest_priority_first() -> Result<(), OxenError> {
+        let mut queue = TaskQueue::InMemory(InMemoryTaskQueue::new());
+        queue.push(post_push_task("low", TaskPriority::Low)?);
+        queue.push(post_push_task("normal_1", TaskPriority::Normal)?);
+        queue.push(post_push_task("high", TaskPriority::High)?);
+        queue.push(post_push_task("normal_2", TaskPriority::Normal)?);
+
+        assert_eq!(popped_commit_id(&mut queue), Some(String::from("high")));
+        // Equal priorities stay FIFO
+        assert_eq!(popped_commit_id(&mut queue), Some(String::from("normal_1")));
+        assert_eq!(popped_commit_id(&mut queue), Some(String::from("normal_2")));
+        assert_eq!(popped_commit_id(&mut queue), Some(String::from("low")));
+        assert_eq!(popped_commit_id(&mut queue), None);
+
+        Ok(())
+    }
+}
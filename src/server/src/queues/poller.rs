@@ -0,0 +1,176 @@
+//! Background worker that runs the tasks pushed onto the [`TaskQueue`]
+
+use std::time::Duration;
+
+use liboxen::core::cache::cacher_status::CacherStatus;
+use liboxen::core::cache::commit_cacher;
+use tokio::time::sleep;
+use tokio_util::sync::CancellationToken;
+
+use crate::queues::TaskQueue;
+use crate::tasks::{Runnable, Task};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(1000);
+
+/// Run queued tasks until `shutdown` is cancelled. The task in flight when shutdown is requested
+/// runs to completion, after that no new tasks are picked up.
+pub async fn poll_queue(queue: TaskQueue, shutdown: CancellationToken) {
+    poll_queue_with(queue, shutdown, |task: &Task| task.run()).await
+}
+
+/// Same as [`poll_queue`] with the function that runs each task swapped out
+pub async fn poll_queue_with<F>(mut queue: TaskQueue, shutdown: CancellationToken, run: F)
+where
+    F: Fn(&Task) + Clone + Send + 'static,
+{
+    log::debug!("Starting queue poller");
+    while !shutdown.is_cancelled() {
+        let Some(task) = queue.pop() else {
+            // log::debug!("No queue items found, sleeping");
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = sleep(POLL_INTERVAL) => continue,
+            }
+        };
+
+        log::debug!("Got queue item: {:?}", task);
+        // Run off the async thread so shutdown signals are still handled while the task runs
+        let run = run.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| run(&task)));
+            (task, result)
+        })
+        .await;
+
+        match result {
+            Ok((_task, Ok(_))) => {}
+            Ok((task, Err(e))) => {
+                log::error!("Error or panic processing commit {:?}", e);
+                set_task_failed(&task, "Panic in commit cache");
+            }
+            Err(e) => {
+                log::error!("Could not join queue task {:?}", e);
+            }
+        }
+    }
+
+    drain_queue(&mut queue);
+    log::debug!("Queue poller stopped");
+}
+
+/// Tasks left in redis are picked up by the next poller, in memory ones are lost on exit so they
+/// are marked as failed instead of staying pending forever
+fn drain_queue(queue: &mut TaskQueue) {
+    match queue {
+        TaskQueue::InMemory(_) => {
+            while let Some(task) = queue.pop() {
+                log::debug!("Abandoning queue item on shutdown: {:?}", task);
+                set_task_failed(&task, "Server shut down before the commit cache ran");
+            }
+        }
+        TaskQueue::Redis(_) => {
+            log::debug!("Leaving remaining queue items in redis for the next poller");
+        }
+    }
+}
+
+fn set_task_failed(task: &Task, msg: &str) {
+    match task {
+        Task::PostPushComplete(post_push_complete) => {
+            let repo = &post_push_complete.repo;
+            let commit = &post_push_complete.commit;
+
+            match commit_cacher::set_all_cachers_status(repo, commit, CacherStatus::failed(msg)) {
+                Ok(_) => {
+                    log::debug!("Set all cachers to failed status");
+                }
+                Err(e) => {
+                    log::error!("Error setting all cachers to failed status: {:?}", e);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use liboxen::command;
+    use liboxen::core::cache::cacher_status::CacherStatusType;
+    use liboxen::core::cache::commit_cacher;
+    use liboxen::error::OxenError;
+    use liboxen::util;
+    use tokio_util::sync::CancellationToken;
+
+    use crate::queues::poller;
+    use crate::queues::{InMemoryTaskQueue, TaskQueue};
+    use crate::tasks::post_push_complete::PostPushComplete;
+    use crate::tasks::{Task, TaskPriority};
+    use crate::test;
+
+    #[actix_web::test]
+    async fn test_poller_finishes_in_flight_task_on_shutdown() -> Result<(), OxenError> {
+        let sync_dir = test::get_sync_dir()?;
+        let repo = test::create_local_repo(&sync_dir, "testing", "poller")?;
+
+        let mut commits = vec![];
+        for name in ["first", "second"] {
+            let path = repo.path.join(format!("{name}.txt"));
+            util::fs::write_to_path(&path, name)?;
+            command::add(&repo, &path)?;
+            commits.push(command::commit(&repo, &format!("Adding {name}"))?);
+        }
+
+        let mut queue = TaskQueue::InMemory(InMemoryTaskQueue::new());
+        for commit in commits.iter() {
+            queue.push(Task::PostPushComplete(PostPushComplete {
+                commit: commit.clone(),
+                repo: repo.clone(),
+                priority: TaskPriority::Normal,
+                request_id: None,
+            }));
+        }
+
+        // The first task gets the shutdown signal while it is running
+        let shutdown = CancellationToken::new();
+        let signal = shutdown.clone();
+        let completed: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(vec![]));
+        let ran = completed.clone();
+        let poller = tokio::spawn(poller::poll_queue_with(
+            queue.clone(),
+            shutdown,
+            move |task: &Task| {
+                signal.cancel();
+                std::thread::sleep(Duration::from_millis(100));
+                match task {
+                    Task::PostPushComplete(task) => {
+                        commit_cacher::run_all(&task.repo, &task.commit, false).unwrap();
+                        ran.lock().unwrap().push(task.commit.id.clone());
+                    }
+                }
+            },
+        ));
+
+        tokio::time::timeout(Duration::from_secs(30), poller)
+            .await
+            .expect("Poller did not exit after shutdown")
+            .expect("Poller panicked");
+
+        // The in-flight task finished and nothing else was picked up
+        assert_eq!(*completed.lock().unwrap(), vec![commits[0].id.clone()]);
+        assert!(commit_cacher::is_cached(&repo, &commits[0])?);
+
+        // The task still queued was marked as failed and removed
+        assert_eq!(
+            commit_cacher::get_status(&repo, &commits[1])?,
+            Some(CacherStatusType::Failed)
+        );
+        assert!(queue.pop().is_none());
+
+        util::fs::remove_dir_all(sync_dir)?;
+
+        Ok(())
+    }
+}
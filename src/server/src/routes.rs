@@ -13,6 +13,7 @@ pub fn config(cfg: &mut web::ServiceConfig) {
                 // we give the resource a name here so it can be used with HttpRequest.url_for
                 .name("repo_root")
                 .route(web::get().to(controllers::repositories::show))
+                .route(web::head().to(controllers::repositories::exists))
                 .route(web::delete().to(controllers::repositories::delete)),
         )
         .route(
@@ -60,6 +61,10 @@ pub fn config(cfg: &mut web::ServiceConfig) {
             "/{namespace}/{repo_name}/commits/all",
             web::get().to(controllers::commits::list_all),
         )
+        .route(
+            "/{namespace}/{repo_name}/commits/between",
+            web::get().to(controllers::commits::list_between),
+        )
         .route(
             "/{namespace}/{repo_name}/commits/{commit_id}/latest_synced",
             web::get().to(controllers::commits::latest_synced),
@@ -88,6 +93,10 @@ pub fn config(cfg: &mut web::ServiceConfig) {
             "/{namespace}/{repo_name}/commits/{commit_or_branch:.*}/history",
             web::get().to(controllers::commits::commit_history),
         )
+        .route(
+            "/{namespace}/{repo_name}/commits/{commit_or_branch:.*}/manifest",
+            web::get().to(controllers::commits::entries_manifest),
+        )
         .route(
             "/{namespace}/{repo_name}/commits/{commit_or_branch:.*}/parents",
             web::get().to(controllers::commits::parents),
@@ -96,10 +105,18 @@ pub fn config(cfg: &mut web::ServiceConfig) {
             "/{namespace}/{repo_name}/commits/{commit_or_branch:.*}/is_synced",
             web::get().to(controllers::commits::is_synced),
         )
+        .route(
+            "/{namespace}/{repo_name}/commits/{commit_or_branch:.*}/cache_status",
+            web::get().to(controllers::commits::cache_status),
+        )
         .route(
             "/{namespace}/{repo_name}/commits/{commit_or_branch:.*}/commit_db",
             web::get().to(controllers::commits::download_commit_entries_db),
         )
+        .route(
+            "/{namespace}/{repo_name}/commits/{commit_or_branch:.*}/tarball",
+            web::get().to(controllers::commits::download_commit_tarball),
+        )
         // ----- Branches ----- //
         .route(
             "/{namespace}/{repo_name}/branches",
@@ -125,6 +142,10 @@ pub fn config(cfg: &mut web::ServiceConfig) {
             "/{namespace}/{repo_name}/branches/{branch_name:.*}/lock",
             web::get().to(controllers::branches::is_locked),
         )
+        .route(
+            "/{namespace}/{repo_name}/branches/{branch_name:.*}/head",
+            web::get().to(controllers::branches::head),
+        )
         .route(
             "/{namespace}/{repo_name}/branches/{branch_name:.*}/unlock",
             web::post().to(controllers::branches::unlock),
@@ -201,10 +222,18 @@ pub fn config(cfg: &mut web::ServiceConfig) {
             web::post().to(controllers::merger::merge),
         )
         // ----- Stage Remote Data ----- //
+        .route(
+            "/{namespace}/{repo_name}/staging/status_all/{branch:.*}",
+            web::get().to(controllers::stager::status_all),
+        )
         .route(
             "/{namespace}/{repo_name}/staging/{identifier}/status/{resource:.*}",
             web::get().to(controllers::stager::status_dir),
         )
+        .route(
+            "/{namespace}/{repo_name}/staging/{identifier}/files/{branch:.*}",
+            web::get().to(controllers::stager::list_staged_files),
+        )
         .route(
             "/{namespace}/{repo_name}/staging/{identifier}/df/list_editable/{branch:.*}",
             web::get().to(controllers::stager::list_editable_dfs),
@@ -218,6 +247,18 @@ pub fn config(cfg: &mut web::ServiceConfig) {
             web::get().to(controllers::stager::diff_df),
         )
         // STAGING
+        .route(
+            "/{namespace}/{repo_name}/staging/{identifier}/chunked_uploads",
+            web::post().to(controllers::stager::create_chunked_upload),
+        )
+        .route(
+            "/{namespace}/{repo_name}/staging/{identifier}/chunked_uploads/{upload_id}/parts/{part_num}",
+            web::put().to(controllers::stager::upload_chunked_part),
+        )
+        .route(
+            "/{namespace}/{repo_name}/staging/{identifier}/chunked_uploads/{upload_id}/complete",
+            web::post().to(controllers::stager::complete_chunked_upload),
+        )
         // TODO: add GET for downloading the file from the staging area
         // TODO: implement delete dir from staging to recursively unstage
         .route(
@@ -298,6 +339,15 @@ pub fn config(cfg: &mut web::ServiceConfig) {
             "/{namespace}/{repo_name}/file/{resource:.*}",
             web::get().to(controllers::file::get),
         )
+        // ----- Signed Urls (time limited file access without a token) ----- //
+        .route(
+            "/{namespace}/{repo_name}/signed_url/{resource:.*}",
+            web::post().to(controllers::file::create_signed_url),
+        )
+        .route(
+            "/{namespace}/{repo_name}/signed_file/{token}",
+            web::get().to(controllers::file::get_signed),
+        )
         // ----- Chunk (returns a chunk of a file) ----- //
         .route(
             "/{namespace}/{repo_name}/chunk/{resource:.*}",
@@ -325,6 +375,10 @@ pub fn config(cfg: &mut web::ServiceConfig) {
             "/{namespace}/{repo_name}/data_frame/index/{resource:.*}",
             web::post().to(controllers::data_frames::index),
         )
+        .route(
+            "/{namespace}/{repo_name}/data_frame/search/{resource:.*}",
+            web::get().to(controllers::data_frames::search),
+        )
         .route(
             "/{namespace}/{repo_name}/data_frame/{resource:.*}",
             web::get().to(controllers::data_frames::get),
@@ -334,11 +388,19 @@ pub fn config(cfg: &mut web::ServiceConfig) {
             "/{namespace}/{repo_name}/lines/{resource:.*}",
             web::get().to(controllers::entries::list_lines_in_file),
         )
+        .route(
+            "/{namespace}/{repo_name}/line_range/{resource:.*}",
+            web::get().to(controllers::entries::read_line_range),
+        )
         // ----- Versions - Download directly from the .oxen/versions directory ----- //
         .route(
             "/{namespace}/{repo_name}/versions", // Download tar.gz set of version files
             web::get().to(controllers::entries::download_data_from_version_paths),
         )
+        .route(
+            "/{namespace}/{repo_name}/versions/{hash}", // Upload a single version file
+            web::post().to(controllers::entries::upload_version),
+        )
         // ----- Schemas ----- //
         .route(
             "/{namespace}/{repo_name}/schemas/hash/{hash}",
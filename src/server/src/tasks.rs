@@ -1,18 +1,55 @@
 pub mod post_push_complete;
 
+use serde::{Deserialize, Serialize};
+
+use crate::middleware::request_id;
+
 pub trait Runnable {
     fn run(&self);
 }
 
+/// Order in which queued tasks are picked up, higher priorities pop first
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TaskPriority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+impl TaskPriority {
+    pub fn rank(&self) -> u8 {
+        match self {
+            TaskPriority::Low => 0,
+            TaskPriority::Normal => 1,
+            TaskPriority::High => 2,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum Task {
     PostPushComplete(post_push_complete::PostPushComplete),
 }
 
+impl Task {
+    pub fn priority(&self) -> TaskPriority {
+        match self {
+            Task::PostPushComplete(task) => task.priority,
+        }
+    }
+
+    pub fn request_id(&self) -> Option<String> {
+        match self {
+            Task::PostPushComplete(task) => task.request_id.clone(),
+        }
+    }
+}
+
 impl Runnable for Task {
     fn run(&self) {
-        match self {
+        request_id::with_request_id(self.request_id(), || match self {
             Task::PostPushComplete(task) => task.run(),
-        }
+        })
     }
 }
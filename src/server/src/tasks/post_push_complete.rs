@@ -4,12 +4,17 @@ use liboxen::{
 };
 use serde::{Deserialize, Serialize};
 
-use super::Runnable;
+use super::{Runnable, TaskPriority};
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct PostPushComplete {
     pub commit: Commit,
     pub repo: LocalRepository,
+    #[serde(default)]
+    pub priority: TaskPriority,
+    // Id of the request that enqueued the task, to correlate the logs
+    #[serde(default)]
+    pub request_id: Option<String>,
 }
 
 impl Runnable for PostPushComplete {
@@ -1,20 +1,56 @@
 use crate::app_data::OxenAppData;
+use crate::auth::access_keys::AccessKeyManager;
 use crate::helpers;
+use crate::middleware::request_id;
 use crate::queues::{InMemoryTaskQueue, RedisTaskQueue, TaskQueue};
 
 use liboxen::command;
+use liboxen::config::ColumnAccess;
 use liboxen::error::OxenError;
-use liboxen::model::LocalRepository;
+use liboxen::model::{LocalRepository, User};
 use liboxen::util;
 
 use env_logger::Env;
 use serde::Serialize;
 use std::borrow::Cow;
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+// Log lines tagged with a request id, so tests can check what was logged for a request
+static REQUEST_LOGS: Mutex<Vec<(String, String)>> = Mutex::new(Vec::new());
+
+struct TestLogger {
+    inner: env_logger::Logger,
+}
+
+impl log::Log for TestLogger {
+    fn enabled(&self, _metadata: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        if let Some(request_id) = request_id::current() {
+            REQUEST_LOGS
+                .lock()
+                .unwrap()
+                .push((request_id, record.args().to_string()));
+        }
+        if self.inner.matches(record) {
+            self.inner.log(record);
+        }
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
 
 pub fn init_test_env() {
-    let env = Env::default();
-    if env_logger::try_init_from_env(env).is_ok() {
+    let inner = env_logger::Builder::from_env(Env::default()).build();
+    let filter = inner.filter();
+    if log::set_boxed_logger(Box::new(TestLogger { inner })).is_ok() {
+        // Capture debug lines for requests no matter what RUST_LOG prints
+        log::set_max_level(filter.max(log::LevelFilter::Debug));
         log::debug!("Logger initialized");
     }
 
@@ -36,6 +72,17 @@ pub fn init_queue() -> TaskQueue {
     }
 }
 
+/// Everything logged while handling the request with this id, or the tasks it queued
+pub fn logs_for_request(request_id: &str) -> Vec<String> {
+    REQUEST_LOGS
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|(id, _)| id == request_id)
+        .map(|(_, line)| line.to_owned())
+        .collect()
+}
+
 pub fn get_sync_dir() -> Result<PathBuf, OxenError> {
     let sync_dir = PathBuf::from(format!("data/test/runs/{}", uuid::Uuid::new_v4()));
     std::fs::create_dir_all(&sync_dir)?;
@@ -53,6 +100,42 @@ pub fn create_local_repo(
     Ok(repo)
 }
 
+/// Repo with a committed users.csv whose email column the "analyst" scope can't read,
+/// returned with an analyst token and an unrestricted token
+pub fn create_column_access_repo(
+    sync_dir: &Path,
+    namespace: &str,
+    name: &str,
+) -> Result<(LocalRepository, String, String), OxenError> {
+    let mut repo = create_local_repo(sync_dir, namespace, name)?;
+
+    let csv_path = repo.path.join("users.csv");
+    util::fs::write_to_path(
+        &csv_path,
+        "name,email,age\nada,ada@oxen.ai,36\nbob,bob@oxen.ai,40\n",
+    )?;
+    command::add(&repo, &csv_path)?;
+    command::commit(&repo, "adding users")?;
+
+    repo.set_column_access(
+        "analyst",
+        ColumnAccess {
+            allow: None,
+            deny: vec![String::from("email")],
+        },
+    );
+    repo.save_default()?;
+
+    let keygen = AccessKeyManager::new(sync_dir)?;
+    let user = User {
+        name: String::from("Ox"),
+        email: String::from("ox@oxen.ai"),
+    };
+    let (_, analyst_token) = keygen.create_with_scope(&user, Some("analyst"))?;
+    let (_, admin_token) = keygen.create(&user)?;
+    Ok((repo, analyst_token, admin_token))
+}
+
 pub fn run_empty_sync_dir_test<T>(test: T) -> Result<(), OxenError>
 where
     T: FnOnce(&Path) -> Result<(), OxenError> + std::panic::UnwindSafe,